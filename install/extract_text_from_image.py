@@ -2,11 +2,29 @@
 """
 Extract text from an image using EasyOCR.
 Similar to install/extract_text_from_image.swift for macOS, but uses EasyOCR instead of Vision framework.
+
+With --lines, prints one JSON object per line of text instead, with its
+bounding box in image pixels and recognition confidence:
+{"text": ..., "x": ..., "y": ..., "width": ..., "height": ..., "confidence": ...}
+
+With --lang <code> (ISO 639-1, or zh-Hans / zh-Hant), reads that language
+(plus English) instead of the default languages.
+
+An image path of "-" reads the image from standard input, so it never has
+to be written to disk.
 """
 
+import json
 import sys
 import os
 
+# Language codes that differ between ISO 639-1 and EasyOCR
+EASYOCR_LANGUAGES = {
+    "zh-Hans": "ch_sim",
+    "zh-Hant": "ch_tra",
+    "zh": "ch_sim",
+}
+
 def write_error(message: str) -> None:
     """Write error message to stderr."""
     sys.stderr.write(f"{message}\n")
@@ -15,14 +33,30 @@ def write_error(message: str) -> None:
 def main() -> int:
     """Main function to extract text from image."""
     # Check command-line arguments
-    if len(sys.argv) != 2:
-        write_error("Usage: extract_text_from_image.py <image_path>")
+    args = sys.argv[1:]
+    with_boxes = "--lines" in args
+    args = [arg for arg in args if arg != "--lines"]
+    language = None
+    if "--lang" in args:
+        index = args.index("--lang")
+        if index + 1 >= len(args):
+            write_error("Error: --lang requires a language code")
+            return 1
+        language = args[index + 1]
+        del args[index:index + 2]
+    if len(args) != 1:
+        write_error("Usage: extract_text_from_image.py [--lines] [--lang <code>] <image_path>")
         return 1
     
-    image_path = sys.argv[1]
+    image_path = args[0]
+    if image_path == "-":
+        # EasyOCR takes the encoded image bytes as they are
+        image = sys.stdin.buffer.read()
+    else:
+        image = image_path
     
     # Verify image file exists
-    if not os.path.exists(image_path):
+    if image_path != "-" and not os.path.exists(image_path):
         write_error(f"Error: Image file does not exist: {image_path}")
         return 1
     
@@ -46,10 +80,15 @@ def main() -> int:
             'en',      # English
             'ch_tra',  # Traditional Chinese
         ]
+        if language:
+            # Every EasyOCR language can be combined with English
+            languages = [EASYOCR_LANGUAGES.get(language, language)]
+            if languages[0] != 'en':
+                languages.append('en')
         reader = easyocr.Reader(languages, gpu=False)
         
         # Read text from image
-        results = reader.readtext(image_path)
+        results = reader.readtext(image)
         
         # Group text by Y-coordinate to preserve line breaks
         # results is a list of tuples: (bbox, text, confidence)
@@ -95,11 +134,16 @@ def main() -> int:
         
         # Extract text from each line group
         extracted_lines = []
+        line_boxes = []
         for line_group in line_groups:
             line_text_parts = [text for (_, text, _) in line_group]
             if line_text_parts:
                 # Join words on the same line with spaces
                 extracted_lines.append(" ".join(line_text_parts))
+                xs = [float(point[0]) for (bbox, _, _) in line_group for point in bbox]
+                ys = [float(point[1]) for (bbox, _, _) in line_group for point in bbox]
+                confidence = sum(float(conf) for (_, _, conf) in line_group) / len(line_group)
+                line_boxes.append((min(xs), min(ys), max(xs) - min(xs), max(ys) - min(ys), confidence))
         
         # Join lines with newlines to preserve line breaks
         extracted_text = "\n".join(extracted_lines)
@@ -108,6 +152,13 @@ def main() -> int:
             # No text found - exit with code 1 but no error message (this is expected)
             return 1
         
+        if with_boxes:
+            for line_text, (x, y, width, height, confidence) in zip(extracted_lines, line_boxes):
+                print(json.dumps({
+                    "text": line_text, "x": x, "y": y, "width": width, "height": height, "confidence": confidence,
+                }))
+            return 0
+        
         # Output extracted text to stdout
         print(extracted_text)
         return 0
@@ -1,30 +1,65 @@
 #!/usr/bin/env swift
 
 import Foundation
+import ImageIO
 import Vision
 
+// With --lines, prints one JSON object per line of text instead, with its
+// bounding box in image pixels and recognition confidence:
+// {"text": ..., "x": ..., "y": ..., "width": ..., "height": ..., "confidence": ...}
+//
+// With --lang <code> (ISO 639-1, or zh-Hans / zh-Hant), that language is
+// recognized first.
+//
+// An image path of "-" reads the image from standard input, so it never has
+// to be written to disk.
+
 func writeError(_ message: String) {
     let data = (message + "\n").data(using: .utf8)!
     FileHandle.standardError.write(data)
 }
 
-guard CommandLine.arguments.count == 2 else {
-    writeError("Usage: extract_text_from_image.swift <image_path>")
+let withBoxes = CommandLine.arguments.contains("--lines")
+var arguments = Array(CommandLine.arguments.dropFirst().filter { $0 != "--lines" })
+var preferredLanguage: String? = nil
+if let langIndex = arguments.firstIndex(of: "--lang") {
+    guard langIndex + 1 < arguments.count else {
+        writeError("Error: --lang requires a language code")
+        exit(1)
+    }
+    preferredLanguage = arguments[langIndex + 1]
+    arguments.removeSubrange(langIndex...(langIndex + 1))
+}
+guard arguments.count == 1, let imagePath = arguments.first else {
+    writeError("Usage: extract_text_from_image.swift [--lines] [--lang <code>] <image_path>")
     exit(1)
 }
 
-let imagePath = CommandLine.arguments[1]
-guard FileManager.default.fileExists(atPath: imagePath) else {
+guard imagePath == "-" || FileManager.default.fileExists(atPath: imagePath) else {
     writeError("Error: Image file does not exist: \(imagePath)")
     exit(1)
 }
 
-guard let imageURL = URL(fileURLWithPath: imagePath) as URL?,
-      let imageData = try? Data(contentsOf: imageURL) else {
+let loadedData: Data?
+if imagePath == "-" {
+    loadedData = FileHandle.standardInput.readDataToEndOfFile()
+} else {
+    loadedData = try? Data(contentsOf: URL(fileURLWithPath: imagePath))
+}
+guard let imageData = loadedData, !imageData.isEmpty else {
     writeError("Error: Failed to load image")
     exit(1)
 }
 
+// Image size in pixels, to turn Vision's normalized coordinates into pixels
+guard let imageSource = CGImageSourceCreateWithData(imageData as CFData, nil),
+      let properties = CGImageSourceCopyPropertiesAtIndex(imageSource, 0, nil) as? [CFString: Any],
+      let imageWidth = properties[kCGImagePropertyPixelWidth] as? Int,
+      let imageHeight = properties[kCGImagePropertyPixelHeight] as? Int else {
+    writeError("Error: Failed to read image size")
+    exit(1)
+}
+
 let requestHandler = VNImageRequestHandler(data: imageData, options: [:])
 let textRequest = VNRecognizeTextRequest()
 textRequest.recognitionLevel = .fast
@@ -53,6 +88,14 @@ textRequest.recognitionLanguages = [
     "uk-UA",    // Ukrainian
 ]
 
+// Put the requested language first, Vision favors languages earlier in the list
+if let preferredLanguage = preferredLanguage {
+    let tag = textRequest.recognitionLanguages.first(where: {
+        $0 == preferredLanguage || $0.hasPrefix(preferredLanguage + "-")
+    }) ?? preferredLanguage
+    textRequest.recognitionLanguages = [tag] + textRequest.recognitionLanguages.filter { $0 != tag }
+}
+
 // Enable automatic language detection as fallback
 // This helps when the text contains multiple languages or languages not in the list above
 textRequest.automaticallyDetectsLanguage = true
@@ -74,9 +117,10 @@ guard let observations = textRequest.results, !observations.isEmpty else {
 var lineGroups: [[VNRecognizedTextObservation]] = []
 var currentLine: [VNRecognizedTextObservation] = []
 var lastY: CGFloat? = nil
-let yTolerance: CGFloat = 10.0 // Pixels - text within this Y range is considered same line
+// Bounding boxes are normalized (0-1), so the tolerance is scaled by the image height
+let yTolerance: CGFloat = 10.0 / CGFloat(imageHeight) // 10 pixels - text within this Y range is considered same line
 
-// Sort observations by Y-coordinate (top to bottom)
+// Sort observations by Y-coordinate (top to bottom; Vision's origin is the bottom-left corner)
 let sortedObservations = observations.sorted { obs1, obs2 in
     let y1 = obs1.boundingBox.midY
     let y2 = obs2.boundingBox.midY
@@ -84,7 +128,7 @@ let sortedObservations = observations.sorted { obs1, obs2 in
         // Same line, sort by X (left to right)
         return obs1.boundingBox.minX < obs2.boundingBox.minX
     }
-    return y1 < y2
+    return y1 > y2
 }
 
 for observation in sortedObservations {
@@ -112,18 +156,25 @@ if !currentLine.isEmpty {
 
 // Extract text from each line group
 var extractedLines: [String] = []
+var lineBoxes: [CGRect] = []
+var lineConfidences: [Float] = []
 for lineGroup in lineGroups {
     var lineTextParts: [String] = []
+    var confidences: [Float] = []
     for observation in lineGroup {
         let topCandidates = observation.topCandidates(1)
         guard let topCandidate = topCandidates.first else {
             continue
         }
         lineTextParts.append(topCandidate.string)
+        confidences.append(topCandidate.confidence)
     }
     if !lineTextParts.isEmpty {
         // Join words on the same line with spaces
         extractedLines.append(lineTextParts.joined(separator: " "))
+        let normalized = lineGroup.map { $0.boundingBox }.reduce(lineGroup[0].boundingBox) { $0.union($1) }
+        lineBoxes.append(VNImageRectForNormalizedRect(normalized, imageWidth, imageHeight))
+        lineConfidences.append(confidences.reduce(0, +) / Float(confidences.count))
     }
 }
 
@@ -133,4 +184,24 @@ if extractedText.isEmpty {
     exit(1)
 }
 
+if withBoxes {
+    for (index, (lineText, box)) in zip(extractedLines, lineBoxes).enumerated() {
+        let line: [String: Any] = [
+            "text": lineText,
+            "x": box.minX,
+            // Flip to a top-left origin
+            "y": CGFloat(imageHeight) - box.maxY,
+            "width": box.width,
+            "height": box.height,
+            "confidence": lineConfidences[index],
+        ]
+        guard let json = try? JSONSerialization.data(withJSONObject: line),
+              let jsonLine = String(data: json, encoding: .utf8) else {
+            continue
+        }
+        print(jsonLine)
+    }
+    exit(0)
+}
+
 print(extractedText)
@@ -0,0 +1,143 @@
+//! What the app is doing with speech, as one state machine.
+//!
+//! The main bar shows a single [`Activity`] at a time: preparing a reading,
+//! downloading a voice, playing, paused, a failed reading, or nothing.
+//! Handlers report what happened as an [`Event`] and [`Activity::on`] decides
+//! the next state, so combinations like loading while playing cannot arise.
+//!
+//! A voice download asked for during a reading runs in the background
+//! without changing the state; it only shows as [`Activity::Downloading`]
+//! when nothing is being read.
+
+/// What the app is doing with speech.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum Activity {
+    /// Nothing read, prepared or downloaded
+    #[default]
+    Idle,
+    /// Preparing a reading (loading a file, Natural Reading, synthesis) or a
+    /// dictated note, with the status shown meanwhile
+    Loading { status: String },
+    /// Downloading a Piper voice, with the status shown meanwhile
+    Downloading { status: String },
+    /// Speech playing
+    Playing,
+    /// Speech paused, by the user, for typing or until headphones are used
+    Paused,
+    /// The last reading or download failed
+    Failed { error: String },
+}
+
+/// Something that happened to the activity.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    /// A reading started or moved on to its next step (status to show)
+    Load(String),
+    /// A voice download started (status to show)
+    Download(String),
+    /// The voice download finished
+    Downloaded,
+    /// Speech started or resumed
+    Play,
+    /// Speech paused
+    Pause,
+    /// Speech stopped or ended, or preparing was given up
+    Stop,
+    /// The reading or download being prepared failed
+    Fail(String),
+}
+
+impl Activity {
+    /// The state after `event`, or None if the event does not apply in this state.
+    pub fn on(&self, event: Event) -> Option<Activity> {
+        use Activity::*;
+        match (self, event) {
+            // A new reading replaces whatever was going on
+            (_, Event::Load(status)) => Some(Loading { status }),
+            (Idle | Failed { .. } | Downloading { .. }, Event::Download(status)) => Some(Downloading { status }),
+            (Downloading { .. }, Event::Downloaded) => Some(Idle),
+            // Replaying the last reading works from any state
+            (_, Event::Play) => Some(Playing),
+            // Readings held until headphones are used start paused
+            (Loading { .. } | Playing | Paused, Event::Pause) => Some(Paused),
+            (_, Event::Stop) => Some(Idle),
+            (Loading { .. } | Downloading { .. }, Event::Fail(error)) => Some(Failed { error }),
+            _ => None,
+        }
+    }
+
+    /// Whether a reading is being prepared or a voice downloaded, which
+    /// animates the waveform.
+    pub fn is_busy(&self) -> bool {
+        matches!(self, Activity::Loading { .. } | Activity::Downloading { .. })
+    }
+
+    /// Whether nothing is being read, prepared or downloaded.
+    pub fn is_idle(&self) -> bool {
+        matches!(self, Activity::Idle | Activity::Failed { .. })
+    }
+
+    /// Whether speech is playing or paused.
+    pub fn has_speech(&self) -> bool {
+        matches!(self, Activity::Playing | Activity::Paused)
+    }
+
+    /// Whether a reading is being prepared or spoken, so new ones should wait.
+    pub fn is_reading(&self) -> bool {
+        matches!(self, Activity::Loading { .. } | Activity::Playing | Activity::Paused)
+    }
+
+    /// Status line for the main bar, in place of the progress bar.
+    pub fn status(&self) -> Option<&str> {
+        match self {
+            Activity::Loading { status } | Activity::Downloading { status } => Some(status),
+            Activity::Failed { error } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transitions() {
+        let loading = Activity::Idle.on(Event::Load("Synthesizing voice...".to_string())).unwrap();
+        assert_eq!(loading.status(), Some("Synthesizing voice..."));
+        let playing = loading.on(Event::Play).unwrap();
+        assert_eq!(playing, Activity::Playing);
+        assert_eq!(playing.on(Event::Pause), Some(Activity::Paused));
+        assert_eq!(Activity::Paused.on(Event::Play), Some(Activity::Playing));
+        assert_eq!(playing.on(Event::Stop), Some(Activity::Idle));
+
+        // Nothing to pause without a reading, but the last one can be replayed
+        assert_eq!(Activity::Idle.on(Event::Pause), None);
+        assert_eq!(Activity::Idle.on(Event::Play), Some(Activity::Playing));
+        // A download during a reading runs in the background, and its end or failure leaves the reading alone
+        assert_eq!(playing.on(Event::Download("Downloading voice: Amy...".to_string())), None);
+        assert_eq!(playing.on(Event::Downloaded), None);
+        assert_eq!(playing.on(Event::Fail("Download failed".to_string())), None);
+        // A new reading replaces the one playing
+        assert!(matches!(playing.on(Event::Load("Loading file...".to_string())), Some(Activity::Loading { .. })));
+    }
+
+    #[test]
+    fn test_downloads_and_failures() {
+        let downloading = Activity::Idle.on(Event::Download("Downloading voice: Amy...".to_string())).unwrap();
+        assert!(downloading.is_busy());
+        assert!(!downloading.is_reading());
+        assert_eq!(downloading.on(Event::Downloaded), Some(Activity::Idle));
+
+        let failed = Activity::Loading { status: "Synthesizing voice...".to_string() }
+            .on(Event::Fail("Voice gen. failed".to_string()))
+            .unwrap();
+        assert_eq!(failed.status(), Some("Voice gen. failed"));
+        assert!(!failed.has_speech());
+        assert!(failed.is_idle());
+        // A failed reading can be retried or dismissed
+        assert!(failed.on(Event::Load("Synthesizing voice...".to_string())).is_some());
+        assert_eq!(failed.on(Event::Stop), Some(Activity::Idle));
+        assert_eq!(failed.on(Event::Pause), None);
+    }
+}
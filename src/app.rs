@@ -1,18 +1,27 @@
 //! Iced application adapter (thin UI layer)
 
+use iced::futures::{SinkExt, Stream, StreamExt};
 use iced::keyboard;
 use iced::time::{self, Duration};
 use iced::{Element, Point, Size, Subscription, Task};
 use iced::window;
 use tracing::{debug, info};
 
-use crate::model::{App, Message, PlaybackState};
+use crate::model::{App, Message};
 use crate::update;
 use crate::view;
 
 pub fn new() -> (App, Task<Message>) {
+    // Pull settings and history from the sync folder before they are loaded
+    if let Err(e) = crate::sync::sync_now() {
+        tracing::warn!(error = %e, "Sync on startup failed");
+    }
+    crate::startup::mark("sync");
+
     // Create app immediately without waiting for anything
     let mut app = App::new(None);
+    crate::styles::set_color_scheme(crate::styles::ColorScheme::resolve(app.color_preset, &app.custom_colors));
+    crate::startup::mark("config and plugins loaded");
     
     // Check if hotkeys are disabled due to Wayland/Hyprland
     if app.hotkeys_disabled_wayland {
@@ -32,11 +41,13 @@ pub fn new() -> (App, Task<Message>) {
             tracing::warn!(error = %e, "Failed to initialize system tray, continuing without it");
         }
     }
+    crate::startup::mark("system tray");
     
     // Initialize hotkey manager (skip if disabled on Wayland/Hyprland)
     if !app.hotkeys_disabled_wayland {
         match crate::system::HotkeyManager::new() {
             Ok(mut hotkey_manager) => {
+                hotkey_manager.set_variants(app.hotkey_edit_variant, app.hotkey_image_variant);
                 // Register hotkey if enabled
                 if app.hotkey_enabled {
                     if let Err(e) = hotkey_manager.register(app.hotkey_config.clone()) {
@@ -46,6 +57,12 @@ pub fn new() -> (App, Task<Message>) {
                         info!("Hotkey registered successfully");
                     }
                 }
+                // Registered apart from the reading hotkey so it works even with that one off
+                if app.panic_stop_hotkey {
+                    if let Err(e) = hotkey_manager.register_panic_stop() {
+                        tracing::warn!(error = %e, "Failed to register panic stop hotkey, continuing without it");
+                    }
+                }
                 app.hotkey_manager = Some(hotkey_manager);
             }
             Err(e) => {
@@ -55,13 +72,22 @@ pub fn new() -> (App, Task<Message>) {
         }
     }
     // Note: app.hotkey_manager is already None by default, so no need to set it explicitly
+    crate::startup::mark("hotkeys");
+
+    // Serve the podcast feed on the LAN if enabled
+    update::start_feed_server(&mut app);
+    crate::startup::mark("feed server");
+
+    // Open the audio output and load the voice in the background so the first
+    // reading starts quickly
+    update::prewarm_provider(app.selected_backend, app.selected_polly_voice.clone());
     
     info!("App created, opening UI immediately");
     
     // Open the main window (daemon doesn't open one by default)
     // This happens synchronously but is very fast - just window creation
     let (_main_window_id, open_task) = window::open(window::Settings {
-        size: Size::new(410.0, 70.0),
+        size: Size::new(450.0, 70.0),
         resizable: false,
         decorations: false,
         transparent: true,
@@ -81,59 +107,67 @@ pub fn new() -> (App, Task<Message>) {
     
     // Fetch selected text asynchronously after UI appears (non-blocking)
     // This runs in a background task so it doesn't delay the UI
-    let fetch_text_task = Task::perform(
-        async {
-            debug!("Starting async text fetch task");
-            // Use spawn_blocking for the blocking shell command
-            let result = tokio::task::spawn_blocking(|| {
-                debug!("Executing get_selected_text in blocking thread");
-                crate::system::get_selected_text()
-            })
-            .await;
-            debug!("Text fetch task completed");
-            result.unwrap_or_else(|e| {
-                tracing::warn!(error = %e, "Failed to join blocking task for text fetch");
-                None
-            })
-        },
-        Message::SelectedTextFetched,
-    );
-    
-    // Fetch voices.json asynchronously on startup (Piper voices)
-    let fetch_voices_task = Task::perform(
-        async {
-            debug!("Fetching voices.json from Hugging Face");
-            crate::voices::fetch_voices_json().await
-        },
-        Message::VoicesJsonLoaded,
-    );
-    
-    // Fetch AWS Polly voices asynchronously on startup (only if AWS credentials are available)
-    let fetch_polly_voices_task = Task::perform(
-        async {
-            // Check credentials first before attempting to fetch
-            if crate::providers::PollyTTSProvider::check_credentials().is_ok() {
-                debug!("Fetching AWS Polly voices");
-                crate::voices::aws::fetch_polly_voices().await
-            } else {
-                debug!("AWS credentials not available, skipping voice fetch");
-                Err("AWS credentials not configured".to_string())
-            }
-        },
-        Message::PollyVoicesLoaded,
-    );
+    // When started by the `read` command, its reading arrives instead
+    let speech_options = update::speech_options(&app);
+    let fetch_text_task = if crate::system::launched_for_forwarding() {
+        info!("Started to read a forwarded reading, not capturing the selection");
+        Task::none()
+    } else {
+        Task::perform(
+            async move {
+                debug!("Starting async text fetch task");
+                // Use spawn_blocking for the blocking shell command
+                let result = tokio::task::spawn_blocking(move || {
+                    debug!("Executing get_selected_text in blocking thread");
+                    update::capture_selected_text(speech_options)
+                })
+                .await;
+                debug!("Text fetch task completed");
+                result.unwrap_or_else(|e| {
+                    tracing::warn!(error = %e, "Failed to join blocking task for text fetch");
+                    None
+                })
+            },
+            Message::SelectedTextFetched,
+        )
+    };
     
-    (app, Task::batch([open_task, fetch_text_task, fetch_voices_task, fetch_polly_voices_task]))
+    // Prune old caches, screenshots, logs and history if the cleanup is due
+    let cleanup_task = Task::perform(async { Message::CleanupTick }, |msg| msg);
+    // Show the weekly summary if it came due while the app was not running
+    let summary_task = Task::perform(async { Message::WeeklySummaryTick }, |msg| msg);
+    
+    // Voice lists and Polly credentials are only needed by the settings and
+    // voice selection windows, so they are fetched when those open
+    (app, Task::batch([open_task, fetch_text_task, crate::ui::fonts::load_user_fonts(), cleanup_task, summary_task]))
 }
 
 pub fn title(app: &App, window: window::Id) -> String {
+    if app.main_window_id == Some(window) {
+        return crate::ui::accessibility::main_window_title(app);
+    }
     match window {
         w if app.settings_window_id == Some(w) => "Settings",
         w if app.voice_selection_window_id == Some(w) => "Select Voice",
         w if app.polly_info_window_id == Some(w) => "AWS Polly Pricing Information",
+        w if app.elevenlabs_info_window_id == Some(w) => "ElevenLabs Pricing Information",
+        w if app.capture_source_window_id == Some(w) => "Read From",
         w if app.screenshot_window_id == Some(w) => "Screenshot",
         w if app.text_cleanup_info_window_id == Some(w) => "Natural Reading",
         w if app.extracted_text_dialog_window_id == Some(w) => "Extracted Text",
+        w if app.long_text_dialog_window_id == Some(w) => "Long Text",
+        w if app.sensitive_dialog_window_id == Some(w) => "Sensitive Text",
+        w if app.resume_dialog_window_id == Some(w) => "Continue Reading",
+        w if app.weekly_summary_window_id == Some(w) => "Weekly Summary",
+        w if app.clipboard_history_window_id == Some(w) => "Clipboard History",
+        w if app.processing_chain_window_id == Some(w) => "Processing Chain",
+        w if app.preview_window_id == Some(w) => "Preview",
+        w if app.voice_suggestion_window_id == Some(w) => "Voice Suggestion",
+        w if app.trainer_window_id == Some(w) => "Pronunciation Trainer",
+        w if app.palette_window_id == Some(w) => "Command Palette",
+        w if app.reading_view_window_id == Some(w) => "Reading View",
+        w if app.transcript_window_id == Some(w) => "Transcript",
+        w if app.mini_bar_window_ids.contains(&w) => "Insight Reader Mini Player",
         _ => "Insight Reader",
     }
     .to_string()
@@ -158,6 +192,15 @@ pub fn view(app: &App, window: window::Id) -> Element<'_, Message> {
     if app.polly_info_window_id == Some(window) {
         return view::polly_info_window_view(app);
     }
+
+    if app.capture_source_window_id == Some(window) {
+        return view::capture_source_prompt_view(app);
+    }
+
+    // Show ElevenLabs info modal if this is its info modal window
+    if app.elevenlabs_info_window_id == Some(window) {
+        return view::elevenlabs_info_window_view(app);
+    }
     
     // Show screenshot viewer if this is the screenshot window
     if app.screenshot_window_id == Some(window) {
@@ -179,7 +222,67 @@ pub fn view(app: &App, window: window::Id) -> Element<'_, Message> {
         return view::extracted_text_dialog_view(app);
     }
     
-    view::main_view(app)
+    // Show long text dialog if this is the long text dialog window
+    if app.long_text_dialog_window_id == Some(window) {
+        return view::long_text_dialog_view(app);
+    }
+    
+    // Show sensitive text dialog if this is the sensitive text dialog window
+    if app.sensitive_dialog_window_id == Some(window) {
+        return view::sensitive_text_dialog_view(app);
+    }
+    
+    // Show continue reading dialog if this is the continue reading dialog window
+    if app.resume_dialog_window_id == Some(window) {
+        return view::resume_document_dialog_view(app);
+    }
+    
+    // Show the weekly listening summary if this is its window
+    if app.weekly_summary_window_id == Some(window) {
+        return view::weekly_summary_dialog_view(app);
+    }
+    
+    // Show the last captured texts if this is the clipboard history window
+    if app.clipboard_history_window_id == Some(window) {
+        return view::clipboard_history_view(app);
+    }
+    
+    // Show each stage of the last reading if this is the processing chain window
+    if app.processing_chain_window_id == Some(window) {
+        return view::processing_chain_view(app);
+    }
+    
+    // Show preview popup if this is the hotkey capture preview window
+    if app.preview_window_id == Some(window) {
+        return view::preview_popup_view(app);
+    }
+    
+    // Show voice download suggestion if this is its popup
+    if app.voice_suggestion_window_id == Some(window) {
+        return view::voice_suggestion_popup_view(app);
+    }
+    
+    // Show pronunciation trainer if this is the trainer window
+    if app.trainer_window_id == Some(window) {
+        return crate::ui::trainer::pronunciation_trainer_view(app);
+    }
+    
+    // Show command palette if this is the palette window
+    if app.palette_window_id == Some(window) {
+        return crate::ui::palette::command_palette_view(app);
+    }
+    
+    // Show reading view if this is the reading view window
+    if app.reading_view_window_id == Some(window) {
+        return crate::ui::reading::reading_view(app);
+    }
+    
+    // Show the sentences being spoken if this is the transcript window
+    if app.transcript_window_id == Some(window) {
+        return crate::ui::transcript::transcript_view(app);
+    }
+    
+    view::main_view(app, window)
 }
 
 pub fn subscription(app: &App) -> Subscription<Message> {
@@ -192,29 +295,80 @@ pub fn subscription(app: &App) -> Subscription<Message> {
         Message::WindowClosed(id)
     });
     
-    // Run animation/polling at ~75ms intervals
-    // Poll when playing, paused, loading, or downloading a voice
-    let tick = match (app.playback_state, app.is_loading, app.downloading_voice.is_some()) {
-        (PlaybackState::Stopped, false, false) => Subscription::none(),
-        _ => time::every(Duration::from_millis(75)).map(|_| Message::Tick),
+    // Files dropped on a window are read aloud; sizes and positions are remembered
+    let window_events = window::events().filter_map(|(id, event)| match event {
+        window::Event::FileDropped(path) => Some(Message::FileDropped(id, path)),
+        window::Event::Resized(size) => Some(Message::WindowResized(id, size)),
+        window::Event::Moved(position) => Some(Message::WindowMoved(id, position)),
+        _ => None,
+    });
+    
+    // Run the loading animation at ~75ms intervals while synthesizing or downloading a voice,
+    // and keep the playback time current while speaking
+    let tick = if app.activity.is_busy() || app.downloading_voice.is_some() {
+        time::every(Duration::from_millis(75)).map(|_| Message::Tick)
+    } else if app.activity.has_speech() {
+        time::every(Duration::from_millis(250)).map(|_| Message::Tick)
+    } else {
+        Subscription::none()
+    };
+    
+    // Poll the microphone while hotkey readings are held back during a call
+    let microphone = if app.held_readings.is_empty() {
+        Subscription::none()
+    } else {
+        time::every(Duration::from_secs(3)).map(|_| Message::CheckMicrophone)
     };
     
-    // Poll for system tray events periodically (every 100ms)
-    let tray_poll = if app.system_tray.is_some() {
-        time::every(Duration::from_millis(100)).map(|_| Message::TrayEventReceived)
+    // Watch for typing while reading, and for its end while paused for it
+    let typing = if app.pause_while_typing
+        && (app.activity == crate::activity::Activity::Playing || app.paused_for_typing)
+    {
+        time::every(update::TYPING_CHECK_INTERVAL).map(|_| Message::CheckTyping)
     } else {
         Subscription::none()
     };
     
-    // Poll for hotkey events periodically (every 100ms)
-    // Note: The actual hotkey event checking happens in update.rs when HotkeyPressed is received
-    // Skip if disabled on Wayland/Hyprland
-    let hotkey_poll = if !app.hotkeys_disabled_wayland && app.hotkey_manager.is_some() && app.hotkey_enabled {
-        time::every(Duration::from_millis(100)).map(|_| Message::HotkeyPressed)
+    // Check every half hour whether the storage cleanup is due
+    let cleanup = if app.cleanup_schedule == crate::storage::CleanupSchedule::Off {
+        Subscription::none()
+    } else {
+        time::every(Duration::from_secs(30 * 60)).map(|_| Message::CleanupTick)
+    };
+    
+    // Check every half hour whether the weekly summary is due
+    let weekly_summary = if app.weekly_summary_enabled {
+        time::every(Duration::from_secs(30 * 60)).map(|_| Message::WeeklySummaryTick)
     } else {
         Subscription::none()
     };
     
+    // Synthesize frequently read phrases ahead of time every ten minutes while idle
+    let phrase_warmup = if app.history_enabled {
+        time::every(Duration::from_secs(10 * 60)).map(|_| Message::PhraseWarmupTick)
+    } else {
+        Subscription::none()
+    };
+    
+    // Playback progress and visualization are pushed by the audio thread
+    let playback = Subscription::run(playback_updates);
+    
+    // Tray menu and hotkey events wake the app when they arrive instead of being polled
+    let wake = if app.system_tray.is_some() || app.hotkey_manager.is_some() {
+        Subscription::run(wake_events)
+    } else {
+        Subscription::none()
+    };
+    
+    // Text and files sent by other apps through the context menu entries
+    let forwarded = Subscription::run(forwarded_readings);
+    
+    // New files in the watch folder are read as they arrive
+    let watch = match app.watch_dir.clone() {
+        Some(dir) => Subscription::run_with(dir, watch_folder_events),
+        None => Subscription::none(),
+    };
+    
     // Subscribe to keyboard events when listening for hotkey input
     let keyboard_sub = if app.listening_for_hotkey {
         keyboard::listen().filter_map(|event| {
@@ -241,8 +395,87 @@ pub fn subscription(app: &App) -> Subscription<Message> {
             }
         })
     } else {
-        Subscription::none()
+        // Keys no widget used, for operating the app from the keyboard
+        iced::event::listen_with(|event, status, window| match (event, status) {
+            (
+                iced::Event::Keyboard(keyboard::Event::KeyPressed { key, modifiers, .. }),
+                iced::event::Status::Ignored,
+            ) => Some(Message::WindowKeyPressed(window, key, modifiers)),
+            _ => None,
+        })
     };
     
-    Subscription::batch(vec![window_opened, window_closed, tick, tray_poll, hotkey_poll, keyboard_sub])
+    Subscription::batch(vec![window_opened, window_closed, window_events, tick, microphone, typing, cleanup, weekly_summary, phrase_warmup, playback, wake, forwarded, watch, keyboard_sub])
+}
+
+/// Stream of playback updates from the audio thread.
+fn playback_updates() -> impl Stream<Item = Message> {
+    iced::stream::channel(16, async |mut output| {
+        let (sender, mut receiver) = iced::futures::channel::mpsc::unbounded();
+        crate::providers::set_playback_listener(move |update| sender.unbounded_send(update).is_ok());
+        while let Some(update) = receiver.next().await {
+            if output.send(Message::PlaybackUpdated(update)).await.is_err() {
+                break;
+            }
+        }
+    })
+}
+
+/// Stream of messages draining the tray and hotkey queues whenever they get an event.
+fn wake_events() -> impl Stream<Item = Message> {
+    iced::stream::channel(16, async |mut output| {
+        let (sender, mut receiver) = iced::futures::channel::mpsc::unbounded();
+        crate::system::set_wake_listener(move |source| sender.unbounded_send(source).is_ok());
+        // Drain anything queued before the listener was registered
+        let _ = output.send(Message::TrayEventReceived).await;
+        let _ = output.send(Message::HotkeyPressed).await;
+        while let Some(source) = receiver.next().await {
+            let message = match source {
+                crate::system::WakeSource::Tray => Message::TrayEventReceived,
+                crate::system::WakeSource::Hotkey => Message::HotkeyPressed,
+            };
+            if output.send(message).await.is_err() {
+                break;
+            }
+        }
+    })
+}
+
+/// Stream of readings sent by other processes, served for the lifetime of the app.
+fn forwarded_readings() -> impl Stream<Item = Message> {
+    iced::stream::channel(16, async |mut output| {
+        let (sender, mut receiver) = iced::futures::channel::mpsc::unbounded();
+        std::thread::spawn(move || {
+            let result = crate::system::serve_forwarded_readings(|reading| sender.unbounded_send(reading).is_ok());
+            if let Err(e) = result {
+                tracing::warn!(error = %e, "Readings from other apps are unavailable");
+            }
+        });
+        while let Some(reading) = receiver.next().await {
+            if output.send(Message::ReadingForwarded(reading)).await.is_err() {
+                break;
+            }
+        }
+    })
+}
+
+/// Stream of files that finished arriving in the watch folder, for as long as it is watched.
+fn watch_folder_events(dir: &std::path::PathBuf) -> impl Stream<Item = Message> {
+    let dir = dir.clone();
+    iced::stream::channel(16, async move |mut output| {
+        let (sender, mut receiver) = iced::futures::channel::mpsc::unbounded();
+        // Dropping the watcher when the stream ends stops watching
+        let _watcher = match crate::system::watch_folder(&dir, move |path| sender.unbounded_send(path).is_ok()) {
+            Ok(watcher) => watcher,
+            Err(e) => {
+                let _ = output.send(Message::WatchFolderFailed(e)).await;
+                return;
+            }
+        };
+        while let Some(path) = receiver.next().await {
+            if output.send(Message::WatchedFileAdded(path)).await.is_err() {
+                break;
+            }
+        }
+    })
 }
@@ -0,0 +1,128 @@
+//! First-run setup of the platform bundles (AppImage, dmg, msi).
+//!
+//! `make appimage`, `make dmg` and `make msi` ship a Piper build and one
+//! default voice inside the package, in a `bundle` folder next to the
+//! executable (`Contents/Resources/bundle` in the macOS app,
+//! `usr/share/insight-reader/bundle` in the AppImage). On launch they are
+//! extracted into the data dir, where the Piper provider looks for them,
+//! so speech works without running an install script or downloading a
+//! voice. `bundle.json` names the bundle version and the voice; a copy is
+//! kept in the data dir once extracted, so each version is set up once.
+
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+const MANIFEST: &str = "bundle.json";
+const PIPER_ARCHIVE: &str = "piper.zip";
+
+/// What a bundle contains, from its `bundle.json`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Manifest {
+    /// Version of the package the bundle came with
+    version: String,
+    /// Key of the bundled Piper voice (e.g. "en_US-lessac-medium")
+    voice: String,
+}
+
+/// The `bundle` folder shipped with the executable, if the app runs from a bundle.
+fn bundle_dir() -> Option<PathBuf> {
+    let exe = std::env::current_exe().ok()?;
+    let exe_dir = exe.parent()?;
+    [
+        exe_dir.join("bundle"),
+        exe_dir.join("..").join("Resources").join("bundle"),
+        exe_dir.join("..").join("share").join("insight-reader").join("bundle"),
+    ]
+    .into_iter()
+    .find(|dir| dir.join(MANIFEST).is_file())
+}
+
+fn read_manifest(path: &Path) -> Option<Manifest> {
+    serde_json::from_str(&std::fs::read_to_string(path).ok()?).ok()
+}
+
+/// Extract the bundled Piper and voice if this version of the bundle was
+/// not set up yet, and select the voice when none is. Does nothing when the
+/// app does not run from a bundle.
+pub fn install() {
+    let Some(bundle) = bundle_dir() else {
+        return;
+    };
+    let Some(data_dir) = dirs::data_dir().map(|dir| dir.join("insight-reader")) else {
+        warn!("No data directory, bundled voice not installed");
+        return;
+    };
+    match install_from(&bundle, &data_dir) {
+        Ok(Some(voice)) => {
+            info!(voice = %voice, bundle = %bundle.display(), "Bundled Piper and voice installed");
+            if crate::config::load_selected_voice().is_none() {
+                crate::config::save_selected_voice(voice);
+            }
+        }
+        Ok(None) => debug!("Bundle already installed"),
+        Err(e) => warn!(error = %e, bundle = %bundle.display(), "Failed to install the bundled Piper and voice"),
+    }
+}
+
+/// Extract `bundle` into `data_dir`: Piper into `piper/`, the voice into
+/// `models/`. Returns the voice key, or None if this version was already set up.
+fn install_from(bundle: &Path, data_dir: &Path) -> Result<Option<String>, String> {
+    let manifest = read_manifest(&bundle.join(MANIFEST)).ok_or_else(|| format!("Invalid {MANIFEST}"))?;
+    let installed = data_dir.join(MANIFEST);
+    if read_manifest(&installed).as_ref() == Some(&manifest) {
+        return Ok(None);
+    }
+
+    let archive = bundle.join(PIPER_ARCHIVE);
+    if archive.is_file() {
+        let file = File::open(&archive).map_err(|e| format!("Failed to open {}: {e}", archive.display()))?;
+        let mut zip = zip::ZipArchive::new(file).map_err(|e| format!("Invalid {PIPER_ARCHIVE}: {e}"))?;
+        // The archive holds a `piper/` folder with the binary, its libraries and espeak-ng data
+        zip.extract(data_dir).map_err(|e| format!("Failed to extract {PIPER_ARCHIVE}: {e}"))?;
+    }
+
+    let models = data_dir.join("models");
+    std::fs::create_dir_all(&models).map_err(|e| format!("Failed to create {}: {e}", models.display()))?;
+    for name in [format!("{}.onnx", manifest.voice), format!("{}.onnx.json", manifest.voice)] {
+        std::fs::copy(bundle.join(&name), models.join(&name)).map_err(|e| format!("Failed to copy {name}: {e}"))?;
+    }
+
+    let json = serde_json::to_string_pretty(&manifest).map_err(|e| e.to_string())?;
+    std::fs::write(&installed, json).map_err(|e| format!("Failed to write {}: {e}", installed.display()))?;
+    Ok(Some(manifest.voice))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+
+    #[test]
+    fn test_install_from() {
+        let root = std::env::temp_dir().join(format!("insight-reader-bundle-{}", std::process::id()));
+        let bundle = root.join("bundle");
+        let data_dir = root.join("data");
+        std::fs::create_dir_all(&bundle).unwrap();
+        std::fs::write(bundle.join(MANIFEST), r#"{"version": "1.0", "voice": "en_US-test-low"}"#).unwrap();
+        std::fs::write(bundle.join("en_US-test-low.onnx"), "model").unwrap();
+        std::fs::write(bundle.join("en_US-test-low.onnx.json"), "{}").unwrap();
+        let mut zip = zip::ZipWriter::new(File::create(bundle.join(PIPER_ARCHIVE)).unwrap());
+        zip.start_file("piper/piper", SimpleFileOptions::default().unix_permissions(0o755)).unwrap();
+        zip.write_all(b"binary").unwrap();
+        zip.finish().unwrap();
+
+        assert_eq!(install_from(&bundle, &data_dir).unwrap().as_deref(), Some("en_US-test-low"));
+        assert_eq!(std::fs::read_to_string(data_dir.join("piper").join("piper")).unwrap(), "binary");
+        assert!(data_dir.join("models").join("en_US-test-low.onnx.json").is_file());
+        // Set up once per version
+        assert_eq!(install_from(&bundle, &data_dir).unwrap(), None);
+        std::fs::write(bundle.join(MANIFEST), r#"{"version": "1.1", "voice": "en_US-test-low"}"#).unwrap();
+        assert!(install_from(&bundle, &data_dir).unwrap().is_some());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
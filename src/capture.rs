@@ -0,0 +1,109 @@
+//! Where the hotkey looks for the text to read.
+//!
+//! The hotkey tries the capture sources in the order of the configured
+//! [`SourcePriority`] and reads the first text found. When the order reaches
+//! OCR, the user is offered to capture a screen region instead; with
+//! [`SourcePriority::AlwaysAsk`] the user picks the source every time.
+
+use tracing::debug;
+
+/// A place the text to read can come from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptureSource {
+    /// The selected text (the PRIMARY selection on Linux, the clipboard elsewhere)
+    Selection,
+    /// The text on the clipboard
+    Clipboard,
+    /// A screen region captured and read with OCR, after asking
+    Ocr,
+}
+
+impl CaptureSource {
+    /// This source alone, as a capture order.
+    pub fn only(self) -> &'static [CaptureSource] {
+        match self {
+            CaptureSource::Selection => &[CaptureSource::Selection],
+            CaptureSource::Clipboard => &[CaptureSource::Clipboard],
+            CaptureSource::Ocr => &[CaptureSource::Ocr],
+        }
+    }
+}
+
+/// Order the hotkey tries the capture sources in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourcePriority {
+    /// Selection, then clipboard, then offer OCR
+    SelectionFirst,
+    /// Clipboard, then selection, then offer OCR
+    ClipboardFirst,
+    /// Ask which source to read on every hotkey press
+    AlwaysAsk,
+}
+
+impl SourcePriority {
+    /// Sources to try in turn; empty when the user is asked instead.
+    pub fn sources(self) -> &'static [CaptureSource] {
+        match self {
+            SourcePriority::SelectionFirst => &[CaptureSource::Selection, CaptureSource::Clipboard, CaptureSource::Ocr],
+            SourcePriority::ClipboardFirst => &[CaptureSource::Clipboard, CaptureSource::Selection, CaptureSource::Ocr],
+            SourcePriority::AlwaysAsk => &[],
+        }
+    }
+}
+
+/// What trying the capture sources found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Captured {
+    /// Text from one of the sources
+    Text(String),
+    /// No text; the order goes on to OCR, so offer a screen capture
+    OfferOcr,
+    /// No text in any source
+    Nothing,
+}
+
+/// Try `sources` in turn with `read`, which returns the text of a text source.
+///
+/// Blocks as long as `read` does.
+pub fn capture_text(sources: &[CaptureSource], mut read: impl FnMut(CaptureSource) -> Option<String>) -> Captured {
+    for &source in sources {
+        if source == CaptureSource::Ocr {
+            return Captured::OfferOcr;
+        }
+        if let Some(text) = read(source).filter(|text| !text.trim().is_empty()) {
+            debug!(?source, bytes = text.len(), "Captured text");
+            return Captured::Text(text);
+        }
+        debug!(?source, "No text in capture source");
+    }
+    Captured::Nothing
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capture_text() {
+        let clipboard_only = |source| (source == CaptureSource::Clipboard).then(|| "copied".to_string());
+        assert_eq!(
+            capture_text(SourcePriority::SelectionFirst.sources(), clipboard_only),
+            Captured::Text("copied".to_string())
+        );
+
+        let mut tried = Vec::new();
+        let both = |source| {
+            tried.push(source);
+            Some(format!("{source:?}"))
+        };
+        assert_eq!(
+            capture_text(SourcePriority::ClipboardFirst.sources(), both),
+            Captured::Text("Clipboard".to_string())
+        );
+        assert_eq!(tried, [CaptureSource::Clipboard]);
+
+        let blank = |_| Some("  ".to_string());
+        assert_eq!(capture_text(SourcePriority::SelectionFirst.sources(), blank), Captured::OfferOcr);
+        assert_eq!(capture_text(CaptureSource::Selection.only(), |_| None), Captured::Nothing);
+    }
+}
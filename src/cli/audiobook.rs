@@ -0,0 +1,318 @@
+//! `insight-reader audiobook`: export a book as chaptered, tagged audio files.
+//!
+//! Each chapter is synthesized paragraph by paragraph and written to disk as
+//! soon as it is done. A manifest (`audiobook.json`) in the output directory
+//! records finished chapters, so an interrupted job picks up where it left off.
+//! With `--encrypt`, the finished files are encrypted with age at the end and
+//! everything left in plain text (chapter WAVs, the manifest) is shredded.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::{create_provider, encryption, provider_option, selected_voice, Options};
+use crate::providers::{samples_to_wav, TTSProvider};
+use crate::system::{
+    add_to_feed, encode_m4b, encode_mp3, encode_mp3_chapters, encrypt_file, find_ffmpeg, sanitize_file_name,
+    shred, AudioTags, ChapterMarker,
+};
+use crate::text::chapters::{load_document, Chapter, Document};
+use crate::{config, text};
+
+/// Silence inserted between paragraphs, in seconds.
+const PARAGRAPH_PAUSE_SECS: f32 = 0.4;
+
+/// Name of the resume manifest inside the output directory.
+const MANIFEST_FILE: &str = "audiobook.json";
+
+const USAGE: &str = "\
+Usage: insight-reader audiobook <input> [options]
+
+Export an EPUB, text, Markdown or HTML file as one audio file per chapter.
+
+Options:
+  --voice <VOICE>        Voice key (Piper model, or Polly voice like Joanna:Neural)
+  --provider <NAME>      piper, polly, elevenlabs, macos, windows or espeak (default: provider from settings)
+  --out <DIR>            Output directory (default: <input name> next to the input)
+  --format <FORMAT>      mp3, m4b or wav (default: mp3)
+  --single-file          With mp3, write one file with chapter markers instead of one per chapter
+  --restart              Ignore previous progress and synthesize everything again
+  --no-feed              Do not add the exported files to the podcast feed
+  --encrypt              Encrypt the finished files with age (not added to the feed,
+                         and the export cannot be resumed once done)
+  --recipient <KEY>      age or SSH public key, or recipients file, to encrypt to
+                         (repeatable; default: the keys set under Privacy in the settings)
+  -h, --help             Show this message
+
+MP3 and M4B output need ffmpeg on PATH, --encrypt needs age.";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OutputFormat {
+    Mp3,
+    M4b,
+    Wav,
+}
+
+/// A finished chapter recorded in the resume manifest.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ManifestChapter {
+    title: String,
+    hash: String,
+    file: String,
+    duration_secs: f32,
+}
+
+/// Resume manifest written after each chapter.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Manifest {
+    /// Voice used for the finished chapters; changing it restarts the job
+    voice: String,
+    /// Output layout of the finished chapters; changing it restarts the job
+    #[serde(default)]
+    format: String,
+    chapters: Vec<ManifestChapter>,
+}
+
+impl Manifest {
+    fn load(path: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(path).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    fn save(&self, path: &Path) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize manifest: {e}"))?;
+        std::fs::write(path, json).map_err(|e| format!("Failed to write manifest: {e}"))
+    }
+
+    /// The finished entry for a chapter, if its text is unchanged and the file still exists.
+    fn finished(&self, out_dir: &Path, hash: &str) -> Option<&ManifestChapter> {
+        self.chapters
+            .iter()
+            .find(|c| c.hash == hash && out_dir.join(&c.file).is_file())
+    }
+}
+
+pub fn run(args: &[String]) -> Result<(), String> {
+    let options = Options::parse(args, &["voice", "provider", "out", "format", "recipient"])?;
+    if options.flag("help") {
+        println!("{USAGE}");
+        return Ok(());
+    }
+    let input = match options.positional.as_slice() {
+        [input] => PathBuf::from(input),
+        _ => return Err(format!("expected one input file\n\n{USAGE}")),
+    };
+
+    let format = match options.value("format").unwrap_or("mp3") {
+        "mp3" => OutputFormat::Mp3,
+        "m4b" => OutputFormat::M4b,
+        "wav" => OutputFormat::Wav,
+        other => return Err(format!("unknown format '{other}' (expected mp3, m4b or wav)")),
+    };
+    let ffmpeg = match format {
+        OutputFormat::Wav => None,
+        _ => Some(find_ffmpeg().ok_or("ffmpeg not found on PATH (needed for mp3/m4b output)")?),
+    };
+    let encryption = encryption(&options)?;
+
+    let backend = provider_option(&options)?;
+    let voice = options.value("voice").map(str::to_string);
+    let single_file = format == OutputFormat::Mp3 && options.flag("single-file");
+    // Encrypted files cannot be played from the feed
+    let feed = Some(config::load_feed_config())
+        .filter(|f| f.enabled && !options.flag("no-feed") && encryption.is_none());
+    // Voice name written as the artist tag
+    let voice_name = voice
+        .clone()
+        .or_else(|| selected_voice(backend))
+        .unwrap_or_else(|| format!("{backend:?}"));
+
+    let out_dir = options
+        .value("out")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| input.with_extension(""));
+    std::fs::create_dir_all(&out_dir)
+        .map_err(|e| format!("Failed to create {}: {e}", out_dir.display()))?;
+
+    let document = load_document(&input)?;
+    eprintln!(
+        "{} - {} chapter(s), {}",
+        document.title,
+        document.chapters.len(),
+        out_dir.display()
+    );
+
+    let mut provider = create_provider(backend, voice.clone())?;
+
+    let voice_label = format!("{backend:?}:{}", voice.unwrap_or_default());
+    let format_label = format!("{format:?}{}", if single_file { "-single" } else { "" });
+    let manifest_path = out_dir.join(MANIFEST_FILE);
+    let mut manifest = Manifest::load(&manifest_path)
+        .filter(|m| !options.flag("restart") && m.voice == voice_label && m.format == format_label)
+        .unwrap_or_else(|| Manifest {
+            voice: voice_label,
+            format: format_label,
+            chapters: Vec::new(),
+        });
+
+    let total = document.chapters.len();
+    let mut markers = Vec::with_capacity(total);
+    for (index, chapter) in document.chapters.iter().enumerate() {
+        let number = index + 1;
+        let hash = text::content_hash(&format!("{}\n{}", chapter.title, chapter.text));
+
+        if let Some(done) = manifest.finished(&out_dir, &hash) {
+            eprintln!("[{number}/{total}] {} (already done)", chapter.title);
+            markers.push(ChapterMarker {
+                title: done.title.clone(),
+                wav_path: out_dir.join(&done.file).with_extension("wav"),
+                duration_secs: done.duration_secs,
+            });
+            continue;
+        }
+
+        let samples = synthesize_chapter(provider.as_mut(), chapter, number, total)?;
+        let sample_rate = provider.sample_rate();
+        let duration_secs = samples.len() as f32 / sample_rate as f32;
+
+        let stem = format!("{number:02} - {}", sanitize_file_name(&chapter.title));
+        let wav_path = out_dir.join(format!("{stem}.wav"));
+        std::fs::write(&wav_path, samples_to_wav(&samples, sample_rate))
+            .map_err(|e| format!("Failed to write {}: {e}", wav_path.display()))?;
+
+        let file = match (format, ffmpeg.as_deref()) {
+            (OutputFormat::Mp3, Some(ffmpeg)) if !single_file => {
+                let mp3_path = out_dir.join(format!("{stem}.mp3"));
+                let tags = chapter_tags(&document, chapter, &voice_name, number, total);
+                encode_mp3(ffmpeg, &wav_path, &mp3_path, &tags)?;
+                let _ = std::fs::remove_file(&wav_path);
+                format!("{stem}.mp3")
+            }
+            // M4B and single-file MP3 keep the WAVs until the final mux
+            _ => format!("{stem}.wav"),
+        };
+
+        // One episode per chapter file; joined outputs are added once at the end
+        let joined_later = format == OutputFormat::M4b || single_file;
+        if let (Some(feed), false) = (&feed, joined_later) {
+            let episode = format!("{} - {}", document.title, chapter.title);
+            add_to_feed(feed, &out_dir.join(&file), &episode, &document.title, duration_secs)?;
+        }
+
+        manifest.chapters.retain(|c| c.hash != hash);
+        manifest.chapters.push(ManifestChapter {
+            title: chapter.title.clone(),
+            hash,
+            file,
+            duration_secs,
+        });
+        manifest.save(&manifest_path)?;
+        markers.push(ChapterMarker {
+            title: chapter.title.clone(),
+            wav_path,
+            duration_secs,
+        });
+    }
+
+    let joined = if let Some(ffmpeg) = ffmpeg.as_deref() {
+        let tags = AudioTags {
+            title: document.title.clone(),
+            album: Some(document.title.clone()),
+            artist: Some(voice_name.clone()),
+            album_artist: document.author.clone(),
+            track: None,
+            genre: Some("Audiobook".to_string()),
+        };
+        let stem = sanitize_file_name(&document.title);
+        let joined = if format == OutputFormat::M4b {
+            let m4b_path = out_dir.join(format!("{stem}.m4b"));
+            eprintln!("Building {}", m4b_path.display());
+            encode_m4b(ffmpeg, &markers, &m4b_path, &tags)?;
+            Some(m4b_path)
+        } else if single_file {
+            let mp3_path = out_dir.join(format!("{stem}.mp3"));
+            eprintln!("Building {}", mp3_path.display());
+            encode_mp3_chapters(ffmpeg, &markers, &mp3_path, &tags)?;
+            Some(mp3_path)
+        } else {
+            None
+        };
+
+        if let (Some(feed), Some(joined)) = (&feed, &joined) {
+            let duration_secs = markers.iter().map(|m| m.duration_secs).sum();
+            let description = document.author.clone().unwrap_or_default();
+            add_to_feed(feed, joined, &document.title, &description, duration_secs)?;
+        }
+        joined
+    } else {
+        None
+    };
+
+    if let Some((age, recipients)) = &encryption {
+        let outputs: Vec<PathBuf> = match joined {
+            Some(joined) => vec![joined],
+            None => manifest.chapters.iter().map(|c| out_dir.join(&c.file)).collect(),
+        };
+        for output in &outputs {
+            let encrypted = encrypt_file(age, output, recipients)?;
+            eprintln!("Encrypted {}", encrypted.display());
+        }
+        // Chapter WAVs kept for joining and the manifest (chapter titles) would stay readable
+        for leftover in markers.iter().map(|m| &m.wav_path).chain([&manifest_path]) {
+            if leftover.is_file() {
+                shred(leftover);
+            }
+        }
+    }
+
+    eprintln!("Done: {}", out_dir.display());
+    Ok(())
+}
+
+/// Synthesize one chapter paragraph by paragraph, printing progress to stderr.
+fn synthesize_chapter(
+    provider: &mut dyn TTSProvider,
+    chapter: &Chapter,
+    number: usize,
+    total: usize,
+) -> Result<Vec<f32>, String> {
+    let paragraphs = text::split_paragraphs(&chapter.text);
+    let pause = vec![0.0; (provider.sample_rate() as f32 * PARAGRAPH_PAUSE_SECS) as usize];
+    let mut samples = Vec::new();
+
+    for (i, paragraph) in paragraphs.iter().enumerate() {
+        let percent = i * 100 / paragraphs.len().max(1);
+        eprint!("\r[{number}/{total}] {} {percent:>3}%", chapter.title);
+        let _ = std::io::stderr().flush();
+
+        let audio = provider
+            .synthesize(paragraph)
+            .map_err(|e| format!("Chapter '{}': {e}", chapter.title))?;
+        if !samples.is_empty() {
+            samples.extend_from_slice(&pause);
+        }
+        samples.extend(audio);
+    }
+    eprintln!("\r[{number}/{total}] {} 100%", chapter.title);
+    Ok(samples)
+}
+
+/// ID3 tags for a single chapter file.
+fn chapter_tags(
+    document: &Document,
+    chapter: &Chapter,
+    voice_name: &str,
+    number: usize,
+    total: usize,
+) -> AudioTags {
+    AudioTags {
+        title: chapter.title.clone(),
+        album: Some(document.title.clone()),
+        artist: Some(voice_name.to_string()),
+        album_artist: document.author.clone(),
+        track: Some((number, total)),
+        genre: Some("Audiobook".to_string()),
+    }
+}
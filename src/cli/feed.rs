@@ -0,0 +1,103 @@
+//! `insight-reader feed`: manage and serve the local podcast feed.
+
+use std::path::PathBuf;
+
+use super::Options;
+use crate::config;
+use crate::system::{add_to_feed, refresh_feed, FeedServer};
+
+const USAGE: &str = "\
+Usage: insight-reader feed [serve | add <file> | config] [options]
+
+Without a subcommand, shows the feed settings and URL.
+
+Subcommands:
+  serve                  Serve the feed folder on the LAN until interrupted
+  add <file>             Add an audio file as an episode
+      --title <TITLE>        Episode title (default: file name)
+      --description <TEXT>   Episode description
+  config                 Change feed settings
+      --enable | --disable   Turn automatic adding of exports on or off
+      --title <TITLE>        Feed title
+      --author <NAME>        Feed author
+      --description <TEXT>   Feed description
+      --dir <DIR>            Feed folder
+      --port <PORT>          Server port
+      --base-url <URL>       URL used in the feed instead of the LAN address (empty to reset)";
+
+pub fn run(args: &[String]) -> Result<(), String> {
+    let options = Options::parse(
+        args,
+        &["title", "author", "description", "dir", "port", "base-url"],
+    )?;
+    if options.flag("help") {
+        println!("{USAGE}");
+        return Ok(());
+    }
+
+    let mut feed = config::load_feed_config();
+    match options.positional.first().map(String::as_str) {
+        None => {}
+        Some("serve") => {
+            let _server = FeedServer::start(&feed)?;
+            eprintln!("Serving {} at {} (Ctrl+C to stop)", feed.dir.display(), feed.feed_url());
+            loop {
+                std::thread::park();
+            }
+        }
+        Some("add") => {
+            let file = options
+                .positional
+                .get(1)
+                .map(PathBuf::from)
+                .ok_or_else(|| format!("add needs an audio file\n\n{USAGE}"))?;
+            let title = options
+                .value("title")
+                .map(str::to_string)
+                .or_else(|| file.file_stem().map(|s| s.to_string_lossy().to_string()))
+                .unwrap_or_default();
+            add_to_feed(&feed, &file, &title, options.value("description").unwrap_or(""), 0.0)?;
+            eprintln!("Added '{title}'");
+            return Ok(());
+        }
+        Some("config") => {
+            if options.flag("enable") {
+                feed.enabled = true;
+            }
+            if options.flag("disable") {
+                feed.enabled = false;
+            }
+            if let Some(title) = options.value("title") {
+                feed.title = title.to_string();
+            }
+            if let Some(author) = options.value("author") {
+                feed.author = author.to_string();
+            }
+            if let Some(description) = options.value("description") {
+                feed.description = description.to_string();
+            }
+            if let Some(dir) = options.value("dir") {
+                feed.dir = PathBuf::from(dir);
+            }
+            if let Some(port) = options.value("port") {
+                feed.port = port
+                    .parse()
+                    .map_err(|_| format!("invalid port '{port}'"))?;
+            }
+            if let Some(base_url) = options.value("base-url") {
+                feed.base_url = Some(base_url.to_string()).filter(|u| !u.is_empty());
+            }
+            config::save_feed_config(&feed);
+            refresh_feed(&feed)?;
+        }
+        Some(other) => return Err(format!("unknown feed command '{other}'\n\n{USAGE}")),
+    }
+
+    println!("Enabled:     {}", if feed.enabled { "yes" } else { "no" });
+    println!("Title:       {}", feed.title);
+    println!("Author:      {}", feed.author);
+    println!("Description: {}", feed.description);
+    println!("Folder:      {}", feed.dir.display());
+    println!("Feed URL:    {}", feed.feed_url());
+    Ok(())
+}
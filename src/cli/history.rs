@@ -0,0 +1,99 @@
+//! `insight-reader history`: export the reading history as an archive.
+
+use std::io::{Cursor, Write};
+use std::path::PathBuf;
+
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use super::{encryption, Options};
+use crate::system::{encrypt_bytes, AGE_EXTENSION};
+
+const USAGE: &str = "\
+Usage: insight-reader history export [options]
+
+Export the reading history, document positions and listening statistics
+as a zip archive.
+
+Options:
+  --out <FILE>           Archive path (default: insight-reader-history-<date>.zip here)
+  --encrypt              Encrypt the archive with age; it is never written unencrypted
+  --recipient <KEY>      age or SSH public key, or recipients file, to encrypt to
+                         (repeatable; default: the keys set under Privacy in the settings)
+  -h, --help             Show this message
+
+--encrypt needs age on PATH.";
+
+pub fn run(args: &[String]) -> Result<(), String> {
+    let options = Options::parse(args, &["out", "recipient"])?;
+    if options.flag("help") {
+        println!("{USAGE}");
+        return Ok(());
+    }
+    match options.positional.as_slice() {
+        [command] if command == "export" => {}
+        _ => return Err(format!("expected a subcommand\n\n{USAGE}")),
+    }
+    let encryption = encryption(&options)?;
+
+    let mut out = options.value("out").map(PathBuf::from).unwrap_or_else(|| {
+        PathBuf::from(format!("insight-reader-history-{}.zip", chrono::Local::now().format("%Y-%m-%d")))
+    });
+    let files = [
+        crate::history::history_path(),
+        crate::history::positions_path(),
+        crate::stats::stats_path(),
+    ];
+    let archive = build_archive(&files)?;
+
+    match encryption {
+        Some((age, recipients)) => {
+            if out.extension().is_none_or(|extension| extension != AGE_EXTENSION) {
+                let mut encrypted = out.into_os_string();
+                encrypted.push(format!(".{AGE_EXTENSION}"));
+                out = PathBuf::from(encrypted);
+            }
+            encrypt_bytes(&age, &archive, &recipients, &out)?;
+        }
+        None => std::fs::write(&out, archive).map_err(|e| format!("Failed to write {}: {e}", out.display()))?,
+    }
+    eprintln!("Exported history to {}", out.display());
+    Ok(())
+}
+
+/// Zip archive, built in memory, of the files that exist.
+fn build_archive(files: &[PathBuf]) -> Result<Vec<u8>, String> {
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    for path in files {
+        let Ok(content) = std::fs::read(path) else {
+            continue;
+        };
+        let name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+        zip.start_file(name, options)
+            .map_err(|e| format!("Failed to add {} to the archive: {e}", path.display()))?;
+        zip.write_all(&content)
+            .map_err(|e| format!("Failed to add {} to the archive: {e}", path.display()))?;
+    }
+    let archive = zip.finish().map_err(|e| format!("Failed to build the archive: {e}"))?;
+    Ok(archive.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_archive_skips_missing_files() {
+        let dir = std::env::temp_dir().join(format!("insight-reader-history-export-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let history = dir.join("history.json");
+        std::fs::write(&history, "{}").unwrap();
+
+        let archive = build_archive(&[history, dir.join("missing.json")]).unwrap();
+        let mut zip = zip::ZipArchive::new(Cursor::new(archive)).unwrap();
+        assert_eq!(zip.len(), 1);
+        assert_eq!(zip.by_index(0).unwrap().name(), "history.json");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,289 @@
+//! `insight-reader integrate`: add "Read with Insight Reader" to the system menus.
+//!
+//! Each entry runs `insight-reader read`, which hands the selection to the
+//! running app:
+//! - macOS: a Quick Action in the Services menu for selected text
+//! - Windows: a Send To shortcut for files chosen in Explorer
+//! - Linux: a Nautilus script for files chosen in Files
+
+use std::path::{Path, PathBuf};
+
+use super::Options;
+
+/// Name of the menu entry on every platform.
+const ENTRY_NAME: &str = "Read with Insight Reader";
+
+const USAGE: &str = "\
+Usage: insight-reader integrate [--remove]
+
+Add \"Read with Insight Reader\" to the system menus:
+  macOS      Services menu (and right-click > Services) for selected text
+  Windows    Send To menu for files in Explorer
+  Linux      Scripts menu for files in Files (Nautilus)
+
+Options:
+  --remove               Remove the menu entry
+  -h, --help             Show this message
+
+Run it again after moving the app, as the entry points to this executable.";
+
+pub fn run(args: &[String]) -> Result<(), String> {
+    let options = Options::parse(args, &[])?;
+    if options.flag("help") {
+        println!("{USAGE}");
+        return Ok(());
+    }
+    let entry = entry_path()?;
+    if options.flag("remove") {
+        return remove(&entry);
+    }
+    let exe = std::env::current_exe().map_err(|e| format!("Failed to find this executable: {e}"))?;
+    if let Some(dir) = entry.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {e}", dir.display()))?;
+    }
+    install(&entry, &exe)?;
+    println!("Installed \"{ENTRY_NAME}\" at {}", entry.display());
+    Ok(())
+}
+
+fn remove(entry: &Path) -> Result<(), String> {
+    let result = if entry.is_dir() {
+        std::fs::remove_dir_all(entry)
+    } else {
+        std::fs::remove_file(entry)
+    };
+    match result {
+        Ok(()) => {
+            println!("Removed {}", entry.display());
+            Ok(())
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            println!("\"{ENTRY_NAME}\" is not installed");
+            Ok(())
+        }
+        Err(e) => Err(format!("Failed to remove {}: {e}", entry.display())),
+    }
+}
+
+/// Escape text for an XML document.
+#[cfg(any(target_os = "macos", test))]
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// Quote a path for a POSIX shell.
+#[cfg(any(not(target_os = "windows"), test))]
+fn shell_quote(path: &Path) -> String {
+    format!("'{}'", path.display().to_string().replace('\'', r"'\''"))
+}
+
+#[cfg(target_os = "macos")]
+fn entry_path() -> Result<PathBuf, String> {
+    let home = dirs::home_dir().ok_or("Failed to find the home folder")?;
+    Ok(home.join("Library/Services").join(format!("{ENTRY_NAME}.workflow")))
+}
+
+/// Write a Quick Action that passes the selected text to `read` on stdin.
+#[cfg(target_os = "macos")]
+fn install(entry: &Path, exe: &Path) -> Result<(), String> {
+    let contents = entry.join("Contents");
+    std::fs::create_dir_all(&contents).map_err(|e| format!("Failed to create {}: {e}", contents.display()))?;
+    let command = format!("{} read -", shell_quote(exe));
+    let info = INFO_PLIST.replace("{name}", &xml_escape(ENTRY_NAME));
+    let workflow = WORKFLOW.replace("{command}", &xml_escape(&command));
+    for (name, text) in [("Info.plist", info), ("document.wflow", workflow)] {
+        let path = contents.join(name);
+        std::fs::write(&path, text).map_err(|e| format!("Failed to write {}: {e}", path.display()))?;
+    }
+    // Make the Services menu pick up the new entry without logging out
+    let _ = std::process::Command::new("/System/Library/CoreServices/pbs").arg("-update").status();
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+const INFO_PLIST: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+	<key>NSServices</key>
+	<array>
+		<dict>
+			<key>NSMenuItem</key>
+			<dict>
+				<key>default</key>
+				<string>{name}</string>
+			</dict>
+			<key>NSMessage</key>
+			<string>runWorkflowAsService</string>
+			<key>NSSendTypes</key>
+			<array>
+				<string>public.utf8-plain-text</string>
+			</array>
+		</dict>
+	</array>
+</dict>
+</plist>
+"#;
+
+#[cfg(target_os = "macos")]
+const WORKFLOW: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+	<key>AMApplicationBuild</key>
+	<string>523</string>
+	<key>AMApplicationVersion</key>
+	<string>2.10</string>
+	<key>AMDocumentVersion</key>
+	<string>2</string>
+	<key>actions</key>
+	<array>
+		<dict>
+			<key>action</key>
+			<dict>
+				<key>AMAccepts</key>
+				<dict>
+					<key>Container</key>
+					<string>List</string>
+					<key>Optional</key>
+					<true/>
+					<key>Types</key>
+					<array>
+						<string>com.apple.cocoa.string</string>
+					</array>
+				</dict>
+				<key>AMActionVersion</key>
+				<string>2.0.3</string>
+				<key>AMApplication</key>
+				<array>
+					<string>Automator</string>
+				</array>
+				<key>AMProvides</key>
+				<dict>
+					<key>Container</key>
+					<string>List</string>
+					<key>Types</key>
+					<array>
+						<string>com.apple.cocoa.string</string>
+					</array>
+				</dict>
+				<key>ActionBundlePath</key>
+				<string>/System/Library/Automator/Run Shell Script.action</string>
+				<key>ActionName</key>
+				<string>Run Shell Script</string>
+				<key>ActionParameters</key>
+				<dict>
+					<key>COMMAND_STRING</key>
+					<string>{command}</string>
+					<key>CheckedForUserDefaultShell</key>
+					<true/>
+					<key>inputMethod</key>
+					<integer>0</integer>
+					<key>shell</key>
+					<string>/bin/sh</string>
+					<key>source</key>
+					<string></string>
+				</dict>
+				<key>BundleIdentifier</key>
+				<string>com.apple.RunShellScript</string>
+				<key>CFBundleVersion</key>
+				<string>2.0.3</string>
+				<key>CanShowSelectedItemsWhenRun</key>
+				<false/>
+				<key>CanShowWhenRun</key>
+				<true/>
+				<key>Category</key>
+				<array>
+					<string>AMCategoryUtilities</string>
+				</array>
+				<key>Class Name</key>
+				<string>RunShellScriptAction</string>
+				<key>InputUUID</key>
+				<string>6E4B3C1A-2F0D-4B7E-9C58-1D2A7F3E8B01</string>
+				<key>OutputUUID</key>
+				<string>0B9F2E47-5C3A-4D61-8E2B-7A4C9D1F6E02</string>
+				<key>UUID</key>
+				<string>A3D1F6C8-7E24-4B9A-B05D-2C8E6F1A9D03</string>
+				<key>UnlocalizedApplications</key>
+				<array>
+					<string>Automator</string>
+				</array>
+				<key>isViewVisible</key>
+				<integer>1</integer>
+			</dict>
+		</dict>
+	</array>
+	<key>connectors</key>
+	<dict/>
+	<key>workflowMetaData</key>
+	<dict>
+		<key>serviceInputTypeIdentifier</key>
+		<string>com.apple.Automator.text</string>
+		<key>serviceOutputTypeIdentifier</key>
+		<string>com.apple.Automator.nothing</string>
+		<key>serviceProcessesInput</key>
+		<integer>0</integer>
+		<key>workflowTypeIdentifier</key>
+		<string>com.apple.Automator.servicesMenu</string>
+	</dict>
+</dict>
+</plist>
+"#;
+
+#[cfg(target_os = "windows")]
+fn entry_path() -> Result<PathBuf, String> {
+    let roaming = dirs::data_dir().ok_or("Failed to find the AppData folder")?;
+    Ok(roaming.join("Microsoft\\Windows\\SendTo").join(format!("{ENTRY_NAME}.lnk")))
+}
+
+/// Create a Send To shortcut that passes the chosen files to `read --files`.
+#[cfg(target_os = "windows")]
+fn install(entry: &Path, exe: &Path) -> Result<(), String> {
+    // Single quotes are doubled inside PowerShell string literals
+    let quote = |path: &Path| format!("'{}'", path.display().to_string().replace('\'', "''"));
+    let script = format!(
+        "$s = (New-Object -ComObject WScript.Shell).CreateShortcut({}); $s.TargetPath = {}; $s.Arguments = 'read --files'; $s.Save()",
+        quote(entry),
+        quote(exe),
+    );
+    let status = std::process::Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", &script])
+        .status()
+        .map_err(|e| format!("Failed to run PowerShell: {e}"))?;
+    if !status.success() {
+        return Err(format!("PowerShell failed to create {}", entry.display()));
+    }
+    Ok(())
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn entry_path() -> Result<PathBuf, String> {
+    let data = dirs::data_dir().ok_or("Failed to find the data folder")?;
+    Ok(data.join("nautilus/scripts").join(ENTRY_NAME))
+}
+
+/// Write a Nautilus script that passes the chosen files to `read --files`.
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn install(entry: &Path, exe: &Path) -> Result<(), String> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let script = format!(
+        "#!/bin/sh\n# Added by `insight-reader integrate`\nexec {} read --files \"$@\"\n",
+        shell_quote(exe)
+    );
+    std::fs::write(entry, script).map_err(|e| format!("Failed to write {}: {e}", entry.display()))?;
+    std::fs::set_permissions(entry, std::fs::Permissions::from_mode(0o755))
+        .map_err(|e| format!("Failed to make {} executable: {e}", entry.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quoting() {
+        assert_eq!(shell_quote(Path::new("/opt/Insight Reader/insight-reader")), "'/opt/Insight Reader/insight-reader'");
+        assert_eq!(shell_quote(Path::new("/home/o'neil/bin/ir")), r"'/home/o'\''neil/bin/ir'");
+        assert_eq!(xml_escape("'/Apps/R&D' read -"), "'/Apps/R&amp;D' read -");
+    }
+}
@@ -0,0 +1,139 @@
+//! `insight-reader lexicon`: manage AWS Polly pronunciation lexicons.
+
+use std::path::PathBuf;
+
+use super::Options;
+use crate::config;
+use crate::lexicon::{is_valid_polly_name, lexicon_path, load_lexicon, to_pls, MAX_POLLY_LEXICONS, POLLY_LEXICON_NAME};
+use crate::voices::aws;
+
+const USAGE: &str = "\
+Usage: insight-reader lexicon [list | sync | upload <file> | delete <name> | attach <name> | detach <name>] [options]
+
+Without a subcommand, lists the lexicons stored in AWS Polly. Attached
+lexicons (marked *) are applied to every Polly reading.
+
+Subcommands:
+  list                   List the Polly lexicons in the current region
+  sync                   Upload the local pronunciation lexicon and attach it
+      --name <NAME>          Polly lexicon name (default: InsightReader)
+      --language <CODE>      Lexicon language (default: the selected Polly voice's, else en-US)
+  upload <file>          Upload a PLS lexicon file and attach it
+      --name <NAME>          Polly lexicon name (default: the file name)
+  delete <name>          Delete a lexicon from Polly and detach it
+  attach <name>          Apply a Polly lexicon to readings (at most 5)
+  detach <name>          Stop applying a Polly lexicon
+
+Lexicon names are 1 to 20 letters and digits. Polly applies a lexicon only
+to voices of its language.";
+
+pub fn run(args: &[String]) -> Result<(), String> {
+    let options = Options::parse(args, &["name", "language"])?;
+    if options.flag("help") {
+        println!("{USAGE}");
+        return Ok(());
+    }
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| format!("Failed to create tokio runtime: {e}"))?;
+    let argument = |what: &str| {
+        options
+            .positional
+            .get(1)
+            .cloned()
+            .ok_or_else(|| format!("{} needs a {what}\n\n{USAGE}", options.positional[0]))
+    };
+
+    match options.positional.first().map(String::as_str) {
+        None | Some("list") => {
+            let attached = config::load_polly_lexicons();
+            let lexicons = runtime.block_on(aws::list_polly_lexicons())?;
+            if lexicons.is_empty() {
+                println!("No lexicons in AWS Polly");
+            }
+            for lexicon in lexicons {
+                let mark = if attached.contains(&lexicon.name) { "*" } else { " " };
+                println!("{mark} {:<20}  {:<6}  {} words", lexicon.name, lexicon.language, lexicon.lexemes);
+            }
+        }
+        Some("sync") => {
+            let name = options.value("name").unwrap_or(POLLY_LEXICON_NAME);
+            check_name(name)?;
+            let lexicon = load_lexicon();
+            if lexicon.is_empty() {
+                return Err(format!("The pronunciation lexicon ({}) is empty", lexicon_path().display()));
+            }
+            let language = options.value("language").map(str::to_string).unwrap_or_else(selected_voice_language);
+            runtime.block_on(aws::upload_polly_lexicon(name, &to_pls(&lexicon, &language)))?;
+            eprintln!("Uploaded {} words as '{name}' ({language})", lexicon.len());
+            attach(name)?;
+        }
+        Some("upload") => {
+            let file = PathBuf::from(argument("PLS file")?);
+            let name = options
+                .value("name")
+                .map(str::to_string)
+                .or_else(|| file.file_stem().map(|stem| stem.to_string_lossy().to_string()))
+                .unwrap_or_default();
+            check_name(&name)?;
+            let pls = std::fs::read_to_string(&file).map_err(|e| format!("Failed to read {}: {e}", file.display()))?;
+            runtime.block_on(aws::upload_polly_lexicon(&name, &pls))?;
+            eprintln!("Uploaded '{name}'");
+            attach(&name)?;
+        }
+        Some("delete") => {
+            let name = argument("lexicon name")?;
+            runtime.block_on(aws::delete_polly_lexicon(&name))?;
+            eprintln!("Deleted '{name}'");
+            detach(&name);
+        }
+        Some("attach") => attach(&argument("lexicon name")?)?,
+        Some("detach") => detach(&argument("lexicon name")?),
+        Some(other) => return Err(format!("unknown lexicon command '{other}'\n\n{USAGE}")),
+    }
+    Ok(())
+}
+
+fn check_name(name: &str) -> Result<(), String> {
+    if is_valid_polly_name(name) {
+        Ok(())
+    } else {
+        Err(format!("'{name}' is not a valid lexicon name: use 1 to 20 letters and digits (--name)"))
+    }
+}
+
+/// Language code ("en-US") of the selected Polly voice, from the cached voice list.
+fn selected_voice_language() -> String {
+    config::load_selected_polly_voice()
+        .zip(aws::load_cached_polly_voices())
+        .and_then(|(key, voices)| voices.get(&key).map(|voice| voice.language.code.replace('_', "-")))
+        .unwrap_or_else(|| "en-US".to_string())
+}
+
+fn attach(name: &str) -> Result<(), String> {
+    let mut names = config::load_polly_lexicons();
+    if names.iter().any(|attached| attached == name) {
+        return Ok(());
+    }
+    if names.len() >= MAX_POLLY_LEXICONS {
+        return Err(format!(
+            "Polly applies at most {MAX_POLLY_LEXICONS} lexicons; detach one first ({})",
+            names.join(", ")
+        ));
+    }
+    names.push(name.to_string());
+    config::save_polly_lexicons(&names);
+    eprintln!("Attached '{name}' to Polly readings");
+    Ok(())
+}
+
+fn detach(name: &str) {
+    let mut names = config::load_polly_lexicons();
+    let before = names.len();
+    names.retain(|attached| attached != name);
+    if names.len() != before {
+        config::save_polly_lexicons(&names);
+        eprintln!("Detached '{name}'");
+    }
+}
@@ -0,0 +1,319 @@
+//! `insight-reader listen`: read aloud lines written to a named pipe.
+//!
+//! A reader thread keeps the FIFO open, reopening it each time a writer
+//! closes it, and queues every non-empty line. The main thread speaks the
+//! queued lines one after another, so `echo "text" > /tmp/reader.fifo` from a
+//! shell script or editor never has to wait for the speech to finish.
+//!
+//! With `--editor`, each line is a JSON request instead, read from the FIFO
+//! or from stdin, and progress events are written to stdout as JSON lines so
+//! editor plugins (Neovim jobs, Emacs processes) can highlight the sentence
+//! being read:
+//!
+//! ```text
+//! -> {"id": "buf3", "text": "First sentence. Second one."}
+//! <- {"event":"started","id":"buf3","sentences":2}
+//! <- {"event":"sentence","id":"buf3","index":0,"start":0,"end":15}
+//! <- {"event":"sentence","id":"buf3","index":1,"start":16,"end":27}
+//! <- {"event":"finished","id":"buf3"}
+//! ```
+//!
+//! Instead of `text`, a request can carry `segments`, each with the editor's
+//! own `marker` ID, which is echoed back in the segment's `sentence` event.
+
+use std::io::Write;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{create_provider, provider_option, Options};
+use crate::providers::TTSProvider;
+use crate::text::timing::sentence_spans;
+
+/// How often playback is polled for the end of a line.
+const PLAYBACK_POLL: Duration = Duration::from_millis(100);
+
+const USAGE: &str = "\
+Usage: insight-reader listen --fifo <PATH> [options]
+       insight-reader listen --editor [--fifo <PATH>] [options]
+
+Read aloud each line written to a named pipe, in order.
+
+Options:
+  --fifo <PATH>          Named pipe to read from (created if missing)
+  --editor               Take JSON requests (from the pipe, or stdin without --fifo)
+                         and write sentence progress events to stdout
+  --voice <VOICE>        Voice key (Piper model, or Polly voice like Joanna:Neural)
+  --provider <NAME>      piper, polly, elevenlabs, macos, windows or espeak (default: provider from settings)
+  -h, --help             Show this message
+
+Example:
+  insight-reader listen --fifo /tmp/reader.fifo &
+  echo \"Build finished\" > /tmp/reader.fifo
+
+Editor requests are {\"id\": ..., \"text\": ...} or {\"id\": ..., \"segments\": [{\"marker\": ..., \"text\": ...}]};
+events are started, sentence (index, byte range or marker), finished and error.
+
+Named pipes are only available on Linux and macOS.";
+
+/// A reading request from an editor plugin.
+#[derive(Debug, Deserialize)]
+struct EditorRequest {
+    /// Request ID echoed back in every event
+    #[serde(default)]
+    id: Value,
+    /// Text split into sentences by the reader
+    #[serde(default)]
+    text: String,
+    /// Text already split by the editor, read instead of `text` when present
+    #[serde(default)]
+    segments: Vec<Segment>,
+}
+
+/// A piece of text tagged with an editor marker (extmark, Emacs marker...).
+#[derive(Debug, Deserialize)]
+struct Segment {
+    #[serde(default)]
+    marker: Value,
+    text: String,
+}
+
+/// Progress event written to stdout in editor mode.
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+enum EditorEvent {
+    Started {
+        id: Value,
+        sentences: usize,
+    },
+    Sentence {
+        id: Value,
+        index: usize,
+        /// Byte offsets within the request `text`
+        #[serde(skip_serializing_if = "Option::is_none")]
+        start: Option<usize>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        end: Option<usize>,
+        #[serde(skip_serializing_if = "Value::is_null")]
+        marker: Value,
+    },
+    Finished {
+        id: Value,
+    },
+    Error {
+        id: Value,
+        message: String,
+    },
+}
+
+/// One sentence or segment of an editor request, spoken on its own.
+#[derive(Debug, PartialEq)]
+struct Utterance {
+    text: String,
+    range: Option<Range<usize>>,
+    marker: Value,
+}
+
+pub fn run(args: &[String]) -> Result<(), String> {
+    let options = Options::parse(args, &["fifo", "voice", "provider"])?;
+    if options.flag("help") {
+        println!("{USAGE}");
+        return Ok(());
+    }
+    let editor = options.flag("editor");
+    let fifo = options.value("fifo").map(PathBuf::from);
+    if fifo.is_none() && !editor {
+        return Err(format!("--fifo is required\n\n{USAGE}"));
+    }
+    let backend = provider_option(&options)?;
+    let voice = options.value("voice").map(str::to_string);
+
+    if let Some(fifo) = &fifo {
+        create_fifo(fifo)?;
+    }
+    let mut provider = create_provider(backend, voice)?;
+
+    let (sender, receiver) = mpsc::channel();
+    let reader_path = fifo.clone();
+    std::thread::spawn(move || {
+        let result = match &reader_path {
+            Some(path) => read_fifo(path, &sender),
+            None => read_lines(std::io::stdin().lock(), &sender),
+        };
+        if let Err(e) = result {
+            eprintln!("error: {e}");
+        }
+    });
+    let source = fifo.as_ref().map_or("stdin".to_string(), |fifo| fifo.display().to_string());
+    eprintln!("Listening on {source} (Ctrl+C to stop)");
+
+    // Ends when the reader thread gives up and drops the sender
+    for line in receiver {
+        if editor {
+            read_request(provider.as_mut(), &line);
+        } else if let Err(e) = speak_to_end(provider.as_mut(), &line) {
+            eprintln!("{e}");
+        }
+    }
+    match fifo {
+        Some(fifo) => Err(format!("stopped reading {}", fifo.display())),
+        None => Ok(()),
+    }
+}
+
+/// Speak `text` and wait until playback has finished.
+fn speak_to_end(provider: &mut dyn TTSProvider, text: &str) -> Result<(), String> {
+    provider.speak(text).map_err(|e| e.to_string())?;
+    while provider.is_playing() {
+        std::thread::sleep(PLAYBACK_POLL);
+    }
+    Ok(())
+}
+
+/// Read one editor request sentence by sentence, emitting progress events.
+fn read_request(provider: &mut dyn TTSProvider, line: &str) {
+    let request: EditorRequest = match serde_json::from_str(line) {
+        Ok(request) => request,
+        Err(e) => {
+            emit(&EditorEvent::Error { id: Value::Null, message: format!("invalid request: {e}") });
+            return;
+        }
+    };
+    let id = request.id.clone();
+    let utterances = utterances(request);
+    emit(&EditorEvent::Started { id: id.clone(), sentences: utterances.len() });
+    for (index, utterance) in utterances.into_iter().enumerate() {
+        emit(&EditorEvent::Sentence {
+            id: id.clone(),
+            index,
+            start: utterance.range.as_ref().map(|range| range.start),
+            end: utterance.range.as_ref().map(|range| range.end),
+            marker: utterance.marker,
+        });
+        if let Err(message) = speak_to_end(provider, &utterance.text) {
+            emit(&EditorEvent::Error { id, message });
+            return;
+        }
+    }
+    emit(&EditorEvent::Finished { id });
+}
+
+/// The pieces of a request spoken one at a time: its segments, or the sentences of its text.
+fn utterances(request: EditorRequest) -> Vec<Utterance> {
+    if !request.segments.is_empty() {
+        return request
+            .segments
+            .into_iter()
+            .filter(|segment| !segment.text.trim().is_empty())
+            .map(|segment| Utterance { text: segment.text, range: None, marker: segment.marker })
+            .collect();
+    }
+    sentence_spans(&request.text)
+        .into_iter()
+        .map(|range| Utterance { text: request.text[range.clone()].to_string(), range: Some(range), marker: Value::Null })
+        .collect()
+}
+
+/// Write an event to stdout as one JSON line.
+fn emit(event: &EditorEvent) {
+    let Ok(json) = serde_json::to_string(event) else {
+        return;
+    };
+    let mut stdout = std::io::stdout().lock();
+    let _ = writeln!(stdout, "{json}");
+    let _ = stdout.flush();
+}
+
+/// Queue the non-empty lines of `input` until it ends.
+fn read_lines(input: impl std::io::BufRead, sender: &mpsc::Sender<String>) -> Result<(), String> {
+    for line in input.lines() {
+        let line = line.map_err(|e| format!("Failed to read input: {e}"))?;
+        if let Some(text) = queued_text(&line) {
+            if sender.send(text).is_err() {
+                break;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Queue the lines written to the FIFO, reopening it after each writer closes it.
+#[cfg(unix)]
+fn read_fifo(path: &Path, sender: &mpsc::Sender<String>) -> Result<(), String> {
+    loop {
+        // Blocks until a writer opens the pipe
+        let file = std::fs::File::open(path).map_err(|e| format!("Failed to open {}: {e}", path.display()))?;
+        read_lines(std::io::BufReader::new(file), sender)?;
+    }
+}
+
+#[cfg(not(unix))]
+fn read_fifo(_path: &Path, _sender: &mpsc::Sender<String>) -> Result<(), String> {
+    Err("named pipes are not supported on this platform".to_string())
+}
+
+/// Create the FIFO at `path` unless one is already there.
+#[cfg(unix)]
+fn create_fifo(path: &Path) -> Result<(), String> {
+    use std::os::unix::fs::FileTypeExt;
+
+    match std::fs::metadata(path) {
+        Ok(metadata) if metadata.file_type().is_fifo() => return Ok(()),
+        Ok(_) => return Err(format!("{} exists and is not a named pipe", path.display())),
+        Err(_) => {}
+    }
+    let status = std::process::Command::new("mkfifo")
+        .arg(path)
+        .status()
+        .map_err(|e| format!("Failed to run mkfifo: {e}"))?;
+    if !status.success() {
+        return Err(format!("mkfifo failed to create {}", path.display()));
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn create_fifo(_path: &Path) -> Result<(), String> {
+    Err("named pipes are not supported on this platform".to_string())
+}
+
+/// Text to read for one line of input, `None` for blank lines.
+fn queued_text(line: &str) -> Option<String> {
+    let text = line.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_queued_text() {
+        assert_eq!(queued_text("  Build finished\r"), Some("Build finished".to_string()));
+        assert_eq!(queued_text(" \t "), None);
+        assert_eq!(queued_text(""), None);
+    }
+
+    #[test]
+    fn test_utterances() {
+        let request: EditorRequest = serde_json::from_str(r#"{"id": 3, "text": "First sentence. Second one!"}"#).unwrap();
+        let spoken = utterances(request);
+        assert_eq!(spoken.len(), 2);
+        assert_eq!(spoken[1].text, "Second one!");
+        assert_eq!(spoken[1].range, Some(16..27));
+
+        let request: EditorRequest = serde_json::from_str(
+            r#"{"id": "buf", "segments": [{"marker": 12, "text": "Title"}, {"marker": 13, "text": " "}, {"marker": "m14", "text": "Body."}]}"#,
+        )
+        .unwrap();
+        let markers: Vec<Value> = utterances(request).into_iter().map(|utterance| utterance.marker).collect();
+        assert_eq!(markers, vec![Value::from(12), Value::from("m14")]);
+
+        let event = EditorEvent::Sentence { id: Value::from("buf"), index: 0, start: None, end: None, marker: Value::from(12) };
+        assert_eq!(serde_json::to_string(&event).unwrap(), r#"{"event":"sentence","id":"buf","index":0,"marker":12}"#);
+    }
+}
@@ -0,0 +1,251 @@
+//! Command-line subcommands (`insight-reader <command> ...`).
+//!
+//! Without a recognised subcommand the GUI starts as usual; with one, the
+//! command runs headless and the process exits with its status code.
+
+mod audiobook;
+mod feed;
+mod history;
+mod integrate;
+mod lexicon;
+mod listen;
+mod ocr;
+mod read;
+mod rpc;
+mod speak;
+mod text;
+
+use std::path::PathBuf;
+
+use crate::config;
+use crate::model::TTSBackend;
+use crate::providers::{macos_provider, windows_provider, ElevenLabsTTSProvider, EspeakTTSProvider, PiperTTSProvider, PollyTTSProvider, TTSProvider};
+use crate::system::{find_age, parse_recipients};
+
+/// Subcommands handled without starting the GUI.
+const COMMANDS: &[&str] = &["audiobook", "feed", "history", "integrate", "lexicon", "listen", "ocr-batch", "read", "rpc", "speak", "text", "help", "--help", "-h"];
+
+/// Return true if the arguments name a CLI subcommand.
+pub fn is_command(args: &[String]) -> bool {
+    args.get(1)
+        .is_some_and(|arg| COMMANDS.contains(&arg.as_str()))
+}
+
+/// Run the subcommand and return the process exit code.
+pub fn run(args: &[String]) -> i32 {
+    attach_console();
+
+    let rest = &args[2..];
+    let result = match args[1].as_str() {
+        "audiobook" => audiobook::run(rest),
+        "feed" => feed::run(rest),
+        "history" => history::run(rest),
+        "integrate" => integrate::run(rest),
+        "lexicon" => lexicon::run(rest),
+        "listen" => listen::run(rest),
+        "ocr-batch" => ocr::run(rest),
+        "read" => read::run(rest),
+        "rpc" => rpc::run(rest),
+        "speak" => speak::run(rest),
+        "text" => text::run(rest),
+        _ => {
+            print_usage();
+            Ok(())
+        }
+    };
+    match result {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("error: {e}");
+            1
+        }
+    }
+}
+
+fn print_usage() {
+    println!("Usage: insight-reader [COMMAND]");
+    println!();
+    println!("Without a command, starts the Insight Reader tray app.");
+    println!();
+    println!("Commands:");
+    println!("  audiobook   Export a book (EPUB, text, Markdown, HTML) as chaptered audio");
+    println!("  feed        Manage and serve the podcast feed of exported readings");
+    println!("  history     Export the reading history, optionally encrypted");
+    println!("  integrate   Add \"Read with Insight Reader\" to the system menus");
+    println!("  lexicon     Upload and attach AWS Polly pronunciation lexicons");
+    println!("  listen      Read aloud lines written to a named pipe");
+    println!("  ocr-batch   Extract the text of every image in a folder");
+    println!("  read        Read text or files in the running app");
+    println!("  rpc         Serve JSON-RPC over stdio for embedding in other apps");
+    println!("  speak       Read text aloud, or show each processing stage with --dry-run");
+    println!("  text        Preview what would be spoken for a text");
+    println!("  help        Show this message");
+    println!();
+    println!("Run `insight-reader <command> --help` for command options.");
+}
+
+/// Parsed `--flag value` options and positional arguments.
+#[derive(Debug, Default)]
+pub(crate) struct Options {
+    pub positional: Vec<String>,
+    pub values: Vec<(String, String)>,
+    pub flags: Vec<String>,
+}
+
+impl Options {
+    /// Parse arguments; `value_options` lists the options that take a value.
+    pub fn parse(args: &[String], value_options: &[&str]) -> Result<Self, String> {
+        let mut options = Options::default();
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            if let Some(name) = arg.strip_prefix("--") {
+                if let Some((name, value)) = name.split_once('=') {
+                    options.values.push((name.to_string(), value.to_string()));
+                } else if value_options.contains(&name) {
+                    let value = iter
+                        .next()
+                        .ok_or_else(|| format!("--{name} requires a value"))?;
+                    options.values.push((name.to_string(), value.clone()));
+                } else {
+                    options.flags.push(name.to_string());
+                }
+            } else if arg == "-h" {
+                options.flags.push("help".to_string());
+            } else {
+                options.positional.push(arg.clone());
+            }
+        }
+        Ok(options)
+    }
+
+    /// Last value given for an option.
+    pub fn value(&self, name: &str) -> Option<&str> {
+        self.values
+            .iter()
+            .rev()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    pub fn flag(&self, name: &str) -> bool {
+        self.flags.iter().any(|f| f == name)
+    }
+
+    /// Every value given for an option, in order.
+    pub fn all_values(&self, name: &str) -> Vec<&str> {
+        self.values
+            .iter()
+            .filter(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+            .collect()
+    }
+}
+
+/// Backend named by a `--provider` value.
+pub(crate) fn parse_provider(name: &str) -> Result<TTSBackend, String> {
+    config::backend_from_str(name)
+        .ok_or_else(|| format!("unknown provider '{name}' (expected piper, polly, elevenlabs, macos, windows or espeak)"))
+}
+
+/// Backend given with `--provider`, or else the one from the settings.
+pub(crate) fn provider_option(options: &Options) -> Result<TTSBackend, String> {
+    options
+        .value("provider")
+        .map_or_else(|| Ok(config::load_voice_provider()), parse_provider)
+}
+
+/// Voice selected in the settings for `backend`.
+pub(crate) fn selected_voice(backend: TTSBackend) -> Option<String> {
+    match backend {
+        TTSBackend::Piper => config::load_selected_voice(),
+        TTSBackend::AwsPolly => config::load_selected_polly_voice(),
+        TTSBackend::ElevenLabs => config::load_selected_elevenlabs_voice(),
+        TTSBackend::MacOS => config::load_selected_macos_voice(),
+        TTSBackend::Windows => config::load_selected_windows_voice(),
+        TTSBackend::Espeak => None,
+    }
+}
+
+/// Create a provider for `backend` speaking with `voice`, or with the voice
+/// selected in the settings.
+pub(crate) fn create_provider(backend: TTSBackend, voice: Option<String>) -> Result<Box<dyn TTSProvider>, String> {
+    Ok(match backend {
+        TTSBackend::Piper => match voice.as_deref() {
+            Some(voice) => Box::new(PiperTTSProvider::with_voice(voice).map_err(|e| e.to_string())?),
+            None => Box::new(PiperTTSProvider::new().map_err(|e| e.to_string())?),
+        },
+        TTSBackend::AwsPolly => {
+            PollyTTSProvider::check_credentials()?;
+            let voice = voice.or_else(config::load_selected_polly_voice);
+            Box::new(PollyTTSProvider::new(voice).map_err(|e| e.to_string())?)
+        }
+        TTSBackend::ElevenLabs => {
+            let voice = voice.or_else(config::load_selected_elevenlabs_voice);
+            Box::new(ElevenLabsTTSProvider::new(voice).map_err(|e| e.to_string())?)
+        }
+        TTSBackend::MacOS => {
+            let voice = voice.or_else(config::load_selected_macos_voice);
+            macos_provider(voice).map_err(|e| e.to_string())?
+        }
+        TTSBackend::Windows => {
+            let voice = voice.or_else(config::load_selected_windows_voice);
+            windows_provider(voice).map_err(|e| e.to_string())?
+        }
+        TTSBackend::Espeak => Box::new(EspeakTTSProvider::new().map_err(|e| e.to_string())?),
+    })
+}
+
+/// age and the recipients to encrypt an export to, with `--encrypt`: every
+/// `--recipient` given, or else the ones from the privacy settings.
+pub(crate) fn encryption(options: &Options) -> Result<Option<(PathBuf, Vec<String>)>, String> {
+    if !options.flag("encrypt") {
+        return Ok(None);
+    }
+    let mut recipients: Vec<String> = options.all_values("recipient").into_iter().map(str::to_string).collect();
+    if recipients.is_empty() {
+        recipients = parse_recipients(&config::load_export_recipients());
+    }
+    if recipients.is_empty() {
+        return Err("--encrypt needs an age recipient: pass --recipient or set one under Privacy in the settings".into());
+    }
+    let age = find_age().ok_or("age not found on PATH (needed for --encrypt)")?;
+    Ok(Some((age, recipients)))
+}
+
+/// Attach to the parent console so output is visible from a terminal.
+///
+/// Release builds use the Windows GUI subsystem, which has no console.
+#[cfg(target_os = "windows")]
+fn attach_console() {
+    use windows::Win32::System::Console::{AttachConsole, ATTACH_PARENT_PROCESS};
+    unsafe {
+        let _ = AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn attach_console() {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_options_parse() {
+        let parsed = Options::parse(
+            &args(&["book.epub", "--voice", "en_US-amy", "--format=m4b", "--restart", "--recipient", "age1a", "--recipient=age1b"]),
+            &["voice", "format", "recipient"],
+        )
+        .unwrap();
+        assert_eq!(parsed.positional, vec!["book.epub"]);
+        assert_eq!(parsed.value("voice"), Some("en_US-amy"));
+        assert_eq!(parsed.value("format"), Some("m4b"));
+        assert!(parsed.flag("restart"));
+        assert_eq!(parsed.all_values("recipient"), vec!["age1a", "age1b"]);
+        assert!(Options::parse(&args(&["--voice"]), &["voice"]).is_err());
+    }
+}
@@ -0,0 +1,194 @@
+//! `insight-reader ocr-batch`: extract the text of every image in a folder.
+//!
+//! Images are handed out to a few worker threads, each running the same OCR
+//! backend as screenshots. Text is written next to each image's name in the
+//! output folder as soon as it is ready, or into one combined file at the end.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+
+use super::Options;
+use crate::system::{extract_text_lines, lines_text};
+
+/// File extensions treated as images.
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "bmp", "gif", "tif", "tiff", "webp"];
+
+/// Name of the combined output file inside the output folder.
+const COMBINED_FILE: &str = "combined.txt";
+
+/// Width of the progress bar, in characters.
+const PROGRESS_WIDTH: usize = 30;
+
+const USAGE: &str = "\
+Usage: insight-reader ocr-batch <folder> [options]
+
+Extract the text of every image (PNG, JPEG, BMP, GIF, TIFF, WebP) in a folder.
+
+Options:
+  --out <DIR>            Output directory (default: <folder>/text)
+  --jobs <N>             Images processed in parallel (default: half the CPU cores)
+  --combined             Write all text to one combined.txt, with a heading per image
+  -h, --help             Show this message
+
+Uses the same OCR as screenshots: EasyOCR on Linux, Vision on macOS, Windows OCR on Windows.";
+
+pub fn run(args: &[String]) -> Result<(), String> {
+    let options = Options::parse(args, &["out", "jobs"])?;
+    if options.flag("help") {
+        println!("{USAGE}");
+        return Ok(());
+    }
+    let folder = match options.positional.as_slice() {
+        [folder] => PathBuf::from(folder),
+        _ => return Err(format!("expected one input folder\n\n{USAGE}")),
+    };
+    let jobs = match options.value("jobs") {
+        Some(jobs) => jobs
+            .parse::<usize>()
+            .ok()
+            .filter(|&jobs| jobs > 0)
+            .ok_or_else(|| format!("invalid --jobs '{jobs}' (expected a positive number)"))?,
+        None => std::thread::available_parallelism().map_or(1, |n| (n.get() / 2).max(1)),
+    };
+    let combined = options.flag("combined");
+
+    let images = list_images(&folder)?;
+    if images.is_empty() {
+        return Err(format!("no images found in {}", folder.display()));
+    }
+    let out_dir = options
+        .value("out")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| folder.join("text"));
+    std::fs::create_dir_all(&out_dir)
+        .map_err(|e| format!("Failed to create {}: {e}", out_dir.display()))?;
+
+    let total = images.len();
+    let jobs = jobs.min(total);
+    eprintln!("{total} image(s), {jobs} worker(s), {}", out_dir.display());
+
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    let mut texts: Vec<Option<String>> = vec![None; total];
+    let mut failed = 0;
+    std::thread::scope(|scope| {
+        for _ in 0..jobs {
+            let sender = sender.clone();
+            let (next, images) = (&next, &images);
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(image) = images.get(index) else {
+                    break;
+                };
+                let result = extract_text_lines(&image.to_string_lossy()).map(|lines| lines_text(&lines));
+                if sender.send((index, result)).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+
+        for (done, (index, result)) in receiver.iter().enumerate() {
+            let name = file_name(&images[index]);
+            match result {
+                Ok(text) => {
+                    if !combined {
+                        let text_path = out_dir.join(Path::new(&name).with_extension("txt"));
+                        if let Err(e) = std::fs::write(&text_path, format!("{text}\n")) {
+                            eprintln!("\r{name}: failed to write {}: {e}", text_path.display());
+                            failed += 1;
+                        }
+                    }
+                    texts[index] = Some(text);
+                }
+                // An image without text is not a failure
+                Err(e) if e.contains("No text found") => {}
+                Err(e) => {
+                    eprintln!("\r{name}: {e}");
+                    failed += 1;
+                }
+            }
+            eprint!("\r{} {}/{total} {name}", progress_bar(done + 1, total, PROGRESS_WIDTH), done + 1);
+            let _ = std::io::stderr().flush();
+        }
+    });
+    eprintln!();
+
+    if combined {
+        let combined_path = out_dir.join(COMBINED_FILE);
+        std::fs::write(&combined_path, combine(&images, &texts))
+            .map_err(|e| format!("Failed to write {}: {e}", combined_path.display()))?;
+    }
+
+    let with_text = texts.iter().flatten().count();
+    eprintln!("Done: text in {with_text} of {total} image(s), {}", out_dir.display());
+    if failed > 0 {
+        return Err(format!("{failed} image(s) failed"));
+    }
+    Ok(())
+}
+
+/// Images directly inside `folder`, sorted by name.
+fn list_images(folder: &Path) -> Result<Vec<PathBuf>, String> {
+    let entries = std::fs::read_dir(folder).map_err(|e| format!("Failed to read {}: {e}", folder.display()))?;
+    let mut images: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && is_image(path))
+        .collect();
+    images.sort();
+    Ok(images)
+}
+
+fn is_image(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| IMAGE_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// Text of all images in order, each under a `# <file name>` heading; images without text are left out.
+fn combine(images: &[PathBuf], texts: &[Option<String>]) -> String {
+    images
+        .iter()
+        .zip(texts)
+        .filter_map(|(image, text)| Some(format!("# {}\n\n{}\n", file_name(image), text.as_ref()?)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// `[#####-----]` bar for `done` of `total` items.
+fn progress_bar(done: usize, total: usize, width: usize) -> String {
+    let filled = (done * width).checked_div(total).unwrap_or(width).min(width);
+    format!("[{}{}]", "#".repeat(filled), "-".repeat(width - filled))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_image() {
+        assert!(is_image(Path::new("scans/page 1.PNG")));
+        assert!(is_image(Path::new("receipt.jpeg")));
+        assert!(!is_image(Path::new("notes.txt")));
+        assert!(!is_image(Path::new("png")));
+    }
+
+    #[test]
+    fn test_progress_and_combine() {
+        assert_eq!(progress_bar(1, 4, 8), "[##------]");
+        assert_eq!(progress_bar(4, 4, 8), "[########]");
+
+        let images = [PathBuf::from("a.png"), PathBuf::from("b.png"), PathBuf::from("c.png")];
+        let texts = [Some("First".to_string()), None, Some("Third\nline".to_string())];
+        assert_eq!(combine(&images, &texts), "# a.png\n\nFirst\n\n# c.png\n\nThird\nline\n");
+    }
+}
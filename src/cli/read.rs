@@ -0,0 +1,61 @@
+//! `insight-reader read`: read text or files in the running app.
+//!
+//! This is what the context menu entries installed by `integrate` run. The
+//! reading is handed to the running instance, which is started if needed, so
+//! it plays with the usual controls and settings.
+
+use std::io::Read;
+use std::path::PathBuf;
+
+use super::Options;
+use crate::system::{forward_reading, ForwardedReading};
+
+const USAGE: &str = "\
+Usage: insight-reader read [TEXT...]
+       insight-reader read --files <PATH>...
+
+Read text in the running Insight Reader, starting it if needed.
+Without TEXT (or with -), the text is read from stdin.
+
+Options:
+  --files                Read the given documents (text, Markdown, HTML, EPUB...) instead
+  -h, --help             Show this message
+
+Example:
+  echo \"Build finished\" | insight-reader read";
+
+pub fn run(args: &[String]) -> Result<(), String> {
+    let options = Options::parse(args, &[])?;
+    if options.flag("help") {
+        println!("{USAGE}");
+        return Ok(());
+    }
+
+    let reading = if options.flag("files") {
+        if options.positional.is_empty() {
+            return Err(format!("--files needs at least one path\n\n{USAGE}"));
+        }
+        // The running app has its own working directory
+        let files = options
+            .positional
+            .iter()
+            .map(|path| std::path::absolute(path).map_err(|e| format!("Invalid path {path}: {e}")))
+            .collect::<Result<Vec<PathBuf>, String>>()?;
+        ForwardedReading::Files(files)
+    } else {
+        let text = if options.positional.is_empty() || options.positional == ["-"] {
+            let mut text = String::new();
+            std::io::stdin()
+                .read_to_string(&mut text)
+                .map_err(|e| format!("Failed to read stdin: {e}"))?;
+            text
+        } else {
+            options.positional.join(" ")
+        };
+        if text.trim().is_empty() {
+            return Err("nothing to read".to_string());
+        }
+        ForwardedReading::Text(text)
+    };
+    forward_reading(reading)
+}
@@ -0,0 +1,441 @@
+//! `insight-reader rpc`: JSON-RPC 2.0 over stdio for embedding the reader.
+//!
+//! Other desktop apps start the reader as a child process and talk to it
+//! with one JSON object per line: requests on stdin, responses on stdout.
+//! The methods are `speak`, `stop`, `status`, `listVoices` and `setVoice`.
+//! When a reading plays to its end, a `finished` notification is written:
+//!
+//! ```text
+//! -> {"jsonrpc": "2.0", "id": 1, "method": "speak", "params": {"text": "Hello there."}}
+//! <- {"jsonrpc":"2.0","id":1,"result":null}
+//! <- {"jsonrpc":"2.0","method":"finished","params":{}}
+//! ```
+//!
+//! The methods, their parameters and the shape of their results are a stable
+//! interface: changes that break clients bump [`PROTOCOL_VERSION`], which
+//! `status` reports.
+
+use std::io::Write;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::{create_provider, parse_provider, provider_option, selected_voice, Options};
+use crate::config;
+use crate::model::TTSBackend;
+use crate::providers::TTSProvider;
+use crate::voices::{aws, download, elevenlabs, system};
+
+/// Version of the protocol, reported by `status`.
+const PROTOCOL_VERSION: u32 = 1;
+
+/// How often playback is polled for the end of a reading.
+const PLAYBACK_POLL: Duration = Duration::from_millis(100);
+
+/// The line is not valid JSON.
+const PARSE_ERROR: i64 = -32700;
+/// The JSON is not a JSON-RPC 2.0 request.
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+/// The provider failed: no voice, no credentials, no audio device...
+const SPEECH_ERROR: i64 = -32000;
+
+const USAGE: &str = "\
+Usage: insight-reader rpc [options]
+
+Serve JSON-RPC 2.0 on stdin and stdout, one message per line, until stdin closes.
+
+Options:
+  --voice <VOICE>        Voice key to start with (default: voice from settings)
+  --provider <NAME>      piper, polly, elevenlabs, macos, windows or espeak (default: provider from settings)
+  -h, --help             Show this message
+
+Methods:
+  speak {\"text\": ...}                    Read text aloud, replacing any reading in progress
+  stop                                   Stop reading
+  status                                 State, provider, voice, position and duration
+  listVoices {\"provider\": ...}           Voices of a provider (default: the current one)
+  setVoice {\"provider\": ..., \"voice\": ...} Switch provider and/or voice
+
+A `finished` notification is sent when a reading plays to its end.";
+
+/// A JSON-RPC request or notification.
+#[derive(Debug, Deserialize)]
+struct Request {
+    #[serde(default)]
+    jsonrpc: String,
+    method: String,
+    #[serde(default)]
+    params: Value,
+    /// Absent for notifications, which get no response
+    #[serde(default)]
+    id: Option<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpeakParams {
+    text: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct VoiceParams {
+    #[serde(default)]
+    provider: Option<String>,
+    #[serde(default)]
+    voice: Option<String>,
+}
+
+/// A request with its parameters checked.
+#[derive(Debug, PartialEq)]
+enum Call {
+    Speak { text: String },
+    Stop,
+    Status,
+    ListVoices { provider: Option<TTSBackend> },
+    SetVoice { provider: Option<TTSBackend>, voice: Option<String> },
+}
+
+/// Error object of a failed request.
+#[derive(Debug, PartialEq, Serialize)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self { code, message: message.into() }
+    }
+}
+
+/// A voice in a `listVoices` result.
+#[derive(Debug, Serialize)]
+struct VoiceEntry {
+    /// Key to pass to `setVoice`
+    id: String,
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    language: Option<String>,
+}
+
+/// The provider and voice requests are served with.
+struct Session {
+    backend: TTSBackend,
+    /// Voice given with `--voice` or `setVoice`; the selected one otherwise
+    voice: Option<String>,
+    /// Created on first use, so `status` and `listVoices` work without audio
+    provider: Option<Box<dyn TTSProvider>>,
+    /// A reading was started and its end not reported yet
+    reading: bool,
+}
+
+pub fn run(args: &[String]) -> Result<(), String> {
+    let options = Options::parse(args, &["voice", "provider"])?;
+    if options.flag("help") {
+        println!("{USAGE}");
+        return Ok(());
+    }
+    let mut session = Session {
+        backend: provider_option(&options)?,
+        voice: options.value("voice").map(str::to_string),
+        provider: None,
+        reading: false,
+    };
+
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        for line in std::io::stdin().lines() {
+            let Ok(line) = line else { break };
+            if !line.trim().is_empty() && sender.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    loop {
+        match receiver.recv_timeout(PLAYBACK_POLL) {
+            Ok(line) => {
+                if let Some(response) = session.respond(&line) {
+                    emit(&response);
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            // stdin closed: the embedding app is done with us
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+        if session.finished() {
+            emit(&json!({"jsonrpc": "2.0", "method": "finished", "params": {}}));
+        }
+    }
+    if let Some(provider) = &mut session.provider {
+        let _ = provider.stop();
+    }
+    Ok(())
+}
+
+/// Check the version, method and parameters of a request.
+fn parse_call(request: &Request) -> Result<Call, RpcError> {
+    if request.jsonrpc != "2.0" {
+        return Err(RpcError::new(INVALID_REQUEST, "expected \"jsonrpc\": \"2.0\""));
+    }
+    let invalid_params = |e: serde_json::Error| RpcError::new(INVALID_PARAMS, format!("invalid params: {e}"));
+    let voice_params = || -> Result<VoiceParams, RpcError> {
+        if request.params.is_null() {
+            return Ok(VoiceParams::default());
+        }
+        serde_json::from_value(request.params.clone()).map_err(invalid_params)
+    };
+    let provider = |name: Option<String>| {
+        name.as_deref()
+            .map(parse_provider)
+            .transpose()
+            .map_err(|e| RpcError::new(INVALID_PARAMS, e))
+    };
+    match request.method.as_str() {
+        "speak" => {
+            let params: SpeakParams = serde_json::from_value(request.params.clone()).map_err(invalid_params)?;
+            if params.text.trim().is_empty() {
+                return Err(RpcError::new(INVALID_PARAMS, "text is empty"));
+            }
+            Ok(Call::Speak { text: params.text })
+        }
+        "stop" => Ok(Call::Stop),
+        "status" => Ok(Call::Status),
+        "listVoices" => Ok(Call::ListVoices { provider: provider(voice_params()?.provider)? }),
+        "setVoice" => {
+            let params = voice_params()?;
+            if params.provider.is_none() && params.voice.is_none() {
+                return Err(RpcError::new(INVALID_PARAMS, "expected a provider or a voice"));
+            }
+            Ok(Call::SetVoice { provider: provider(params.provider)?, voice: params.voice })
+        }
+        other => Err(RpcError::new(METHOD_NOT_FOUND, format!("unknown method '{other}'"))),
+    }
+}
+
+/// The response to a request with `id`.
+fn response(id: Value, outcome: Result<Value, RpcError>) -> Value {
+    match outcome {
+        Ok(result) => json!({"jsonrpc": "2.0", "id": id, "result": result}),
+        Err(error) => json!({"jsonrpc": "2.0", "id": id, "error": error}),
+    }
+}
+
+impl Session {
+    /// Serve one line of input; `None` for notifications.
+    fn respond(&mut self, line: &str) -> Option<Value> {
+        let request: Request = match serde_json::from_str(line) {
+            Ok(request) => request,
+            Err(e) => {
+                let code = if serde_json::from_str::<Value>(line).is_ok() { INVALID_REQUEST } else { PARSE_ERROR };
+                return Some(response(Value::Null, Err(RpcError::new(code, e.to_string()))));
+            }
+        };
+        let outcome = parse_call(&request).and_then(|call| self.call(call));
+        request.id.map(|id| response(id, outcome))
+    }
+
+    fn call(&mut self, call: Call) -> Result<Value, RpcError> {
+        match call {
+            Call::Speak { text } => {
+                self.provider()?.speak(&text).map_err(speech_error)?;
+                self.reading = true;
+                Ok(Value::Null)
+            }
+            Call::Stop => {
+                if let Some(provider) = &mut self.provider {
+                    provider.stop().map_err(speech_error)?;
+                }
+                self.reading = false;
+                Ok(Value::Null)
+            }
+            Call::Status => Ok(self.status()),
+            Call::ListVoices { provider } => {
+                let voices = list_voices(provider.unwrap_or(self.backend)).map_err(speech_error)?;
+                Ok(json!(voices))
+            }
+            Call::SetVoice { provider, voice } => {
+                let backend = provider.unwrap_or(self.backend);
+                // A new provider starts with its own selected voice
+                let voice = voice.or_else(|| self.voice.clone().filter(|_| backend == self.backend));
+                let new_provider = create_provider(backend, voice.clone()).map_err(speech_error)?;
+                if let Some(provider) = &mut self.provider {
+                    let _ = provider.stop();
+                }
+                self.provider = Some(new_provider);
+                self.backend = backend;
+                self.voice = voice;
+                self.reading = false;
+                Ok(Value::Null)
+            }
+        }
+    }
+
+    /// The provider, created on first use.
+    fn provider(&mut self) -> Result<&mut dyn TTSProvider, RpcError> {
+        if self.provider.is_none() {
+            self.provider = Some(create_provider(self.backend, self.voice.clone()).map_err(speech_error)?);
+        }
+        Ok(self.provider.as_deref_mut().expect("provider was just created"))
+    }
+
+    fn status(&self) -> Value {
+        let (state, position, duration) = match &self.provider {
+            Some(provider) => {
+                let state = if provider.is_paused() {
+                    "paused"
+                } else if provider.is_playing() {
+                    "speaking"
+                } else {
+                    "idle"
+                };
+                let duration = provider.duration().as_secs_f32();
+                (state, provider.get_progress() * duration, duration)
+            }
+            None => ("idle", 0.0, 0.0),
+        };
+        json!({
+            "protocol": PROTOCOL_VERSION,
+            "state": state,
+            "provider": config::backend_to_str(self.backend),
+            "voice": self.voice.clone().or_else(|| selected_voice(self.backend)),
+            "position": position,
+            "duration": duration,
+        })
+    }
+
+    /// Whether the reading in progress has just played to its end.
+    fn finished(&mut self) -> bool {
+        let done = self
+            .provider
+            .as_ref()
+            .is_some_and(|provider| !provider.is_playing() && !provider.is_paused());
+        if self.reading && done {
+            self.reading = false;
+            return true;
+        }
+        false
+    }
+}
+
+fn speech_error(e: impl ToString) -> RpcError {
+    RpcError::new(SPEECH_ERROR, e.to_string())
+}
+
+/// Voices `backend` can speak with.
+fn list_voices(backend: TTSBackend) -> Result<Vec<VoiceEntry>, String> {
+    let runtime = || {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| format!("Failed to create tokio runtime: {e}"))
+    };
+    let system_voices = |voices: Vec<system::SystemVoice>| {
+        voices
+            .into_iter()
+            .map(|voice| VoiceEntry { id: voice.id, name: voice.name, language: Some(voice.language) })
+            .collect()
+    };
+    Ok(match backend {
+        TTSBackend::Piper => {
+            let mut keys = download::installed_voice_keys();
+            keys.sort();
+            keys.into_iter().map(piper_voice).collect()
+        }
+        TTSBackend::AwsPolly => {
+            let voices = match aws::load_cached_polly_voices() {
+                Some(voices) => voices,
+                None => runtime()?.block_on(aws::fetch_polly_voices())?,
+            };
+            let mut voices: Vec<VoiceEntry> = voices
+                .into_iter()
+                .map(|(key, info)| VoiceEntry {
+                    name: format!("{} ({})", info.name, info.engine),
+                    id: key,
+                    language: Some(info.language.code),
+                })
+                .collect();
+            voices.sort_by(|a, b| a.id.cmp(&b.id));
+            voices
+        }
+        TTSBackend::ElevenLabs => {
+            runtime()?
+                .block_on(elevenlabs::fetch_elevenlabs_voices(config::load_elevenlabs_api_key()))?
+                .into_iter()
+                .map(|voice| VoiceEntry { id: voice.id, name: voice.name, language: None })
+                .collect()
+        }
+        TTSBackend::MacOS => system_voices(system::list_macos_voices()?),
+        TTSBackend::Windows => system_voices(system::list_windows_voices()?),
+        // The basic voice speaks the language of the user interface
+        TTSBackend::Espeak => Vec::new(),
+    })
+}
+
+/// An installed Piper voice ("en_US-lessac-medium").
+fn piper_voice(key: String) -> VoiceEntry {
+    let mut parts = key.splitn(3, '-');
+    let language = parts.next().map(str::to_string);
+    let name = parts.next().unwrap_or(&key).to_string();
+    VoiceEntry { id: key, name, language }
+}
+
+/// Write a message to stdout as one JSON line.
+fn emit(message: &Value) {
+    let mut stdout = std::io::stdout().lock();
+    let _ = writeln!(stdout, "{message}");
+    let _ = stdout.flush();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(json: &str) -> Request {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_parse_call() {
+        assert_eq!(
+            parse_call(&request(r#"{"jsonrpc": "2.0", "id": 1, "method": "speak", "params": {"text": "Hi."}}"#)),
+            Ok(Call::Speak { text: "Hi.".to_string() })
+        );
+        assert_eq!(
+            parse_call(&request(r#"{"jsonrpc": "2.0", "id": 2, "method": "setVoice", "params": {"provider": "polly", "voice": "Joanna:Neural"}}"#)),
+            Ok(Call::SetVoice { provider: Some(TTSBackend::AwsPolly), voice: Some("Joanna:Neural".to_string()) })
+        );
+        assert_eq!(
+            parse_call(&request(r#"{"jsonrpc": "2.0", "method": "listVoices"}"#)),
+            Ok(Call::ListVoices { provider: None })
+        );
+        let code = |json| parse_call(&request(json)).unwrap_err().code;
+        assert_eq!(code(r#"{"id": 3, "method": "stop"}"#), INVALID_REQUEST);
+        assert_eq!(code(r#"{"jsonrpc": "2.0", "id": 4, "method": "sing"}"#), METHOD_NOT_FOUND);
+        assert_eq!(code(r#"{"jsonrpc": "2.0", "id": 5, "method": "speak", "params": {}}"#), INVALID_PARAMS);
+        assert_eq!(code(r#"{"jsonrpc": "2.0", "id": 6, "method": "listVoices", "params": {"provider": "sam"}}"#), INVALID_PARAMS);
+    }
+
+    #[test]
+    fn test_respond() {
+        let mut session = Session { backend: TTSBackend::Piper, voice: Some("en_US-amy-low".to_string()), provider: None, reading: false };
+        let status = session.respond(r#"{"jsonrpc": "2.0", "id": "s", "method": "status"}"#).unwrap();
+        assert_eq!(status["id"], "s");
+        assert_eq!(status["result"]["state"], "idle");
+        assert_eq!(status["result"]["provider"], "piper");
+        assert_eq!(status["result"]["voice"], "en_US-amy-low");
+
+        assert_eq!(session.respond(r#"{"jsonrpc": "2.0", "method": "stop"}"#), None);
+        let stopped = session.respond(r#"{"jsonrpc": "2.0", "id": 7, "method": "stop"}"#).unwrap();
+        assert_eq!(stopped, json!({"jsonrpc": "2.0", "id": 7, "result": null}));
+        assert_eq!(session.respond("not json").unwrap()["error"]["code"], PARSE_ERROR);
+        assert_eq!(session.respond("[1, 2]").unwrap()["error"]["code"], INVALID_REQUEST);
+        assert!(!session.finished());
+
+        let voice = piper_voice("en_US-lessac-medium".to_string());
+        assert_eq!((voice.name.as_str(), voice.language.as_deref()), ("lessac", Some("en_US")));
+    }
+}
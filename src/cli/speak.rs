@@ -0,0 +1,114 @@
+//! `insight-reader speak`: read a text aloud from the terminal, or save it as
+//! audio with `--output`.
+//!
+//! With `--dry-run`, nothing is synthesized: each stage of the processing
+//! chain is printed instead, from the raw input to what the provider would be
+//! sent for each chunk, to find out why a text reads strangely.
+
+use std::io::Read;
+use std::path::Path;
+use std::time::Duration;
+
+use super::{create_provider, provider_option, selected_voice, Options};
+use crate::providers::samples_to_wav;
+use crate::system::{export_audio, AudioTags};
+use crate::text::pipeline::{self, Rules, Trace};
+use crate::text::title_from_text;
+
+/// How often playback is polled for the end of the reading.
+const PLAYBACK_POLL: Duration = Duration::from_millis(100);
+
+const USAGE: &str = "\
+Usage: insight-reader speak [TEXT...] [options]
+
+Read TEXT aloud, or stdin when no TEXT is given, with the Spoken Text settings.
+
+Options:
+  --output <FILE>        Save the speech to FILE instead of playing it (.wav, .mp3 or .ogg;
+                         MP3 and Ogg need ffmpeg)
+  --dry-run              Print the output of each stage instead of speaking:
+                         raw input, cleanup, pacing, segmentation and provider payload
+  --voice <VOICE>        Voice key (Piper model, or Polly voice like Joanna:Neural)
+  --provider <NAME>      piper, polly, elevenlabs, macos, windows or espeak (default: provider from settings)
+  -h, --help             Show this message
+
+Natural Reading and plugins are not applied. Pauses are shown as [pause].";
+
+pub fn run(args: &[String]) -> Result<(), String> {
+    let options = Options::parse(args, &["voice", "provider", "output"])?;
+    if options.flag("help") {
+        println!("{USAGE}");
+        return Ok(());
+    }
+    let backend = provider_option(&options)?;
+    let input = if options.positional.is_empty() {
+        let mut input = String::new();
+        std::io::stdin()
+            .read_to_string(&mut input)
+            .map_err(|e| format!("Failed to read stdin: {e}"))?;
+        input
+    } else {
+        options.positional.join(" ")
+    };
+    if input.trim().is_empty() {
+        return Err("nothing to speak".into());
+    }
+
+    let rules = Rules::from_config();
+    if options.flag("dry-run") {
+        print!("{}", format_trace(&pipeline::trace(&input, &rules, backend)));
+        return Ok(());
+    }
+    let voice = options.value("voice").map(str::to_string);
+    let mut provider = create_provider(backend, voice.clone())?;
+    let title = title_from_text(&input).unwrap_or_default();
+    let spoken = pipeline::spoken_text(input, &rules);
+    if let Some(output) = options.value("output") {
+        let samples = provider.synthesize(&spoken).map_err(|e| e.to_string())?;
+        let tags = AudioTags {
+            title,
+            artist: voice.or_else(|| selected_voice(backend)),
+            ..AudioTags::default()
+        };
+        export_audio(&samples_to_wav(&samples, provider.sample_rate()), Path::new(output), &tags)?;
+        eprintln!("Saved {output}");
+        return Ok(());
+    }
+    provider.speak(&spoken).map_err(|e| e.to_string())?;
+    while provider.is_playing() {
+        std::thread::sleep(PLAYBACK_POLL);
+    }
+    Ok(())
+}
+
+/// The stages of `trace` as text, chunks numbered.
+fn format_trace(trace: &Trace) -> String {
+    let mut out = String::new();
+    for stage in &trace.stages {
+        out.push_str(&format!("== {} ==\n", stage.name));
+        if stage.output.len() == 1 {
+            out.push_str(&format!("{}\n\n", stage.output[0]));
+            continue;
+        }
+        for (index, chunk) in stage.output.iter().enumerate() {
+            out.push_str(&format!("[{}] {chunk}\n", index + 1));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::TTSBackend;
+
+    #[test]
+    fn test_format_trace() {
+        let trace = pipeline::trace("Hello there. How are you?", &Rules::default(), TTSBackend::Piper);
+        let out = format_trace(&trace);
+        assert!(out.starts_with("== Raw capture ==\nHello there. How are you?\n\n"), "{out}");
+        assert!(out.contains("== Segmentation ==\n[1] Hello there.\n[2] How are you?\n"), "{out}");
+        assert!(out.ends_with("== Provider payload (piper) ==\n[1] Hello there.\n[2] How are you?\n\n"), "{out}");
+    }
+}
@@ -0,0 +1,186 @@
+//! `insight-reader text preview`: print what would be spoken for a text.
+//!
+//! Runs the text passes of [`crate::text::pipeline`] (and the Markdown or
+//! HTML conversion for those formats) without synthesizing anything, so the
+//! effect of the Spoken Text settings can be checked from a terminal. The
+//! golden-file tests below run the same preview over `tests/golden`.
+
+use std::io::Read;
+use std::path::Path;
+
+use super::Options;
+use crate::config;
+use crate::text::html::{html_to_speech, SpeechOptions};
+use crate::text::markdown::markdown_to_text;
+use crate::text::pipeline::{show_pauses, spoken_text, Rules};
+
+const USAGE: &str = "\
+Usage: insight-reader text preview [FILE] [options]
+
+Print what would be spoken for a text, without synthesizing it. Reads FILE,
+or stdin when no FILE (or -) is given.
+
+Options:
+  --format <FORMAT>            text, markdown or html (default: from the file extension, else text)
+  --profile <PROFILE>          auto, plain, chat or email (default: from settings)
+  --math, --no-math            Read math aloud (default: from settings)
+  --lists, --no-lists          Pace lists with pauses and spoken ordinals (default: from settings)
+  --alt-text, --no-alt-text    Read the alt text of HTML images (default: from settings)
+  -h, --help                   Show this message
+
+Pauses are shown as [pause]. Natural Reading and plugins are not applied.";
+
+/// How the input is converted to text before the passes run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Text,
+    Markdown,
+    Html,
+}
+
+/// Everything a preview depends on besides the input.
+#[derive(Debug, Clone, Copy)]
+struct Preview {
+    format: Format,
+    rules: Rules,
+    speech: SpeechOptions,
+}
+
+pub fn run(args: &[String]) -> Result<(), String> {
+    let options = Options::parse(args, &["format", "profile"])?;
+    if options.flag("help") || options.positional.first().map(String::as_str) != Some("preview") {
+        println!("{USAGE}");
+        return Ok(());
+    }
+    let path = options.positional.get(1).filter(|path| path.as_str() != "-").map(Path::new);
+    let defaults = Preview {
+        format: path.map_or(Format::Text, format_of),
+        rules: Rules::from_config(),
+        speech: SpeechOptions {
+            read_alt_text: config::load_read_alt_text(),
+        },
+    };
+    let preview = preview_options(&options, defaults)?;
+    let input = match path {
+        Some(path) => std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {e}", path.display()))?,
+        None => {
+            let mut input = String::new();
+            std::io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|e| format!("Failed to read stdin: {e}"))?;
+            input
+        }
+    };
+    println!("{}", preview_text(&input, &preview));
+    Ok(())
+}
+
+/// Format guessed from a file extension.
+fn format_of(path: &Path) -> Format {
+    match path.extension().and_then(|ext| ext.to_str()).map(str::to_ascii_lowercase).as_deref() {
+        Some("md" | "markdown") => Format::Markdown,
+        Some("html" | "htm") => Format::Html,
+        _ => Format::Text,
+    }
+}
+
+/// `--name` as true, `--no-name` as false, neither as None.
+fn switch(options: &Options, name: &str) -> Option<bool> {
+    if options.flag(&format!("no-{name}")) {
+        Some(false)
+    } else if options.flag(name) {
+        Some(true)
+    } else {
+        None
+    }
+}
+
+/// `defaults` with the options given applied.
+fn preview_options(options: &Options, defaults: Preview) -> Result<Preview, String> {
+    let mut preview = defaults;
+    if let Some(format) = options.value("format") {
+        preview.format = match format {
+            "text" => Format::Text,
+            "markdown" | "md" => Format::Markdown,
+            "html" => Format::Html,
+            other => return Err(format!("unknown format '{other}' (expected text, markdown or html)")),
+        };
+    }
+    if let Some(profile) = options.value("profile") {
+        preview.rules.profile = config::text_profile_from_str(profile)
+            .ok_or_else(|| format!("unknown profile '{profile}' (expected auto, plain, chat or email)"))?;
+    }
+    preview.rules.read_math = switch(options, "math").unwrap_or(preview.rules.read_math);
+    preview.rules.list_prosody = switch(options, "lists").unwrap_or(preview.rules.list_prosody);
+    preview.speech.read_alt_text = switch(options, "alt-text").unwrap_or(preview.speech.read_alt_text);
+    Ok(preview)
+}
+
+/// What would be spoken for `input`, pauses written out.
+fn preview_text(input: &str, preview: &Preview) -> String {
+    let text = match preview.format {
+        Format::Text => input.to_string(),
+        Format::Markdown => markdown_to_text(input),
+        Format::Html => html_to_speech(input, preview.speech),
+    };
+    show_pauses(&spoken_text(text, &preview.rules))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Run every rule set in `tests/golden`: `input.txt` previewed with the
+    /// options in `rules.txt` (over the default settings) must give
+    /// `expected.txt`. With `UPDATE_GOLDEN=1`, mismatches rewrite `expected.txt`.
+    #[test]
+    fn test_golden_files() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("golden");
+        let update = std::env::var_os("UPDATE_GOLDEN").is_some();
+        let mut sets: Vec<_> = std::fs::read_dir(&root)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.is_dir())
+            .collect();
+        sets.sort();
+        assert!(!sets.is_empty(), "no rule sets in {}", root.display());
+
+        let mut failures = Vec::new();
+        for set in sets {
+            let read = |name: &str| std::fs::read_to_string(set.join(name));
+            let args: Vec<String> = read("rules.txt").unwrap_or_default().split_whitespace().map(str::to_string).collect();
+            let defaults = Preview {
+                format: Format::Text,
+                rules: Rules::default(),
+                speech: SpeechOptions::default(),
+            };
+            let preview = preview_options(&Options::parse(&args, &["format", "profile"]).unwrap(), defaults).unwrap();
+            let actual = preview_text(&read("input.txt").unwrap(), &preview);
+            let expected = read("expected.txt").unwrap_or_default();
+            if actual.trim_end() != expected.trim_end() {
+                if update {
+                    std::fs::write(set.join("expected.txt"), format!("{}\n", actual.trim_end())).unwrap();
+                }
+                failures.push(format!("{}:\n--- expected\n{expected}\n--- actual\n{actual}", set.display()));
+            }
+        }
+        assert!(failures.is_empty() || update, "golden files differ:\n\n{}", failures.join("\n\n"));
+    }
+
+    #[test]
+    fn test_preview_options() {
+        let args: Vec<String> = ["--profile", "plain", "--no-lists", "--alt-text"].iter().map(|s| s.to_string()).collect();
+        let defaults = Preview {
+            format: format_of(Path::new("notes.MD")),
+            rules: Rules::default(),
+            speech: SpeechOptions::default(),
+        };
+        let preview = preview_options(&Options::parse(&args, &["format", "profile"]).unwrap(), defaults).unwrap();
+        assert_eq!(preview.format, Format::Markdown);
+        assert_eq!(preview.rules.profile, crate::model::TextProfile::Plain);
+        assert!(preview.rules.read_math && !preview.rules.list_prosody && preview.speech.read_alt_text);
+
+        let bad: Vec<String> = vec!["--format".to_string(), "pdf".to_string()];
+        assert!(preview_options(&Options::parse(&bad, &["format"]).unwrap(), defaults).is_err());
+    }
+}
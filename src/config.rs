@@ -3,19 +3,41 @@
 //! Persists the selected voice provider and log level in a simple JSON file:
 //! `~/.config/insight-reader/config.json` with fields like:
 //! `{ "voice_provider": "piper", "log_level": "INFO" }`.
+//!
+//! Files written by older versions are upgraded on load, see [`migrations`].
+
+mod migrations;
 
+use std::collections::BTreeMap;
 use std::fs;
 use std::io;
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 
 use dirs::config_dir;
-use tracing::{debug, error, warn};
+use tracing::{debug, error, info, warn};
 
-use crate::model::{LogLevel, OCRBackend, TTSBackend};
+use crate::capture::SourcePriority;
+use crate::model::{
+    ColorPreset, CustomColors, DuplicateCaptureAction, LogLevel, LongTextAction, OCRBackend, ReadingFont, ReadingStyle,
+    ReadingTint, TTSBackend, TextProfile,
+};
+use crate::providers::{EarconSet, EarconSettings, ExecutionProvider, VisualizerStyle};
+use crate::storage::CleanupSchedule;
+use crate::system::{parse_recipients, FeedConfig, PowerPolicy, ProcessPriority};
 
 const APP_CONFIG_DIR_NAME: &str = "insight-reader";
 const CONFIG_FILE_NAME: &str = "config.json";
 
+/// Default number of paragraphs read by "Read first N paragraphs".
+pub const DEFAULT_LONG_TEXT_PARAGRAPHS: usize = 5;
+
+/// Most paragraphs "Read first N paragraphs" can be set to in the settings.
+pub const MAX_LONG_TEXT_PARAGRAPHS: usize = 20;
+
+/// Default seconds without typing before a reading paused for typing resumes.
+pub const DEFAULT_TYPING_RESUME_SECS: u32 = 3;
+
 #[derive(Debug)]
 pub enum ConfigError {
     Io(io::Error),
@@ -43,9 +65,13 @@ impl From<serde_json::Error> for ConfigError {
     }
 }
 
-#[derive(Debug, serde::Serialize, serde::Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, Default)]
 struct RawConfig {
-    /// Voice provider name ("piper" or "polly").
+    /// Schema version the file was written with (see [`migrations`]).
+    #[serde(default)]
+    config_version: Option<u32>,
+
+    /// Voice provider name ("piper", "polly", "elevenlabs", "macos" or "windows").
     #[serde(default)]
     voice_provider: Option<String>,
 
@@ -63,6 +89,18 @@ struct RawConfig {
     /// Selected AWS Polly voice ID (e.g., "Matthew", "Joanna").
     #[serde(default)]
     selected_polly_voice: Option<String>,
+    /// Selected ElevenLabs voice ID (e.g., "21m00Tcm4TlvDq8ikWAM").
+    #[serde(default)]
+    selected_elevenlabs_voice: Option<String>,
+    /// ElevenLabs API key; kept on this machine, never synced.
+    #[serde(default)]
+    elevenlabs_api_key: Option<String>,
+    /// Selected macOS system voice identifier (e.g., "com.apple.voice.compact.en-US.Samantha").
+    #[serde(default)]
+    selected_macos_voice: Option<String>,
+    /// Selected Windows system voice identifier.
+    #[serde(default)]
+    selected_windows_voice: Option<String>,
 
     /// OCR backend name ("default" or "better_ocr").
     #[serde(default)]
@@ -79,13 +117,327 @@ struct RawConfig {
     /// Hotkey key code (e.g., "r", "t", "space").
     #[serde(default)]
     hotkey_key: Option<String>,
+
+    /// Default action for very long captures ("ask", "read_first" or "read_all").
+    #[serde(default)]
+    long_text_action: Option<String>,
+
+    /// Number of paragraphs read by "Read first N paragraphs".
+    #[serde(default)]
+    long_text_paragraphs: Option<usize>,
+
+    /// Whether short captures interrupt a long reading, which then resumes.
+    #[serde(default)]
+    interrupt_readings: Option<bool>,
+
+    /// Action when the same text is captured twice in a row ("replay" or "ignore").
+    #[serde(default)]
+    duplicate_capture_action: Option<String>,
+
+    /// Order the hotkey tries capture sources in ("selection_first", "clipboard_first" or "ask").
+    #[serde(default)]
+    source_priority: Option<String>,
+
+    /// Whether exports are added to the local podcast feed (served on the LAN).
+    #[serde(default)]
+    podcast_feed_enabled: Option<bool>,
+
+    /// Podcast feed folder (defaults to the app data directory).
+    #[serde(default)]
+    podcast_feed_dir: Option<String>,
+
+    /// Podcast feed title.
+    #[serde(default)]
+    podcast_feed_title: Option<String>,
+
+    /// Podcast feed author.
+    #[serde(default)]
+    podcast_feed_author: Option<String>,
+
+    /// Podcast feed description.
+    #[serde(default)]
+    podcast_feed_description: Option<String>,
+
+    /// Port of the podcast feed server.
+    #[serde(default)]
+    podcast_feed_port: Option<u16>,
+
+    /// Base URL used in the feed instead of the detected LAN address.
+    #[serde(default)]
+    podcast_feed_base_url: Option<String>,
+
+    /// Whether texts read aloud are kept in the reading history.
+    #[serde(default)]
+    history_enabled: Option<bool>,
+
+    /// Shared folder (Dropbox, Syncthing, ...) used to sync settings and history.
+    #[serde(default)]
+    sync_dir: Option<String>,
+
+    /// Folder whose new text files are read automatically.
+    #[serde(default)]
+    watch_dir: Option<String>,
+
+    /// Plugins (by file name, without extension) the user turned off.
+    #[serde(default)]
+    disabled_plugins: Option<Vec<String>>,
+
+    /// Language families ("pt") the user does not want voice downloads suggested for.
+    #[serde(default)]
+    ignored_voice_languages: Option<Vec<String>>,
+
+    /// Hardware Piper runs voices on ("cpu", "cuda", "coreml" or "directml").
+    #[serde(default)]
+    execution_provider: Option<String>,
+
+    /// Run Piper as a separate process (the `piper` binary) instead of in the app.
+    #[serde(default)]
+    piper_subprocess: Option<bool>,
+
+    /// AWS Polly lexicons (by name) applied to every Polly reading.
+    #[serde(default)]
+    polly_lexicons: Option<Vec<String>>,
+
+    /// How synthesis adapts to running on battery ("performance", "balanced" or "battery_saver").
+    #[serde(default)]
+    power_policy: Option<String>,
+
+    /// Threads (logical CPUs) Piper may use, 0 or unset for all of them.
+    #[serde(default)]
+    synthesis_threads: Option<u32>,
+
+    /// Priority of the Piper processes ("normal", "low" or "lowest").
+    #[serde(default)]
+    synthesis_priority: Option<String>,
+
+    /// Whether voice languages are listed by their own names ("Deutsch") rather than English ones.
+    #[serde(default)]
+    native_language_names: Option<bool>,
+
+    /// Voice languages browsed most recently, newest first (e.g. ["pt_BR", "en_US"]).
+    #[serde(default)]
+    recent_languages: Option<Vec<String>>,
+
+    /// Voice language last browsed with Piper (e.g. "pt_BR").
+    #[serde(default)]
+    last_piper_language: Option<String>,
+    /// Voice language last browsed with AWS Polly.
+    #[serde(default)]
+    last_polly_language: Option<String>,
+
+    /// Piper voices selected most recently, newest first.
+    #[serde(default)]
+    recent_piper_voices: Option<Vec<String>>,
+    /// AWS Polly voices selected most recently, newest first.
+    #[serde(default)]
+    recent_polly_voices: Option<Vec<String>>,
+    /// ElevenLabs voices selected most recently, newest first.
+    #[serde(default)]
+    recent_elevenlabs_voices: Option<Vec<String>>,
+    /// macOS system voices selected most recently, newest first.
+    #[serde(default)]
+    recent_macos_voices: Option<Vec<String>>,
+    /// Windows system voices selected most recently, newest first.
+    #[serde(default)]
+    recent_windows_voices: Option<Vec<String>>,
+
+    /// Multiplier of how far a scroll wheel notch on the main window moves volume, speed and position.
+    #[serde(default)]
+    scroll_sensitivity: Option<f32>,
+
+    /// Whether the panic stop hotkey (Ctrl + Alt + Period) is registered.
+    #[serde(default)]
+    panic_stop_hotkey: Option<bool>,
+
+    /// Whether the hotkey with Shift added is registered, to edit the text first.
+    #[serde(default)]
+    hotkey_edit_variant: Option<bool>,
+
+    /// Whether the hotkey with Alt added is registered, to read the clipboard image.
+    #[serde(default)]
+    hotkey_image_variant: Option<bool>,
+
+    /// Length of the fades on play, pause, stop and seek, in milliseconds.
+    #[serde(default)]
+    fade_ms: Option<u32>,
+
+    /// Pitch of the Piper and Polly voices, in semitones from their own.
+    #[serde(default)]
+    pitch_semitones: Option<i32>,
+
+    /// Playback speed multiplier of the main window (0.5 to 3.0).
+    #[serde(default)]
+    playback_speed: Option<f32>,
+
+    /// Speech volume of the main window (0.0 to 1.0).
+    #[serde(default)]
+    playback_volume: Option<f32>,
+
+    /// Color scheme ("default", "high_contrast", "warm" or "custom").
+    #[serde(default)]
+    color_preset: Option<String>,
+
+    /// Audio visualization on the main bar ("bars", "mirrored", "level" or "off").
+    #[serde(default)]
+    visualizer_style: Option<String>,
+
+    /// Custom accent color (`#rrggbb`), used by the "custom" scheme.
+    #[serde(default)]
+    accent_color: Option<String>,
+
+    /// Custom progress bar color (`#rrggbb`), used by the "custom" scheme.
+    #[serde(default)]
+    progress_color: Option<String>,
+
+    /// Custom waveform color (`#rrggbb`), used by the "custom" scheme.
+    #[serde(default)]
+    waveform_color: Option<String>,
+
+    /// Font of text shown for reading ("default", "open_dyslexic", "serif" or "monospace").
+    #[serde(default)]
+    reading_font: Option<String>,
+
+    /// Line height of text shown for reading, relative to the font size.
+    #[serde(default)]
+    reading_line_spacing: Option<f32>,
+
+    /// Background tint of text shown for reading ("none", "cream", "blue", "green" or "gray").
+    #[serde(default)]
+    reading_tint: Option<String>,
+
+    /// Whether the reading view follows the current sentence and dims other paragraphs.
+    #[serde(default)]
+    reading_focus_follow: Option<bool>,
+
+    /// How much the reading view dims paragraphs other than the current one (0.0 to 0.9).
+    #[serde(default)]
+    reading_focus_dim: Option<f32>,
+
+    /// Whether image alt text is read when the selection is HTML.
+    #[serde(default)]
+    read_alt_text: Option<bool>,
+
+    /// Whether math notation (LaTeX, exponents, symbols) is read as words.
+    #[serde(default)]
+    read_math: Option<bool>,
+
+    /// Whether list items are paced with pauses and spoken ordinals.
+    #[serde(default)]
+    list_prosody: Option<bool>,
+
+    /// Cleanup profile for captured text ("auto", "plain", "chat" or "email").
+    #[serde(default)]
+    text_profile: Option<String>,
+
+    /// Whether short audio cues play for reading events.
+    #[serde(default)]
+    earcons_enabled: Option<bool>,
+
+    /// Volume of audio cues (0.0 to 1.0).
+    #[serde(default)]
+    earcon_volume: Option<f32>,
+
+    /// Sound set used for audio cues ("chime", "beep" or "click").
+    #[serde(default)]
+    earcon_set: Option<String>,
+
+    /// Whether readings start paused unless headphones are the audio output.
+    #[serde(default)]
+    headphones_only: Option<bool>,
+
+    /// Whether hotkey readings wait while another app uses the microphone.
+    #[serde(default)]
+    defer_while_mic_active: Option<bool>,
+
+    /// Whether playback pauses while the user is typing.
+    #[serde(default)]
+    pause_while_typing: Option<bool>,
+
+    /// Seconds without typing before playback resumes.
+    #[serde(default)]
+    typing_resume_secs: Option<u32>,
+
+    /// Whether hotkey captures are previewed in a popup before reading.
+    #[serde(default)]
+    preview_before_reading: Option<bool>,
+
+    /// Whether selection captures open in the text editor before reading.
+    #[serde(default)]
+    edit_before_reading: Option<bool>,
+
+    /// Whether texts that look like passwords or tokens need confirmation before reading.
+    #[serde(default)]
+    sensitive_text_guard: Option<bool>,
+
+    /// Apps (names or window title words) whose captures are only read with Piper, never sent to cloud services.
+    #[serde(default)]
+    local_only_apps: Option<Vec<String>>,
+
+    /// Keep screenshots in memory for OCR instead of saving them to the temp dir.
+    #[serde(default)]
+    private_screenshots: Option<bool>,
+
+    /// age recipients (public keys or recipients files) exports are encrypted to.
+    #[serde(default)]
+    export_recipients: Option<Vec<String>>,
+
+    /// Keep the last captured texts (in memory) for the clipboard history window.
+    #[serde(default)]
+    clipboard_history: Option<bool>,
+
+    /// How often old caches, screenshots, logs and history are cleaned up ("off", "daily" or "weekly").
+    #[serde(default)]
+    cleanup_schedule: Option<String>,
+
+    /// Days of reading history kept by the cleanup job (0 keeps everything).
+    #[serde(default)]
+    history_max_days: Option<u32>,
+
+    /// Size limit of the synthesis cache in megabytes (0 turns the cache off).
+    #[serde(default)]
+    synthesis_cache_mb: Option<u32>,
+
+    /// Show a summary of the week's listening once a week.
+    #[serde(default)]
+    weekly_summary: Option<bool>,
+
+    /// Day of the weekly summary ("Mon" to "Sun").
+    #[serde(default)]
+    weekly_summary_day: Option<String>,
+
+    /// Hour of the weekly summary (0 to 23, local time).
+    #[serde(default)]
+    weekly_summary_hour: Option<u32>,
+
+    /// Last size and position of resizable windows, by window kind ("extracted_text", "screenshot_viewer").
+    #[serde(default)]
+    window_geometry: Option<BTreeMap<String, WindowGeometry>>,
 }
 
+/// Size and position of a window, in logical pixels.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct WindowGeometry {
+    pub width: f32,
+    pub height: f32,
+    /// Top-left corner, once the window has been moved
+    #[serde(default)]
+    pub position: Option<(f32, f32)>,
+}
+
+/// Changes staged while the settings window is open: the config as it was
+/// when staging started, and as it is with the changes.
+static STAGED: Mutex<Option<(RawConfig, RawConfig)>> = Mutex::new(None);
+
 fn config_path() -> Option<PathBuf> {
     let path = config_dir()?.join(APP_CONFIG_DIR_NAME).join(CONFIG_FILE_NAME);
     Some(path)
 }
 
+/// Location of the config file, for folder sync.
+pub(crate) fn config_file_path() -> Option<PathBuf> {
+    config_path()
+}
+
 fn ensure_config_dir_exists(path: &Path) -> io::Result<()> {
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)?;
@@ -94,6 +446,14 @@ fn ensure_config_dir_exists(path: &Path) -> io::Result<()> {
 }
 
 fn load_raw_config() -> Result<RawConfig, ConfigError> {
+    if let Some((_, staged)) = STAGED.lock().unwrap().as_ref() {
+        return Ok(staged.clone());
+    }
+    read_config_file()
+}
+
+/// Read the config file itself, ignoring staged changes.
+fn read_config_file() -> Result<RawConfig, ConfigError> {
     let Some(path) = config_path() else {
         // No config directory available on this platform; treat as empty config.
         debug!("No config_dir available, using defaults only");
@@ -106,7 +466,18 @@ fn load_raw_config() -> Result<RawConfig, ConfigError> {
     }
 
     let data = fs::read_to_string(&path)?;
-    let cfg = serde_json::from_str(&data)?;
+    let (cfg, upgraded_from) = migrations::parse(&data)?;
+    if let Some(version) = upgraded_from {
+        // Keep the original next to the upgraded file, once per version
+        let backup = path.with_extension(format!("json.v{version}.bak"));
+        if !backup.exists() {
+            if let Err(err) = fs::copy(&path, &backup) {
+                warn!(error = ?err, ?backup, "Failed to back up config before upgrading it");
+            }
+        }
+        info!(from = version, to = migrations::CURRENT_VERSION, "Config upgraded");
+        write_config_file(&path, &cfg)?;
+    }
     debug!(?path, "Config loaded");
     Ok(cfg)
 }
@@ -119,33 +490,87 @@ fn save_raw_config(mut cfg: RawConfig) -> Result<(), ConfigError> {
     };
 
     ensure_config_dir_exists(&path)?;
+    cfg.config_version = Some(migrations::CURRENT_VERSION);
     // Normalize by dropping empty strings if present.
     cfg.selected_polly_voice = cfg.selected_polly_voice.filter(|s| !s.is_empty());
+    cfg.selected_elevenlabs_voice = cfg.selected_elevenlabs_voice.filter(|s| !s.is_empty());
+    cfg.elevenlabs_api_key = cfg.elevenlabs_api_key.filter(|s| !s.trim().is_empty());
+    cfg.selected_macos_voice = cfg.selected_macos_voice.filter(|s| !s.is_empty());
+    cfg.selected_windows_voice = cfg.selected_windows_voice.filter(|s| !s.is_empty());
     cfg.voice_provider = cfg.voice_provider.filter(|s| !s.is_empty());
     cfg.log_level = cfg.log_level.filter(|s| !s.is_empty());
     cfg.selected_voice = cfg.selected_voice.filter(|s| !s.is_empty());
+    cfg.last_piper_language = cfg.last_piper_language.filter(|s| !s.is_empty());
+    cfg.last_polly_language = cfg.last_polly_language.filter(|s| !s.is_empty());
     cfg.ocr_backend = cfg.ocr_backend.filter(|s| !s.is_empty());
     cfg.hotkey_modifiers = cfg.hotkey_modifiers.filter(|s| !s.is_empty());
     cfg.hotkey_key = cfg.hotkey_key.filter(|s| !s.is_empty());
+    cfg.long_text_action = cfg.long_text_action.filter(|s| !s.is_empty());
+    cfg.duplicate_capture_action = cfg.duplicate_capture_action.filter(|s| !s.is_empty());
+    cfg.source_priority = cfg.source_priority.filter(|s| !s.is_empty());
+    cfg.text_profile = cfg.text_profile.filter(|s| !s.is_empty());
+    cfg.podcast_feed_dir = cfg.podcast_feed_dir.filter(|s| !s.is_empty());
+    cfg.podcast_feed_title = cfg.podcast_feed_title.filter(|s| !s.is_empty());
+    cfg.podcast_feed_author = cfg.podcast_feed_author.filter(|s| !s.is_empty());
+    cfg.podcast_feed_description = cfg.podcast_feed_description.filter(|s| !s.is_empty());
+    cfg.podcast_feed_base_url = cfg.podcast_feed_base_url.filter(|s| !s.is_empty());
+    cfg.sync_dir = cfg.sync_dir.filter(|s| !s.is_empty());
+    cfg.watch_dir = cfg.watch_dir.filter(|s| !s.is_empty());
+    cfg.disabled_plugins = cfg.disabled_plugins.filter(|names| !names.is_empty());
+    cfg.ignored_voice_languages = cfg.ignored_voice_languages.filter(|languages| !languages.is_empty());
+    cfg.execution_provider = cfg.execution_provider.filter(|s| !s.is_empty());
+    cfg.polly_lexicons = cfg.polly_lexicons.filter(|names| !names.is_empty());
+    cfg.power_policy = cfg.power_policy.filter(|s| !s.is_empty());
+    cfg.synthesis_priority = cfg.synthesis_priority.filter(|s| !s.is_empty());
+    cfg.color_preset = cfg.color_preset.filter(|s| !s.is_empty());
+    cfg.visualizer_style = cfg.visualizer_style.filter(|s| !s.is_empty());
+    cfg.accent_color = cfg.accent_color.filter(|s| !s.is_empty());
+    cfg.progress_color = cfg.progress_color.filter(|s| !s.is_empty());
+    cfg.waveform_color = cfg.waveform_color.filter(|s| !s.is_empty());
+    cfg.reading_font = cfg.reading_font.filter(|s| !s.is_empty());
+    cfg.reading_tint = cfg.reading_tint.filter(|s| !s.is_empty());
+    cfg.earcon_set = cfg.earcon_set.filter(|s| !s.is_empty());
+    cfg.local_only_apps = cfg.local_only_apps.filter(|apps| !apps.is_empty());
+    cfg.export_recipients = cfg.export_recipients.filter(|recipients| !recipients.is_empty());
+    cfg.cleanup_schedule = cfg.cleanup_schedule.filter(|s| !s.is_empty());
+    cfg.weekly_summary_day = cfg.weekly_summary_day.filter(|s| !s.is_empty());
+
+    if let Some((_, staged)) = STAGED.lock().unwrap().as_mut() {
+        *staged = cfg;
+        debug!("Config change staged");
+        return Ok(());
+    }
+
+    write_config_file(&path, &cfg)
+}
 
-    let data = serde_json::to_string_pretty(&cfg)?;
-    fs::write(&path, data)?;
+fn write_config_file(path: &Path, cfg: &RawConfig) -> Result<(), ConfigError> {
+    let data = serde_json::to_string_pretty(cfg)?;
+    fs::write(path, data)?;
     debug!(?path, "Config saved");
     Ok(())
 }
 
-fn backend_from_str(s: &str) -> Option<TTSBackend> {
+pub(crate) fn backend_from_str(s: &str) -> Option<TTSBackend> {
     match s {
         "piper" => Some(TTSBackend::Piper),
         "polly" => Some(TTSBackend::AwsPolly),
+        "elevenlabs" => Some(TTSBackend::ElevenLabs),
+        "macos" => Some(TTSBackend::MacOS),
+        "windows" => Some(TTSBackend::Windows),
+        "espeak" => Some(TTSBackend::Espeak),
         _ => None,
     }
 }
 
-fn backend_to_str(backend: TTSBackend) -> &'static str {
+pub(crate) fn backend_to_str(backend: TTSBackend) -> &'static str {
     match backend {
         TTSBackend::Piper => "piper",
         TTSBackend::AwsPolly => "polly",
+        TTSBackend::ElevenLabs => "elevenlabs",
+        TTSBackend::MacOS => "macos",
+        TTSBackend::Windows => "windows",
+        TTSBackend::Espeak => "espeak",
     }
 }
 
@@ -214,6 +639,69 @@ fn load_or_default_config() -> RawConfig {
     }
 }
 
+/// Keep config changes in memory instead of saving them, until
+/// [`apply_staged_changes`] or [`discard_staged_changes`].
+///
+/// Loads return the staged values, so the app behaves as if the changes were
+/// saved. Does nothing if changes are already being staged.
+pub fn begin_staging() {
+    if STAGED.lock().unwrap().is_some() {
+        return;
+    }
+    let cfg = load_or_default_config();
+    debug!("Staging config changes");
+    *STAGED.lock().unwrap() = Some((cfg.clone(), cfg));
+}
+
+/// Whether staged config differs from the saved config.
+pub fn has_staged_changes() -> bool {
+    STAGED
+        .lock()
+        .unwrap()
+        .as_ref()
+        .is_some_and(|(saved, staged)| saved != staged)
+}
+
+/// Save the staged changes and stop staging.
+///
+/// Only the fields changed while staging are written, on top of the config
+/// as it is on disk now, so changes made meanwhile (e.g. by a sync pull) are
+/// kept. Errors are logged and otherwise ignored.
+pub fn apply_staged_changes() {
+    let Some((saved, staged)) = STAGED.lock().unwrap().take() else {
+        return;
+    };
+    debug!("Applying staged config changes");
+    let result = load_raw_config()
+        .and_then(|current| merge_changes(&saved, &staged, current))
+        .and_then(save_raw_config);
+    if let Err(err) = result {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+/// Copy the fields that differ between `saved` and `staged` into `current`.
+fn merge_changes(saved: &RawConfig, staged: &RawConfig, current: RawConfig) -> Result<RawConfig, ConfigError> {
+    let saved = serde_json::to_value(saved)?;
+    let staged = serde_json::to_value(staged)?;
+    let mut merged = serde_json::to_value(current)?;
+    if let (Some(saved), Some(staged), Some(merged)) = (saved.as_object(), staged.as_object(), merged.as_object_mut()) {
+        for (field, value) in staged {
+            if saved.get(field) != Some(value) {
+                merged.insert(field.clone(), value.clone());
+            }
+        }
+    }
+    Ok(serde_json::from_value(merged)?)
+}
+
+/// Drop the staged changes and stop staging.
+pub fn discard_staged_changes() {
+    if STAGED.lock().unwrap().take().is_some() {
+        debug!("Discarded staged config changes");
+    }
+}
+
 /// Persist the selected voice provider to disk.
 ///
 /// Errors are logged and otherwise ignored.
@@ -307,6 +795,103 @@ pub fn save_selected_polly_voice(voice_id: String) {
     }
 }
 
+/// Load the persisted selected ElevenLabs voice, returning None if not set.
+pub fn load_selected_elevenlabs_voice() -> Option<String> {
+    match load_raw_config() {
+        Ok(cfg) => cfg.selected_elevenlabs_voice.filter(|s| !s.is_empty()),
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, no ElevenLabs voice selected");
+            None
+        }
+    }
+}
+
+/// Persist the selected ElevenLabs voice to disk.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_selected_elevenlabs_voice(voice_id: String) {
+    debug!(voice_id = %voice_id, "Saving selected ElevenLabs voice");
+    let mut cfg = load_or_default_config();
+    cfg.selected_elevenlabs_voice = Some(voice_id);
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+/// Load the persisted selected macOS system voice, returning None if not set.
+pub fn load_selected_macos_voice() -> Option<String> {
+    match load_raw_config() {
+        Ok(cfg) => cfg.selected_macos_voice.filter(|s| !s.is_empty()),
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, no macOS voice selected");
+            None
+        }
+    }
+}
+
+/// Persist the selected macOS system voice to disk.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_selected_macos_voice(voice_id: String) {
+    debug!(voice_id = %voice_id, "Saving selected macOS voice");
+    let mut cfg = load_or_default_config();
+    cfg.selected_macos_voice = Some(voice_id);
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+/// Load the persisted selected Windows system voice, returning None if not set.
+pub fn load_selected_windows_voice() -> Option<String> {
+    match load_raw_config() {
+        Ok(cfg) => cfg.selected_windows_voice.filter(|s| !s.is_empty()),
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, no Windows voice selected");
+            None
+        }
+    }
+}
+
+/// Persist the selected Windows system voice to disk.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_selected_windows_voice(voice_id: String) {
+    debug!(voice_id = %voice_id, "Saving selected Windows voice");
+    let mut cfg = load_or_default_config();
+    cfg.selected_windows_voice = Some(voice_id);
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+/// Load the ElevenLabs API key, falling back to the `ELEVENLABS_API_KEY`
+/// environment variable; empty if neither is set.
+pub fn load_elevenlabs_api_key() -> String {
+    let saved = match load_raw_config() {
+        Ok(cfg) => cfg.elevenlabs_api_key,
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, no ElevenLabs API key");
+            None
+        }
+    };
+    saved
+        .or_else(|| std::env::var("ELEVENLABS_API_KEY").ok())
+        .map(|key| key.trim().to_string())
+        .unwrap_or_default()
+}
+
+/// Persist the ElevenLabs API key to disk; an empty key removes it.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_elevenlabs_api_key(api_key: &str) {
+    debug!(set = !api_key.trim().is_empty(), "Saving ElevenLabs API key");
+    let mut cfg = load_or_default_config();
+    cfg.elevenlabs_api_key = Some(api_key.trim().to_string());
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
 fn ocr_backend_from_str(s: &str) -> Option<OCRBackend> {
     match s {
         "default" => Some(OCRBackend::Default),
@@ -461,3 +1046,1619 @@ pub fn save_hotkey_config(config: &HotkeyConfig, enabled: bool) {
         error!(error = ?err, "Failed to save hotkey config");
     }
 }
+
+fn long_text_action_from_str(s: &str) -> Option<LongTextAction> {
+    match s {
+        "ask" => Some(LongTextAction::Ask),
+        "read_first" => Some(LongTextAction::ReadFirst),
+        "read_all" => Some(LongTextAction::ReadAll),
+        _ => None,
+    }
+}
+
+fn long_text_action_to_str(action: LongTextAction) -> &'static str {
+    match action {
+        LongTextAction::Ask => "ask",
+        LongTextAction::ReadFirst => "read_first",
+        LongTextAction::ReadAll => "read_all",
+    }
+}
+
+/// Load the persisted long text settings (default action, paragraph count).
+///
+/// Defaults to asking and reading the first 5 paragraphs.
+pub fn load_long_text_settings() -> (LongTextAction, usize) {
+    match load_raw_config() {
+        Ok(cfg) => {
+            let action = cfg
+                .long_text_action
+                .as_deref()
+                .and_then(long_text_action_from_str)
+                .unwrap_or(LongTextAction::Ask);
+            let paragraphs = cfg
+                .long_text_paragraphs
+                .filter(|&n| n > 0)
+                .unwrap_or(DEFAULT_LONG_TEXT_PARAGRAPHS);
+            (action, paragraphs)
+        }
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, using default long text settings");
+            (LongTextAction::Ask, DEFAULT_LONG_TEXT_PARAGRAPHS)
+        }
+    }
+}
+
+/// Persist the default action for long captures to disk.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_long_text_action(action: LongTextAction) {
+    debug!(?action, "Saving long text action");
+    let mut cfg = load_or_default_config();
+    cfg.long_text_action = Some(long_text_action_to_str(action).to_string());
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+/// Persist the number of paragraphs read by "Read first N paragraphs".
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_long_text_paragraphs(paragraphs: usize) {
+    debug!(paragraphs, "Saving long text paragraphs");
+    let mut cfg = load_or_default_config();
+    cfg.long_text_paragraphs = Some(paragraphs);
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+/// Load whether short captures interrupt a long reading instead of replacing it (on by default).
+pub fn load_interrupt_readings() -> bool {
+    match load_raw_config() {
+        Ok(cfg) => cfg.interrupt_readings.unwrap_or(true),
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, letting short captures interrupt readings");
+            true
+        }
+    }
+}
+
+/// Persist whether short captures interrupt a long reading.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_interrupt_readings(enabled: bool) {
+    debug!(enabled, "Saving interrupt readings");
+    let mut cfg = load_or_default_config();
+    cfg.interrupt_readings = Some(enabled);
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+fn duplicate_capture_action_from_str(s: &str) -> Option<DuplicateCaptureAction> {
+    match s {
+        "replay" => Some(DuplicateCaptureAction::Replay),
+        "ignore" => Some(DuplicateCaptureAction::Ignore),
+        _ => None,
+    }
+}
+
+fn duplicate_capture_action_to_str(action: DuplicateCaptureAction) -> &'static str {
+    match action {
+        DuplicateCaptureAction::Replay => "replay",
+        DuplicateCaptureAction::Ignore => "ignore",
+    }
+}
+
+/// Load the persisted action for repeated captures, defaulting to replay.
+pub fn load_duplicate_capture_action() -> DuplicateCaptureAction {
+    match load_raw_config() {
+        Ok(cfg) => cfg
+            .duplicate_capture_action
+            .as_deref()
+            .and_then(duplicate_capture_action_from_str)
+            .unwrap_or(DuplicateCaptureAction::Replay),
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, using default duplicate capture action");
+            DuplicateCaptureAction::Replay
+        }
+    }
+}
+
+/// Persist the action for repeated captures to disk.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_duplicate_capture_action(action: DuplicateCaptureAction) {
+    debug!(?action, "Saving duplicate capture action");
+    let mut cfg = load_or_default_config();
+    cfg.duplicate_capture_action = Some(duplicate_capture_action_to_str(action).to_string());
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+fn source_priority_from_str(s: &str) -> Option<SourcePriority> {
+    match s {
+        "selection_first" => Some(SourcePriority::SelectionFirst),
+        "clipboard_first" => Some(SourcePriority::ClipboardFirst),
+        "ask" => Some(SourcePriority::AlwaysAsk),
+        _ => None,
+    }
+}
+
+fn source_priority_to_str(priority: SourcePriority) -> &'static str {
+    match priority {
+        SourcePriority::SelectionFirst => "selection_first",
+        SourcePriority::ClipboardFirst => "clipboard_first",
+        SourcePriority::AlwaysAsk => "ask",
+    }
+}
+
+/// Load the persisted capture source order, defaulting to selection first.
+pub fn load_source_priority() -> SourcePriority {
+    match load_raw_config() {
+        Ok(cfg) => cfg
+            .source_priority
+            .as_deref()
+            .and_then(source_priority_from_str)
+            .unwrap_or(SourcePriority::SelectionFirst),
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, using default capture source order");
+            SourcePriority::SelectionFirst
+        }
+    }
+}
+
+/// Persist the capture source order to disk.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_source_priority(priority: SourcePriority) {
+    debug!(?priority, "Saving capture source order");
+    let mut cfg = load_or_default_config();
+    cfg.source_priority = Some(source_priority_to_str(priority).to_string());
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+pub(crate) fn text_profile_from_str(s: &str) -> Option<TextProfile> {
+    match s {
+        "auto" => Some(TextProfile::Auto),
+        "plain" => Some(TextProfile::Plain),
+        "chat" => Some(TextProfile::Chat),
+        "email" => Some(TextProfile::Email),
+        _ => None,
+    }
+}
+
+fn text_profile_to_str(profile: TextProfile) -> &'static str {
+    match profile {
+        TextProfile::Auto => "auto",
+        TextProfile::Plain => "plain",
+        TextProfile::Chat => "chat",
+        TextProfile::Email => "email",
+    }
+}
+
+/// Load the cleanup profile for captured text, defaulting to automatic detection.
+pub fn load_text_profile() -> TextProfile {
+    match load_raw_config() {
+        Ok(cfg) => cfg
+            .text_profile
+            .as_deref()
+            .and_then(text_profile_from_str)
+            .unwrap_or(TextProfile::Auto),
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, detecting the text profile");
+            TextProfile::Auto
+        }
+    }
+}
+
+/// Persist the cleanup profile for captured text.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_text_profile(profile: TextProfile) {
+    debug!(?profile, "Saving text profile");
+    let mut cfg = load_or_default_config();
+    cfg.text_profile = Some(text_profile_to_str(profile).to_string());
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+/// Load the podcast feed settings, filling unset fields with defaults.
+pub fn load_feed_config() -> FeedConfig {
+    let defaults = FeedConfig::default();
+    match load_raw_config() {
+        Ok(cfg) => FeedConfig {
+            enabled: cfg.podcast_feed_enabled.unwrap_or(defaults.enabled),
+            dir: cfg.podcast_feed_dir.map(PathBuf::from).unwrap_or(defaults.dir),
+            title: cfg.podcast_feed_title.unwrap_or(defaults.title),
+            author: cfg.podcast_feed_author.unwrap_or(defaults.author),
+            description: cfg.podcast_feed_description.unwrap_or(defaults.description),
+            port: cfg.podcast_feed_port.unwrap_or(defaults.port),
+            base_url: cfg.podcast_feed_base_url,
+        },
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, using default podcast feed settings");
+            defaults
+        }
+    }
+}
+
+/// Persist the podcast feed settings to disk.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_feed_config(feed: &FeedConfig) {
+    debug!(?feed, "Saving podcast feed config");
+    let mut cfg = load_or_default_config();
+    cfg.podcast_feed_enabled = Some(feed.enabled);
+    cfg.podcast_feed_dir = Some(feed.dir.to_string_lossy().to_string());
+    cfg.podcast_feed_title = Some(feed.title.clone());
+    cfg.podcast_feed_author = Some(feed.author.clone());
+    cfg.podcast_feed_description = Some(feed.description.clone());
+    cfg.podcast_feed_port = Some(feed.port);
+    cfg.podcast_feed_base_url = feed.base_url.clone();
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+/// Load whether the reading history is kept, defaulting to on.
+pub fn load_history_enabled() -> bool {
+    match load_raw_config() {
+        Ok(cfg) => cfg.history_enabled.unwrap_or(true),
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, keeping reading history");
+            true
+        }
+    }
+}
+
+/// Persist whether the reading history is kept.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_history_enabled(enabled: bool) {
+    debug!(enabled, "Saving history enabled");
+    let mut cfg = load_or_default_config();
+    cfg.history_enabled = Some(enabled);
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+/// Load whether image alt text is read when the selection is HTML (off by default).
+pub fn load_read_alt_text() -> bool {
+    match load_raw_config() {
+        Ok(cfg) => cfg.read_alt_text.unwrap_or(false),
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, not reading alt text");
+            false
+        }
+    }
+}
+
+/// Persist whether image alt text is read when the selection is HTML.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_read_alt_text(enabled: bool) {
+    debug!(enabled, "Saving read alt text");
+    let mut cfg = load_or_default_config();
+    cfg.read_alt_text = Some(enabled);
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+/// Load whether math notation is read as words (on by default).
+pub fn load_read_math() -> bool {
+    match load_raw_config() {
+        Ok(cfg) => cfg.read_math.unwrap_or(true),
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, reading math as words");
+            true
+        }
+    }
+}
+
+/// Persist whether math notation is read as words.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_read_math(enabled: bool) {
+    debug!(enabled, "Saving read math");
+    let mut cfg = load_or_default_config();
+    cfg.read_math = Some(enabled);
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+/// Load whether list items are paced with pauses and spoken ordinals (on by default).
+pub fn load_list_prosody() -> bool {
+    match load_raw_config() {
+        Ok(cfg) => cfg.list_prosody.unwrap_or(true),
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, pacing lists");
+            true
+        }
+    }
+}
+
+/// Persist whether list items are paced.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_list_prosody(enabled: bool) {
+    debug!(enabled, "Saving list prosody");
+    let mut cfg = load_or_default_config();
+    cfg.list_prosody = Some(enabled);
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+/// Load whether readings only play automatically through headphones (off by default).
+pub fn load_headphones_only() -> bool {
+    match load_raw_config() {
+        Ok(cfg) => cfg.headphones_only.unwrap_or(false),
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, playing through any output");
+            false
+        }
+    }
+}
+
+/// Persist whether readings only play automatically through headphones.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_headphones_only(enabled: bool) {
+    debug!(enabled, "Saving headphones only");
+    let mut cfg = load_or_default_config();
+    cfg.headphones_only = Some(enabled);
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+/// Load whether hotkey readings wait while the microphone is in use (off by default).
+pub fn load_defer_while_mic_active() -> bool {
+    match load_raw_config() {
+        Ok(cfg) => cfg.defer_while_mic_active.unwrap_or(false),
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, reading during calls");
+            false
+        }
+    }
+}
+
+/// Persist whether hotkey readings wait while the microphone is in use.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_defer_while_mic_active(enabled: bool) {
+    debug!(enabled, "Saving defer while microphone active");
+    let mut cfg = load_or_default_config();
+    cfg.defer_while_mic_active = Some(enabled);
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+/// Load whether playback pauses while typing (off by default) and after how
+/// many seconds without typing it resumes.
+pub fn load_typing_pause() -> (bool, u32) {
+    match load_raw_config() {
+        Ok(cfg) => (
+            cfg.pause_while_typing.unwrap_or(false),
+            cfg.typing_resume_secs.filter(|&secs| secs > 0).unwrap_or(DEFAULT_TYPING_RESUME_SECS),
+        ),
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, not pausing while typing");
+            (false, DEFAULT_TYPING_RESUME_SECS)
+        }
+    }
+}
+
+/// Persist whether playback pauses while typing.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_pause_while_typing(enabled: bool) {
+    debug!(enabled, "Saving pause while typing");
+    let mut cfg = load_or_default_config();
+    cfg.pause_while_typing = Some(enabled);
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+/// Persist the seconds without typing before playback resumes.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_typing_resume_secs(secs: u32) {
+    debug!(secs, "Saving typing resume delay");
+    let mut cfg = load_or_default_config();
+    cfg.typing_resume_secs = Some(secs);
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+/// Load whether hotkey captures are previewed before reading (off by default).
+pub fn load_preview_before_reading() -> bool {
+    match load_raw_config() {
+        Ok(cfg) => cfg.preview_before_reading.unwrap_or(false),
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, reading without preview");
+            false
+        }
+    }
+}
+
+/// Persist whether hotkey captures are previewed before reading.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_preview_before_reading(enabled: bool) {
+    debug!(enabled, "Saving preview before reading");
+    let mut cfg = load_or_default_config();
+    cfg.preview_before_reading = Some(enabled);
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+/// Load whether selection captures open in the editor before reading (off by default).
+pub fn load_edit_before_reading() -> bool {
+    match load_raw_config() {
+        Ok(cfg) => cfg.edit_before_reading.unwrap_or(false),
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, reading selections directly");
+            false
+        }
+    }
+}
+
+/// Persist whether selection captures open in the editor before reading.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_edit_before_reading(enabled: bool) {
+    debug!(enabled, "Saving edit before reading");
+    let mut cfg = load_or_default_config();
+    cfg.edit_before_reading = Some(enabled);
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+/// Load whether texts that look like secrets need confirmation (on by default).
+pub fn load_sensitive_text_guard() -> bool {
+    match load_raw_config() {
+        Ok(cfg) => cfg.sensitive_text_guard.unwrap_or(true),
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, guarding sensitive text");
+            true
+        }
+    }
+}
+
+/// Persist whether texts that look like secrets need confirmation.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_sensitive_text_guard(enabled: bool) {
+    debug!(enabled, "Saving sensitive text guard");
+    let mut cfg = load_or_default_config();
+    cfg.sensitive_text_guard = Some(enabled);
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+/// Load whether screenshots are kept off the disk (off by default).
+pub fn load_private_screenshots() -> bool {
+    match load_raw_config() {
+        Ok(cfg) => cfg.private_screenshots.unwrap_or(false),
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, saving screenshots to the temp dir");
+            false
+        }
+    }
+}
+
+/// Persist whether screenshots are kept off the disk.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_private_screenshots(enabled: bool) {
+    debug!(enabled, "Saving private screenshots setting");
+    let mut cfg = load_or_default_config();
+    cfg.private_screenshots = Some(enabled);
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+/// Load whether the last captured texts are kept for the clipboard history (off by default).
+pub fn load_clipboard_history() -> bool {
+    match load_raw_config() {
+        Ok(cfg) => cfg.clipboard_history.unwrap_or(false),
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, not keeping a clipboard history");
+            false
+        }
+    }
+}
+
+/// Persist whether the last captured texts are kept for the clipboard history.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_clipboard_history(enabled: bool) {
+    debug!(enabled, "Saving clipboard history setting");
+    let mut cfg = load_or_default_config();
+    cfg.clipboard_history = Some(enabled);
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+/// Load the age recipients exports are encrypted to, as a comma-separated list.
+pub fn load_export_recipients() -> String {
+    match load_raw_config() {
+        Ok(cfg) => cfg.export_recipients.unwrap_or_default().join(", "),
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, no export recipients");
+            String::new()
+        }
+    }
+}
+
+/// Persist the age recipients exports are encrypted to, from a comma-separated list.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_export_recipients(recipients: &str) {
+    debug!(recipients, "Saving export recipients");
+    let mut cfg = load_or_default_config();
+    cfg.export_recipients = Some(parse_recipients(recipients));
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+/// Load the apps whose captures must stay offline, as a comma-separated list.
+pub fn load_local_only_apps() -> String {
+    match load_raw_config() {
+        Ok(cfg) => cfg.local_only_apps.unwrap_or_default().join(", "),
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, no apps kept offline");
+            String::new()
+        }
+    }
+}
+
+/// Persist the apps whose captures must stay offline, from a comma-separated list.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_local_only_apps(apps: &str) {
+    debug!(apps, "Saving offline-only apps");
+    let mut cfg = load_or_default_config();
+    cfg.local_only_apps = Some(
+        apps.split(',')
+            .map(str::trim)
+            .filter(|app| !app.is_empty())
+            .map(str::to_string)
+            .collect(),
+    );
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+fn cleanup_schedule_from_str(s: &str) -> Option<CleanupSchedule> {
+    match s {
+        "off" => Some(CleanupSchedule::Off),
+        "daily" => Some(CleanupSchedule::Daily),
+        "weekly" => Some(CleanupSchedule::Weekly),
+        _ => None,
+    }
+}
+
+fn cleanup_schedule_to_str(schedule: CleanupSchedule) -> &'static str {
+    match schedule {
+        CleanupSchedule::Off => "off",
+        CleanupSchedule::Daily => "daily",
+        CleanupSchedule::Weekly => "weekly",
+    }
+}
+
+/// Load the storage cleanup settings: schedule (default weekly) and days of
+/// history to keep (default 0, keep everything).
+pub fn load_cleanup_settings() -> (CleanupSchedule, u32) {
+    match load_raw_config() {
+        Ok(cfg) => (
+            cfg.cleanup_schedule
+                .as_deref()
+                .and_then(cleanup_schedule_from_str)
+                .unwrap_or(CleanupSchedule::Weekly),
+            cfg.history_max_days.unwrap_or(0),
+        ),
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, using default cleanup settings");
+            (CleanupSchedule::Weekly, 0)
+        }
+    }
+}
+
+/// Persist how often the cleanup job runs.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_cleanup_schedule(schedule: CleanupSchedule) {
+    debug!(?schedule, "Saving cleanup schedule");
+    let mut cfg = load_or_default_config();
+    cfg.cleanup_schedule = Some(cleanup_schedule_to_str(schedule).to_string());
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+/// Persist the days of reading history kept by the cleanup job (0 keeps everything).
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_history_max_days(days: u32) {
+    debug!(days, "Saving history retention");
+    let mut cfg = load_or_default_config();
+    cfg.history_max_days = Some(days);
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+/// Load the size limit of the synthesis cache in megabytes (0 when it is off).
+pub fn load_synthesis_cache_mb() -> u32 {
+    match load_raw_config() {
+        Ok(cfg) => cfg.synthesis_cache_mb.unwrap_or(crate::synthesis_cache::DEFAULT_LIMIT_MB),
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, using default synthesis cache size");
+            crate::synthesis_cache::DEFAULT_LIMIT_MB
+        }
+    }
+}
+
+/// Persist the size limit of the synthesis cache in megabytes (0 turns it off).
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_synthesis_cache_mb(mb: u32) {
+    debug!(mb, "Saving synthesis cache size");
+    let mut cfg = load_or_default_config();
+    cfg.synthesis_cache_mb = Some(mb);
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+/// Load the weekly summary settings: whether it is shown (default on), and
+/// the day and hour (default Sunday at 18:00).
+pub fn load_weekly_summary() -> (bool, chrono::Weekday, u32) {
+    match load_raw_config() {
+        Ok(cfg) => (
+            cfg.weekly_summary.unwrap_or(true),
+            cfg.weekly_summary_day
+                .and_then(|day| day.parse().ok())
+                .unwrap_or(chrono::Weekday::Sun),
+            cfg.weekly_summary_hour.filter(|hour| *hour < 24).unwrap_or(18),
+        ),
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, using default weekly summary settings");
+            (true, chrono::Weekday::Sun, 18)
+        }
+    }
+}
+
+/// Persist whether the weekly summary is shown.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_weekly_summary(enabled: bool) {
+    debug!(enabled, "Saving weekly summary setting");
+    let mut cfg = load_or_default_config();
+    cfg.weekly_summary = Some(enabled);
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+/// Persist the day and hour of the weekly summary.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_weekly_summary_time(day: chrono::Weekday, hour: u32) {
+    debug!(%day, hour, "Saving weekly summary time");
+    let mut cfg = load_or_default_config();
+    cfg.weekly_summary_day = Some(day.to_string());
+    cfg.weekly_summary_hour = Some(hour);
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+/// Load the last size and position of a kind of window.
+pub fn load_window_geometry(kind: &str) -> Option<WindowGeometry> {
+    match load_raw_config() {
+        Ok(cfg) => cfg
+            .window_geometry?
+            .get(kind)
+            .copied()
+            .filter(|geometry| geometry.width >= 100.0 && geometry.height >= 100.0),
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, using default window size");
+            None
+        }
+    }
+}
+
+/// Persist the size and position of a kind of window.
+///
+/// Window geometry is not a setting, so it is saved right away even while
+/// settings changes are staged. Errors are logged and otherwise ignored.
+pub fn save_window_geometry(kind: &str, geometry: WindowGeometry) {
+    debug!(kind, ?geometry, "Saving window geometry");
+    save_unstaged(|cfg| {
+        cfg.window_geometry
+            .get_or_insert_with(BTreeMap::new)
+            .insert(kind.to_string(), geometry);
+    });
+}
+
+/// Save a change that is not a setting right away, even while settings
+/// changes are staged, so that neither Cancel nor Apply loses or reverts it.
+///
+/// Errors are logged and otherwise ignored.
+fn save_unstaged(set: impl Fn(&mut RawConfig)) {
+    let staging = STAGED
+        .lock()
+        .unwrap()
+        .as_mut()
+        .map(|(saved, staged)| {
+            // Both copies get it, so it does not count as a staged change
+            set(saved);
+            set(staged);
+        })
+        .is_some();
+    let result = match (staging, config_path()) {
+        (true, Some(path)) => read_config_file().and_then(|mut cfg| {
+            set(&mut cfg);
+            ensure_config_dir_exists(&path)?;
+            write_config_file(&path, &cfg)
+        }),
+        (true, None) => Ok(()),
+        (false, _) => {
+            let mut cfg = load_or_default_config();
+            set(&mut cfg);
+            save_raw_config(cfg)
+        }
+    };
+    if let Err(err) = result {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+/// Load the sync folder, if folder sync is enabled.
+pub fn load_sync_dir() -> Option<PathBuf> {
+    match load_raw_config() {
+        Ok(cfg) => cfg.sync_dir.map(PathBuf::from),
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, sync disabled");
+            None
+        }
+    }
+}
+
+/// Persist the sync folder (`None` turns sync off).
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_sync_dir(dir: Option<&Path>) {
+    debug!(?dir, "Saving sync folder");
+    let mut cfg = load_or_default_config();
+    cfg.sync_dir = dir.map(|d| d.to_string_lossy().to_string());
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+/// Load the watch folder, if watching is enabled.
+pub fn load_watch_dir() -> Option<PathBuf> {
+    match load_raw_config() {
+        Ok(cfg) => cfg.watch_dir.map(PathBuf::from),
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, watch folder disabled");
+            None
+        }
+    }
+}
+
+/// Persist the watch folder (`None` turns watching off).
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_watch_dir(dir: Option<&Path>) {
+    debug!(?dir, "Saving watch folder");
+    let mut cfg = load_or_default_config();
+    cfg.watch_dir = dir.map(|d| d.to_string_lossy().to_string());
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+/// Load the names of plugins the user turned off.
+pub fn load_disabled_plugins() -> Vec<String> {
+    match load_raw_config() {
+        Ok(cfg) => cfg.disabled_plugins.unwrap_or_default(),
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, enabling all plugins");
+            Vec::new()
+        }
+    }
+}
+
+/// Load the names of the AWS Polly lexicons applied to Polly readings.
+pub fn load_polly_lexicons() -> Vec<String> {
+    match load_raw_config() {
+        Ok(cfg) => cfg.polly_lexicons.unwrap_or_default(),
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, no Polly lexicons applied");
+            Vec::new()
+        }
+    }
+}
+
+/// Persist the names of the AWS Polly lexicons applied to Polly readings.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_polly_lexicons(names: &[String]) {
+    debug!(?names, "Saving Polly lexicons");
+    let mut cfg = load_or_default_config();
+    cfg.polly_lexicons = Some(names.to_vec());
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+/// Load the languages for which no voice download is suggested.
+pub fn load_ignored_voice_languages() -> Vec<String> {
+    match load_raw_config() {
+        Ok(cfg) => cfg.ignored_voice_languages.unwrap_or_default(),
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, suggesting voices for every language");
+            Vec::new()
+        }
+    }
+}
+
+/// Persist the languages for which no voice download is suggested.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_ignored_voice_languages(languages: &[String]) {
+    debug!(?languages, "Saving ignored voice languages");
+    let mut cfg = load_or_default_config();
+    cfg.ignored_voice_languages = Some(languages.to_vec());
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+fn execution_provider_from_str(s: &str) -> Option<ExecutionProvider> {
+    match s {
+        "cpu" => Some(ExecutionProvider::Cpu),
+        "cuda" => Some(ExecutionProvider::Cuda),
+        "coreml" => Some(ExecutionProvider::CoreMl),
+        "directml" => Some(ExecutionProvider::DirectMl),
+        _ => None,
+    }
+}
+
+fn execution_provider_to_str(provider: ExecutionProvider) -> &'static str {
+    match provider {
+        ExecutionProvider::Cpu => "cpu",
+        ExecutionProvider::Cuda => "cuda",
+        ExecutionProvider::CoreMl => "coreml",
+        ExecutionProvider::DirectMl => "directml",
+    }
+}
+
+/// Load the hardware Piper runs voices on, the CPU if not set.
+pub fn load_execution_provider() -> ExecutionProvider {
+    match load_raw_config() {
+        Ok(cfg) => cfg
+            .execution_provider
+            .and_then(|s| execution_provider_from_str(&s))
+            .unwrap_or_default(),
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, running Piper on the CPU");
+            ExecutionProvider::Cpu
+        }
+    }
+}
+
+/// Load whether Piper runs as a separate process instead of in the app (off by default).
+pub fn load_piper_subprocess() -> bool {
+    match load_raw_config() {
+        Ok(cfg) => cfg.piper_subprocess.unwrap_or(false),
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, running Piper in the app");
+            false
+        }
+    }
+}
+
+/// Persist the hardware Piper runs voices on.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_execution_provider(provider: ExecutionProvider) {
+    debug!(?provider, "Saving execution provider");
+    let mut cfg = load_or_default_config();
+    cfg.execution_provider = Some(execution_provider_to_str(provider).to_string());
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+fn power_policy_from_str(s: &str) -> Option<PowerPolicy> {
+    match s {
+        "performance" => Some(PowerPolicy::Performance),
+        "balanced" => Some(PowerPolicy::Balanced),
+        "battery_saver" => Some(PowerPolicy::BatterySaver),
+        _ => None,
+    }
+}
+
+fn power_policy_to_str(policy: PowerPolicy) -> &'static str {
+    match policy {
+        PowerPolicy::Performance => "performance",
+        PowerPolicy::Balanced => "balanced",
+        PowerPolicy::BatterySaver => "battery_saver",
+    }
+}
+
+/// Load how synthesis adapts to running on battery, balanced if not set.
+pub fn load_power_policy() -> PowerPolicy {
+    match load_raw_config() {
+        Ok(cfg) => cfg.power_policy.and_then(|s| power_policy_from_str(&s)).unwrap_or_default(),
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, using the balanced power policy");
+            PowerPolicy::default()
+        }
+    }
+}
+
+/// Persist how synthesis adapts to running on battery.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_power_policy(policy: PowerPolicy) {
+    debug!(?policy, "Saving power policy");
+    let mut cfg = load_or_default_config();
+    cfg.power_policy = Some(power_policy_to_str(policy).to_string());
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+fn process_priority_from_str(s: &str) -> Option<ProcessPriority> {
+    match s {
+        "normal" => Some(ProcessPriority::Normal),
+        "low" => Some(ProcessPriority::Low),
+        "lowest" => Some(ProcessPriority::Lowest),
+        _ => None,
+    }
+}
+
+fn process_priority_to_str(priority: ProcessPriority) -> &'static str {
+    match priority {
+        ProcessPriority::Normal => "normal",
+        ProcessPriority::Low => "low",
+        ProcessPriority::Lowest => "lowest",
+    }
+}
+
+/// Load the threads Piper may use (0 for all of them) and the priority it runs at.
+pub fn load_synthesis_resources() -> (usize, ProcessPriority) {
+    match load_raw_config() {
+        Ok(cfg) => (
+            cfg.synthesis_threads.unwrap_or(0) as usize,
+            cfg.synthesis_priority
+                .and_then(|s| process_priority_from_str(&s))
+                .unwrap_or_default(),
+        ),
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, running Piper without limits");
+            (0, ProcessPriority::default())
+        }
+    }
+}
+
+/// Persist the threads Piper may use (0 for all of them).
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_synthesis_threads(threads: usize) {
+    debug!(threads, "Saving synthesis threads");
+    let mut cfg = load_or_default_config();
+    cfg.synthesis_threads = Some(threads as u32);
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+/// Persist the priority Piper runs at.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_synthesis_priority(priority: ProcessPriority) {
+    debug!(?priority, "Saving synthesis priority");
+    let mut cfg = load_or_default_config();
+    cfg.synthesis_priority = Some(process_priority_to_str(priority).to_string());
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+/// Load whether voice languages are listed by their native names, on by default.
+pub fn load_native_language_names() -> bool {
+    match load_raw_config() {
+        Ok(cfg) => cfg.native_language_names.unwrap_or(true),
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, using native language names");
+            true
+        }
+    }
+}
+
+/// Persist whether voice languages are listed by their native names.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_native_language_names(native: bool) {
+    debug!(?native, "Saving native language names");
+    let mut cfg = load_or_default_config();
+    cfg.native_language_names = Some(native);
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+/// Load the voice languages browsed most recently, newest first.
+pub fn load_recent_languages() -> Vec<String> {
+    match load_raw_config() {
+        Ok(cfg) => cfg.recent_languages.unwrap_or_default(),
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, no recent languages");
+            Vec::new()
+        }
+    }
+}
+
+/// Persist the voice languages browsed most recently.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_recent_languages(codes: &[String]) {
+    debug!(?codes, "Saving recent languages");
+    save_unstaged(|cfg| cfg.recent_languages = Some(codes.to_vec()));
+}
+
+/// Load the voice language last browsed with `backend`.
+pub fn load_last_language(backend: TTSBackend) -> Option<String> {
+    match load_raw_config() {
+        Ok(cfg) => match backend {
+            TTSBackend::Piper => cfg.last_piper_language,
+            TTSBackend::AwsPolly => cfg.last_polly_language,
+            // ElevenLabs, system voices and the basic voice are not browsed by language
+            TTSBackend::ElevenLabs | TTSBackend::MacOS | TTSBackend::Windows | TTSBackend::Espeak => None,
+        }
+        .filter(|s| !s.is_empty()),
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, no last language");
+            None
+        }
+    }
+}
+
+/// Persist the voice language last browsed with `backend`.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_last_language(backend: TTSBackend, code: &str) {
+    debug!(?backend, code, "Saving last language");
+    if !matches!(backend, TTSBackend::Piper | TTSBackend::AwsPolly) {
+        return;
+    }
+    save_unstaged(|cfg| {
+        let last = match backend {
+            TTSBackend::Piper => &mut cfg.last_piper_language,
+            TTSBackend::AwsPolly => &mut cfg.last_polly_language,
+            TTSBackend::ElevenLabs | TTSBackend::MacOS | TTSBackend::Windows | TTSBackend::Espeak => return,
+        };
+        *last = Some(code.to_string());
+    });
+}
+
+/// Load the voices selected most recently with `backend`, newest first.
+pub fn load_recent_voices(backend: TTSBackend) -> Vec<String> {
+    match load_raw_config() {
+        Ok(cfg) => match backend {
+            TTSBackend::Piper => cfg.recent_piper_voices,
+            TTSBackend::AwsPolly => cfg.recent_polly_voices,
+            TTSBackend::ElevenLabs => cfg.recent_elevenlabs_voices,
+            TTSBackend::MacOS => cfg.recent_macos_voices,
+            TTSBackend::Windows => cfg.recent_windows_voices,
+            // The basic voice has no voices to choose from
+            TTSBackend::Espeak => None,
+        }
+        .unwrap_or_default(),
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, no recent voices");
+            Vec::new()
+        }
+    }
+}
+
+/// Persist the voices selected most recently with `backend`.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_recent_voices(backend: TTSBackend, keys: &[String]) {
+    debug!(?backend, ?keys, "Saving recent voices");
+    if backend == TTSBackend::Espeak {
+        return;
+    }
+    save_unstaged(|cfg| {
+        let recent = match backend {
+            TTSBackend::Piper => &mut cfg.recent_piper_voices,
+            TTSBackend::AwsPolly => &mut cfg.recent_polly_voices,
+            TTSBackend::ElevenLabs => &mut cfg.recent_elevenlabs_voices,
+            TTSBackend::MacOS => &mut cfg.recent_macos_voices,
+            TTSBackend::Windows => &mut cfg.recent_windows_voices,
+            TTSBackend::Espeak => return,
+        };
+        *recent = Some(keys.to_vec());
+    });
+}
+
+/// Load the scroll wheel sensitivity of the main window, 1.0 by default.
+pub fn load_scroll_sensitivity() -> f32 {
+    match load_raw_config() {
+        Ok(cfg) => cfg
+            .scroll_sensitivity
+            .filter(|sensitivity| (0.25..=3.0).contains(sensitivity))
+            .unwrap_or(1.0),
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, using the default scroll sensitivity");
+            1.0
+        }
+    }
+}
+
+/// Persist the scroll wheel sensitivity of the main window.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_scroll_sensitivity(sensitivity: f32) {
+    debug!(sensitivity, "Saving scroll sensitivity");
+    let mut cfg = load_or_default_config();
+    cfg.scroll_sensitivity = Some(sensitivity);
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+/// Load whether the panic stop hotkey is registered (on by default).
+pub fn load_panic_stop_hotkey() -> bool {
+    match load_raw_config() {
+        Ok(cfg) => cfg.panic_stop_hotkey.unwrap_or(true),
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, registering the panic stop hotkey");
+            true
+        }
+    }
+}
+
+/// Persist whether the panic stop hotkey is registered.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_panic_stop_hotkey(enabled: bool) {
+    debug!(enabled, "Saving panic stop hotkey");
+    let mut cfg = load_or_default_config();
+    cfg.panic_stop_hotkey = Some(enabled);
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+/// Load whether the hotkey with Shift added is registered (off by default).
+pub fn load_hotkey_edit_variant() -> bool {
+    match load_raw_config() {
+        Ok(cfg) => cfg.hotkey_edit_variant.unwrap_or(false),
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, not registering the edit-first hotkey");
+            false
+        }
+    }
+}
+
+/// Persist whether the hotkey with Shift added is registered.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_hotkey_edit_variant(enabled: bool) {
+    debug!(enabled, "Saving edit-first hotkey");
+    let mut cfg = load_or_default_config();
+    cfg.hotkey_edit_variant = Some(enabled);
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+/// Load whether the hotkey with Alt added is registered (off by default).
+pub fn load_hotkey_image_variant() -> bool {
+    match load_raw_config() {
+        Ok(cfg) => cfg.hotkey_image_variant.unwrap_or(false),
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, not registering the clipboard image hotkey");
+            false
+        }
+    }
+}
+
+/// Persist whether the hotkey with Alt added is registered.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_hotkey_image_variant(enabled: bool) {
+    debug!(enabled, "Saving clipboard image hotkey");
+    let mut cfg = load_or_default_config();
+    cfg.hotkey_image_variant = Some(enabled);
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+/// Longest fade offered in the settings, in milliseconds.
+pub const MAX_FADE_MS: u32 = 500;
+
+/// Load the length of the playback fades in milliseconds, 120 by default.
+pub fn load_fade_ms() -> u32 {
+    match load_raw_config() {
+        Ok(cfg) => cfg
+            .fade_ms
+            .filter(|ms| *ms <= MAX_FADE_MS)
+            .unwrap_or(crate::providers::DEFAULT_FADE_MS),
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, using the default fade length");
+            crate::providers::DEFAULT_FADE_MS
+        }
+    }
+}
+
+/// Persist the length of the playback fades.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_fade_ms(ms: u32) {
+    debug!(ms, "Saving fade length");
+    let mut cfg = load_or_default_config();
+    cfg.fade_ms = Some(ms);
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+/// Largest pitch change offered in the settings, in semitones either way.
+pub const MAX_PITCH_SEMITONES: i32 = 6;
+
+/// Load the voice pitch in semitones, 0 (the voice's own) by default.
+pub fn load_pitch_semitones() -> i32 {
+    match load_raw_config() {
+        Ok(cfg) => cfg
+            .pitch_semitones
+            .filter(|semitones| semitones.abs() <= MAX_PITCH_SEMITONES)
+            .unwrap_or(0),
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, using the voice's own pitch");
+            0
+        }
+    }
+}
+
+/// Persist the voice pitch.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_pitch_semitones(semitones: i32) {
+    debug!(semitones, "Saving voice pitch");
+    let mut cfg = load_or_default_config();
+    cfg.pitch_semitones = Some(semitones);
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+/// Load the playback speed multiplier, 1.0 by default.
+pub fn load_playback_speed() -> f32 {
+    match load_raw_config() {
+        Ok(cfg) => cfg
+            .playback_speed
+            .filter(|speed| (0.5..=3.0).contains(speed))
+            .unwrap_or(1.0),
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, playing at normal speed");
+            1.0
+        }
+    }
+}
+
+/// Persist the playback speed multiplier.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_playback_speed(speed: f32) {
+    debug!(speed, "Saving playback speed");
+    save_unstaged(|cfg| cfg.playback_speed = Some(speed));
+}
+
+/// Load the speech volume, full volume by default.
+pub fn load_playback_volume() -> f32 {
+    match load_raw_config() {
+        Ok(cfg) => cfg
+            .playback_volume
+            .filter(|volume| (0.0..=1.0).contains(volume))
+            .unwrap_or(1.0),
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, playing at full volume");
+            1.0
+        }
+    }
+}
+
+/// Persist the speech volume.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_playback_volume(volume: f32) {
+    debug!(volume, "Saving playback volume");
+    save_unstaged(|cfg| cfg.playback_volume = Some(volume));
+}
+
+/// Persist the names of plugins the user turned off.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_disabled_plugins(names: &[String]) {
+    debug!(?names, "Saving disabled plugins");
+    let mut cfg = load_or_default_config();
+    cfg.disabled_plugins = Some(names.to_vec());
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+fn color_preset_from_str(s: &str) -> Option<ColorPreset> {
+    match s {
+        "default" => Some(ColorPreset::Default),
+        "high_contrast" => Some(ColorPreset::HighContrast),
+        "warm" => Some(ColorPreset::Warm),
+        "custom" => Some(ColorPreset::Custom),
+        _ => None,
+    }
+}
+
+fn color_preset_to_str(preset: ColorPreset) -> &'static str {
+    match preset {
+        ColorPreset::Default => "default",
+        ColorPreset::HighContrast => "high_contrast",
+        ColorPreset::Warm => "warm",
+        ColorPreset::Custom => "custom",
+    }
+}
+
+/// Load the color scheme preset and the custom colors.
+pub fn load_color_scheme() -> (ColorPreset, CustomColors) {
+    match load_raw_config() {
+        Ok(cfg) => {
+            let preset = cfg
+                .color_preset
+                .as_deref()
+                .and_then(color_preset_from_str)
+                .unwrap_or(ColorPreset::Default);
+            let custom = CustomColors {
+                accent: cfg.accent_color.unwrap_or_default(),
+                progress: cfg.progress_color.unwrap_or_default(),
+                waveform: cfg.waveform_color.unwrap_or_default(),
+            };
+            (preset, custom)
+        }
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, using default colors");
+            (ColorPreset::Default, CustomColors::default())
+        }
+    }
+}
+
+/// Persist the color scheme preset and the custom colors.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_color_scheme(preset: ColorPreset, custom: &CustomColors) {
+    debug!(?preset, ?custom, "Saving color scheme");
+    let mut cfg = load_or_default_config();
+    cfg.color_preset = Some(color_preset_to_str(preset).to_string());
+    cfg.accent_color = Some(custom.accent.clone());
+    cfg.progress_color = Some(custom.progress.clone());
+    cfg.waveform_color = Some(custom.waveform.clone());
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+fn visualizer_style_from_str(s: &str) -> Option<VisualizerStyle> {
+    match s {
+        "bars" => Some(VisualizerStyle::Bars),
+        "mirrored" => Some(VisualizerStyle::Mirrored),
+        "level" => Some(VisualizerStyle::Level),
+        "off" => Some(VisualizerStyle::Off),
+        _ => None,
+    }
+}
+
+fn visualizer_style_to_str(style: VisualizerStyle) -> &'static str {
+    match style {
+        VisualizerStyle::Bars => "bars",
+        VisualizerStyle::Mirrored => "mirrored",
+        VisualizerStyle::Level => "level",
+        VisualizerStyle::Off => "off",
+    }
+}
+
+/// Load the audio visualization of the main bar, bars by default.
+pub fn load_visualizer_style() -> VisualizerStyle {
+    match load_raw_config() {
+        Ok(cfg) => cfg
+            .visualizer_style
+            .as_deref()
+            .and_then(visualizer_style_from_str)
+            .unwrap_or(VisualizerStyle::Bars),
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, showing bars");
+            VisualizerStyle::Bars
+        }
+    }
+}
+
+/// Persist the audio visualization of the main bar.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_visualizer_style(style: VisualizerStyle) {
+    debug!(?style, "Saving visualizer style");
+    let mut cfg = load_or_default_config();
+    cfg.visualizer_style = Some(visualizer_style_to_str(style).to_string());
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+fn reading_font_from_str(s: &str) -> Option<ReadingFont> {
+    match s {
+        "default" => Some(ReadingFont::Default),
+        "open_dyslexic" => Some(ReadingFont::OpenDyslexic),
+        "serif" => Some(ReadingFont::Serif),
+        "monospace" => Some(ReadingFont::Monospace),
+        _ => None,
+    }
+}
+
+fn reading_font_to_str(font: ReadingFont) -> &'static str {
+    match font {
+        ReadingFont::Default => "default",
+        ReadingFont::OpenDyslexic => "open_dyslexic",
+        ReadingFont::Serif => "serif",
+        ReadingFont::Monospace => "monospace",
+    }
+}
+
+fn reading_tint_from_str(s: &str) -> Option<ReadingTint> {
+    match s {
+        "none" => Some(ReadingTint::None),
+        "cream" => Some(ReadingTint::Cream),
+        "blue" => Some(ReadingTint::Blue),
+        "green" => Some(ReadingTint::Green),
+        "gray" => Some(ReadingTint::Gray),
+        _ => None,
+    }
+}
+
+fn reading_tint_to_str(tint: ReadingTint) -> &'static str {
+    match tint {
+        ReadingTint::None => "none",
+        ReadingTint::Cream => "cream",
+        ReadingTint::Blue => "blue",
+        ReadingTint::Green => "green",
+        ReadingTint::Gray => "gray",
+    }
+}
+
+fn earcon_set_from_str(s: &str) -> Option<EarconSet> {
+    match s {
+        "chime" => Some(EarconSet::Chime),
+        "beep" => Some(EarconSet::Beep),
+        "click" => Some(EarconSet::Click),
+        _ => None,
+    }
+}
+
+fn earcon_set_to_str(set: EarconSet) -> &'static str {
+    match set {
+        EarconSet::Chime => "chime",
+        EarconSet::Beep => "beep",
+        EarconSet::Click => "click",
+    }
+}
+
+/// Load which audio cues play and how loud, filling unset fields with defaults.
+pub fn load_earcon_settings() -> EarconSettings {
+    let defaults = EarconSettings::default();
+    match load_raw_config() {
+        Ok(cfg) => EarconSettings {
+            enabled: cfg.earcons_enabled.unwrap_or(defaults.enabled),
+            volume: cfg
+                .earcon_volume
+                .filter(|volume| (0.0..=1.0).contains(volume))
+                .unwrap_or(defaults.volume),
+            set: cfg
+                .earcon_set
+                .as_deref()
+                .and_then(earcon_set_from_str)
+                .unwrap_or(defaults.set),
+        },
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, using default audio cues");
+            defaults
+        }
+    }
+}
+
+/// Persist which audio cues play and how loud.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_earcon_settings(settings: &EarconSettings) {
+    debug!(?settings, "Saving audio cue settings");
+    let mut cfg = load_or_default_config();
+    cfg.earcons_enabled = Some(settings.enabled);
+    cfg.earcon_volume = Some(settings.volume);
+    cfg.earcon_set = Some(earcon_set_to_str(settings.set).to_string());
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
+
+/// Load how text shown for reading is laid out, filling unset fields with defaults.
+pub fn load_reading_style() -> ReadingStyle {
+    let defaults = ReadingStyle::default();
+    match load_raw_config() {
+        Ok(cfg) => ReadingStyle {
+            font: cfg
+                .reading_font
+                .as_deref()
+                .and_then(reading_font_from_str)
+                .unwrap_or(defaults.font),
+            line_spacing: cfg
+                .reading_line_spacing
+                .filter(|spacing| (1.0..=3.0).contains(spacing))
+                .unwrap_or(defaults.line_spacing),
+            tint: cfg
+                .reading_tint
+                .as_deref()
+                .and_then(reading_tint_from_str)
+                .unwrap_or(defaults.tint),
+            focus_follow: cfg.reading_focus_follow.unwrap_or(defaults.focus_follow),
+            focus_dim: cfg
+                .reading_focus_dim
+                .filter(|dim| (0.0..=0.9).contains(dim))
+                .unwrap_or(defaults.focus_dim),
+        },
+        Err(err) => {
+            warn!(error = ?err, "Failed to load config, using default reading style");
+            defaults
+        }
+    }
+}
+
+/// Persist how text shown for reading is laid out.
+///
+/// Errors are logged and otherwise ignored.
+pub fn save_reading_style(style: &ReadingStyle) {
+    debug!(?style, "Saving reading style");
+    let mut cfg = load_or_default_config();
+    cfg.reading_font = Some(reading_font_to_str(style.font).to_string());
+    cfg.reading_line_spacing = Some(style.line_spacing);
+    cfg.reading_tint = Some(reading_tint_to_str(style.tint).to_string());
+    cfg.reading_focus_follow = Some(style.focus_follow);
+    cfg.reading_focus_dim = Some(style.focus_dim);
+    if let Err(err) = save_raw_config(cfg) {
+        error!(error = ?err, "Failed to save config");
+    }
+}
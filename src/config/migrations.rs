@@ -0,0 +1,124 @@
+//! Upgrades of config files written by older versions.
+//!
+//! `config.json` records the schema it was written with in `config_version`;
+//! files from before versioning count as version 0. On load, the migrations
+//! from the file's version on run in order over the JSON object, so a renamed
+//! or reshaped setting is carried over instead of dropped. Values that still
+//! do not fit their setting are dropped one by one rather than failing the
+//! whole file. The upgraded file is written back once, next to a copy of the
+//! original.
+//!
+//! To change the schema, append a migration to [`MIGRATIONS`]: the current
+//! version is the number of migrations.
+
+use serde_json::{Map, Value};
+use tracing::{debug, warn};
+
+use super::RawConfig;
+
+/// Key of the schema version in `config.json`.
+const VERSION_KEY: &str = "config_version";
+
+/// A migration from one version to the next, over the JSON object of the file.
+type Migration = fn(&mut Map<String, Value>);
+
+/// Migrations in order: the first upgrades version 0 to 1, and so on.
+const MIGRATIONS: &[Migration] = &[drop_empty_values];
+
+/// Schema version written by this version of the app.
+pub(super) const CURRENT_VERSION: u32 = MIGRATIONS.len() as u32;
+
+/// Version 1: remove blank values (`""`, `[]`), which older versions could
+/// write for settings that were cleared, and `null`s, which mean unset anyway.
+fn drop_empty_values(config: &mut Map<String, Value>) {
+    config.retain(|_, value| match value {
+        Value::Null => false,
+        Value::String(s) => !s.trim().is_empty(),
+        Value::Array(items) => !items.is_empty(),
+        _ => true,
+    });
+}
+
+/// Parse a config file, upgrading it if it is older than [`CURRENT_VERSION`].
+///
+/// Returns the config and, if it had to be upgraded or repaired and should be
+/// written back, the version the file had.
+pub(super) fn parse(data: &str) -> Result<(RawConfig, Option<u32>), serde_json::Error> {
+    let Value::Object(mut config) = serde_json::from_str(data)? else {
+        // Not an object: let the config itself report the error
+        return serde_json::from_str(data).map(|cfg| (cfg, None));
+    };
+    let version = config.get(VERSION_KEY).and_then(Value::as_u64).unwrap_or(0) as u32;
+    let mut changed = upgrade(&mut config, version);
+    let cfg = match serde_json::from_value(Value::Object(config.clone())) {
+        Ok(cfg) => cfg,
+        Err(_) => {
+            salvage(&mut config);
+            changed = true;
+            serde_json::from_value(Value::Object(config))?
+        }
+    };
+    Ok((cfg, changed.then_some(version)))
+}
+
+/// Run the migrations from `version` on. Returns whether any ran.
+fn upgrade(config: &mut Map<String, Value>, version: u32) -> bool {
+    if version > CURRENT_VERSION {
+        warn!(version, current = CURRENT_VERSION, "Config written by a newer version, settings it added are not kept");
+    }
+    if version >= CURRENT_VERSION {
+        return false;
+    }
+    for (index, migration) in MIGRATIONS.iter().enumerate().skip(version as usize) {
+        migration(config);
+        debug!(version = index + 1, "Config migrated");
+    }
+    config.insert(VERSION_KEY.to_string(), CURRENT_VERSION.into());
+    true
+}
+
+/// Drop the settings whose values do not fit, keeping all the others.
+fn salvage(config: &mut Map<String, Value>) {
+    config.retain(|key, value| {
+        let single = Map::from_iter([(key.clone(), value.clone())]);
+        let fits = serde_json::from_value::<RawConfig>(Value::Object(single)).is_ok();
+        if !fits {
+            warn!(key = %key, value = %value, "Dropping config setting with an invalid value");
+        }
+        fits
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_upgrades_old_files() {
+        let old = r#"{"voice_provider": "polly", "selected_polly_voice": "", "disabled_plugins": [], "hotkey_key": null}"#;
+        let (cfg, upgraded_from) = parse(old).unwrap();
+        assert_eq!(upgraded_from, Some(0));
+        assert_eq!(cfg.voice_provider.as_deref(), Some("polly"));
+        assert_eq!(cfg.selected_polly_voice, None);
+        assert_eq!(cfg.disabled_plugins, None);
+        assert_eq!(cfg.config_version, Some(CURRENT_VERSION));
+
+        // Once upgraded, the file is left alone
+        let current = serde_json::to_string(&cfg).unwrap();
+        assert_eq!(parse(&current).unwrap(), (cfg, None));
+    }
+
+    #[test]
+    fn test_parse_drops_invalid_values_only() {
+        let data = format!(r#"{{"config_version": {CURRENT_VERSION}, "read_math": "yes", "fade_ms": 120, "selected_voice": "en_US-amy-low"}}"#);
+        let (cfg, repaired) = parse(&data).unwrap();
+        assert_eq!(repaired, Some(CURRENT_VERSION));
+        assert_eq!(cfg.read_math, None);
+        assert_eq!(cfg.fade_ms, Some(120));
+        assert_eq!(cfg.selected_voice.as_deref(), Some("en_US-amy-low"));
+
+        // Files from newer versions are read as they are
+        let newer = r#"{"config_version": 999, "fade_ms": 80}"#;
+        assert_eq!(parse(newer).unwrap().1, None);
+    }
+}
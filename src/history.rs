@@ -0,0 +1,284 @@
+//! Reading history.
+//!
+//! Every text read aloud is kept in `history.json` in the app data directory,
+//! keyed by [`crate::text::content_hash`] so reading the same text again only
+//! updates its timestamp. The file is a flat JSON object so it can take part
+//! in folder sync (see [`crate::sync`]).
+//!
+//! Files and web pages also keep how far they were read in `positions.json`,
+//! keyed by the hash of the file's text or by the URL, so opening them again
+//! can continue from there.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+/// Oldest non-favorite entries are dropped beyond this count.
+const MAX_HISTORY_ENTRIES: usize = 500;
+
+/// Positions closer than this to the start or the end are not worth continuing from.
+const MIN_RESUME_PROGRESS: f32 = 0.02;
+const MAX_RESUME_PROGRESS: f32 = 0.98;
+
+/// A text that was read aloud.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub text: String,
+    /// What triggered the reading (e.g. "SelectedTextFetched", "ReadExtractedText")
+    pub source: String,
+    /// Last time the text was read (seconds since the Unix epoch)
+    pub read_at: i64,
+    /// Favorites are never pruned
+    #[serde(default)]
+    pub favorite: bool,
+}
+
+/// Location of the history file.
+pub fn history_path() -> PathBuf {
+    crate::sync::app_data_dir().join("history.json")
+}
+
+/// Load the history, keyed by content hash.
+pub fn load_history() -> BTreeMap<String, HistoryEntry> {
+    let path = history_path();
+    let Ok(content) = std::fs::read_to_string(&path) else {
+        return BTreeMap::new();
+    };
+    serde_json::from_str(&content).unwrap_or_else(|e| {
+        warn!(error = %e, path = %path.display(), "Failed to parse history, starting fresh");
+        BTreeMap::new()
+    })
+}
+
+fn save_history(history: &BTreeMap<String, HistoryEntry>) {
+    if let Err(e) = write_json(&history_path(), history) {
+        warn!(error = %e, "Failed to save history");
+    }
+}
+
+fn write_json(path: &Path, value: &impl Serialize) -> Result<(), String> {
+    path.parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .map_err(|e| e.to_string())
+        .and_then(|_| serde_json::to_string_pretty(value).map_err(|e| e.to_string()))
+        .and_then(|json| std::fs::write(path, json).map_err(|e| e.to_string()))
+}
+
+/// Drop the oldest non-favorite entries beyond the limit.
+fn prune(history: &mut BTreeMap<String, HistoryEntry>, max_entries: usize) {
+    if history.len() <= max_entries {
+        return;
+    }
+    let mut candidates: Vec<(i64, String)> = history
+        .iter()
+        .filter(|(_, entry)| !entry.favorite)
+        .map(|(id, entry)| (entry.read_at, id.clone()))
+        .collect();
+    candidates.sort();
+    let excess = history.len() - max_entries;
+    for (_, id) in candidates.into_iter().take(excess) {
+        history.remove(&id);
+    }
+}
+
+/// Add a reading to the history (or refresh its timestamp).
+pub fn record_reading(text: &str, source: &str) {
+    if text.trim().is_empty() {
+        return;
+    }
+    let id = crate::text::content_hash(text);
+    let mut history = load_history();
+    let favorite = history.get(&id).is_some_and(|entry| entry.favorite);
+    history.insert(
+        id,
+        HistoryEntry {
+            text: text.to_string(),
+            source: source.to_string(),
+            read_at: chrono::Utc::now().timestamp(),
+            favorite,
+        },
+    );
+    prune(&mut history, MAX_HISTORY_ENTRIES);
+    debug!(entries = history.len(), "Recorded reading in history");
+    save_history(&history);
+}
+
+/// Whether the entry `id` is a favorite.
+pub fn is_favorite(id: &str) -> bool {
+    load_history().get(id).is_some_and(|entry| entry.favorite)
+}
+
+/// Mark the entry `id` as a favorite, or not. Returns `false` if there is no
+/// such entry (e.g. it was cleaned up).
+pub fn set_favorite(id: &str, favorite: bool) -> bool {
+    let mut history = load_history();
+    let Some(entry) = history.get_mut(id) else {
+        return false;
+    };
+    entry.favorite = favorite;
+    debug!(favorite, "Updated favorite in history");
+    save_history(&history);
+    true
+}
+
+/// Drop non-favorite entries and document positions last read more than
+/// `max_days` days ago.
+pub fn prune_older_than(max_days: u32) {
+    let cutoff = chrono::Utc::now().timestamp() - i64::from(max_days) * 24 * 60 * 60;
+    let mut history = load_history();
+    let before = history.len();
+    history.retain(|_, entry| entry.favorite || entry.read_at >= cutoff);
+    if history.len() != before {
+        debug!(removed = before - history.len(), "Pruned old history entries");
+        save_history(&history);
+    }
+    let mut positions = load_positions();
+    let before = positions.len();
+    positions.retain(|_, position| position.read_at >= cutoff);
+    if positions.len() != before {
+        save_positions(&positions);
+    }
+}
+
+/// Drop every non-favorite entry, and every document position.
+pub fn clear_history() {
+    let mut history = load_history();
+    history.retain(|_, entry| entry.favorite);
+    save_history(&history);
+    save_positions(&BTreeMap::new());
+}
+
+/// Furthest point reached in a file or web page.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DocumentPosition {
+    /// Fraction of the reading (0.0 to 1.0)
+    pub progress: f32,
+    /// Last time it was read (seconds since the Unix epoch)
+    pub read_at: i64,
+}
+
+/// A file or web page being read, and how far it has got.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocumentReading {
+    pub key: String,
+    /// Hash of the full text, to tell when something else is read instead
+    pub text_hash: String,
+    pub progress: f32,
+}
+
+impl DocumentReading {
+    pub fn new(key: String, text: &str) -> Self {
+        Self {
+            key,
+            text_hash: crate::text::content_hash(text),
+            progress: 0.0,
+        }
+    }
+}
+
+/// Position key of a file: the hash of its text, so moved or renamed copies share it.
+pub fn file_document_key(text: &str) -> String {
+    format!("file:{}", crate::text::content_hash(text))
+}
+
+/// Position key of a web page.
+pub fn url_document_key(url: &str) -> String {
+    format!("url:{url}")
+}
+
+/// Location of the document positions file.
+pub fn positions_path() -> PathBuf {
+    crate::sync::app_data_dir().join("positions.json")
+}
+
+fn load_positions() -> BTreeMap<String, DocumentPosition> {
+    let path = positions_path();
+    let Ok(content) = std::fs::read_to_string(&path) else {
+        return BTreeMap::new();
+    };
+    serde_json::from_str(&content).unwrap_or_else(|e| {
+        warn!(error = %e, path = %path.display(), "Failed to parse document positions, starting fresh");
+        BTreeMap::new()
+    })
+}
+
+fn save_positions(positions: &BTreeMap<String, DocumentPosition>) {
+    if let Err(e) = write_json(&positions_path(), positions) {
+        warn!(error = %e, "Failed to save document positions");
+    }
+}
+
+/// Saved position of a document, if it is worth continuing from.
+pub fn resume_position(key: &str) -> Option<f32> {
+    load_positions()
+        .get(key)
+        .map(|position| position.progress)
+        .filter(|progress| (MIN_RESUME_PROGRESS..MAX_RESUME_PROGRESS).contains(progress))
+}
+
+/// Remember how far a document was read.
+pub fn record_position(key: &str, progress: f32) {
+    let mut positions = load_positions();
+    update_position(&mut positions, key, progress, chrono::Utc::now().timestamp());
+    debug!(key, progress, "Recorded document position");
+    save_positions(&positions);
+}
+
+/// Keep the furthest point read and forget documents read to the end, within
+/// the same limit as the history.
+fn update_position(positions: &mut BTreeMap<String, DocumentPosition>, key: &str, progress: f32, now: i64) {
+    if progress >= MAX_RESUME_PROGRESS {
+        positions.remove(key);
+        return;
+    }
+    let furthest = positions.get(key).map_or(progress, |position| position.progress.max(progress));
+    positions.insert(
+        key.to_string(),
+        DocumentPosition {
+            progress: furthest,
+            read_at: now,
+        },
+    );
+    if positions.len() > MAX_HISTORY_ENTRIES {
+        let oldest = positions.iter().min_by_key(|(_, position)| position.read_at).map(|(key, _)| key.clone());
+        if let Some(oldest) = oldest {
+            positions.remove(&oldest);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prune_keeps_favorites_and_newest() {
+        let entry = |read_at, favorite| HistoryEntry {
+            text: String::new(),
+            source: "selection".into(),
+            read_at,
+            favorite,
+        };
+        let mut history = BTreeMap::from([
+            ("old-fav".to_string(), entry(1, true)),
+            ("old".to_string(), entry(2, false)),
+            ("mid".to_string(), entry(3, false)),
+            ("new".to_string(), entry(4, false)),
+        ]);
+        prune(&mut history, 2);
+        let ids: Vec<&str> = history.keys().map(String::as_str).collect();
+        assert_eq!(ids, vec!["new", "old-fav"]);
+    }
+
+    #[test]
+    fn test_update_position_keeps_furthest() {
+        let mut positions = BTreeMap::new();
+        update_position(&mut positions, "url:https://example.com", 0.42, 1);
+        update_position(&mut positions, "url:https://example.com", 0.1, 2);
+        assert_eq!(positions["url:https://example.com"], DocumentPosition { progress: 0.42, read_at: 2 });
+        update_position(&mut positions, "url:https://example.com", 1.0, 3);
+        assert!(positions.is_empty());
+    }
+}
@@ -0,0 +1,95 @@
+//! The pronunciation lexicon and AWS Polly lexicons.
+//!
+//! Words and how to say them are kept in `lexicon.json` in the app data
+//! directory (`{"nginx": "engine x"}`), which is synced with the settings.
+//! Polly applies pronunciation lexicons on the server side: `insight-reader
+//! lexicon sync` uploads the local lexicon as a PLS document, and the names
+//! attached in the config are sent with every Polly request.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use tracing::warn;
+
+/// Name the local lexicon is uploaded to Polly under.
+pub const POLLY_LEXICON_NAME: &str = "InsightReader";
+
+/// Most lexicons Polly applies to one request.
+pub const MAX_POLLY_LEXICONS: usize = 5;
+
+/// Location of the local lexicon.
+pub fn lexicon_path() -> PathBuf {
+    crate::sync::app_data_dir().join("lexicon.json")
+}
+
+/// Load the local lexicon: words and what to say instead.
+pub fn load_lexicon() -> BTreeMap<String, String> {
+    let path = lexicon_path();
+    let Ok(content) = std::fs::read_to_string(&path) else {
+        return BTreeMap::new();
+    };
+    serde_json::from_str(&content).unwrap_or_else(|e| {
+        warn!(error = %e, path = %path.display(), "Failed to parse pronunciation lexicon");
+        BTreeMap::new()
+    })
+}
+
+/// Whether Polly accepts `name` as a lexicon name (1 to 20 letters and digits).
+pub fn is_valid_polly_name(name: &str) -> bool {
+    (1..=20).contains(&name.len()) && name.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// The lexicon as a PLS document for `language` ("en-US"), with each word
+/// spoken as its alias.
+pub fn to_pls(lexicon: &BTreeMap<String, String>, language: &str) -> String {
+    let mut pls = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <lexicon version=\"1.0\" xmlns=\"http://www.w3.org/2005/01/pronunciation-lexicon\" \
+         alphabet=\"ipa\" xml:lang=\"{}\">\n",
+        escape_xml(language)
+    );
+    for (word, alias) in lexicon {
+        let (word, alias) = (word.trim(), alias.trim());
+        if word.is_empty() || alias.is_empty() {
+            continue;
+        }
+        pls.push_str(&format!(
+            "  <lexeme>\n    <grapheme>{}</grapheme>\n    <alias>{}</alias>\n  </lexeme>\n",
+            escape_xml(word),
+            escape_xml(alias)
+        ));
+    }
+    pls.push_str("</lexicon>\n");
+    pls
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_pls() {
+        let lexicon = BTreeMap::from([
+            ("AT&T".to_string(), "A T and T".to_string()),
+            ("nginx".to_string(), "engine x".to_string()),
+            ("empty".to_string(), " ".to_string()),
+        ]);
+        let pls = to_pls(&lexicon, "en-US");
+        assert!(pls.contains("xml:lang=\"en-US\""));
+        assert!(pls.contains("<grapheme>AT&amp;T</grapheme>\n    <alias>A T and T</alias>"));
+        assert!(pls.contains("<grapheme>nginx</grapheme>\n    <alias>engine x</alias>"));
+        assert!(!pls.contains("empty"));
+        assert_eq!(pls.matches("<lexeme>").count(), 2);
+
+        assert!(is_valid_polly_name(POLLY_LEXICON_NAME));
+        assert!(!is_valid_polly_name("my-lexicon"));
+        assert!(!is_valid_polly_name(""));
+    }
+}
@@ -4,11 +4,15 @@
 //! - Human-readable format: timestamp, level, target, file:line, message
 //! - Runtime log level control via GUI and `RUST_LOG` environment variable
 //! - Dual output to stderr and rotating log files
+//! - [`trace_throttled!`] for hot paths (ticks, rendering, playback tracking)
+//!   that would otherwise log every frame
 
 use std::fmt;
 use std::io;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::OnceLock;
+use std::time::{Duration, Instant};
 
 use tracing::{Event, Level, Subscriber};
 use tracing_appender::rolling::{RollingFileAppender, Rotation};
@@ -279,8 +283,99 @@ pub fn set_verbosity(level: LogLevel) {
 /// Get the default log directory path.
 ///
 /// Useful for displaying to the user where logs are stored.
-#[allow(dead_code)]
 pub fn default_log_dir() -> PathBuf {
     resolve_log_dir(&LoggingConfig::default())
 }
 
+/// Shortest time between two logs of a [`trace_throttled!`] call site.
+pub const THROTTLE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// State of one [`trace_throttled!`] call site.
+pub struct RateLimit {
+    /// Occurrences since the last log
+    count: AtomicU64,
+    /// When it last logged, in milliseconds since [`throttle_epoch`] (0 for never)
+    last_ms: AtomicU64,
+}
+
+/// Start of the clock rate limits are measured with.
+fn throttle_epoch() -> Instant {
+    static EPOCH: OnceLock<Instant> = OnceLock::new();
+    *EPOCH.get_or_init(Instant::now)
+}
+
+impl RateLimit {
+    pub const fn new() -> Self {
+        Self {
+            count: AtomicU64::new(0),
+            last_ms: AtomicU64::new(0),
+        }
+    }
+
+    /// Count an occurrence at `now`. Returns how many occurrences the log
+    /// stands for if this one is logged: the first one, then at most one per
+    /// [`THROTTLE_INTERVAL`].
+    pub fn check_at(&self, now: Instant) -> Option<u64> {
+        self.count.fetch_add(1, Ordering::Relaxed);
+        // Offset by one so a log right at the epoch is not taken for "never"
+        let now_ms = now.saturating_duration_since(throttle_epoch()).as_millis() as u64 + 1;
+        let last_ms = self.last_ms.load(Ordering::Relaxed);
+        if last_ms != 0 && now_ms < last_ms + THROTTLE_INTERVAL.as_millis() as u64 {
+            return None;
+        }
+        // Another thread may log this interval first
+        self.last_ms
+            .compare_exchange(last_ms, now_ms, Ordering::Relaxed, Ordering::Relaxed)
+            .ok()?;
+        Some(self.count.swap(0, Ordering::Relaxed).max(1))
+    }
+
+    pub fn check(&self) -> Option<u64> {
+        self.check_at(Instant::now())
+    }
+}
+
+impl Default for RateLimit {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `tracing::trace!` for code that runs every frame or tick: logs the first
+/// time, then at most once per [`THROTTLE_INTERVAL`] per call site, with the
+/// number of occurrences the line stands for as `occurrences`. Costs nothing
+/// when trace logging is off.
+///
+/// ```ignore
+/// trace_throttled!(position, "Playback position advanced");
+/// ```
+macro_rules! trace_throttled {
+    ($($arg:tt)+) => {{
+        if tracing::enabled!(tracing::Level::TRACE) {
+            static LIMIT: $crate::logging::RateLimit = $crate::logging::RateLimit::new();
+            if let Some(occurrences) = LIMIT.check() {
+                tracing::trace!(occurrences, $($arg)+);
+            }
+        }
+    }};
+}
+pub(crate) use trace_throttled;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limit() {
+        let limit = RateLimit::new();
+        let start = throttle_epoch() + Duration::from_secs(5);
+        assert_eq!(limit.check_at(start), Some(1));
+        for ms in [10, 100, 999] {
+            assert_eq!(limit.check_at(start + Duration::from_millis(ms)), None);
+        }
+        // The next log stands for the ones held back
+        assert_eq!(limit.check_at(start + Duration::from_millis(1000)), Some(4));
+        assert_eq!(limit.check_at(start + Duration::from_millis(1500)), None);
+        assert_eq!(limit.check_at(start + Duration::from_secs(3)), Some(2));
+    }
+}
@@ -2,14 +2,28 @@
 
 #![cfg_attr(target_os = "windows", windows_subsystem = "windows")]
 
+mod activity;
 mod app;
+mod bundle;
+mod capture;
+mod cli;
 mod config;
 mod flags;
+mod history;
+mod lexicon;
 mod logging;
 mod model;
+mod phrase_cache;
+mod plugins;
 mod providers;
+mod startup;
+mod stats;
+mod storage;
 mod styles;
+mod sync;
+mod synthesis_cache;
 mod system;
+mod text;
 mod update;
 mod ui;
 mod view;
@@ -19,10 +33,20 @@ use iced::daemon;
 use tracing::info;
 
 fn main() -> iced::Result {
+    let args: Vec<String> = std::env::args().collect();
+    let is_cli = cli::is_command(&args);
+    startup::begin(&args);
+
     // Initialize logging first (before anything else)
+    // CLI commands print their own progress, so keep stderr free of log lines
+    // Profiling logs at info level, so make sure it is not filtered out
+    let mut verbosity = config::load_log_level();
+    if startup::is_enabled() && matches!(verbosity, model::LogLevel::Error | model::LogLevel::Warn) {
+        verbosity = model::LogLevel::Info;
+    }
     let log_config = logging::LoggingConfig {
-        verbosity: config::load_log_level(),
-        log_to_stderr: true,
+        verbosity,
+        log_to_stderr: !is_cli,
         log_to_file: true,
         log_dir: None, // Use default: ~/.local/share/insight-reader/logs
     };
@@ -32,7 +56,16 @@ fn main() -> iced::Result {
         // Continue anyway - app can run without logging
     }
 
+    if is_cli {
+        std::process::exit(cli::run(&args));
+    }
+
     info!("Insight Reader starting up");
+    startup::mark("logging initialized");
+
+    // Packaged builds set up their bundled Piper and voice on first run
+    bundle::install();
+    startup::mark("bundle");
 
     // Use daemon for multi-window support (view receives window::Id)
     // Note: Text selection is now fetched asynchronously after UI appears for blazing fast startup
@@ -2,13 +2,33 @@
 
 use std::collections::HashMap;
 use iced::window;
-use crate::providers::TTSProvider;
+use crate::activity::Activity;
+use crate::providers::{ExecutionProvider, TTSProvider, WordMark};
 use crate::config;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TTSBackend {
     Piper,
     AwsPolly,
+    ElevenLabs,
+    /// The voices that come with macOS (AVSpeechSynthesizer)
+    MacOS,
+    /// The voices that come with Windows (Windows.Media.SpeechSynthesis)
+    Windows,
+    /// espeak-ng, the basic voice that needs no download or credentials
+    Espeak,
+}
+
+impl TTSBackend {
+    /// Whether speech is synthesized on this machine, without sending the text anywhere.
+    pub fn is_local(self) -> bool {
+        matches!(self, TTSBackend::Piper | TTSBackend::MacOS | TTSBackend::Windows | TTSBackend::Espeak)
+    }
+
+    /// Whether this speaks with the voices installed with the operating system.
+    pub fn is_system(self) -> bool {
+        matches!(self, TTSBackend::MacOS | TTSBackend::Windows)
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -26,11 +46,259 @@ pub enum LogLevel {
     Trace,
 }
 
+/// What to do when a captured text is very long.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LongTextAction {
+    /// Show the "Read first N paragraphs / Read all / Open in editor" dialog
+    Ask,
+    /// Read only the first N paragraphs without asking
+    ReadFirst,
+    /// Read the whole text without asking
+    ReadAll,
+}
+
+/// What to do when the same text is captured twice in a row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicateCaptureAction {
+    /// Replay the already synthesized audio from the start
+    Replay,
+    /// Ignore the capture and keep the current playback state
+    Ignore,
+}
+
+/// How captured text is cleaned up for the kind of content it is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextProfile {
+    /// Pick the profile from the shape of the text
+    Auto,
+    /// Read the text as it is
+    Plain,
+    /// Chat messages: announce senders, skip timestamps and reactions
+    Chat,
+    /// Emails: read sender and subject once, skip quoted replies and signatures
+    Email,
+}
+
+/// Page of the settings window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsTab {
+    General,
+    Voices,
+    Hotkeys,
+    OCR,
+    Audio,
+    Advanced,
+}
+
+impl SettingsTab {
+    pub const ALL: [SettingsTab; 6] = [
+        SettingsTab::General,
+        SettingsTab::Voices,
+        SettingsTab::Hotkeys,
+        SettingsTab::OCR,
+        SettingsTab::Audio,
+        SettingsTab::Advanced,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SettingsTab::General => "General",
+            SettingsTab::Voices => "Voices",
+            SettingsTab::Hotkeys => "Hotkeys",
+            SettingsTab::OCR => "OCR",
+            SettingsTab::Audio => "Audio",
+            SettingsTab::Advanced => "Advanced",
+        }
+    }
+}
+
+/// A section of the settings window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsSection {
+    Appearance,
+    Reading,
+    TextSource,
+    LongText,
+    SameTextAgain,
+    Privacy,
+    Provider,
+    Acceleration,
+    Power,
+    SpokenText,
+    NaturalReading,
+    Hotkeys,
+    Ocr,
+    AudioCues,
+    PodcastFeed,
+    HistorySync,
+    WatchFolder,
+    Storage,
+    Plugins,
+    LogLevel,
+}
+
+impl SettingsSection {
+    /// Every section, in the order they appear within their tab.
+    pub const ALL: [SettingsSection; 20] = [
+        SettingsSection::Appearance,
+        SettingsSection::Reading,
+        SettingsSection::TextSource,
+        SettingsSection::LongText,
+        SettingsSection::SameTextAgain,
+        SettingsSection::Privacy,
+        SettingsSection::Provider,
+        SettingsSection::Acceleration,
+        SettingsSection::Power,
+        SettingsSection::SpokenText,
+        SettingsSection::NaturalReading,
+        SettingsSection::Hotkeys,
+        SettingsSection::Ocr,
+        SettingsSection::AudioCues,
+        SettingsSection::PodcastFeed,
+        SettingsSection::HistorySync,
+        SettingsSection::WatchFolder,
+        SettingsSection::Storage,
+        SettingsSection::Plugins,
+        SettingsSection::LogLevel,
+    ];
+
+    pub fn tab(self) -> SettingsTab {
+        match self {
+            SettingsSection::Appearance
+            | SettingsSection::Reading
+            | SettingsSection::TextSource
+            | SettingsSection::LongText
+            | SettingsSection::SameTextAgain
+            | SettingsSection::Privacy => SettingsTab::General,
+            SettingsSection::Provider
+            | SettingsSection::Acceleration
+            | SettingsSection::Power
+            | SettingsSection::SpokenText
+            | SettingsSection::NaturalReading => SettingsTab::Voices,
+            SettingsSection::Hotkeys => SettingsTab::Hotkeys,
+            SettingsSection::Ocr => SettingsTab::OCR,
+            SettingsSection::AudioCues => SettingsTab::Audio,
+            SettingsSection::PodcastFeed
+            | SettingsSection::HistorySync
+            | SettingsSection::WatchFolder
+            | SettingsSection::Storage
+            | SettingsSection::Plugins
+            | SettingsSection::LogLevel => SettingsTab::Advanced,
+        }
+    }
+}
+
+/// Color scheme of the main bar and settings controls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorPreset {
+    /// Thin white progress bar and blue accents
+    Default,
+    /// Thick yellow progress bar and bright white waveform
+    HighContrast,
+    /// Warm orange tones
+    Warm,
+    /// Colors entered by the user
+    Custom,
+}
+
+/// A customizable color of the color scheme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorRole {
+    Accent,
+    Progress,
+    Waveform,
+}
+
+/// User-entered colors for [`ColorPreset::Custom`], as hex strings (`#rrggbb`).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CustomColors {
+    pub accent: String,
+    pub progress: String,
+    pub waveform: String,
+}
+
+impl CustomColors {
+    pub fn get(&self, role: ColorRole) -> &str {
+        match role {
+            ColorRole::Accent => &self.accent,
+            ColorRole::Progress => &self.progress,
+            ColorRole::Waveform => &self.waveform,
+        }
+    }
+
+    pub fn set(&mut self, role: ColorRole, value: String) {
+        match role {
+            ColorRole::Accent => self.accent = value,
+            ColorRole::Progress => self.progress = value,
+            ColorRole::Waveform => self.waveform = value,
+        }
+    }
+}
+
+/// Font used for text shown for reading (the extracted text window).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadingFont {
+    Default,
+    /// OpenDyslexic, if installed on the system or in the app's fonts folder
+    OpenDyslexic,
+    Serif,
+    Monospace,
+}
+
+/// Background tint behind text shown for reading.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadingTint {
+    /// Dark background, like the rest of the app
+    None,
+    Cream,
+    Blue,
+    Green,
+    Gray,
+}
+
+/// How text shown for reading is laid out.
 #[derive(Debug, Clone, Copy, PartialEq)]
-pub enum PlaybackState {
-    Stopped,
-    Playing,
-    Paused,
+pub struct ReadingStyle {
+    pub font: ReadingFont,
+    /// Line height relative to the font size
+    pub line_spacing: f32,
+    pub tint: ReadingTint,
+    /// Keep the current sentence in view and dim other paragraphs in the reading view
+    pub focus_follow: bool,
+    /// How much other paragraphs are dimmed in focus-follow mode (0.0 to 0.9)
+    pub focus_dim: f32,
+}
+
+impl Default for ReadingStyle {
+    fn default() -> Self {
+        Self {
+            font: ReadingFont::Default,
+            line_spacing: 1.3,
+            tint: ReadingTint::None,
+            focus_follow: true,
+            focus_dim: 0.6,
+        }
+    }
+}
+
+/// A command offered by the command palette.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PaletteCommand {
+    /// Read the text that was selected when the palette opened
+    ReadSelection,
+    DictateNote,
+    PronunciationTrainer,
+    ClipboardHistory,
+    /// Mark or unmark the reading as a favorite
+    ToggleFavorite,
+    ProcessingChain,
+    ReadingView,
+    Transcript,
+    MiniPlayer,
+    Settings,
+    ReloadPlugins,
+    /// Run a plugin action on the selected text and read the result
+    Plugin(crate::plugins::PluginAction),
 }
 
 #[derive(Debug, Clone)]
@@ -39,39 +307,106 @@ pub enum Message {
     SkipForward,
     PlayPause,
     Stop,
-    Tick,
+    Tick, // Loading animation frame, or refresh of the playback time
+    PlaybackUpdated(crate::providers::PlaybackUpdate), // Progress and visualization pushed by the audio thread
     Settings,
-    CloseSettings,
+    CloseSettings, // Close the settings window, reverting changes that were not applied
+    SettingsTabSelected(SettingsTab), // Settings window page selected
+    SettingsSearchChanged(String), // Settings search box edited
+    SettingsSearchSubmitted, // Enter in the settings search box: go to the first matching section's page
+    ApplySettings, // Save the settings changed since the settings window opened
+    ResetSettingsToDefaults, // Set every setting back to its default (still to be applied)
+    ResetSectionToDefaults(SettingsSection), // Set one settings section back to its defaults (still to be applied)
     ProviderSelected(TTSBackend),
     LogLevelSelected(LogLevel),
     TextCleanupToggled(bool),
     WindowOpened(window::Id),
     WindowClosed(window::Id),
+    WindowResized(window::Id, iced::Size), // A window was resized (logical size)
+    WindowMoved(window::Id, iced::Point), // A window was moved (top-left corner)
     TTSInitialized(Result<(), String>), // Result of async TTS initialization
     SelectedTextFetched(Option<String>), // Result of async text selection fetch
     TextCleanupResponse(Result<String, String>), // Result of Natural Reading API call
-    StartDrag, // Begin dragging the window
+    StartDrag(window::Id), // Begin dragging a main bar or mini player window
+    OpenMiniBar, // Open another mini player, e.g. to move to a different monitor or workspace
+    CloseMiniBar(window::Id), // Close a mini player
+    ShowBarMenu(window::Id), // Right-click on a main bar or mini player: show its menu
+    CloseBarMenu, // Dismiss the main bar menu
+    ReadClipboard, // Read the text on the clipboard (from the main bar menu)
+    ClipboardTextFetched(Option<String>), // Result of reading the clipboard
+    ReplayReading, // Read the last text again from the start
+    ExportAudio, // Save the audio of the last reading to the export folder
+    AudioExported(Result<std::path::PathBuf, String>), // Export of the last reading finished: file written, or error
+    OpenHistory, // Open the reading history file
+    ToggleFavorite, // Mark or unmark the reading as a favorite in the reading history
     VoiceSelected(String), // Voice key selected (e.g., "en_US-lessac-medium")
     VoiceDownloadRequested(String), // Voice key to download
     VoiceDownloaded(Result<String, String>), // Download completion (voice key or error)
+    RepairVoice(String), // Re-download a voice whose model files are damaged
+    VoiceSuggested(Option<VoiceInfo>), // Voice to offer for the language of the text being read, if none installed speaks it
+    DownloadSuggestedVoice, // Voice suggestion popup: download the offered voice
+    DeclineVoiceSuggestion(bool), // Voice suggestion popup dismissed (true: never ask for this language)
+    VoiceSuggestionTimedOut(window::Id), // Voice suggestion popup left unanswered
+    ExecutionProvidersDetected(Vec<ExecutionProvider>), // Hardware the installed Piper can run voices on
+    ExecutionProviderSelected(ExecutionProvider), // Hardware Piper runs voices on changed
+    BenchmarkExecutionProviders, // Time a sample reading on each usable execution provider
+    ExecutionProvidersBenchmarked(Result<Vec<(ExecutionProvider, std::time::Duration)>, String>), // Benchmark timings (or error)
+    PowerPolicySelected(crate::system::PowerPolicy), // How synthesis adapts to running on battery changed
+    PowerSourceChecked(crate::system::PowerSource), // Whether the machine runs on battery, for the settings window
+    SynthesisThreadsChanged(usize), // Threads Piper may use changed (0 = all)
+    SynthesisPrioritySelected(crate::system::ProcessPriority), // Priority of the Piper processes changed
     VoicesJsonLoaded(Result<HashMap<String, VoiceInfo>, String>), // voices.json loaded
     PollyVoicesLoaded(Result<HashMap<String, PollyVoiceInfo>, String>), // AWS Polly voices loaded
+    ElevenLabsVoicesLoaded(Result<Vec<ElevenLabsVoice>, String>), // ElevenLabs voices of the account loaded
+    ElevenLabsApiKeyChanged(String), // ElevenLabs API key field edited
+    SystemVoicesLoaded(Result<Vec<SystemVoice>, String>), // Voices installed with the system listed
+    RefreshVoices(TTSBackend), // "Refresh voices" button: fetch a provider's voice list again
     OpenVoiceSelection(String), // Open voice selection window for language code
+    NativeLanguageNamesToggled(bool), // List voice languages by their native or English names
+    LanguageSearchChanged(String), // Voice language grid search box edited
+    LanguageRegionToggled(crate::voices::labels::WorldRegion), // Fold or unfold a region of the voice language grid
+    VolumeScrolled(f32), // Vertical scroll over the waveform, in lines: up is louder
+    VolumePopoverToggled(window::Id), // Volume icon of a main bar or mini player: show or hide its volume slider
+    VolumeChanged(f32), // Volume slider moved
+    VolumeReleased, // Volume slider let go: save the volume
+    SentenceScrolled(f32), // Vertical scroll over the progress bar, in lines: down moves to later sentences
+    SkipSentence(i32), // Jump by sentences: 1 is the next, -1 the previous
+    SpeedScrolled(f32), // Horizontal scroll over the main window, in lines: right is faster
+    SpeedCycled, // Speed button of the main window: next preset speed
+    ScrollSensitivityChanged(f32), // Scroll wheel sensitivity slider moved
     CloseVoiceSelection, // Close voice selection window
     OpenPollyInfo, // Open AWS Polly pricing info modal
     ClosePollyInfo, // Close AWS Polly pricing info modal
     OpenPollyPricingUrl, // Open AWS Polly pricing URL in browser
+    OpenElevenLabsInfo, // Open ElevenLabs pricing info modal
+    CloseElevenLabsInfo, // Close ElevenLabs pricing info modal
+    OpenElevenLabsPricingUrl, // Open ElevenLabs pricing URL in browser
     OCRBackendSelected(OCRBackend), // OCR backend selected
     OpenOCRInfo, // Open Better OCR info modal
     CloseOCRInfo, // Close Better OCR info modal
     OpenTextCleanupInfo, // Open Natural Reading info modal
     CloseTextCleanupInfo, // Close Natural Reading info modal
     ScreenshotRequested, // User clicked screenshot button
-    ScreenshotCaptured(Result<String, String>), // Screenshot result (file path or error)
-    ScreenshotTextExtracted(Result<String, String>), // Text extracted from screenshot (text or error)
+    ScreenshotCaptured(Result<crate::system::Screenshot, String>), // Screenshot result (file or in memory, or error)
+    ScreenshotTextExtracted(Result<Vec<crate::system::OcrLine>, String>), // Lines of text found in the screenshot (or error)
     #[allow(dead_code)] // Message variant - matched but not directly constructed
     OpenScreenshotViewer, // Open screenshot viewer window
     CloseScreenshotViewer, // Close screenshot viewer window
+    ScreenshotZoom(f32), // Zoom the screenshot viewer by a factor
+    ScreenshotScrolled(iced::mouse::ScrollDelta), // Mouse wheel over the screenshot: zoom
+    ScreenshotFit, // Fit the whole screenshot in the viewer
+    ScreenshotActualSize, // Show the screenshot at 1:1
+    ScreenshotDragStarted, // Mouse pressed on the screenshot: start panning
+    ScreenshotDragEnded, // Mouse released: stop panning
+    ScreenshotCursorMoved(iced::Point), // Cursor moved over the screenshot (pans while dragging)
+    OcrVisibleRegion, // Run OCR again on the part of the screenshot shown in the viewer
+    ToggleOcrBoxes, // Show or hide the OCR line boxes over the screenshot
+    OcrLineClicked(usize), // OCR box clicked: select that line in the extracted text editor
+    ScreenshotQrDecoded(Vec<String>), // Contents of the QR codes found in the screenshot
+    OpenLink(String), // Open a link found in the screenshot in the browser
+    ReadLinkedPage(String), // Fetch the page a link points to and read it
+    LinkedPageFetched(String, Result<String, String>), // URL and text of a fetched page (or error)
+    CopyLink(String), // Copy a link or QR code content to the clipboard
     #[allow(dead_code)] // Message variant - matched but not directly constructed
     OpenExtractedTextDialog, // Open extracted text dialog window
     CloseExtractedTextDialog, // Close extracted text dialog window
@@ -81,14 +416,134 @@ pub enum Message {
     ShowWindow, // Show the main window (from tray menu)
     HideWindow, // Hide the main window (from tray menu)
     ReadSelected, // Read currently selected text (from tray menu)
+    ToggleDictation, // Start recording a dictated note, or stop and transcribe it
+    DictationTranscribed(Result<String, String>), // Transcript of the dictated note (text or error)
+    OpenPronunciationTrainer, // Open pronunciation trainer window
+    ClosePronunciationTrainer, // Close pronunciation trainer window
+    TrainerSentenceChanged(String), // Practice sentence edited
+    TrainerListen, // Synthesize and play the practice sentence
+    TrainerToggleRecording, // Start or stop recording the user's attempt
     Quit, // Quit the application (from tray menu)
     TrayEventReceived, // Poll for tray events
     HotkeyPressed, // Global hotkey was pressed
+    ClipboardImageTextExtracted(Result<String, String>), // Text of the image on the clipboard, for the clipboard image hotkey (or error)
     HotkeyConfigChanged(crate::system::HotkeyConfig), // Hotkey configuration changed
     HotkeyToggled(bool), // Hotkey enabled/disabled
     StartListeningForHotkey, // Start listening for hotkey input
     StopListeningForHotkey, // Stop listening for hotkey input
     HotkeyCaptured(iced::keyboard::Key, iced::keyboard::Modifiers), // Hotkey combination captured
+    WindowKeyPressed(window::Id, iced::keyboard::Key, iced::keyboard::Modifiers), // Key not handled by a widget, for keyboard operation
+    LongTextActionSelected(LongTextAction), // Default action for long captures changed
+    LongTextParagraphsChanged(usize), // Number of paragraphs "Read first" reads changed
+    InterruptReadingsToggled(bool), // Short captures interrupting a long reading enabled/disabled
+    ReadFirstParagraphs, // Long text dialog: read only the first N paragraphs
+    ReadAllText, // Long text dialog: read the whole text
+    OpenLongTextInEditor, // Long text dialog: open the text in the extracted text editor
+    CloseLongTextDialog, // Long text dialog: discard the text
+    ContinueDocument, // Continue dialog: read the document from where it was left
+    RestartDocument, // Continue dialog: read the document from the start
+    CloseResumeDialog, // Continue dialog: discard the document
+    DuplicateCaptureActionSelected(DuplicateCaptureAction), // Action for repeated captures changed
+    SourcePrioritySelected(crate::capture::SourcePriority), // Order the hotkey tries capture sources in changed
+    CaptureSourceChosen(crate::capture::CaptureSource), // Source picked in the capture source prompt
+    CloseCaptureSourcePrompt, // Close the capture source prompt without reading
+    OfferOcrCapture, // No text in the selection or clipboard: offer to capture a screen region
+    PodcastFeedToggled(bool), // Podcast feed enabled/disabled
+    PodcastFeedTitleChanged(String), // Podcast feed title edited
+    PodcastFeedAuthorChanged(String), // Podcast feed author edited
+    CopyPodcastFeedUrl, // Copy the podcast feed URL to the clipboard
+    OpenPodcastFeedFolder, // Open the podcast feed folder in the file manager
+    HistoryToggled(bool), // Reading history enabled/disabled
+    SyncDirInputChanged(String), // Sync folder path edited
+    SyncNow, // Save the sync folder and sync settings and history with it
+    WatchDirInputChanged(String), // Watch folder path edited
+    WatchDirSaved, // Save the watch folder and start or stop watching it
+    WatchedFileAdded(std::path::PathBuf), // New file finished arriving in the watch folder
+    WatchedFileLoaded(Result<String, String>), // Text of a file from the watch folder (or error)
+    WatchFolderFailed(String), // Watching the folder could not start
+    ReadingForwarded(crate::system::ForwardedReading), // Text or files sent by another process (context menu, `read` command)
+    ForwardedFilesLoaded(Result<String, String>), // Text of the forwarded files (or error)
+    OpenCommandPalette, // Capture the selected text, then open the command palette
+    CommandPaletteTextFetched(Option<String>), // Selected text captured for the command palette
+    CloseCommandPalette, // Close the command palette window
+    PaletteFilterChanged(String), // Command palette filter edited
+    RunPaletteCommand(PaletteCommand), // Command chosen in the command palette
+    PluginToggled(String, bool), // Plugin (by name) enabled/disabled
+    ReloadPlugins, // Re-scan the plugin directory
+    OpenPluginsFolder, // Open the plugin directory in the file manager
+    ColorPresetSelected(ColorPreset), // Color scheme preset changed
+    VisualizerStyleSelected(VisualizerStyle), // Audio visualization of the main bar changed
+    CustomColorChanged(ColorRole, String), // Custom color hex input edited
+    ReadingFontSelected(ReadingFont), // Reading text font changed
+    ReadingLineSpacingChanged(f32), // Reading text line spacing changed
+    ReadingTintSelected(ReadingTint), // Reading text background tint changed
+    OpenReadingView, // Open the reading view window for the text being read
+    CloseReadingView, // Close the reading view window
+    FocusFollowToggled(bool), // Reading view focus-follow mode enabled/disabled
+    FocusDimChanged(f32), // Reading view dimming of other paragraphs changed
+    FileDropped(window::Id, std::path::PathBuf), // File dropped on a window
+    DroppedFileLoaded(Result<String, String>), // Text of a file dropped on the main window (text or error)
+    ReadAltTextToggled(bool), // Reading image alt text in HTML selections enabled/disabled
+    ReadMathToggled(bool), // Reading math notation as words enabled/disabled
+    ListProsodyToggled(bool), // Pacing of bulleted and numbered lists enabled/disabled
+    TextProfileSelected(TextProfile), // Cleanup profile for captured text changed
+    EarconsToggled(bool), // Audio cues for reading events enabled/disabled
+    EarconVolumeChanged(f32), // Audio cue volume changed
+    EarconSetSelected(crate::providers::EarconSet), // Audio cue sound set changed
+    FadeDurationChanged(u32), // Length of the playback fades changed (milliseconds)
+    PitchChanged(i32), // Pitch of the Piper and Polly voices changed (semitones)
+    HeadphonesOnlyToggled(bool), // Headphone-only playback enabled/disabled
+    DeferWhileMicActiveToggled(bool), // Holding hotkey readings during calls enabled/disabled
+    HotkeyTextCaptured(Option<String>, bool), // Text selected when the hotkey was pressed, and whether the microphone was in use
+    CheckMicrophone, // Periodic check whether held readings can start
+    MicrophoneChecked(bool), // Result of the microphone check (true if still in use)
+    PauseWhileTypingToggled(bool), // Pausing playback while typing enabled/disabled
+    TypingResumeDelayChanged(u32), // Seconds without typing before playback resumes
+    CheckTyping, // Periodic check whether the user started or stopped typing
+    PreviewBeforeReadingToggled(bool), // Preview popup for hotkey captures enabled/disabled
+    PreviewRead, // Preview popup: read the captured text now
+    PreviewEdit, // Preview popup: open the captured text in the extracted text editor
+    PreviewCancel, // Preview popup: discard the captured text
+    EditBeforeReadingToggled(bool), // Open selection captures in the editor before reading enabled/disabled
+    PanicStopHotkeyToggled(bool), // Register or unregister the panic stop hotkey
+    HotkeyEditVariantToggled(bool), // Register or unregister the hotkey with Shift (edit first)
+    HotkeyImageVariantToggled(bool), // Register or unregister the hotkey with Alt (read the clipboard image)
+    SelectedTextForEditing(Option<String>), // Selected text fetched to be edited before reading
+    SensitiveTextGuardToggled(bool), // Confirmation for texts that look like secrets enabled/disabled
+    PrivateScreenshotsToggled(bool), // Keeping screenshots off the disk enabled/disabled
+    ClipboardHistoryToggled(bool), // Keeping the last captured texts for the clipboard history enabled/disabled
+    OpenClipboardHistory, // Open the clipboard history window
+    CloseClipboardHistory, // Close the clipboard history window
+    ReadClipboardHistoryItem(usize), // Read an earlier capture from the clipboard history
+    ClearClipboardHistory, // Forget the captured texts of the clipboard history
+    OpenProcessingChain, // Open the window showing each stage of the last reading
+    CloseProcessingChain, // Close the processing chain window
+    OpenTranscript, // Open the transcript window for the text being read
+    CloseTranscript, // Close the transcript window
+    TranscriptSentenceClicked(usize), // Transcript sentence clicked: seek playback to it
+    ExportRecipientsChanged(String), // age recipients exports are encrypted to edited
+    ReadSensitiveText, // Sensitive text dialog: read the capture anyway
+    CloseSensitiveDialog, // Sensitive text dialog dismissed, discard the capture
+    LocalOnlyAppsChanged(String), // List of apps whose captures stay offline edited
+    CleanupScheduleSelected(crate::storage::CleanupSchedule), // How often the storage cleanup runs changed
+    HistoryMaxDaysSelected(u32), // Days of history kept by the cleanup changed (0 = forever)
+    SynthesisCacheSizeSelected(u32), // Size limit of the synthesis cache changed, in MB (0 = off)
+    CleanupTick, // Periodic check whether the cleanup is due while idle
+    PhraseWarmupTick, // Periodic check whether frequent phrases can be synthesized ahead while idle
+    PhrasesWarmed(usize), // Number of frequent phrases synthesized into the phrase cache
+    WeeklySummaryToggled(bool), // Weekly listening summary enabled/disabled
+    WeeklySummaryDaySelected(chrono::Weekday), // Day of the weekly summary changed
+    WeeklySummaryHourChanged(u32), // Hour of the weekly summary changed
+    WeeklySummaryTick, // Periodic check whether the weekly summary is due
+    CloseWeeklySummary, // Weekly summary: close the window
+    WeeklySummaryOptOut, // Weekly summary: close it and stop showing it
+    RunStorageCleanup, // Prune caches, screenshots, logs and history by age
+    StorageCleanedUp(u64), // Cleanup finished, with the bytes freed
+    MeasureStorage, // Measure disk usage per storage category
+    StorageMeasured(Vec<(crate::storage::StorageCategory, u64)>), // Disk usage measured
+    PurgeStorage(crate::storage::StorageCategory), // Delete everything in a storage category
+    StoragePurged(crate::storage::StorageCategory, u64), // Purge finished, with the bytes freed
+    PreviewTimedOut(window::Id), // Preview popup shown long enough, read unless dismissed
 }
 
 /// Voice metadata from piper-voices repository
@@ -108,6 +563,10 @@ pub struct VoiceInfo {
 
 // Re-export PollyVoiceInfo from voices::aws module
 pub use crate::voices::aws::PollyVoiceInfo;
+pub use crate::voices::elevenlabs::ElevenLabsVoice;
+pub use crate::voices::system::SystemVoice;
+pub use crate::providers::VisualizerStyle;
+pub use crate::capture::{CaptureSource, SourcePriority};
 
 /// Language information for a voice
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
@@ -127,13 +586,33 @@ pub struct FileInfo {
     pub md5_digest: String,
 }
 
+/// A long reading paused for a short interrupting one, with what is needed
+/// to carry on with it once that ends.
+pub struct InterruptedReading {
+    /// Provider holding the paused audio
+    pub provider: Box<dyn TTSProvider>,
+    /// Text being read, for the reading view
+    pub text: Option<String>,
+    /// Sentences of the reading, for the transcript
+    pub transcript: Vec<String>,
+    /// Sentences of the reading as spoken, for a voice change
+    pub spoken_sentences: Vec<String>,
+    /// File or web page being read, to remember how far it got
+    pub document: Option<crate::history::DocumentReading>,
+    /// Voice and text of the reading, cached once it plays to its end
+    pub synthesis_cache_key: Option<(String, String)>,
+}
+
 /// Application state.
 ///
 /// Note: Does not derive `Clone` because the TTS provider contains
 /// audio resources that cannot be cloned.
 pub struct App {
-    pub playback_state: PlaybackState,
+    /// What the app is doing with speech (see [`crate::activity`])
+    pub activity: Activity,
     pub progress: f32,
+    /// Playback position and length of the speech, refreshed on Tick
+    pub playback_time: (std::time::Duration, std::time::Duration),
     pub frequency_bands: Vec<f32>,
     pub provider: Option<Box<dyn TTSProvider>>,
     pub selected_backend: TTSBackend,
@@ -145,9 +624,9 @@ pub struct App {
     pub main_window_id: Option<window::Id>,
     pub pending_text: Option<String>,
     pub error_message: Option<String>,
-    pub is_loading: bool,
     pub loading_animation_time: f32,
-    /// Status text shown during loading (e.g., "Cleaning text...", "Synthesizing voice...")
+    /// Note shown in the status line when the activity has no status of its
+    /// own (e.g., "Link copied to clipboard", "No speech detected")
     pub status_text: Option<String>,
     /// Selected voice key (e.g., "en_US-lessac-medium")
     pub selected_voice: Option<String>,
@@ -161,14 +640,36 @@ pub struct App {
     pub polly_error_message: Option<String>,
     /// Selected AWS Polly voice ID (e.g., "Matthew", "Joanna")
     pub selected_polly_voice: Option<String>,
+    /// Voices of the ElevenLabs account, sorted by name
+    pub elevenlabs_voices: Option<Vec<ElevenLabsVoice>>,
+    /// Whether the ElevenLabs voice list is being fetched
+    pub elevenlabs_voices_loading: bool,
+    /// Error message from ElevenLabs voice loading
+    pub elevenlabs_error_message: Option<String>,
+    /// Selected ElevenLabs voice ID
+    pub selected_elevenlabs_voice: Option<String>,
+    /// ElevenLabs API key as typed in the settings
+    pub elevenlabs_api_key: String,
+    /// Voices installed with the system (macOS or Windows), the user's language first
+    pub system_voices: Option<Vec<SystemVoice>>,
+    /// Error message from listing the system voices
+    pub system_voices_error: Option<String>,
+    /// Selected macOS system voice identifier
+    pub selected_macos_voice: Option<String>,
+    /// Selected Windows system voice identifier
+    pub selected_windows_voice: Option<String>,
     /// Voice selection window ID
     pub voice_selection_window_id: Option<window::Id>,
     /// Voice currently being downloaded (if any)
     pub downloading_voice: Option<String>,
     /// AWS Polly info modal window ID
     pub polly_info_window_id: Option<window::Id>,
-    /// Path to the captured screenshot file
-    pub screenshot_path: Option<String>,
+    /// ElevenLabs info modal window ID
+    pub elevenlabs_info_window_id: Option<window::Id>,
+    /// Captured screenshot, as a file or in memory
+    pub screenshot: Option<crate::system::Screenshot>,
+    /// Image of the captured screenshot for the viewer, made once per capture
+    pub screenshot_handle: Option<iced::widget::image::Handle>,
     /// Screenshot viewer window ID
     pub screenshot_window_id: Option<window::Id>,
     /// Selected OCR backend
@@ -197,13 +698,282 @@ pub struct App {
     pub listening_for_hotkey: bool,
     /// Whether hotkeys are disabled due to Wayland/Hyprland (not supported)
     pub hotkeys_disabled_wayland: bool,
+    /// Default action for very long captures
+    pub long_text_action: LongTextAction,
+    /// Number of paragraphs read by "Read first N paragraphs"
+    pub long_text_paragraphs: usize,
+    /// Let short captures interrupt a long reading, which resumes after them
+    pub interrupt_readings: bool,
+    /// Long reading put aside for a short one, resumed once that ends
+    pub interrupted_reading: Option<InterruptedReading>,
+    /// Long text dialog window ID
+    pub long_text_dialog_window_id: Option<window::Id>,
+    /// Long captured text waiting for a decision in the long text dialog
+    pub long_text_pending: Option<String>,
+    /// What to do when the same text is captured twice in a row
+    pub duplicate_capture_action: DuplicateCaptureAction,
+    /// Content hash of the last captured text sent to TTS (see `text::content_hash`)
+    pub last_capture_hash: Option<String>,
+    /// Active microphone recording for a dictated note
+    pub dictation_recorder: Option<crate::system::Recorder>,
+    /// Pronunciation trainer window ID
+    pub trainer_window_id: Option<window::Id>,
+    /// Sentence being practiced in the pronunciation trainer
+    pub trainer_sentence: String,
+    /// Synthesized reference audio for the practice sentence (mono, 16 kHz, trimmed)
+    pub trainer_reference: Option<Vec<f32>>,
+    /// Recorded attempt at the practice sentence (mono, 16 kHz, trimmed)
+    pub trainer_attempt: Option<Vec<f32>>,
+    /// Active microphone recording of a pronunciation attempt
+    pub trainer_recorder: Option<crate::system::Recorder>,
+    /// Whether the next synthesized audio should become the trainer's reference
+    pub trainer_awaiting_reference: bool,
+    /// Podcast feed settings
+    pub feed_config: crate::system::FeedConfig,
+    /// Running podcast feed server (when the feed is enabled)
+    pub feed_server: Option<crate::system::FeedServer>,
+    /// Feed URL shown in settings (resolved when the server starts)
+    pub feed_url: String,
+    /// Error from starting the podcast feed server
+    pub feed_error: Option<String>,
+    /// Whether texts read aloud are kept in the reading history
+    pub history_enabled: bool,
+    /// Sync folder path as typed in settings
+    pub sync_dir_input: String,
+    /// Result of the last manual sync, shown in settings
+    pub sync_status: Option<String>,
+    /// User plugins (text transforms and command palette actions)
+    pub plugin_host: crate::plugins::PluginHost,
+    /// Command palette window ID
+    pub palette_window_id: Option<window::Id>,
+    /// Filter typed into the command palette
+    pub palette_filter: String,
+    /// Text selected when the command palette was opened (input for plugin actions)
+    pub palette_text: Option<String>,
+    /// voices.json fetch in flight (fetched when settings first opens)
+    pub voices_loading: bool,
+    /// AWS Polly voice list fetch in flight
+    pub polly_voices_loading: bool,
+    /// Color scheme of the main bar and controls
+    pub color_preset: ColorPreset,
+    /// Colors used by the custom color scheme
+    pub custom_colors: CustomColors,
+    /// How the audio being played is shown on the main bar
+    pub visualizer_style: VisualizerStyle,
+    /// Font, line spacing and tint of text shown for reading
+    pub reading_style: ReadingStyle,
+    /// Reading view window ID
+    pub reading_view_window_id: Option<window::Id>,
+    /// Text being read, as shown in the reading view
+    pub reading_text: Option<String>,
+    /// Word being spoken, when the provider reports word timings
+    pub current_word: Option<WordMark>,
+    /// Reading history entry (content hash) of the reading, if it was recorded
+    pub history_entry: Option<String>,
+    /// Whether that entry is a favorite, kept by every history cleanup
+    pub reading_favorite: bool,
+    /// Read image alt text in HTML selections
+    pub read_alt_text: bool,
+    /// Read math notation (LaTeX, exponents, symbols) as words
+    pub read_math: bool,
+    /// Pause before list items and read numbered items with ordinals
+    pub list_prosody: bool,
+    /// Cleanup profile for the kind of text captured
+    pub text_profile: TextProfile,
+    /// Audio cues played for reading events
+    pub earcons: crate::providers::EarconSettings,
+    /// Piper voice whose model failed the integrity check, offered for re-download
+    pub damaged_voice: Option<String>,
+    /// Start readings paused unless headphones are the audio output
+    pub headphones_only: bool,
+    /// Hold hotkey readings while another app uses the microphone
+    pub defer_while_mic_active: bool,
+    /// Texts captured by the hotkey during a call, read once the microphone is free
+    pub held_readings: Vec<String>,
+    /// Show a preview popup for hotkey captures before reading them
+    pub preview_before_reading: bool,
+    /// Preview popup window for a hotkey capture
+    pub preview_window_id: Option<window::Id>,
+    /// Text shown in the preview popup, read when it times out
+    pub preview_text: Option<String>,
+    /// Open selection captures in the text editor before reading them
+    pub edit_before_reading: bool,
+    /// Page shown in the settings window
+    pub settings_tab: SettingsTab,
+    /// Search box contents of the settings window; when not empty, matching sections of every tab are shown
+    pub settings_search: String,
+    /// Ask before reading texts that look like passwords or tokens
+    pub sensitive_text_guard: bool,
+    /// Confirmation dialog for a capture that looks like a secret
+    pub sensitive_dialog_window_id: Option<window::Id>,
+    /// Text waiting for confirmation, with the kind of secret it seems to
+    /// contain and where it was read from
+    pub sensitive_pending: Option<(String, &'static str, &'static str)>,
+    /// Apps whose captures are only read with Piper (comma-separated names or window title words)
+    pub local_only_apps: String,
+    /// Application the text being read was captured from
+    pub capture_app: Option<crate::system::ForegroundApp>,
+    /// How often old caches, screenshots, logs and history are cleaned up
+    pub cleanup_schedule: crate::storage::CleanupSchedule,
+    /// Days of reading history kept by the cleanup job (0 keeps everything)
+    pub history_max_days: u32,
+    /// Size limit of the synthesis cache in megabytes (0 = off)
+    pub synthesis_cache_mb: u32,
+    /// Voice and text of the reading playing, cached once it plays to its end
+    pub synthesis_cache_key: Option<(String, String)>,
+    /// Disk usage per storage category, measured when the Advanced settings tab is shown
+    pub storage_usage: Vec<(crate::storage::StorageCategory, u64)>,
+    /// Result of the last cleanup or purge, shown in the Storage section
+    pub storage_status: Option<String>,
+    /// Whether the storage cleanup job is running
+    pub cleanup_running: bool,
+    /// Extra mini player windows, controlling the same playback as the main bar
+    pub mini_bar_window_ids: Vec<window::Id>,
+    /// Kind, size and position of open windows whose geometry is remembered, saved when they close
+    pub window_geometry: HashMap<window::Id, (&'static str, config::WindowGeometry)>,
+    /// Zoom and pan of the screenshot viewer
+    pub screenshot_viewport: crate::ui::screenshot::ScreenshotViewport,
+    /// Whether the screenshot is being dragged to pan it
+    pub screenshot_dragging: bool,
+    /// Last cursor position over the screenshot, for panning by drag
+    pub screenshot_cursor: Option<iced::Point>,
+    /// Lines found by OCR in the screenshot, in screenshot pixels, in the order of the extracted text
+    pub ocr_lines: Vec<crate::system::OcrLine>,
+    /// Whether the OCR line boxes are drawn over the screenshot
+    pub show_ocr_boxes: bool,
+    /// Contents of the QR codes found in the screenshot
+    pub qr_codes: Vec<String>,
+    /// Watch folder path being edited in settings
+    pub watch_dir_input: String,
+    /// Folder whose new text files are read automatically
+    pub watch_dir: Option<std::path::PathBuf>,
+    /// Result of the last watch folder change, shown in settings
+    pub watch_status: Option<String>,
+    /// Texts waiting for the current reading to finish (files from the watch folder)
+    pub queued_readings: std::collections::VecDeque<String>,
+    /// Pause playback while the user is typing
+    pub pause_while_typing: bool,
+    /// Seconds without typing before a reading paused for typing resumes
+    pub typing_resume_secs: u32,
+    /// Whether playback was paused because the user started typing
+    pub paused_for_typing: bool,
+    /// When typing was last checked during playback
+    pub typing_checked_at: Option<std::time::Instant>,
+    /// Voice offered for download because no installed voice speaks the language being read
+    pub voice_suggestion: Option<VoiceInfo>,
+    /// Voice suggestion popup window ID
+    pub voice_suggestion_window_id: Option<window::Id>,
+    /// Language families no voice download is suggested for (declined this session or for good)
+    pub declined_voice_languages: Vec<String>,
+    /// Hardware Piper runs voices on
+    pub execution_provider: ExecutionProvider,
+    /// Execution providers the installed Piper can run on, once detected
+    pub available_execution_providers: Option<Vec<ExecutionProvider>>,
+    /// Execution provider benchmark in progress
+    pub execution_benchmark_running: bool,
+    /// Timings of the last execution provider benchmark (or its error)
+    pub execution_benchmark: Option<Result<Vec<(ExecutionProvider, std::time::Duration)>, String>>,
+    /// How synthesis adapts to running on battery
+    pub power_policy: crate::system::PowerPolicy,
+    /// Power source when the settings window was opened
+    pub power_source: Option<crate::system::PowerSource>,
+    /// Threads Piper may use, 0 for all of them
+    pub synthesis_threads: usize,
+    /// Priority the Piper processes run at
+    pub synthesis_priority: crate::system::ProcessPriority,
+    /// Whether voice languages are listed by their native names
+    pub native_language_names: bool,
+    /// Filter typed in the search box of the voice language grid
+    pub language_search: String,
+    /// Regions folded away in the voice language grid
+    pub collapsed_language_regions: Vec<crate::voices::labels::WorldRegion>,
+    /// Voice languages browsed most recently, newest first
+    pub recent_languages: Vec<String>,
+    /// Voices of the selected provider chosen most recently, newest first
+    pub recent_voices: Vec<String>,
+    /// Speech volume (0.0 to 1.0), saved
+    pub playback_volume: f32,
+    /// Playback speed multiplier (0.5 to 3.0), saved
+    pub playback_speed: f32,
+    /// Scrolling over the progress bar not yet enough for a whole sentence
+    pub scroll_seek_lines: f32,
+    /// How far a scroll wheel notch on the main window moves volume, speed and position
+    pub scroll_sensitivity: f32,
+    /// Main bar or mini player showing its right-click menu instead of the controls
+    pub bar_menu_window: Option<window::Id>,
+    /// Whether the panic stop hotkey is registered, even with the reading hotkey off
+    pub panic_stop_hotkey: bool,
+    /// Whether the hotkey is registered with Shift added too, to edit the text first
+    pub hotkey_edit_variant: bool,
+    /// Whether the hotkey is registered with Alt added too, to read the clipboard image
+    pub hotkey_image_variant: bool,
+    /// Length of the fades on play, pause, stop and seek, in milliseconds
+    pub fade_ms: u32,
+    /// Pitch of the Piper and Polly voices, in semitones from their own
+    pub pitch_semitones: i32,
+    /// File or web page being read, to remember how far it got
+    pub current_document: Option<crate::history::DocumentReading>,
+    /// Document read before (text, saved progress, context), waiting for the continue dialog
+    pub resume_pending: Option<(String, f32, &'static str)>,
+    /// Continue reading dialog window ID
+    pub resume_dialog_window_id: Option<window::Id>,
+    /// Where to start the next reading once synthesized, to continue a document
+    pub pending_seek: Option<f32>,
+    /// Main bar or mini player showing the volume slider instead of the controls
+    pub volume_popover_window: Option<window::Id>,
+    /// Show a summary of the week's listening once a week
+    pub weekly_summary_enabled: bool,
+    /// Day of the weekly summary
+    pub weekly_summary_day: chrono::Weekday,
+    /// Hour of the weekly summary (local time)
+    pub weekly_summary_hour: u32,
+    /// Weekly summary window
+    pub weekly_summary_window_id: Option<window::Id>,
+    /// Text of the weekly summary being shown
+    pub weekly_summary_text: Option<String>,
+    /// Seconds of the current reading played so far, for the listening statistics
+    pub listened_secs: f32,
+    /// Keep screenshots in memory for OCR instead of saving them to the temp dir
+    pub private_screenshots: bool,
+    /// age recipients exports are encrypted to (comma-separated, edited in the privacy settings)
+    pub export_recipients: String,
+    /// Frequent phrases are being synthesized into the phrase cache
+    pub phrase_warmup_running: bool,
+    /// Keep the last captured texts for the clipboard history window (opt-in)
+    pub clipboard_history_enabled: bool,
+    /// Last texts captured, newest first; kept in memory only
+    pub clipboard_history: std::collections::VecDeque<String>,
+    /// Clipboard history window ID
+    pub clipboard_history_window_id: Option<window::Id>,
+    /// Order the hotkey tries capture sources in
+    pub source_priority: crate::capture::SourcePriority,
+    /// Capture source prompt window ID (asking for a source, or offering OCR)
+    pub capture_source_window_id: Option<window::Id>,
+    /// Whether the capture source prompt asks for a source rather than offering OCR
+    pub capture_source_asking: bool,
+    /// Whether the hotkey press the prompt is for opens the text in the editor first
+    pub capture_source_edit_first: bool,
+    /// Stages the last reading went through, for the Processing Chain window
+    pub processing_trace: crate::text::pipeline::Trace,
+    /// Processing Chain window ID
+    pub processing_chain_window_id: Option<window::Id>,
+    /// Sentences of the text being spoken, as the provider synthesizes them
+    pub transcript: Vec<String>,
+    /// Index in `transcript` of the sentence being spoken
+    pub transcript_sentence: Option<usize>,
+    /// The sentences of `transcript` as handed to the provider, with their
+    /// list pacing, to read the rest again with another voice
+    pub spoken_sentences: Vec<String>,
+    /// Transcript window ID
+    pub transcript_window_id: Option<window::Id>,
 }
 
 impl Default for App {
     fn default() -> Self {
         Self {
-            playback_state: PlaybackState::Stopped,
+            activity: Activity::Idle,
             progress: 0.0,
+            playback_time: Default::default(),
             frequency_bands: vec![0.0; 10],
             provider: None,
             selected_backend: TTSBackend::Piper,
@@ -215,7 +985,6 @@ impl Default for App {
             main_window_id: None,
             pending_text: None,
             error_message: None,
-            is_loading: false,
             loading_animation_time: 0.0,
             status_text: None,
             selected_voice: None,
@@ -224,10 +993,21 @@ impl Default for App {
             polly_voices: None,
             polly_error_message: None,
             selected_polly_voice: None,
+            elevenlabs_voices: None,
+            elevenlabs_voices_loading: false,
+            elevenlabs_error_message: None,
+            selected_elevenlabs_voice: None,
+            elevenlabs_api_key: String::new(),
+            system_voices: None,
+            system_voices_error: None,
+            selected_macos_voice: None,
+            selected_windows_voice: None,
             voice_selection_window_id: None,
             downloading_voice: None,
             polly_info_window_id: None,
-            screenshot_path: None,
+            elevenlabs_info_window_id: None,
+            screenshot: None,
+            screenshot_handle: None,
             screenshot_window_id: None,
             selected_ocr_backend: OCRBackend::Default,
             ocr_info_window_id: None,
@@ -242,11 +1022,227 @@ impl Default for App {
             hotkey_enabled: false,
             listening_for_hotkey: false,
             hotkeys_disabled_wayland: false,
+            long_text_action: LongTextAction::Ask,
+            long_text_paragraphs: config::DEFAULT_LONG_TEXT_PARAGRAPHS,
+            interrupt_readings: true,
+            interrupted_reading: None,
+            long_text_dialog_window_id: None,
+            long_text_pending: None,
+            duplicate_capture_action: DuplicateCaptureAction::Replay,
+            last_capture_hash: None,
+            dictation_recorder: None,
+            trainer_window_id: None,
+            trainer_sentence: String::new(),
+            trainer_reference: None,
+            trainer_attempt: None,
+            trainer_recorder: None,
+            trainer_awaiting_reference: false,
+            feed_config: crate::system::FeedConfig::default(),
+            feed_server: None,
+            feed_url: String::new(),
+            feed_error: None,
+            history_enabled: true,
+            sync_dir_input: String::new(),
+            sync_status: None,
+            plugin_host: crate::plugins::PluginHost::default(),
+            palette_window_id: None,
+            palette_filter: String::new(),
+            palette_text: None,
+            voices_loading: false,
+            polly_voices_loading: false,
+            color_preset: ColorPreset::Default,
+            custom_colors: CustomColors::default(),
+            visualizer_style: VisualizerStyle::Bars,
+            reading_style: ReadingStyle::default(),
+            reading_view_window_id: None,
+            reading_text: None,
+            current_word: None,
+            history_entry: None,
+            reading_favorite: false,
+            read_alt_text: false,
+            read_math: true,
+            list_prosody: true,
+            text_profile: TextProfile::Auto,
+            earcons: crate::providers::EarconSettings::default(),
+            damaged_voice: None,
+            headphones_only: false,
+            defer_while_mic_active: false,
+            held_readings: Vec::new(),
+            preview_before_reading: false,
+            preview_window_id: None,
+            preview_text: None,
+            edit_before_reading: false,
+            settings_tab: SettingsTab::General,
+            settings_search: String::new(),
+            sensitive_text_guard: true,
+            sensitive_dialog_window_id: None,
+            sensitive_pending: None,
+            local_only_apps: String::new(),
+            capture_app: None,
+            cleanup_schedule: crate::storage::CleanupSchedule::Weekly,
+            history_max_days: 0,
+            synthesis_cache_mb: crate::synthesis_cache::DEFAULT_LIMIT_MB,
+            synthesis_cache_key: None,
+            storage_usage: Vec::new(),
+            storage_status: None,
+            cleanup_running: false,
+            mini_bar_window_ids: Vec::new(),
+            window_geometry: HashMap::new(),
+            screenshot_viewport: Default::default(),
+            screenshot_dragging: false,
+            screenshot_cursor: None,
+            ocr_lines: Vec::new(),
+            show_ocr_boxes: true,
+            qr_codes: Vec::new(),
+            watch_dir_input: String::new(),
+            watch_dir: None,
+            watch_status: None,
+            queued_readings: std::collections::VecDeque::new(),
+            pause_while_typing: false,
+            typing_resume_secs: config::DEFAULT_TYPING_RESUME_SECS,
+            paused_for_typing: false,
+            typing_checked_at: None,
+            voice_suggestion: None,
+            voice_suggestion_window_id: None,
+            declined_voice_languages: Vec::new(),
+            execution_provider: ExecutionProvider::Cpu,
+            available_execution_providers: None,
+            execution_benchmark_running: false,
+            execution_benchmark: None,
+            power_policy: crate::system::PowerPolicy::Balanced,
+            power_source: None,
+            synthesis_threads: 0,
+            synthesis_priority: crate::system::ProcessPriority::Normal,
+            native_language_names: true,
+            language_search: String::new(),
+            collapsed_language_regions: Vec::new(),
+            recent_languages: Vec::new(),
+            recent_voices: Vec::new(),
+            playback_volume: 1.0,
+            playback_speed: 1.0,
+            scroll_seek_lines: 0.0,
+            scroll_sensitivity: 1.0,
+            bar_menu_window: None,
+            panic_stop_hotkey: true,
+            hotkey_edit_variant: false,
+            hotkey_image_variant: false,
+            fade_ms: crate::providers::DEFAULT_FADE_MS,
+            pitch_semitones: 0,
+            current_document: None,
+            resume_pending: None,
+            resume_dialog_window_id: None,
+            pending_seek: None,
+            volume_popover_window: None,
+            weekly_summary_enabled: true,
+            weekly_summary_day: chrono::Weekday::Sun,
+            weekly_summary_hour: 18,
+            weekly_summary_window_id: None,
+            weekly_summary_text: None,
+            listened_secs: 0.0,
+            private_screenshots: false,
+            export_recipients: String::new(),
+            phrase_warmup_running: false,
+            clipboard_history_enabled: false,
+            clipboard_history: std::collections::VecDeque::new(),
+            clipboard_history_window_id: None,
+            source_priority: crate::capture::SourcePriority::SelectionFirst,
+            capture_source_window_id: None,
+            capture_source_asking: false,
+            capture_source_edit_first: false,
+            processing_trace: crate::text::pipeline::Trace::default(),
+            processing_chain_window_id: None,
+            transcript: Vec::new(),
+            transcript_sentence: None,
+            spoken_sentences: Vec::new(),
+            transcript_window_id: None,
         }
     }
 }
 
 impl App {
+    /// Settings at their default values, for "Reset to defaults".
+    pub fn default_settings() -> Self {
+        Self {
+            hotkey_enabled: true,
+            ..Self::default()
+        }
+    }
+
+    /// Settings as saved in the config file, for reverting staged changes.
+    ///
+    /// Only settings shown in the settings window are loaded; everything else
+    /// (windows, providers, plugins) is left at its default.
+    pub fn saved_settings() -> Self {
+        let (hotkey_config, hotkey_enabled) = config::load_hotkey_config();
+        let (cleanup_schedule, history_max_days) = config::load_cleanup_settings();
+        let (weekly_summary_enabled, weekly_summary_day, weekly_summary_hour) = config::load_weekly_summary();
+        let (long_text_action, long_text_paragraphs) = config::load_long_text_settings();
+        let (color_preset, custom_colors) = config::load_color_scheme();
+        let (pause_while_typing, typing_resume_secs) = config::load_typing_pause();
+        let (synthesis_threads, synthesis_priority) = config::load_synthesis_resources();
+        Self {
+            selected_backend: config::load_voice_provider(),
+            log_level: config::load_log_level(),
+            text_cleanup_enabled: config::load_text_cleanup_enabled(),
+            selected_voice: config::load_selected_voice(),
+            selected_polly_voice: config::load_selected_polly_voice(),
+            selected_elevenlabs_voice: config::load_selected_elevenlabs_voice(),
+            elevenlabs_api_key: config::load_elevenlabs_api_key(),
+            selected_macos_voice: config::load_selected_macos_voice(),
+            selected_windows_voice: config::load_selected_windows_voice(),
+            selected_ocr_backend: config::load_ocr_backend(),
+            execution_provider: config::load_execution_provider(),
+            power_policy: config::load_power_policy(),
+            synthesis_threads,
+            synthesis_priority,
+            native_language_names: config::load_native_language_names(),
+            scroll_sensitivity: config::load_scroll_sensitivity(),
+            panic_stop_hotkey: config::load_panic_stop_hotkey(),
+            hotkey_edit_variant: config::load_hotkey_edit_variant(),
+            hotkey_image_variant: config::load_hotkey_image_variant(),
+            fade_ms: config::load_fade_ms(),
+            pitch_semitones: config::load_pitch_semitones(),
+            hotkey_config,
+            hotkey_enabled,
+            long_text_action,
+            long_text_paragraphs,
+            interrupt_readings: config::load_interrupt_readings(),
+            duplicate_capture_action: config::load_duplicate_capture_action(),
+            source_priority: config::load_source_priority(),
+            feed_config: config::load_feed_config(),
+            history_enabled: config::load_history_enabled(),
+            sync_dir_input: config::load_sync_dir().map(|d| d.display().to_string()).unwrap_or_default(),
+            watch_dir_input: config::load_watch_dir().map(|d| d.display().to_string()).unwrap_or_default(),
+            color_preset,
+            custom_colors,
+            visualizer_style: config::load_visualizer_style(),
+            reading_style: config::load_reading_style(),
+            read_alt_text: config::load_read_alt_text(),
+            read_math: config::load_read_math(),
+            list_prosody: config::load_list_prosody(),
+            text_profile: config::load_text_profile(),
+            earcons: config::load_earcon_settings(),
+            headphones_only: config::load_headphones_only(),
+            defer_while_mic_active: config::load_defer_while_mic_active(),
+            pause_while_typing,
+            typing_resume_secs,
+            preview_before_reading: config::load_preview_before_reading(),
+            edit_before_reading: config::load_edit_before_reading(),
+            sensitive_text_guard: config::load_sensitive_text_guard(),
+            local_only_apps: config::load_local_only_apps(),
+            private_screenshots: config::load_private_screenshots(),
+            export_recipients: config::load_export_recipients(),
+            clipboard_history_enabled: config::load_clipboard_history(),
+            cleanup_schedule,
+            history_max_days,
+            synthesis_cache_mb: config::load_synthesis_cache_mb(),
+            weekly_summary_enabled,
+            weekly_summary_day,
+            weekly_summary_hour,
+            ..Self::default()
+        }
+    }
+
     /// Create a new app with pending text to speak.
     pub fn new(pending_text: Option<String>) -> Self {
         let selected_backend = config::load_voice_provider();
@@ -255,9 +1251,29 @@ impl App {
         let selected_voice = config::load_selected_voice();
         let selected_ocr_backend = config::load_ocr_backend();
         let (hotkey_config, hotkey_enabled) = config::load_hotkey_config();
+        let (long_text_action, long_text_paragraphs) = config::load_long_text_settings();
+        let duplicate_capture_action = config::load_duplicate_capture_action();
+        let (color_preset, custom_colors) = config::load_color_scheme();
+        let earcons = config::load_earcon_settings();
+        crate::providers::set_earcon_settings(earcons);
+        let (cleanup_schedule, history_max_days) = config::load_cleanup_settings();
+        let (pause_while_typing, typing_resume_secs) = config::load_typing_pause();
+        let (synthesis_threads, synthesis_priority) = config::load_synthesis_resources();
+        let playback_speed = config::load_playback_speed();
+        crate::providers::set_playback_speed(playback_speed);
+        let playback_volume = config::load_playback_volume();
+        crate::providers::set_playback_volume(playback_volume);
+        let fade_ms = config::load_fade_ms();
+        crate::providers::set_fade_duration(fade_ms);
+        let pitch_semitones = config::load_pitch_semitones();
+        crate::providers::set_pitch(pitch_semitones);
+        let visualizer_style = config::load_visualizer_style();
+        crate::providers::set_visualizer_style(visualizer_style);
+        let (weekly_summary_enabled, weekly_summary_day, weekly_summary_hour) = config::load_weekly_summary();
         Self {
-            playback_state: PlaybackState::Stopped,
+            activity: Activity::Idle,
             progress: 0.0,
+            playback_time: Default::default(),
             frequency_bands: vec![0.0; 10],
             provider: None,
             selected_backend,
@@ -269,19 +1285,29 @@ impl App {
             main_window_id: None,
             pending_text,
             error_message: None,
-            is_loading: false,
             loading_animation_time: 0.0,
             status_text: None,
             selected_voice,
-            selected_language: None,
+            selected_language: config::load_last_language(selected_backend),
             voices: None,
             polly_voices: None,
             polly_error_message: None,
             selected_polly_voice: config::load_selected_polly_voice(),
+            elevenlabs_voices: None,
+            elevenlabs_voices_loading: false,
+            elevenlabs_error_message: None,
+            selected_elevenlabs_voice: config::load_selected_elevenlabs_voice(),
+            elevenlabs_api_key: config::load_elevenlabs_api_key(),
+            system_voices: None,
+            system_voices_error: None,
+            selected_macos_voice: config::load_selected_macos_voice(),
+            selected_windows_voice: config::load_selected_windows_voice(),
             voice_selection_window_id: None,
             downloading_voice: None,
             polly_info_window_id: None,
-            screenshot_path: None,
+            elevenlabs_info_window_id: None,
+            screenshot: None,
+            screenshot_handle: None,
             screenshot_window_id: None,
             selected_ocr_backend,
             ocr_info_window_id: None,
@@ -296,6 +1322,139 @@ impl App {
             hotkey_enabled,
             listening_for_hotkey: false,
             hotkeys_disabled_wayland: crate::system::is_wayland_hyprland(),
+            long_text_action,
+            long_text_paragraphs,
+            interrupt_readings: config::load_interrupt_readings(),
+            interrupted_reading: None,
+            long_text_dialog_window_id: None,
+            long_text_pending: None,
+            duplicate_capture_action,
+            last_capture_hash: None,
+            dictation_recorder: None,
+            trainer_window_id: None,
+            trainer_sentence: String::new(),
+            trainer_reference: None,
+            trainer_attempt: None,
+            trainer_recorder: None,
+            trainer_awaiting_reference: false,
+            feed_config: config::load_feed_config(),
+            feed_server: None,
+            feed_url: String::new(),
+            feed_error: None,
+            history_enabled: config::load_history_enabled(),
+            sync_dir_input: config::load_sync_dir().map(|d| d.display().to_string()).unwrap_or_default(),
+            sync_status: None,
+            plugin_host: crate::plugins::PluginHost::load(&config::load_disabled_plugins()),
+            palette_window_id: None,
+            palette_filter: String::new(),
+            palette_text: None,
+            voices_loading: false,
+            polly_voices_loading: false,
+            color_preset,
+            custom_colors,
+            visualizer_style,
+            reading_style: config::load_reading_style(),
+            reading_view_window_id: None,
+            reading_text: None,
+            current_word: None,
+            history_entry: None,
+            reading_favorite: false,
+            read_alt_text: config::load_read_alt_text(),
+            read_math: config::load_read_math(),
+            list_prosody: config::load_list_prosody(),
+            text_profile: config::load_text_profile(),
+            earcons,
+            damaged_voice: None,
+            headphones_only: config::load_headphones_only(),
+            defer_while_mic_active: config::load_defer_while_mic_active(),
+            held_readings: Vec::new(),
+            preview_before_reading: config::load_preview_before_reading(),
+            preview_window_id: None,
+            preview_text: None,
+            edit_before_reading: config::load_edit_before_reading(),
+            settings_tab: SettingsTab::General,
+            settings_search: String::new(),
+            sensitive_text_guard: config::load_sensitive_text_guard(),
+            sensitive_dialog_window_id: None,
+            sensitive_pending: None,
+            local_only_apps: config::load_local_only_apps(),
+            capture_app: None,
+            cleanup_schedule,
+            history_max_days,
+            synthesis_cache_mb: config::load_synthesis_cache_mb(),
+            synthesis_cache_key: None,
+            storage_usage: Vec::new(),
+            storage_status: None,
+            cleanup_running: false,
+            mini_bar_window_ids: Vec::new(),
+            window_geometry: HashMap::new(),
+            screenshot_viewport: Default::default(),
+            screenshot_dragging: false,
+            screenshot_cursor: None,
+            ocr_lines: Vec::new(),
+            show_ocr_boxes: true,
+            qr_codes: Vec::new(),
+            watch_dir_input: config::load_watch_dir().map(|d| d.display().to_string()).unwrap_or_default(),
+            watch_dir: config::load_watch_dir(),
+            watch_status: None,
+            queued_readings: std::collections::VecDeque::new(),
+            pause_while_typing,
+            typing_resume_secs,
+            paused_for_typing: false,
+            typing_checked_at: None,
+            voice_suggestion: None,
+            voice_suggestion_window_id: None,
+            declined_voice_languages: config::load_ignored_voice_languages(),
+            execution_provider: config::load_execution_provider(),
+            available_execution_providers: None,
+            execution_benchmark_running: false,
+            execution_benchmark: None,
+            power_policy: config::load_power_policy(),
+            power_source: None,
+            synthesis_threads,
+            synthesis_priority,
+            native_language_names: config::load_native_language_names(),
+            language_search: String::new(),
+            collapsed_language_regions: Vec::new(),
+            recent_languages: config::load_recent_languages(),
+            recent_voices: config::load_recent_voices(selected_backend),
+            playback_volume,
+            playback_speed,
+            scroll_seek_lines: 0.0,
+            scroll_sensitivity: config::load_scroll_sensitivity(),
+            bar_menu_window: None,
+            panic_stop_hotkey: config::load_panic_stop_hotkey(),
+            hotkey_edit_variant: config::load_hotkey_edit_variant(),
+            hotkey_image_variant: config::load_hotkey_image_variant(),
+            fade_ms,
+            pitch_semitones,
+            current_document: None,
+            resume_pending: None,
+            resume_dialog_window_id: None,
+            pending_seek: None,
+            volume_popover_window: None,
+            weekly_summary_enabled,
+            weekly_summary_day,
+            weekly_summary_hour,
+            weekly_summary_window_id: None,
+            weekly_summary_text: None,
+            listened_secs: 0.0,
+            private_screenshots: config::load_private_screenshots(),
+            export_recipients: config::load_export_recipients(),
+            phrase_warmup_running: false,
+            clipboard_history_enabled: config::load_clipboard_history(),
+            clipboard_history: std::collections::VecDeque::new(),
+            clipboard_history_window_id: None,
+            source_priority: config::load_source_priority(),
+            capture_source_window_id: None,
+            capture_source_asking: false,
+            capture_source_edit_first: false,
+            processing_trace: crate::text::pipeline::Trace::default(),
+            processing_chain_window_id: None,
+            transcript: Vec::new(),
+            transcript_sentence: None,
+            spoken_sentences: Vec::new(),
+            transcript_window_id: None,
         }
     }
 }
@@ -0,0 +1,172 @@
+//! Speech cache of frequently read short phrases.
+//!
+//! Short texts read again and again (UI strings, canned messages) are counted
+//! in `phrases.json` in the app data directory. While the app is idle, the most
+//! frequent ones are synthesized with the current voice into the cache folder,
+//! so reading them again plays at once without synthesis. Phrases are only
+//! counted while the reading history is on, as their text is kept.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+/// Longest text counted as a phrase.
+pub const MAX_PHRASE_CHARS: usize = 200;
+
+/// Reads before a phrase is synthesized ahead of time.
+const MIN_READS: u32 = 3;
+
+/// Most frequent phrases kept synthesized.
+const MAX_WARM_PHRASES: usize = 50;
+
+/// Phrases counted; the least recently read are forgotten beyond this.
+const MAX_TRACKED: usize = 500;
+
+/// How often one phrase was read.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct PhraseStats {
+    count: u32,
+    /// Unix time of the last reading
+    last_read: i64,
+}
+
+/// Location of the phrase counts.
+pub fn phrases_path() -> PathBuf {
+    crate::sync::app_data_dir().join("phrases.json")
+}
+
+/// Forget the phrases counted so far, with the reading history.
+pub fn forget_phrases() {
+    match std::fs::remove_file(phrases_path()) {
+        Ok(()) => debug!("Phrase counts removed"),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => warn!(error = %e, "Failed to remove phrase counts"),
+    }
+}
+
+/// Folder of the synthesized phrases (`~/.cache/insight-reader/phrases`).
+pub fn cache_dir() -> PathBuf {
+    dirs::cache_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("insight-reader")
+        .join("phrases")
+}
+
+fn load_phrases() -> BTreeMap<String, PhraseStats> {
+    let Ok(content) = std::fs::read_to_string(phrases_path()) else {
+        return BTreeMap::new();
+    };
+    serde_json::from_str(&content).unwrap_or_else(|e| {
+        warn!(error = %e, "Failed to parse phrase counts, starting fresh");
+        BTreeMap::new()
+    })
+}
+
+fn save_phrases(phrases: &BTreeMap<String, PhraseStats>) {
+    let result = std::fs::create_dir_all(crate::sync::app_data_dir())
+        .map_err(|e| e.to_string())
+        .and_then(|_| serde_json::to_string(phrases).map_err(|e| e.to_string()))
+        .and_then(|json| std::fs::write(phrases_path(), json).map_err(|e| e.to_string()));
+    if let Err(e) = result {
+        warn!(error = %e, "Failed to save phrase counts");
+    }
+}
+
+fn normalize(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Count a reading of `text` if it is short enough to be a phrase.
+pub fn record(text: &str) {
+    let phrase = normalize(text);
+    if phrase.is_empty() || phrase.chars().count() > MAX_PHRASE_CHARS {
+        return;
+    }
+    let mut phrases = load_phrases();
+    let stats = phrases.entry(phrase).or_default();
+    stats.count += 1;
+    stats.last_read = chrono::Utc::now().timestamp();
+    if phrases.len() > MAX_TRACKED {
+        let mut by_age: Vec<(i64, String)> = phrases.iter().map(|(p, s)| (s.last_read, p.clone())).collect();
+        by_age.sort();
+        for (_, phrase) in by_age.into_iter().take(phrases.len() - MAX_TRACKED) {
+            phrases.remove(&phrase);
+        }
+    }
+    save_phrases(&phrases);
+}
+
+/// Phrases read often enough to keep synthesized, most frequent first.
+fn frequent(phrases: &BTreeMap<String, PhraseStats>) -> Vec<String> {
+    let mut frequent: Vec<(&String, &PhraseStats)> =
+        phrases.iter().filter(|(_, stats)| stats.count >= MIN_READS).collect();
+    frequent.sort_by(|a, b| b.1.count.cmp(&a.1.count).then(b.1.last_read.cmp(&a.1.last_read)));
+    frequent.into_iter().take(MAX_WARM_PHRASES).map(|(phrase, _)| phrase.clone()).collect()
+}
+
+fn audio_path(voice: &str, phrase: &str) -> PathBuf {
+    cache_dir().join(format!("{}.pcm", crate::text::content_hash(&format!("{voice}\n{phrase}"))))
+}
+
+/// Frequent phrases not synthesized with `voice` yet.
+pub fn pending(voice: &str) -> Vec<String> {
+    frequent(&load_phrases())
+        .into_iter()
+        .filter(|phrase| !audio_path(voice, phrase).exists())
+        .collect()
+}
+
+/// Keep the samples of a phrase spoken with `voice`: the sample rate as a
+/// little-endian u32, then the f32 samples.
+pub fn store(voice: &str, phrase: &str, samples: &[f32], sample_rate: u32) {
+    let path = audio_path(voice, phrase);
+    let mut bytes = Vec::with_capacity(4 + samples.len() * 4);
+    bytes.extend_from_slice(&sample_rate.to_le_bytes());
+    for sample in samples {
+        bytes.extend_from_slice(&sample.to_le_bytes());
+    }
+    match std::fs::create_dir_all(cache_dir()).and_then(|_| std::fs::write(&path, bytes)) {
+        Ok(()) => debug!(chars = phrase.len(), voice, "Phrase cached"),
+        Err(e) => warn!(error = %e, path = %path.display(), "Failed to cache phrase"),
+    }
+}
+
+/// Samples of `text` spoken with `voice`, if it was synthesized ahead of time
+/// at `sample_rate`.
+pub fn cached(voice: &str, text: &str, sample_rate: u32) -> Option<Vec<f32>> {
+    let phrase = normalize(text);
+    if phrase.chars().count() > MAX_PHRASE_CHARS {
+        return None;
+    }
+    let bytes = std::fs::read(audio_path(voice, &phrase)).ok()?;
+    let (rate, samples) = bytes.split_first_chunk::<4>()?;
+    if u32::from_le_bytes(*rate) != sample_rate {
+        return None;
+    }
+    Some(
+        samples
+            .chunks_exact(4)
+            .map(|sample| f32::from_le_bytes([sample[0], sample[1], sample[2], sample[3]]))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frequent_phrases() {
+        let stats = |count, last_read| PhraseStats { count, last_read };
+        let phrases = BTreeMap::from([
+            ("Build failed".to_string(), stats(7, 10)),
+            ("Build passed".to_string(), stats(7, 20)),
+            ("You have mail".to_string(), stats(12, 5)),
+            ("Once".to_string(), stats(1, 30)),
+        ]);
+        assert_eq!(frequent(&phrases), ["You have mail", "Build passed", "Build failed"]);
+        assert_eq!(normalize("  Build\n failed "), "Build failed");
+    }
+}
@@ -0,0 +1,288 @@
+//! User plugins written in Rhai.
+//!
+//! Every `*.rhai` file in the plugin directory (`<app data>/plugins`) is one
+//! plugin, named after its file. A plugin can define any of:
+//!
+//! ```rhai
+//! // Rewrite text before it is read aloud
+//! fn transform(text) { text.replace("e.g.", "for example"); text }
+//!
+//! // Actions listed in the command palette
+//! fn actions() { ["Read uppercase"] }
+//!
+//! // Run an action on the selected text; returns the text to read ("" = nothing)
+//! fn run(action, text) { text.to_upper() }
+//! ```
+//!
+//! Transforms run in file name order, so prefix files with numbers to order
+//! them. Scripts are sandboxed: Rhai has no file, network or process access,
+//! `import` and `eval` are disabled, and every call runs under operation and
+//! size limits so a runaway script fails instead of hanging the app.
+
+use std::path::PathBuf;
+
+use rhai::{Array, Dynamic, Engine, Scope, AST};
+use tracing::{debug, info, warn};
+
+/// Upper bound on the work a single plugin call may do.
+const MAX_OPERATIONS: u64 = 5_000_000;
+
+/// Upper bound on strings built by a plugin (bytes).
+const MAX_STRING_SIZE: usize = 8 * 1024 * 1024;
+
+/// Upper bound on arrays and object maps built by a plugin.
+const MAX_COLLECTION_SIZE: usize = 100_000;
+
+/// A loaded plugin script.
+pub struct Plugin {
+    /// File name without the `.rhai` extension
+    pub name: String,
+    pub enabled: bool,
+    /// Command palette actions the plugin offers
+    pub actions: Vec<String>,
+    /// Compile or load error; a plugin with an error does nothing
+    pub error: Option<String>,
+    ast: Option<AST>,
+    has_transform: bool,
+}
+
+/// A command palette action offered by a plugin.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginAction {
+    pub plugin: String,
+    pub name: String,
+}
+
+/// The scripting engine and all plugins found in the plugin directory.
+pub struct PluginHost {
+    engine: Engine,
+    pub plugins: Vec<Plugin>,
+}
+
+/// Directory scanned for `*.rhai` plugins.
+pub fn plugins_dir() -> PathBuf {
+    crate::sync::app_data_dir().join("plugins")
+}
+
+/// Build an engine with the sandbox limits applied.
+fn sandboxed_engine() -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.set_max_string_size(MAX_STRING_SIZE);
+    engine.set_max_array_size(MAX_COLLECTION_SIZE);
+    engine.set_max_map_size(MAX_COLLECTION_SIZE);
+    engine.set_max_call_levels(64);
+    engine.set_max_expr_depths(64, 32);
+    engine.set_module_resolver(rhai::module_resolvers::DummyModuleResolver::new());
+    engine.disable_symbol("eval");
+    engine.on_print(|s| info!(target: "plugins", "{s}"));
+    engine.on_debug(|s, source, pos| debug!(target: "plugins", source = source.unwrap_or(""), %pos, "{s}"));
+    engine
+}
+
+impl Default for PluginHost {
+    /// A host with no plugins loaded.
+    fn default() -> Self {
+        Self {
+            engine: sandboxed_engine(),
+            plugins: Vec::new(),
+        }
+    }
+}
+
+impl PluginHost {
+    /// Load every plugin in the plugin directory.
+    ///
+    /// Plugins listed in `disabled` are loaded but skipped when running.
+    pub fn load(disabled: &[String]) -> Self {
+        let mut host = Self::default();
+        host.reload(disabled);
+        host
+    }
+
+    /// Re-scan the plugin directory, picking up new, changed and removed files.
+    pub fn reload(&mut self, disabled: &[String]) {
+        let dir = plugins_dir();
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(&dir)
+            .map(|entries| {
+                entries
+                    .filter_map(Result::ok)
+                    .map(|entry| entry.path())
+                    .filter(|path| path.extension().is_some_and(|ext| ext == "rhai"))
+                    .collect()
+            })
+            .unwrap_or_default();
+        paths.sort();
+
+        self.plugins = paths
+            .iter()
+            .map(|path| {
+                let name = path
+                    .file_stem()
+                    .map(|stem| stem.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                let enabled = !disabled.contains(&name);
+                self.compile(name, path, enabled)
+            })
+            .collect();
+        info!(
+            dir = %dir.display(),
+            count = self.plugins.len(),
+            failed = self.plugins.iter().filter(|p| p.error.is_some()).count(),
+            "Plugins loaded"
+        );
+    }
+
+    fn compile(&self, name: String, path: &std::path::Path, enabled: bool) -> Plugin {
+        let mut plugin = Plugin {
+            name,
+            enabled,
+            actions: Vec::new(),
+            error: None,
+            ast: None,
+            has_transform: false,
+        };
+        let ast = match std::fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|source| self.engine.compile(source).map_err(|e| e.to_string()))
+        {
+            Ok(ast) => ast,
+            Err(e) => {
+                warn!(plugin = %plugin.name, error = %e, "Failed to load plugin");
+                plugin.error = Some(e);
+                return plugin;
+            }
+        };
+
+        let defines = |fn_name: &str, arity: usize| {
+            ast.iter_functions()
+                .any(|f| f.name == fn_name && f.params.len() == arity)
+        };
+        plugin.has_transform = defines("transform", 1);
+        if defines("actions", 0) && defines("run", 2) {
+            match self.engine.call_fn::<Array>(&mut Scope::new(), &ast, "actions", ()) {
+                Ok(actions) => {
+                    plugin.actions = actions
+                        .into_iter()
+                        .filter_map(|action| action.into_string().ok())
+                        .collect();
+                }
+                Err(e) => {
+                    warn!(plugin = %plugin.name, error = %e, "Plugin actions() failed");
+                    plugin.error = Some(format!("actions(): {e}"));
+                }
+            }
+        }
+        debug!(plugin = %plugin.name, transform = plugin.has_transform, actions = ?plugin.actions, "Plugin compiled");
+        plugin.ast = Some(ast);
+        plugin
+    }
+
+    fn runnable(&self) -> impl Iterator<Item = (&Plugin, &AST)> {
+        self.plugins
+            .iter()
+            .filter(|p| p.enabled)
+            .filter_map(|p| p.ast.as_ref().map(|ast| (p, ast)))
+    }
+
+    /// Names of plugins that are currently turned off.
+    pub fn disabled_names(&self) -> Vec<String> {
+        self.plugins
+            .iter()
+            .filter(|p| !p.enabled)
+            .map(|p| p.name.clone())
+            .collect()
+    }
+
+    /// Run every enabled plugin's `transform` over the text, in order.
+    ///
+    /// A plugin that fails is skipped (and logged) so one broken script never
+    /// stops the text from being read.
+    pub fn transform(&self, text: String) -> String {
+        self.runnable()
+            .filter(|(plugin, _)| plugin.has_transform)
+            .fold(text, |text, (plugin, ast)| {
+                match self
+                    .engine
+                    .call_fn::<Dynamic>(&mut Scope::new(), ast, "transform", (text.clone(),))
+                    .map(|result| result.into_string())
+                {
+                    Ok(Ok(transformed)) => transformed,
+                    Ok(Err(type_name)) => {
+                        warn!(plugin = %plugin.name, type_name, "Plugin transform() did not return a string, skipping");
+                        text
+                    }
+                    Err(e) => {
+                        warn!(plugin = %plugin.name, error = %e, "Plugin transform() failed, skipping");
+                        text
+                    }
+                }
+            })
+    }
+
+    /// Actions offered by enabled plugins, for the command palette.
+    pub fn actions(&self) -> Vec<PluginAction> {
+        self.runnable()
+            .flat_map(|(plugin, _)| {
+                plugin.actions.iter().map(|name| PluginAction {
+                    plugin: plugin.name.clone(),
+                    name: name.clone(),
+                })
+            })
+            .collect()
+    }
+
+    /// Run a plugin action on the given text and return the text to read.
+    pub fn run_action(&self, action: &PluginAction, text: &str) -> Result<String, String> {
+        let (_, ast) = self
+            .runnable()
+            .find(|(plugin, _)| plugin.name == action.plugin)
+            .ok_or_else(|| format!("Plugin '{}' is not loaded", action.plugin))?;
+        info!(plugin = %action.plugin, action = %action.name, "Running plugin action");
+        let result = self
+            .engine
+            .call_fn::<Dynamic>(&mut Scope::new(), ast, "run", (action.name.clone(), text.to_string()))
+            .map_err(|e| format!("{}: {e}", action.plugin))?;
+        if result.is_unit() {
+            return Ok(String::new());
+        }
+        result
+            .into_string()
+            .map_err(|type_name| format!("{}: run() returned {type_name}, expected a string", action.plugin))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn host_with(name: &str, source: &str) -> PluginHost {
+        let engine = sandboxed_engine();
+        let ast = engine.compile(source).unwrap();
+        let has_transform = ast.iter_functions().any(|f| f.name == "transform");
+        PluginHost {
+            engine,
+            plugins: vec![Plugin {
+                name: name.to_string(),
+                enabled: true,
+                actions: Vec::new(),
+                error: None,
+                ast: Some(ast),
+                has_transform,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_transform_and_sandbox_limits() {
+        let host = host_with("abbrev", r#"fn transform(text) { text.replace("e.g.", "for example"); text }"#);
+        assert_eq!(host.transform("Fruit, e.g. apples".into()), "Fruit, for example apples");
+
+        // A script that never finishes is stopped and the text passes through
+        let host = host_with("loop", "fn transform(text) { loop {} }");
+        assert_eq!(host.transform("unchanged".into()), "unchanged");
+
+        assert!(sandboxed_engine().run(r#"import "os" as os;"#).is_err());
+        assert!(sandboxed_engine().eval::<i64>(r#"eval("1")"#).is_err());
+    }
+}
@@ -0,0 +1,210 @@
+//! ONNX Runtime execution providers the Piper voice model can run on.
+//!
+//! Piper is a Python package on top of ONNX Runtime, so the providers it can
+//! use are the ones the onnxruntime build next to it reports (`onnxruntime-gpu`
+//! adds CUDA, `onnxruntime-directml` adds DirectML...). A Piper binary without
+//! a Python environment is the C++ build, which can use CUDA when it was built
+//! with it; an NVIDIA driver is taken as the hint that it was.
+//!
+//! The Piper command only offers a choice between CPU and `--cuda`, so CoreML
+//! and DirectML are reported when present but cannot be selected.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+#[cfg(target_os = "windows")]
+use std::os::windows::process::CommandExt;
+
+use tracing::{debug, info, warn};
+
+/// Paragraph synthesized by the benchmark, long enough that loading the
+/// model does not hide the synthesis speed.
+const BENCHMARK_TEXT: &str = "The committee met on Tuesday to review the proposal. \
+    After a long discussion about the budget, the schedule and the risks involved, \
+    the members agreed to start a small pilot in the spring. If the pilot goes well, \
+    the project will be extended to every office by the end of next year, \
+    and a report on the results will be shared with all the teams involved.";
+
+/// Hardware the voice model runs on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExecutionProvider {
+    #[default]
+    Cpu,
+    /// NVIDIA GPUs
+    Cuda,
+    /// Apple Neural Engine and GPU
+    CoreMl,
+    /// Any DirectX 12 GPU on Windows
+    DirectMl,
+}
+
+impl ExecutionProvider {
+    pub const ALL: [ExecutionProvider; 4] = [
+        ExecutionProvider::Cpu,
+        ExecutionProvider::Cuda,
+        ExecutionProvider::CoreMl,
+        ExecutionProvider::DirectMl,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ExecutionProvider::Cpu => "CPU",
+            ExecutionProvider::Cuda => "CUDA (NVIDIA GPU)",
+            ExecutionProvider::CoreMl => "CoreML (Apple)",
+            ExecutionProvider::DirectMl => "DirectML (DirectX 12 GPU)",
+        }
+    }
+
+    /// Name ONNX Runtime reports for this provider.
+    fn onnx_name(self) -> &'static str {
+        match self {
+            ExecutionProvider::Cpu => "CPUExecutionProvider",
+            ExecutionProvider::Cuda => "CUDAExecutionProvider",
+            ExecutionProvider::CoreMl => "CoreMLExecutionProvider",
+            ExecutionProvider::DirectMl => "DmlExecutionProvider",
+        }
+    }
+
+    /// Arguments selecting this provider on the Piper command line, `None`
+    /// when the command cannot use it.
+    pub fn piper_args(self) -> Option<&'static [&'static str]> {
+        match self {
+            ExecutionProvider::Cpu => Some(&[]),
+            ExecutionProvider::Cuda => Some(&["--cuda"]),
+            ExecutionProvider::CoreMl | ExecutionProvider::DirectMl => None,
+        }
+    }
+}
+
+/// Providers available to the Piper at `piper_bin`, CPU first.
+pub fn detect_execution_providers(piper_bin: &Path) -> Vec<ExecutionProvider> {
+    let providers = match venv_python(piper_bin) {
+        Some(python) => onnxruntime_providers(&python).unwrap_or_else(|e| {
+            warn!(error = %e, "Failed to ask onnxruntime for its execution providers");
+            Vec::new()
+        }),
+        None if nvidia_driver_present() => vec![ExecutionProvider::Cuda],
+        None => Vec::new(),
+    };
+    let mut detected = vec![ExecutionProvider::Cpu];
+    detected.extend(providers.into_iter().filter(|p| *p != ExecutionProvider::Cpu));
+    info!(?detected, "Detected execution providers");
+    detected
+}
+
+/// The Python interpreter of the virtualenv `piper_bin` was installed into.
+fn venv_python(piper_bin: &Path) -> Option<PathBuf> {
+    let bin_dir = piper_bin.parent()?;
+    ["python3", "python", "python.exe"]
+        .into_iter()
+        .map(|name| bin_dir.join(name))
+        .find(|path| path.is_file())
+}
+
+/// Providers reported by the onnxruntime installed for `python`.
+fn onnxruntime_providers(python: &Path) -> Result<Vec<ExecutionProvider>, String> {
+    let output = command(python)
+        .args(["-c", "import onnxruntime; print('\\n'.join(onnxruntime.get_available_providers()))"])
+        .output()
+        .map_err(|e| format!("Failed to run {}: {e}", python.display()))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(parse_providers(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Known providers among the names onnxruntime printed, one per line.
+fn parse_providers(output: &str) -> Vec<ExecutionProvider> {
+    let names: Vec<&str> = output.lines().map(str::trim).collect();
+    ExecutionProvider::ALL
+        .into_iter()
+        .filter(|provider| names.contains(&provider.onnx_name()))
+        .collect()
+}
+
+fn nvidia_driver_present() -> bool {
+    command(Path::new("nvidia-smi"))
+        .arg("-L")
+        .output()
+        .is_ok_and(|output| output.status.success())
+}
+
+/// Time Piper reading the benchmark paragraph with `provider`, including
+/// loading the model.
+pub fn benchmark_execution_provider(
+    piper_bin: &Path,
+    model_path: &Path,
+    provider: ExecutionProvider,
+) -> Result<Duration, String> {
+    let args = provider
+        .piper_args()
+        .ok_or_else(|| format!("Piper cannot run on {}", provider.label()))?;
+    let output_file = std::env::temp_dir().join(format!("insight-reader-benchmark-{provider:?}.wav"));
+
+    let started = Instant::now();
+    let mut child = command(piper_bin)
+        .arg("--model")
+        .arg(model_path)
+        .arg("--output_file")
+        .arg(&output_file)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to start piper: {e}"))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(BENCHMARK_TEXT.as_bytes())
+            .map_err(|e| format!("Failed to write to piper: {e}"))?;
+    }
+    let output = child.wait_with_output().map_err(|e| format!("Piper failed: {e}"))?;
+    let elapsed = started.elapsed();
+    let _ = std::fs::remove_file(&output_file);
+
+    if !output.status.success() {
+        return Err(format!(
+            "Piper failed on {}: {}",
+            provider.label(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    debug!(?provider, elapsed_ms = elapsed.as_millis() as u64, "Benchmarked execution provider");
+    Ok(elapsed)
+}
+
+/// The fastest provider of a benchmark.
+pub fn recommended_provider(results: &[(ExecutionProvider, Duration)]) -> Option<ExecutionProvider> {
+    results.iter().min_by_key(|(_, elapsed)| *elapsed).map(|(provider, _)| *provider)
+}
+
+/// A command that opens no console window on Windows.
+fn command(program: &Path) -> Command {
+    #[allow(unused_mut)]
+    let mut command = Command::new(program);
+    #[cfg(target_os = "windows")]
+    command.creation_flags(crate::system::CREATE_NO_WINDOW);
+    command
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_providers() {
+        let output = "TensorrtExecutionProvider\nCUDAExecutionProvider\nCPUExecutionProvider\n";
+        assert_eq!(parse_providers(output), vec![ExecutionProvider::Cpu, ExecutionProvider::Cuda]);
+        assert_eq!(parse_providers("DmlExecutionProvider\r\nCPUExecutionProvider"), vec![
+            ExecutionProvider::Cpu,
+            ExecutionProvider::DirectMl
+        ]);
+        let results = [
+            (ExecutionProvider::Cpu, Duration::from_millis(2400)),
+            (ExecutionProvider::Cuda, Duration::from_millis(900)),
+        ];
+        assert_eq!(recommended_provider(&results), Some(ExecutionProvider::Cuda));
+    }
+}
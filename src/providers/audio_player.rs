@@ -2,30 +2,255 @@
 //!
 //! Extracts common playback logic (rodio sink, position tracking, FFT visualization)
 //! so providers only need to implement audio synthesis.
+//!
+//! The position tracker thread pushes a [`PlaybackUpdate`] to the registered
+//! playback listener (see [`set_playback_listener`]) for every chunk it plays,
+//! so the UI is driven by the audio itself instead of polling. Each chunk is
+//! also handed to the [`visualizer`] thread for the waveform bars.
+//!
+//! [`panic_stop`] silences every player from any thread: the sources being
+//! mixed check it for every sample, so it takes effect on the audio thread
+//! without waiting for `update()`.
+//!
+//! Speed changes keep the pitch of mono speech through [`TimeStretch`].
+//!
+//! Play, pause, stop and seek fade the audio in and out over a short,
+//! configurable time (see [`set_fade_duration`]) instead of cutting it.
+//!
+//! Streamed readings ([`AudioPlayer::play_stream`]) start playing while the
+//! rest is synthesized; progress and duration are estimated until the stream
+//! is complete, and the tracker waits at the end of the samples so far.
 
-use std::io::Cursor;
+use std::sync::atomic::{AtomicU32, AtomicU64, AtomicU8, Ordering};
 use std::sync::{Arc, Mutex};
 use std::thread;
+use std::time::Duration;
 
-use rodio::{Decoder, OutputStream, OutputStreamHandle, Sink};
-use rustfft::{num_complex::Complex, FftPlanner};
-use tracing::{debug, error, trace};
+use rodio::{OutputStream, OutputStreamHandle, Sink, Source};
+use tracing::{debug, error, trace, warn};
 
+use super::sample_store::{EncodedSamples, SampleStore, StreamingSamples};
+use super::time_stretch::TimeStretch;
+use super::visualizer;
 use super::TTSError;
+use crate::logging::trace_throttled;
+
+/// Playback tracking interval; one [`PlaybackUpdate`] is pushed per interval.
+pub const CHUNK_DURATION_MS: usize = 75;
+
+/// Progress and visualization data pushed from the audio thread.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlaybackUpdate {
+    /// Playback progress (0.0 to 1.0)
+    pub progress: f32,
+    /// Playback reached the end of the audio
+    pub finished: bool,
+}
+
+/// Receives playback updates; returns false once nobody is listening anymore.
+type PlaybackListener = Box<dyn Fn(PlaybackUpdate) -> bool + Send>;
+
+static PLAYBACK_LISTENER: Mutex<Option<PlaybackListener>> = Mutex::new(None);
+
+/// Register the callback receiving playback updates from every player.
+///
+/// Replaces any previous listener. The callback runs on the audio thread and
+/// must not block.
+pub fn set_playback_listener(listener: impl Fn(PlaybackUpdate) -> bool + Send + 'static) {
+    *PLAYBACK_LISTENER.lock().unwrap() = Some(Box::new(listener));
+}
+
+/// Push an update to the playback listener, dropping it if it has gone away.
+fn publish(update: PlaybackUpdate) {
+    let mut listener = PLAYBACK_LISTENER.lock().unwrap();
+    if let Some(ref callback) = *listener {
+        if !callback(update) {
+            debug!("Playback listener gone, unregistering");
+            *listener = None;
+        }
+    }
+}
+
+/// Volume and speed of speech, applied to every player so they carry over
+/// from one reading to the next.
+#[derive(Debug, Clone, Copy)]
+struct PlaybackLevels {
+    volume: f32,
+    speed: f32,
+}
+
+static LEVELS: Mutex<PlaybackLevels> = Mutex::new(PlaybackLevels { volume: 1.0, speed: 1.0 });
+
+fn levels() -> PlaybackLevels {
+    *LEVELS.lock().unwrap()
+}
+
+fn current_speed() -> f32 {
+    levels().speed
+}
+
+/// Set the speech volume (0.0 to 1.0) of every player, e.g. from the saved settings.
+pub fn set_playback_volume(volume: f32) {
+    LEVELS.lock().unwrap().volume = volume.clamp(0.0, 1.0);
+}
+
+/// Set the playback speed multiplier of every player, e.g. from the saved settings.
+pub fn set_playback_speed(speed: f32) {
+    LEVELS.lock().unwrap().speed = speed;
+}
+
+/// Bumped by [`panic_stop`]; sources and trackers started before the bump end.
+static SILENCE_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Silence every player now, from any thread.
+///
+/// The players still count as playing until the UI stops them, so this is
+/// followed by a normal stop once `update()` gets to it.
+pub fn panic_stop() {
+    SILENCE_GENERATION.fetch_add(1, Ordering::SeqCst);
+    visualizer::clear_bands();
+}
+
+/// Source wrapper ending the inner source as soon as [`panic_stop`] is called.
+struct Interruptible<S> {
+    inner: S,
+    generation: u64,
+}
+
+impl<S: Source<Item = f32>> Interruptible<S> {
+    fn new(inner: S) -> Self {
+        Self {
+            inner,
+            generation: SILENCE_GENERATION.load(Ordering::SeqCst),
+        }
+    }
+}
+
+impl<S: Source<Item = f32>> Iterator for Interruptible<S> {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if SILENCE_GENERATION.load(Ordering::Relaxed) != self.generation {
+            return None;
+        }
+        self.inner.next()
+    }
+}
+
+impl<S: Source<Item = f32>> Source for Interruptible<S> {
+    fn current_frame_len(&self) -> Option<usize> {
+        self.inner.current_frame_len()
+    }
+
+    fn channels(&self) -> u16 {
+        self.inner.channels()
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.inner.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.inner.total_duration()
+    }
+}
+
+/// Default length of the fades, in milliseconds.
+pub const DEFAULT_FADE_MS: u32 = 120;
+
+static FADE_MS: AtomicU32 = AtomicU32::new(DEFAULT_FADE_MS);
+
+/// Set the length of the fades on play, pause, stop and seek (0 for none),
+/// for the next transitions of every player.
+pub fn set_fade_duration(ms: u32) {
+    FADE_MS.store(ms, Ordering::Relaxed);
+}
+
+/// Where a [`Fading`] source is heading, shared with the player controlling it.
+const FADE_PLAY: u8 = 0;
+/// Fade out, then play silence without moving on until playing again
+const FADE_PAUSE: u8 = 1;
+/// Fade out, then end
+const FADE_STOP: u8 = 2;
+/// Faded out for a pause; the player then pauses the sink
+const FADE_HELD: u8 = 3;
+
+/// Source wrapper ramping the gain of the inner source up or down, as asked
+/// by the player through `target`.
+struct Fading<S> {
+    inner: S,
+    target: Arc<AtomicU8>,
+    gain: f32,
+    /// Gain change per sample
+    step: f32,
+}
+
+impl<S: Source<Item = f32>> Fading<S> {
+    /// Wrap a source, starting silent and fading in.
+    fn new(inner: S, target: Arc<AtomicU8>) -> Self {
+        let samples = FADE_MS.load(Ordering::Relaxed) as f32 * inner.sample_rate() as f32 * inner.channels() as f32 / 1000.0;
+        Self {
+            inner,
+            target,
+            gain: 0.0,
+            step: if samples >= 1.0 { 1.0 / samples } else { 1.0 },
+        }
+    }
+}
+
+impl<S: Source<Item = f32>> Iterator for Fading<S> {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        let target = self.target.load(Ordering::Relaxed);
+        self.gain = if target == FADE_PLAY {
+            (self.gain + self.step).min(1.0)
+        } else {
+            (self.gain - self.step).max(0.0)
+        };
+        match target {
+            FADE_STOP if self.gain == 0.0 => None,
+            // Paused: hold the position until playing again
+            FADE_PAUSE | FADE_HELD if self.gain == 0.0 => {
+                let _ = self.target.compare_exchange(FADE_PAUSE, FADE_HELD, Ordering::Relaxed, Ordering::Relaxed);
+                Some(0.0)
+            }
+            _ => self.inner.next().map(|sample| sample * self.gain),
+        }
+    }
+}
+
+impl<S: Source<Item = f32>> Source for Fading<S> {
+    fn current_frame_len(&self) -> Option<usize> {
+        self.inner.current_frame_len()
+    }
+
+    fn channels(&self) -> u16 {
+        self.inner.channels()
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.inner.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.inner.total_duration()
+    }
+}
 
 /// Internal playback state shared between threads.
 #[derive(Default)]
 pub struct PlaybackState {
-    /// Audio samples (normalized f32, -1.0 to 1.0)
-    pub audio_data: Vec<f32>,
+    /// Audio samples (normalized f32, -1.0 to 1.0), shared with the visualizer
+    pub audio_data: SampleStore,
     /// Current playback position in samples
     pub position: usize,
     /// Whether playback is active
     pub is_playing: bool,
     /// Whether playback is paused
     pub is_paused: bool,
-    /// Recent audio chunk for FFT visualization
-    pub current_chunk: Vec<f32>,
+    /// Incremented for every tracker thread started; older trackers exit
+    pub tracker_generation: u64,
 }
 
 /// Shared audio playback engine for TTS providers.
@@ -42,26 +267,43 @@ pub struct AudioPlayer {
     /// Audio output stream handle
     stream_handle: Option<OutputStreamHandle>,
     /// Audio sink for playback control
-    sink: Option<Sink>,
+    sink: Option<Arc<Sink>>,
+    /// Start the next playback paused (see [`AudioPlayer::hold_next_playback`])
+    hold_next: bool,
+    /// Fade direction of the audio in `sink`
+    fade: Arc<AtomicU8>,
+    /// Whether the audio in `sink` goes through the time stretcher, which
+    /// follows the speed by itself
+    time_stretched: bool,
 }
 
 impl AudioPlayer {
     /// Create a new audio player with the given sample rate.
+    ///
+    /// If no audio output is available (e.g. headless batch export), the player
+    /// is still created and playback calls return an error.
     pub fn new(sample_rate: u32) -> Result<Self, TTSError> {
         trace!(sample_rate, "AudioPlayer::new");
-        let (stream, stream_handle) = OutputStream::try_default().map_err(|e| {
-            error!("Failed to open audio output: {e}");
-            TTSError::AudioError(format!("Failed to open audio output: {e}"))
-        })?;
-
-        debug!(sample_rate, "Audio output stream initialized");
+        let (stream, stream_handle) = match OutputStream::try_default() {
+            Ok((stream, handle)) => {
+                debug!(sample_rate, "Audio output stream initialized");
+                (Some(stream), Some(handle))
+            }
+            Err(e) => {
+                warn!("Failed to open audio output, playback unavailable: {e}");
+                (None, None)
+            }
+        };
 
         Ok(Self {
             sample_rate,
             state: Arc::new(Mutex::new(PlaybackState::default())),
-            _stream: Some(stream),
-            stream_handle: Some(stream_handle),
+            _stream: stream,
+            stream_handle,
             sink: None,
+            hold_next: false,
+            fade: Arc::new(AtomicU8::new(FADE_PLAY)),
+            time_stretched: false,
         })
     }
 
@@ -74,17 +316,55 @@ impl AudioPlayer {
         // Store audio data
         {
             let mut state = self.state.lock().unwrap();
-            state.audio_data = audio_data;
+            cancel_stream(&state.audio_data);
+            state.audio_data = SampleStore::new(audio_data, self.sample_rate);
             state.position = 0;
             state.is_playing = false;
             state.is_paused = false;
-            state.current_chunk.clear();
         }
 
         // Start playback
         self.start_playback()
     }
 
+    /// Load compressed audio (e.g. MP3) and start playback.
+    ///
+    /// The audio stays encoded and is decoded as it plays, so long readings
+    /// take a fraction of the memory of PCM samples. Its sample rate must match
+    /// the player's.
+    pub fn play_encoded(&mut self, encoded: Vec<u8>) -> Result<(), TTSError> {
+        debug!(bytes = encoded.len(), "AudioPlayer::play_encoded");
+        let encoded = EncodedSamples::new(encoded).map_err(TTSError::AudioError)?;
+        {
+            let mut state = self.state.lock().unwrap();
+            cancel_stream(&state.audio_data);
+            state.audio_data = SampleStore::Encoded(Arc::new(encoded));
+            state.position = 0;
+            state.is_playing = false;
+            state.is_paused = false;
+        }
+
+        self.start_playback()
+    }
+
+    /// Start playing a reading whose samples are still being synthesized.
+    ///
+    /// The stream must already hold its first samples. Playback ends once it
+    /// is finished and played out; stopping or loading other audio cancels it.
+    pub fn play_stream(&mut self, stream: Arc<StreamingSamples>) -> Result<(), TTSError> {
+        debug!("AudioPlayer::play_stream");
+        {
+            let mut state = self.state.lock().unwrap();
+            cancel_stream(&state.audio_data);
+            state.audio_data = SampleStore::Streaming(stream);
+            state.position = 0;
+            state.is_playing = false;
+            state.is_paused = false;
+        }
+
+        self.start_playback()
+    }
+
     /// Convert raw PCM bytes (16-bit signed LE mono) to normalized f32 samples.
     pub fn pcm_to_f32(pcm_bytes: &[u8]) -> Vec<f32> {
         pcm_bytes
@@ -96,16 +376,38 @@ impl AudioPlayer {
             .collect()
     }
 
+    /// Root mean square level of normalized f32 samples (0.0 for no samples).
+    pub fn rms(samples: &[f32]) -> f32 {
+        if samples.is_empty() {
+            return 0.0;
+        }
+        let sum_sq: f32 = samples.iter().map(|s| s * s).sum();
+        (sum_sq / samples.len() as f32).sqrt()
+    }
+
+    /// Load the audio of the next [`AudioPlayer::play_audio`] or
+    /// [`AudioPlayer::play_encoded`] paused instead of playing it.
+    pub fn hold_next_playback(&mut self) {
+        self.hold_next = true;
+    }
+
     /// Pause the current playback.
+    ///
+    /// The audio fades out without moving on and the sink is then paused, so
+    /// resuming fades back in where the fade out ended.
     pub fn pause(&mut self) -> Result<(), TTSError> {
         trace!("AudioPlayer::pause");
-        if let Some(ref sink) = self.sink {
-            sink.pause();
+        let fading_out = self.fade.compare_exchange(FADE_PLAY, FADE_PAUSE, Ordering::Relaxed, Ordering::Relaxed).is_ok();
+        if let (true, Some(sink)) = (fading_out, &self.sink) {
+            self.pause_when_silent(sink);
         }
 
         let mut state = self.state.lock().unwrap();
         if state.is_playing && !state.is_paused {
             state.is_paused = true;
+            // The fade out still plays part of the audio
+            let fade_samples = (FADE_MS.load(Ordering::Relaxed) as f32 * self.sample_rate as f32 * levels().speed / 1000.0) as usize;
+            state.position = (state.position + fade_samples).min(state.audio_data.len());
         }
         Ok(())
     }
@@ -113,30 +415,59 @@ impl AudioPlayer {
     /// Resume paused playback.
     pub fn resume(&mut self) -> Result<(), TTSError> {
         trace!("AudioPlayer::resume");
-        if let Some(ref sink) = self.sink {
-            sink.play();
+        {
+            // Under the state lock, so a pause_when_silent thread cannot pause it again
+            let _state = self.state.lock().unwrap();
+            // The sink is paused once a pause has faded out, or from the start
+            // with hold_next_playback; play it before fading back in
+            if let Some(ref sink) = self.sink {
+                sink.play();
+            }
+            self.fade.store(FADE_PLAY, Ordering::Relaxed);
         }
 
-        let mut state = self.state.lock().unwrap();
-        if state.is_paused {
+        let position = {
+            let mut state = self.state.lock().unwrap();
+            if !(state.is_playing && state.is_paused) {
+                return Ok(());
+            }
             state.is_paused = false;
-        }
+            state.position
+        };
+        // The tracker exits while paused so an idle player costs nothing
+        self.start_position_tracker_from(position);
         Ok(())
     }
 
-    /// Stop playback and reset position.
+    /// Stop playback and reset position, giving up on the rest of a streamed reading.
     pub fn stop(&mut self) -> Result<(), TTSError> {
         trace!("AudioPlayer::stop");
-        if let Some(sink) = self.sink.take() {
-            sink.stop();
-        }
+        cancel_stream(&self.state.lock().unwrap().audio_data);
+        self.halt();
+        Ok(())
+    }
+
+    /// Stop playback and reset position.
+    fn halt(&mut self) {
+        self.fade_out();
 
         let mut state = self.state.lock().unwrap();
         state.is_playing = false;
         state.is_paused = false;
         state.position = 0;
-        state.current_chunk.clear();
-        Ok(())
+        visualizer::clear_bands();
+    }
+
+    /// Restart playback of the loaded audio from the beginning.
+    ///
+    /// Fails for a streamed reading that was stopped before it was fully synthesized.
+    pub fn replay(&mut self) -> Result<(), TTSError> {
+        trace!("AudioPlayer::replay");
+        if matches!(self.state.lock().unwrap().audio_data, SampleStore::Streaming(ref stream) if stream.is_cancelled()) {
+            return Err(TTSError::AudioError("Reading was stopped before it was fully synthesized".into()));
+        }
+        self.halt();
+        self.start_playback()
     }
 
     /// Check if audio is currently playing.
@@ -179,90 +510,109 @@ impl AudioPlayer {
         if state.audio_data.is_empty() {
             return 0.0;
         }
-        (state.position as f32 / state.audio_data.len() as f32).clamp(0.0, 1.0)
+        (state.position as f32 / state.audio_data.expected_len() as f32).clamp(0.0, 1.0)
     }
 
-    /// Get frequency band amplitudes for audio visualization.
-    pub fn get_frequency_bands(&self, num_bands: usize) -> Vec<f32> {
-        let state = self.state.lock().unwrap();
-
-        if state.current_chunk.len() < 128 {
-            return vec![0.0; num_bands];
-        }
-
-        let chunk = state.current_chunk.clone();
-        drop(state); // Release lock before FFT computation
-
-        // Apply Hanning window
-        let n = chunk.len();
-        let windowed: Vec<Complex<f32>> = chunk
-            .iter()
-            .enumerate()
-            .map(|(i, &sample)| {
-                let window = 0.5 * (1.0 - (2.0 * std::f32::consts::PI * i as f32 / n as f32).cos());
-                Complex::new(sample * window, 0.0)
-            })
-            .collect();
-
-        // Perform FFT
-        let mut planner = FftPlanner::new();
-        let fft = planner.plan_fft_forward(n);
-        let mut buffer = windowed;
-        fft.process(&mut buffer);
+    /// Get the playback position in the loaded audio.
+    pub fn position(&self) -> Duration {
+        let position = self.state.lock().unwrap().position;
+        Duration::from_secs_f64(position as f64 / self.sample_rate.max(1) as f64)
+    }
 
-        // Get magnitude of positive frequencies only
-        let half_n = n / 2;
-        let magnitudes: Vec<f32> = buffer[..half_n].iter().map(|c| c.norm()).collect();
+    /// Get the length of the loaded audio (estimated while it is streaming).
+    pub fn duration(&self) -> Duration {
+        let samples = self.state.lock().unwrap().audio_data.expected_len();
+        Duration::from_secs_f64(samples as f64 / self.sample_rate.max(1) as f64)
+    }
 
-        if magnitudes.len() < num_bands {
-            return vec![0.0; num_bands];
+    /// Set the speech volume (0.0 to 1.0), for this and the next players.
+    pub fn set_volume(&mut self, volume: f32) {
+        trace!(volume, "AudioPlayer::set_volume");
+        let volume = volume.clamp(0.0, 1.0);
+        set_playback_volume(volume);
+        if let Some(ref sink) = self.sink {
+            sink.set_volume(volume);
         }
+    }
 
-        // Split into logarithmic frequency bands
-        let mut bands = Vec::with_capacity(num_bands);
-        let log_max = (magnitudes.len() as f32).log10();
-
-        for i in 0..num_bands {
-            let start = (10f32.powf(log_max * i as f32 / num_bands as f32)) as usize;
-            let end = (10f32.powf(log_max * (i + 1) as f32 / num_bands as f32)) as usize;
-            let end = end.min(magnitudes.len());
-
-            if end > start {
-                // Use RMS for better energy representation
-                let sum_sq: f32 = magnitudes[start..end].iter().map(|&x| x * x).sum();
-                let rms = (sum_sq / (end - start) as f32).sqrt();
-                bands.push(rms);
-            } else {
-                bands.push(0.0);
+    /// Set the playback speed multiplier, for this and the next players.
+    ///
+    /// Mono speech is time-stretched and keeps its pitch; other audio is
+    /// played faster by the sink, so its pitch rises with the speed.
+    pub fn set_speed(&mut self, speed: f32) {
+        trace!(speed, "AudioPlayer::set_speed");
+        set_playback_speed(speed);
+        if let Some(ref sink) = self.sink {
+            if !self.time_stretched {
+                sink.set_speed(speed);
             }
         }
+    }
 
-        // Normalize and apply power curve
-        let max_val = bands.iter().cloned().fold(0.0f32, f32::max);
-        if max_val > 0.0 {
-            for band in &mut bands {
-                *band = (*band / max_val).powf(0.7);
+    /// Jump to a point of the loaded audio, as a fraction of its length.
+    pub fn seek_to_progress(&mut self, progress: f32) {
+        trace!(progress, "AudioPlayer::seek_to_progress");
+        let position = {
+            let state = self.state.lock().unwrap();
+            (progress.clamp(0.0, 1.0) * state.audio_data.expected_len() as f32) as usize
+        };
+        self.seek_to(position).ok();
+    }
+
+    /// Jump `offset` sentences from the one playing in a streamed reading.
+    /// Returns false for other audio, or if that sentence is not synthesized yet.
+    pub fn skip_sentences(&mut self, offset: i32) -> bool {
+        trace!(offset, "AudioPlayer::skip_sentences");
+        let target = {
+            let state = self.state.lock().unwrap();
+            match &state.audio_data {
+                SampleStore::Streaming(stream) => stream.part_start(state.position, offset),
+                _ => None,
             }
+        };
+        match target {
+            Some(position) => self.seek_to(position).is_ok(),
+            None => false,
         }
+    }
+
+    /// Start times of the sentences synthesized so far in a streamed reading;
+    /// empty for other audio.
+    pub fn sentence_starts(&self) -> Vec<Duration> {
+        let state = self.state.lock().unwrap();
+        match &state.audio_data {
+            SampleStore::Streaming(stream) => stream
+                .part_starts()
+                .into_iter()
+                .map(|start| Duration::from_secs_f64(start as f64 / self.sample_rate.max(1) as f64))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Get a copy of the loaded audio samples.
+    pub fn audio_data(&self) -> Vec<f32> {
+        self.state.lock().unwrap().audio_data.to_vec()
+    }
 
-        bands
+    /// Get the output sample rate.
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
     }
 
     /// Start audio playback from current position.
     fn start_playback(&mut self) -> Result<(), TTSError> {
         trace!("AudioPlayer::start_playback");
-        // Stop any existing playback first
-        if let Some(sink) = self.sink.take() {
-            sink.stop();
-        }
+        // Fade out any existing playback first; it overlaps the fade in of the new one
+        self.fade_out();
 
         let stream_handle = self
             .stream_handle
             .as_ref()
             .ok_or_else(|| TTSError::AudioError("No audio output available".into()))?;
 
-        // Get audio data from current position
-        let (audio_slice, position) = {
+        // Play the stored samples from the current position
+        let (store, position) = {
             let state = self.state.lock().unwrap();
             if state.audio_data.is_empty() {
                 return Err(TTSError::AudioError("No audio data to play".into()));
@@ -271,82 +621,88 @@ impl AudioPlayer {
             if pos >= state.audio_data.len() {
                 return Err(TTSError::AudioError("Playback position at end".into()));
             }
-            (state.audio_data[pos..].to_vec(), pos)
+            (state.audio_data.clone(), pos)
         };
-
-        // Convert f32 samples back to i16 for WAV encoding
-        let samples_i16: Vec<i16> = audio_slice
-            .iter()
-            .map(|&s| (s * 32767.0).clamp(-32768.0, 32767.0) as i16)
-            .collect();
-
-        // Create a WAV in memory
-        let wav_data = Self::create_wav(&samples_i16, self.sample_rate);
-
-        // Create decoder and sink
-        let cursor = Cursor::new(wav_data);
-        let source = Decoder::new(cursor).map_err(|e| {
-            error!("Failed to decode audio: {e}");
-            TTSError::AudioError(format!("Failed to decode audio: {e}"))
-        })?;
+        self.fade = Arc::new(AtomicU8::new(FADE_PLAY));
+        let source = store.source_from(position, self.sample_rate);
+        self.time_stretched = source.channels() == 1;
+        let source: Box<dyn Source<Item = f32> + Send> = if self.time_stretched {
+            Box::new(TimeStretch::new(source, current_speed))
+        } else {
+            source
+        };
+        let source = Interruptible::new(Fading::new(source, Arc::clone(&self.fade)));
 
         let sink = Sink::try_new(stream_handle).map_err(|e| {
             error!("Failed to create audio sink: {e}");
             TTSError::AudioError(format!("Failed to create audio sink: {e}"))
         })?;
 
+        let PlaybackLevels { volume, speed } = levels();
+        sink.set_volume(volume);
+        sink.set_speed(if self.time_stretched { 1.0 } else { speed });
+
+        let hold = std::mem::take(&mut self.hold_next);
+        if hold {
+            debug!("Holding playback, audio loaded paused");
+            sink.pause();
+        }
         sink.append(source);
-        self.sink = Some(sink);
+        self.sink = Some(Arc::new(sink));
 
         // Update state
         {
             let mut state = self.state.lock().unwrap();
             state.is_playing = true;
-            state.is_paused = false;
+            state.is_paused = hold;
         }
 
-        // Start position tracking in a background thread
-        self.start_position_tracker_from(position);
+        // Start position tracking in a background thread (resume starts it when held)
+        if !hold {
+            self.start_position_tracker_from(position);
+        }
 
         Ok(())
     }
 
-    /// Create a WAV file in memory from i16 samples.
-    fn create_wav(samples: &[i16], sample_rate: u32) -> Vec<u8> {
-        trace!(
-            samples = samples.len(),
-            sample_rate,
-            "AudioPlayer::create_wav"
-        );
-        let num_samples = samples.len();
-        let data_size = num_samples * 2; // 16-bit = 2 bytes per sample
-        let file_size = 36 + data_size;
-
-        let mut wav = Vec::with_capacity(44 + data_size);
-
-        // RIFF header
-        wav.extend_from_slice(b"RIFF");
-        wav.extend_from_slice(&(file_size as u32).to_le_bytes());
-        wav.extend_from_slice(b"WAVE");
-
-        // fmt chunk
-        wav.extend_from_slice(b"fmt ");
-        wav.extend_from_slice(&16u32.to_le_bytes()); // chunk size
-        wav.extend_from_slice(&1u16.to_le_bytes()); // PCM format
-        wav.extend_from_slice(&1u16.to_le_bytes()); // mono
-        wav.extend_from_slice(&sample_rate.to_le_bytes());
-        wav.extend_from_slice(&(sample_rate * 2).to_le_bytes()); // byte rate
-        wav.extend_from_slice(&2u16.to_le_bytes()); // block align
-        wav.extend_from_slice(&16u16.to_le_bytes()); // bits per sample
-
-        // data chunk
-        wav.extend_from_slice(b"data");
-        wav.extend_from_slice(&(data_size as u32).to_le_bytes());
-        for &sample in samples {
-            wav.extend_from_slice(&sample.to_le_bytes());
+    /// Fade out the current sink and let go of it; it ends on its own once silent.
+    fn fade_out(&mut self) {
+        if let Some(sink) = self.sink.take() {
+            self.fade.store(FADE_STOP, Ordering::Relaxed);
+            if sink.is_paused() {
+                // Held or paused, nothing to fade
+                sink.stop();
+            } else if let Ok(sink) = Arc::try_unwrap(sink) {
+                sink.detach();
+            }
+            // Otherwise a pause_when_silent thread is pausing it: it is silent
+            // already and stops when dropped
         }
+    }
 
-        wav
+    /// Pause `sink` in a background thread once the fade out of a pause has
+    /// reached silence, so a paused player stops pulling audio.
+    fn pause_when_silent(&self, sink: &Arc<Sink>) {
+        let sink = Arc::downgrade(sink);
+        let fade = Arc::clone(&self.fade);
+        let state = Arc::clone(&self.state);
+        thread::spawn(move || loop {
+            thread::sleep(Duration::from_millis(10));
+            let _state = state.lock().unwrap();
+            // Gone, or held paused since it was loaded
+            let Some(sink) = sink.upgrade().filter(|sink| !sink.is_paused()) else {
+                break;
+            };
+            match fade.load(Ordering::Relaxed) {
+                FADE_PAUSE => {}
+                FADE_HELD => {
+                    sink.pause();
+                    break;
+                }
+                // Resumed or stopped before the fade out ended
+                _ => break,
+            }
+        });
     }
 
     /// Start a background thread to track playback position.
@@ -359,44 +715,71 @@ impl AudioPlayer {
         let state = Arc::clone(&self.state);
         let sample_rate = self.sample_rate;
 
-        thread::spawn(move || {
-            let chunk_duration_ms = 75; // Match UI update rate
-            let samples_per_chunk = (sample_rate as usize * chunk_duration_ms) / 1000;
+        // Initialize position to start position and retire any previous tracker
+        let generation = {
+            let mut state_guard = state.lock().unwrap();
+            state_guard.position = start_position;
+            state_guard.tracker_generation += 1;
+            state_guard.tracker_generation
+        };
+        let silence_generation = SILENCE_GENERATION.load(Ordering::SeqCst);
 
-            // Initialize position to start position
-            {
-                let mut state_guard = state.lock().unwrap();
-                state_guard.position = start_position;
-            }
+        thread::spawn(move || {
+            let samples_per_chunk = (sample_rate as usize * CHUNK_DURATION_MS) / 1000;
 
             loop {
-                thread::sleep(std::time::Duration::from_millis(chunk_duration_ms as u64));
+                thread::sleep(Duration::from_millis(CHUNK_DURATION_MS as u64));
 
                 let mut state_guard = state.lock().unwrap();
 
-                // Exit thread if not playing (stopped or position changed externally)
-                if !state_guard.is_playing {
+                // Exit thread if stopped, paused (resume starts a new tracker), superseded
+                // or silenced by a panic stop
+                if !state_guard.is_playing
+                    || state_guard.is_paused
+                    || state_guard.tracker_generation != generation
+                    || SILENCE_GENERATION.load(Ordering::Relaxed) != silence_generation
+                {
                     break;
                 }
 
-                if state_guard.is_paused {
-                    continue;
-                }
-
-                // Update position
-                let new_position = state_guard.position + samples_per_chunk;
-                if new_position >= state_guard.audio_data.len() {
+                // Update position, faster or slower than real time with the playback speed
+                let advance = (samples_per_chunk as f32 * levels().speed) as usize;
+                let mut new_position = state_guard.position + advance;
+                if state_guard.audio_data.is_pending() {
+                    // Silence plays until synthesis catches up; wait with it
+                    new_position = new_position.min(state_guard.audio_data.len());
+                } else if new_position >= state_guard.audio_data.len() {
                     state_guard.is_playing = false;
                     state_guard.position = state_guard.audio_data.len();
+                    drop(state_guard);
+                    visualizer::clear_bands();
+                    publish(PlaybackUpdate {
+                        progress: 1.0,
+                        finished: true,
+                    });
                     break;
                 }
 
                 state_guard.position = new_position;
+                trace_throttled!(position = new_position, "Playback position advanced");
+                if let SampleStore::Streaming(stream) = &state_guard.audio_data {
+                    stream.set_played(new_position);
+                }
+
+                // Hand the chunk just played to the visualizer (shares the samples, no copy);
+                // encoded audio feeds the visualizer from its decoder instead
+                let start = new_position.saturating_sub(advance);
+                let samples = state_guard.audio_data.clone();
+                let progress = new_position as f32 / samples.expected_len() as f32;
+                drop(state_guard);
 
-                // Store current chunk for visualization
-                let start = new_position.saturating_sub(samples_per_chunk);
-                let end = new_position.min(state_guard.audio_data.len());
-                state_guard.current_chunk = state_guard.audio_data[start..end].to_vec();
+                if samples.is_random_access() {
+                    visualizer::analyze(samples, start..new_position);
+                }
+                publish(PlaybackUpdate {
+                    progress,
+                    finished: false,
+                });
             }
         });
     }
@@ -416,11 +799,6 @@ impl AudioPlayer {
             state.is_playing = false; // Stop current tracker thread
         }
 
-        // Give the old tracker thread time to exit (it checks is_playing every 75ms)
-        if was_playing {
-            thread::sleep(std::time::Duration::from_millis(80));
-        }
-
         // Restart playback if we were playing
         if was_playing {
             self.start_playback()?;
@@ -430,3 +808,137 @@ impl AudioPlayer {
     }
 }
 
+impl Drop for AudioPlayer {
+    fn drop(&mut self) {
+        cancel_stream(&self.state.lock().unwrap().audio_data);
+    }
+}
+
+/// Tell the synthesis of a streamed reading to stop, if one is loaded.
+fn cancel_stream(store: &SampleStore) {
+    if let SampleStore::Streaming(stream) = store {
+        stream.cancel();
+    }
+}
+
+/// Encode normalized f32 mono samples as a 16-bit PCM WAV file in memory.
+pub fn samples_to_wav(samples: &[f32], sample_rate: u32) -> Vec<u8> {
+    let samples_i16: Vec<i16> = samples
+        .iter()
+        .map(|&s| (s * 32767.0).clamp(-32768.0, 32767.0) as i16)
+        .collect();
+    create_wav(&samples_i16, sample_rate)
+}
+
+/// Create a WAV file in memory from i16 samples.
+fn create_wav(samples: &[i16], sample_rate: u32) -> Vec<u8> {
+    trace!(
+        samples = samples.len(),
+        sample_rate,
+        "create_wav"
+    );
+    let num_samples = samples.len();
+    let data_size = num_samples * 2; // 16-bit = 2 bytes per sample
+    let file_size = 36 + data_size;
+
+    let mut wav = Vec::with_capacity(44 + data_size);
+
+    // RIFF header
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(file_size as u32).to_le_bytes());
+    wav.extend_from_slice(b"WAVE");
+
+    // fmt chunk
+    wav.extend_from_slice(b"fmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes()); // chunk size
+    wav.extend_from_slice(&1u16.to_le_bytes()); // PCM format
+    wav.extend_from_slice(&1u16.to_le_bytes()); // mono
+    wav.extend_from_slice(&sample_rate.to_le_bytes());
+    wav.extend_from_slice(&(sample_rate * 2).to_le_bytes()); // byte rate
+    wav.extend_from_slice(&2u16.to_le_bytes()); // block align
+    wav.extend_from_slice(&16u16.to_le_bytes()); // bits per sample
+
+    // data chunk
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&(data_size as u32).to_le_bytes());
+    for &sample in samples {
+        wav.extend_from_slice(&sample.to_le_bytes());
+    }
+
+    wav
+}
+
+/// Decode a 16-bit PCM WAV file in memory as normalized f32 mono samples
+/// (channels mixed down) and its sample rate. None if it is not one.
+pub fn wav_to_samples(wav: &[u8]) -> Option<(Vec<f32>, u32)> {
+    if wav.len() < 12 || &wav[0..4] != b"RIFF" || &wav[8..12] != b"WAVE" {
+        return None;
+    }
+    let mut format = None;
+    let mut rest = &wav[12..];
+    while rest.len() >= 8 {
+        let (id, size) = (&rest[0..4], u32::from_le_bytes([rest[4], rest[5], rest[6], rest[7]]) as usize);
+        let body = rest.get(8..8 + size).unwrap_or(&rest[8..]);
+        match id {
+            b"fmt " if body.len() >= 16 => {
+                let channels = u16::from_le_bytes([body[2], body[3]]).max(1) as usize;
+                let sample_rate = u32::from_le_bytes([body[4], body[5], body[6], body[7]]);
+                let bits = u16::from_le_bytes([body[14], body[15]]);
+                if bits != 16 {
+                    return None;
+                }
+                format = Some((channels, sample_rate));
+            }
+            b"data" => {
+                let (channels, sample_rate) = format?;
+                let samples = AudioPlayer::pcm_to_f32(body)
+                    .chunks(channels)
+                    .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32)
+                    .collect();
+                return Some((samples, sample_rate));
+            }
+            _ => {}
+        }
+        // Chunks are padded to an even length
+        rest = rest.get(8 + size + size % 2..).unwrap_or_default();
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rodio::buffer::SamplesBuffer;
+
+    #[test]
+    fn test_wav_to_samples() {
+        let wav = samples_to_wav(&[0.0, 0.5, -0.5], 16000);
+        let (samples, sample_rate) = wav_to_samples(&wav).unwrap();
+        assert_eq!(sample_rate, 16000);
+        assert_eq!(samples.len(), 3);
+        assert!((samples[1] - 0.5).abs() < 0.001);
+        assert_eq!(wav_to_samples(b"not a wav file"), None);
+    }
+
+    #[test]
+    fn test_fading() {
+        // 1000 Hz mono, so the default fade lasts 120 samples
+        let target = Arc::new(AtomicU8::new(FADE_PLAY));
+        let mut source = Fading::new(SamplesBuffer::new(1, 1000, vec![1.0f32; 1000]), Arc::clone(&target));
+        let fade_in: Vec<f32> = source.by_ref().take(DEFAULT_FADE_MS as usize).collect();
+        assert!(fade_in[0] < 0.05);
+        assert!(fade_in.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(source.next(), Some(1.0));
+
+        // Pausing fades out, then holds the rest of the audio
+        target.store(FADE_PAUSE, Ordering::Relaxed);
+        assert_eq!(source.by_ref().take(DEFAULT_FADE_MS as usize + 10).last(), Some(0.0));
+        assert_eq!(target.load(Ordering::Relaxed), FADE_HELD);
+        let remaining = source.inner.size_hint();
+        assert_eq!(source.next(), Some(0.0));
+        assert_eq!(source.inner.size_hint(), remaining);
+
+        target.store(FADE_STOP, Ordering::Relaxed);
+        assert_eq!(source.next(), None);
+    }
+}
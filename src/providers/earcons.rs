@@ -0,0 +1,150 @@
+//! Short audio cues ("earcons") for reading events.
+//!
+//! Cues are generated from a small built-in sound set instead of audio files,
+//! and are played on their own output stream so they never interrupt speech.
+//! The "sentence skipped" cue is mixed into the synthesized audio instead (see
+//! [`super::recovery`]) so it plays at the right moment.
+
+use std::sync::Mutex;
+use std::thread;
+
+use rodio::buffer::SamplesBuffer;
+use rodio::{OutputStream, Sink};
+use tracing::{debug, warn};
+
+/// Sample rate cues are generated at when played on their own.
+const CUE_SAMPLE_RATE: u32 = 22050;
+
+/// An event announced by an audio cue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Earcon {
+    /// Reading started
+    Start,
+    /// Reading reached the end of the text
+    End,
+    /// Reading failed
+    Error,
+    /// A sentence could not be synthesized and was left out
+    Skipped,
+}
+
+/// Sound set used for audio cues.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EarconSet {
+    /// Soft decaying bell tones
+    Chime,
+    /// Plain short beeps
+    Beep,
+    /// Very short ticks
+    Click,
+}
+
+/// Which audio cues play and how loud.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EarconSettings {
+    pub enabled: bool,
+    /// Cue volume (0.0 to 1.0)
+    pub volume: f32,
+    pub set: EarconSet,
+}
+
+impl EarconSettings {
+    const DEFAULT: Self = Self {
+        enabled: true,
+        volume: 0.4,
+        set: EarconSet::Chime,
+    };
+}
+
+impl Default for EarconSettings {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+static SETTINGS: Mutex<EarconSettings> = Mutex::new(EarconSettings::DEFAULT);
+
+/// Set which audio cues play and how loud, for every provider.
+pub fn set_earcon_settings(settings: EarconSettings) {
+    *SETTINGS.lock().unwrap() = settings;
+}
+
+/// Notes of a cue, as frequencies in Hz played one after the other.
+fn notes(earcon: Earcon) -> &'static [f32] {
+    match earcon {
+        Earcon::Start => &[523.25, 783.99],
+        Earcon::End => &[783.99, 523.25],
+        Earcon::Error => &[311.13, 311.13],
+        Earcon::Skipped => &[659.25, 440.0],
+    }
+}
+
+/// One note of the given sound set, at full volume.
+fn note(set: EarconSet, freq: f32, sample_rate: u32) -> Vec<f32> {
+    let rate = sample_rate as f32;
+    let secs = match set {
+        EarconSet::Chime => 0.16,
+        EarconSet::Beep => 0.09,
+        EarconSet::Click => 0.025,
+    };
+    let len = (rate * secs) as usize;
+    // Short fade in and out to avoid clicks at the edges
+    let fade = rate * 0.005;
+    (0..len)
+        .map(|i| {
+            let t = i as f32 / rate;
+            let phase = t * freq * std::f32::consts::TAU;
+            let edges = (i.min(len - i) as f32 / fade).min(1.0);
+            let sample = match set {
+                EarconSet::Chime => (phase.sin() + 0.3 * (2.0 * phase).sin()) / 1.3 * (-t * 18.0).exp(),
+                EarconSet::Beep => phase.sin(),
+                EarconSet::Click => phase.sin().signum() * 0.5 * (-t * 120.0).exp(),
+            };
+            sample * edges
+        })
+        .collect()
+}
+
+/// Samples of a cue at the configured volume; empty when cues are turned off.
+pub(super) fn cue_samples(earcon: Earcon, sample_rate: u32) -> Vec<f32> {
+    let settings = *SETTINGS.lock().unwrap();
+    if !settings.enabled || settings.volume <= 0.0 {
+        return Vec::new();
+    }
+    let pause = sample_rate as usize / 25;
+    let mut samples = Vec::new();
+    for (index, &freq) in notes(earcon).iter().enumerate() {
+        if index > 0 {
+            samples.extend(std::iter::repeat_n(0.0, pause));
+        }
+        samples.extend(note(settings.set, freq, sample_rate).into_iter().map(|s| s * settings.volume * 0.6));
+    }
+    samples
+}
+
+/// Play a cue in the background, alongside any speech.
+///
+/// Does nothing when cues are turned off or no audio output is available.
+pub fn play_earcon(earcon: Earcon) {
+    let samples = cue_samples(earcon, CUE_SAMPLE_RATE);
+    if samples.is_empty() {
+        return;
+    }
+    debug!(?earcon, "Playing audio cue");
+    thread::spawn(move || {
+        let (_stream, handle) = match OutputStream::try_default() {
+            Ok(output) => output,
+            Err(e) => {
+                warn!("No audio output for audio cue: {e}");
+                return;
+            }
+        };
+        match Sink::try_new(&handle) {
+            Ok(sink) => {
+                sink.append(SamplesBuffer::new(1, CUE_SAMPLE_RATE, samples));
+                sink.sleep_until_end();
+            }
+            Err(e) => warn!("Failed to create audio sink for audio cue: {e}"),
+        }
+    });
+}
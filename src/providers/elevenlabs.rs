@@ -0,0 +1,240 @@
+//! ElevenLabs TTS provider implementation.
+//!
+//! Requests 16-bit PCM from the ElevenLabs text-to-speech API with the user's
+//! API key. Like [`super::polly`], longer texts are streamed: the first
+//! sentence plays while the rest is requested on another thread.
+
+use std::sync::Arc;
+
+use serde::Serialize;
+use tracing::{debug, info};
+
+use super::audio_player::AudioPlayer;
+use super::sample_store::StreamingSamples;
+use super::streaming;
+use super::{TTSError, TTSProvider};
+use crate::text::prosody;
+use crate::text::sentences::sentences;
+use crate::voices::elevenlabs::API_URL;
+
+/// Sample rate of the requested PCM (`pcm_22050`).
+const SAMPLE_RATE: u32 = 22050;
+
+/// Voice used until one is chosen in the settings ("Rachel", a premade voice).
+const DEFAULT_VOICE_ID: &str = "21m00Tcm4TlvDq8ikWAM";
+
+/// Model used for synthesis: the most natural one, speaking 29 languages.
+const MODEL_ID: &str = "eleven_multilingual_v2";
+
+const API_KEY_ERROR_MSG: &str = "ElevenLabs API key not set. Enter it under Text-to-Speech Provider in Settings, or set the ELEVENLABS_API_KEY environment variable.";
+
+/// ElevenLabs TTS provider.
+pub struct ElevenLabsTTSProvider {
+    /// API client and voice, shared with streaming synthesis
+    request: ElevenLabsRequest,
+    /// Shared audio playback engine
+    player: AudioPlayer,
+}
+
+/// Everything needed to request speech, cloned into the thread synthesizing
+/// the rest of a streamed reading.
+#[derive(Clone)]
+struct ElevenLabsRequest {
+    client: reqwest::Client,
+    /// Tokio runtime for the async HTTP calls
+    runtime: Arc<tokio::runtime::Runtime>,
+    api_key: String,
+    voice_id: String,
+}
+
+#[derive(Serialize)]
+struct SpeechRequest<'a> {
+    text: &'a str,
+    model_id: &'a str,
+}
+
+impl ElevenLabsTTSProvider {
+    /// Create an ElevenLabs provider speaking with `voice_id` (or the default voice).
+    pub fn new(voice_id: Option<String>) -> Result<Self, TTSError> {
+        info!("Initializing ElevenLabs TTS provider");
+        let api_key = Self::check_api_key().map_err(TTSError::ProcessError)?;
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_all()
+            .build()
+            .map_err(|e| TTSError::ProcessError(format!("Failed to create tokio runtime: {e}")))?;
+        let voice_id = voice_id.unwrap_or_else(|| DEFAULT_VOICE_ID.to_string());
+        debug!(voice_id = %voice_id, "Using ElevenLabs voice");
+
+        Ok(Self {
+            request: ElevenLabsRequest {
+                client: reqwest::Client::new(),
+                runtime: Arc::new(runtime),
+                api_key,
+                voice_id,
+            },
+            player: AudioPlayer::new(SAMPLE_RATE)?,
+        })
+    }
+
+    /// Return the API key, or an error message if none is set.
+    pub fn check_api_key() -> Result<String, String> {
+        let api_key = crate::config::load_elevenlabs_api_key();
+        if api_key.trim().is_empty() {
+            Err(API_KEY_ERROR_MSG.to_string())
+        } else {
+            Ok(api_key.trim().to_string())
+        }
+    }
+}
+
+impl ElevenLabsRequest {
+    /// Request speech for `text` as normalized samples at [`SAMPLE_RATE`].
+    fn request_audio(&self, text: &str) -> Result<Vec<f32>, TTSError> {
+        debug!(chars = text.len(), "ElevenLabs: synthesizing speech");
+        // Paced lists get break tags between the items
+        let text = &prosody::break_tags(text);
+        let url = format!("{API_URL}/text-to-speech/{}?output_format=pcm_22050", self.voice_id);
+        let pcm = self.runtime.block_on(async {
+            let response = self
+                .client
+                .post(url)
+                .header("xi-api-key", &self.api_key)
+                .json(&SpeechRequest { text, model_id: MODEL_ID })
+                .send()
+                .await
+                .map_err(|e| TTSError::ProcessError(format!("ElevenLabs API error: {e}")))?;
+            let status = response.status();
+            if !status.is_success() {
+                let body = response.text().await.unwrap_or_default();
+                return Err(TTSError::ProcessError(match status {
+                    reqwest::StatusCode::UNAUTHORIZED => "ElevenLabs rejected the API key. Check it in Settings.".to_string(),
+                    reqwest::StatusCode::PAYMENT_REQUIRED | reqwest::StatusCode::TOO_MANY_REQUESTS => {
+                        format!("ElevenLabs quota or rate limit reached: {}", body.trim())
+                    }
+                    _ => format!("ElevenLabs API error ({status}): {}", body.trim()),
+                }));
+            }
+            response
+                .bytes()
+                .await
+                .map_err(|e| TTSError::ProcessError(format!("Failed to read ElevenLabs audio: {e}")))
+        })?;
+
+        if pcm.is_empty() {
+            return Err(TTSError::ProcessError("No audio data generated by ElevenLabs".into()));
+        }
+        Ok(AudioPlayer::pcm_to_f32(&pcm))
+    }
+}
+
+impl TTSProvider for ElevenLabsTTSProvider {
+    fn speak(&mut self, text: &str) -> Result<(), TTSError> {
+        self.player.stop()?;
+
+        let parts: Vec<String> = sentences(text).into_iter().map(String::from).collect();
+        if parts.len() < 2 {
+            let samples = self.request.request_audio(text)?;
+            return self.player.play_audio(samples);
+        }
+
+        // Play the first sentence while the rest is requested
+        info!(parts = parts.len(), "ElevenLabs: streaming reading");
+        let first = self.request.request_audio(&parts[0])?;
+        let stream = Arc::new(StreamingSamples::new(first, SAMPLE_RATE));
+        self.player.play_stream(Arc::clone(&stream))?;
+        let request = self.request.clone();
+        streaming::synthesize_rest(stream, parts, SAMPLE_RATE, move |part| request.request_audio(part));
+        Ok(())
+    }
+
+    fn synthesize(&mut self, text: &str) -> Result<Vec<f32>, TTSError> {
+        let samples = self.request.request_audio(text)?;
+        info!(
+            duration_sec = format!("{:.1}", samples.len() as f32 / SAMPLE_RATE as f32),
+            "ElevenLabs: audio received"
+        );
+        Ok(samples)
+    }
+
+    fn play_samples(&mut self, samples: Vec<f32>) -> Result<(), TTSError> {
+        self.player.stop()?;
+        self.player.play_audio(samples)
+    }
+
+    fn hold_next_playback(&mut self) {
+        self.player.hold_next_playback();
+    }
+
+    fn pause(&mut self) -> Result<(), TTSError> {
+        self.player.pause()
+    }
+
+    fn resume(&mut self) -> Result<(), TTSError> {
+        self.player.resume()
+    }
+
+    fn stop(&mut self) -> Result<(), TTSError> {
+        self.player.stop()
+    }
+
+    fn replay(&mut self) -> Result<(), TTSError> {
+        self.player.replay()
+    }
+
+    fn is_playing(&self) -> bool {
+        self.player.is_playing()
+    }
+
+    fn is_paused(&self) -> bool {
+        self.player.is_paused()
+    }
+
+    fn skip_forward(&mut self, seconds: f32) {
+        self.player.skip_forward(seconds);
+    }
+
+    fn skip_backward(&mut self, seconds: f32) {
+        self.player.skip_backward(seconds);
+    }
+
+    fn get_progress(&self) -> f32 {
+        self.player.get_progress()
+    }
+
+    fn duration(&self) -> std::time::Duration {
+        self.player.duration()
+    }
+
+    fn position(&self) -> std::time::Duration {
+        self.player.position()
+    }
+
+    fn set_volume(&mut self, volume: f32) {
+        self.player.set_volume(volume);
+    }
+
+    fn set_speed(&mut self, speed: f32) {
+        self.player.set_speed(speed);
+    }
+
+    fn seek(&mut self, progress: f32) {
+        self.player.seek_to_progress(progress);
+    }
+
+    fn skip_sentences(&mut self, offset: i32) -> bool {
+        self.player.skip_sentences(offset)
+    }
+
+    fn sentence_starts(&self) -> Vec<std::time::Duration> {
+        self.player.sentence_starts()
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.player.sample_rate()
+    }
+
+    fn synthesized_audio(&self) -> (Vec<f32>, u32) {
+        (self.player.audio_data(), self.player.sample_rate())
+    }
+}
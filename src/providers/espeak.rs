@@ -0,0 +1,226 @@
+//! espeak-ng provider, the basic voice used as a fallback.
+//!
+//! espeak-ng is small, fast and often already installed, so it speaks when no
+//! Piper voice is downloaded yet and no cloud credentials are set: first-run
+//! users hear something at once, if with a robotic voice. It is surfaced as
+//! "Basic voice (fallback)". Texts are synthesized by running `espeak-ng
+//! --stdout` and played with the shared [`AudioPlayer`].
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+#[cfg(target_os = "windows")]
+use std::os::windows::process::CommandExt;
+
+use tracing::{debug, info, warn};
+
+use super::audio_player::{wav_to_samples, AudioPlayer};
+use super::{TTSError, TTSProvider};
+use crate::text::prosody::{self, PAUSE};
+
+/// Sample rate of the played audio (espeak-ng's own).
+const SAMPLE_RATE: u32 = 22050;
+
+/// Where the Windows installer puts espeak-ng, which is not added to PATH.
+#[cfg(target_os = "windows")]
+const WINDOWS_INSTALL_PATH: &str = r"C:\Program Files\eSpeak NG\espeak-ng.exe";
+
+const NOT_FOUND_MSG: &str = "espeak-ng not found. Install it (e.g. `sudo apt install espeak-ng`, `brew install espeak-ng` or the eSpeak NG installer on Windows) or download a Piper voice.";
+
+/// espeak-ng TTS provider.
+pub struct EspeakTTSProvider {
+    /// Path to the espeak-ng binary
+    espeak_bin: PathBuf,
+    /// espeak-ng voice for the language of the user interface, if any
+    voice: Option<String>,
+    /// Shared audio playback engine
+    player: AudioPlayer,
+}
+
+impl EspeakTTSProvider {
+    /// Create a provider speaking the language of the user interface.
+    pub fn new() -> Result<Self, TTSError> {
+        info!("Initializing espeak-ng provider");
+        let espeak_bin = Self::check_binary().map_err(TTSError::ProcessError)?;
+        let voice = crate::system::ui_language();
+        debug!(?espeak_bin, ?voice, "espeak-ng configuration");
+        Ok(Self {
+            espeak_bin,
+            voice,
+            player: AudioPlayer::new(SAMPLE_RATE)?,
+        })
+    }
+
+    /// Return the espeak-ng binary, or an error message if it is not installed.
+    pub fn check_binary() -> Result<PathBuf, String> {
+        find_espeak().ok_or_else(|| NOT_FOUND_MSG.to_string())
+    }
+
+    /// Synthesize `text` as samples at [`SAMPLE_RATE`], with silence between
+    /// the pieces of paced text.
+    fn synthesize_text(&self, text: &str) -> Result<Vec<f32>, TTSError> {
+        if text.contains(PAUSE) {
+            return prosody::synthesize_paced(text, SAMPLE_RATE, |piece| self.synthesize_text(piece));
+        }
+        debug!(chars = text.len(), "espeak-ng: synthesizing speech");
+        let wav = match self.run_espeak(self.voice.as_deref(), text) {
+            Err(e) if self.voice.is_some() => {
+                // espeak-ng may have no voice for the language; the default one still speaks
+                warn!(error = %e, voice = ?self.voice, "espeak-ng failed with the language voice, using the default voice");
+                self.run_espeak(None, text)?
+            }
+            result => result?,
+        };
+        let (samples, sample_rate) = wav_to_samples(&wav)
+            .ok_or_else(|| TTSError::ProcessError("Invalid audio format from espeak-ng".into()))?;
+        if samples.is_empty() {
+            return Err(TTSError::ProcessError("No audio data generated by espeak-ng".into()));
+        }
+        Ok(crate::system::resample(&samples, sample_rate, SAMPLE_RATE))
+    }
+
+    /// Run espeak-ng on `text` and return the WAV it writes.
+    fn run_espeak(&self, voice: Option<&str>, text: &str) -> Result<Vec<u8>, TTSError> {
+        let mut cmd = Command::new(&self.espeak_bin);
+        cmd.args(["--stdout", "--stdin"]);
+        if let Some(voice) = voice {
+            cmd.args(["-v", voice]);
+        }
+        cmd.stdin(Stdio::piped()).stdout(Stdio::piped()).stderr(Stdio::piped());
+        #[cfg(target_os = "windows")]
+        cmd.creation_flags(crate::system::CREATE_NO_WINDOW);
+        let mut child = cmd
+            .spawn()
+            .map_err(|e| TTSError::ProcessError(format!("Failed to run espeak-ng: {e}")))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(text.as_bytes())
+                .map_err(|e| TTSError::ProcessError(format!("Failed to send text to espeak-ng: {e}")))?;
+        }
+        let output = child
+            .wait_with_output()
+            .map_err(|e| TTSError::ProcessError(format!("espeak-ng failed: {e}")))?;
+        if !output.status.success() {
+            return Err(TTSError::ProcessError(format!(
+                "espeak-ng failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Ok(output.stdout)
+    }
+}
+
+/// Locate espeak-ng on PATH (or where its Windows installer puts it).
+fn find_espeak() -> Option<PathBuf> {
+    if let Some(path) = crate::system::find_on_path("espeak-ng") {
+        return Some(path);
+    }
+    #[cfg(target_os = "windows")]
+    {
+        let path = PathBuf::from(WINDOWS_INSTALL_PATH);
+        if path.is_file() {
+            return Some(path);
+        }
+    }
+    None
+}
+
+impl TTSProvider for EspeakTTSProvider {
+    fn speak(&mut self, text: &str) -> Result<(), TTSError> {
+        self.player.stop()?;
+        // espeak-ng synthesizes far faster than real time, so nothing is streamed
+        let samples = self.synthesize_text(text)?;
+        self.player.play_audio(samples)
+    }
+
+    fn synthesize(&mut self, text: &str) -> Result<Vec<f32>, TTSError> {
+        let samples = self.synthesize_text(text)?;
+        info!(
+            duration_sec = format!("{:.1}", samples.len() as f32 / SAMPLE_RATE as f32),
+            "espeak-ng: audio synthesized"
+        );
+        Ok(samples)
+    }
+
+    fn play_samples(&mut self, samples: Vec<f32>) -> Result<(), TTSError> {
+        self.player.stop()?;
+        self.player.play_audio(samples)
+    }
+
+    fn hold_next_playback(&mut self) {
+        self.player.hold_next_playback();
+    }
+
+    fn pause(&mut self) -> Result<(), TTSError> {
+        self.player.pause()
+    }
+
+    fn resume(&mut self) -> Result<(), TTSError> {
+        self.player.resume()
+    }
+
+    fn stop(&mut self) -> Result<(), TTSError> {
+        self.player.stop()
+    }
+
+    fn replay(&mut self) -> Result<(), TTSError> {
+        self.player.replay()
+    }
+
+    fn is_playing(&self) -> bool {
+        self.player.is_playing()
+    }
+
+    fn is_paused(&self) -> bool {
+        self.player.is_paused()
+    }
+
+    fn skip_forward(&mut self, seconds: f32) {
+        self.player.skip_forward(seconds);
+    }
+
+    fn skip_backward(&mut self, seconds: f32) {
+        self.player.skip_backward(seconds);
+    }
+
+    fn get_progress(&self) -> f32 {
+        self.player.get_progress()
+    }
+
+    fn duration(&self) -> std::time::Duration {
+        self.player.duration()
+    }
+
+    fn position(&self) -> std::time::Duration {
+        self.player.position()
+    }
+
+    fn set_volume(&mut self, volume: f32) {
+        self.player.set_volume(volume);
+    }
+
+    fn set_speed(&mut self, speed: f32) {
+        self.player.set_speed(speed);
+    }
+
+    fn seek(&mut self, progress: f32) {
+        self.player.seek_to_progress(progress);
+    }
+
+    fn skip_sentences(&mut self, offset: i32) -> bool {
+        self.player.skip_sentences(offset)
+    }
+
+    fn sentence_starts(&self) -> Vec<std::time::Duration> {
+        self.player.sentence_starts()
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.player.sample_rate()
+    }
+
+    fn synthesized_audio(&self) -> (Vec<f32>, u32) {
+        (self.player.audio_data(), self.player.sample_rate())
+    }
+}
@@ -0,0 +1,236 @@
+//! macOS system voice provider.
+//!
+//! Speaks with the voices that come with macOS through AVSpeechSynthesizer, so
+//! reading works without downloading a Piper model or setting up a cloud
+//! account. Utterances are written to audio buffers instead of being spoken
+//! directly, and played with the shared [`AudioPlayer`], so pause, resume,
+//! seeking and progress work as with the other providers. Like
+//! [`super::polly`], longer texts are streamed.
+
+use std::ptr::NonNull;
+use std::sync::{mpsc, Arc};
+use std::time::Duration;
+
+use block2::RcBlock;
+use objc2_avf_audio::{
+    AVAudioBuffer, AVAudioCommonFormat, AVAudioPCMBuffer, AVSpeechSynthesisVoice, AVSpeechSynthesizer, AVSpeechUtterance,
+};
+use objc2_foundation::NSString;
+use tracing::{debug, info, warn};
+
+use super::audio_player::AudioPlayer;
+use super::sample_store::StreamingSamples;
+use super::streaming;
+use super::{TTSError, TTSProvider};
+use crate::text::prosody::{self, PAUSE};
+use crate::text::sentences::sentences;
+
+/// Sample rate of the played audio; voices writing another rate are resampled.
+const SAMPLE_RATE: u32 = 22050;
+
+/// Longest wait for the next audio buffer of an utterance.
+const BUFFER_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// macOS system voice provider.
+pub struct MacOSTTSProvider {
+    /// Voice identifier, or None for the system voice
+    voice_id: Option<String>,
+    /// Shared audio playback engine
+    player: AudioPlayer,
+}
+
+impl MacOSTTSProvider {
+    /// Create a provider speaking with `voice_id` (or the system voice).
+    pub fn new(voice_id: Option<String>) -> Result<Self, TTSError> {
+        info!("Initializing macOS system voice provider");
+        debug!(voice_id = ?voice_id, "Using macOS voice");
+        Ok(Self {
+            voice_id,
+            player: AudioPlayer::new(SAMPLE_RATE)?,
+        })
+    }
+}
+
+/// Samples and sample rate of a buffer written by the synthesizer, or None
+/// for the empty buffer ending the utterance.
+///
+/// # Safety
+///
+/// `buffer` must be a buffer passed to the `writeUtterance` callback.
+unsafe fn buffer_samples(buffer: &AVAudioBuffer) -> Option<(Vec<f32>, u32)> {
+    // The synthesizer only writes PCM buffers
+    let buffer = &*(buffer as *const AVAudioBuffer).cast::<AVAudioPCMBuffer>();
+    let frames = buffer.frameLength() as usize;
+    if frames == 0 {
+        return None;
+    }
+    let format = buffer.format();
+    let rate = format.sampleRate() as u32;
+    let common_format = format.commonFormat();
+    let samples = if common_format == AVAudioCommonFormat::PCMFormatFloat32 && !buffer.floatChannelData().is_null() {
+        std::slice::from_raw_parts((*buffer.floatChannelData()).as_ptr(), frames).to_vec()
+    } else if common_format == AVAudioCommonFormat::PCMFormatInt16 && !buffer.int16ChannelData().is_null() {
+        std::slice::from_raw_parts((*buffer.int16ChannelData()).as_ptr(), frames)
+            .iter()
+            .map(|&sample| sample as f32 / 32768.0)
+            .collect()
+    } else {
+        warn!(?common_format, "macOS: unsupported audio buffer format, skipped");
+        Vec::new()
+    };
+    Some((samples, rate))
+}
+
+/// Synthesize `text` with the voice `voice_id` as samples at [`SAMPLE_RATE`].
+///
+/// Paced text is synthesized piece by piece with silence between the pieces.
+fn synthesize_text(voice_id: Option<&str>, text: &str) -> Result<Vec<f32>, TTSError> {
+    if text.contains(PAUSE) {
+        return prosody::synthesize_paced(text, SAMPLE_RATE, |piece| synthesize_text(voice_id, piece));
+    }
+    debug!(chars = text.len(), "macOS: synthesizing speech");
+    let (tx, rx) = mpsc::channel();
+    let callback = RcBlock::new(move |buffer: NonNull<AVAudioBuffer>| {
+        let _ = tx.send(unsafe { buffer_samples(buffer.as_ref()) });
+    });
+    // Kept alive until the last buffer is written
+    let synthesizer = unsafe { AVSpeechSynthesizer::new() };
+    unsafe {
+        let utterance = AVSpeechUtterance::speechUtteranceWithString(&NSString::from_str(text));
+        if let Some(id) = voice_id {
+            match AVSpeechSynthesisVoice::voiceWithIdentifier(&NSString::from_str(id)) {
+                Some(voice) => utterance.setVoice(Some(&voice)),
+                None => warn!(voice_id = id, "macOS voice not installed, using the system voice"),
+            }
+        }
+        synthesizer.writeUtterance_toBufferCallback(&utterance, RcBlock::as_ptr(&callback));
+    }
+
+    let mut samples = Vec::new();
+    loop {
+        match rx.recv_timeout(BUFFER_TIMEOUT) {
+            Ok(Some((chunk, rate))) => samples.extend(crate::system::resample(&chunk, rate, SAMPLE_RATE)),
+            Ok(None) => break,
+            Err(e) => {
+                return Err(TTSError::ProcessError(format!("macOS speech synthesis did not finish: {e}")));
+            }
+        }
+    }
+    drop(synthesizer);
+
+    if samples.is_empty() {
+        return Err(TTSError::ProcessError("No audio data generated by the macOS voice".into()));
+    }
+    Ok(samples)
+}
+
+impl TTSProvider for MacOSTTSProvider {
+    fn speak(&mut self, text: &str) -> Result<(), TTSError> {
+        self.player.stop()?;
+
+        let parts: Vec<String> = sentences(text).into_iter().map(String::from).collect();
+        if parts.len() < 2 {
+            let samples = synthesize_text(self.voice_id.as_deref(), text)?;
+            return self.player.play_audio(samples);
+        }
+
+        // Play the first sentence while the rest is synthesized
+        info!(parts = parts.len(), "macOS: streaming reading");
+        let first = synthesize_text(self.voice_id.as_deref(), &parts[0])?;
+        let stream = Arc::new(StreamingSamples::new(first, SAMPLE_RATE));
+        self.player.play_stream(Arc::clone(&stream))?;
+        let voice_id = self.voice_id.clone();
+        streaming::synthesize_rest(stream, parts, SAMPLE_RATE, move |part| synthesize_text(voice_id.as_deref(), part));
+        Ok(())
+    }
+
+    fn synthesize(&mut self, text: &str) -> Result<Vec<f32>, TTSError> {
+        let samples = synthesize_text(self.voice_id.as_deref(), text)?;
+        info!(
+            duration_sec = format!("{:.1}", samples.len() as f32 / SAMPLE_RATE as f32),
+            "macOS: audio synthesized"
+        );
+        Ok(samples)
+    }
+
+    fn play_samples(&mut self, samples: Vec<f32>) -> Result<(), TTSError> {
+        self.player.stop()?;
+        self.player.play_audio(samples)
+    }
+
+    fn hold_next_playback(&mut self) {
+        self.player.hold_next_playback();
+    }
+
+    fn pause(&mut self) -> Result<(), TTSError> {
+        self.player.pause()
+    }
+
+    fn resume(&mut self) -> Result<(), TTSError> {
+        self.player.resume()
+    }
+
+    fn stop(&mut self) -> Result<(), TTSError> {
+        self.player.stop()
+    }
+
+    fn replay(&mut self) -> Result<(), TTSError> {
+        self.player.replay()
+    }
+
+    fn is_playing(&self) -> bool {
+        self.player.is_playing()
+    }
+
+    fn is_paused(&self) -> bool {
+        self.player.is_paused()
+    }
+
+    fn skip_forward(&mut self, seconds: f32) {
+        self.player.skip_forward(seconds);
+    }
+
+    fn skip_backward(&mut self, seconds: f32) {
+        self.player.skip_backward(seconds);
+    }
+
+    fn get_progress(&self) -> f32 {
+        self.player.get_progress()
+    }
+
+    fn duration(&self) -> std::time::Duration {
+        self.player.duration()
+    }
+
+    fn position(&self) -> std::time::Duration {
+        self.player.position()
+    }
+
+    fn set_volume(&mut self, volume: f32) {
+        self.player.set_volume(volume);
+    }
+
+    fn set_speed(&mut self, speed: f32) {
+        self.player.set_speed(speed);
+    }
+
+    fn seek(&mut self, progress: f32) {
+        self.player.seek_to_progress(progress);
+    }
+
+    fn skip_sentences(&mut self, offset: i32) -> bool {
+        self.player.skip_sentences(offset)
+    }
+
+    fn sentence_starts(&self) -> Vec<std::time::Duration> {
+        self.player.sentence_starts()
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.player.sample_rate()
+    }
+
+    fn synthesized_audio(&self) -> (Vec<f32>, u32) {
+        (self.player.audio_data(), self.player.sample_rate())
+    }
+}
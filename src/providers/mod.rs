@@ -3,12 +3,45 @@
 //! This module defines the [`TTSProvider`] trait and provides implementations
 //! for different TTS engines.
 
+mod acceleration;
 mod audio_player;
+mod earcons;
+mod output_device;
 mod piper;
+mod piper_embedded;
+mod piper_server;
+mod pitch;
+mod recovery;
+mod sample_store;
+mod speech_marks;
+mod streaming;
+mod time_stretch;
+mod visualizer;
+pub mod elevenlabs;
+pub mod espeak;
+#[cfg(target_os = "macos")]
+pub mod macos;
 pub mod polly;
+#[cfg(target_os = "windows")]
+pub mod winrt;
 
+pub use acceleration::{recommended_provider, ExecutionProvider};
+pub use audio_player::{
+    panic_stop, samples_to_wav, wav_to_samples, CHUNK_DURATION_MS, set_fade_duration, set_playback_listener, set_playback_speed, set_playback_volume, PlaybackUpdate, DEFAULT_FADE_MS,
+};
+pub use earcons::{play_earcon, set_earcon_settings, Earcon, EarconSet, EarconSettings};
+pub use output_device::{default_output_kind, OutputKind};
+pub use pitch::set_pitch;
+pub use speech_marks::{set_word_marks_wanted, WordMark};
+pub use visualizer::{current_bands, set_visualizer_style, VisualizerStyle, NUM_BANDS};
+pub use elevenlabs::ElevenLabsTTSProvider;
+pub use espeak::EspeakTTSProvider;
+#[cfg(target_os = "macos")]
+pub use macos::MacOSTTSProvider;
 pub use piper::PiperTTSProvider;
 pub use polly::PollyTTSProvider;
+#[cfg(target_os = "windows")]
+pub use winrt::WindowsTTSProvider;
 
 use thiserror::Error;
 
@@ -20,6 +53,41 @@ pub enum TTSError {
 
     #[error("Audio playback error: {0}")]
     AudioError(String),
+
+    #[error("Voice model is damaged ({reason}): {}", .path.display())]
+    DamagedModel {
+        /// Path to the `.onnx` file
+        path: std::path::PathBuf,
+        reason: String,
+    },
+}
+
+/// Create the macOS system voice provider speaking with `voice_id`; an error
+/// on other platforms.
+pub fn macos_provider(voice_id: Option<String>) -> Result<Box<dyn TTSProvider>, TTSError> {
+    #[cfg(target_os = "macos")]
+    {
+        MacOSTTSProvider::new(voice_id).map(|p| Box::new(p) as Box<dyn TTSProvider>)
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = voice_id;
+        Err(TTSError::ProcessError("System voices are only available on macOS".into()))
+    }
+}
+
+/// Create the Windows system voice provider speaking with `voice_id`; an
+/// error on other platforms.
+pub fn windows_provider(voice_id: Option<String>) -> Result<Box<dyn TTSProvider>, TTSError> {
+    #[cfg(target_os = "windows")]
+    {
+        WindowsTTSProvider::new(voice_id).map(|p| Box::new(p) as Box<dyn TTSProvider>)
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = voice_id;
+        Err(TTSError::ProcessError("Windows voices are only available on Windows".into()))
+    }
 }
 
 /// Abstract interface for TTS providers.
@@ -29,9 +97,31 @@ pub trait TTSProvider {
     /// Speak the given text.
     ///
     /// This method synthesizes and plays the audio. It may block during
-    /// synthesis but audio playback is non-blocking.
+    /// synthesis but audio playback is non-blocking. Texts of more than one
+    /// sentence return once the first sentence plays; the rest is synthesized
+    /// in the background.
     fn speak(&mut self, text: &str) -> Result<(), TTSError>;
 
+    /// Synthesize the given text without playing it.
+    ///
+    /// Returns normalized f32 mono samples at [`TTSProvider::sample_rate`].
+    fn synthesize(&mut self, text: &str) -> Result<Vec<f32>, TTSError>;
+
+    /// Sample rate of the synthesized audio.
+    fn sample_rate(&self) -> u32;
+
+    /// Play speech synthesized earlier (e.g. from the phrase cache), as
+    /// samples at [`TTSProvider::sample_rate`].
+    fn play_samples(&mut self, samples: Vec<f32>) -> Result<(), TTSError>;
+
+    /// Do any slow setup ahead of the first [`TTSProvider::speak`] (e.g. load the voice model).
+    fn prewarm(&mut self) -> Result<(), TTSError> {
+        Ok(())
+    }
+
+    /// Make the next [`TTSProvider::speak`] load its audio paused instead of playing it.
+    fn hold_next_playback(&mut self);
+
     /// Pause the current speech playback.
     fn pause(&mut self) -> Result<(), TTSError>;
 
@@ -41,6 +131,9 @@ pub trait TTSProvider {
     /// Stop the current speech playback and reset position.
     fn stop(&mut self) -> Result<(), TTSError>;
 
+    /// Play the last synthesized speech again from the start, without re-synthesizing.
+    fn replay(&mut self) -> Result<(), TTSError>;
+
     /// Check if speech is currently playing.
     fn is_playing(&self) -> bool;
 
@@ -56,8 +149,36 @@ pub trait TTSProvider {
     /// Get playback progress as a value between 0.0 and 1.0.
     fn get_progress(&self) -> f32;
 
-    /// Get frequency band amplitudes for audio visualization.
-    ///
-    /// Returns normalized amplitude values (0.0-1.0) for each frequency band.
-    fn get_frequency_bands(&self, num_bands: usize) -> Vec<f32>;
+    /// Get the length of the loaded speech.
+    fn duration(&self) -> std::time::Duration;
+
+    /// Get the playback position in the loaded speech.
+    fn position(&self) -> std::time::Duration;
+
+    /// Set the speech volume (0.0 to 1.0), kept for the next readings.
+    fn set_volume(&mut self, volume: f32);
+
+    /// Set the playback speed multiplier (0.5 to 3.0), kept for the next
+    /// readings. Speech keeps its pitch.
+    fn set_speed(&mut self, speed: f32);
+
+    /// Jump to a point of the current speech, as a fraction of its length.
+    fn seek(&mut self, progress: f32);
+
+    /// Jump `offset` sentences from the one playing (negative goes back).
+    /// Returns false when the speech is not split into sentences or the
+    /// sentence is not synthesized yet.
+    fn skip_sentences(&mut self, offset: i32) -> bool;
+
+    /// Start times of the sentences synthesized so far, when the speech is
+    /// streamed sentence by sentence; empty otherwise.
+    fn sentence_starts(&self) -> Vec<std::time::Duration>;
+
+    /// Get the last synthesized audio as normalized samples and its sample rate.
+    fn synthesized_audio(&self) -> (Vec<f32>, u32);
+
+    /// The word being spoken, for providers that report word timings (Polly).
+    fn get_current_word(&self) -> Option<WordMark> {
+        None
+    }
 }
@@ -0,0 +1,122 @@
+//! Classification of the default audio output device.
+//!
+//! Used by the headphone-only option to avoid reading text aloud through
+//! speakers. On Linux the PulseAudio/PipeWire sink properties are used (the
+//! ALSA device is usually just "default"); elsewhere the device name is
+//! matched against common headphone and speaker names.
+
+#[cfg(target_os = "linux")]
+use std::process::Command;
+
+use rodio::cpal::traits::{DeviceTrait, HostTrait};
+use tracing::debug;
+
+/// What kind of device the default audio output is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputKind {
+    /// Headphones, headsets and Bluetooth audio devices
+    Headphones,
+    /// Built-in or external speakers, HDMI outputs
+    Speakers,
+    /// The device could not be identified
+    Unknown,
+}
+
+/// Classify the current default audio output.
+pub fn default_output_kind() -> OutputKind {
+    #[cfg(target_os = "linux")]
+    if let Some(kind) = pulse_default_sink_kind() {
+        debug!(?kind, "Classified default PulseAudio sink");
+        return kind;
+    }
+
+    let name = rodio::cpal::default_host()
+        .default_output_device()
+        .and_then(|device| device.name().ok());
+    let kind = name.as_deref().map(classify_name).unwrap_or(OutputKind::Unknown);
+    debug!(device = ?name, ?kind, "Classified default audio output");
+    kind
+}
+
+/// Classify a device from its name (e.g. "Headphones (Realtek Audio)", "AirPods Pro").
+fn classify_name(name: &str) -> OutputKind {
+    let name = name.to_lowercase();
+    const HEADPHONES: &[&str] = &[
+        "headphone", "headset", "earphone", "earbud", "airpods", "buds", "bluetooth", "hands-free", "a2dp", "bluez",
+    ];
+    const SPEAKERS: &[&str] = &["speaker", "hdmi", "displayport", "monitor", "tv"];
+    if HEADPHONES.iter().any(|word| name.contains(word)) {
+        OutputKind::Headphones
+    } else if SPEAKERS.iter().any(|word| name.contains(word)) {
+        OutputKind::Speakers
+    } else {
+        OutputKind::Unknown
+    }
+}
+
+/// Classify the default sink using `pactl`, if it is available.
+#[cfg(target_os = "linux")]
+fn pulse_default_sink_kind() -> Option<OutputKind> {
+    let default = Command::new("pactl").arg("get-default-sink").output().ok()?;
+    let sink = String::from_utf8_lossy(&default.stdout).trim().to_string();
+    if sink.is_empty() {
+        return None;
+    }
+    let list = Command::new("pactl").args(["list", "sinks"]).output().ok()?;
+    Some(classify_pulse_sink(&String::from_utf8_lossy(&list.stdout), &sink))
+}
+
+/// Classify `sink` from `pactl list sinks` output: its form factor, bus and active port.
+#[cfg(any(target_os = "linux", test))]
+fn classify_pulse_sink(list: &str, sink: &str) -> OutputKind {
+    let Some(block) = list
+        .split("\nSink #")
+        .find(|block| block.lines().any(|line| line.trim() == format!("Name: {sink}")))
+    else {
+        return classify_name(sink);
+    };
+    let property = |key: &str| {
+        block.lines().find_map(|line| {
+            let value = line.trim().strip_prefix(key)?.trim_start().strip_prefix('=')?;
+            Some(value.trim().trim_matches('"').to_lowercase())
+        })
+    };
+    if let Some(form_factor) = property("device.form_factor") {
+        return classify_name(&form_factor);
+    }
+    if property("device.bus").as_deref() == Some("bluetooth") {
+        return OutputKind::Headphones;
+    }
+    let active_port = block
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("Active Port:"))
+        .map(str::trim);
+    match active_port.map(classify_name) {
+        Some(OutputKind::Unknown) | None => classify_name(sink),
+        Some(kind) => kind,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_name() {
+        assert_eq!(classify_name("Headphones (Realtek(R) Audio)"), OutputKind::Headphones);
+        assert_eq!(classify_name("AirPods Pro"), OutputKind::Headphones);
+        assert_eq!(classify_name("MacBook Pro Speakers"), OutputKind::Speakers);
+        assert_eq!(classify_name("default"), OutputKind::Unknown);
+    }
+
+    #[test]
+    fn test_classify_pulse_sink() {
+        let list = "Sink #1\n\tName: alsa_output.pci-0000_00_1f.3.analog-stereo\n\tProperties:\n\t\tdevice.bus = \"pci\"\n\tActive Port: analog-output-headphones\n\nSink #2\n\tName: alsa_output.hdmi\n\tActive Port: hdmi-output-0\n";
+        assert_eq!(
+            classify_pulse_sink(list, "alsa_output.pci-0000_00_1f.3.analog-stereo"),
+            OutputKind::Headphones
+        );
+        assert_eq!(classify_pulse_sink(list, "alsa_output.hdmi"), OutputKind::Speakers);
+        assert_eq!(classify_pulse_sink("", "bluez_output.AA_BB.1"), OutputKind::Headphones);
+    }
+}
@@ -1,38 +1,101 @@
 //! Piper TTS provider implementation.
 //!
-//! Uses the Piper binary to synthesize speech from text and plays it using rodio.
+//! Synthesizes speech with Piper voices and plays it using rodio. Voices run
+//! in-process when built with the `embedded-piper` feature (see
+//! [`super::piper_embedded`]); the Piper binary from the virtualenv is used
+//! instead without it, when `piper_subprocess` is set in the config, for GPU
+//! execution providers, or if a voice cannot be loaded in-process.
+//!
+//! Texts of more than one sentence are streamed: the first sentence plays
+//! while the rest is synthesized on another thread, which shares the Piper
+//! setup, loaded voice and warm server through [`PiperCommand`].
 
 use std::env;
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 
 #[cfg(target_os = "windows")]
 use std::os::windows::process::CommandExt;
 
 use tracing::{debug, error, info, warn};
 
+use super::acceleration::{benchmark_execution_provider, detect_execution_providers, ExecutionProvider};
 use super::audio_player::AudioPlayer;
+use super::piper_embedded::{self, EmbeddedPiper};
+use super::piper_server::PiperServer;
+use super::pitch;
+use super::recovery::synthesize_with_recovery;
+use super::sample_store::StreamingSamples;
+use super::streaming;
+use crate::system::SynthesisLimits;
+use crate::text::prosody::{self, PAUSE};
+use crate::text::sentences::sentences;
 use super::{TTSError, TTSProvider};
 
+/// Audio below this RMS level is treated as silence (about -54 dBFS).
+const SILENCE_RMS: f32 = 0.002;
+
 /// Piper TTS provider using local ONNX models.
 pub struct PiperTTSProvider {
+    /// How to run Piper, shared with streaming synthesis
+    command: PiperCommand,
+    /// Shared audio playback engine
+    player: AudioPlayer,
+    /// Model files passed the integrity check
+    model_checked: bool,
+}
+
+/// Everything needed to run Piper, cloned into the thread synthesizing the
+/// rest of a streamed reading.
+#[derive(Clone)]
+struct PiperCommand {
     /// Path to the piper binary
     piper_bin: PathBuf,
     /// Path to the model file (without .onnx extension)
     model_path: PathBuf,
-    /// Shared audio playback engine
-    player: AudioPlayer,
+    /// Voice loaded in-process, on first use or by [`TTSProvider::prewarm`]
+    engine: Arc<Mutex<Option<EmbeddedPiper>>>,
+    /// Run the piper binary instead of the in-process engine
+    subprocess: Arc<AtomicBool>,
+    /// Piper process with the model loaded, started by [`TTSProvider::prewarm`]
+    server: Arc<Mutex<Option<PiperServer>>>,
+    /// Hardware Piper runs the model on
+    execution_provider: ExecutionProvider,
+    /// Cores and priority Piper processes run with
+    limits: SynthesisLimits,
 }
 
 impl PiperTTSProvider {
     /// Create a new Piper TTS provider with default configuration.
     ///
-    /// Searches for piper binary and model in standard locations:
+    /// Searches for the model, and for the piper binary used when Piper does
+    /// not run in-process, in standard locations:
     /// 1. Project root: `./venv/bin/piper` (development)
     /// 2. User installation: `~/.local/share/insight-reader/venv/bin/piper` (XDG Base Directory)
     /// 3. System PATH
+    ///
+    /// On battery, the power policy may swap the selected voice for a faster
+    /// installed variant and keep Piper on fewer cores.
     pub fn new() -> Result<Self, TTSError> {
-        Self::with_config(None, None)
+        let limits = crate::system::current_synthesis_limits();
+        let voice_key = Self::selected_voice_key();
+        let faster_voice = if limits.prefer_faster_voice {
+            crate::voices::faster_variant(&voice_key, &crate::voices::download::installed_voice_keys())
+        } else {
+            None
+        };
+        if let Some(ref faster) = faster_voice {
+            info!(voice = %voice_key, faster = %faster, "On battery, using a faster variant of the voice");
+        }
+        let model_path = faster_voice.map(|faster| Self::find_model_named(&faster));
+        Self::with_limits(None, model_path, limits)
+    }
+
+    /// Create a new Piper TTS provider for a specific voice key (e.g., "en_US-lessac-medium").
+    pub fn with_voice(voice_key: &str) -> Result<Self, TTSError> {
+        Self::with_config(None, Some(Self::find_model_named(voice_key)))
     }
 
     /// Create a new Piper TTS provider with custom paths.
@@ -43,15 +106,36 @@ impl PiperTTSProvider {
     pub fn with_config(
         piper_bin: Option<PathBuf>,
         model_path: Option<PathBuf>,
+    ) -> Result<Self, TTSError> {
+        Self::with_limits(piper_bin, model_path, crate::system::current_synthesis_limits())
+    }
+
+    /// Create a provider whose Piper processes run under `limits`.
+    fn with_limits(
+        piper_bin: Option<PathBuf>,
+        model_path: Option<PathBuf>,
+        limits: SynthesisLimits,
     ) -> Result<Self, TTSError> {
         let piper_bin = piper_bin.unwrap_or_else(Self::find_piper_binary);
         let model_path = model_path.unwrap_or_else(Self::find_model);
 
-        info!("Initializing Piper TTS provider");
+        let execution_provider = match crate::config::load_execution_provider() {
+            provider if provider.piper_args().is_some() => provider,
+            provider => {
+                warn!(?provider, "Piper cannot run on this execution provider, using the CPU");
+                ExecutionProvider::Cpu
+            }
+        };
+        // The in-process engine runs on the CPU; GPUs go through the piper binary
+        let subprocess = crate::config::load_piper_subprocess()
+            || !piper_embedded::AVAILABLE
+            || execution_provider != ExecutionProvider::Cpu;
+
+        info!(subprocess, "Initializing Piper TTS provider");
         debug!(?piper_bin, ?model_path, "Piper configuration");
 
         // Validate that the binary and model actually exist before continuing.
-        if !piper_bin.is_file() {
+        if subprocess && !piper_bin.is_file() {
             error!(?piper_bin, "Piper binary not found");
             return Err(TTSError::ProcessError(format!(
                 "Piper binary not found at {}",
@@ -70,9 +154,17 @@ impl PiperTTSProvider {
         let player = AudioPlayer::new(22050)?;
 
         Ok(Self {
-            piper_bin,
-            model_path,
+            command: PiperCommand {
+                piper_bin,
+                model_path,
+                engine: Arc::default(),
+                subprocess: Arc::new(AtomicBool::new(subprocess)),
+                server: Arc::default(),
+                execution_provider,
+                limits,
+            },
             player,
+            model_checked: false,
         })
     }
 
@@ -132,6 +224,15 @@ impl PiperTTSProvider {
             }
         }
 
+        // Piper extracted from a platform bundle (see crate::bundle)
+        if let Some(data_dir) = dirs::data_dir() {
+            let bundled_piper = data_dir.join("insight-reader").join("piper").join(PIPER_BIN_NAME);
+            if bundled_piper.exists() {
+                debug!(path = %bundled_piper.display(), "Using bundled piper binary");
+                return bundled_piper;
+            }
+        }
+
         // On macOS, also check Linux-style location (~/.local/share/insight-reader)
         // since install scripts may use this location
         #[cfg(target_os = "macos")]
@@ -149,10 +250,9 @@ impl PiperTTSProvider {
         #[cfg(target_os = "windows")]
         {
             let path_cmd = "where";
-            const CREATE_NO_WINDOW: u32 = 0x08000000;
             if let Ok(output) = Command::new(path_cmd)
                 .arg("piper")
-                .creation_flags(CREATE_NO_WINDOW)
+                .creation_flags(crate::system::CREATE_NO_WINDOW)
                 .output()
             {
                 if output.status.success() {
@@ -203,15 +303,43 @@ impl PiperTTSProvider {
         fallback
     }
 
-    /// Find the model file in standard locations.
+    /// Execution providers the installed Piper can run on, CPU first.
+    pub fn available_execution_providers() -> Vec<ExecutionProvider> {
+        detect_execution_providers(&Self::find_piper_binary())
+    }
+
+    /// Time the selected voice reading a sample paragraph on each of
+    /// `providers` the Piper command can use.
+    pub fn benchmark_execution_providers(
+        providers: &[ExecutionProvider],
+    ) -> Result<Vec<(ExecutionProvider, std::time::Duration)>, String> {
+        let piper_bin = Self::find_piper_binary();
+        let model_path = Self::find_model();
+        providers
+            .iter()
+            .filter(|provider| provider.piper_args().is_some())
+            .map(|&provider| {
+                benchmark_execution_provider(&piper_bin, &model_path, provider).map(|elapsed| (provider, elapsed))
+            })
+            .collect()
+    }
+
+    /// Key of the voice used by default: the selected voice, or the bundled default.
+    pub fn selected_voice_key() -> String {
+        crate::config::load_selected_voice().unwrap_or_else(|| "en_US-lessac-medium".to_string())
+    }
+
+    /// Find the model file for the selected voice in standard locations.
     fn find_model() -> PathBuf {
-        // Try to load selected voice from config, fallback to default
-        let model_name = crate::config::load_selected_voice()
-            .unwrap_or_else(|| "en_US-lessac-medium".to_string());
+        Self::find_model_named(&Self::selected_voice_key())
+    }
+
+    /// Find the model file for a voice key (e.g., "en_US-lessac-medium") in standard locations.
+    fn find_model_named(model_name: &str) -> PathBuf {
 
         // Check project models directory first (for development)
         if let Ok(current_dir) = env::current_dir() {
-            let project_model = current_dir.join("models").join(&model_name);
+            let project_model = current_dir.join("models").join(model_name);
             if project_model.with_extension("onnx").exists() {
                 debug!(
                     path = %project_model.with_extension("onnx").display(),
@@ -225,7 +353,7 @@ impl PiperTTSProvider {
         // On Windows: %LOCALAPPDATA%\insight-reader\models
         // On Unix: ~/.local/share/insight-reader/models (via data_dir)
         if let Some(data_dir) = dirs::data_local_dir() {
-            let user_model = data_dir.join("insight-reader").join("models").join(&model_name);
+            let user_model = data_dir.join("insight-reader").join("models").join(model_name);
             if user_model.with_extension("onnx").exists() {
                 debug!(
                     path = %user_model.with_extension("onnx").display(),
@@ -237,7 +365,7 @@ impl PiperTTSProvider {
         
         // Also check data_dir (XDG Base Directory standard on Unix)
         if let Some(data_dir) = dirs::data_dir() {
-            let user_model = data_dir.join("insight-reader").join("models").join(&model_name);
+            let user_model = data_dir.join("insight-reader").join("models").join(model_name);
             if user_model.with_extension("onnx").exists() {
                 debug!(
                     path = %user_model.with_extension("onnx").display(),
@@ -271,7 +399,7 @@ impl PiperTTSProvider {
         let fallback = fallback_base
             .join("insight-reader")
             .join("models")
-            .join(&model_name);
+            .join(model_name);
         warn!(
             path = %fallback.with_extension("onnx").display(),
             "Piper model not found in known locations, using fallback path"
@@ -285,8 +413,101 @@ fn model_with_extension(path: &Path) -> PathBuf {
     path.with_extension("onnx")
 }
 
-impl TTSProvider for PiperTTSProvider {
-    fn speak(&mut self, text: &str) -> Result<(), TTSError> {
+impl PiperTTSProvider {
+    /// Check the model and config files once, before they are first used.
+    fn check_model(&mut self) -> Result<(), TTSError> {
+        if self.model_checked {
+            return Ok(());
+        }
+        let model_path = &self.command.model_path;
+        crate::voices::integrity::check_model(model_path).map_err(|reason| {
+            error!(model = %model_path.display(), reason = %reason, "Piper voice model is damaged");
+            TTSError::DamagedModel {
+                path: model_with_extension(model_path),
+                reason,
+            }
+        })?;
+        self.model_checked = true;
+        Ok(())
+    }
+}
+
+impl PiperCommand {
+    /// Whether Piper runs in-process, loading the voice on first use. If it
+    /// cannot be loaded and the piper binary is installed, Piper runs as a
+    /// process from then on.
+    fn in_process(&self) -> Result<bool, TTSError> {
+        if self.subprocess.load(Ordering::Relaxed) {
+            return Ok(false);
+        }
+        let mut engine = self.engine.lock().unwrap();
+        if engine.is_some() {
+            return Ok(true);
+        }
+        match EmbeddedPiper::load(&self.model_path) {
+            Ok(loaded) => {
+                info!(model = %self.model_path.display(), "Piper voice loaded in-process");
+                *engine = Some(loaded);
+                Ok(true)
+            }
+            Err(e) if self.piper_bin.is_file() => {
+                warn!(error = %e, "Cannot run Piper in-process, using the piper binary");
+                self.subprocess.store(true, Ordering::Relaxed);
+                Ok(false)
+            }
+            Err(e) => {
+                error!(error = %e, "Cannot run Piper in-process and the piper binary is not installed");
+                Err(e)
+            }
+        }
+    }
+
+    /// Load the voice in-process, or start the warm server if it is not running.
+    fn warm_up(&self) -> Result<(), TTSError> {
+        if self.in_process()? {
+            return Ok(());
+        }
+        self.start_server()
+    }
+
+    /// Start the warm server if it is not running.
+    fn start_server(&self) -> Result<(), TTSError> {
+        let mut server = self.server.lock().unwrap();
+        if server.is_none() {
+            *server = Some(PiperServer::start(
+                &self.piper_bin,
+                &self.model_path,
+                self.execution_provider,
+                self.limits,
+            )?);
+        }
+        Ok(())
+    }
+
+    /// Synthesize `text` at the pitch set, retrying sentence by sentence if
+    /// the whole text fails.
+    ///
+    /// Paced text is synthesized piece by piece with silence between the pieces.
+    fn synthesize_recovering(&self, text: &str, sample_rate: u32) -> Result<Vec<f32>, TTSError> {
+        if text.contains(PAUSE) {
+            return prosody::synthesize_paced(text, sample_rate, |piece| self.synthesize_recovering(piece, sample_rate));
+        }
+        let was_warm = self.server.lock().unwrap().is_some();
+        synthesize_with_recovery(text, sample_rate, |chunk| {
+            // A crashing sentence can take the server down; restart it so the
+            // remaining sentences don't each load the model again
+            if was_warm && self.server.lock().unwrap().is_none() {
+                if let Err(e) = self.start_server() {
+                    debug!(error = %e, "Could not restart piper server");
+                }
+            }
+            // Shifted chunk by chunk, leaving the earcon of a skipped sentence as it is
+            self.synthesize_once(chunk).map(|samples| pitch::shift(samples, sample_rate))
+        })
+    }
+
+    /// Synthesize `text` in one Piper request, using the warm server if there is one.
+    fn synthesize_once(&self, text: &str) -> Result<Vec<f32>, TTSError> {
         // Validate input text
         let text = text.trim();
         if text.is_empty() {
@@ -302,8 +523,29 @@ impl TTSProvider for PiperTTSProvider {
             "Piper: synthesizing speech"
         );
 
-        // Stop any current playback
-        self.player.stop()?;
+        if self.in_process()? {
+            let engine = self.engine.lock().unwrap();
+            let audio_data = engine.as_ref().expect("voice loaded by in_process").synthesize(text)?;
+            debug!(samples = audio_data.len(), "Piper: audio generated in-process");
+            return Ok(audio_data);
+        }
+
+        // A warm server skips loading the model; fall back to a one-off process if it fails
+        {
+            let mut server = self.server.lock().unwrap();
+            if let Some(running) = server.as_mut() {
+                match running.synthesize(text) {
+                    Ok(audio_data) => {
+                        debug!(samples = audio_data.len(), "Piper: audio generated by server");
+                        return Ok(audio_data);
+                    }
+                    Err(e) => {
+                        warn!(error = %e, "Piper server failed, running piper once instead");
+                        *server = None;
+                    }
+                }
+            }
+        }
 
         // Build command for logging
         let model_arg = self.model_path.to_str().unwrap_or("");
@@ -329,7 +571,6 @@ impl TTSProvider for PiperTTSProvider {
             
             // Run piper with temp file output
             // Use CREATE_NO_WINDOW flag to prevent console window from appearing
-            const CREATE_NO_WINDOW: u32 = 0x08000000;
             let mut child = Command::new(&self.piper_bin)
                 .args([
                     "--model",
@@ -337,10 +578,11 @@ impl TTSProvider for PiperTTSProvider {
                     "--output_file",
                     &temp_file_str,
                 ])
+                .args(self.execution_provider.piper_args().unwrap_or_default())
                 .stdin(Stdio::piped())
                 .stdout(Stdio::piped())
                 .stderr(Stdio::piped())
-                .creation_flags(CREATE_NO_WINDOW)
+                .creation_flags(crate::system::CREATE_NO_WINDOW)
                 .spawn()
                 .map_err(|e| {
                     error!(
@@ -350,6 +592,7 @@ impl TTSProvider for PiperTTSProvider {
                     );
                     TTSError::ProcessError(format!("Failed to start piper: {e}"))
                 })?;
+            self.limits.apply(&child);
 
             // Send text to piper
             {
@@ -425,6 +668,7 @@ impl TTSProvider for PiperTTSProvider {
                     "--output_file",
                     "-",
                 ])
+                .args(self.execution_provider.piper_args().unwrap_or_default())
                 .stdin(Stdio::piped())
                 .stdout(Stdio::piped())
                 .stderr(Stdio::piped())
@@ -437,6 +681,7 @@ impl TTSProvider for PiperTTSProvider {
                     );
                     TTSError::ProcessError(format!("Failed to start piper: {e}"))
                 })?;
+            self.limits.apply(&child);
 
             // Send text to piper
             {
@@ -514,7 +759,79 @@ impl TTSProvider for PiperTTSProvider {
             "Piper: audio generated"
         );
 
-        self.player.play_audio(audio_data)
+        Ok(audio_data)
+    }
+}
+
+impl TTSProvider for PiperTTSProvider {
+    fn speak(&mut self, text: &str) -> Result<(), TTSError> {
+        // Stop any current playback
+        self.player.stop()?;
+
+        let parts: Vec<String> = sentences(text).into_iter().map(String::from).collect();
+        if parts.len() < 2 {
+            let audio_data = self.synthesize(text)?;
+            return self.player.play_audio(audio_data);
+        }
+
+        // Play the first sentence while the rest is synthesized
+        info!(parts = parts.len(), "Piper: streaming reading");
+        let first = self.synthesize(&parts[0])?;
+        let stream = Arc::new(StreamingSamples::new(first, self.sample_rate()));
+        self.player.play_stream(Arc::clone(&stream))?;
+        let command = self.command.clone();
+        let sample_rate = self.sample_rate();
+        streaming::synthesize_rest(stream, parts, sample_rate, move |part| {
+            command.synthesize_recovering(part, sample_rate)
+        });
+        Ok(())
+    }
+
+    fn synthesize(&mut self, text: &str) -> Result<Vec<f32>, TTSError> {
+        self.check_model()?;
+        let sample_rate = self.sample_rate();
+        let samples = self.command.synthesize_recovering(text, sample_rate)?;
+        let level = AudioPlayer::rms(&samples);
+        if level >= SILENCE_RMS {
+            return Ok(samples);
+        }
+
+        let model = model_with_extension(&self.command.model_path);
+        warn!(rms = level, model = %model.display(), "Piper produced silent audio, retrying once");
+        // Start from a freshly loaded voice or process in case the warm one got into a bad state
+        self.command.engine.lock().unwrap().take();
+        let was_warm = self.command.server.lock().unwrap().take().is_some();
+        if was_warm {
+            if let Err(e) = self.command.start_server() {
+                debug!(error = %e, "Could not restart piper server");
+            }
+        }
+        let samples = self.command.synthesize_recovering(text, sample_rate)?;
+        let level = AudioPlayer::rms(&samples);
+        if level >= SILENCE_RMS {
+            info!(rms = level, "Retry after silent audio succeeded");
+            return Ok(samples);
+        }
+
+        error!(rms = level, model = %model.display(), "Piper produced silent audio twice");
+        Err(TTSError::DamagedModel {
+            path: model,
+            reason: "it only produces silence".to_string(),
+        })
+    }
+
+    fn prewarm(&mut self) -> Result<(), TTSError> {
+        self.check_model()?;
+        self.command.warm_up()
+    }
+
+    fn play_samples(&mut self, samples: Vec<f32>) -> Result<(), TTSError> {
+        self.player.stop()?;
+        self.player.play_audio(samples)
+    }
+
+    fn hold_next_playback(&mut self) {
+        self.player.hold_next_playback();
     }
 
     fn pause(&mut self) -> Result<(), TTSError> {
@@ -529,6 +846,10 @@ impl TTSProvider for PiperTTSProvider {
         self.player.stop()
     }
 
+    fn replay(&mut self) -> Result<(), TTSError> {
+        self.player.replay()
+    }
+
     fn is_playing(&self) -> bool {
         self.player.is_playing()
     }
@@ -549,7 +870,39 @@ impl TTSProvider for PiperTTSProvider {
         self.player.get_progress()
     }
 
-    fn get_frequency_bands(&self, num_bands: usize) -> Vec<f32> {
-        self.player.get_frequency_bands(num_bands)
+    fn duration(&self) -> std::time::Duration {
+        self.player.duration()
+    }
+
+    fn position(&self) -> std::time::Duration {
+        self.player.position()
+    }
+
+    fn set_volume(&mut self, volume: f32) {
+        self.player.set_volume(volume);
+    }
+
+    fn set_speed(&mut self, speed: f32) {
+        self.player.set_speed(speed);
+    }
+
+    fn seek(&mut self, progress: f32) {
+        self.player.seek_to_progress(progress);
+    }
+
+    fn skip_sentences(&mut self, offset: i32) -> bool {
+        self.player.skip_sentences(offset)
+    }
+
+    fn sentence_starts(&self) -> Vec<std::time::Duration> {
+        self.player.sentence_starts()
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.player.sample_rate()
+    }
+
+    fn synthesized_audio(&self) -> (Vec<f32>, u32) {
+        (self.player.audio_data(), self.player.sample_rate())
     }
 }
@@ -0,0 +1,64 @@
+//! Piper running inside the app, on ONNX Runtime through piper-rs.
+//!
+//! The voice model is loaded once and kept with the provider, so readings need
+//! neither the Python virtualenv nor the `piper` binary. Text is phonemized by
+//! espeak-ng, which piper-rs links in. Only built with the `embedded-piper`
+//! feature (off by default); without it, with `piper_subprocess` set in the
+//! config, or on a GPU execution provider, Piper runs as a separate process
+//! (see [`super::piper_server`]).
+
+use std::path::Path;
+
+use super::TTSError;
+
+/// Whether this build can run Piper in-process.
+pub const AVAILABLE: bool = cfg!(feature = "embedded-piper");
+
+/// A Piper voice loaded in-process.
+pub struct EmbeddedPiper {
+    #[cfg(feature = "embedded-piper")]
+    synthesizer: piper_rs::synth::PiperSpeechSynthesizer,
+}
+
+impl EmbeddedPiper {
+    /// Load the voice at `model_path` (without extension) from its `.onnx`
+    /// model and `.onnx.json` config.
+    #[cfg(feature = "embedded-piper")]
+    pub fn load(model_path: &Path) -> Result<Self, TTSError> {
+        let config = model_path.with_extension("onnx.json");
+        let model = piper_rs::from_config_path(&config)
+            .map_err(|e| TTSError::ProcessError(format!("Failed to load Piper voice: {e}")))?;
+        let synthesizer = piper_rs::synth::PiperSpeechSynthesizer::new(model)
+            .map_err(|e| TTSError::ProcessError(format!("Failed to load Piper voice: {e}")))?;
+        Ok(Self { synthesizer })
+    }
+
+    #[cfg(not(feature = "embedded-piper"))]
+    pub fn load(_model_path: &Path) -> Result<Self, TTSError> {
+        Err(TTSError::ProcessError(
+            "Piper cannot run in-process: built without the embedded-piper feature".into(),
+        ))
+    }
+
+    /// Synthesize `text` to mono samples at the voice's sample rate.
+    #[cfg(feature = "embedded-piper")]
+    pub fn synthesize(&self, text: &str) -> Result<Vec<f32>, TTSError> {
+        let chunks = self
+            .synthesizer
+            .synthesize_parallel(text.to_string(), None)
+            .map_err(|e| TTSError::ProcessError(format!("Piper failed: {e}")))?;
+        let mut samples = Vec::new();
+        for chunk in chunks {
+            let chunk = chunk.map_err(|e| TTSError::ProcessError(format!("Piper failed: {e}")))?;
+            samples.extend(chunk.into_vec());
+        }
+        Ok(samples)
+    }
+
+    #[cfg(not(feature = "embedded-piper"))]
+    pub fn synthesize(&self, _text: &str) -> Result<Vec<f32>, TTSError> {
+        Err(TTSError::ProcessError(
+            "Piper cannot run in-process: built without the embedded-piper feature".into(),
+        ))
+    }
+}
@@ -0,0 +1,157 @@
+//! Long-running Piper process that keeps the voice model loaded.
+//!
+//! Starting Piper loads the ONNX model, which dominates the time to first
+//! audio for short readings. The server starts Piper once in `--output_dir`
+//! mode: each line written to its stdin is synthesized into a WAV file in a
+//! private directory, and the file path is reported on stdout (C++ Piper) or
+//! in a "Wrote <path>" log line on stderr (Python Piper). Both streams are
+//! drained on background threads, which forward the reported paths.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+#[cfg(target_os = "windows")]
+use std::os::windows::process::CommandExt;
+
+use tracing::{debug, info, trace, warn};
+
+use super::acceleration::ExecutionProvider;
+use super::audio_player::AudioPlayer;
+use super::TTSError;
+use crate::system::SynthesisLimits;
+
+/// A running Piper process, deleted with its output directory on drop.
+pub struct PiperServer {
+    child: Child,
+    stdin: ChildStdin,
+    wav_paths: Receiver<PathBuf>,
+    output_dir: PathBuf,
+}
+
+impl PiperServer {
+    /// Start Piper with the given model. The model loads in the background;
+    /// the first request waits for it.
+    pub fn start(
+        piper_bin: &Path,
+        model_path: &Path,
+        provider: ExecutionProvider,
+        limits: SynthesisLimits,
+    ) -> Result<Self, TTSError> {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+        let output_dir = std::env::temp_dir().join(format!(
+            "insight-reader-piper-{}-{}",
+            std::process::id(),
+            NEXT_ID.fetch_add(1, Ordering::Relaxed)
+        ));
+        std::fs::create_dir_all(&output_dir)
+            .map_err(|e| TTSError::ProcessError(format!("Failed to create piper output dir: {e}")))?;
+
+        let mut command = Command::new(piper_bin);
+        command
+            .arg("--model")
+            .arg(model_path)
+            .arg("--output_dir")
+            .arg(&output_dir)
+            .args(provider.piper_args().unwrap_or_default())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        #[cfg(target_os = "windows")]
+        command.creation_flags(crate::system::CREATE_NO_WINDOW);
+        let mut child = command.spawn().map_err(|e| {
+            let _ = std::fs::remove_dir_all(&output_dir);
+            TTSError::ProcessError(format!("Failed to start piper: {e}"))
+        })?;
+        limits.apply(&child);
+
+        let (sender, wav_paths) = mpsc::channel();
+        let stdin = child.stdin.take().expect("stdin is piped");
+        forward_wav_paths(child.stdout.take().expect("stdout is piped"), sender.clone());
+        forward_wav_paths(child.stderr.take().expect("stderr is piped"), sender);
+
+        info!(pid = child.id(), model = %model_path.display(), ?provider, "Piper server started");
+        Ok(Self {
+            child,
+            stdin,
+            wav_paths,
+            output_dir,
+        })
+    }
+
+    /// Synthesize `text` and return normalized samples.
+    pub fn synthesize(&mut self, text: &str) -> Result<Vec<f32>, TTSError> {
+        // One line per request: newlines would split the text into several files
+        let line = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        writeln!(self.stdin, "{line}")
+            .and_then(|()| self.stdin.flush())
+            .map_err(|e| TTSError::ProcessError(format!("Failed to write to piper server: {e}")))?;
+
+        let wav_path = self
+            .wav_paths
+            .recv()
+            .map_err(|_| TTSError::ProcessError("Piper server exited".into()))?;
+        let wav_data = std::fs::read(&wav_path)
+            .map_err(|e| TTSError::ProcessError(format!("Failed to read piper output: {e}")));
+        let _ = std::fs::remove_file(&wav_path);
+        let wav_data = wav_data?;
+
+        // WAV files have a 44-byte header, skip it to get raw PCM
+        if wav_data.len() <= 44 || &wav_data[0..4] != b"RIFF" {
+            return Err(TTSError::ProcessError("No audio data generated by piper".into()));
+        }
+        Ok(AudioPlayer::pcm_to_f32(&wav_data[44..]))
+    }
+}
+
+impl Drop for PiperServer {
+    fn drop(&mut self) {
+        debug!(pid = self.child.id(), "Stopping piper server");
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        if let Err(e) = std::fs::remove_dir_all(&self.output_dir) {
+            warn!(error = %e, dir = %self.output_dir.display(), "Failed to remove piper output dir");
+        }
+    }
+}
+
+/// Drain a Piper output stream, sending every WAV path it reports.
+fn forward_wav_paths(stream: impl Read + Send + 'static, sender: Sender<PathBuf>) {
+    thread::spawn(move || {
+        for line in BufReader::new(stream).lines() {
+            let Ok(line) = line else { break };
+            trace!(target: "piper", "{line}");
+            if let Some(path) = wav_path_in(&line) {
+                if sender.send(path).is_err() {
+                    break;
+                }
+            }
+        }
+    });
+}
+
+/// Path of the WAV file reported in a Piper output line, if any.
+fn wav_path_in(line: &str) -> Option<PathBuf> {
+    let line = line.trim();
+    let start = line.find("Wrote ").map(|i| i + "Wrote ".len()).unwrap_or(0);
+    let path = line[start..].trim();
+    path.ends_with(".wav").then(|| PathBuf::from(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wav_path_in_piper_output() {
+        assert_eq!(wav_path_in("/tmp/out/123.wav"), Some(PathBuf::from("/tmp/out/123.wav")));
+        assert_eq!(
+            wav_path_in("INFO:__main__:Wrote /tmp/my dir/123.wav"),
+            Some(PathBuf::from("/tmp/my dir/123.wav"))
+        );
+        assert_eq!(wav_path_in("DEBUG:piper.voice:Synthesizing"), None);
+    }
+}
@@ -0,0 +1,80 @@
+//! Pitch of the synthesized voice, in semitones from the voice's own.
+//!
+//! Piper has no pitch among its synthesis parameters, so its audio is shifted
+//! after synthesis: stretched in time without changing pitch (see
+//! [`super::time_stretch`]), then resampled back to its length, which moves
+//! every frequency by the same ratio. Polly is asked for the pitch in SSML
+//! instead (see [`percent`]).
+
+use std::sync::atomic::{AtomicI32, Ordering};
+
+use super::time_stretch;
+
+static PITCH: AtomicI32 = AtomicI32::new(0);
+
+/// Set the pitch of the next syntheses, in semitones (0 for the voice's own).
+pub fn set_pitch(semitones: i32) {
+    PITCH.store(semitones, Ordering::Relaxed);
+}
+
+/// The pitch set, in semitones.
+pub(super) fn semitones() -> i32 {
+    PITCH.load(Ordering::Relaxed)
+}
+
+/// Frequency ratio of `semitones`.
+fn ratio(semitones: i32) -> f32 {
+    2f32.powf(semitones as f32 / 12.0)
+}
+
+/// The pitch set as a change in percent, as SSML prosody takes it.
+pub(super) fn percent() -> i32 {
+    ((ratio(semitones()) - 1.0) * 100.0).round() as i32
+}
+
+/// Mono `samples` at the pitch set, same length and speed.
+pub(super) fn shift(samples: Vec<f32>, sample_rate: u32) -> Vec<f32> {
+    match semitones() {
+        0 => samples,
+        semitones => shift_by(&samples, sample_rate, semitones),
+    }
+}
+
+fn shift_by(samples: &[f32], sample_rate: u32, semitones: i32) -> Vec<f32> {
+    let ratio = ratio(semitones);
+    let stretched = time_stretch::stretch(samples, sample_rate, 1.0 / ratio);
+    // Read the stretched audio `ratio` times as fast, interpolating between samples
+    (0..samples.len())
+        .map(|i| {
+            let position = i as f32 * ratio;
+            let index = position as usize;
+            let fraction = position - index as f32;
+            let a = stretched.get(index).copied().unwrap_or(0.0);
+            let b = stretched.get(index + 1).copied().unwrap_or(0.0);
+            a + (b - a) * fraction
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+    use time_stretch::frequency;
+
+    #[test]
+    fn test_shift() {
+        let sample_rate = 16000;
+        let tone: Vec<f32> = (0..sample_rate * 2)
+            .map(|i| (2.0 * PI * 200.0 * i as f32 / sample_rate as f32).sin() * 0.5)
+            .collect();
+        let edge = sample_rate as usize / 10;
+
+        for (semitones, expected) in [(12, 400.0), (-12, 100.0), (5, 200.0 * ratio(5))] {
+            let shifted = shift_by(&tone, sample_rate, semitones);
+            assert_eq!(shifted.len(), tone.len(), "length at {semitones} semitones");
+            let middle = &shifted[edge..shifted.len() - edge];
+            assert!((frequency(middle, sample_rate) - expected).abs() < expected * 0.05, "pitch at {semitones} semitones");
+        }
+    }
+}
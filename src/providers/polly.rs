@@ -1,29 +1,58 @@
 //! AWS Polly TTS provider implementation.
 //!
 //! Uses the AWS SDK for Rust to synthesize speech and plays it using rodio.
+//!
+//! Single sentences are played from MP3. Longer texts are streamed: the first
+//! sentence plays while the rest is requested as PCM on another thread.
+//!
+//! Word speech marks are requested for each sentence once its audio is in,
+//! so the word being spoken can be highlighted (see [`super::speech_marks`]).
+
+use std::ops::Range;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use aws_config::BehaviorVersion;
-use aws_sdk_polly::types::{Engine, OutputFormat, VoiceId};
+use aws_sdk_polly::types::{Engine, OutputFormat, SpeechMarkType, TextType, VoiceId};
 use tracing::{debug, info};
 
 use super::audio_player::AudioPlayer;
+use super::pitch;
+use super::recovery::SENTENCE_GAP_SECS;
+use super::sample_store::StreamingSamples;
+use super::speech_marks::{self, WordMark};
+use super::streaming;
 use super::{TTSError, TTSProvider};
+use crate::text::prosody::{self, PAUSE};
+use crate::text::sentences::sentences;
 use crate::voices::aws;
 
 const CREDENTIALS_ERROR_MSG: &str = "AWS credentials not found. Please configure credentials via:\n  - Environment variables: AWS_ACCESS_KEY_ID and AWS_SECRET_ACCESS_KEY\n  - Or credentials file: ~/.aws/credentials";
 
 /// AWS Polly TTS provider using the official AWS SDK.
 pub struct PollyTTSProvider {
-    /// AWS Polly client
-    client: aws_sdk_polly::Client,
+    /// Client and voice, shared with streaming synthesis
+    request: PollyRequest,
     /// Shared audio playback engine
     player: AudioPlayer,
+    /// Word timings of the loaded audio, filled in as sentences are synthesized
+    word_marks: Arc<Mutex<Vec<WordMark>>>,
+}
+
+/// Everything needed to request speech, cloned into the thread synthesizing
+/// the rest of a streamed reading.
+#[derive(Clone)]
+struct PollyRequest {
+    /// AWS Polly client
+    client: aws_sdk_polly::Client,
     /// Tokio runtime for async AWS calls
-    runtime: tokio::runtime::Runtime,
+    runtime: Arc<tokio::runtime::Runtime>,
     /// Selected voice ID (e.g., "Matthew", "Joanna")
     voice_id: String,
     /// Selected engine type (e.g., "Standard", "Neural", "Generative", "LongForm")
     engine: Engine,
+    /// Polly lexicons applied to the speech, see [`crate::lexicon`]
+    lexicons: Vec<String>,
 }
 
 impl PollyTTSProvider {
@@ -82,11 +111,15 @@ impl PollyTTSProvider {
         let player = AudioPlayer::new(16000)?;
 
         Ok(Self {
-            client,
+            request: PollyRequest {
+                client,
+                runtime: Arc::new(runtime),
+                voice_id: voice_id_str,
+                engine,
+                lexicons: crate::config::load_polly_lexicons(),
+            },
             player,
-            runtime,
-            voice_id: voice_id_str,
-            engine,
+            word_marks: Arc::default(),
         })
     }
 
@@ -156,12 +189,34 @@ impl PollyTTSProvider {
     }
 }
 
-impl TTSProvider for PollyTTSProvider {
-    fn speak(&mut self, text: &str) -> Result<(), TTSError> {
-        debug!(chars = text.len(), "Polly: synthesizing speech");
+/// What Polly is sent for `text`: paced lists are read as SSML, with breaks
+/// between the items, and so is text spoken `pitch` percent off the voice's
+/// own pitch.
+fn request_input(text: &str, pitch: i32) -> (String, TextType) {
+    if pitch != 0 {
+        (prosody::to_ssml_with_pitch(text, pitch), TextType::Ssml)
+    } else if text.contains(PAUSE) {
+        (prosody::to_ssml(text), TextType::Ssml)
+    } else {
+        (text.to_string(), TextType::Text)
+    }
+}
 
-        // Stop any current playback
-        self.player.stop()?;
+impl PollyRequest {
+    /// Pitch change to request, in percent. Only standard voices take a
+    /// pitch in SSML; the others keep their own.
+    fn pitch(&self) -> i32 {
+        if self.engine == Engine::Standard {
+            pitch::percent()
+        } else {
+            0
+        }
+    }
+
+    /// Request speech for `text` from AWS Polly in the given format at 16 kHz.
+    fn request_audio(&self, text: &str, format: OutputFormat) -> Result<Vec<u8>, TTSError> {
+        debug!(chars = text.len(), format = %format.as_str(), "Polly: synthesizing speech");
+        let (text, text_type) = request_input(text, self.pitch());
 
         // Call AWS Polly to synthesize speech
         let audio_bytes = self.runtime.block_on(async {
@@ -169,9 +224,11 @@ impl TTSProvider for PollyTTSProvider {
                 .client
                 .synthesize_speech()
                 .text(text)
-                .output_format(OutputFormat::Pcm)
+                .text_type(text_type)
+                .output_format(format)
                 .voice_id(VoiceId::from(self.voice_id.as_str()))
                 .engine(self.engine.clone())
+                .set_lexicon_names(Some(self.lexicons.clone()).filter(|names| !names.is_empty()))
                 .sample_rate("16000")
                 .send()
                 .await
@@ -192,7 +249,117 @@ impl TTSProvider for PollyTTSProvider {
             ));
         }
 
-        // Convert PCM to f32 and play
+        Ok(audio_bytes)
+    }
+
+    /// Request word speech marks for the `part` of `text` whose audio starts
+    /// at `start` into the reading. A failed request only costs the
+    /// highlighting, so it is logged and gives no marks.
+    ///
+    /// Gives no marks without a request while the reading view is closed, as
+    /// each request is billed like the audio of the part.
+    fn request_word_marks(&self, text: &str, part: Range<usize>, start: Duration) -> Vec<WordMark> {
+        if !speech_marks::word_marks_wanted() {
+            return Vec::new();
+        }
+        let (input, text_type) = request_input(&text[part.clone()], self.pitch());
+        let json = self.runtime.block_on(async {
+            let response = self
+                .client
+                .synthesize_speech()
+                .text(input)
+                .text_type(text_type)
+                .output_format(OutputFormat::Json)
+                .speech_mark_types(SpeechMarkType::Word)
+                .voice_id(VoiceId::from(self.voice_id.as_str()))
+                .engine(self.engine.clone())
+                .set_lexicon_names(Some(self.lexicons.clone()).filter(|names| !names.is_empty()))
+                .send()
+                .await
+                .map_err(|e| format!("AWS Polly API error: {e}"))?;
+            let bytes = response
+                .audio_stream
+                .collect()
+                .await
+                .map_err(|e| format!("Failed to read speech marks: {e}"))?;
+            Ok::<_, String>(String::from_utf8_lossy(&bytes.into_bytes()).into_owned())
+        });
+        match json {
+            Ok(json) => {
+                let marks = speech_marks::from_polly(&json, text, part, start);
+                debug!(words = marks.len(), "Polly: speech marks received");
+                marks
+            }
+            Err(e) => {
+                debug!(error = %e, "Polly: no speech marks, word highlighting unavailable");
+                Vec::new()
+            }
+        }
+    }
+}
+
+impl TTSProvider for PollyTTSProvider {
+    fn speak(&mut self, text: &str) -> Result<(), TTSError> {
+        // Stop any current playback
+        self.player.stop()?;
+        self.word_marks.lock().unwrap().clear();
+
+        let parts: Vec<&str> = sentences(text);
+        if parts.len() < 2 {
+            // Keep the audio compressed and decode it while playing
+            let mp3 = self.request.request_audio(text, OutputFormat::Mp3)?;
+            info!(bytes = mp3.len(), "Polly: MP3 audio received");
+            self.player.play_encoded(mp3)?;
+            *self.word_marks.lock().unwrap() = self.request.request_word_marks(text, 0..text.len(), Duration::ZERO);
+            return Ok(());
+        }
+
+        // Byte range of each sentence in the text, for its word marks
+        let mut cursor = 0;
+        let ranges: Vec<Range<usize>> = parts
+            .iter()
+            .map(|part| {
+                let start = text[cursor..].find(part).map_or(cursor, |found| cursor + found);
+                cursor = start + part.len();
+                start..cursor
+            })
+            .collect();
+
+        // Play the first sentence while the rest is requested
+        info!(parts = parts.len(), "Polly: streaming reading");
+        let first = self.synthesize(parts[0])?;
+        let stream = Arc::new(StreamingSamples::new(first, self.sample_rate()));
+        self.player.play_stream(Arc::clone(&stream))?;
+        *self.word_marks.lock().unwrap() = self.request.request_word_marks(text, ranges[0].clone(), Duration::ZERO);
+
+        let request = self.request.clone();
+        let sample_rate = self.sample_rate();
+        let gap = (sample_rate as f32 * SENTENCE_GAP_SECS) as usize;
+        let word_marks = Arc::clone(&self.word_marks);
+        let text = text.to_string();
+        let mut ranges = ranges.into_iter().skip(1);
+        let parts = parts.into_iter().map(String::from).collect();
+        streaming::synthesize_rest(Arc::clone(&stream), parts, sample_rate, move |part| {
+            let range = ranges.next();
+            let pcm = request.request_audio(part, OutputFormat::Pcm)?;
+            let samples = AudioPlayer::pcm_to_f32(&pcm);
+            // The part is pushed after a short gap, at the end of the stream
+            let start = Duration::from_secs_f64((stream.len() + gap) as f64 / sample_rate as f64);
+            if let Some(range) = range {
+                let marks = request.request_word_marks(&text, range, start);
+                if !stream.is_cancelled() {
+                    word_marks.lock().unwrap().extend(marks);
+                }
+            }
+            Ok(samples)
+        });
+        Ok(())
+    }
+
+    fn synthesize(&mut self, text: &str) -> Result<Vec<f32>, TTSError> {
+        let audio_bytes = self.request.request_audio(text, OutputFormat::Pcm)?;
+
+        // Convert PCM to f32
         let audio_data = AudioPlayer::pcm_to_f32(&audio_bytes);
         let duration_sec = audio_data.len() as f32 / 16000.0;
         info!(
@@ -201,7 +368,17 @@ impl TTSProvider for PollyTTSProvider {
             "Polly: audio received"
         );
 
-        self.player.play_audio(audio_data)
+        Ok(audio_data)
+    }
+
+    fn play_samples(&mut self, samples: Vec<f32>) -> Result<(), TTSError> {
+        self.player.stop()?;
+        self.word_marks.lock().unwrap().clear();
+        self.player.play_audio(samples)
+    }
+
+    fn hold_next_playback(&mut self) {
+        self.player.hold_next_playback();
     }
 
     fn pause(&mut self) -> Result<(), TTSError> {
@@ -216,6 +393,10 @@ impl TTSProvider for PollyTTSProvider {
         self.player.stop()
     }
 
+    fn replay(&mut self) -> Result<(), TTSError> {
+        self.player.replay()
+    }
+
     fn is_playing(&self) -> bool {
         self.player.is_playing()
     }
@@ -236,7 +417,43 @@ impl TTSProvider for PollyTTSProvider {
         self.player.get_progress()
     }
 
-    fn get_frequency_bands(&self, num_bands: usize) -> Vec<f32> {
-        self.player.get_frequency_bands(num_bands)
+    fn duration(&self) -> std::time::Duration {
+        self.player.duration()
+    }
+
+    fn position(&self) -> std::time::Duration {
+        self.player.position()
+    }
+
+    fn set_volume(&mut self, volume: f32) {
+        self.player.set_volume(volume);
+    }
+
+    fn set_speed(&mut self, speed: f32) {
+        self.player.set_speed(speed);
+    }
+
+    fn seek(&mut self, progress: f32) {
+        self.player.seek_to_progress(progress);
+    }
+
+    fn skip_sentences(&mut self, offset: i32) -> bool {
+        self.player.skip_sentences(offset)
+    }
+
+    fn sentence_starts(&self) -> Vec<std::time::Duration> {
+        self.player.sentence_starts()
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.player.sample_rate()
+    }
+
+    fn synthesized_audio(&self) -> (Vec<f32>, u32) {
+        (self.player.audio_data(), self.player.sample_rate())
+    }
+
+    fn get_current_word(&self) -> Option<WordMark> {
+        speech_marks::word_at(&self.word_marks.lock().unwrap(), self.player.position()).cloned()
     }
 }
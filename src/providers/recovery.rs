@@ -0,0 +1,82 @@
+//! Sentence-by-sentence synthesis when a whole text fails.
+//!
+//! Some input (unusual characters, very long tokens) makes Piper crash. Rather
+//! than failing the whole reading, the text is synthesized again one sentence
+//! at a time: sentences that still fail are replaced by the "skipped" audio
+//! cue and logged, and the rest is read normally.
+
+use tracing::{info, warn};
+
+use super::earcons::{cue_samples, Earcon};
+use super::TTSError;
+use crate::text::sentences::sentences;
+
+/// Pause inserted between sentences synthesized separately, in seconds.
+pub(super) const SENTENCE_GAP_SECS: f32 = 0.15;
+
+/// Synthesize `text`, falling back to one sentence at a time if the whole text fails.
+///
+/// Fails only if no sentence could be synthesized.
+pub(super) fn synthesize_with_recovery(
+    text: &str,
+    sample_rate: u32,
+    mut synthesize: impl FnMut(&str) -> Result<Vec<f32>, TTSError>,
+) -> Result<Vec<f32>, TTSError> {
+    let error = match synthesize(text) {
+        Ok(samples) => return Ok(samples),
+        Err(e) => e,
+    };
+    let sentences = sentences(text);
+    if sentences.len() < 2 {
+        return Err(error);
+    }
+    warn!(error = %error, sentences = sentences.len(), "Synthesis failed, retrying sentence by sentence");
+
+    let gap = (sample_rate as f32 * SENTENCE_GAP_SECS) as usize;
+    let mut samples = Vec::new();
+    let mut skipped = 0;
+    for (index, sentence) in sentences.iter().enumerate() {
+        match synthesize(sentence) {
+            Ok(audio) => samples.extend(audio),
+            Err(e) => {
+                warn!(
+                    error = %e,
+                    sentence = index + 1,
+                    text_preview = %sentence.chars().take(60).collect::<String>(),
+                    "Sentence skipped"
+                );
+                skipped += 1;
+                samples.extend(cue_samples(Earcon::Skipped, sample_rate));
+            }
+        }
+        samples.extend(std::iter::repeat_n(0.0, gap));
+    }
+
+    if skipped == sentences.len() {
+        return Err(error);
+    }
+    info!(skipped, sentences = sentences.len(), "Synthesized sentence by sentence");
+    Ok(samples)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_failed_sentence_is_skipped() {
+        let synthesize = |text: &str| {
+            if text.contains('☃') {
+                Err(TTSError::ProcessError("crashed".into()))
+            } else {
+                Ok(vec![0.5; text.len()])
+            }
+        };
+        let samples = synthesize_with_recovery("Hello there. Snow ☃ man. Bye.", 1000, synthesize).unwrap();
+        let gap = (1000.0 * SENTENCE_GAP_SECS) as usize;
+        let expected = "Hello there.".len() + cue_samples(Earcon::Skipped, 1000).len() + "Bye.".len() + 3 * gap;
+        assert_eq!(samples.len(), expected);
+
+        assert!(synthesize_with_recovery("Only ☃ here.", 1000, synthesize).is_err());
+    }
+}
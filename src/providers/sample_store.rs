@@ -0,0 +1,745 @@
+//! Storage for synthesized samples during playback.
+//!
+//! Short readings stay in memory. Long ones are appended to a temporary
+//! 16-bit PCM file and memory-mapped, so the OS pages them in and out as
+//! playback moves and memory use no longer grows with the length of the text.
+//! Playback reads the store directly through [`SamplesSource`], so seeking
+//! never copies the remaining audio.
+//!
+//! Compressed audio (MP3 from Polly) is kept as-is and decoded while it plays;
+//! it has no random access, so playback seeks in the decoder and the waveform
+//! is fed from the decoded stream through [`VisualizerTap`].
+//!
+//! Long texts are synthesized one sentence at a time into a
+//! [`StreamingSamples`] store that playback starts on right away. It grows
+//! while it plays; if playback catches up with synthesis, silence plays until
+//! the next samples arrive. Each sentence is kept as its own block with where
+//! it starts, so appending never moves what is playing and playback can skip
+//! from sentence to sentence. Once the reading is long, new sentences are
+//! appended to a temporary file as they arrive instead of kept in memory.
+
+use std::borrow::Cow;
+use std::fs::File;
+use std::io::{BufWriter, Cursor, Write};
+use std::ops::Range;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+
+use memmap2::{Mmap, MmapOptions};
+use rodio::{Decoder, Source};
+use tracing::{debug, warn};
+
+/// Audio longer than this (in seconds) is moved to disk.
+const DISK_BACKED_MIN_SECS: usize = 5 * 60;
+
+/// Bytes per sample on disk (16-bit PCM).
+const BYTES_PER_SAMPLE: usize = 2;
+
+/// Samples held in memory, in a memory-mapped temporary file, still encoded,
+/// or still being synthesized.
+#[derive(Clone)]
+pub enum SampleStore {
+    Memory(Arc<[f32]>),
+    Disk(Arc<DiskSamples>),
+    Encoded(Arc<EncodedSamples>),
+    Streaming(Arc<StreamingSamples>),
+}
+
+impl Default for SampleStore {
+    fn default() -> Self {
+        Self::Memory(Arc::default())
+    }
+}
+
+impl SampleStore {
+    /// Store the samples, moving them to disk if they are long.
+    pub fn new(samples: Vec<f32>, sample_rate: u32) -> Self {
+        if samples.len() < DISK_BACKED_MIN_SECS * sample_rate as usize {
+            return Self::Memory(samples.into());
+        }
+        match DiskSamples::create(&samples) {
+            Ok(disk) => {
+                debug!(samples = samples.len(), path = %disk.file.path.display(), "Samples moved to disk");
+                Self::Disk(Arc::new(disk))
+            }
+            Err(e) => {
+                warn!(error = %e, "Failed to move samples to disk, keeping them in memory");
+                Self::Memory(samples.into())
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Memory(samples) => samples.len(),
+            Self::Disk(disk) => disk.len,
+            Self::Encoded(encoded) => encoded.len,
+            Self::Streaming(stream) => stream.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Whether more samples are still to come.
+    pub fn is_pending(&self) -> bool {
+        matches!(self, Self::Streaming(stream) if !stream.is_finished())
+    }
+
+    /// Length the audio is expected to reach, for progress and duration:
+    /// an estimate while streaming, the length otherwise.
+    pub fn expected_len(&self) -> usize {
+        match self {
+            Self::Streaming(stream) if !stream.is_finished() => {
+                self.len().max(stream.expected_len.load(Ordering::Relaxed))
+            }
+            _ => self.len(),
+        }
+    }
+
+    /// Whether samples can be read at any position (encoded audio has to be decoded in order).
+    pub fn is_random_access(&self) -> bool {
+        !matches!(self, Self::Encoded(_))
+    }
+
+    /// Sample at `index`, or `None` if out of range or not randomly accessible.
+    fn get(&self, index: usize) -> Option<f32> {
+        match self {
+            Self::Memory(samples) => samples.get(index).copied(),
+            Self::Disk(disk) => (index < disk.len).then(|| disk.get(index)),
+            Self::Encoded(_) => None,
+            Self::Streaming(stream) => stream.part_at(index).map(|(start, part)| part.get(index - start)),
+        }
+    }
+
+    /// Samples in `range`, borrowed when they are in memory.
+    ///
+    /// Encoded audio is decoded from the start, so avoid this in hot paths.
+    pub fn slice(&self, range: Range<usize>) -> Cow<'_, [f32]> {
+        match self {
+            Self::Memory(samples) => Cow::Borrowed(&samples[range]),
+            Self::Disk(disk) => Cow::Owned(range.map(|i| disk.get(i)).collect()),
+            Self::Streaming(stream) => Cow::Owned(stream.slice(range)),
+            Self::Encoded(encoded) => Cow::Owned(
+                encoded
+                    .samples()
+                    .skip(range.start)
+                    .take(range.len())
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Copy of all samples.
+    pub fn to_vec(&self) -> Vec<f32> {
+        self.slice(0..self.len()).into_owned()
+    }
+
+    /// Playback source starting at `position`.
+    pub fn source_from(&self, position: usize, sample_rate: u32) -> Box<dyn Source<Item = f32> + Send> {
+        match self {
+            Self::Encoded(encoded) => {
+                let offset = Duration::from_secs_f64(position as f64 / sample_rate as f64);
+                let mut decoded = encoded.decoder().convert_samples::<f32>();
+                if position == 0 {
+                    return Box::new(VisualizerTap::new(decoded, sample_rate));
+                }
+                match decoded.try_seek(offset) {
+                    Ok(()) => Box::new(VisualizerTap::new(decoded, sample_rate)),
+                    Err(e) => {
+                        debug!(error = %e, "Decoder cannot seek, skipping instead");
+                        Box::new(VisualizerTap::new(decoded.skip_duration(offset), sample_rate))
+                    }
+                }
+            }
+            _ => Box::new(SamplesSource::new(self.clone(), position, sample_rate)),
+        }
+    }
+}
+
+/// Compressed audio (any format rodio decodes) kept encoded until played.
+pub struct EncodedSamples {
+    bytes: Arc<[u8]>,
+    /// Number of decoded samples
+    len: usize,
+}
+
+impl EncodedSamples {
+    /// Wrap encoded audio, counting its samples from the MP3 frame headers,
+    /// or by decoding it once for other formats.
+    pub fn new(bytes: Vec<u8>) -> Result<Self, String> {
+        let bytes: Arc<[u8]> = bytes.into();
+        Decoder::new(Cursor::new(Arc::clone(&bytes))).map_err(|e| format!("Failed to decode audio: {e}"))?;
+        let mut encoded = Self { bytes, len: 0 };
+        encoded.len = match mp3_sample_count(&encoded.bytes) {
+            Some(len) => len,
+            None => encoded.samples().count(),
+        };
+        debug!(bytes = encoded.bytes.len(), samples = encoded.len, "Encoded audio stored");
+        Ok(encoded)
+    }
+
+    /// Fresh decoder over the audio. Only call after [`EncodedSamples::new`] validated it.
+    fn decoder(&self) -> Decoder<Cursor<Arc<[u8]>>> {
+        Decoder::new(Cursor::new(Arc::clone(&self.bytes))).expect("validated in EncodedSamples::new")
+    }
+
+    /// All samples, decoded in order.
+    fn samples(&self) -> impl Iterator<Item = f32> {
+        self.decoder().convert_samples::<f32>()
+    }
+}
+
+/// Decoded samples (all channels) in MP3 audio, added up from its frame
+/// headers, or `None` if it holds no MPEG layer III frames.
+fn mp3_sample_count(bytes: &[u8]) -> Option<usize> {
+    let mut offset = id3v2_len(bytes);
+    let mut samples = 0;
+    let mut frames = 0;
+    while offset + 4 <= bytes.len() {
+        let Some(frame) = mp3_frame(&bytes[offset..offset + 4]) else {
+            // Not a frame header: look for the next one
+            offset += 1;
+            continue;
+        };
+        let data = &bytes[offset..bytes.len().min(offset + frame.len)];
+        // The Xing/Info frame of VBR and LAME files describes the stream and holds no audio
+        let info = frames == 0 && data.windows(4).take(40).any(|tag| tag == b"Xing" || tag == b"Info");
+        if !info {
+            samples += frame.samples;
+        }
+        frames += 1;
+        offset += frame.len;
+    }
+    (frames > 0).then_some(samples)
+}
+
+/// Length of an ID3v2 tag at the start of `bytes`, 0 without one.
+fn id3v2_len(bytes: &[u8]) -> usize {
+    match bytes {
+        [b'I', b'D', b'3', _, _, flags, size @ ..] if size.len() >= 4 => {
+            // Syncsafe: 7 bits per byte
+            let size = size[..4].iter().fold(0, |len, &byte| (len << 7) | (byte & 0x7F) as usize);
+            let footer = if flags & 0x10 != 0 { 10 } else { 0 };
+            10 + size + footer
+        }
+        _ => 0,
+    }
+}
+
+/// Frame length in bytes and decoded samples (all channels) of an MPEG layer III frame.
+struct Mp3Frame {
+    len: usize,
+    samples: usize,
+}
+
+/// The MPEG layer III frame starting with `header`, if it is a valid frame header.
+fn mp3_frame(header: &[u8]) -> Option<Mp3Frame> {
+    const BITRATES_V1: [usize; 15] = [0, 32, 40, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320];
+    const BITRATES_V2: [usize; 15] = [0, 8, 16, 24, 32, 40, 48, 56, 64, 80, 96, 112, 128, 144, 160];
+    let &[sync, b1, b2, b3] = header else {
+        return None;
+    };
+    // 11 sync bits, then layer III (01)
+    if sync != 0xFF || b1 & 0xE0 != 0xE0 || (b1 >> 1) & 0x03 != 0x01 {
+        return None;
+    }
+    // Version: 3 is MPEG 1, 2 MPEG 2, 0 MPEG 2.5
+    let version = (b1 >> 3) & 0x03;
+    let bitrate_index = (b2 >> 4) as usize;
+    let rate_index = ((b2 >> 2) & 0x03) as usize;
+    // Free-format bitrate (0) has no length in the header
+    if version == 1 || bitrate_index == 0 || bitrate_index == 15 || rate_index == 3 {
+        return None;
+    }
+    let mpeg1 = version == 3;
+    let kbps = if mpeg1 { BITRATES_V1 } else { BITRATES_V2 }[bitrate_index];
+    let sample_rate = [44100, 48000, 32000][rate_index] / if mpeg1 { 1 } else if version == 2 { 2 } else { 4 };
+    let padding = ((b2 >> 1) & 0x01) as usize;
+    let channels = if b3 >> 6 == 0x03 { 1 } else { 2 };
+    let (bytes_per_kbps, samples) = if mpeg1 { (144_000, 1152) } else { (72_000, 576) };
+    Some(Mp3Frame {
+        len: bytes_per_kbps * kbps / sample_rate + padding,
+        samples: samples * channels,
+    })
+}
+
+/// Samples of a reading still being synthesized, appended as each part is ready.
+#[derive(Default)]
+pub struct StreamingSamples {
+    /// Each part with where it starts, in order. Parts are never changed once
+    /// pushed, so playback holds on to the one it reads without the lock.
+    parts: RwLock<Vec<(usize, StreamPart)>>,
+    /// File the parts past `disk_from` are written to, created with the
+    /// first of them. Declared after `parts` so they are unmapped first.
+    file: Mutex<Option<SampleFile>>,
+    /// Length from which parts go to disk; out of reach once writing failed
+    disk_from: AtomicUsize,
+    /// Samples pushed so far
+    len: AtomicUsize,
+    /// Estimated length of the whole reading
+    expected_len: AtomicUsize,
+    /// Position playback has reached, so synthesis does not run too far ahead
+    played: AtomicUsize,
+    finished: AtomicBool,
+    /// Playback no longer wants the rest
+    cancelled: AtomicBool,
+}
+
+impl StreamingSamples {
+    /// Start a stream with the first part of a reading at `sample_rate`.
+    pub fn new(first: Vec<f32>, sample_rate: u32) -> Self {
+        Self {
+            expected_len: AtomicUsize::new(first.len()),
+            len: AtomicUsize::new(first.len()),
+            parts: RwLock::new(vec![(0, StreamPart::Memory(first.into()))]),
+            disk_from: AtomicUsize::new(DISK_BACKED_MIN_SECS * sample_rate as usize),
+            ..Self::default()
+        }
+    }
+
+    /// Append the next part, with the updated estimate of the whole length.
+    pub fn push(&self, samples: &[f32], expected_len: usize) {
+        let start = self.len();
+        let on_disk = if start >= self.disk_from.load(Ordering::Relaxed) {
+            self.write_part(samples)
+        } else {
+            None
+        };
+        let part = on_disk.unwrap_or_else(|| StreamPart::Memory(samples.into()));
+        self.parts.write().unwrap().push((start, part));
+        // Only counted once the part is in, so readers never look past the parts
+        self.len.store(start + samples.len(), Ordering::Release);
+        self.expected_len.store(expected_len, Ordering::Relaxed);
+    }
+
+    /// Append a part to the stream's file and map it for playback, or
+    /// `None` to keep it in memory if the file cannot be written.
+    fn write_part(&self, samples: &[f32]) -> Option<StreamPart> {
+        let mut file = self.file.lock().unwrap();
+        let written = match file.as_mut() {
+            Some(file) => Ok(file),
+            None => SampleFile::create().map(|created| {
+                debug!(path = %created.path.display(), "Streamed reading is long, moving new parts to disk");
+                file.insert(created)
+            }),
+        }
+        .and_then(|file| {
+            let start = file.len;
+            file.append(samples)?;
+            file.map(start..file.len)
+        });
+        match written {
+            Ok(map) => Some(StreamPart::Disk(Arc::new(map))),
+            Err(e) => {
+                warn!(error = %e, "Failed to move streamed samples to disk, keeping the rest in memory");
+                // A failed write may have left part of the samples in the file
+                self.disk_from.store(usize::MAX, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    /// The part holding `position`, with where it starts, or `None` if
+    /// `position` is not synthesized yet.
+    fn part_at(&self, position: usize) -> Option<(usize, StreamPart)> {
+        if position >= self.len() {
+            return None;
+        }
+        let parts = self.parts.read().unwrap();
+        let index = parts.partition_point(|(start, _)| *start <= position).checked_sub(1)?;
+        let (start, part) = &parts[index];
+        (position < start + part.len()).then(|| (*start, part.clone()))
+    }
+
+    /// Copy of the samples in `range` synthesized so far.
+    fn slice(&self, range: Range<usize>) -> Vec<f32> {
+        let mut samples = Vec::with_capacity(range.len());
+        for (start, part) in self.parts.read().unwrap().iter() {
+            let from = range.start.max(*start);
+            let to = range.end.min(start + part.len());
+            if from >= to {
+                continue;
+            }
+            match part {
+                StreamPart::Memory(part) => samples.extend_from_slice(&part[from - start..to - start]),
+                StreamPart::Disk(_) => samples.extend((from..to).map(|index| part.get(index - start))),
+            }
+        }
+        samples
+    }
+
+    /// Start of the part `offset` parts away from the one playing at
+    /// `position` (0 is its own start), or `None` if that part is not
+    /// synthesized yet. Going back stops at the first part.
+    pub fn part_start(&self, position: usize, offset: i32) -> Option<usize> {
+        let parts = self.parts.read().unwrap();
+        let current = parts.iter().rposition(|(start, _)| *start <= position).unwrap_or(0);
+        let target = current.checked_add_signed(offset as isize).unwrap_or(0);
+        parts.get(target).map(|(start, _)| *start)
+    }
+
+    /// Where each part synthesized so far starts, in samples.
+    pub fn part_starts(&self) -> Vec<usize> {
+        self.parts.read().unwrap().iter().map(|(start, _)| *start).collect()
+    }
+
+    pub fn set_played(&self, position: usize) {
+        self.played.store(position, Ordering::Relaxed);
+    }
+
+    pub fn played(&self) -> usize {
+        self.played.load(Ordering::Relaxed)
+    }
+
+    /// Mark the stream complete; playback ends with the last sample.
+    pub fn finish(&self) {
+        self.finished.store(true, Ordering::Relaxed);
+    }
+
+    pub fn len(&self) -> usize {
+        self.len.load(Ordering::Acquire)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished.load(Ordering::Relaxed)
+    }
+
+    /// Stop the stream where it is, telling synthesis to give up on the rest.
+    /// Does nothing once the stream is complete.
+    pub fn cancel(&self) {
+        if !self.is_finished() {
+            self.cancelled.store(true, Ordering::Relaxed);
+            self.finish();
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+/// One part of a streamed reading, in memory or mapped from the stream's file.
+#[derive(Clone)]
+enum StreamPart {
+    Memory(Arc<[f32]>),
+    Disk(Arc<Mmap>),
+}
+
+impl StreamPart {
+    fn len(&self) -> usize {
+        match self {
+            Self::Memory(samples) => samples.len(),
+            Self::Disk(map) => map.len() / BYTES_PER_SAMPLE,
+        }
+    }
+
+    fn get(&self, index: usize) -> f32 {
+        match self {
+            Self::Memory(samples) => samples[index],
+            Self::Disk(map) => sample_at(map, index),
+        }
+    }
+}
+
+/// Temporary file of 16-bit PCM samples, written a chunk at a time as the
+/// samples are produced and deleted on drop. Samples are only ever appended,
+/// so what is written can be mapped while more is still to come.
+struct SampleFile {
+    file: Option<File>,
+    path: PathBuf,
+    /// Samples written so far
+    len: usize,
+}
+
+impl SampleFile {
+    fn create() -> std::io::Result<Self> {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+        let path = std::env::temp_dir().join(format!(
+            "insight-reader-{}-{}.pcm",
+            std::process::id(),
+            NEXT_ID.fetch_add(1, Ordering::Relaxed)
+        ));
+        let file = File::options().read(true).write(true).create_new(true).open(&path)?;
+        Ok(Self {
+            file: Some(file),
+            path,
+            len: 0,
+        })
+    }
+
+    /// Write `samples` after the ones already in the file.
+    fn append(&mut self, samples: &[f32]) -> std::io::Result<()> {
+        let file = self.file.as_mut().expect("file lives until drop");
+        let mut writer = BufWriter::new(file);
+        for &sample in samples {
+            let sample = (sample * 32767.0).clamp(-32768.0, 32767.0) as i16;
+            writer.write_all(&sample.to_le_bytes())?;
+        }
+        writer.flush()?;
+        self.len += samples.len();
+        Ok(())
+    }
+
+    /// Map the samples in `range`, which must be written already.
+    fn map(&self, range: Range<usize>) -> std::io::Result<Mmap> {
+        let file = self.file.as_ref().expect("file lives until drop");
+        // SAFETY: the file is private to this process and written bytes never change
+        unsafe {
+            MmapOptions::new()
+                .offset((range.start * BYTES_PER_SAMPLE) as u64)
+                .len(range.len() * BYTES_PER_SAMPLE)
+                .map(file)
+        }
+    }
+}
+
+impl Drop for SampleFile {
+    fn drop(&mut self) {
+        // Close first: Windows cannot delete an open file
+        self.file = None;
+        if let Err(e) = std::fs::remove_file(&self.path) {
+            warn!(error = %e, path = %self.path.display(), "Failed to remove temporary samples file");
+        }
+    }
+}
+
+/// 16-bit PCM samples in a memory-mapped temporary file, deleted on drop.
+pub struct DiskSamples {
+    // Declared first so it is unmapped before the file is deleted
+    map: Mmap,
+    file: SampleFile,
+    len: usize,
+}
+
+impl DiskSamples {
+    fn create(samples: &[f32]) -> std::io::Result<Self> {
+        let mut file = SampleFile::create()?;
+        file.append(samples)?;
+        Ok(Self {
+            map: file.map(0..file.len)?,
+            len: file.len,
+            file,
+        })
+    }
+
+    fn get(&self, index: usize) -> f32 {
+        sample_at(&self.map, index)
+    }
+}
+
+/// Sample at `index` in mapped 16-bit PCM.
+fn sample_at(map: &[u8], index: usize) -> f32 {
+    let offset = index * BYTES_PER_SAMPLE;
+    i16::from_le_bytes([map[offset], map[offset + 1]]) as f32 / 32768.0
+}
+
+/// Rodio source playing a [`SampleStore`] from a given position.
+pub struct SamplesSource {
+    /// Part of a stream being read, with where it starts, so the stream is
+    /// only locked when playback moves on to the next part. Declared before
+    /// `store` so it is unmapped before the stream can delete its file.
+    part: Option<(usize, StreamPart)>,
+    store: SampleStore,
+    position: usize,
+    sample_rate: u32,
+}
+
+impl SamplesSource {
+    pub fn new(store: SampleStore, position: usize, sample_rate: u32) -> Self {
+        Self {
+            part: None,
+            store,
+            position,
+            sample_rate,
+        }
+    }
+
+    /// Sample at the position, looked up in the part being read when streaming.
+    fn sample(&mut self) -> Option<f32> {
+        let SampleStore::Streaming(stream) = &self.store else {
+            return self.store.get(self.position);
+        };
+        let position = self.position;
+        let in_part = |(start, part): &(usize, StreamPart)| (*start..start + part.len()).contains(&position);
+        if !self.part.as_ref().is_some_and(in_part) {
+            self.part = stream.part_at(position);
+        }
+        self.part.as_ref().map(|(start, part)| part.get(position - start))
+    }
+}
+
+impl Iterator for SamplesSource {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        match self.sample() {
+            Some(sample) => {
+                self.position += 1;
+                Some(sample)
+            }
+            // Synthesis has not caught up with playback: play silence until it does
+            None if self.store.is_pending() => Some(0.0),
+            None => None,
+        }
+    }
+}
+
+impl Source for SamplesSource {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        1
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        if self.store.is_pending() {
+            return None;
+        }
+        let remaining = self.store.len().saturating_sub(self.position);
+        Some(Duration::from_secs_f64(remaining as f64 / self.sample_rate as f64))
+    }
+}
+
+/// Source wrapper feeding the visualizer with the samples as they are played.
+///
+/// Used for encoded audio, where the position tracker cannot read samples itself.
+pub struct VisualizerTap<S> {
+    inner: S,
+    chunk: Vec<f32>,
+    chunk_len: usize,
+}
+
+impl<S: Source<Item = f32>> VisualizerTap<S> {
+    fn new(inner: S, sample_rate: u32) -> Self {
+        let chunk_len = (sample_rate as usize * super::audio_player::CHUNK_DURATION_MS / 1000).max(1);
+        Self {
+            inner,
+            chunk: Vec::with_capacity(chunk_len),
+            chunk_len,
+        }
+    }
+}
+
+impl<S: Source<Item = f32>> Iterator for VisualizerTap<S> {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        let sample = self.inner.next()?;
+        self.chunk.push(sample);
+        if self.chunk.len() >= self.chunk_len {
+            let chunk: Arc<[f32]> = std::mem::take(&mut self.chunk).into();
+            let len = chunk.len();
+            super::visualizer::analyze(SampleStore::Memory(chunk), 0..len);
+            self.chunk.reserve(self.chunk_len);
+        }
+        Some(sample)
+    }
+}
+
+impl<S: Source<Item = f32>> Source for VisualizerTap<S> {
+    fn current_frame_len(&self) -> Option<usize> {
+        self.inner.current_frame_len()
+    }
+
+    fn channels(&self) -> u16 {
+        self.inner.channels()
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.inner.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.inner.total_duration()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disk_store_round_trips_and_cleans_up() {
+        let samples: Vec<f32> = (0..1000).map(|i| (i as f32 / 1000.0) - 0.5).collect();
+        let disk = DiskSamples::create(&samples).unwrap();
+        let path = disk.file.path.clone();
+        let store = SampleStore::Disk(Arc::new(disk));
+        assert_eq!(store.len(), samples.len());
+        for (a, b) in store.to_vec().iter().zip(&samples) {
+            assert!((a - b).abs() < 1.0 / 16384.0);
+        }
+        let played: Vec<f32> = SamplesSource::new(store.clone(), 990, 1000).collect();
+        assert_eq!(played.len(), 10);
+        drop(store);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_mp3_sample_count() {
+        // MPEG 2 layer III, 48 kbps, 22050 Hz, mono: 156-byte frames of 576 samples
+        let frame = |padding: u8| {
+            let mut frame = vec![0xFF, 0xF3, 0x60 | padding << 1, 0xC0];
+            frame.resize(156 + padding as usize, 0);
+            frame
+        };
+        let mut mp3 = vec![b'I', b'D', b'3', 4, 0, 0, 0, 0, 0x01, 0x00];
+        mp3.extend([0; 128]);
+        for padding in [0, 1, 0] {
+            mp3.extend(frame(padding));
+        }
+        assert_eq!(mp3_sample_count(&mp3), Some(3 * 576));
+
+        // A leading Xing frame holds no audio
+        let mut xing = frame(0);
+        xing[21..25].copy_from_slice(b"Xing");
+        assert_eq!(mp3_sample_count(&[xing, frame(0)].concat()), Some(576));
+
+        assert_eq!(mp3_sample_count(b"RIFF\0\0\0\0WAVEfmt "), None);
+    }
+
+    #[test]
+    fn test_streaming_source_reads_across_parts() {
+        let stream = Arc::new(StreamingSamples::new(vec![1.0; 3], 1000));
+        let mut source = SamplesSource::new(SampleStore::Streaming(Arc::clone(&stream)), 1, 1000);
+        assert_eq!(source.by_ref().take(4).collect::<Vec<_>>(), [1.0, 1.0, 0.0, 0.0]);
+        stream.push(&[], 10);
+        stream.push(&[2.0, 3.0], 10);
+        stream.finish();
+        assert_eq!(source.collect::<Vec<_>>(), [2.0, 3.0]);
+        assert_eq!(stream.part_starts(), [0, 3, 3]);
+        assert_eq!(SampleStore::Streaming(stream).slice(2..5).into_owned(), [1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn test_long_stream_moves_new_parts_to_disk() {
+        // At 1 Hz, parts after the first five minutes of samples go to disk
+        let stream = Arc::new(StreamingSamples::new(vec![0.25; DISK_BACKED_MIN_SECS], 1));
+        stream.push(&[0.5; 4], 0);
+        stream.push(&[-0.5; 2], 0);
+        stream.finish();
+        let path = stream.file.lock().unwrap().as_ref().map(|file| file.path.clone()).unwrap();
+        assert!(matches!(stream.part_at(DISK_BACKED_MIN_SECS + 1), Some((_, StreamPart::Disk(_)))));
+
+        let store = SampleStore::Streaming(Arc::clone(&stream));
+        let tail = [0.25, 0.5, 0.5, 0.5, 0.5, -0.5, -0.5];
+        let close = |samples: &[f32]| {
+            samples.len() == tail.len() && samples.iter().zip(tail).all(|(a, b)| (a - b).abs() < 1.0 / 16384.0)
+        };
+        assert!(close(&store.slice(DISK_BACKED_MIN_SECS - 1..DISK_BACKED_MIN_SECS + 6)));
+        let played: Vec<f32> = SamplesSource::new(store.clone(), DISK_BACKED_MIN_SECS - 1, 1).collect();
+        assert!(close(&played));
+
+        drop((stream, store));
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,107 @@
+//! Word timings of speech, for highlighting the word being spoken.
+//!
+//! Polly reports when each word starts as speech marks, requested along with
+//! the audio of each sentence. The provider keeps the [`WordMark`]s with the
+//! audio of the reading and looks them up by playback position in
+//! [`TTSProvider::get_current_word`](super::TTSProvider::get_current_word).
+//!
+//! Polly bills speech marks like the audio itself, so they are only requested
+//! while the reading view, which shows them, is open.
+
+use std::ops::Range;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use serde::Deserialize;
+
+/// Whether the reading view is open, set by the UI.
+static WORD_MARKS_WANTED: AtomicBool = AtomicBool::new(false);
+
+/// Tell providers whether the spoken word is shown, so word marks are only
+/// requested while they are.
+pub fn set_word_marks_wanted(wanted: bool) {
+    WORD_MARKS_WANTED.store(wanted, Ordering::Relaxed);
+}
+
+pub(super) fn word_marks_wanted() -> bool {
+    WORD_MARKS_WANTED.load(Ordering::Relaxed)
+}
+
+/// A word of the speech and when it is spoken.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WordMark {
+    /// Time into the audio the word starts at
+    pub time: Duration,
+    /// Byte range of the word in the text passed to `speak`
+    pub range: Range<usize>,
+    /// Start of the word as a fraction of the characters of that text (0.0 to 1.0)
+    pub fraction: f32,
+    /// The word as spoken
+    pub word: String,
+}
+
+/// One line of Polly speech marks.
+#[derive(Deserialize)]
+struct PollyMark {
+    /// Milliseconds into the audio
+    time: u64,
+    #[serde(rename = "type")]
+    kind: String,
+    value: String,
+}
+
+/// Word marks from Polly speech marks (JSON lines) for the `part` of `text`
+/// whose audio starts at `start` into the reading.
+///
+/// Polly's offsets point into the request, which is SSML for paced text, so
+/// words are located in `text` instead; words not found are left out.
+pub(super) fn from_polly(json: &str, text: &str, part: Range<usize>, start: Duration) -> Vec<WordMark> {
+    let total = text.chars().count().max(1);
+    let mut cursor = part.start;
+    json.lines()
+        .filter_map(|line| serde_json::from_str::<PollyMark>(line).ok())
+        .filter(|mark| mark.kind == "word")
+        .filter_map(|mark| {
+            let found = text.get(cursor..part.end)?.find(&mark.value)?;
+            let range = cursor + found..cursor + found + mark.value.len();
+            cursor = range.end;
+            Some(WordMark {
+                time: start + Duration::from_millis(mark.time),
+                fraction: text[..range.start].chars().count() as f32 / total as f32,
+                range,
+                word: mark.value,
+            })
+        })
+        .collect()
+}
+
+/// The mark of the word spoken at `position` into the audio.
+pub(super) fn word_at(marks: &[WordMark], position: Duration) -> Option<&WordMark> {
+    marks.iter().take_while(|mark| mark.time <= position).last()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_polly() {
+        let text = "Hi. Milk & eggs, milk.";
+        let json = concat!(
+            "{\"time\":0,\"type\":\"sentence\",\"start\":0,\"end\":30,\"value\":\"Milk &amp; eggs, milk.\"}\n",
+            "{\"time\":6,\"type\":\"word\",\"start\":11,\"end\":15,\"value\":\"Milk\"}\n",
+            "{\"time\":380,\"type\":\"word\",\"start\":16,\"end\":21,\"value\":\"&amp;\"}\n",
+            "{\"time\":512,\"type\":\"word\",\"start\":22,\"end\":26,\"value\":\"eggs\"}\n",
+            "{\"time\":900,\"type\":\"word\",\"start\":28,\"end\":32,\"value\":\"milk\"}\n",
+        );
+        let marks = from_polly(json, text, 4..text.len(), Duration::from_secs(1));
+        let words: Vec<(&str, Range<usize>)> = marks.iter().map(|mark| (mark.word.as_str(), mark.range.clone())).collect();
+        assert_eq!(words, [("Milk", 4..8), ("eggs", 11..15), ("milk", 17..21)]);
+        assert_eq!(marks[1].time, Duration::from_millis(1512));
+        assert!((marks[0].fraction - 4.0 / 22.0).abs() < 0.001);
+
+        assert_eq!(word_at(&marks, Duration::from_millis(500)), None);
+        assert_eq!(word_at(&marks, Duration::from_millis(1600)).map(|mark| mark.word.as_str()), Some("eggs"));
+        assert_eq!(word_at(&marks, Duration::from_secs(5)).map(|mark| mark.word.as_str()), Some("milk"));
+    }
+}
@@ -0,0 +1,115 @@
+//! Streaming synthesis: long texts start playing after their first sentence.
+//!
+//! The text is cut into sentences (see [`crate::text::sentences`]). Playback
+//! starts on the first one while a background thread synthesizes the others,
+//! one at a time, into the same [`StreamingSamples`]. The thread stays at most
+//! [`LOOKAHEAD_SECS`] ahead of playback, so a long article never sits in
+//! memory whole before it is heard, and gives up once playback cancels the
+//! stream.
+
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use tracing::{debug, info, warn};
+
+use super::earcons::{cue_samples, Earcon};
+use super::recovery::SENTENCE_GAP_SECS;
+use super::sample_store::StreamingSamples;
+use super::TTSError;
+
+/// Synthesis waits while it is this many seconds of audio ahead of playback.
+const LOOKAHEAD_SECS: usize = 20;
+
+/// How often waiting synthesis checks on playback.
+const LOOKAHEAD_POLL: Duration = Duration::from_millis(50);
+
+/// Synthesize all but the first of `parts` (the sentences of the reading)
+/// into `stream` on a background thread, then mark it finished. The stream
+/// holds the first part already.
+///
+/// Parts that fail are replaced by the "skipped" audio cue, like sentences in
+/// [`super::recovery`]. The length of the whole reading is estimated from the
+/// characters synthesized so far.
+pub(super) fn synthesize_rest(
+    stream: Arc<StreamingSamples>,
+    parts: Vec<String>,
+    sample_rate: u32,
+    mut synthesize: impl FnMut(&str) -> Result<Vec<f32>, TTSError> + Send + 'static,
+) {
+    thread::spawn(move || {
+        let total_chars: usize = parts.iter().map(|part| part.len()).sum();
+        let mut done_chars = parts.first().map_or(0, |part| part.len());
+        let gap = vec![0.0; (sample_rate as f32 * SENTENCE_GAP_SECS) as usize];
+        let lookahead = LOOKAHEAD_SECS * sample_rate as usize;
+        for (index, part) in parts.iter().enumerate().skip(1) {
+            while stream.len() > stream.played() + lookahead && !stream.is_cancelled() {
+                thread::sleep(LOOKAHEAD_POLL);
+            }
+            if stream.is_cancelled() {
+                debug!(left = parts.len() - index, "Streamed reading cancelled, not synthesizing the rest");
+                return;
+            }
+            let mut samples = match synthesize(part) {
+                Ok(samples) => samples,
+                Err(e) => {
+                    warn!(
+                        error = %e,
+                        part = index + 1,
+                        text_preview = %part.chars().take(60).collect::<String>(),
+                        "Part of streamed reading skipped"
+                    );
+                    cue_samples(Earcon::Skipped, sample_rate)
+                }
+            };
+            samples.splice(0..0, gap.iter().copied());
+            done_chars += part.len();
+            let so_far = stream.len() + samples.len();
+            stream.push(&samples, so_far * total_chars / done_chars.max(1));
+        }
+        stream.finish();
+        info!(parts = parts.len(), "Streamed reading fully synthesized");
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stream_parts_and_lookahead() {
+        let stream = Arc::new(StreamingSamples::new(vec![1.0; 10], 1000));
+        let all = vec!["first".to_string(), "ok".to_string(), "fail".to_string()];
+        synthesize_rest(Arc::clone(&stream), all, 1000, |part| {
+            if part == "fail" {
+                Err(TTSError::ProcessError("crashed".into()))
+            } else {
+                Ok(vec![0.5; 5])
+            }
+        });
+        while !stream.is_finished() {
+            thread::sleep(std::time::Duration::from_millis(1));
+        }
+        let gap = (1000.0 * SENTENCE_GAP_SECS) as usize;
+        let expected = 10 + 2 * gap + 5 + cue_samples(Earcon::Skipped, 1000).len();
+        assert_eq!(stream.len(), expected);
+        assert!(!stream.is_cancelled());
+        assert_eq!(stream.part_start(0, 1), Some(10));
+        assert_eq!(stream.part_start(12, 1), Some(10 + gap + 5));
+        assert_eq!(stream.part_start(12, -1), Some(0));
+        assert_eq!(stream.part_start(12, -5), Some(0));
+        assert_eq!(stream.part_start(12, 3), None);
+
+        // Synthesis holds back until playback gets close enough
+        let ahead = Arc::new(StreamingSamples::new(vec![0.0; 25], 1));
+        let parts = vec!["a".to_string(), "b".to_string()];
+        synthesize_rest(Arc::clone(&ahead), parts, 1, |_| Ok(vec![0.5; 5]));
+        thread::sleep(LOOKAHEAD_POLL * 3);
+        assert_eq!(ahead.len(), 25);
+        ahead.set_played(10);
+        while !ahead.is_finished() {
+            thread::sleep(std::time::Duration::from_millis(1));
+        }
+        assert_eq!(ahead.len(), 30);
+    }
+}
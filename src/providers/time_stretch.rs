@@ -0,0 +1,231 @@
+//! Pitch-preserving speed change of mono speech (WSOLA).
+//!
+//! Playing samples faster raises the voice like a tape sped up. Instead, the
+//! audio is cut into overlapping windowed frames that are laid out at a fixed
+//! hop but taken from the input at `hop × speed`: frames are skipped or
+//! repeated, the pitch inside each frame is untouched. Each frame is nudged
+//! within a small tolerance to where it best lines up with the previous one,
+//! which avoids the warble of plain overlap-add.
+//!
+//! The speed is read before every frame, so it can change while playing.
+
+use std::collections::VecDeque;
+use std::f32::consts::PI;
+use std::time::Duration;
+
+use rodio::Source;
+
+/// Frame length; speech pitch periods are well under half of it.
+const FRAME_MS: u32 = 40;
+
+/// Time stretcher working on one channel, fed from any sample iterator.
+struct Stretcher {
+    frame: usize,
+    hop: usize,
+    tolerance: usize,
+    window: Vec<f32>,
+    /// Buffered input, `input[0]` being input sample number `base`
+    input: Vec<f32>,
+    base: usize,
+    exhausted: bool,
+    /// Input position of the next frame before alignment
+    next_position: f64,
+    /// Input position of the last frame
+    previous: Option<usize>,
+    /// Windowed second half of the last frame, added to the next one
+    overlap: Vec<f32>,
+    output: VecDeque<f32>,
+}
+
+impl Stretcher {
+    fn new(sample_rate: u32) -> Self {
+        let hop = (sample_rate * FRAME_MS / 2000).max(1) as usize;
+        let frame = hop * 2;
+        // Periodic Hann: two windows half a frame apart add up to exactly 1
+        let window = (0..frame)
+            .map(|i| 0.5 - 0.5 * (2.0 * PI * i as f32 / frame as f32).cos())
+            .collect();
+        Self {
+            frame,
+            hop,
+            tolerance: hop / 2,
+            window,
+            input: Vec::new(),
+            base: 0,
+            exhausted: false,
+            next_position: 0.0,
+            previous: None,
+            overlap: vec![0.0; hop],
+            output: VecDeque::new(),
+        }
+    }
+
+    /// Read input until sample `end` (exclusive) is buffered or the input ends.
+    fn fill(&mut self, input: &mut impl Iterator<Item = f32>, end: usize) {
+        while !self.exhausted && self.base + self.input.len() < end {
+            match input.next() {
+                Some(sample) => self.input.push(sample),
+                None => self.exhausted = true,
+            }
+        }
+    }
+
+    fn sample(&self, position: usize) -> f32 {
+        position
+            .checked_sub(self.base)
+            .and_then(|i| self.input.get(i))
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    /// Input position near `ideal` whose start best continues the last frame.
+    fn align(&self, ideal: usize, speed: f32) -> usize {
+        let Some(previous) = self.previous else {
+            return ideal;
+        };
+        if speed == 1.0 {
+            return ideal;
+        }
+        let natural = previous + self.hop;
+        let end = self.base + self.input.len();
+        let low = ideal.saturating_sub(self.tolerance).max(self.base);
+        let high = (ideal + self.tolerance).min(end.saturating_sub(self.hop));
+        if low >= high || natural + self.hop > end {
+            return ideal;
+        }
+        // Every other sample and offset is plenty for speech, at a quarter of the cost
+        let score = |start: usize| -> f32 {
+            (0..self.hop)
+                .step_by(2)
+                .map(|i| self.sample(natural + i) * self.sample(start + i))
+                .sum()
+        };
+        (low..=high)
+            .step_by(2)
+            .max_by(|a, b| score(*a).total_cmp(&score(*b)))
+            .unwrap_or(ideal)
+    }
+
+    /// Add one hop of output, or return false once the input is used up.
+    fn process(&mut self, input: &mut impl Iterator<Item = f32>, speed: f32) -> bool {
+        let ideal = self.next_position.round() as usize;
+        self.fill(input, ideal + self.tolerance + self.frame);
+        if ideal >= self.base + self.input.len() {
+            // Play out the end of the last frame
+            self.output.extend(self.overlap.drain(..));
+            return false;
+        }
+
+        let start = self.align(ideal, speed);
+        for i in 0..self.hop {
+            self.output.push_back(self.overlap[i] + self.sample(start + i) * self.window[i]);
+        }
+        for i in 0..self.hop {
+            self.overlap[i] = self.sample(start + self.hop + i) * self.window[self.hop + i];
+        }
+        self.previous = Some(start);
+        self.next_position += self.hop as f64 * speed as f64;
+
+        // Drop input no later frame or alignment can reach
+        let keep_from = (start + self.hop).min((self.next_position as usize).saturating_sub(self.tolerance));
+        if keep_from > self.base {
+            let drop = (keep_from - self.base).min(self.input.len());
+            self.input.drain(..drop);
+            self.base += drop;
+        }
+        true
+    }
+}
+
+/// `input` played at `speed` as a whole, for audio that is not streamed.
+pub(super) fn stretch(input: &[f32], sample_rate: u32, speed: f32) -> Vec<f32> {
+    let mut stretcher = Stretcher::new(sample_rate);
+    let mut samples = input.iter().copied();
+    while stretcher.process(&mut samples, speed) {}
+    stretcher.output.into_iter().collect()
+}
+
+/// Upward zero crossings per second, the frequency of a pure tone.
+#[cfg(test)]
+pub(super) fn frequency(samples: &[f32], sample_rate: u32) -> f32 {
+    let crossings = samples.windows(2).filter(|pair| pair[0] < 0.0 && pair[1] >= 0.0).count();
+    crossings as f32 * sample_rate as f32 / samples.len() as f32
+}
+
+/// Source wrapper changing the speed of a mono source without changing its pitch.
+pub struct TimeStretch<S> {
+    inner: S,
+    stretcher: Stretcher,
+    speed: fn() -> f32,
+    finished: bool,
+}
+
+impl<S: Source<Item = f32>> TimeStretch<S> {
+    /// Wrap a mono source, playing it at the speed `speed` returns.
+    pub fn new(inner: S, speed: fn() -> f32) -> Self {
+        let stretcher = Stretcher::new(inner.sample_rate());
+        Self {
+            inner,
+            stretcher,
+            speed,
+            finished: false,
+        }
+    }
+}
+
+impl<S: Source<Item = f32>> Iterator for TimeStretch<S> {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        while self.stretcher.output.is_empty() && !self.finished {
+            self.finished = !self.stretcher.process(&mut self.inner, (self.speed)());
+        }
+        self.stretcher.output.pop_front()
+    }
+}
+
+impl<S: Source<Item = f32>> Source for TimeStretch<S> {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        1
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.inner.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_time_stretch() {
+        let sample_rate = 16000;
+        let tone: Vec<f32> = (0..sample_rate * 2)
+            .map(|i| (2.0 * PI * 200.0 * i as f32 / sample_rate as f32).sin() * 0.5)
+            .collect();
+        let hop = (sample_rate * FRAME_MS / 2000) as usize;
+
+        // At normal speed the audio comes out unchanged after the first hop
+        let same = stretch(&tone, sample_rate, 1.0);
+        assert!(same.len() >= tone.len());
+        assert!(tone.iter().zip(&same).skip(hop).all(|(a, b)| (a - b).abs() < 1e-4));
+
+        for speed in [0.5, 2.0, 3.0] {
+            let stretched = stretch(&tone, sample_rate, speed);
+            let expected = tone.len() as f32 / speed;
+            assert!((stretched.len() as f32 - expected).abs() < expected * 0.05, "length at {speed}×");
+            // Same pitch, ignoring the edges
+            let middle = &stretched[hop * 2..stretched.len() - hop * 2];
+            assert!((frequency(middle, sample_rate) - 200.0).abs() < 10.0, "pitch at {speed}×");
+        }
+    }
+}
@@ -0,0 +1,269 @@
+//! Visualization service: turns the audio being played into frequency bands.
+//!
+//! The position tracker hands each played chunk to a single long-lived service
+//! thread (a shared handle to the sample store plus a range, so nothing is
+//! copied under the playback lock). The thread runs the FFT with a cached plan and
+//! writes the result into a double buffer of atomics; the view reads the
+//! front buffer with [`current_bands`] without taking any lock.
+//!
+//! What is computed follows the [`VisualizerStyle`]: frequency bands for the
+//! bars, only the loudness for the level meter, and nothing at all when the
+//! visualization is off.
+
+use std::ops::Range;
+use std::sync::atomic::{AtomicU32, AtomicU8, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, OnceLock};
+use std::thread;
+
+use rustfft::{num_complex::Complex, Fft, FftPlanner};
+use tracing::{debug, warn};
+
+use super::sample_store::SampleStore;
+use crate::logging::trace_throttled;
+
+/// Number of frequency bands produced for the waveform bars.
+pub const NUM_BANDS: usize = 10;
+
+/// Chunks shorter than this are too short to analyze.
+const MIN_CHUNK_LEN: usize = 128;
+
+/// How the audio being played is shown on the main bar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VisualizerStyle {
+    /// Frequency bands as bars, low frequencies on the left
+    Bars,
+    /// Frequency bands mirrored around the middle, low frequencies in the center
+    Mirrored,
+    /// One meter of the loudness, published as the first band
+    Level,
+    /// Nothing shown and no audio analyzed
+    Off,
+}
+
+/// Current [`VisualizerStyle`], as its index.
+static STYLE: AtomicU8 = AtomicU8::new(0);
+
+/// Choose what the visualization service computes for the next chunks.
+pub fn set_visualizer_style(style: VisualizerStyle) {
+    STYLE.store(style as u8, Ordering::Relaxed);
+    clear_bands();
+}
+
+fn visualizer_style() -> VisualizerStyle {
+    match STYLE.load(Ordering::Relaxed) {
+        1 => VisualizerStyle::Mirrored,
+        2 => VisualizerStyle::Level,
+        3 => VisualizerStyle::Off,
+        _ => VisualizerStyle::Bars,
+    }
+}
+
+/// A chunk of played audio to analyze.
+struct Job {
+    samples: SampleStore,
+    range: Range<usize>,
+}
+
+/// Two band buffers; writers fill the back one, then make it the front one.
+struct BandBuffer {
+    buffers: [[AtomicU32; NUM_BANDS]; 2],
+    front: AtomicUsize,
+}
+
+static BANDS: BandBuffer = BandBuffer {
+    buffers: [
+        [const { AtomicU32::new(0) }; NUM_BANDS],
+        [const { AtomicU32::new(0) }; NUM_BANDS],
+    ],
+    front: AtomicUsize::new(0),
+};
+
+impl BandBuffer {
+    fn publish(&self, bands: &[f32]) {
+        let back = 1 - self.front.load(Ordering::Acquire);
+        for (slot, value) in self.buffers[back].iter().zip(bands.iter().chain(std::iter::repeat(&0.0))) {
+            slot.store(value.to_bits(), Ordering::Relaxed);
+        }
+        self.front.store(back, Ordering::Release);
+    }
+
+    fn snapshot(&self) -> [f32; NUM_BANDS] {
+        let front = self.front.load(Ordering::Acquire);
+        std::array::from_fn(|i| f32::from_bits(self.buffers[front][i].load(Ordering::Relaxed)))
+    }
+}
+
+/// Latest frequency bands (0.0 to 1.0) of the audio being played.
+pub fn current_bands() -> [f32; NUM_BANDS] {
+    let bands = BANDS.snapshot();
+    trace_throttled!(?bands, "Frequency bands read");
+    bands
+}
+
+/// Reset the bands to silence (playback ended or stopped).
+pub fn clear_bands() {
+    BANDS.publish(&[0.0; NUM_BANDS]);
+}
+
+/// Queue a chunk of `samples` for analysis on the service thread, unless the
+/// visualization is off.
+pub fn analyze(samples: SampleStore, range: Range<usize>) {
+    if visualizer_style() == VisualizerStyle::Off {
+        return;
+    }
+    static SERVICE: OnceLock<Sender<Job>> = OnceLock::new();
+    if SERVICE.get_or_init(spawn_service).send(Job { samples, range }).is_err() {
+        warn!("Visualization thread is gone, bands will not update");
+    }
+}
+
+fn spawn_service() -> Sender<Job> {
+    let (sender, receiver) = mpsc::channel::<Job>();
+    thread::Builder::new()
+        .name("visualizer".into())
+        .spawn(move || {
+            debug!("Visualization thread started");
+            let mut analyzer = Analyzer::default();
+            while let Ok(mut job) = receiver.recv() {
+                // Only the newest chunk matters if the thread fell behind
+                while let Ok(newer) = receiver.try_recv() {
+                    job = newer;
+                }
+                let chunk = job.samples.slice(job.range);
+                match visualizer_style() {
+                    VisualizerStyle::Bars | VisualizerStyle::Mirrored => {
+                        BANDS.publish(&analyzer.frequency_bands(&chunk, NUM_BANDS));
+                    }
+                    VisualizerStyle::Level => BANDS.publish(&[level(&chunk)]),
+                    // Turned off after the chunk was queued
+                    VisualizerStyle::Off => {}
+                }
+            }
+        })
+        .expect("failed to spawn visualization thread");
+    sender
+}
+
+/// Loudness (0.0 to 1.0) of an audio chunk, from its RMS, scaled so that
+/// normal speech fills most of the meter.
+fn level(chunk: &[f32]) -> f32 {
+    if chunk.len() < MIN_CHUNK_LEN {
+        return 0.0;
+    }
+    let rms = (chunk.iter().map(|&x| x * x).sum::<f32>() / chunk.len() as f32).sqrt();
+    (rms * 4.0).min(1.0).powf(0.7)
+}
+
+/// FFT state reused across chunks.
+struct Analyzer {
+    planner: FftPlanner<f32>,
+    plan: Option<Arc<dyn Fft<f32>>>,
+    buffer: Vec<Complex<f32>>,
+}
+
+impl Default for Analyzer {
+    fn default() -> Self {
+        Self {
+            planner: FftPlanner::new(),
+            plan: None,
+            buffer: Vec::new(),
+        }
+    }
+}
+
+impl Analyzer {
+    /// Compute frequency band amplitudes (0.0 to 1.0) of an audio chunk.
+    fn frequency_bands(&mut self, chunk: &[f32], num_bands: usize) -> Vec<f32> {
+        if chunk.len() < MIN_CHUNK_LEN {
+            return vec![0.0; num_bands];
+        }
+
+        // Apply Hanning window
+        let n = chunk.len();
+        self.buffer.clear();
+        self.buffer.extend(chunk.iter().enumerate().map(|(i, &sample)| {
+            let window = 0.5 * (1.0 - (2.0 * std::f32::consts::PI * i as f32 / n as f32).cos());
+            Complex::new(sample * window, 0.0)
+        }));
+
+        // Perform FFT (the plan is only rebuilt when the chunk size changes)
+        let fft = match self.plan {
+            Some(ref plan) if plan.len() == n => Arc::clone(plan),
+            _ => {
+                let plan = self.planner.plan_fft_forward(n);
+                self.plan = Some(Arc::clone(&plan));
+                plan
+            }
+        };
+        fft.process(&mut self.buffer);
+
+        // Get magnitude of positive frequencies only
+        let half_n = n / 2;
+        let magnitudes: Vec<f32> = self.buffer[..half_n].iter().map(|c| c.norm()).collect();
+
+        if magnitudes.len() < num_bands {
+            return vec![0.0; num_bands];
+        }
+
+        // Split into logarithmic frequency bands
+        let mut bands = Vec::with_capacity(num_bands);
+        let log_max = (magnitudes.len() as f32).log10();
+
+        for i in 0..num_bands {
+            let start = (10f32.powf(log_max * i as f32 / num_bands as f32)) as usize;
+            let end = (10f32.powf(log_max * (i + 1) as f32 / num_bands as f32)) as usize;
+            let end = end.min(magnitudes.len());
+
+            if end > start {
+                // Use RMS for better energy representation
+                let sum_sq: f32 = magnitudes[start..end].iter().map(|&x| x * x).sum();
+                let rms = (sum_sq / (end - start) as f32).sqrt();
+                bands.push(rms);
+            } else {
+                bands.push(0.0);
+            }
+        }
+
+        // Normalize and apply power curve
+        let max_val = bands.iter().cloned().fold(0.0f32, f32::max);
+        if max_val > 0.0 {
+            for band in &mut bands {
+                *band = (*band / max_val).powf(0.7);
+            }
+        }
+
+        bands
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_band_buffer_flips_front() {
+        let buffer = BandBuffer {
+            buffers: [
+                [const { AtomicU32::new(0) }; NUM_BANDS],
+                [const { AtomicU32::new(0) }; NUM_BANDS],
+            ],
+            front: AtomicUsize::new(0),
+        };
+        buffer.publish(&[0.5; NUM_BANDS]);
+        assert_eq!(buffer.front.load(Ordering::Relaxed), 1);
+        assert_eq!(buffer.snapshot(), [0.5; NUM_BANDS]);
+        buffer.publish(&[0.25]);
+        assert_eq!(buffer.snapshot()[0], 0.25);
+        assert_eq!(buffer.snapshot()[1], 0.0);
+    }
+
+    #[test]
+    fn test_level() {
+        assert_eq!(level(&[0.0; 512]), 0.0);
+        assert_eq!(level(&[0.5; 64]), 0.0);
+        assert_eq!(level(&[-0.5; 512]), 1.0);
+        let quiet = level(&[0.05; 512]);
+        assert!(quiet > 0.0 && quiet < level(&[0.1; 512]));
+    }
+}
@@ -0,0 +1,195 @@
+//! Windows system voice provider.
+//!
+//! Speaks with the voices installed with Windows (Settings → Time & language
+//! → Speech) through the Windows.Media.SpeechSynthesis API: offline, with
+//! nothing to download. The synthesized WAV stream is decoded and played
+//! with the shared [`AudioPlayer`], so pause, seeking and progress work as
+//! with the other providers. Like [`super::polly`], longer texts are streamed.
+
+use std::sync::Arc;
+
+use tracing::{debug, info, warn};
+use windows::core::HSTRING;
+use windows::Media::SpeechSynthesis::SpeechSynthesizer;
+use windows::Storage::Streams::DataReader;
+
+use super::audio_player::{wav_to_samples, AudioPlayer};
+use super::sample_store::StreamingSamples;
+use super::streaming;
+use super::{TTSError, TTSProvider};
+use crate::text::prosody::{self, PAUSE};
+use crate::text::sentences::sentences;
+use crate::voices::system::with_com;
+
+/// Sample rate of the played audio; voices are resampled to it.
+const SAMPLE_RATE: u32 = 22050;
+
+/// Windows system voice provider.
+pub struct WindowsTTSProvider {
+    /// Voice identifier, or None for the default voice
+    voice_id: Option<String>,
+    /// Shared audio playback engine
+    player: AudioPlayer,
+}
+
+impl WindowsTTSProvider {
+    /// Create a provider speaking with `voice_id` (or the default voice).
+    pub fn new(voice_id: Option<String>) -> Result<Self, TTSError> {
+        info!("Initializing Windows system voice provider");
+        debug!(voice_id = ?voice_id, "Using Windows voice");
+        Ok(Self {
+            voice_id,
+            player: AudioPlayer::new(SAMPLE_RATE)?,
+        })
+    }
+}
+
+/// Synthesize `text` with the voice `voice_id` as a WAV file in memory.
+fn synthesize_wav(voice_id: Option<&str>, text: &str) -> windows::core::Result<Vec<u8>> {
+    let synthesizer = SpeechSynthesizer::new()?;
+    if let Some(id) = voice_id {
+        let voice = SpeechSynthesizer::AllVoices()?
+            .into_iter()
+            .find(|voice| voice.Id().is_ok_and(|voice_id| voice_id.to_string() == id));
+        match voice {
+            Some(voice) => synthesizer.SetVoice(&voice)?,
+            None => warn!(voice_id = id, "Windows voice not installed, using the default voice"),
+        }
+    }
+    let stream = synthesizer.SynthesizeTextToStreamAsync(&HSTRING::from(text))?.get()?;
+    let size = stream.Size()? as u32;
+    let reader = DataReader::CreateDataReader(&stream.GetInputStreamAt(0)?)?;
+    reader.LoadAsync(size)?.get()?;
+    let mut wav = vec![0u8; size as usize];
+    reader.ReadBytes(&mut wav)?;
+    Ok(wav)
+}
+
+/// Synthesize `text` with the voice `voice_id` as samples at [`SAMPLE_RATE`].
+///
+/// Paced text is synthesized piece by piece with silence between the pieces.
+fn synthesize_text(voice_id: Option<&str>, text: &str) -> Result<Vec<f32>, TTSError> {
+    if text.contains(PAUSE) {
+        return prosody::synthesize_paced(text, SAMPLE_RATE, |piece| synthesize_text(voice_id, piece));
+    }
+    debug!(chars = text.len(), "Windows: synthesizing speech");
+    let wav = with_com(|| synthesize_wav(voice_id, text))
+        .map_err(|e| TTSError::ProcessError(format!("Windows speech synthesis failed: {e}")))?;
+    let (samples, sample_rate) = wav_to_samples(&wav)
+        .ok_or_else(|| TTSError::ProcessError("Invalid audio format from Windows speech synthesis".into()))?;
+    if samples.is_empty() {
+        return Err(TTSError::ProcessError("No audio data generated by the Windows voice".into()));
+    }
+    Ok(crate::system::resample(&samples, sample_rate, SAMPLE_RATE))
+}
+
+impl TTSProvider for WindowsTTSProvider {
+    fn speak(&mut self, text: &str) -> Result<(), TTSError> {
+        self.player.stop()?;
+
+        let parts: Vec<String> = sentences(text).into_iter().map(String::from).collect();
+        if parts.len() < 2 {
+            let samples = synthesize_text(self.voice_id.as_deref(), text)?;
+            return self.player.play_audio(samples);
+        }
+
+        // Play the first sentence while the rest is synthesized
+        info!(parts = parts.len(), "Windows: streaming reading");
+        let first = synthesize_text(self.voice_id.as_deref(), &parts[0])?;
+        let stream = Arc::new(StreamingSamples::new(first, SAMPLE_RATE));
+        self.player.play_stream(Arc::clone(&stream))?;
+        let voice_id = self.voice_id.clone();
+        streaming::synthesize_rest(stream, parts, SAMPLE_RATE, move |part| synthesize_text(voice_id.as_deref(), part));
+        Ok(())
+    }
+
+    fn synthesize(&mut self, text: &str) -> Result<Vec<f32>, TTSError> {
+        let samples = synthesize_text(self.voice_id.as_deref(), text)?;
+        info!(
+            duration_sec = format!("{:.1}", samples.len() as f32 / SAMPLE_RATE as f32),
+            "Windows: audio synthesized"
+        );
+        Ok(samples)
+    }
+
+    fn play_samples(&mut self, samples: Vec<f32>) -> Result<(), TTSError> {
+        self.player.stop()?;
+        self.player.play_audio(samples)
+    }
+
+    fn hold_next_playback(&mut self) {
+        self.player.hold_next_playback();
+    }
+
+    fn pause(&mut self) -> Result<(), TTSError> {
+        self.player.pause()
+    }
+
+    fn resume(&mut self) -> Result<(), TTSError> {
+        self.player.resume()
+    }
+
+    fn stop(&mut self) -> Result<(), TTSError> {
+        self.player.stop()
+    }
+
+    fn replay(&mut self) -> Result<(), TTSError> {
+        self.player.replay()
+    }
+
+    fn is_playing(&self) -> bool {
+        self.player.is_playing()
+    }
+
+    fn is_paused(&self) -> bool {
+        self.player.is_paused()
+    }
+
+    fn skip_forward(&mut self, seconds: f32) {
+        self.player.skip_forward(seconds);
+    }
+
+    fn skip_backward(&mut self, seconds: f32) {
+        self.player.skip_backward(seconds);
+    }
+
+    fn get_progress(&self) -> f32 {
+        self.player.get_progress()
+    }
+
+    fn duration(&self) -> std::time::Duration {
+        self.player.duration()
+    }
+
+    fn position(&self) -> std::time::Duration {
+        self.player.position()
+    }
+
+    fn set_volume(&mut self, volume: f32) {
+        self.player.set_volume(volume);
+    }
+
+    fn set_speed(&mut self, speed: f32) {
+        self.player.set_speed(speed);
+    }
+
+    fn seek(&mut self, progress: f32) {
+        self.player.seek_to_progress(progress);
+    }
+
+    fn skip_sentences(&mut self, offset: i32) -> bool {
+        self.player.skip_sentences(offset)
+    }
+
+    fn sentence_starts(&self) -> Vec<std::time::Duration> {
+        self.player.sentence_starts()
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.player.sample_rate()
+    }
+
+    fn synthesized_audio(&self) -> (Vec<f32>, u32) {
+        (self.player.audio_data(), self.player.sample_rate())
+    }
+}
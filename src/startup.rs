@@ -0,0 +1,60 @@
+//! Startup phase timings, logged when started with `--profile-startup`.
+//!
+//! Each [`mark`] logs how long the phase that just ended took and the time
+//! since launch; [`finish`] logs the total once the main window is up.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+
+use tracing::info;
+
+/// Command-line flag that turns on startup profiling.
+pub const PROFILE_FLAG: &str = "--profile-startup";
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static LAUNCHED: OnceLock<Instant> = OnceLock::new();
+static LAST_MARK: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// Record the launch time and turn profiling on if the flag was passed.
+pub fn begin(args: &[String]) {
+    LAUNCHED.get_or_init(Instant::now);
+    ENABLED.store(args.iter().any(|arg| arg == PROFILE_FLAG), Ordering::Relaxed);
+}
+
+/// Whether `--profile-startup` was passed.
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Log the duration of the phase that just ended.
+pub fn mark(phase: &str) {
+    if !is_enabled() {
+        return;
+    }
+    let now = Instant::now();
+    let launched = *LAUNCHED.get_or_init(Instant::now);
+    let previous = LAST_MARK.lock().unwrap().replace(now).unwrap_or(launched);
+    info!(
+        target: "startup",
+        phase,
+        phase_ms = now.duration_since(previous).as_millis() as u64,
+        since_launch_ms = now.duration_since(launched).as_millis() as u64,
+        "Startup phase"
+    );
+}
+
+/// Log the total startup time and stop profiling.
+pub fn finish() {
+    if !is_enabled() {
+        return;
+    }
+    mark("main window opened");
+    let launched = *LAUNCHED.get_or_init(Instant::now);
+    info!(
+        target: "startup",
+        total_ms = launched.elapsed().as_millis() as u64,
+        "Startup complete"
+    );
+    ENABLED.store(false, Ordering::Relaxed);
+}
@@ -0,0 +1,183 @@
+//! Listening statistics and the weekly summary.
+//!
+//! Time spent listening and the number of readings heard are added up per
+//! local day in `stats.json` in the app data directory. Only totals are kept,
+//! never the texts, and days older than a year are dropped. The weekly
+//! summary window reads the last seven days once a week, on the day and hour
+//! chosen under History & Sync.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use chrono::{Datelike, Duration, Local, NaiveDate, NaiveDateTime, Weekday};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+/// Days of statistics kept.
+const MAX_DAYS: i64 = 366;
+
+/// Format of the day keys, which sort by date.
+const DAY_FORMAT: &str = "%Y-%m-%d";
+
+fn day_key(date: NaiveDate) -> String {
+    date.format(DAY_FORMAT).to_string()
+}
+
+/// Listening on one day.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DayStats {
+    /// Seconds of speech played
+    pub seconds: f64,
+    /// Readings listened to, even partly
+    pub items: u32,
+}
+
+/// Location of the statistics file.
+pub fn stats_path() -> PathBuf {
+    crate::sync::app_data_dir().join("stats.json")
+}
+
+/// Load the statistics, keyed by local date ("2024-05-31").
+fn load_stats() -> BTreeMap<String, DayStats> {
+    let path = stats_path();
+    let Ok(content) = std::fs::read_to_string(&path) else {
+        return BTreeMap::new();
+    };
+    serde_json::from_str(&content).unwrap_or_else(|e| {
+        warn!(error = %e, path = %path.display(), "Failed to parse listening statistics, starting fresh");
+        BTreeMap::new()
+    })
+}
+
+fn save_stats(stats: &BTreeMap<String, DayStats>) {
+    let path = stats_path();
+    let result = std::fs::create_dir_all(crate::sync::app_data_dir())
+        .map_err(|e| e.to_string())
+        .and_then(|_| serde_json::to_string_pretty(stats).map_err(|e| e.to_string()))
+        .and_then(|json| std::fs::write(&path, json).map_err(|e| e.to_string()));
+    if let Err(e) = result {
+        warn!(error = %e, "Failed to save listening statistics");
+    }
+}
+
+/// Count a reading that was listened to for `seconds`.
+pub fn record_listening(seconds: f32) {
+    let today = Local::now().date_naive();
+    let mut stats = load_stats();
+    let day = stats.entry(day_key(today)).or_default();
+    day.seconds += f64::from(seconds);
+    day.items += 1;
+    let oldest = day_key(today - Duration::days(MAX_DAYS - 1));
+    stats.retain(|date, _| *date >= oldest);
+    debug!(seconds, "Recorded listening time");
+    save_stats(&stats);
+}
+
+/// Listening over a span of days.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Summary {
+    pub seconds: f64,
+    pub items: u32,
+}
+
+impl Summary {
+    /// "You listened to 3.2 hours across 41 items"
+    pub fn describe(&self) -> String {
+        let time = if self.seconds >= 3600.0 {
+            format!("{:.1} hours", self.seconds / 3600.0)
+        } else {
+            match (self.seconds / 60.0).round() as u64 {
+                1 => "1 minute".to_string(),
+                minutes => format!("{minutes} minutes"),
+            }
+        };
+        let items = match self.items {
+            1 => "1 item".to_string(),
+            items => format!("{items} items"),
+        };
+        format!("You listened to {time} across {items}")
+    }
+}
+
+/// Listening over the seven days ending with `today`.
+fn week_summary(stats: &BTreeMap<String, DayStats>, today: NaiveDate) -> Summary {
+    stats
+        .range(day_key(today - Duration::days(6))..=day_key(today))
+        .fold(Summary::default(), |summary, (_, day)| Summary {
+            seconds: summary.seconds + day.seconds,
+            items: summary.items + day.items,
+        })
+}
+
+/// Listening over the last seven days.
+pub fn last_week() -> Summary {
+    week_summary(&load_stats(), Local::now().date_naive())
+}
+
+/// Latest time at or before `now` that falls on `day` at `hour` o'clock.
+fn last_scheduled(now: NaiveDateTime, day: Weekday, hour: u32) -> NaiveDateTime {
+    let days_back = (7 + now.weekday().num_days_from_monday() - day.num_days_from_monday()) % 7;
+    let scheduled = (now.date() - Duration::days(i64::from(days_back)))
+        .and_hms_opt(hour.min(23), 0, 0)
+        .unwrap_or(now);
+    if scheduled > now {
+        scheduled - Duration::days(7)
+    } else {
+        scheduled
+    }
+}
+
+/// Whether the weekly summary is due at `now` (local time), given when it was
+/// last shown.
+pub fn summary_due(now: NaiveDateTime, day: Weekday, hour: u32, last_shown: Option<NaiveDateTime>) -> bool {
+    last_shown.is_none_or(|shown| shown < last_scheduled(now, day, hour))
+}
+
+fn last_summary_path() -> PathBuf {
+    crate::sync::app_data_dir().join("last-weekly-summary")
+}
+
+/// When the weekly summary was last shown (local time).
+///
+/// Kept next to the statistics like the last cleanup time, out of the config.
+pub fn last_summary_shown() -> Option<NaiveDateTime> {
+    let content = std::fs::read_to_string(last_summary_path()).ok()?;
+    NaiveDateTime::parse_from_str(content.trim(), "%Y-%m-%dT%H:%M:%S").ok()
+}
+
+pub fn mark_summary_shown(now: NaiveDateTime) {
+    let result = std::fs::create_dir_all(crate::sync::app_data_dir())
+        .and_then(|_| std::fs::write(last_summary_path(), now.format("%Y-%m-%dT%H:%M:%S").to_string()));
+    if let Err(e) = result {
+        warn!(error = %e, "Failed to record weekly summary time");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_week_summary_and_schedule() {
+        let date = |d| NaiveDate::from_ymd_opt(2024, 6, d).unwrap();
+        let stats = BTreeMap::from([
+            (day_key(date(1)), DayStats { seconds: 5000.0, items: 3 }),
+            (day_key(date(3)), DayStats { seconds: 3600.0, items: 30 }),
+            (day_key(date(9)), DayStats { seconds: 2920.0, items: 8 }),
+        ]);
+        let summary = week_summary(&stats, date(9));
+        assert_eq!(summary, Summary { seconds: 6520.0, items: 38 });
+        assert_eq!(summary.describe(), "You listened to 1.8 hours across 38 items");
+        assert_eq!(Summary { seconds: 50.0, items: 1 }.describe(), "You listened to 1 minute across 1 item");
+
+        // Sunday 9 June 2024
+        let at = |d, h| date(d).and_hms_opt(h, 0, 0).unwrap();
+        assert_eq!(last_scheduled(at(9, 20), Weekday::Sun, 18), at(9, 18));
+        assert_eq!(last_scheduled(at(9, 17), Weekday::Sun, 18), at(2, 18));
+        assert_eq!(last_scheduled(at(11, 8), Weekday::Tue, 9), at(4, 9));
+        assert!(summary_due(at(9, 20), Weekday::Sun, 18, Some(at(2, 19))));
+        assert!(!summary_due(at(9, 20), Weekday::Sun, 18, Some(at(9, 19))));
+        assert!(!summary_due(at(12, 8), Weekday::Sun, 18, Some(at(9, 19))));
+        assert!(summary_due(at(12, 8), Weekday::Sun, 18, None));
+    }
+}
@@ -0,0 +1,293 @@
+//! Disk usage and cleanup of files the app leaves behind.
+//!
+//! Four kinds of files grow over time: temporary synthesis audio from
+//! sessions that did not exit cleanly, temporary screenshots taken for OCR,
+//! rotated log files and the reading history. The cleanup job prunes each of
+//! them by age and runs on startup and while idle, daily or weekly; the
+//! Storage settings section shows their size and purges them on demand. The
+//! synthesis cache counts as audio cache too, but keeps to its size limit
+//! itself rather than being pruned by age.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use tracing::{debug, info, warn};
+
+/// Log files older than this are removed by the cleanup job.
+const LOG_MAX_AGE: Duration = Duration::from_secs(14 * 24 * 60 * 60);
+
+/// Temporary synthesis audio older than this is removed by the cleanup job.
+///
+/// Files of a running instance are touched while it plays, so they stay younger.
+const AUDIO_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Temporary screenshots older than this are removed by the cleanup job.
+const SCREENSHOT_MAX_AGE: Duration = Duration::from_secs(60 * 60);
+
+/// A kind of file the app stores.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageCategory {
+    AudioCache,
+    Screenshots,
+    Logs,
+    History,
+}
+
+impl StorageCategory {
+    pub const ALL: [StorageCategory; 4] = [
+        StorageCategory::AudioCache,
+        StorageCategory::Screenshots,
+        StorageCategory::Logs,
+        StorageCategory::History,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            StorageCategory::AudioCache => "Audio cache",
+            StorageCategory::Screenshots => "Screenshots",
+            StorageCategory::Logs => "Logs",
+            StorageCategory::History => "History",
+        }
+    }
+}
+
+/// How often the cleanup job runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CleanupSchedule {
+    Off,
+    Daily,
+    Weekly,
+}
+
+impl CleanupSchedule {
+    fn interval(self) -> Option<Duration> {
+        match self {
+            CleanupSchedule::Off => None,
+            CleanupSchedule::Daily => Some(Duration::from_secs(24 * 60 * 60)),
+            CleanupSchedule::Weekly => Some(Duration::from_secs(7 * 24 * 60 * 60)),
+        }
+    }
+}
+
+/// Whether the cleanup job should run, given when it last ran (seconds since the Unix epoch).
+pub fn cleanup_due(schedule: CleanupSchedule, last_run: Option<i64>, now: i64) -> bool {
+    let Some(interval) = schedule.interval() else {
+        return false;
+    };
+    last_run.is_none_or(|last_run| now - last_run >= interval.as_secs() as i64)
+}
+
+fn last_cleanup_path() -> PathBuf {
+    crate::sync::app_data_dir().join("last-cleanup")
+}
+
+/// When the cleanup job last ran (seconds since the Unix epoch).
+///
+/// Kept next to the history rather than in the config so staged settings
+/// and folder sync do not carry it around.
+pub fn last_cleanup() -> Option<i64> {
+    std::fs::read_to_string(last_cleanup_path()).ok()?.trim().parse().ok()
+}
+
+fn mark_cleanup_done() {
+    let path = last_cleanup_path();
+    let result = std::fs::create_dir_all(crate::sync::app_data_dir())
+        .and_then(|_| std::fs::write(&path, chrono::Utc::now().timestamp().to_string()));
+    if let Err(e) = result {
+        warn!(error = %e, "Failed to record cleanup time");
+    }
+}
+
+/// Temporary synthesis audio of other (finished or crashed) processes, and
+/// the phrases synthesized ahead of time (synthesized again while idle).
+fn audio_cache_files() -> Vec<PathBuf> {
+    let own = format!("insight-reader-{}-", std::process::id());
+    let own_piper = format!("insight-reader-piper-{}-", std::process::id());
+    let mut files = temp_entries(|name| {
+        let synthesis = (name.starts_with("insight-reader-") && name.ends_with(".pcm"))
+            || name.starts_with("insight-reader-piper-");
+        synthesis && !name.starts_with(&own) && !name.starts_with(&own_piper)
+    });
+    if let Ok(entries) = std::fs::read_dir(crate::phrase_cache::cache_dir()) {
+        files.extend(entries.flatten().map(|entry| entry.path()));
+    }
+    files
+}
+
+fn screenshot_files() -> Vec<PathBuf> {
+    temp_entries(|name| name.starts_with("insight-reader-screenshot"))
+}
+
+/// Log files, oldest first. The newest one is still being written.
+fn log_files() -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(crate::logging::default_log_dir()) else {
+        return Vec::new();
+    };
+    let mut files: Vec<(Option<SystemTime>, PathBuf)> = entries
+        .flatten()
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with("insight-reader.log"))
+        .map(|entry| (modified(&entry.path()), entry.path()))
+        .collect();
+    files.sort();
+    files.into_iter().map(|(_, path)| path).collect()
+}
+
+fn temp_entries(matches: impl Fn(&str) -> bool) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(std::env::temp_dir()) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter(|entry| matches(&entry.file_name().to_string_lossy()))
+        .map(|entry| entry.path())
+        .collect()
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+fn older_than(path: &Path, age: Duration) -> bool {
+    modified(path)
+        .and_then(|time| time.elapsed().ok())
+        .is_some_and(|elapsed| elapsed >= age)
+}
+
+/// Size of a file, or of everything in a directory.
+fn disk_size(path: &Path) -> u64 {
+    let Ok(meta) = std::fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !meta.is_dir() {
+        return meta.len();
+    }
+    std::fs::read_dir(path)
+        .map(|entries| entries.flatten().map(|entry| disk_size(&entry.path())).sum())
+        .unwrap_or(0)
+}
+
+/// Remove files or directories, returning the bytes freed.
+fn remove_all(paths: impl IntoIterator<Item = PathBuf>) -> u64 {
+    let mut freed = 0;
+    for path in paths {
+        let size = disk_size(&path);
+        let result = if path.is_dir() {
+            std::fs::remove_dir_all(&path)
+        } else {
+            std::fs::remove_file(&path)
+        };
+        match result {
+            Ok(()) => freed += size,
+            Err(e) => debug!(error = %e, path = %path.display(), "Failed to remove file"),
+        }
+    }
+    freed
+}
+
+/// Disk space used by each category, in bytes.
+pub fn usage() -> Vec<(StorageCategory, u64)> {
+    StorageCategory::ALL
+        .into_iter()
+        .map(|category| {
+            let paths = match category {
+                StorageCategory::AudioCache => {
+                    let mut paths = audio_cache_files();
+                    paths.push(crate::synthesis_cache::cache_dir());
+                    paths
+                }
+                StorageCategory::Screenshots => screenshot_files(),
+                StorageCategory::Logs => log_files(),
+                StorageCategory::History => vec![crate::history::history_path(), crate::phrase_cache::phrases_path()],
+            };
+            (category, paths.iter().map(|path| disk_size(path)).sum())
+        })
+        .collect()
+}
+
+/// Remove everything in a category, returning the bytes freed.
+///
+/// The log file being written and favorite history entries are kept.
+pub fn purge(category: StorageCategory) -> u64 {
+    let freed = match category {
+        StorageCategory::AudioCache => {
+            remove_all(audio_cache_files()) + remove_all([crate::synthesis_cache::cache_dir()])
+        }
+        StorageCategory::Screenshots => remove_all(screenshot_files()),
+        StorageCategory::Logs => {
+            let mut files = log_files();
+            files.pop();
+            remove_all(files)
+        }
+        StorageCategory::History => {
+            let before = disk_size(&crate::history::history_path()) + disk_size(&crate::phrase_cache::phrases_path());
+            crate::history::clear_history();
+            crate::phrase_cache::forget_phrases();
+            before.saturating_sub(disk_size(&crate::history::history_path()))
+        }
+    };
+    info!(category = category.label(), freed, "Storage purged");
+    freed
+}
+
+/// Prune every category by age, returning the bytes freed.
+///
+/// History entries older than `history_max_days` are dropped (0 keeps them all).
+pub fn run_cleanup(history_max_days: u32) -> u64 {
+    let mut freed = remove_all(
+        audio_cache_files()
+            .into_iter()
+            .filter(|path| older_than(path, AUDIO_MAX_AGE)),
+    );
+    freed += remove_all(
+        screenshot_files()
+            .into_iter()
+            .filter(|path| older_than(path, SCREENSHOT_MAX_AGE)),
+    );
+    let mut logs = log_files();
+    logs.pop();
+    freed += remove_all(logs.into_iter().filter(|path| older_than(path, LOG_MAX_AGE)));
+    if history_max_days > 0 {
+        let before = disk_size(&crate::history::history_path());
+        crate::history::prune_older_than(history_max_days);
+        freed += before.saturating_sub(disk_size(&crate::history::history_path()));
+    }
+    mark_cleanup_done();
+    info!(freed, "Storage cleanup finished");
+    freed
+}
+
+/// Human-readable size ("1.4 MB").
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.1} {}", UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cleanup_due() {
+        let day = 24 * 60 * 60;
+        assert!(cleanup_due(CleanupSchedule::Daily, None, 10 * day));
+        assert!(cleanup_due(CleanupSchedule::Daily, Some(9 * day), 10 * day));
+        assert!(!cleanup_due(CleanupSchedule::Weekly, Some(9 * day), 10 * day));
+        assert!(!cleanup_due(CleanupSchedule::Off, None, 10 * day));
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1536), "1.5 KB");
+        assert_eq!(format_size(3 * 1024 * 1024), "3.0 MB");
+    }
+}
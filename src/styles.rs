@@ -1,7 +1,89 @@
 //! Custom style functions for UI components (Iced 0.13+ closure-based API)
 
-use iced::widget::{button, checkbox, container, radio};
-use iced::{Background, Border, Color, Theme};
+use std::sync::RwLock;
+
+use iced::widget::{button, checkbox, container, progress_bar, radio, text_editor};
+use iced::{Background, Border, Color, Font, Theme};
+
+use crate::model::{ColorPreset, ColorRole, CustomColors, ReadingFont, ReadingTint};
+
+/// User-customizable colors (Settings > Appearance), read by the style functions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColorScheme {
+    /// Checked checkboxes and selected radio buttons
+    pub accent: Color,
+    /// Filled part of the playback progress bar
+    pub progress: Color,
+    /// Waveform bars
+    pub waveform: Color,
+    /// Thickness of the progress bar in pixels
+    pub progress_height: f32,
+}
+
+impl ColorScheme {
+    pub const DEFAULT: Self = Self {
+        accent: Color::from_rgb(0.4, 0.6, 1.0),
+        progress: Color::WHITE,
+        waveform: Color::from_rgba(1.0, 1.0, 1.0, 0.6),
+        progress_height: 1.0,
+    };
+
+    const HIGH_CONTRAST: Self = Self {
+        accent: Color::from_rgb(1.0, 0.85, 0.0),
+        progress: Color::from_rgb(1.0, 0.85, 0.0),
+        waveform: Color::WHITE,
+        progress_height: 4.0,
+    };
+
+    const WARM: Self = Self {
+        accent: Color::from_rgb(1.0, 0.6, 0.2),
+        progress: Color::from_rgb(1.0, 0.6, 0.2),
+        waveform: Color::from_rgba(1.0, 0.8, 0.55, 0.8),
+        progress_height: 2.0,
+    };
+
+    /// Colors of a preset; custom colors that are not valid hex fall back to the default.
+    pub fn resolve(preset: ColorPreset, custom: &CustomColors) -> Self {
+        match preset {
+            ColorPreset::Default => Self::DEFAULT,
+            ColorPreset::HighContrast => Self::HIGH_CONTRAST,
+            ColorPreset::Warm => Self::WARM,
+            ColorPreset::Custom => {
+                let color = |role, default| parse_hex_color(custom.get(role)).unwrap_or(default);
+                Self {
+                    accent: color(ColorRole::Accent, Self::DEFAULT.accent),
+                    progress: color(ColorRole::Progress, Self::DEFAULT.progress),
+                    waveform: color(ColorRole::Waveform, Self::DEFAULT.waveform),
+                    progress_height: 3.0,
+                }
+            }
+        }
+    }
+}
+
+static COLOR_SCHEME: RwLock<ColorScheme> = RwLock::new(ColorScheme::DEFAULT);
+
+/// Make the style functions use the given colors.
+pub fn set_color_scheme(scheme: ColorScheme) {
+    if let Ok(mut current) = COLOR_SCHEME.write() {
+        *current = scheme;
+    }
+}
+
+/// Colors currently used by the style functions.
+pub fn color_scheme() -> ColorScheme {
+    COLOR_SCHEME.read().map(|scheme| *scheme).unwrap_or(ColorScheme::DEFAULT)
+}
+
+/// Parse a `#rrggbb` (or `#rrggbbaa`) color; the `#` is optional.
+pub fn parse_hex_color(hex: &str) -> Option<Color> {
+    let hex = hex.trim();
+    let digits = hex.strip_prefix('#').unwrap_or(hex);
+    if !digits.is_ascii() || !matches!(digits.len(), 6 | 8) {
+        return None;
+    }
+    digits.parse().ok()
+}
 
 pub fn window_style(_theme: &Theme) -> container::Style {
     container::Style {
@@ -17,7 +99,7 @@ pub fn window_style(_theme: &Theme) -> container::Style {
 
 pub fn wave_bar_style(_theme: &Theme) -> container::Style {
     container::Style {
-        background: Some(Background::Color(Color::from_rgba(1.0, 1.0, 1.0, 0.6))),
+        background: Some(Background::Color(color_scheme().waveform)),
         border: Border {
             radius: 1.5.into(),
             ..Default::default()
@@ -57,6 +139,19 @@ pub fn modal_content_style(_theme: &Theme) -> container::Style {
     }
 }
 
+/// Style for hover tooltips on the main bar
+pub fn tooltip_style(_theme: &Theme) -> container::Style {
+    container::Style {
+        background: Some(Background::Color(Color::from_rgba(0.12, 0.12, 0.14, 0.95))),
+        border: Border {
+            color: Color::from_rgba(1.0, 1.0, 1.0, 0.2),
+            width: 1.0,
+            radius: 6.0.into(),
+        },
+        ..Default::default()
+    }
+}
+
 /// Style for section containers (grouped settings)
 pub fn section_style(_theme: &Theme) -> container::Style {
     container::Style {
@@ -116,6 +211,38 @@ pub fn header_style(_theme: &Theme) -> container::Style {
     }
 }
 
+/// Style for the settings tab buttons.
+pub fn tab_button_style(_theme: &Theme, status: button::Status) -> button::Style {
+    let background = match status {
+        button::Status::Hovered => Color::from_rgba(1.0, 1.0, 1.0, 0.08),
+        button::Status::Pressed => Color::from_rgba(1.0, 1.0, 1.0, 0.15),
+        _ => Color::TRANSPARENT,
+    };
+    button::Style {
+        background: Some(Background::Color(background)),
+        text_color: Color::from_rgba(1.0, 1.0, 1.0, 0.7),
+        border: Border {
+            radius: 6.0.into(),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+/// Style for the settings tab button of the page being shown.
+pub fn selected_tab_button_style(_theme: &Theme, _status: button::Status) -> button::Style {
+    button::Style {
+        background: Some(Background::Color(Color::from_rgba(1.0, 1.0, 1.0, 0.12))),
+        text_color: Color::WHITE,
+        border: Border {
+            color: color_scheme().accent,
+            width: 1.0,
+            radius: 6.0.into(),
+        },
+        ..Default::default()
+    }
+}
+
 /// Transparent button style for icon-only controls (e.g., settings gear).
 pub fn transparent_button_style(_theme: &Theme, _status: button::Status) -> button::Style {
     button::Style {
@@ -126,11 +253,30 @@ pub fn transparent_button_style(_theme: &Theme, _status: button::Status) -> butt
     }
 }
 
+/// Box drawn over a line of text found by OCR in the screenshot viewer.
+pub fn ocr_box_style(_theme: &Theme, status: button::Status) -> button::Style {
+    let accent = color_scheme().accent;
+    let fill = match status {
+        button::Status::Hovered | button::Status::Pressed => 0.3,
+        _ => 0.1,
+    };
+    button::Style {
+        background: Some(Background::Color(Color { a: fill, ..accent })),
+        text_color: Color::WHITE,
+        border: Border {
+            color: Color { a: 0.8, ..accent },
+            width: 1.0,
+            radius: 2.0.into(),
+        },
+        ..Default::default()
+    }
+}
+
 /// White text radio style for dark backgrounds.
 pub fn white_radio_style(_theme: &Theme, _status: radio::Status) -> radio::Style {
     radio::Style {
         background: Background::Color(Color::TRANSPARENT),
-        dot_color: Color::from_rgb(0.4, 0.6, 1.0),
+        dot_color: color_scheme().accent,
         border_width: 1.0,
         border_color: Color::from_rgba(1.0, 1.0, 1.0, 0.6),
         text_color: Some(Color::WHITE),
@@ -146,7 +292,7 @@ pub fn white_checkbox_style(_theme: &Theme, status: checkbox::Status) -> checkbo
     };
     checkbox::Style {
         background: Background::Color(if is_checked {
-            Color::from_rgb(0.4, 0.6, 1.0)
+            color_scheme().accent
         } else {
             Color::TRANSPARENT
         }),
@@ -159,3 +305,80 @@ pub fn white_checkbox_style(_theme: &Theme, status: checkbox::Status) -> checkbo
         text_color: Some(Color::WHITE),
     }
 }
+
+/// Playback progress bar in the progress color over a faint track.
+pub fn progress_bar_style(_theme: &Theme) -> progress_bar::Style {
+    progress_bar::Style {
+        background: Background::Color(Color::from_rgba(1.0, 1.0, 1.0, 0.15)),
+        bar: Background::Color(color_scheme().progress),
+        border: Border::default(),
+    }
+}
+
+/// Font for text shown for reading.
+///
+/// OpenDyslexic is looked up by name, so it works once it is installed on the
+/// system or placed in the app's fonts folder (see `ui::fonts`).
+pub fn reading_font(font: ReadingFont) -> Font {
+    match font {
+        ReadingFont::Default => Font::DEFAULT,
+        ReadingFont::OpenDyslexic => Font::with_name("OpenDyslexic"),
+        ReadingFont::Serif => Font {
+            family: iced::font::Family::Serif,
+            ..Font::DEFAULT
+        },
+        ReadingFont::Monospace => Font::MONOSPACE,
+    }
+}
+
+/// Background and text colors for a reading tint.
+pub fn reading_tint_colors(tint: ReadingTint) -> (Color, Color) {
+    let dark_text = Color::from_rgb(0.1, 0.1, 0.1);
+    match tint {
+        ReadingTint::None => (Color::from_rgb(0.08, 0.08, 0.10), Color::WHITE),
+        ReadingTint::Cream => (Color::from_rgb(0.98, 0.95, 0.85), dark_text),
+        ReadingTint::Blue => (Color::from_rgb(0.85, 0.91, 0.98), dark_text),
+        ReadingTint::Green => (Color::from_rgb(0.87, 0.96, 0.87), dark_text),
+        ReadingTint::Gray => (Color::from_rgb(0.88, 0.88, 0.88), dark_text),
+    }
+}
+
+/// Text editor style for text shown for reading, on the given tint.
+pub fn reading_editor_style(tint: ReadingTint) -> impl Fn(&Theme, text_editor::Status) -> text_editor::Style {
+    move |_theme, status| {
+        let (background, value) = reading_tint_colors(tint);
+        let border_alpha = match status {
+            text_editor::Status::Focused { .. } => 0.5,
+            _ => 0.15,
+        };
+        text_editor::Style {
+            background: Background::Color(background),
+            border: Border {
+                color: Color::from_rgba(0.5, 0.5, 0.5, border_alpha),
+                width: 1.0,
+                radius: 6.0.into(),
+            },
+            placeholder: Color { a: 0.5, ..value },
+            value,
+            selection: Color { a: 0.3, ..color_scheme().accent },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_custom_scheme_falls_back_on_invalid_hex() {
+        let custom = CustomColors {
+            accent: "#ff0000".to_string(),
+            progress: "not a color".to_string(),
+            waveform: "00ff00".to_string(),
+        };
+        let scheme = ColorScheme::resolve(ColorPreset::Custom, &custom);
+        assert_eq!(scheme.accent, Color::from_rgb(1.0, 0.0, 0.0));
+        assert_eq!(scheme.progress, ColorScheme::DEFAULT.progress);
+        assert_eq!(scheme.waveform, Color::from_rgb(0.0, 1.0, 0.0));
+    }
+}
@@ -0,0 +1,323 @@
+//! Optional sync of settings, history, document positions and lexicon through a shared folder.
+//!
+//! Point the sync folder at a Dropbox/Syncthing/network directory on every
+//! machine. Each machine only ever writes its own files
+//! (`<sync dir>/insight-reader/<machine id>/<kind>.json`), so the sync tool
+//! never has to resolve write conflicts. Every key carries the time it last
+//! changed; merging takes the newest value for each key across all machines
+//! (deletions are kept as tombstones so they propagate too).
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use tracing::{debug, info, warn};
+
+use crate::config;
+
+/// Folder created inside the user's sync directory.
+const SYNC_SUBDIR: &str = "insight-reader";
+
+/// Config keys that describe this machine rather than the user's setup.
+const LOCAL_CONFIG_KEYS: &[&str] = &[
+    "sync_dir",
+    "watch_dir",
+    "podcast_feed_dir",
+    "podcast_feed_base_url",
+    "window_geometry",
+    "execution_provider",
+    "synthesis_threads",
+    "elevenlabs_api_key",
+    "config_version",
+];
+
+/// A synced value and when it last changed (milliseconds since the Unix epoch).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Stamped {
+    #[serde(default)]
+    value: Value,
+    updated_at: i64,
+    #[serde(default)]
+    deleted: bool,
+}
+
+/// Everything one machine has published for one kind of data.
+type Published = BTreeMap<String, Stamped>;
+
+/// A local JSON object file taking part in sync.
+struct SyncedFile {
+    kind: &'static str,
+    path: PathBuf,
+    local_keys: &'static [&'static str],
+}
+
+/// App data directory (`~/.local/share/insight-reader`, `%LOCALAPPDATA%\insight-reader`).
+pub fn app_data_dir() -> PathBuf {
+    #[cfg(target_os = "windows")]
+    let base = dirs::data_local_dir().unwrap_or_else(|| PathBuf::from("C:\\Temp"));
+    #[cfg(not(target_os = "windows"))]
+    let base = dirs::data_dir().unwrap_or_else(|| PathBuf::from("/tmp"));
+    base.join("insight-reader")
+}
+
+fn synced_files() -> Vec<SyncedFile> {
+    let mut files = Vec::new();
+    if let Some(path) = config::config_file_path() {
+        files.push(SyncedFile {
+            kind: "config",
+            path,
+            local_keys: LOCAL_CONFIG_KEYS,
+        });
+    }
+    files.push(SyncedFile {
+        kind: "history",
+        path: crate::history::history_path(),
+        local_keys: &[],
+    });
+    files.push(SyncedFile {
+        kind: "positions",
+        path: crate::history::positions_path(),
+        local_keys: &[],
+    });
+    files.push(SyncedFile {
+        kind: "lexicon",
+        path: crate::lexicon::lexicon_path(),
+        local_keys: &[],
+    });
+    files
+}
+
+/// Stable identifier of this machine, created on first use.
+fn machine_id() -> String {
+    let path = app_data_dir().join("machine-id");
+    if let Ok(id) = std::fs::read_to_string(&path) {
+        let id = id.trim();
+        if !id.is_empty() {
+            return id.to_string();
+        }
+    }
+    let host = std::env::var("HOSTNAME")
+        .or_else(|_| std::env::var("COMPUTERNAME"))
+        .unwrap_or_else(|_| "machine".to_string());
+    let host: String = host
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-')
+        .collect();
+    let nonce = crate::text::content_hash(&format!("{host} {}", chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0)));
+    let id = format!("{host}-{}", &nonce[..8]);
+    if let Err(e) = std::fs::create_dir_all(app_data_dir()).and_then(|_| std::fs::write(&path, &id)) {
+        warn!(error = %e, "Failed to persist machine id");
+    }
+    id
+}
+
+fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> Option<T> {
+    let content = std::fs::read_to_string(path).ok()?;
+    match serde_json::from_str(&content) {
+        Ok(value) => Some(value),
+        Err(e) => {
+            warn!(error = %e, path = %path.display(), "Skipping unreadable sync file");
+            None
+        }
+    }
+}
+
+fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
+    }
+    let json = serde_json::to_string_pretty(value).map_err(|e| format!("Failed to serialize: {e}"))?;
+    // Write then rename so the sync tool never picks up a half-written file
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, json).map_err(|e| format!("Failed to write {}: {e}", tmp.display()))?;
+    std::fs::rename(&tmp, path).map_err(|e| format!("Failed to write {}: {e}", path.display()))
+}
+
+/// Record local changes into this machine's published state.
+///
+/// Returns true if anything changed.
+fn publish_local(local: &Map<String, Value>, published: &mut Published, local_keys: &[&str], now: i64) -> bool {
+    let mut changed = false;
+    for (key, value) in local {
+        if local_keys.contains(&key.as_str()) {
+            continue;
+        }
+        let current = published.get(key);
+        if current.is_none_or(|s| s.deleted || s.value != *value) {
+            published.insert(
+                key.clone(),
+                Stamped {
+                    value: value.clone(),
+                    updated_at: now,
+                    deleted: false,
+                },
+            );
+            changed = true;
+        }
+    }
+    for (key, stamped) in published.iter_mut() {
+        if !stamped.deleted && !local.contains_key(key) {
+            *stamped = Stamped {
+                value: Value::Null,
+                updated_at: now,
+                deleted: true,
+            };
+            changed = true;
+        }
+    }
+    changed
+}
+
+/// Merge other machines' state into ours (newest change per key wins) and
+/// apply the winners to the local object.
+///
+/// Returns (local changed, published changed).
+fn merge_remote(
+    local: &mut Map<String, Value>,
+    published: &mut Published,
+    remotes: &[Published],
+    local_keys: &[&str],
+) -> (bool, bool) {
+    let mut local_changed = false;
+    let mut published_changed = false;
+    for remote in remotes {
+        for (key, theirs) in remote {
+            if local_keys.contains(&key.as_str()) {
+                continue;
+            }
+            let newer = published
+                .get(key)
+                .is_none_or(|ours| theirs.updated_at > ours.updated_at);
+            if !newer {
+                continue;
+            }
+            if theirs.deleted {
+                local_changed |= local.remove(key).is_some();
+            } else if local.get(key) != Some(&theirs.value) {
+                local.insert(key.clone(), theirs.value.clone());
+                local_changed = true;
+            }
+            published.insert(key.clone(), theirs.clone());
+            published_changed = true;
+        }
+    }
+    (local_changed, published_changed)
+}
+
+/// Sync one file with the shared folder.
+fn sync_file(file: &SyncedFile, root: &Path, machine: &str, now: i64) -> Result<(), String> {
+    let own_path = root.join(machine).join(format!("{}.json", file.kind));
+    let mut published: Published = read_json(&own_path).unwrap_or_default();
+
+    let local_exists = file.path.is_file();
+    let mut local: Map<String, Value> = if local_exists {
+        match read_json::<Value>(&file.path) {
+            Some(Value::Object(map)) => map,
+            _ => return Err(format!("{} is not a JSON object, not syncing it", file.path.display())),
+        }
+    } else {
+        Map::new()
+    };
+
+    // A missing local file means "nothing known yet", not "everything deleted"
+    let mut published_changed = local_exists && publish_local(&local, &mut published, file.local_keys, now);
+
+    let remotes: Vec<Published> = std::fs::read_dir(root)
+        .map_err(|e| format!("Failed to read {}: {e}", root.display()))?
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_name().to_string_lossy() != machine)
+        .filter_map(|entry| read_json(&entry.path().join(format!("{}.json", file.kind))))
+        .collect();
+    let (local_changed, merged) = merge_remote(&mut local, &mut published, &remotes, file.local_keys);
+    published_changed |= merged;
+
+    if local_changed {
+        info!(kind = file.kind, "Applying changes from sync folder");
+        write_json(&file.path, &Value::Object(local))?;
+    }
+    if published_changed || !own_path.exists() {
+        write_json(&own_path, &published)?;
+    }
+    Ok(())
+}
+
+/// Sync config, history and lexicon with the configured sync folder.
+///
+/// Does nothing when sync is off. Files that fail to sync are skipped and
+/// reported together in the error.
+pub fn sync_now() -> Result<(), String> {
+    let Some(dir) = config::load_sync_dir() else {
+        return Ok(());
+    };
+    if !dir.is_dir() {
+        return Err(format!("Sync folder {} does not exist", dir.display()));
+    }
+    let root = dir.join(SYNC_SUBDIR);
+    let machine = machine_id();
+    let now = chrono::Utc::now().timestamp_millis();
+    debug!(root = %root.display(), machine = %machine, "Syncing");
+
+    let errors: Vec<String> = synced_files()
+        .iter()
+        .filter_map(|file| {
+            sync_file(file, &root, &machine, now)
+                .err()
+                .map(|e| format!("{}: {e}", file.kind))
+        })
+        .collect();
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.join("; "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn object(value: Value) -> Map<String, Value> {
+        match value {
+            Value::Object(map) => map,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_newest_change_wins_per_key() {
+        let mut laptop = object(json!({"voice": "amy", "ocr": "default", "sync_dir": "/a"}));
+        let mut laptop_pub = Published::new();
+        publish_local(&laptop, &mut laptop_pub, LOCAL_CONFIG_KEYS, 100);
+        assert!(!laptop_pub.contains_key("sync_dir"));
+
+        // Desktop changed the voice later and removed the OCR setting
+        let mut desktop_pub = laptop_pub.clone();
+        let desktop = object(json!({"voice": "ryan"}));
+        publish_local(&desktop, &mut desktop_pub, LOCAL_CONFIG_KEYS, 200);
+
+        // Laptop then changed the OCR setting again, after the desktop removed it
+        laptop.insert("ocr".into(), json!("better"));
+        publish_local(&laptop, &mut laptop_pub, LOCAL_CONFIG_KEYS, 300);
+
+        let (local_changed, _) = merge_remote(&mut laptop, &mut laptop_pub, &[desktop_pub], LOCAL_CONFIG_KEYS);
+        assert!(local_changed);
+        assert_eq!(laptop, object(json!({"voice": "ryan", "ocr": "better", "sync_dir": "/a"})));
+    }
+
+    #[test]
+    fn test_remote_deletion_propagates() {
+        let mut local = object(json!({"a": 1, "b": 2}));
+        let mut ours = Published::new();
+        publish_local(&local, &mut ours, &[], 100);
+
+        let mut theirs = ours.clone();
+        publish_local(&object(json!({"a": 1})), &mut theirs, &[], 200);
+
+        merge_remote(&mut local, &mut ours, &[theirs], &[]);
+        assert_eq!(local, object(json!({"a": 1})));
+        assert!(ours["b"].deleted);
+    }
+}
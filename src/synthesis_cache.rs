@@ -0,0 +1,129 @@
+//! Disk cache of synthesized readings.
+//!
+//! Once a reading has played to its end, its audio is kept as a WAV file in
+//! `~/.cache/insight-reader/audio`, named by a hash of the text and the
+//! voice, so reading the same selection again plays at once without
+//! synthesis. The playback speed is applied while playing, so the audio does
+//! not depend on it and it is not part of the key. The least recently read
+//! files are evicted when the cache grows past its size limit, set under
+//! Storage in the settings.
+
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use tracing::{debug, warn};
+
+use crate::providers::{samples_to_wav, wav_to_samples};
+
+/// Default size limit of the cache, in megabytes.
+pub const DEFAULT_LIMIT_MB: u32 = 500;
+
+/// Folder of the cached readings (`~/.cache/insight-reader/audio`).
+pub fn cache_dir() -> PathBuf {
+    dirs::cache_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("insight-reader")
+        .join("audio")
+}
+
+fn audio_path(dir: &Path, voice: &str, text: &str) -> PathBuf {
+    dir.join(format!("{}.wav", crate::text::content_hash(&format!("{voice}\n{text}"))))
+}
+
+/// Samples of `text` spoken with `voice`, if they were cached at `sample_rate`.
+pub fn cached(voice: &str, text: &str, sample_rate: u32) -> Option<Vec<f32>> {
+    cached_in(&cache_dir(), voice, text, sample_rate)
+}
+
+fn cached_in(dir: &Path, voice: &str, text: &str, sample_rate: u32) -> Option<Vec<f32>> {
+    let path = audio_path(dir, voice, text);
+    let (samples, rate) = wav_to_samples(&std::fs::read(&path).ok()?)?;
+    if rate != sample_rate {
+        return None;
+    }
+    // The modification time orders the files for eviction
+    if let Err(e) = std::fs::File::options().write(true).open(&path).and_then(|file| file.set_modified(SystemTime::now())) {
+        debug!(error = %e, path = %path.display(), "Failed to mark cached reading as used");
+    }
+    Some(samples)
+}
+
+/// Keep the audio of `text` spoken with `voice`, then evict the least
+/// recently read files beyond `limit_mb` megabytes.
+pub fn store(voice: &str, text: &str, samples: &[f32], sample_rate: u32, limit_mb: u32) {
+    store_in(&cache_dir(), voice, text, samples, sample_rate, limit_mb as u64 * 1024 * 1024);
+}
+
+fn store_in(dir: &Path, voice: &str, text: &str, samples: &[f32], sample_rate: u32, limit: u64) {
+    let wav = samples_to_wav(samples, sample_rate);
+    if wav.len() as u64 > limit {
+        debug!(bytes = wav.len(), limit, "Reading larger than the synthesis cache, not cached");
+        return;
+    }
+    let path = audio_path(dir, voice, text);
+    match std::fs::create_dir_all(dir).and_then(|_| std::fs::write(&path, wav)) {
+        Ok(()) => debug!(chars = text.len(), voice, "Reading cached"),
+        Err(e) => warn!(error = %e, path = %path.display(), "Failed to cache reading"),
+    }
+    evict(dir, limit);
+}
+
+/// Remove the least recently read files until the cache fits in `limit` bytes.
+fn evict(dir: &Path, limit: u64) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let mut files: Vec<(SystemTime, u64, PathBuf)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let meta = entry.metadata().ok()?;
+            Some((meta.modified().unwrap_or(SystemTime::UNIX_EPOCH), meta.len(), entry.path()))
+        })
+        .collect();
+    let mut total: u64 = files.iter().map(|(_, size, _)| size).sum();
+    files.sort();
+    for (_, size, path) in files {
+        if total <= limit {
+            break;
+        }
+        match std::fs::remove_file(&path) {
+            Ok(()) => {
+                debug!(path = %path.display(), "Evicted cached reading");
+                total -= size;
+            }
+            Err(e) => warn!(error = %e, path = %path.display(), "Failed to evict cached reading"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_store_and_evict() {
+        let dir = std::env::temp_dir().join(format!("insight-reader-synthesis-cache-{}", std::process::id()));
+        let samples = vec![0.25; 1000];
+        let size = samples_to_wav(&samples, 16000).len() as u64;
+
+        store_in(&dir, "piper:amy", "First reading.", &samples, 16000, 2 * size);
+        let cached = cached_in(&dir, "piper:amy", "First reading.", 16000).unwrap();
+        assert_eq!(cached.len(), samples.len());
+        assert!(cached.iter().all(|sample| (sample - 0.25).abs() < 0.001));
+        assert!(cached_in(&dir, "polly:Joanna", "First reading.", 16000).is_none());
+        assert!(cached_in(&dir, "piper:amy", "First reading.", 22050).is_none());
+
+        // A third reading evicts the least recently used one
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        store_in(&dir, "piper:amy", "Second reading.", &samples, 16000, 2 * size);
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        assert!(cached_in(&dir, "piper:amy", "First reading.", 16000).is_some());
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        store_in(&dir, "piper:amy", "Third reading.", &samples, 16000, 2 * size);
+        assert!(cached_in(&dir, "piper:amy", "First reading.", 16000).is_some());
+        assert!(cached_in(&dir, "piper:amy", "Second reading.", 16000).is_none());
+        assert!(cached_in(&dir, "piper:amy", "Third reading.", 16000).is_some());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
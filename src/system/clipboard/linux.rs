@@ -1,6 +1,7 @@
 //! Linux-specific clipboard implementation
 
 use super::process_text;
+use std::process::Command;
 use tracing::{debug, info};
 
 /// Gets the currently selected text on Linux.
@@ -27,3 +28,74 @@ pub(super) fn get_selected_text_linux() -> Option<String> {
         .ok()
         .and_then(|text| process_text(text, "clipboard (fallback)"))
 }
+
+/// Whether the PRIMARY selection holds text, in which case it is the selection
+/// source and other flavors must come from it too.
+fn primary_has_text() -> bool {
+    use arboard::{Clipboard, GetExtLinux, LinuxClipboardKind};
+    
+    Clipboard::new().is_ok_and(|mut clipboard| {
+        clipboard
+            .get()
+            .clipboard(LinuxClipboardKind::Primary)
+            .text()
+            .is_ok_and(|text| !text.trim().is_empty())
+    })
+}
+
+/// Gets the HTML flavor of the selection on Linux, from the same source
+/// [`get_selected_text_linux`] would read: PRIMARY if it holds text, else the clipboard.
+pub(super) fn get_selected_html_linux() -> Option<String> {
+    use arboard::{Clipboard, GetExtLinux, LinuxClipboardKind};
+    
+    let mut clipboard = Clipboard::new().ok()?;
+    
+    if primary_has_text() {
+        // Don't fall back to the clipboard: its HTML may be from an older copy
+        return clipboard
+            .get()
+            .clipboard(LinuxClipboardKind::Primary)
+            .html()
+            .ok()
+            .and_then(|html| process_text(html, "PRIMARY selection (HTML)"));
+    }
+    
+    clipboard.get()
+        .html()
+        .ok()
+        .and_then(|html| process_text(html, "clipboard (HTML)"))
+}
+
+/// Gets the RTF flavor of the selection on Linux, from the same source as
+/// [`get_selected_html_linux`].
+///
+/// arboard only reads text and HTML, so this asks `wl-paste` (Wayland) or
+/// `xclip` (X11) for the `text/rtf` target; returns None if neither is installed.
+pub(super) fn get_selected_rtf_linux() -> Option<String> {
+    let primary = primary_has_text();
+    let wayland = std::env::var_os("WAYLAND_DISPLAY").is_some();
+    
+    let mut command = if wayland {
+        let mut command = Command::new("wl-paste");
+        command.args(["--no-newline", "--type", "text/rtf"]);
+        if primary {
+            command.arg("--primary");
+        }
+        command
+    } else {
+        let mut command = Command::new("xclip");
+        command.args(["-o", "-t", "text/rtf", "-selection", if primary { "primary" } else { "clipboard" }]);
+        command
+    };
+    
+    let output = command.output().map_err(|e| debug!(error = %e, "RTF clipboard helper unavailable")).ok()?;
+    if !output.status.success() {
+        debug!("Selection has no RTF flavor");
+        return None;
+    }
+    let rtf = String::from_utf8_lossy(&output.stdout).to_string();
+    if !rtf.trim_start().starts_with("{\\rtf") {
+        return None;
+    }
+    process_text(rtf, if primary { "PRIMARY selection (RTF)" } else { "clipboard (RTF)" })
+}
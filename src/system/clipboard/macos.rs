@@ -1,6 +1,7 @@
 //! macOS-specific clipboard implementation
 
 use super::process_text;
+use std::process::Command;
 
 /// Gets the currently selected text on macOS.
 pub(super) fn get_selected_text_macos() -> Option<String> {
@@ -12,3 +13,28 @@ pub(super) fn get_selected_text_macos() -> Option<String> {
         .ok()
         .and_then(|text| process_text(text, "clipboard"))
 }
+
+/// Gets the HTML flavor of the clipboard on macOS, if present.
+pub(super) fn get_selected_html_macos() -> Option<String> {
+    use arboard::Clipboard;
+    
+    Clipboard::new()
+        .ok()?
+        .get()
+        .html()
+        .ok()
+        .and_then(|html| process_text(html, "clipboard (HTML)"))
+}
+
+/// Gets the RTF flavor of the clipboard on macOS, if present.
+///
+/// `pbpaste -Prefer rtf` falls back to plain text when there is no RTF, so the
+/// output is only used if it is actually RTF.
+pub(super) fn get_selected_rtf_macos() -> Option<String> {
+    let output = Command::new("pbpaste").args(["-Prefer", "rtf"]).output().ok()?;
+    let rtf = String::from_utf8_lossy(&output.stdout).to_string();
+    if !output.status.success() || !rtf.trim_start().starts_with("{\\rtf") {
+        return None;
+    }
+    process_text(rtf, "clipboard (RTF)")
+}
@@ -65,6 +65,71 @@ pub fn get_selected_text() -> Option<String> {
     }
 }
 
+/// Gets the HTML flavor of the selected text, when the source application provided one.
+/// Reads the same selection as [`get_selected_text`]; returns None if it has no HTML.
+pub fn get_selected_html() -> Option<String> {
+    #[cfg(target_os = "macos")]
+    {
+        macos::get_selected_html_macos()
+    }
+    
+    #[cfg(target_os = "linux")]
+    {
+        linux::get_selected_html_linux()
+    }
+    
+    #[cfg(target_os = "windows")]
+    {
+        windows::get_selected_html_windows()
+    }
+    
+    #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+    {
+        None
+    }
+}
+
+/// Gets the RTF flavor of the selected text, when the source application provided one
+/// (word processors, mail clients). Returns None if it has no RTF.
+pub fn get_selected_rtf() -> Option<String> {
+    #[cfg(target_os = "macos")]
+    {
+        macos::get_selected_rtf_macos()
+    }
+    
+    #[cfg(target_os = "linux")]
+    {
+        linux::get_selected_rtf_linux()
+    }
+    
+    #[cfg(target_os = "windows")]
+    {
+        windows::get_selected_rtf_windows()
+    }
+    
+    #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+    {
+        None
+    }
+}
+
+/// Selected text in the richest flavor available.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SelectedContent {
+    Html(String),
+    Rtf(String),
+    Text(String),
+}
+
+/// Gets the selection, preferring HTML, then RTF, then plain text, so structure
+/// (headings, lists, paragraphs) is not lost by flattening.
+pub fn get_selected_content() -> Option<SelectedContent> {
+    get_selected_html()
+        .map(SelectedContent::Html)
+        .or_else(|| get_selected_rtf().map(SelectedContent::Rtf))
+        .or_else(|| get_selected_text().map(SelectedContent::Text))
+}
+
 /// Copies text to the clipboard.
 /// - On macOS: Uses arboard
 /// - On Linux: Uses arboard
@@ -95,3 +160,70 @@ pub fn copy_to_clipboard(text: &str) -> Result<(), String> {
         Err("Clipboard copy not supported on this platform".to_string())
     }
 }
+
+/// Gets the text on the clipboard, ignoring the PRIMARY selection on Linux.
+pub fn get_clipboard_text() -> Option<String> {
+    #[cfg(any(target_os = "macos", target_os = "linux", target_os = "windows"))]
+    {
+        use arboard::Clipboard;
+
+        let mut clipboard = Clipboard::new()
+            .map_err(|e| warn!(error = %e, "Failed to initialize clipboard"))
+            .ok()?;
+        match clipboard.get_text() {
+            Ok(text) => process_text(text, "clipboard"),
+            Err(e) => {
+                debug!(error = %e, "No text on the clipboard");
+                None
+            }
+        }
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+    {
+        warn!("Platform not supported for clipboard text");
+        None
+    }
+}
+
+/// Saves the image on the clipboard (e.g. from a capture-to-clipboard
+/// shortcut) as a PNG file and returns its path, for OCR.
+pub fn get_clipboard_image() -> Result<String, String> {
+    let buffer = clipboard_image()?;
+    let path = std::env::temp_dir().join("insight-reader-clipboard-image.png");
+    buffer
+        .save(&path)
+        .map_err(|e| format!("Failed to save clipboard image: {}", e))?;
+    info!(width = buffer.width(), height = buffer.height(), "Saved clipboard image");
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// The image on the clipboard encoded as PNG in memory, for OCR without a temp file.
+pub fn get_clipboard_image_png() -> Result<Vec<u8>, String> {
+    crate::system::encode_png(&image::DynamicImage::ImageRgba8(clipboard_image()?))
+}
+
+fn clipboard_image() -> Result<image::RgbaImage, String> {
+    #[cfg(any(target_os = "macos", target_os = "linux", target_os = "windows"))]
+    {
+        use arboard::Clipboard;
+
+        let mut clipboard = Clipboard::new().map_err(|e| {
+            warn!(error = %e, "Failed to initialize clipboard");
+            format!("Failed to initialize clipboard: {}", e)
+        })?;
+        let data = clipboard.get_image().map_err(|e| {
+            debug!(error = %e, "No image on the clipboard");
+            "No image on the clipboard".to_string()
+        })?;
+        let (width, height) = (data.width as u32, data.height as u32);
+        image::RgbaImage::from_raw(width, height, data.bytes.into_owned())
+            .ok_or_else(|| "Clipboard image has an unexpected size".to_string())
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+    {
+        warn!("Platform not supported for clipboard images");
+        Err("Clipboard images not supported on this platform".to_string())
+    }
+}
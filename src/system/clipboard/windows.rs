@@ -13,3 +13,30 @@ pub(super) fn get_selected_text_windows() -> Option<String> {
         .ok()
         .and_then(|text| process_text(text, "clipboard"))
 }
+
+/// Gets the HTML flavor ("HTML Format") of the clipboard on Windows, if present.
+pub(super) fn get_selected_html_windows() -> Option<String> {
+    use arboard::Clipboard;
+    
+    let html = Clipboard::new().ok()?.get().html().ok()?;
+    // Skip the "Version:0.9 StartHTML:..." description header if it is still there
+    let html = match html.find('<') {
+        Some(start) if html.starts_with("Version:") => html[start..].to_string(),
+        _ => html,
+    };
+    process_text(html, "clipboard (HTML)")
+}
+
+/// Gets the RTF flavor ("Rich Text Format") of the clipboard on Windows, if present.
+pub(super) fn get_selected_rtf_windows() -> Option<String> {
+    let format = clipboard_win::register_format("Rich Text Format")?.get();
+    let _clipboard = clipboard_win::Clipboard::new_attempts(10).ok()?;
+    if !clipboard_win::is_format_avail(format) {
+        return None;
+    }
+    let mut data = Vec::new();
+    clipboard_win::raw::get_vec(format, &mut data).ok()?;
+    // RTF is 7-bit ASCII with escapes; the data is NUL-terminated
+    let end = data.iter().position(|&b| b == 0).unwrap_or(data.len());
+    process_text(String::from_utf8_lossy(&data[..end]).to_string(), "clipboard (RTF)")
+}
@@ -0,0 +1,274 @@
+//! Audio file encoding via ffmpeg (MP3, Ogg and chaptered M4B).
+//!
+//! Synthesized audio is written as WAV first, then handed to ffmpeg for
+//! compression, like other heavy lifting done by external tools. Tags are
+//! written by the [`super::tagging`] module.
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+#[cfg(target_os = "windows")]
+use std::os::windows::process::CommandExt;
+
+use tracing::{debug, error};
+
+use super::tagging::{ffmetadata, timed_chapters, write_id3, AudioTags};
+
+/// A chapter WAV file to be joined into a single output file.
+#[derive(Debug, Clone)]
+pub struct ChapterMarker {
+    pub title: String,
+    pub wav_path: PathBuf,
+    pub duration_secs: f32,
+}
+
+/// Formats a single reading can be exported in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioFormat {
+    Wav,
+    Mp3,
+    Ogg,
+}
+
+impl AudioFormat {
+    /// Format named by the extension of `path`.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "wav" => Some(Self::Wav),
+            "mp3" => Some(Self::Mp3),
+            "ogg" | "oga" => Some(Self::Ogg),
+            _ => None,
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Wav => "wav",
+            Self::Mp3 => "mp3",
+            Self::Ogg => "ogg",
+        }
+    }
+}
+
+/// Locate ffmpeg on PATH.
+pub fn find_ffmpeg() -> Option<PathBuf> {
+    find_on_path("ffmpeg")
+}
+
+/// Locate an external tool on PATH.
+pub fn find_on_path(bin: &str) -> Option<PathBuf> {
+    #[cfg(target_os = "windows")]
+    let path_cmd = "where";
+    #[cfg(not(target_os = "windows"))]
+    let path_cmd = "which";
+
+    let mut cmd = Command::new(path_cmd);
+    cmd.arg(bin);
+    #[cfg(target_os = "windows")]
+    cmd.creation_flags(super::CREATE_NO_WINDOW);
+    let output = cmd.output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let path = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .next()
+        .unwrap_or("")
+        .trim()
+        .to_string();
+    (!path.is_empty()).then(|| PathBuf::from(path))
+}
+
+/// Run ffmpeg with the given arguments, returning stderr on failure.
+fn run_ffmpeg(ffmpeg: &Path, args: &[String]) -> Result<(), String> {
+    debug!(ffmpeg = %ffmpeg.display(), ?args, "Running ffmpeg");
+    let mut cmd = Command::new(ffmpeg);
+    cmd.args(["-hide_banner", "-loglevel", "error", "-y"])
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped());
+    #[cfg(target_os = "windows")]
+    cmd.creation_flags(super::CREATE_NO_WINDOW);
+    let output = cmd
+        .output()
+        .map_err(|e| format!("Failed to run ffmpeg: {e}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        error!(stderr = %stderr.trim(), "ffmpeg failed");
+        return Err(format!("ffmpeg failed: {}", stderr.trim()));
+    }
+    Ok(())
+}
+
+/// Write an ffconcat list of the chapter WAVs into the work directory.
+fn write_concat_list(work_dir: &Path, chapters: &[ChapterMarker]) -> Result<PathBuf, String> {
+    std::fs::create_dir_all(work_dir)
+        .map_err(|e| format!("Failed to create {}: {e}", work_dir.display()))?;
+    let list_path = work_dir.join("chapters.txt");
+    let list: String = chapters
+        .iter()
+        .map(|c| {
+            let path = c.wav_path.to_string_lossy().replace('\'', "'\\''");
+            format!("file '{path}'\n")
+        })
+        .collect();
+    std::fs::write(&list_path, list).map_err(|e| format!("Failed to write chapter list: {e}"))?;
+    Ok(list_path)
+}
+
+/// ffmpeg arguments for MP3 output without ffmpeg's own ID3 tag.
+fn mp3_codec_args() -> Vec<String> {
+    ["-write_id3v2", "0", "-codec:a", "libmp3lame", "-q:a", "4"]
+        .map(String::from)
+        .to_vec()
+}
+
+/// Encode a WAV file to a tagged MP3.
+pub fn encode_mp3(ffmpeg: &Path, wav: &Path, out: &Path, tags: &AudioTags) -> Result<(), String> {
+    let mut args = vec!["-i".to_string(), wav.to_string_lossy().to_string()];
+    args.extend(mp3_codec_args());
+    args.push(out.to_string_lossy().to_string());
+    run_ffmpeg(ffmpeg, &args)?;
+    write_id3(out, tags, &[])
+}
+
+/// Encode a WAV file to Ogg Vorbis, with the title and voice as comments.
+pub fn encode_ogg(ffmpeg: &Path, wav: &Path, out: &Path, tags: &AudioTags) -> Result<(), String> {
+    let mut args = vec!["-i".to_string(), wav.to_string_lossy().to_string()];
+    args.extend(["-codec:a", "libvorbis", "-q:a", "4"].map(String::from));
+    args.extend(["-metadata".to_string(), format!("title={}", tags.title)]);
+    if let Some(artist) = &tags.artist {
+        args.extend(["-metadata".to_string(), format!("artist={artist}")]);
+    }
+    args.push(out.to_string_lossy().to_string());
+    run_ffmpeg(ffmpeg, &args)
+}
+
+/// Write WAV audio to `out` in the format of its extension. MP3 and Ogg are
+/// encoded with ffmpeg from a WAV written next to `out`.
+pub fn export_audio(wav: &[u8], out: &Path, tags: &AudioTags) -> Result<(), String> {
+    let format = AudioFormat::from_path(out)
+        .ok_or_else(|| format!("Unsupported audio file {} (expected .wav, .mp3 or .ogg)", out.display()))?;
+    if let Some(dir) = out.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {e}", dir.display()))?;
+    }
+    if format == AudioFormat::Wav {
+        return std::fs::write(out, wav).map_err(|e| format!("Failed to write {}: {e}", out.display()));
+    }
+    let ffmpeg = find_ffmpeg().ok_or("ffmpeg not found on PATH (needed for MP3 and Ogg export)")?;
+    let wav_path = out.with_extension("export.wav");
+    std::fs::write(&wav_path, wav).map_err(|e| format!("Failed to write {}: {e}", wav_path.display()))?;
+    let result = match format {
+        AudioFormat::Mp3 => encode_mp3(&ffmpeg, &wav_path, out, tags),
+        AudioFormat::Ogg => encode_ogg(&ffmpeg, &wav_path, out, tags),
+        AudioFormat::Wav => Ok(()),
+    };
+    let _ = std::fs::remove_file(&wav_path);
+    result
+}
+
+/// Default folder for exported readings.
+///
+/// The Music folder (`~/Music/Insight Reader`), or the home folder without one.
+pub fn default_export_dir() -> PathBuf {
+    dirs::audio_dir()
+        .or_else(dirs::home_dir)
+        .unwrap_or_else(std::env::temp_dir)
+        .join("Insight Reader")
+}
+
+/// Make a title safe to use as a file name on all platforms.
+pub fn sanitize_file_name(name: &str) -> String {
+    let cleaned: String = name
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .take(60)
+        .collect();
+    let cleaned = cleaned.trim().trim_end_matches('.').to_string();
+    if cleaned.is_empty() {
+        "Untitled".to_string()
+    } else {
+        cleaned
+    }
+}
+
+/// Concatenate chapter WAVs into a single MP3 with ID3 chapter frames.
+pub fn encode_mp3_chapters(
+    ffmpeg: &Path,
+    chapters: &[ChapterMarker],
+    out: &Path,
+    tags: &AudioTags,
+) -> Result<(), String> {
+    let work_dir = out.with_extension("mp3.parts");
+    let list_path = write_concat_list(&work_dir, chapters)?;
+
+    let mut args: Vec<String> = ["-f", "concat", "-safe", "0", "-i"].map(String::from).to_vec();
+    args.push(list_path.to_string_lossy().to_string());
+    args.extend(mp3_codec_args());
+    args.push(out.to_string_lossy().to_string());
+
+    let result = run_ffmpeg(ffmpeg, &args);
+    let _ = std::fs::remove_dir_all(&work_dir);
+    result?;
+
+    let markers = timed_chapters(chapters.iter().map(|c| (c.title.as_str(), c.duration_secs)));
+    write_id3(out, tags, &markers)
+}
+
+/// Concatenate chapter WAVs into a single M4B audiobook with chapter markers.
+pub fn encode_m4b(
+    ffmpeg: &Path,
+    chapters: &[ChapterMarker],
+    out: &Path,
+    tags: &AudioTags,
+) -> Result<(), String> {
+    let work_dir = out.with_extension("m4b.parts");
+    let list_path = write_concat_list(&work_dir, chapters)?;
+
+    let markers = timed_chapters(chapters.iter().map(|c| (c.title.as_str(), c.duration_secs)));
+    let meta_path = work_dir.join("metadata.txt");
+    std::fs::write(&meta_path, ffmetadata(tags, &markers))
+        .map_err(|e| format!("Failed to write chapter metadata: {e}"))?;
+
+    let mut args: Vec<String> = ["-f", "concat", "-safe", "0", "-i"].map(String::from).to_vec();
+    args.push(list_path.to_string_lossy().to_string());
+    args.extend(["-i".to_string(), meta_path.to_string_lossy().to_string()]);
+    args.extend(
+        ["-map_metadata", "1", "-map", "0:a", "-codec:a", "aac", "-b:a", "64k"].map(String::from),
+    );
+    args.push(out.to_string_lossy().to_string());
+
+    let result = run_ffmpeg(ffmpeg, &args);
+    let _ = std::fs::remove_dir_all(&work_dir);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_file_name() {
+        assert_eq!(sanitize_file_name("Part 1: Why? / How."), "Part 1_ Why_ _ How");
+        assert_eq!(sanitize_file_name("  "), "Untitled");
+    }
+
+    #[test]
+    fn test_audio_format() {
+        assert_eq!(AudioFormat::from_path(Path::new("reading.MP3")), Some(AudioFormat::Mp3));
+        assert_eq!(AudioFormat::from_path(Path::new("/tmp/a.b/reading.ogg")), Some(AudioFormat::Ogg));
+        assert_eq!(AudioFormat::from_path(Path::new("reading.flac")), None);
+        assert_eq!(AudioFormat::from_path(Path::new("reading")), None);
+
+        let out = std::env::temp_dir().join(format!("insight-reader-export-{}", std::process::id())).join("r.wav");
+        export_audio(b"RIFF", &out, &AudioTags::default()).unwrap();
+        assert_eq!(std::fs::read(&out).unwrap(), b"RIFF");
+        assert!(export_audio(b"RIFF", &out.with_extension("flac"), &AudioTags::default()).is_err());
+        let _ = std::fs::remove_dir_all(out.parent().unwrap());
+    }
+}
@@ -0,0 +1,128 @@
+//! Encryption of exports with age (https://age-encryption.org).
+//!
+//! Exported audio and history archives can be encrypted to the user's own
+//! key by the `age` tool, found on PATH like ffmpeg. Only public keys (age
+//! `age1…` keys, SSH public keys, or files listing them) are needed to
+//! encrypt, so no secret key ever passes through the app. Plaintext files an
+//! export had to write are shredded once encrypted.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+#[cfg(target_os = "windows")]
+use std::os::windows::process::CommandExt;
+
+use tracing::{debug, error};
+
+use super::encode::find_on_path;
+use super::screenshot::shred;
+
+/// Extension added to encrypted files.
+pub const AGE_EXTENSION: &str = "age";
+
+/// Locate age on PATH.
+pub fn find_age() -> Option<PathBuf> {
+    find_on_path("age")
+}
+
+/// Recipients from a comma-separated list, like the export setting.
+pub fn parse_recipients(list: &str) -> Vec<String> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|recipient| !recipient.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// age arguments for the recipients: public keys are passed with `-r`,
+/// anything else must be a recipients file (`-R`).
+fn recipient_args(recipients: &[String]) -> Result<Vec<String>, String> {
+    if recipients.is_empty() {
+        return Err("No age recipient to encrypt to".to_string());
+    }
+    recipients
+        .iter()
+        .flat_map(|recipient| {
+            let flag = if recipient.starts_with("age1") || recipient.starts_with("ssh-") {
+                Ok("-r")
+            } else if Path::new(recipient).is_file() {
+                Ok("-R")
+            } else {
+                Err(format!("'{recipient}' is neither an age or SSH public key nor a recipients file"))
+            };
+            [flag.map(str::to_string), Ok(recipient.clone())]
+        })
+        .collect()
+}
+
+fn age_command(age: &Path, recipients: &[String], out: &Path) -> Result<Command, String> {
+    let mut cmd = Command::new(age);
+    cmd.arg("--encrypt")
+        .args(recipient_args(recipients)?)
+        .arg("--output")
+        .arg(out)
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped());
+    #[cfg(target_os = "windows")]
+    cmd.creation_flags(super::CREATE_NO_WINDOW);
+    Ok(cmd)
+}
+
+fn check_output(output: std::process::Output, out: &Path) -> Result<(), String> {
+    if output.status.success() {
+        debug!(out = %out.display(), "Export encrypted");
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    error!(stderr = %stderr.trim(), "age failed");
+    let _ = std::fs::remove_file(out);
+    Err(format!("age failed: {}", stderr.trim()))
+}
+
+/// Encrypt data held in memory into `out`, so the plaintext never touches the disk.
+pub fn encrypt_bytes(age: &Path, plaintext: &[u8], recipients: &[String], out: &Path) -> Result<(), String> {
+    let mut child = age_command(age, recipients, out)?
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run age: {e}"))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(plaintext)
+            .map_err(|e| format!("Failed to pass data to age: {e}"))?;
+    }
+    let output = child.wait_with_output().map_err(|e| format!("Failed to run age: {e}"))?;
+    check_output(output, out)
+}
+
+/// Encrypt a file to `<file>.age` and shred the original.
+pub fn encrypt_file(age: &Path, path: &Path, recipients: &[String]) -> Result<PathBuf, String> {
+    let mut out = path.as_os_str().to_owned();
+    out.push(format!(".{AGE_EXTENSION}"));
+    let out = PathBuf::from(out);
+    let output = age_command(age, recipients, &out)?
+        .arg(path)
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| format!("Failed to run age: {e}"))?;
+    check_output(output, &out)?;
+    shred(path);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recipient_args() {
+        let recipients = parse_recipients(" age1qyqszqgpqyqszqgp, ssh-ed25519 AAAAC3Nza me@laptop ,, ");
+        assert_eq!(recipients, ["age1qyqszqgpqyqszqgp", "ssh-ed25519 AAAAC3Nza me@laptop"]);
+        assert_eq!(
+            recipient_args(&recipients).unwrap(),
+            ["-r", "age1qyqszqgpqyqszqgp", "-r", "ssh-ed25519 AAAAC3Nza me@laptop"]
+        );
+        assert!(recipient_args(&[]).is_err());
+        assert!(recipient_args(&["not a key".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,452 @@
+//! Local podcast feed of exported readings.
+//!
+//! Exported audio is copied into a feed folder next to an `items.json` index
+//! and a generated `feed.xml` (RSS 2.0 with iTunes tags). A small built-in
+//! HTTP server shares the folder on the LAN so a phone's podcast app can
+//! subscribe to the feed URL.
+
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, TcpListener, TcpStream, UdpSocket};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+use crate::text::{content_hash, percent_decode};
+
+/// Default port of the feed server.
+pub const DEFAULT_FEED_PORT: u16 = 8765;
+
+const FEED_FILE: &str = "feed.xml";
+const ITEMS_FILE: &str = "items.json";
+
+/// Connections served at once; more are closed right away.
+const MAX_CONNECTIONS: usize = 8;
+
+/// How long a connection may stall sending its request or taking the audio.
+const IO_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Podcast feed settings.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeedConfig {
+    /// Whether exports are added to the feed and the folder is served on the LAN
+    pub enabled: bool,
+    /// Folder holding `feed.xml`, `items.json` and the audio files
+    pub dir: PathBuf,
+    pub title: String,
+    pub author: String,
+    pub description: String,
+    pub port: u16,
+    /// Public base URL of the folder; detected from the LAN address when unset
+    pub base_url: Option<String>,
+}
+
+impl Default for FeedConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            dir: default_feed_dir(),
+            title: "Insight Reader".to_string(),
+            author: "Insight Reader".to_string(),
+            description: "Readings exported from Insight Reader".to_string(),
+            port: DEFAULT_FEED_PORT,
+            base_url: None,
+        }
+    }
+}
+
+impl FeedConfig {
+    /// Base URL the audio files are served from, always ending in `/`.
+    pub fn base_url(&self) -> String {
+        let base = self.base_url.clone().unwrap_or_else(|| {
+            let host = lan_ip().unwrap_or(IpAddr::V4(Ipv4Addr::LOCALHOST));
+            format!("http://{host}:{}/", self.port)
+        });
+        if base.ends_with('/') {
+            base
+        } else {
+            format!("{base}/")
+        }
+    }
+
+    /// URL to subscribe to in a podcast app.
+    pub fn feed_url(&self) -> String {
+        format!("{}{FEED_FILE}", self.base_url())
+    }
+}
+
+/// Default feed folder.
+///
+/// Linux/macOS: `~/.local/share/insight-reader/feed`
+/// Windows: `%LOCALAPPDATA%\insight-reader\feed`
+pub fn default_feed_dir() -> PathBuf {
+    crate::sync::app_data_dir().join("feed")
+}
+
+/// Address of this machine on the local network.
+///
+/// Connecting a UDP socket sends no packets; it only selects the outgoing interface.
+fn lan_ip() -> Option<IpAddr> {
+    let socket = UdpSocket::bind("0.0.0.0:0").ok()?;
+    socket.connect("192.0.2.1:80").ok()?;
+    let ip = socket.local_addr().ok()?.ip();
+    (!ip.is_unspecified()).then_some(ip)
+}
+
+/// An episode in the feed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedItem {
+    pub guid: String,
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    /// File name inside the feed folder
+    pub file: String,
+    pub length_bytes: u64,
+    pub duration_secs: f32,
+    /// RFC 2822 publication date
+    pub pub_date: String,
+}
+
+fn load_items(dir: &Path) -> Vec<FeedItem> {
+    let path = dir.join(ITEMS_FILE);
+    match std::fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            warn!(error = %e, path = %path.display(), "Invalid feed index, starting a new one");
+            Vec::new()
+        }),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Copy an exported audio file into the feed folder and add it as an episode.
+pub fn add_to_feed(
+    config: &FeedConfig,
+    audio: &Path,
+    title: &str,
+    description: &str,
+    duration_secs: f32,
+) -> Result<(), String> {
+    std::fs::create_dir_all(&config.dir)
+        .map_err(|e| format!("Failed to create {}: {e}", config.dir.display()))?;
+
+    let now = chrono::Utc::now();
+    let original = audio
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .ok_or_else(|| format!("Not a file: {}", audio.display()))?;
+    // Prefix with a timestamp so repeated exports never overwrite each other
+    let file = format!("{}-{original}", now.format("%Y%m%d-%H%M%S"));
+    let target = config.dir.join(&file);
+    let length_bytes = std::fs::copy(audio, &target)
+        .map_err(|e| format!("Failed to copy {} into feed: {e}", audio.display()))?;
+
+    let mut items = load_items(&config.dir);
+    items.push(FeedItem {
+        guid: content_hash(&format!("{file} {length_bytes}")),
+        title: title.to_string(),
+        description: description.to_string(),
+        file,
+        length_bytes,
+        duration_secs,
+        pub_date: now.to_rfc2822(),
+    });
+
+    let json = serde_json::to_string_pretty(&items)
+        .map_err(|e| format!("Failed to serialize feed index: {e}"))?;
+    std::fs::write(config.dir.join(ITEMS_FILE), json)
+        .map_err(|e| format!("Failed to write feed index: {e}"))?;
+    write_feed(config, &items)?;
+    info!(title = %title, "Added episode to podcast feed");
+    Ok(())
+}
+
+/// Regenerate `feed.xml` from the index (e.g. after feed metadata changed).
+pub fn refresh_feed(config: &FeedConfig) -> Result<(), String> {
+    if !config.dir.is_dir() {
+        return Ok(());
+    }
+    write_feed(config, &load_items(&config.dir))
+}
+
+fn write_feed(config: &FeedConfig, items: &[FeedItem]) -> Result<(), String> {
+    std::fs::write(config.dir.join(FEED_FILE), render_feed(config, items))
+        .map_err(|e| format!("Failed to write feed: {e}"))
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Percent-encode a file name for use in a URL path.
+fn encode_path(name: &str) -> String {
+    name.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{b:02X}"),
+        })
+        .collect()
+}
+
+fn mime_type(file: &str) -> &'static str {
+    match file.rsplit('.').next().map(str::to_ascii_lowercase).as_deref() {
+        Some("mp3") => "audio/mpeg",
+        Some("m4a") | Some("m4b") => "audio/mp4",
+        Some("wav") => "audio/wav",
+        Some("xml") => "application/rss+xml; charset=utf-8",
+        Some("json") => "application/json",
+        _ => "application/octet-stream",
+    }
+}
+
+fn format_duration(secs: f32) -> String {
+    let secs = secs.max(0.0) as u64;
+    format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+/// Render the RSS document, newest episodes first.
+fn render_feed(config: &FeedConfig, items: &[FeedItem]) -> String {
+    let base = config.base_url();
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <rss version=\"2.0\" xmlns:itunes=\"http://www.itunes.com/dtds/podcast-1.0.dtd\">\n\
+         <channel>\n",
+    );
+    xml.push_str(&format!("<title>{}</title>\n", escape_xml(&config.title)));
+    xml.push_str(&format!("<link>{}</link>\n", escape_xml(&base)));
+    xml.push_str(&format!("<description>{}</description>\n", escape_xml(&config.description)));
+    xml.push_str(&format!("<itunes:author>{}</itunes:author>\n", escape_xml(&config.author)));
+    xml.push_str("<language>en</language>\n");
+
+    for item in items.iter().rev() {
+        let url = format!("{base}{}", encode_path(&item.file));
+        xml.push_str("<item>\n");
+        xml.push_str(&format!("<title>{}</title>\n", escape_xml(&item.title)));
+        xml.push_str(&format!("<description>{}</description>\n", escape_xml(&item.description)));
+        xml.push_str(&format!("<guid isPermaLink=\"false\">{}</guid>\n", item.guid));
+        xml.push_str(&format!("<pubDate>{}</pubDate>\n", item.pub_date));
+        xml.push_str(&format!(
+            "<enclosure url=\"{}\" length=\"{}\" type=\"{}\"/>\n",
+            escape_xml(&url),
+            item.length_bytes,
+            mime_type(&item.file)
+        ));
+        if item.duration_secs > 0.0 {
+            xml.push_str(&format!(
+                "<itunes:duration>{}</itunes:duration>\n",
+                format_duration(item.duration_secs)
+            ));
+        }
+        xml.push_str("</item>\n");
+    }
+    xml.push_str("</channel>\n</rss>\n");
+    xml
+}
+
+/// Minimal HTTP server for the feed folder (GET/HEAD with byte ranges).
+pub struct FeedServer {
+    addr: SocketAddr,
+    stop: Arc<AtomicBool>,
+}
+
+impl FeedServer {
+    /// Start serving the feed folder on all interfaces.
+    pub fn start(config: &FeedConfig) -> Result<Self, String> {
+        std::fs::create_dir_all(&config.dir)
+            .map_err(|e| format!("Failed to create {}: {e}", config.dir.display()))?;
+        refresh_feed(config)?;
+
+        let listener = TcpListener::bind(("0.0.0.0", config.port))
+            .map_err(|e| format!("Failed to listen on port {}: {e}", config.port))?;
+        let addr = listener
+            .local_addr()
+            .map_err(|e| format!("Failed to read listener address: {e}"))?;
+        let stop = Arc::new(AtomicBool::new(false));
+        let dir = config.dir.clone();
+
+        let stop_flag = stop.clone();
+        let active = Arc::new(AtomicUsize::new(0));
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                if stop_flag.load(Ordering::Relaxed) {
+                    break;
+                }
+                let Ok(stream) = stream else { continue };
+                if active.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+                    active.fetch_sub(1, Ordering::SeqCst);
+                    debug!("Feed server busy, connection closed");
+                    continue;
+                }
+                let dir = dir.clone();
+                let active = active.clone();
+                std::thread::spawn(move || {
+                    if let Err(e) = handle_connection(stream, &dir) {
+                        debug!(error = %e, "Feed request failed");
+                    }
+                    active.fetch_sub(1, Ordering::SeqCst);
+                });
+            }
+            debug!("Feed server stopped");
+        });
+
+        info!(feed_url = %config.feed_url(), "Podcast feed server started");
+        Ok(Self { addr, stop })
+    }
+}
+
+impl Drop for FeedServer {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        // Wake the accept loop so it sees the stop flag
+        let _ = TcpStream::connect((Ipv4Addr::LOCALHOST, self.addr.port()));
+    }
+}
+
+/// Parse a `Range: bytes=start-end` header value.
+fn parse_range(value: &str, len: u64) -> Option<(u64, u64)> {
+    let spec = value.trim().strip_prefix("bytes=")?;
+    let (start, end) = spec.split(',').next()?.split_once('-')?;
+    let (start, end) = match (start.trim(), end.trim()) {
+        ("", suffix) => {
+            let suffix: u64 = suffix.parse().ok()?;
+            (len.saturating_sub(suffix), len.checked_sub(1)?)
+        }
+        (start, "") => (start.parse().ok()?, len.checked_sub(1)?),
+        (start, end) => (start.parse().ok()?, end.parse::<u64>().ok()?.min(len.checked_sub(1)?)),
+    };
+    (start <= end && end < len).then_some((start, end))
+}
+
+/// Whether `name` is a file of the feed folder that may be served: a plain
+/// name (no separators, nor a Windows drive as in `C:name`), not hidden and
+/// not the item index.
+fn is_served_name(name: &str) -> bool {
+    !name.contains(['/', '\\', ':']) && !name.starts_with('.') && name != ITEMS_FILE
+}
+
+fn handle_connection(stream: TcpStream, dir: &Path) -> std::io::Result<()> {
+    // Also applies to the clone reading the request
+    stream.set_read_timeout(Some(IO_TIMEOUT))?;
+    stream.set_write_timeout(Some(IO_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut range = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("range") {
+                range = Some(value.trim().to_string());
+            }
+        }
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or("");
+    let path = parts.next().unwrap_or("/");
+    let mut stream = stream;
+
+    if method != "GET" && method != "HEAD" {
+        return stream.write_all(b"HTTP/1.1 405 Method Not Allowed\r\nContent-Length: 0\r\nConnection: close\r\n\r\n");
+    }
+
+    // Only plain file names inside the feed folder are served
+    let name = percent_decode(path.trim_start_matches('/').split('?').next().unwrap_or(""));
+    let name = if name.is_empty() { FEED_FILE.to_string() } else { name };
+    let file_path = dir.join(&name);
+    let Some(mut file) = is_served_name(&name)
+        .then(|| std::fs::File::open(&file_path).ok())
+        .flatten()
+    else {
+        return stream.write_all(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n");
+    };
+
+    let len = file.metadata()?.len();
+    let (status, start, end) = match range.as_deref().map(|r| parse_range(r, len)) {
+        Some(Some((start, end))) => ("206 Partial Content", start, end),
+        Some(None) => {
+            let header = format!("HTTP/1.1 416 Range Not Satisfiable\r\nContent-Range: bytes */{len}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n");
+            return stream.write_all(header.as_bytes());
+        }
+        None => ("200 OK", 0, len.saturating_sub(1)),
+    };
+    let body_len = if len == 0 { 0 } else { end - start + 1 };
+
+    let mut header = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: {}\r\nContent-Length: {body_len}\r\nAccept-Ranges: bytes\r\nConnection: close\r\n",
+        mime_type(&name)
+    );
+    if status.starts_with("206") {
+        header.push_str(&format!("Content-Range: bytes {start}-{end}/{len}\r\n"));
+    }
+    header.push_str("\r\n");
+    stream.write_all(header.as_bytes())?;
+
+    if method == "GET" && body_len > 0 {
+        file.seek(SeekFrom::Start(start))?;
+        std::io::copy(&mut file.take(body_len), &mut stream)?;
+    }
+    stream.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(parse_range("bytes=0-99", 1000), Some((0, 99)));
+        assert_eq!(parse_range("bytes=900-", 1000), Some((900, 999)));
+        assert_eq!(parse_range("bytes=-100", 1000), Some((900, 999)));
+        assert_eq!(parse_range("bytes=0-5000", 1000), Some((0, 999)));
+        assert_eq!(parse_range("bytes=2000-", 1000), None);
+    }
+
+    #[test]
+    fn test_is_served_name() {
+        assert!(is_served_name("chapter-1.mp3"));
+        assert!(is_served_name(FEED_FILE));
+        assert!(!is_served_name(ITEMS_FILE));
+        assert!(!is_served_name("sub/secret.txt"));
+        assert!(!is_served_name("sub\\secret.txt"));
+        assert!(!is_served_name("C:secret.txt"));
+        assert!(!is_served_name(".hidden"));
+    }
+
+    #[test]
+    fn test_render_feed_items() {
+        let config = FeedConfig {
+            title: "Me & My Readings".into(),
+            base_url: Some("http://10.0.0.2:8765".into()),
+            ..Default::default()
+        };
+        let items = vec![FeedItem {
+            guid: "abc".into(),
+            title: "Chapter <1>".into(),
+            description: String::new(),
+            file: "20260101-000000-01 - One.mp3".into(),
+            length_bytes: 42,
+            duration_secs: 3725.0,
+            pub_date: "Thu, 01 Jan 2026 00:00:00 +0000".into(),
+        }];
+        let xml = render_feed(&config, &items);
+        assert!(xml.contains("<title>Me &amp; My Readings</title>"));
+        assert!(xml.contains("<title>Chapter &lt;1&gt;</title>"));
+        assert!(xml.contains(
+            "url=\"http://10.0.0.2:8765/20260101-000000-01%20-%20One.mp3\" length=\"42\" type=\"audio/mpeg\""
+        ));
+        assert!(xml.contains("<itunes:duration>01:02:05</itunes:duration>"));
+    }
+}
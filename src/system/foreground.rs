@@ -0,0 +1,152 @@
+//! Detect which application has the focus when text is captured.
+//!
+//! Used to keep text from some applications (password managers, a banking
+//! browser window) away from cloud services. Each platform reports the
+//! application name and the title of its focused window:
+//! - Linux: `hyprctl` on Hyprland, `xdotool` on X11
+//! - macOS: System Events through `osascript`
+//! - Windows: the foreground window and its process image name
+
+#[cfg(not(target_os = "windows"))]
+use std::process::Command;
+
+use tracing::debug;
+
+/// The focused application.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ForegroundApp {
+    /// Application or process name (e.g. "keepassxc", "firefox")
+    pub name: String,
+    /// Title of the focused window
+    pub title: String,
+}
+
+impl ForegroundApp {
+    /// Whether any of `entries` names this app or appears in its window title (case-insensitive).
+    pub fn matches_any<'a>(&self, entries: impl IntoIterator<Item = &'a str>) -> bool {
+        let name = self.name.to_lowercase();
+        let title = self.title.to_lowercase();
+        entries
+            .into_iter()
+            .map(|entry| entry.trim().to_lowercase())
+            .filter(|entry| !entry.is_empty())
+            .any(|entry| name.contains(&entry) || title.contains(&entry))
+    }
+}
+
+/// The application that has the focus, if it can be told.
+pub fn foreground_app() -> Option<ForegroundApp> {
+    let app = probe();
+    debug!(?app, "Foreground application probed");
+    app
+}
+
+#[cfg(target_os = "linux")]
+fn probe() -> Option<ForegroundApp> {
+    if crate::system::is_wayland_hyprland() {
+        let output = Command::new("hyprctl").args(["activewindow", "-j"]).output().ok()?;
+        let window: serde_json::Value = serde_json::from_slice(&output.stdout).ok()?;
+        return Some(ForegroundApp {
+            name: window["class"].as_str()?.to_string(),
+            title: window["title"].as_str().unwrap_or_default().to_string(),
+        });
+    }
+
+    let xdotool = |command: &str| {
+        Command::new("xdotool")
+            .args(["getactivewindow", command])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+    let pid = xdotool("getwindowpid")?;
+    let name = std::fs::read_to_string(format!("/proc/{pid}/comm")).ok()?;
+    Some(ForegroundApp {
+        name: name.trim().to_string(),
+        title: xdotool("getwindowname").unwrap_or_default(),
+    })
+}
+
+#[cfg(target_os = "macos")]
+fn probe() -> Option<ForegroundApp> {
+    const SCRIPT: &str = r#"tell application "System Events"
+    set frontApp to first application process whose frontmost is true
+    set windowTitle to ""
+    try
+        set windowTitle to name of front window of frontApp
+    end try
+    return (name of frontApp) & linefeed & windowTitle
+end tell"#;
+    let output = Command::new("osascript").args(["-e", SCRIPT]).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let (name, title) = stdout.trim_end().split_once('\n').unwrap_or((stdout.trim_end(), ""));
+    Some(ForegroundApp {
+        name: name.to_string(),
+        title: title.to_string(),
+    })
+}
+
+#[cfg(target_os = "windows")]
+fn probe() -> Option<ForegroundApp> {
+    use windows::core::PWSTR;
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Threading::{
+        OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32, PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+    use windows::Win32::UI::WindowsAndMessaging::{GetForegroundWindow, GetWindowTextW, GetWindowThreadProcessId};
+
+    // SAFETY: plain Win32 queries; buffers outlive the calls and their sizes are passed along
+    unsafe {
+        let window = GetForegroundWindow();
+        if window.is_invalid() {
+            return None;
+        }
+        let mut title = [0u16; 512];
+        let title_len = GetWindowTextW(window, &mut title).max(0) as usize;
+
+        let mut pid = 0u32;
+        GetWindowThreadProcessId(window, Some(&mut pid as *mut u32));
+        let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()?;
+        let mut path = [0u16; 1024];
+        let mut path_len = path.len() as u32;
+        let queried = QueryFullProcessImageNameW(process, PROCESS_NAME_WIN32, PWSTR(path.as_mut_ptr()), &mut path_len);
+        let _ = CloseHandle(process);
+        queried.ok()?;
+
+        let path = String::from_utf16_lossy(&path[..path_len as usize]);
+        let name = std::path::Path::new(&path)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.clone());
+        Some(ForegroundApp {
+            name,
+            title: String::from_utf16_lossy(&title[..title_len]),
+        })
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn probe() -> Option<ForegroundApp> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_any() {
+        let app = ForegroundApp {
+            name: "firefox".to_string(),
+            title: "My Bank - Accounts — Mozilla Firefox".to_string(),
+        };
+        assert!(app.matches_any("KeePassXC, my bank".split(',')));
+        assert!(app.matches_any(["Firefox"]));
+        assert!(!app.matches_any("keepassxc, ,".split(',')));
+        assert!(!app.matches_any([]));
+    }
+}
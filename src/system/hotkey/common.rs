@@ -1,12 +1,24 @@
 //! Shared hotkey implementation code for platforms that support global hotkeys
 
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::mpsc;
 use global_hotkey::{
     hotkey::{Code, HotKey, Modifiers},
-    GlobalHotKeyManager, GlobalHotKeyEvent,
+    GlobalHotKeyManager, GlobalHotKeyEvent, HotKeyState,
 };
 use tracing::{info, warn};
 
+use super::HotkeyAction;
+
+/// Id of the registered edit-first (Shift) variant of the hotkey, 0 if none.
+static EDIT_HOTKEY_ID: AtomicU32 = AtomicU32::new(0);
+
+/// Id of the registered clipboard image (Alt) variant of the hotkey, 0 if none.
+static CLIPBOARD_IMAGE_HOTKEY_ID: AtomicU32 = AtomicU32::new(0);
+
+/// Id of the registered panic stop hotkey, 0 if none.
+static PANIC_STOP_HOTKEY_ID: AtomicU32 = AtomicU32::new(0);
+
 /// Hotkey configuration
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct HotkeyConfig {
@@ -28,12 +40,47 @@ impl Default for HotkeyConfig {
     }
 }
 
+impl HotkeyConfig {
+    /// Fixed combination of the panic stop hotkey: Ctrl + Alt + Period.
+    pub fn panic_stop() -> Self {
+        Self {
+            modifiers: Modifiers::CONTROL | Modifiers::ALT,
+            key: Code::Period,
+        }
+    }
+
+    /// The hotkey with Shift added, to edit the text before reading it, or
+    /// `None` if the hotkey already uses Shift.
+    pub fn edit_first_variant(&self) -> Option<Self> {
+        self.with(Modifiers::SHIFT)
+    }
+
+    /// The hotkey with Alt added, to read the text of the image on the
+    /// clipboard, or `None` if the hotkey already uses Alt.
+    pub fn clipboard_image_variant(&self) -> Option<Self> {
+        self.with(Modifiers::ALT)
+    }
+
+    fn with(&self, modifier: Modifiers) -> Option<Self> {
+        (!self.modifiers.contains(modifier)).then(|| Self {
+            modifiers: self.modifiers | modifier,
+            key: self.key,
+        })
+    }
+}
+
 /// Global hotkey manager
 pub struct HotkeyManager {
     manager: GlobalHotKeyManager,
-    receiver: mpsc::Receiver<()>,
-    _sender: mpsc::Sender<()>,
+    receiver: mpsc::Receiver<HotkeyAction>,
+    _sender: mpsc::Sender<HotkeyAction>,
     current_hotkey: Option<HotKey>,
+    edit_hotkey: Option<HotKey>,
+    clipboard_image_hotkey: Option<HotKey>,
+    panic_stop_hotkey: Option<HotKey>,
+    /// Whether the variants of the hotkey are registered with it
+    edit_first_enabled: bool,
+    clipboard_image_enabled: bool,
 }
 
 impl HotkeyManager {
@@ -47,8 +94,28 @@ impl HotkeyManager {
         // Set up event handler for hotkey presses
         GlobalHotKeyEvent::set_event_handler(Some({
             let sender = sender.clone();
-            move |_event: GlobalHotKeyEvent| {
-                let _ = sender.send(());
+            move |event: GlobalHotKeyEvent| {
+                // Silence the audio right here rather than in update(), which
+                // may be busy; the UI catches up from the action
+                if event.id == PANIC_STOP_HOTKEY_ID.load(Ordering::Relaxed) {
+                    if event.state == HotKeyState::Pressed {
+                        crate::providers::panic_stop();
+                        if sender.send(HotkeyAction::PanicStop).is_ok() {
+                            crate::system::wake(crate::system::WakeSource::Hotkey);
+                        }
+                    }
+                    return;
+                }
+                let action = if event.id == EDIT_HOTKEY_ID.load(Ordering::Relaxed) {
+                    HotkeyAction::EditFirst
+                } else if event.id == CLIPBOARD_IMAGE_HOTKEY_ID.load(Ordering::Relaxed) {
+                    HotkeyAction::ReadClipboardImage
+                } else {
+                    HotkeyAction::Read
+                };
+                if sender.send(action).is_ok() {
+                    crate::system::wake(crate::system::WakeSource::Hotkey);
+                }
             }
         }));
         
@@ -59,10 +126,36 @@ impl HotkeyManager {
             receiver,
             _sender: sender,
             current_hotkey: None,
+            edit_hotkey: None,
+            clipboard_image_hotkey: None,
+            panic_stop_hotkey: None,
+            edit_first_enabled: false,
+            clipboard_image_enabled: false,
         })
     }
     
+    /// Choose which variants are registered with the hotkey (see
+    /// [`HotkeyManager::register`]), re-registering them if a hotkey is
+    /// registered already.
+    pub fn set_variants(&mut self, edit_first: bool, clipboard_image: bool) {
+        self.edit_first_enabled = edit_first;
+        self.clipboard_image_enabled = clipboard_image;
+        self.unregister_variants();
+        if let Some(hotkey) = self.current_hotkey {
+            self.register_variants(&HotkeyConfig {
+                modifiers: hotkey.mods,
+                key: hotkey.key,
+            });
+        }
+    }
+
     /// Register a hotkey with the given configuration
+    ///
+    /// If turned on with [`HotkeyManager::set_variants`], the same hotkey with
+    /// Shift added is registered too, to edit the text before reading it, and
+    /// with Alt added, to read the text of the image on the clipboard. These
+    /// variants are optional: failing to register them (e.g. another app owns
+    /// them) is only logged.
     pub fn register(&mut self, config: HotkeyConfig) -> Result<(), Box<dyn std::error::Error>> {
         // Unregister existing hotkey if any
         if let Some(ref hotkey) = self.current_hotkey {
@@ -70,6 +163,7 @@ impl HotkeyManager {
                 warn!(error = %e, "Failed to unregister previous hotkey");
             }
         }
+        self.unregister_variants();
         
         let hotkey = HotKey::new(Some(config.modifiers), config.key);
         
@@ -78,11 +172,78 @@ impl HotkeyManager {
         
         self.current_hotkey = Some(hotkey);
         info!(?config, "Hotkey registered successfully");
+        self.register_variants(&config);
         Ok(())
     }
+
+    fn register_variants(&mut self, config: &HotkeyConfig) {
+        if let Some(variant) = config.edit_first_variant().filter(|_| self.edit_first_enabled) {
+            let edit_hotkey = HotKey::new(Some(variant.modifiers), variant.key);
+            match self.manager.register(edit_hotkey) {
+                Ok(()) => {
+                    EDIT_HOTKEY_ID.store(edit_hotkey.id(), Ordering::Relaxed);
+                    self.edit_hotkey = Some(edit_hotkey);
+                }
+                Err(e) => warn!(error = %e, "Failed to register edit-first hotkey"),
+            }
+        }
+        if let Some(variant) = config.clipboard_image_variant().filter(|_| self.clipboard_image_enabled) {
+            let image_hotkey = HotKey::new(Some(variant.modifiers), variant.key);
+            match self.manager.register(image_hotkey) {
+                Ok(()) => {
+                    CLIPBOARD_IMAGE_HOTKEY_ID.store(image_hotkey.id(), Ordering::Relaxed);
+                    self.clipboard_image_hotkey = Some(image_hotkey);
+                }
+                Err(e) => warn!(error = %e, "Failed to register clipboard image hotkey"),
+            }
+        }
+    }
+
+    fn unregister_variants(&mut self) {
+        if let Some(hotkey) = self.edit_hotkey.take() {
+            EDIT_HOTKEY_ID.store(0, Ordering::Relaxed);
+            if let Err(e) = self.manager.unregister(hotkey) {
+                warn!(error = %e, "Failed to unregister edit-first hotkey");
+            }
+        }
+        if let Some(hotkey) = self.clipboard_image_hotkey.take() {
+            CLIPBOARD_IMAGE_HOTKEY_ID.store(0, Ordering::Relaxed);
+            if let Err(e) = self.manager.unregister(hotkey) {
+                warn!(error = %e, "Failed to unregister clipboard image hotkey");
+            }
+        }
+    }
     
+    /// Register the panic stop hotkey, kept apart from the reading hotkey so
+    /// it stays registered when that one is turned off or changed.
+    pub fn register_panic_stop(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        if self.panic_stop_hotkey.is_some() {
+            return Ok(());
+        }
+        let config = HotkeyConfig::panic_stop();
+        let hotkey = HotKey::new(Some(config.modifiers), config.key);
+        self.manager.register(hotkey)
+            .map_err(|e| format!("Failed to register panic stop hotkey: {e}"))?;
+        PANIC_STOP_HOTKEY_ID.store(hotkey.id(), Ordering::Relaxed);
+        self.panic_stop_hotkey = Some(hotkey);
+        info!(?config, "Panic stop hotkey registered");
+        Ok(())
+    }
+
+    /// Unregister the panic stop hotkey
+    pub fn unregister_panic_stop(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(hotkey) = self.panic_stop_hotkey.take() {
+            PANIC_STOP_HOTKEY_ID.store(0, Ordering::Relaxed);
+            self.manager.unregister(hotkey)
+                .map_err(|e| format!("Failed to unregister panic stop hotkey: {e}"))?;
+            info!("Panic stop hotkey unregistered");
+        }
+        Ok(())
+    }
+
     /// Unregister the current hotkey
     pub fn unregister(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        self.unregister_variants();
         if let Some(ref hotkey) = self.current_hotkey {
             self.manager.unregister(*hotkey)
                 .map_err(|e| format!("Failed to unregister hotkey: {e}"))?;
@@ -93,7 +254,7 @@ impl HotkeyManager {
     }
     
     /// Try to receive a hotkey press event (non-blocking)
-    pub fn try_recv(&self) -> Option<()> {
+    pub fn try_recv(&self) -> Option<HotkeyAction> {
         self.receiver.try_recv().ok()
     }
 }
@@ -105,6 +266,7 @@ pub(crate) fn format_key_code(code: Code) -> String {
         Code::KeyT => "T".to_string(),
         Code::KeyS => "S".to_string(),
         Code::Space => "Space".to_string(),
+        Code::Period => ".".to_string(),
         _ => {
             let debug_str = format!("{:?}", code);
             let stripped = debug_str.strip_prefix("Key").unwrap_or(&debug_str);
@@ -1,5 +1,18 @@
 //! Global hotkey management for triggering reading actions
 
+/// What a hotkey press asks for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HotkeyAction {
+    /// Read the selected text
+    Read,
+    /// Open the selected text in the editor first (hotkey pressed with Shift)
+    EditFirst,
+    /// Read the text of the image on the clipboard (hotkey pressed with Alt)
+    ReadClipboardImage,
+    /// Stop reading; the audio was already silenced when the key was pressed
+    PanicStop,
+}
+
 // Shared implementation for platforms that support global hotkeys
 #[cfg(any(target_os = "macos", target_os = "windows"))]
 mod common;
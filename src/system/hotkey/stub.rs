@@ -3,6 +3,8 @@
 use global_hotkey::hotkey::{Code, Modifiers};
 use tracing::warn;
 
+use super::HotkeyAction;
+
 /// Hotkey configuration
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct HotkeyConfig {
@@ -19,6 +21,35 @@ impl Default for HotkeyConfig {
     }
 }
 
+impl HotkeyConfig {
+    /// Fixed combination of the panic stop hotkey: Ctrl + Alt + Period.
+    pub fn panic_stop() -> Self {
+        Self {
+            modifiers: Modifiers::CONTROL | Modifiers::ALT,
+            key: Code::Period,
+        }
+    }
+
+    /// The hotkey with Shift added, to edit the text before reading it, or
+    /// `None` if the hotkey already uses Shift.
+    pub fn edit_first_variant(&self) -> Option<Self> {
+        self.with(Modifiers::SHIFT)
+    }
+
+    /// The hotkey with Alt added, to read the text of the image on the
+    /// clipboard, or `None` if the hotkey already uses Alt.
+    pub fn clipboard_image_variant(&self) -> Option<Self> {
+        self.with(Modifiers::ALT)
+    }
+
+    fn with(&self, modifier: Modifiers) -> Option<Self> {
+        (!self.modifiers.contains(modifier)).then(|| Self {
+            modifiers: self.modifiers | modifier,
+            key: self.key,
+        })
+    }
+}
+
 /// Global hotkey manager (stub)
 pub struct HotkeyManager {
     enabled: bool,
@@ -33,6 +64,9 @@ impl HotkeyManager {
         })
     }
     
+    /// Choose which variants are registered with the hotkey (stub)
+    pub fn set_variants(&mut self, _edit_first: bool, _clipboard_image: bool) {}
+
     /// Register a hotkey (stub)
     pub fn register(&mut self, _config: HotkeyConfig) -> Result<(), Box<dyn std::error::Error>> {
         warn!("Global hotkeys not supported on this platform");
@@ -44,19 +78,32 @@ impl HotkeyManager {
         Ok(())
     }
     
+    /// Register the panic stop hotkey (stub)
+    pub fn register_panic_stop(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        Ok(())
+    }
+
+    /// Unregister the panic stop hotkey (stub)
+    pub fn unregister_panic_stop(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        Ok(())
+    }
+
     /// Check if hotkey is currently enabled
     pub fn is_enabled(&self) -> bool {
         false
     }
     
     /// Try to receive a hotkey press event (stub)
-    pub fn try_recv(&self) -> Option<()> {
+    pub fn try_recv(&self) -> Option<HotkeyAction> {
         None
     }
 }
 
 /// Format key code as a display string (stub)
 pub fn format_key_code(code: Code) -> String {
+    if code == Code::Period {
+        return ".".to_string();
+    }
     let debug_str = format!("{:?}", code);
     debug_str
         .strip_prefix("Key")
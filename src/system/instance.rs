@@ -0,0 +1,207 @@
+//! Hand text and files to the running app from other processes.
+//!
+//! The app listens on a loopback port and writes the port, with a random
+//! token, to `instance.json` in its data folder. The `read` command and the
+//! context menu entries it backs send one JSON request per connection, which
+//! is answered with `ok` once the app has taken it:
+//!
+//! ```text
+//! -> {"token":"5f3a...","reading":{"text":"Selected text"}}
+//! <- ok
+//! ```
+//!
+//! When no instance answers, the app is started (without reading the current
+//! selection) and the request is sent again once it listens.
+
+use std::hash::{BuildHasher, Hasher};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+/// File holding the port and token of the running instance.
+const INSTANCE_FILE: &str = "instance.json";
+
+/// Set when the app is started to receive a forwarded reading, so it does not
+/// also read the current selection.
+const FORWARDED_LAUNCH_ENV: &str = "INSIGHT_READER_FORWARDED";
+
+/// How long to wait for a started app to listen.
+const LAUNCH_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// How long the running app has to answer a request.
+const ANSWER_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// What another process asks the running app to read.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ForwardedReading {
+    /// Text selected in another app
+    Text(String),
+    /// Documents chosen in a file manager, read one after the other
+    Files(Vec<PathBuf>),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Request {
+    token: String,
+    reading: ForwardedReading,
+}
+
+/// Where the running instance listens.
+#[derive(Debug, Serialize, Deserialize)]
+struct InstanceInfo {
+    port: u16,
+    token: String,
+}
+
+fn instance_file() -> PathBuf {
+    crate::sync::app_data_dir().join(INSTANCE_FILE)
+}
+
+/// Whether this process was started to receive a forwarded reading.
+pub fn launched_for_forwarding() -> bool {
+    std::env::var_os(FORWARDED_LAUNCH_ENV).is_some()
+}
+
+/// Listen for readings sent from other processes and pass each one to
+/// `on_reading`, which returns false to stop. Blocks the calling thread.
+pub fn serve_forwarded_readings(on_reading: impl Fn(ForwardedReading) -> bool) -> Result<(), String> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+        .map_err(|e| format!("Failed to listen for other processes: {e}"))?;
+    let port = listener.local_addr().map_err(|e| e.to_string())?.port();
+    let token = random_token();
+    write_instance_file(&InstanceInfo { port, token: token.clone() })?;
+    info!(port, "Listening for readings from other apps");
+
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                warn!(error = %e, "Failed to accept forwarded reading");
+                continue;
+            }
+        };
+        match receive(stream, &token) {
+            Ok(reading) => {
+                debug!(?reading, "Reading forwarded from another process");
+                if !on_reading(reading) {
+                    break;
+                }
+            }
+            Err(e) => warn!(error = %e, "Rejected forwarded reading"),
+        }
+    }
+    Ok(())
+}
+
+/// Read one request from `stream`, answering `ok` if it carries `token`.
+fn receive(mut stream: TcpStream, token: &str) -> Result<ForwardedReading, String> {
+    stream.set_read_timeout(Some(ANSWER_TIMEOUT)).map_err(|e| e.to_string())?;
+    let mut line = String::new();
+    BufReader::new(&stream)
+        .read_line(&mut line)
+        .map_err(|e| format!("Failed to read request: {e}"))?;
+    let request: Request = serde_json::from_str(&line).map_err(|e| format!("Invalid request: {e}"))?;
+    if request.token != token {
+        let _ = writeln!(stream, "error: wrong token");
+        return Err("wrong token".to_string());
+    }
+    let _ = writeln!(stream, "ok");
+    Ok(request.reading)
+}
+
+/// Send `reading` to the running app, starting it first if none answers.
+pub fn forward_reading(reading: ForwardedReading) -> Result<(), String> {
+    if send(&reading).is_ok() {
+        return Ok(());
+    }
+    info!("No running instance answered, starting the app");
+    let exe = std::env::current_exe().map_err(|e| format!("Failed to find the app: {e}"))?;
+    let stale = std::fs::metadata(instance_file()).and_then(|m| m.modified()).ok();
+    std::process::Command::new(exe)
+        .env(FORWARDED_LAUNCH_ENV, "1")
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Failed to start the app: {e}"))?;
+
+    let started = Instant::now();
+    while started.elapsed() < LAUNCH_TIMEOUT {
+        std::thread::sleep(Duration::from_millis(200));
+        // Wait for the new instance to replace the file a previous one left behind
+        let modified = std::fs::metadata(instance_file()).and_then(|m| m.modified()).ok();
+        if modified.is_none() || modified == stale {
+            continue;
+        }
+        if send(&reading).is_ok() {
+            return Ok(());
+        }
+    }
+    Err("Insight Reader did not start in time".to_string())
+}
+
+/// Send `reading` to the instance named in the instance file.
+fn send(reading: &ForwardedReading) -> Result<(), String> {
+    let json = std::fs::read_to_string(instance_file()).map_err(|e| format!("No running instance: {e}"))?;
+    let info: InstanceInfo = serde_json::from_str(&json).map_err(|e| format!("Invalid instance file: {e}"))?;
+    let mut stream = TcpStream::connect((Ipv4Addr::LOCALHOST, info.port))
+        .map_err(|e| format!("No running instance: {e}"))?;
+    stream.set_read_timeout(Some(ANSWER_TIMEOUT)).map_err(|e| e.to_string())?;
+    let request = Request { token: info.token, reading: reading.clone() };
+    let json = serde_json::to_string(&request).map_err(|e| e.to_string())?;
+    writeln!(stream, "{json}").map_err(|e| format!("Failed to send reading: {e}"))?;
+    let mut answer = String::new();
+    BufReader::new(&stream)
+        .read_line(&mut answer)
+        .map_err(|e| format!("No answer from the running instance: {e}"))?;
+    match answer.trim() {
+        "ok" => Ok(()),
+        other => Err(format!("Running instance refused the reading ({other})")),
+    }
+}
+
+fn write_instance_file(info: &InstanceInfo) -> Result<(), String> {
+    let path = instance_file();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {e}", dir.display()))?;
+    }
+    let json = serde_json::to_string(info).map_err(|e| e.to_string())?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write {}: {e}", path.display()))?;
+    // Only the user may read the token
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600));
+    }
+    Ok(())
+}
+
+/// A token other users cannot guess, from the randomly seeded std hasher.
+fn random_token() -> String {
+    (0..2)
+        .map(|_| format!("{:016x}", std::collections::hash_map::RandomState::new().build_hasher().finish()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_format() {
+        let request = Request {
+            token: "abc".to_string(),
+            reading: ForwardedReading::Files(vec![PathBuf::from("/tmp/notes.md")]),
+        };
+        let json = serde_json::to_string(&request).unwrap();
+        assert_eq!(json, r#"{"token":"abc","reading":{"files":["/tmp/notes.md"]}}"#);
+        let parsed: Request = serde_json::from_str(r#"{"token":"abc","reading":{"text":"Hello"}}"#).unwrap();
+        assert_eq!(parsed.reading, ForwardedReading::Text("Hello".to_string()));
+        assert_eq!(random_token().len(), 32);
+    }
+}
@@ -0,0 +1,106 @@
+//! The language of the user interface (system locale)
+
+use std::sync::OnceLock;
+
+/// Language of the user's locale as an ISO 639-1 code ("de"), with the
+/// script for Chinese ("zh-Hans", "zh-Hant").
+pub fn ui_language() -> Option<String> {
+    language_from_locale(&locale_name()?)
+}
+
+/// Language and region of the user's locale in voice code form ("pt_BR"),
+/// or only the language when the locale names no region. Looked up once.
+pub fn ui_locale() -> Option<&'static str> {
+    static LOCALE: OnceLock<Option<String>> = OnceLock::new();
+    LOCALE.get_or_init(|| locale_code(&locale_name()?)).as_deref()
+}
+
+fn locale_name() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.is_empty())
+        .or_else(system_locale)
+}
+
+/// The locale set in the system preferences, for apps started without `LANG`.
+#[cfg(target_os = "macos")]
+fn system_locale() -> Option<String> {
+    let output = std::process::Command::new("defaults")
+        .args(["read", "-g", "AppleLocale"])
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(target_os = "windows")]
+fn system_locale() -> Option<String> {
+    use windows::Win32::Globalization::GetUserDefaultLocaleName;
+
+    let mut name = [0u16; 85];
+    // SAFETY: the buffer outlives the call and its length is passed along
+    let len = unsafe { GetUserDefaultLocaleName(&mut name) };
+    // The length includes the terminating null
+    (len > 1).then(|| String::from_utf16_lossy(&name[..len as usize - 1]))
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn system_locale() -> Option<String> {
+    None
+}
+
+/// Language of a POSIX ("pt_BR.UTF-8") or BCP 47 ("zh-Hant-TW") locale name.
+fn language_from_locale(locale: &str) -> Option<String> {
+    let locale = locale.split(['.', '@']).next()?;
+    let mut parts = locale.split(['_', '-']);
+    let language = parts.next()?.to_ascii_lowercase();
+    if !(2..=3).contains(&language.len()) || !language.chars().all(|c| c.is_ascii_alphabetic()) {
+        // "C", "POSIX" and the like
+        return None;
+    }
+    if language != "zh" {
+        return Some(language);
+    }
+    let traditional = parts.any(|part| ["hant", "tw", "hk", "mo"].contains(&part.to_ascii_lowercase().as_str()));
+    Some(if traditional { "zh-Hant" } else { "zh-Hans" }.to_string())
+}
+
+/// Voice-style code ("pt_BR") of a POSIX ("pt_BR.UTF-8") or BCP 47
+/// ("sr-Latn-RS") locale name.
+fn locale_code(locale: &str) -> Option<String> {
+    let language = language_from_locale(locale)?;
+    let language = language.split('-').next()?;
+    let region = locale
+        .split(['.', '@'])
+        .next()?
+        .split(['_', '-'])
+        .skip(1)
+        .find(|part| part.len() == 2 && part.chars().all(|c| c.is_ascii_alphabetic()));
+    Some(match region {
+        Some(region) => format!("{language}_{}", region.to_ascii_uppercase()),
+        None => language.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_language_from_locale() {
+        assert_eq!(language_from_locale("pt_BR.UTF-8").as_deref(), Some("pt"));
+        assert_eq!(language_from_locale("de-DE").as_deref(), Some("de"));
+        assert_eq!(language_from_locale("zh_TW.UTF-8").as_deref(), Some("zh-Hant"));
+        assert_eq!(language_from_locale("zh-Hans-CN").as_deref(), Some("zh-Hans"));
+        assert_eq!(language_from_locale("sr_RS@latin").as_deref(), Some("sr"));
+        assert_eq!(language_from_locale("C.UTF-8"), None);
+        assert_eq!(language_from_locale("POSIX"), None);
+        assert_eq!(locale_code("pt_BR.UTF-8").as_deref(), Some("pt_BR"));
+        assert_eq!(locale_code("sr-Latn-rs").as_deref(), Some("sr_RS"));
+        assert_eq!(locale_code("zh-Hant-TW").as_deref(), Some("zh_TW"));
+        assert_eq!(locale_code("de").as_deref(), Some("de"));
+    }
+}
@@ -0,0 +1,136 @@
+//! Detect whether another application is recording from the microphone.
+//!
+//! Used to hold back readings triggered by the hotkey while the user is in a
+//! call or meeting. Each platform has its own probe:
+//! - Linux: active (uncorked) PulseAudio/PipeWire recording streams of other processes
+//! - macOS: whether the default input device is running in any process (CoreAudio)
+//! - Windows: microphone consent entries that are in use (no stop time yet)
+//!
+//! Probes return false when they cannot tell, so readings are never held back
+//! by a broken probe.
+
+#[cfg(any(target_os = "linux", target_os = "windows"))]
+use std::process::Command;
+
+use tracing::debug;
+
+/// Whether the microphone is in use by another application.
+pub fn microphone_in_use() -> bool {
+    let in_use = probe();
+    debug!(in_use, "Microphone activity probed");
+    in_use
+}
+
+#[cfg(target_os = "linux")]
+fn probe() -> bool {
+    let Ok(output) = Command::new("pactl").args(["list", "source-outputs"]).output() else {
+        return false;
+    };
+    active_recordings(&String::from_utf8_lossy(&output.stdout), std::process::id()) > 0
+}
+
+/// Count recording streams in `pactl list source-outputs` output that are
+/// running (not corked) and do not belong to process `own_pid`.
+#[cfg(any(target_os = "linux", test))]
+fn active_recordings(list: &str, own_pid: u32) -> usize {
+    let own_pid = format!("application.process.id = \"{own_pid}\"");
+    list.split("Source Output #")
+        .skip(1)
+        .filter(|block| !block.lines().any(|line| line.trim() == "Corked: yes"))
+        .filter(|block| !block.lines().any(|line| line.trim() == own_pid))
+        .count()
+}
+
+#[cfg(target_os = "macos")]
+fn probe() -> bool {
+    use std::ffi::c_void;
+
+    #[repr(C)]
+    struct AudioObjectPropertyAddress {
+        selector: u32,
+        scope: u32,
+        element: u32,
+    }
+
+    #[link(name = "CoreAudio", kind = "framework")]
+    extern "C" {
+        fn AudioObjectGetPropertyData(
+            object_id: u32,
+            address: *const AudioObjectPropertyAddress,
+            qualifier_size: u32,
+            qualifier: *const c_void,
+            data_size: *mut u32,
+            data: *mut c_void,
+        ) -> i32;
+    }
+
+    const SYSTEM_OBJECT: u32 = 1;
+    const SCOPE_GLOBAL: u32 = u32::from_be_bytes(*b"glob");
+    const DEFAULT_INPUT_DEVICE: u32 = u32::from_be_bytes(*b"dIn ");
+    const IS_RUNNING_SOMEWHERE: u32 = u32::from_be_bytes(*b"gone");
+
+    /// Read a u32 property of an audio object.
+    fn get_u32(object_id: u32, selector: u32) -> Option<u32> {
+        let address = AudioObjectPropertyAddress {
+            selector,
+            scope: SCOPE_GLOBAL,
+            element: 0,
+        };
+        let mut value: u32 = 0;
+        let mut size = std::mem::size_of::<u32>() as u32;
+        // SAFETY: `address` and `value` outlive the call, and `size` matches the buffer
+        let status = unsafe {
+            AudioObjectGetPropertyData(
+                object_id,
+                &address,
+                0,
+                std::ptr::null(),
+                &mut size,
+                &mut value as *mut u32 as *mut c_void,
+            )
+        };
+        (status == 0).then_some(value)
+    }
+
+    get_u32(SYSTEM_OBJECT, DEFAULT_INPUT_DEVICE)
+        .filter(|&device| device != 0)
+        .and_then(|device| get_u32(device, IS_RUNNING_SOMEWHERE))
+        .is_some_and(|running| running != 0)
+}
+
+#[cfg(target_os = "windows")]
+fn probe() -> bool {
+    use std::os::windows::process::CommandExt;
+    const CONSENT_STORE: &str =
+        r"HKCU\Software\Microsoft\Windows\CurrentVersion\CapabilityAccessManager\ConsentStore\microphone";
+
+    let Ok(output) = Command::new("reg")
+        .args(["query", CONSENT_STORE, "/s", "/v", "LastUsedTimeStop"])
+        .creation_flags(super::CREATE_NO_WINDOW)
+        .output()
+    else {
+        return false;
+    };
+    // Apps using the microphone right now have a start time but no stop time
+    String::from_utf8_lossy(&output.stdout).lines().any(|line| {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        fields.as_slice() == ["LastUsedTimeStop", "REG_QWORD", "0x0"]
+    })
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn probe() -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_active_recordings() {
+        let list = "Source Output #12\n\tCorked: no\n\tProperties:\n\t\tapplication.process.id = \"4242\"\n\nSource Output #13\n\tCorked: yes\n\tProperties:\n\t\tapplication.process.id = \"5000\"\n\nSource Output #14\n\tCorked: no\n\tProperties:\n\t\tapplication.process.id = \"77\"\n";
+        assert_eq!(active_recordings(list, 77), 1);
+        assert_eq!(active_recordings("", 77), 0);
+    }
+}
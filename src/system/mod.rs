@@ -1,16 +1,59 @@
 //! System interactions (clipboard, external commands, etc.)
 
 mod clipboard;
+mod encode;
+mod encrypt;
+mod feed;
+mod foreground;
+mod locale;
 mod text_cleanup;
 mod screenshot;
 mod tray;
 mod hotkey;
+mod instance;
+mod microphone;
+mod power;
+mod process;
+mod recording;
+mod stt;
+mod tagging;
+mod typing;
+mod wake;
+mod watch;
+mod web;
 
-pub use clipboard::{get_selected_text, copy_to_clipboard};
+/// Process creation flag that keeps console programs (Piper, ffmpeg,
+/// PowerShell) from opening a console window on Windows.
+#[cfg(target_os = "windows")]
+pub(crate) const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+pub use clipboard::{get_clipboard_image, get_clipboard_image_png, get_clipboard_text, get_selected_content, get_selected_text, copy_to_clipboard, SelectedContent};
+pub use encode::{
+    default_export_dir, encode_m4b, encode_mp3, encode_mp3_chapters, export_audio, find_ffmpeg, find_on_path, sanitize_file_name, AudioFormat,
+    ChapterMarker,
+};
+pub use encrypt::{encrypt_bytes, encrypt_file, find_age, parse_recipients, AGE_EXTENSION};
 pub use text_cleanup::cleanup_text;
-pub use screenshot::{capture_region, extract_text_from_image};
+pub use screenshot::{
+    capture_region, capture_region_in_memory, encode_png, extract_text_lines, extract_text_lines_from_png, lines_text, shred, OcrLine,
+    Screenshot,
+};
 pub use tray::{SystemTray, TrayEvent};
-pub use hotkey::{HotkeyManager, HotkeyConfig, format_hotkey_display};
+pub use hotkey::{HotkeyAction, HotkeyManager, HotkeyConfig, format_hotkey_display};
+pub use foreground::{foreground_app, ForegroundApp};
+pub use instance::{forward_reading, launched_for_forwarding, serve_forwarded_readings, ForwardedReading};
+pub use locale::{ui_language, ui_locale};
+pub use microphone::microphone_in_use;
+pub use power::{power_source, PowerPolicy, PowerSource};
+pub use process::{current_synthesis_limits, ProcessPriority, SynthesisLimits};
+pub use recording::{resample, trim_silence, waveform_envelope, Recorder};
+pub use stt::{clean_transcript, transcribe, WHISPER_SAMPLE_RATE};
+pub use tagging::AudioTags;
+pub use typing::{time_since_typing, typing_detection_supported};
+pub use feed::{add_to_feed, refresh_feed, FeedConfig, FeedServer};
+pub use wake::{set_wake_listener, wake, WakeSource};
+pub use watch::{watch_folder, FolderWatcher};
+pub use web::fetch_page_text;
 
 /// Check if running on Wayland with Hyprland compositor
 #[cfg(target_os = "linux")]
@@ -0,0 +1,145 @@
+//! Power source detection, to go easy on laptop batteries while synthesizing.
+//!
+//! - Linux: the `Mains` and `Battery` supplies under `/sys/class/power_supply`
+//! - macOS: the power source line of `pmset -g batt`
+//! - Windows: `GetSystemPowerStatus`
+//!
+//! Under a power policy other than performance, readings on battery are
+//! limited to fewer cores (the efficiency cores on macOS) and, in battery
+//! saver, use the fastest installed variant of the selected voice.
+
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+use tracing::warn;
+
+use super::process::SynthesisLimits;
+
+/// Where the machine draws power from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PowerSource {
+    Ac,
+    Battery,
+    /// Desktops without a battery, and platforms that cannot tell
+    Unknown,
+}
+
+/// How synthesis adapts to running on battery.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PowerPolicy {
+    /// Always use every core and the selected voice
+    Performance,
+    /// On battery, use half the cores
+    #[default]
+    Balanced,
+    /// On battery, use a quarter of the cores and the fastest variant of the voice
+    BatterySaver,
+}
+
+/// Limits to apply under `policy` when drawing power from `source`, on a
+/// machine with `cores` cores.
+pub fn synthesis_limits(policy: PowerPolicy, source: PowerSource, cores: usize) -> SynthesisLimits {
+    if source != PowerSource::Battery {
+        return SynthesisLimits::default();
+    }
+    match policy {
+        PowerPolicy::Performance => SynthesisLimits::default(),
+        PowerPolicy::Balanced => SynthesisLimits {
+            max_cores: Some((cores / 2).max(1)),
+            ..SynthesisLimits::default()
+        },
+        PowerPolicy::BatterySaver => SynthesisLimits {
+            max_cores: Some((cores / 4).max(1)),
+            prefer_faster_voice: true,
+            ..SynthesisLimits::default()
+        },
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub fn power_source() -> PowerSource {
+    let read = |path: std::path::PathBuf| std::fs::read_to_string(path).map(|s| s.trim().to_string()).unwrap_or_default();
+    let Ok(supplies) = std::fs::read_dir("/sys/class/power_supply") else {
+        return PowerSource::Unknown;
+    };
+    let mut has_battery = false;
+    for supply in supplies.filter_map(|entry| entry.ok()).map(|entry| entry.path()) {
+        match read(supply.join("type")).as_str() {
+            "Mains" | "USB" if read(supply.join("online")) == "1" => return PowerSource::Ac,
+            "Battery" if read(supply.join("scope")) != "Device" => has_battery = true,
+            _ => {}
+        }
+    }
+    if has_battery {
+        PowerSource::Battery
+    } else {
+        PowerSource::Unknown
+    }
+}
+
+#[cfg(target_os = "macos")]
+pub fn power_source() -> PowerSource {
+    match std::process::Command::new("pmset").args(["-g", "batt"]).output() {
+        Ok(output) => parse_pmset(&String::from_utf8_lossy(&output.stdout)),
+        Err(e) => {
+            warn!(error = %e, "Failed to run pmset");
+            PowerSource::Unknown
+        }
+    }
+}
+
+/// Power source from the first line of `pmset -g batt`:
+/// `Now drawing from 'Battery Power'`.
+#[cfg(any(target_os = "macos", test))]
+fn parse_pmset(output: &str) -> PowerSource {
+    let first_line = output.lines().next().unwrap_or_default();
+    if first_line.contains("'Battery Power'") {
+        PowerSource::Battery
+    } else if first_line.contains("'AC Power'") {
+        PowerSource::Ac
+    } else {
+        PowerSource::Unknown
+    }
+}
+
+#[cfg(target_os = "windows")]
+pub fn power_source() -> PowerSource {
+    use windows::Win32::System::Power::{GetSystemPowerStatus, SYSTEM_POWER_STATUS};
+
+    /// No system battery, in `BatteryFlag`
+    const NO_BATTERY: u8 = 128;
+
+    let mut status = SYSTEM_POWER_STATUS::default();
+    if let Err(e) = unsafe { GetSystemPowerStatus(&mut status) } {
+        warn!(error = %e, "Failed to get the power status");
+        return PowerSource::Unknown;
+    }
+    match status.ACLineStatus {
+        1 => PowerSource::Ac,
+        0 if status.BatteryFlag != NO_BATTERY => PowerSource::Battery,
+        _ => PowerSource::Unknown,
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+pub fn power_source() -> PowerSource {
+    PowerSource::Unknown
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_synthesis_limits() {
+        assert_eq!(synthesis_limits(PowerPolicy::BatterySaver, PowerSource::Ac, 8), SynthesisLimits::default());
+        assert_eq!(synthesis_limits(PowerPolicy::Performance, PowerSource::Battery, 8), SynthesisLimits::default());
+        assert_eq!(synthesis_limits(PowerPolicy::Balanced, PowerSource::Battery, 8).max_cores, Some(4));
+        let saver = synthesis_limits(PowerPolicy::BatterySaver, PowerSource::Battery, 2);
+        assert_eq!(saver.max_cores, Some(1));
+        assert!(saver.prefer_faster_voice);
+        assert_eq!(
+            parse_pmset("Now drawing from 'Battery Power'\n -InternalBattery-0 (id=1234)\t82%; discharging"),
+            PowerSource::Battery
+        );
+        assert_eq!(parse_pmset("Now drawing from 'AC Power'\n"), PowerSource::Ac);
+    }
+}
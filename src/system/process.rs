@@ -0,0 +1,178 @@
+//! Resource limits for the Piper processes, so narration in the background
+//! does not slow down the rest of the machine (a compile job, a game).
+//!
+//! The limits combine the user's thread and priority settings with the power
+//! policy, and are applied to each Piper process right after it starts, before
+//! it loads the voice model and starts its worker threads:
+//! - Threads: the process is kept on that many logical CPUs, so onnxruntime's
+//!   worker threads share them
+//! - Priority: a higher nice value on Linux and macOS, a lower priority class
+//!   on Windows
+
+use std::process::Child;
+
+use tracing::{debug, warn};
+
+use super::power::{power_source, synthesis_limits};
+
+/// Scheduling priority of the Piper processes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProcessPriority {
+    #[default]
+    Normal,
+    /// Gives way to interactive work
+    Low,
+    /// Only runs when nothing else wants the CPU
+    Lowest,
+}
+
+/// Limits on synthesis for the current settings and power source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SynthesisLimits {
+    /// Cores the voice model may run on, `None` for all of them
+    pub max_cores: Option<usize>,
+    /// Use the fastest installed quality of the selected voice
+    pub prefer_faster_voice: bool,
+    pub priority: ProcessPriority,
+}
+
+impl SynthesisLimits {
+    /// These limits narrowed to at most `threads` cores (0 for no limit) and
+    /// run at `priority`.
+    pub fn with_user_settings(self, threads: usize, priority: ProcessPriority) -> Self {
+        let max_cores = match (self.max_cores, threads) {
+            (cores, 0) => cores,
+            (Some(cores), threads) => Some(cores.min(threads)),
+            (None, threads) => Some(threads),
+        };
+        Self { max_cores, priority, ..self }
+    }
+
+    /// Apply the core and priority limits to a process that just started.
+    pub fn apply(&self, child: &Child) {
+        if let Some(cores) = self.max_cores {
+            limit_cpu_cores(child, cores);
+        }
+        if self.priority != ProcessPriority::Normal {
+            match set_priority(child, self.priority) {
+                Ok(()) => debug!(pid = child.id(), priority = ?self.priority, "Lowered the priority of a process"),
+                Err(e) => warn!(error = %e, pid = child.id(), "Failed to lower the priority of a process"),
+            }
+        }
+    }
+}
+
+/// Limits for the saved settings, power policy and the current power source.
+pub fn current_synthesis_limits() -> SynthesisLimits {
+    let cores = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let (threads, priority) = crate::config::load_synthesis_resources();
+    let limits = synthesis_limits(crate::config::load_power_policy(), power_source(), cores)
+        .with_user_settings(threads, priority);
+    debug!(?limits, "Synthesis limits");
+    limits
+}
+
+/// Keep a child process (and the threads it starts from now on) on its first
+/// `cores` cores. macOS does not let processes pick cores, so the process is
+/// given the background policy instead, which keeps it on the efficiency cores.
+fn limit_cpu_cores(child: &Child, cores: usize) {
+    if let Err(e) = set_affinity(child, cores) {
+        warn!(error = %e, pid = child.id(), cores, "Failed to limit the cores of a process");
+    } else {
+        debug!(pid = child.id(), cores, "Limited the cores of a process");
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn set_affinity(child: &Child, cores: usize) -> Result<(), String> {
+    let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    for core in 0..cores {
+        unsafe { libc::CPU_SET(core, &mut set) };
+    }
+    let result = unsafe { libc::sched_setaffinity(child.id() as libc::pid_t, std::mem::size_of_val(&set), &set) };
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error().to_string())
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn set_affinity(child: &Child, cores: usize) -> Result<(), String> {
+    use std::os::windows::io::AsRawHandle;
+    use windows::Win32::Foundation::HANDLE;
+    use windows::Win32::System::Threading::SetProcessAffinityMask;
+
+    let mask = if cores >= usize::BITS as usize { usize::MAX } else { (1usize << cores) - 1 };
+    unsafe { SetProcessAffinityMask(HANDLE(child.as_raw_handle()), mask) }.map_err(|e| e.to_string())
+}
+
+#[cfg(target_os = "macos")]
+fn set_affinity(child: &Child, _cores: usize) -> Result<(), String> {
+    let status = std::process::Command::new("taskpolicy")
+        .args(["-b", "-p", &child.id().to_string()])
+        .status()
+        .map_err(|e| format!("Failed to run taskpolicy: {e}"))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("taskpolicy failed ({status})"))
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn set_affinity(_child: &Child, _cores: usize) -> Result<(), String> {
+    Err("not supported on this platform".to_string())
+}
+
+#[cfg(unix)]
+fn set_priority(child: &Child, priority: ProcessPriority) -> Result<(), String> {
+    let nice = match priority {
+        ProcessPriority::Normal => 0,
+        ProcessPriority::Low => 10,
+        ProcessPriority::Lowest => 19,
+    };
+    let result = unsafe { libc::setpriority(libc::PRIO_PROCESS, child.id() as libc::id_t, nice) };
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error().to_string())
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn set_priority(child: &Child, priority: ProcessPriority) -> Result<(), String> {
+    use std::os::windows::io::AsRawHandle;
+    use windows::Win32::Foundation::HANDLE;
+    use windows::Win32::System::Threading::{
+        SetPriorityClass, BELOW_NORMAL_PRIORITY_CLASS, IDLE_PRIORITY_CLASS, NORMAL_PRIORITY_CLASS,
+    };
+
+    let class = match priority {
+        ProcessPriority::Normal => NORMAL_PRIORITY_CLASS,
+        ProcessPriority::Low => BELOW_NORMAL_PRIORITY_CLASS,
+        ProcessPriority::Lowest => IDLE_PRIORITY_CLASS,
+    };
+    unsafe { SetPriorityClass(HANDLE(child.as_raw_handle()), class) }.map_err(|e| e.to_string())
+}
+
+#[cfg(not(any(unix, target_os = "windows")))]
+fn set_priority(_child: &Child, _priority: ProcessPriority) -> Result<(), String> {
+    Err("not supported on this platform".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_user_settings() {
+        let unlimited = SynthesisLimits::default();
+        assert_eq!(unlimited.with_user_settings(0, ProcessPriority::Normal), unlimited);
+        assert_eq!(unlimited.with_user_settings(3, ProcessPriority::Low).max_cores, Some(3));
+        let on_battery = SynthesisLimits { max_cores: Some(2), ..SynthesisLimits::default() };
+        let limits = on_battery.with_user_settings(6, ProcessPriority::Lowest);
+        assert_eq!(limits.max_cores, Some(2));
+        assert_eq!(limits.priority, ProcessPriority::Lowest);
+    }
+}
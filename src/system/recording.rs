@@ -0,0 +1,238 @@
+//! Microphone recording.
+//!
+//! Captures audio from the default input device using cpal through rodio,
+//! the same audio stack used for playback. Used by dictation and the
+//! pronunciation trainer.
+
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use rodio::cpal;
+use rodio::cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use tracing::{debug, info, warn};
+
+/// Longest recording we keep in memory (anything after this is dropped).
+const MAX_RECORDING_SECONDS: usize = 300;
+
+/// Shortest recording worth keeping.
+const MIN_RECORDING_SECONDS: f32 = 0.5;
+
+/// Amplitude below which a sample is considered silence when trimming.
+const SILENCE_THRESHOLD: f32 = 0.02;
+
+/// An in-progress microphone recording.
+///
+/// The cpal stream is not `Send` on every platform, so it lives on its own
+/// thread for the lifetime of the recording.
+pub struct Recorder {
+    samples: Arc<Mutex<Vec<f32>>>,
+    stop_tx: mpsc::Sender<()>,
+    thread: thread::JoinHandle<()>,
+    sample_rate: u32,
+    channels: usize,
+}
+
+impl Recorder {
+    /// Start recording from the default input device.
+    pub fn start() -> Result<Self, String> {
+        let samples = Arc::new(Mutex::new(Vec::new()));
+        let (stop_tx, stop_rx) = mpsc::channel();
+        let (ready_tx, ready_rx) = mpsc::channel();
+
+        let thread_samples = Arc::clone(&samples);
+        let thread = thread::spawn(move || {
+            let stream = match build_input_stream(thread_samples) {
+                Ok((stream, sample_rate, channels)) => {
+                    let _ = ready_tx.send(Ok((sample_rate, channels)));
+                    stream
+                }
+                Err(e) => {
+                    let _ = ready_tx.send(Err(e));
+                    return;
+                }
+            };
+            // Keep the stream alive until asked to stop (or the recorder is dropped)
+            let _ = stop_rx.recv();
+            drop(stream);
+            debug!("Microphone stream closed");
+        });
+
+        let (sample_rate, channels) = ready_rx
+            .recv()
+            .map_err(|e| format!("Microphone thread exited unexpectedly: {e}"))??;
+
+        info!(sample_rate, channels, "Recording started");
+        Ok(Self {
+            samples,
+            stop_tx,
+            thread,
+            sample_rate,
+            channels,
+        })
+    }
+
+    /// Stop recording and return mono samples resampled to `target_rate`.
+    pub fn stop(self, target_rate: u32) -> Result<Vec<f32>, String> {
+        let _ = self.stop_tx.send(());
+        if self.thread.join().is_err() {
+            warn!("Microphone thread panicked");
+        }
+
+        let raw = std::mem::take(&mut *self.samples.lock().unwrap());
+        let mono = downmix(&raw, self.channels);
+        let duration = mono.len() as f32 / self.sample_rate as f32;
+        info!(duration, "Recording stopped");
+
+        if duration < MIN_RECORDING_SECONDS {
+            return Err("Recording too short".to_string());
+        }
+        Ok(resample(&mono, self.sample_rate, target_rate))
+    }
+}
+
+/// Build and start an input stream that appends samples to the shared buffer.
+fn build_input_stream(
+    samples: Arc<Mutex<Vec<f32>>>,
+) -> Result<(cpal::Stream, u32, usize), String> {
+    let host = cpal::default_host();
+    let device = host
+        .default_input_device()
+        .ok_or_else(|| "No microphone found".to_string())?;
+    let supported = device
+        .default_input_config()
+        .map_err(|e| format!("Failed to query microphone: {e}"))?;
+
+    let sample_rate = supported.sample_rate().0;
+    let channels = supported.channels() as usize;
+    let max_samples = sample_rate as usize * channels * MAX_RECORDING_SECONDS;
+    let config: cpal::StreamConfig = supported.config();
+    let err_fn = |e| warn!(error = %e, "Microphone stream error");
+
+    let push = move |data: &mut dyn Iterator<Item = f32>| {
+        let mut buffer = samples.lock().unwrap();
+        let room = max_samples.saturating_sub(buffer.len());
+        buffer.extend(data.take(room));
+    };
+
+    let stream = match supported.sample_format() {
+        cpal::SampleFormat::F32 => device.build_input_stream(
+            &config,
+            move |data: &[f32], _: &cpal::InputCallbackInfo| push(&mut data.iter().copied()),
+            err_fn,
+            None,
+        ),
+        cpal::SampleFormat::I16 => device.build_input_stream(
+            &config,
+            move |data: &[i16], _: &cpal::InputCallbackInfo| {
+                push(&mut data.iter().map(|&s| s as f32 / 32768.0))
+            },
+            err_fn,
+            None,
+        ),
+        cpal::SampleFormat::U16 => device.build_input_stream(
+            &config,
+            move |data: &[u16], _: &cpal::InputCallbackInfo| {
+                push(&mut data.iter().map(|&s| (s as f32 - 32768.0) / 32768.0))
+            },
+            err_fn,
+            None,
+        ),
+        other => return Err(format!("Unsupported microphone sample format: {other:?}")),
+    }
+    .map_err(|e| format!("Failed to open microphone: {e}"))?;
+
+    stream
+        .play()
+        .map_err(|e| format!("Failed to start microphone: {e}"))?;
+    Ok((stream, sample_rate, channels))
+}
+
+/// Average interleaved channels down to mono.
+fn downmix(samples: &[f32], channels: usize) -> Vec<f32> {
+    if channels <= 1 {
+        return samples.to_vec();
+    }
+    samples
+        .chunks(channels)
+        .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32)
+        .collect()
+}
+
+/// Linear-interpolation resampler (good enough for speech).
+pub fn resample(samples: &[f32], from_rate: u32, to_rate: u32) -> Vec<f32> {
+    if from_rate == to_rate || samples.is_empty() {
+        return samples.to_vec();
+    }
+    let ratio = from_rate as f64 / to_rate as f64;
+    let out_len = (samples.len() as f64 / ratio) as usize;
+    (0..out_len)
+        .map(|i| {
+            let pos = i as f64 * ratio;
+            let idx = pos as usize;
+            let frac = (pos - idx as f64) as f32;
+            let a = samples[idx];
+            let b = samples.get(idx + 1).copied().unwrap_or(a);
+            a + (b - a) * frac
+        })
+        .collect()
+}
+
+/// Trim leading and trailing silence from mono samples.
+pub fn trim_silence(samples: &[f32]) -> &[f32] {
+    let start = samples.iter().position(|s| s.abs() > SILENCE_THRESHOLD);
+    let end = samples.iter().rposition(|s| s.abs() > SILENCE_THRESHOLD);
+    match (start, end) {
+        (Some(start), Some(end)) => &samples[start..=end],
+        _ => &[],
+    }
+}
+
+/// Compute a coarse amplitude envelope with `bins` values normalized to 0.0-1.0.
+///
+/// Each value is the peak amplitude of its slice of the signal, which is
+/// enough to compare the rhythm of two recordings side by side.
+pub fn waveform_envelope(samples: &[f32], bins: usize) -> Vec<f32> {
+    if samples.is_empty() || bins == 0 {
+        return vec![0.0; bins];
+    }
+    let chunk = samples.len().div_ceil(bins);
+    let mut envelope: Vec<f32> = samples
+        .chunks(chunk)
+        .map(|c| c.iter().fold(0.0f32, |max, s| max.max(s.abs())))
+        .collect();
+    envelope.resize(bins, 0.0);
+
+    let peak = envelope.iter().cloned().fold(0.0f32, f32::max);
+    if peak > 0.0 {
+        for value in &mut envelope {
+            *value /= peak;
+        }
+    }
+    envelope
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resample_halves_length() {
+        let samples = vec![0.0; 32000];
+        assert_eq!(resample(&samples, 32000, 16000).len(), 16000);
+    }
+
+    #[test]
+    fn test_trim_silence() {
+        let samples = [0.0, 0.01, 0.5, 0.0, -0.4, 0.001, 0.0];
+        assert_eq!(trim_silence(&samples), &[0.5, 0.0, -0.4]);
+        assert!(trim_silence(&[0.0, 0.001]).is_empty());
+    }
+
+    #[test]
+    fn test_waveform_envelope_normalized() {
+        let samples = [0.1, 0.2, 0.5, 0.25];
+        assert_eq!(waveform_envelope(&samples, 2), vec![0.4, 1.0]);
+        assert_eq!(waveform_envelope(&[], 3), vec![0.0; 3]);
+    }
+}
@@ -56,8 +56,9 @@ fn try_tool(tool: &Tool, output_path: &Path) -> Option<Result<String, String>> {
     }
 }
 
-/// Special handler for grim+slurp (Wayland) which requires two commands
-fn try_grim_slurp(output_path: &Path) -> Option<Result<String, String>> {
+/// Let the user select a region with slurp (Wayland), for grim to capture.
+/// Same results as [`try_tool`], with the region instead of a path.
+fn select_region_with_slurp() -> Option<Result<String, String>> {
     // Both tools must be available
     if Command::new("grim").arg("--version").output().is_err() 
         || Command::new("slurp").arg("--version").output().is_err() {
@@ -87,6 +88,15 @@ fn try_grim_slurp(output_path: &Path) -> Option<Result<String, String>> {
     if region.is_empty() {
         return None;
     }
+    Some(Ok(region))
+}
+
+/// Special handler for grim+slurp (Wayland) which requires two commands
+fn try_grim_slurp(output_path: &Path) -> Option<Result<String, String>> {
+    let region = match select_region_with_slurp()? {
+        Ok(region) => region,
+        Err(e) => return Some(Err(e)),
+    };
     
     // Capture the selected region with grim
     match Command::new("grim").arg("-g").arg(&region).arg(output_path.as_os_str()).output() {
@@ -142,3 +152,55 @@ pub(super) fn capture_region_linux() -> Result<String, String> {
     error!("No screenshot tools found. Please install one of: flameshot, maim, grim+slurp, scrot, gnome-screenshot, or spectacle");
     Err("No screenshot tools available. Please install flameshot, maim, grim+slurp, scrot, gnome-screenshot, or spectacle".to_string())
 }
+
+/// Captures a screenshot region with a tool writing the PNG to standard
+/// output, so it is never saved. Returns `None` if no such tool is available.
+pub(super) fn capture_region_to_stdout() -> Option<Result<Vec<u8>, String>> {
+    info!("Starting interactive screenshot region selection on Linux, in memory");
+    
+    // Tools that print the PNG instead of saving it
+    const TOOLS: &[Tool] = &[
+        Tool { name: "flameshot", args: &["gui", "--raw"] },
+        Tool { name: "maim", args: &["-s"] },
+    ];
+    
+    for tool in TOOLS {
+        if Command::new(tool.name).arg("--version").output().is_err() {
+            continue;
+        }
+        info!("Using {} for screenshot capture", tool.name);
+        match Command::new(tool.name).args(tool.args).output() {
+            Ok(output) if output.status.success() && !output.stdout.is_empty() => {
+                info!(bytes = output.stdout.len(), "Screenshot captured in memory with {}", tool.name);
+                return Some(Ok(output.stdout));
+            }
+            // flameshot exits successfully with nothing printed when cancelled
+            Ok(output) if output.status.success() || output.status.code() == Some(1) => {
+                debug!("User cancelled screenshot selection");
+                return Some(Err("Screenshot selection cancelled".to_string()));
+            }
+            Ok(output) => {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                error!(code = output.status.code().unwrap_or(-1), stderr = %stderr.trim(), "{} command failed", tool.name);
+            }
+            Err(e) => debug!(error = %e, "{} execution failed, trying next tool", tool.name),
+        }
+    }
+    
+    let region = match select_region_with_slurp()? {
+        Ok(region) => region,
+        Err(e) => return Some(Err(e)),
+    };
+    // grim writes to standard output when given "-" as the file
+    match Command::new("grim").arg("-g").arg(&region).arg("-").output() {
+        Ok(output) if output.status.success() && !output.stdout.is_empty() => {
+            info!(bytes = output.stdout.len(), "Screenshot captured in memory with grim+slurp");
+            Some(Ok(output.stdout))
+        }
+        Ok(_) => None,
+        Err(e) => {
+            debug!(error = %e, "grim execution failed");
+            None
+        }
+    }
+}
@@ -35,3 +35,21 @@ pub fn capture_region() -> Result<String, String> {
         Err("Screenshot region selection is only supported on macOS, Linux, and Windows".to_string())
     }
 }
+
+/// Captures a screenshot of a selected screen region as PNG bytes, without
+/// keeping it on disk.
+///
+/// On Linux, tools that can write the image to standard output (flameshot,
+/// maim, grim+slurp) are tried first. Otherwise, and on macOS and Windows, the
+/// capture goes through a temp file that is shredded as soon as it is read.
+pub fn capture_region_in_memory() -> Result<Vec<u8>, String> {
+    #[cfg(target_os = "linux")]
+    {
+        if let Some(result) = linux::capture_region_to_stdout() {
+            return result;
+        }
+    }
+
+    let path = capture_region()?;
+    super::read_and_shred(std::path::Path::new(&path))
+}
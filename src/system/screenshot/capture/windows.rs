@@ -198,7 +198,6 @@ pub(super) fn capture_region_windows() -> Result<String, String> {
     
     // Execute PowerShell script for region selection
     // Use CREATE_NO_WINDOW flag to prevent console window from appearing
-    const CREATE_NO_WINDOW: u32 = 0x08000000;
     let output = match Command::new("powershell")
         .args([
             "-NoProfile",
@@ -206,7 +205,7 @@ pub(super) fn capture_region_windows() -> Result<String, String> {
             "-ExecutionPolicy", "Bypass",
             "-Command", &script,
         ])
-        .creation_flags(CREATE_NO_WINDOW)
+        .creation_flags(crate::system::CREATE_NO_WINDOW)
         .output()
     {
         Ok(output) => output,
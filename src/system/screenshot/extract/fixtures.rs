@@ -0,0 +1,117 @@
+//! OCR tests against the fixture images in `tests/fixtures/ocr`.
+//!
+//! These run the real OCR backend of the platform (Vision on macOS, EasyOCR on
+//! Linux, Windows.Media.Ocr on Windows), so they are behind the
+//! `ocr-fixtures` feature: `cargo test --features ocr-fixtures`.
+
+use super::*;
+use crate::system::Screenshot;
+
+/// Fixture images and phrases their text must contain, in reading order.
+const FIXTURES: &[(&str, &[&str])] = &[
+    ("heading.png", &["multiple tts providers"]),
+    (
+        "bullets.png",
+        &[
+            "piper",
+            "local offline",
+            "fast privacy-focused local",
+            "tts with 100+ voices",
+            "aws polly",
+            "high-quality neural voices",
+            "with multiple engines",
+            "generative longform",
+        ],
+    ),
+    (
+        "dialog.png",
+        &[
+            "multiple tts providers",
+            "piper local offline",
+            "privacy-focused local",
+            "tts with 100+ voices",
+            "aws polly",
+            "high-quality neural voices",
+            "standard neural",
+            "more to come",
+        ],
+    ),
+];
+
+fn fixture_path(name: &str) -> String {
+    format!("{}/tests/fixtures/ocr/{name}", env!("CARGO_MANIFEST_DIR"))
+}
+
+/// Lowercase words, keeping the characters the phrases rely on.
+fn normalized(text: &str) -> String {
+    text.to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '+' { c } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Check that `text` contains `phrases` one after another.
+fn assert_phrases_in_order(name: &str, text: &str, phrases: &[&str]) {
+    let text = normalized(text);
+    let mut rest = text.as_str();
+    for phrase in phrases {
+        let Some(index) = rest.find(phrase) else {
+            panic!("{name}: expected \"{phrase}\" in order in OCR text \"{text}\"");
+        };
+        rest = &rest[index + phrase.len()..];
+    }
+}
+
+#[test]
+fn test_fixture_text() {
+    for (name, phrases) in FIXTURES {
+        let lines = extract_text_lines(&fixture_path(name)).unwrap_or_else(|e| panic!("{name}: {e}"));
+        assert_phrases_in_order(name, &lines_text(&lines), phrases);
+    }
+}
+
+#[test]
+fn test_fixture_from_png() {
+    for (name, _) in FIXTURES {
+        let png = std::fs::read(fixture_path(name)).unwrap();
+        let from_file = extract_text_lines(&fixture_path(name)).unwrap();
+        let from_png = extract_text_lines_from_png(&png).unwrap_or_else(|e| panic!("{name}: {e}"));
+        assert_eq!(lines_text(&from_png), lines_text(&from_file), "{name}: in-memory OCR differs from file OCR");
+    }
+}
+
+#[test]
+fn test_fixture_lines() {
+    let path = fixture_path("dialog.png");
+    let (width, height) = image::image_dimensions(&path).unwrap();
+    let lines = extract_text_lines(&path).unwrap();
+    // One line of text per line of the image, top to bottom
+    assert!(lines.len() >= 8, "expected the 8 lines of dialog.png, got {lines:?}");
+    assert!(lines.windows(2).all(|pair| pair[0].y <= pair[1].y), "lines out of order: {lines:?}");
+    for line in &lines {
+        assert!(line.x >= 0.0 && line.y >= 0.0, "{line:?}");
+        assert!(line.x + line.width <= width as f32 + 1.0 && line.y + line.height <= height as f32 + 1.0, "{line:?}");
+        if let Some(confidence) = line.confidence {
+            assert!((0.0..=1.0).contains(&confidence), "{line:?}");
+        }
+    }
+    // Hyphenated words stay whole
+    let text = normalized(&lines_text(&lines));
+    assert!(text.contains("privacy-focused") && text.contains("high-quality"), "{text}");
+    assert_eq!(lines_text(&lines).lines().count(), lines.len());
+}
+
+#[test]
+fn test_cropped_screenshot() {
+    let png = std::fs::read(fixture_path("dialog.png")).unwrap();
+    let screenshot = Screenshot::Png(std::sync::Arc::new(png));
+    let (width, _) = screenshot.dimensions().unwrap();
+    // The first three lines of the dialog
+    let top = screenshot.crop(0, 0, width, 95).unwrap();
+    let text = lines_text(&top.text_lines().unwrap());
+    assert_phrases_in_order("dialog.png (top)", &text, &["multiple tts providers", "tts with 100+ voices"]);
+    assert!(!normalized(&text).contains("generative"), "{text}");
+}
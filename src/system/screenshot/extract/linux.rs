@@ -7,6 +7,8 @@ use tracing::{debug, error, info, warn};
 
 use dirs;
 
+use super::{run_script, ImageSource};
+
 /// Find Python interpreter in the venv (same location as piper binary)
 fn find_venv_python() -> Option<PathBuf> {
     // Check project-local virtualenv first (development)
@@ -28,14 +30,18 @@ fn find_venv_python() -> Option<PathBuf> {
     None
 }
 
-/// Extracts text from an image on Linux using Python script with EasyOCR.
-pub(super) fn extract_text_from_image_linux(image_path: &str) -> Result<String, String> {
-    info!(path = %image_path, "Starting text extraction from image on Linux");
+/// Runs the OCR script on an image, returning one JSON object per line of text.
+///
+/// `language` (e.g. "de", "zh-Hant") replaces the script's default languages.
+pub(super) fn run_ocr_script(image: ImageSource, language: Option<&str>) -> Result<String, String> {
+    info!(?image, ?language, "Starting text extraction from image on Linux");
     
     // Verify the image file exists
-    if !Path::new(image_path).exists() {
-        error!(path = %image_path, "Image file does not exist");
-        return Err(format!("Image file does not exist: {}", image_path));
+    if let ImageSource::File(image_path) = image {
+        if !Path::new(image_path).exists() {
+            error!(path = %image_path, "Image file does not exist");
+            return Err(format!("Image file does not exist: {}", image_path));
+        }
     }
     
     // Find the Python script path: try executable directory, parent, then current directory
@@ -85,11 +91,13 @@ pub(super) fn extract_text_from_image_linux(image_path: &str) -> Result<String,
     debug!(python = %python_interpreter.display(), "Using Python interpreter for text extraction");
     
     // Execute Python script
-    let output = match Command::new(&python_interpreter)
-        .arg(script_path.as_os_str())
-        .arg(image_path)
-        .output()
-    {
+    let output = match run_script(
+        Command::new(&python_interpreter)
+            .arg(script_path.as_os_str())
+            .arg("--lines")
+            .args(language.into_iter().flat_map(|language| ["--lang", language])),
+        image,
+    ) {
         Ok(output) => output,
         Err(e) => {
             error!(error = %e, "Failed to execute python3 command");
@@ -117,16 +125,16 @@ pub(super) fn extract_text_from_image_linux(image_path: &str) -> Result<String,
         return Err(format!("Text extraction failed: {}", stderr.trim()));
     }
     
-    // Preserve all newlines from OCR output - only trim trailing newline from script output
-    let extracted_text = String::from_utf8_lossy(&output.stdout).trim_end().to_string();
+    // One JSON object per line - only trim trailing newline from script output
+    let output = String::from_utf8_lossy(&output.stdout).trim_end().to_string();
     
-    if extracted_text.is_empty() {
+    if output.is_empty() {
         warn!("No text found in image");
         return Err("No text found in image".to_string());
     }
     
-    info!(bytes = extracted_text.len(), "Text extracted successfully from image");
-    debug!(text = %extracted_text.chars().take(100).collect::<String>(), "Extracted text preview");
+    info!(bytes = output.len(), "Text extracted successfully from image");
+    debug!(text = %output.chars().take(100).collect::<String>(), "Extracted text preview");
     
-    Ok(extracted_text)
+    Ok(output)
 }
@@ -5,14 +5,20 @@ use std::path::Path;
 use std::process::Command;
 use tracing::{debug, error, info, warn};
 
-/// Extracts text from an image on macOS using Swift script with Vision framework.
-pub(super) fn extract_text_from_image_macos(image_path: &str) -> Result<String, String> {
-    info!(path = %image_path, "Starting text extraction from image");
+use super::{run_script, ImageSource};
+
+/// Runs the OCR script on an image, returning one JSON object per line of text.
+///
+/// `language` (e.g. "de", "zh-Hant") replaces the script's default languages.
+pub(super) fn run_ocr_script(image: ImageSource, language: Option<&str>) -> Result<String, String> {
+    info!(?image, ?language, "Starting text extraction from image");
     
     // Verify the image file exists
-    if !Path::new(image_path).exists() {
-        error!(path = %image_path, "Image file does not exist");
-        return Err(format!("Image file does not exist: {}", image_path));
+    if let ImageSource::File(image_path) = image {
+        if !Path::new(image_path).exists() {
+            error!(path = %image_path, "Image file does not exist");
+            return Err(format!("Image file does not exist: {}", image_path));
+        }
     }
     
     // Find the Swift script path: try multiple locations
@@ -80,11 +86,13 @@ pub(super) fn extract_text_from_image_macos(image_path: &str) -> Result<String,
     debug!(script = %script_path.display(), "Using Swift script for text extraction");
     
     // Execute Swift script
-    let output = match Command::new("swift")
-        .arg(script_path.as_os_str())
-        .arg(image_path)
-        .output()
-    {
+    let output = match run_script(
+        Command::new("swift")
+            .arg(script_path.as_os_str())
+            .arg("--lines")
+            .args(language.into_iter().flat_map(|language| ["--lang", language])),
+        image,
+    ) {
         Ok(output) => output,
         Err(e) => {
             error!(error = %e, "Failed to execute swift command");
@@ -112,16 +120,16 @@ pub(super) fn extract_text_from_image_macos(image_path: &str) -> Result<String,
         return Err(format!("Text extraction failed: {}", stderr.trim()));
     }
     
-    // Preserve all newlines from OCR output - only trim trailing newline from script output
-    let extracted_text = String::from_utf8_lossy(&output.stdout).trim_end().to_string();
+    // One JSON object per line - only trim trailing newline from script output
+    let output = String::from_utf8_lossy(&output.stdout).trim_end().to_string();
     
-    if extracted_text.is_empty() {
+    if output.is_empty() {
         warn!("No text found in image");
         return Err("No text found in image".to_string());
     }
     
-    info!(bytes = extracted_text.len(), "Text extracted successfully from image");
-    debug!(text = %extracted_text.chars().take(100).collect::<String>(), "Extracted text preview");
+    info!(bytes = output.len(), "Text extracted successfully from image");
+    debug!(text = %output.chars().take(100).collect::<String>(), "Extracted text preview");
     
-    Ok(extracted_text)
+    Ok(output)
 }
@@ -6,32 +6,225 @@ mod linux;
 mod macos;
 #[cfg(target_os = "windows")]
 mod windows;
+#[cfg(all(test, feature = "ocr-fixtures"))]
+mod fixtures;
 
-/// Extracts text from an image using platform-native OCR APIs.
+use serde::Deserialize;
+use tracing::{info, warn};
+
+use crate::text::language::{detect_language, Detection};
+
+/// Average OCR confidence below which the text is checked for another language.
+const LOW_CONFIDENCE: f32 = 0.6;
+
+/// Language guesses less sure than this do not trigger a retry.
+const MIN_DETECTION_CONFIDENCE: f32 = 0.6;
+
+/// Image handed to the OCR backend: a file, or PNG bytes that never touch the disk.
+#[derive(Clone, Copy)]
+enum ImageSource<'a> {
+    File(&'a str),
+    Png(&'a [u8]),
+}
+
+impl std::fmt::Debug for ImageSource<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImageSource::File(path) => write!(f, "{path}"),
+            ImageSource::Png(png) => write!(f, "PNG in memory ({} bytes)", png.len()),
+        }
+    }
+}
+
+/// A line of text found by OCR, with where it is in the image.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct OcrLine {
+    pub text: String,
+    /// Left edge in image pixels
+    pub x: f32,
+    /// Top edge in image pixels
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    /// Recognition confidence from 0 to 1, when the backend reports it
+    #[serde(default)]
+    pub confidence: Option<f32>,
+}
+
+impl OcrLine {
+    /// Move the line by `(dx, dy)` pixels, for lines found in a crop of a larger image.
+    pub fn offset(mut self, dx: f32, dy: f32) -> Self {
+        self.x += dx;
+        self.y += dy;
+        self
+    }
+}
+
+/// Text of OCR lines, one per line.
+pub fn lines_text(lines: &[OcrLine]) -> String {
+    lines.iter().map(|line| line.text.as_str()).collect::<Vec<_>>().join("\n")
+}
+
+/// Average confidence of the lines, if the backend reports it.
+fn average_confidence(lines: &[OcrLine]) -> Option<f32> {
+    let confidences: Vec<f32> = lines.iter().filter_map(|line| line.confidence).collect();
+    (!confidences.is_empty()).then(|| confidences.iter().sum::<f32>() / confidences.len() as f32)
+}
+
+/// Language to run OCR again with, when the first pass in `language` looks unsure
+/// and the text it found reads like another language.
+///
+/// Backends without a confidence (Windows) retry whenever the guess differs.
+fn retry_language(language: &str, confidence: Option<f32>, detection: Option<Detection>) -> Option<&'static str> {
+    let detection = detection.filter(|detection| detection.confidence >= MIN_DETECTION_CONFIDENCE)?;
+    if detection.language == language || confidence.is_some_and(|confidence| confidence >= LOW_CONFIDENCE) {
+        return None;
+    }
+    Some(detection.language)
+}
+
+/// Extracts the lines of text in an image, with their bounding boxes, using platform-native OCR APIs.
 /// 
 /// On macOS, uses Swift script with Vision framework for OCR.
 /// On Linux, uses EasyOCR via Python script.
 /// On Windows, uses built-in Windows.Media.Ocr API (no external dependencies required).
-/// Returns the extracted text, or an error message.
-pub fn extract_text_from_image(image_path: &str) -> Result<String, String> {
+/// The scripts print one JSON object per line of text when called with `--lines`.
+///
+/// OCR first runs for the language of the user interface. When the result is
+/// unsure and reads like another language, it runs again with that language's
+/// pack and the more confident result is kept.
+/// Returns the lines top to bottom, or an error message.
+pub fn extract_text_lines(image_path: &str) -> Result<Vec<OcrLine>, String> {
+    extract_lines(ImageSource::File(image_path))
+}
+
+/// Like [`extract_text_lines`], for a PNG image kept in memory.
+///
+/// The image is piped to the OCR scripts on macOS and Linux and decoded in
+/// memory on Windows, so it is never written to disk.
+pub fn extract_text_lines_from_png(png: &[u8]) -> Result<Vec<OcrLine>, String> {
+    extract_lines(ImageSource::Png(png))
+}
+
+fn extract_lines(image: ImageSource) -> Result<Vec<OcrLine>, String> {
+    // English uses each backend's default languages
+    let ui_language = crate::system::ui_language().filter(|language| language != "en");
+    let lines = match extract_text_lines_in(image, ui_language.as_deref()) {
+        // The language may not be supported or installed
+        Err(e) if ui_language.is_some() && !e.contains("No text found") => {
+            warn!(error = %e, language = ?ui_language, "OCR in the interface language failed, using the default languages");
+            extract_text_lines_in(image, None)?
+        }
+        result => result?,
+    };
+
+    let confidence = average_confidence(&lines);
+    let detection = detect_language(&lines_text(&lines));
+    let Some(language) = retry_language(ui_language.as_deref().unwrap_or("en"), confidence, detection) else {
+        return Ok(lines);
+    };
+    info!(?confidence, ?detection, "OCR result looks like another language, retrying with its language pack");
+    match extract_text_lines_in(image, Some(language)) {
+        Ok(retried) => match (average_confidence(&retried), confidence) {
+            (Some(new), Some(old)) if new < old => Ok(lines),
+            _ => Ok(retried),
+        },
+        Err(e) => {
+            warn!(error = %e, language, "OCR retry failed, keeping the first result");
+            Ok(lines)
+        }
+    }
+}
+
+/// Runs OCR for `language` (ISO 639-1, "zh-Hans"/"zh-Hant" for Chinese), or the backend's default languages.
+fn extract_text_lines_in(image: ImageSource, language: Option<&str>) -> Result<Vec<OcrLine>, String> {
     #[cfg(target_os = "macos")]
     {
-        macos::extract_text_from_image_macos(image_path)
+        parse_lines(&macos::run_ocr_script(image, language)?)
     }
-    
+
     #[cfg(target_os = "linux")]
     {
-        linux::extract_text_from_image_linux(image_path)
+        parse_lines(&linux::run_ocr_script(image, language)?)
     }
-    
+
     #[cfg(target_os = "windows")]
     {
-        windows::extract_text_from_image_windows(image_path)
+        windows::extract_text_lines_windows(image, language)
     }
-    
+
     #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
     {
-        tracing::warn!("Text extraction from images not supported on this platform");
+        let _ = (image, language);
+        warn!("Text extraction from images not supported on this platform");
         Err("Text extraction from images is only supported on macOS, Linux, and Windows".to_string())
     }
 }
+
+/// Run an OCR script on `image`: a file path argument, or "-" with the PNG
+/// written to its standard input.
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn run_script(command: &mut std::process::Command, image: ImageSource) -> std::io::Result<std::process::Output> {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let png = match image {
+        ImageSource::File(path) => return command.arg(path).output(),
+        ImageSource::Png(png) => png,
+    };
+    let mut child = command
+        .arg("-")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    // The scripts read the whole image before writing anything
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(png)?;
+    }
+    child.wait_with_output()
+}
+
+/// Parse the `--lines` output of an OCR script (one JSON object per line).
+#[cfg(any(target_os = "macos", target_os = "linux", test))]
+fn parse_lines(output: &str) -> Result<Vec<OcrLine>, String> {
+    let lines = output
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| serde_json::from_str::<OcrLine>(line).map_err(|e| format!("Invalid OCR output: {e}")))
+        .collect::<Result<Vec<_>, _>>()?;
+    if lines.is_empty() {
+        return Err("No text found in image".to_string());
+    }
+    Ok(lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_lines() {
+        let output = "{\"text\": \"Hello world\", \"x\": 10, \"y\": 20.5, \"width\": 100, \"height\": 14}\n\
+                      {\"text\": \"Second\", \"x\": 10, \"y\": 40, \"width\": 60, \"height\": 14}\n";
+        let lines = parse_lines(output).unwrap();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].y, 20.5);
+        assert_eq!(lines_text(&lines), "Hello world\nSecond");
+        assert_eq!(lines[1].clone().offset(5.0, 5.0).x, 15.0);
+
+        assert_eq!(parse_lines("\n"), Err("No text found in image".to_string()));
+        assert!(parse_lines("not json").is_err());
+    }
+
+    #[test]
+    fn test_retry_language() {
+        let russian = Some(Detection { language: "ru", confidence: 0.95 });
+        assert_eq!(retry_language("en", Some(0.3), russian), Some("ru"));
+        assert_eq!(retry_language("en", None, russian), Some("ru"));
+        assert_eq!(retry_language("en", Some(0.9), russian), None);
+        assert_eq!(retry_language("ru", Some(0.3), russian), None);
+        assert_eq!(retry_language("en", Some(0.3), Some(Detection { language: "de", confidence: 0.4 })), None);
+        assert_eq!(retry_language("en", Some(0.3), None), None);
+    }
+}
@@ -3,15 +3,22 @@
 use std::path::Path;
 use tracing::{debug, error, info, warn};
 
-/// Extracts text from an image on Windows using the built-in Windows.Media.Ocr API.
+use super::{ImageSource, OcrLine};
+
+/// Extracts the lines of text in an image with their bounding boxes on Windows,
+/// using the built-in Windows.Media.Ocr API.
 /// This is similar to macOS Vision framework - no external dependencies required.
-pub(super) fn extract_text_from_image_windows(image_path: &str) -> Result<String, String> {
-    info!(path = %image_path, "Starting text extraction from image on Windows using native OCR");
+/// `language` (e.g. "de") replaces the user's profile languages; its OCR language pack must be installed.
+/// PNG images in memory are decoded in memory too, without a temp file.
+pub(super) fn extract_text_lines_windows(image: ImageSource, language: Option<&str>) -> Result<Vec<OcrLine>, String> {
+    info!(?image, ?language, "Starting text extraction from image on Windows using native OCR");
     
     // Verify the image file exists
-    if !Path::new(image_path).exists() {
-        error!(path = %image_path, "Image file does not exist");
-        return Err(format!("Image file does not exist: {}", image_path));
+    if let ImageSource::File(image_path) = image {
+        if !Path::new(image_path).exists() {
+            error!(path = %image_path, "Image file does not exist");
+            return Err(format!("Image file does not exist: {}", image_path));
+        }
     }
     
     // Initialize Windows Runtime (required for WinRT APIs)
@@ -29,7 +36,7 @@ pub(super) fn extract_text_from_image_windows(image_path: &str) -> Result<String
     }
     
     // Use Windows.Media.Ocr API
-    let result = extract_text_with_windows_ocr(image_path);
+    let result = extract_lines_with_windows_ocr(image, language);
     
     // Cleanup COM
     unsafe {
@@ -39,7 +46,7 @@ pub(super) fn extract_text_from_image_windows(image_path: &str) -> Result<String
     result
 }
 
-fn extract_text_with_windows_ocr(image_path: &str) -> Result<String, String> {
+fn extract_lines_with_windows_ocr(image: ImageSource, language: Option<&str>) -> Result<Vec<OcrLine>, String> {
     use std::fs;
     use windows::{
         core::*,
@@ -49,10 +56,13 @@ fn extract_text_with_windows_ocr(image_path: &str) -> Result<String, String> {
     };
     
     // Read the image file into memory
-    let image_bytes = fs::read(image_path).map_err(|e| {
-        error!(error = %e, "Failed to read image file");
-        format!("Failed to read image file: {}", e)
-    })?;
+    let image_bytes = match image {
+        ImageSource::File(image_path) => std::borrow::Cow::Owned(fs::read(image_path).map_err(|e| {
+            error!(error = %e, "Failed to read image file");
+            format!("Failed to read image file: {}", e)
+        })?),
+        ImageSource::Png(png) => std::borrow::Cow::Borrowed(png),
+    };
     
     debug!(bytes = image_bytes.len(), "Read image file into memory");
     
@@ -124,8 +134,22 @@ fn extract_text_with_windows_ocr(image_path: &str) -> Result<String, String> {
             format!("Failed to process image: {}", e)
         })?;
     
-    // Create OCR engine with user's profile languages (automatically detects available languages)
-    let ocr_engine = OcrEngine::TryCreateFromUserProfileLanguages()
+    // Create OCR engine for the requested language, or with user's profile languages
+    // (automatically detects available languages)
+    let ocr_engine = match language {
+        Some(tag) => {
+            let language = windows::Globalization::Language::CreateLanguage(&HSTRING::from(tag)).map_err(|e| {
+                error!(error = %e, language = tag, "Invalid OCR language");
+                format!("Invalid OCR language '{}': {}", tag, e)
+            })?;
+            if !OcrEngine::IsLanguageSupported(&language).unwrap_or(false) {
+                warn!(language = tag, "Windows OCR language pack not installed");
+                return Err(format!("The Windows OCR language pack for '{}' is not installed", tag));
+            }
+            OcrEngine::TryCreateFromLanguage(&language)
+        }
+        None => OcrEngine::TryCreateFromUserProfileLanguages(),
+    }
         .map_err(|e| {
             error!(error = %e, "Failed to create OCR engine");
             format!("Failed to initialize OCR engine: {}", e)
@@ -152,7 +176,7 @@ fn extract_text_with_windows_ocr(image_path: &str) -> Result<String, String> {
         format!("Failed to extract text: {}", e)
     })?;
     
-    let mut extracted_text_parts = Vec::new();
+    let mut extracted_lines = Vec::new();
     let line_count = lines.Size().map_err(|e| {
         error!(error = %e, "Failed to get lines count");
         format!("Failed to extract text: {}", e)
@@ -170,28 +194,45 @@ fn extract_text_with_windows_ocr(image_path: &str) -> Result<String, String> {
         })?;
         
         let text_str = text.to_string();
-        if !text_str.trim().is_empty() {
-            extracted_text_parts.push(text_str);
+        if text_str.trim().is_empty() {
+            continue;
+        }
+        
+        // A line's bounding box is the union of its words' boxes
+        let words = line.Words().map_err(|e| {
+            error!(error = %e, line_index = i, "Failed to get line words");
+            format!("Failed to extract text: {}", e)
+        })?;
+        let (mut left, mut top, mut right, mut bottom) = (f32::MAX, f32::MAX, f32::MIN, f32::MIN);
+        for word in words {
+            if let Ok(rect) = word.BoundingRect() {
+                left = left.min(rect.X);
+                top = top.min(rect.Y);
+                right = right.max(rect.X + rect.Width);
+                bottom = bottom.max(rect.Y + rect.Height);
+            }
+        }
+        if left > right {
+            (left, top, right, bottom) = (0.0, 0.0, 0.0, 0.0);
         }
+        
+        extracted_lines.push(OcrLine {
+            text: text_str,
+            x: left,
+            y: top,
+            width: right - left,
+            height: bottom - top,
+            // Windows OCR does not report a confidence
+            confidence: None,
+        });
     }
     
-    // Join all text parts with newlines to preserve line breaks
-    let extracted_text = extracted_text_parts.join("\n");
-    
-    if extracted_text.trim().is_empty() {
+    if extracted_lines.is_empty() {
         warn!("No text found in image");
         return Err("No text found in image".to_string());
     }
     
-    info!(
-        bytes = extracted_text.len(),
-        lines = line_count,
-        "Text extracted successfully from image using Windows OCR"
-    );
-    debug!(
-        text = %extracted_text.chars().take(100).collect::<String>(),
-        "Extracted text preview"
-    );
-    
-    Ok(extracted_text)
+    info!(lines = extracted_lines.len(), "Text extracted successfully from image using Windows OCR");
+    
+    Ok(extracted_lines)
 }
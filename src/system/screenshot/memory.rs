@@ -0,0 +1,131 @@
+//! Screenshots kept in memory, and shredding of the temp files that could not be avoided
+
+use std::fmt;
+use std::io::{Cursor, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+use tracing::{debug, warn};
+
+use super::{decode_qr_codes, extract_text_lines, extract_text_lines_from_png, OcrLine};
+
+/// A captured screenshot: a PNG file in the temp dir, or PNG bytes that never
+/// touch the disk (with "Keep screenshots off the disk" in the privacy settings).
+#[derive(Clone, PartialEq)]
+pub enum Screenshot {
+    File(String),
+    Png(Arc<Vec<u8>>),
+}
+
+impl fmt::Debug for Screenshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Screenshot::File(path) => f.debug_tuple("File").field(path).finish(),
+            Screenshot::Png(png) => write!(f, "Png({} bytes)", png.len()),
+        }
+    }
+}
+
+impl Screenshot {
+    fn open(&self) -> Result<image::DynamicImage, String> {
+        match self {
+            Screenshot::File(path) => image::open(path),
+            Screenshot::Png(png) => image::load_from_memory(png),
+        }
+        .map_err(|e| format!("Failed to open screenshot: {e}"))
+    }
+
+    /// Width and height in pixels.
+    pub fn dimensions(&self) -> Result<(u32, u32), String> {
+        match self {
+            Screenshot::File(path) => image::image_dimensions(path).map_err(|e| e.to_string()),
+            Screenshot::Png(_) => self.open().map(|image| (image.width(), image.height())),
+        }
+    }
+
+    /// Lines of text found by OCR, see [`extract_text_lines`].
+    pub fn text_lines(&self) -> Result<Vec<OcrLine>, String> {
+        match self {
+            Screenshot::File(path) => extract_text_lines(path),
+            Screenshot::Png(png) => extract_text_lines_from_png(png),
+        }
+    }
+
+    /// Contents of the QR codes in the screenshot.
+    pub fn qr_codes(&self) -> Vec<String> {
+        match self.open() {
+            Ok(image) => decode_qr_codes(&image),
+            Err(e) => {
+                warn!(error = %e, "Failed to open screenshot for QR code detection");
+                Vec::new()
+            }
+        }
+    }
+
+    /// Part of the screenshot, kept the same way (file or memory) as the whole.
+    pub fn crop(&self, x: u32, y: u32, width: u32, height: u32) -> Result<Screenshot, String> {
+        let cropped = self.open()?.crop_imm(x, y, width, height);
+        match self {
+            Screenshot::File(_) => {
+                let cropped_path = std::env::temp_dir().join("insight-reader-screenshot-region.png");
+                cropped
+                    .save(&cropped_path)
+                    .map_err(|e| format!("Failed to save screenshot region: {e}"))?;
+                Ok(Screenshot::File(cropped_path.to_string_lossy().to_string()))
+            }
+            Screenshot::Png(_) => encode_png(&cropped).map(|png| Screenshot::Png(Arc::new(png))),
+        }
+    }
+}
+
+/// Encode an image as PNG in memory.
+pub fn encode_png(image: &image::DynamicImage) -> Result<Vec<u8>, String> {
+    let mut png = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut png), image::ImageFormat::Png)
+        .map_err(|e| format!("Failed to encode image: {e}"))?;
+    Ok(png)
+}
+
+/// Read a temp file and shred it right away.
+pub fn read_and_shred(path: &Path) -> Result<Vec<u8>, String> {
+    let bytes = std::fs::read(path).map_err(|e| format!("Failed to read {}: {e}", path.display()));
+    shred(path);
+    bytes
+}
+
+/// Overwrite a file with zeros before deleting it, so the screenshot cannot be
+/// recovered from the temp dir. Errors are logged and otherwise ignored.
+pub fn shred(path: &Path) {
+    let overwrite = std::fs::metadata(path).and_then(|metadata| {
+        let mut file = std::fs::OpenOptions::new().write(true).open(path)?;
+        file.write_all(&vec![0; metadata.len() as usize])?;
+        file.sync_all()
+    });
+    if let Err(e) = overwrite {
+        warn!(error = %e, path = %path.display(), "Failed to overwrite temp file before deleting it");
+    }
+    match std::fs::remove_file(path) {
+        Ok(()) => debug!(path = %path.display(), "Temp file shredded"),
+        Err(e) => warn!(error = %e, path = %path.display(), "Failed to delete temp file"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_png_crop_and_shred() {
+        let image = image::DynamicImage::ImageRgba8(image::RgbaImage::new(40, 30));
+        let screenshot = Screenshot::Png(Arc::new(encode_png(&image).unwrap()));
+        assert_eq!(screenshot.dimensions(), Ok((40, 30)));
+        assert_eq!(screenshot.crop(10, 5, 20, 10).unwrap().dimensions(), Ok((20, 10)));
+        assert!(format!("{screenshot:?}").starts_with("Png("));
+
+        let path = std::env::temp_dir().join(format!("insight-reader-shred-test-{}", std::process::id()));
+        std::fs::write(&path, b"secret").unwrap();
+        assert_eq!(read_and_shred(&path), Ok(b"secret".to_vec()));
+        assert!(!path.exists());
+    }
+}
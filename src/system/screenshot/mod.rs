@@ -2,6 +2,10 @@
 
 mod capture;
 mod extract;
+mod memory;
+mod qr;
 
-pub use capture::capture_region;
-pub use extract::extract_text_from_image;
+pub use capture::{capture_region, capture_region_in_memory};
+pub use extract::{extract_text_lines, extract_text_lines_from_png, lines_text, OcrLine};
+pub use memory::{encode_png, read_and_shred, shred, Screenshot};
+pub use qr::decode_qr_codes;
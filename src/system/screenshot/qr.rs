@@ -0,0 +1,28 @@
+//! QR code decoding in screenshots
+
+use tracing::{debug, info};
+
+/// Decodes the QR codes in an image, returning their contents (URLs, text, ...).
+///
+/// Images without QR codes give an empty list.
+pub fn decode_qr_codes(image: &image::DynamicImage) -> Vec<String> {
+    let image = image.to_luma8();
+    let mut prepared = rqrr::PreparedImage::prepare_from_greyscale(
+        image.width() as usize,
+        image.height() as usize,
+        |x, y| image.get_pixel(x as u32, y as u32).0[0],
+    );
+
+    let mut codes: Vec<String> = Vec::new();
+    for grid in prepared.detect_grids() {
+        match grid.decode() {
+            Ok((_, content)) if !content.trim().is_empty() && !codes.contains(&content) => codes.push(content),
+            Ok(_) => {}
+            Err(e) => debug!(error = ?e, "Failed to decode QR code"),
+        }
+    }
+    if !codes.is_empty() {
+        info!(count = codes.len(), "QR codes found in image");
+    }
+    codes
+}
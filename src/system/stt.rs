@@ -0,0 +1,120 @@
+//! Speech-to-text dictation.
+//!
+//! Transcribes notes recorded with [`super::recording::Recorder`] using
+//! whisper.cpp. Transcription is only available when built with the `stt` feature.
+
+use std::path::PathBuf;
+
+use tracing::warn;
+
+/// Sample rate expected by Whisper models.
+pub const WHISPER_SAMPLE_RATE: u32 = 16000;
+
+/// Default Whisper model file name (multilingual base model).
+const WHISPER_MODEL_NAME: &str = "ggml-base.bin";
+
+/// Directory where Whisper models are looked up.
+///
+/// Linux/macOS: `~/.local/share/insight-reader/whisper`
+/// Windows: `%LOCALAPPDATA%\insight-reader\whisper`
+pub fn whisper_model_dir() -> PathBuf {
+    crate::sync::app_data_dir().join("whisper")
+}
+
+/// Find a Whisper model, preferring `INSIGHT_READER_WHISPER_MODEL` if set.
+#[cfg_attr(not(feature = "stt"), allow(dead_code))]
+fn find_whisper_model() -> Option<PathBuf> {
+    if let Ok(path) = std::env::var("INSIGHT_READER_WHISPER_MODEL") {
+        let path = PathBuf::from(path);
+        if path.is_file() {
+            return Some(path);
+        }
+        warn!(path = %path.display(), "INSIGHT_READER_WHISPER_MODEL does not point to a file");
+    }
+    let default = whisper_model_dir().join(WHISPER_MODEL_NAME);
+    default.is_file().then_some(default)
+}
+
+/// Transcribe 16 kHz mono samples to text.
+#[cfg(feature = "stt")]
+pub fn transcribe(samples: &[f32]) -> Result<String, String> {
+    use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
+
+    let model = find_whisper_model().ok_or_else(|| {
+        format!(
+            "Whisper model not found. Download {} into {}",
+            WHISPER_MODEL_NAME,
+            whisper_model_dir().display()
+        )
+    })?;
+    tracing::debug!(model = %model.display(), samples = samples.len(), "Transcribing dictation");
+
+    let ctx = WhisperContext::new_with_params(
+        &model.to_string_lossy(),
+        WhisperContextParameters::default(),
+    )
+    .map_err(|e| format!("Failed to load Whisper model: {e}"))?;
+    let mut state = ctx
+        .create_state()
+        .map_err(|e| format!("Failed to create Whisper state: {e}"))?;
+
+    let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
+    params.set_language(Some("auto"));
+    params.set_print_progress(false);
+    params.set_print_realtime(false);
+    params.set_print_special(false);
+    params.set_print_timestamps(false);
+
+    state
+        .full(params, samples)
+        .map_err(|e| format!("Transcription failed: {e}"))?;
+
+    let segments = state
+        .full_n_segments()
+        .map_err(|e| format!("Transcription failed: {e}"))?;
+    let mut text = String::new();
+    for i in 0..segments {
+        let segment = state
+            .full_get_segment_text(i)
+            .map_err(|e| format!("Transcription failed: {e}"))?;
+        text.push_str(&segment);
+    }
+    Ok(text)
+}
+
+/// Transcribe 16 kHz mono samples to text (unavailable without the `stt` feature).
+#[cfg(not(feature = "stt"))]
+pub fn transcribe(_samples: &[f32]) -> Result<String, String> {
+    Err("Speech-to-text is not available in this build (rebuild with --features stt)".to_string())
+}
+
+/// Clean up a raw Whisper transcript for reading back.
+///
+/// Removes non-speech annotations such as `[BLANK_AUDIO]` or `(music)` and
+/// collapses whitespace.
+pub fn clean_transcript(text: &str) -> String {
+    let mut cleaned = String::with_capacity(text.len());
+    let mut depth = 0usize;
+    for c in text.chars() {
+        match c {
+            '[' | '(' => depth += 1,
+            ']' | ')' if depth > 0 => depth -= 1,
+            _ if depth == 0 => cleaned.push(c),
+            _ => {}
+        }
+    }
+    cleaned.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_transcript_removes_annotations() {
+        assert_eq!(
+            clean_transcript(" [BLANK_AUDIO]\n Buy milk (coughs) and  eggs. "),
+            "Buy milk and eggs."
+        );
+    }
+}
@@ -0,0 +1,219 @@
+//! Metadata tagging for exported audio (ID3v2 for MP3, ffmetadata for MP4/M4B).
+//!
+//! ID3 tags are written natively, including CHAP/CTOC chapter frames, so MP3
+//! exports show titles and chapters in podcast and audiobook apps. MP4
+//! containers are tagged by ffmpeg from the ffmetadata document built here.
+
+use std::path::Path;
+
+/// Metadata written into exported files.
+#[derive(Debug, Clone, Default)]
+pub struct AudioTags {
+    /// Track title (chapter title, page title, or first line of the text)
+    pub title: String,
+    /// Source document
+    pub album: Option<String>,
+    /// Voice that read the text
+    pub artist: Option<String>,
+    /// Author of the source document
+    pub album_artist: Option<String>,
+    /// Track number and total tracks
+    pub track: Option<(usize, usize)>,
+    pub genre: Option<String>,
+}
+
+/// A chapter position within a single exported file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimedChapter {
+    pub title: String,
+    pub start_ms: u32,
+    pub end_ms: u32,
+}
+
+/// Lay chapters of the given durations end to end.
+pub fn timed_chapters<'a>(chapters: impl IntoIterator<Item = (&'a str, f32)>) -> Vec<TimedChapter> {
+    let mut start_ms = 0u32;
+    chapters
+        .into_iter()
+        .map(|(title, duration_secs)| {
+            let end_ms = start_ms + (duration_secs * 1000.0) as u32;
+            let chapter = TimedChapter {
+                title: title.to_string(),
+                start_ms,
+                end_ms,
+            };
+            start_ms = end_ms;
+            chapter
+        })
+        .collect()
+}
+
+/// ID3v2 sizes are "synchsafe": 7 bits per byte.
+fn synchsafe(size: u32) -> [u8; 4] {
+    [
+        ((size >> 21) & 0x7f) as u8,
+        ((size >> 14) & 0x7f) as u8,
+        ((size >> 7) & 0x7f) as u8,
+        (size & 0x7f) as u8,
+    ]
+}
+
+/// Build an ID3v2.3 frame.
+fn frame(id: &str, data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(10 + data.len());
+    out.extend_from_slice(id.as_bytes());
+    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    out.extend_from_slice(&[0, 0]);
+    out.extend_from_slice(data);
+    out
+}
+
+/// Build a UTF-16 text frame (encoding 1, with BOM).
+fn text_frame(id: &str, value: &str) -> Vec<u8> {
+    let mut data = vec![1u8, 0xff, 0xfe];
+    for unit in value.encode_utf16() {
+        data.extend_from_slice(&unit.to_le_bytes());
+    }
+    frame(id, &data)
+}
+
+/// Build a complete ID3v2.3 tag with optional chapter frames.
+pub fn id3_tag(tags: &AudioTags, chapters: &[TimedChapter]) -> Vec<u8> {
+    let mut frames = text_frame("TIT2", &tags.title);
+    if let Some(ref album) = tags.album {
+        frames.extend(text_frame("TALB", album));
+    }
+    if let Some(ref artist) = tags.artist {
+        frames.extend(text_frame("TPE1", artist));
+    }
+    if let Some(ref album_artist) = tags.album_artist {
+        frames.extend(text_frame("TPE2", album_artist));
+    }
+    if let Some((track, total)) = tags.track {
+        frames.extend(text_frame("TRCK", &format!("{track}/{total}")));
+    }
+    if let Some(ref genre) = tags.genre {
+        frames.extend(text_frame("TCON", genre));
+    }
+
+    // Chapter frames (ID3v2 Chapter Frame Addendum): one CHAP per chapter,
+    // and a top-level ordered CTOC listing them. CTOC holds at most 255 entries.
+    let chapters = &chapters[..chapters.len().min(255)];
+    if !chapters.is_empty() {
+        let mut toc = b"toc\0".to_vec();
+        toc.push(0x03); // top-level, ordered
+        toc.push(chapters.len() as u8);
+        for (i, chapter) in chapters.iter().enumerate() {
+            let element_id = format!("ch{i}\0");
+            toc.extend_from_slice(element_id.as_bytes());
+
+            let mut chap = element_id.into_bytes();
+            chap.extend_from_slice(&chapter.start_ms.to_be_bytes());
+            chap.extend_from_slice(&chapter.end_ms.to_be_bytes());
+            // Byte offsets unused
+            chap.extend_from_slice(&u32::MAX.to_be_bytes());
+            chap.extend_from_slice(&u32::MAX.to_be_bytes());
+            chap.extend(text_frame("TIT2", &chapter.title));
+            frames.extend(frame("CHAP", &chap));
+        }
+        frames.extend(frame("CTOC", &toc));
+    }
+
+    let mut tag = b"ID3".to_vec();
+    tag.extend_from_slice(&[3, 0, 0]);
+    tag.extend_from_slice(&synchsafe(frames.len() as u32));
+    tag.extend(frames);
+    tag
+}
+
+/// Length of an existing ID3v2 tag at the start of the data, if any.
+fn existing_id3_len(data: &[u8]) -> usize {
+    if data.len() < 10 || &data[..3] != b"ID3" {
+        return 0;
+    }
+    let size = data[6..10]
+        .iter()
+        .fold(0usize, |acc, &b| (acc << 7) | (b & 0x7f) as usize);
+    let footer = if data[5] & 0x10 != 0 { 10 } else { 0 };
+    (10 + size + footer).min(data.len())
+}
+
+/// Write (or replace) the ID3v2 tag of an MP3 file.
+pub fn write_id3(path: &Path, tags: &AudioTags, chapters: &[TimedChapter]) -> Result<(), String> {
+    let data = std::fs::read(path).map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+    let audio = &data[existing_id3_len(&data)..];
+    let mut out = id3_tag(tags, chapters);
+    out.extend_from_slice(audio);
+    std::fs::write(path, out).map_err(|e| format!("Failed to write {}: {e}", path.display()))
+}
+
+/// Escape a value for an ffmetadata file.
+fn escape_ffmetadata(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '=' | ';' | '#' | '\\' | '\n') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Build an ffmetadata document (global tags plus chapter markers) for MP4 output.
+pub fn ffmetadata(tags: &AudioTags, chapters: &[TimedChapter]) -> String {
+    let mut meta = String::from(";FFMETADATA1\n");
+    let fields = [
+        ("title", Some(&tags.title)),
+        ("album", tags.album.as_ref()),
+        ("artist", tags.artist.as_ref()),
+        ("album_artist", tags.album_artist.as_ref()),
+        ("genre", tags.genre.as_ref()),
+    ];
+    for (key, value) in fields {
+        if let Some(value) = value {
+            meta.push_str(&format!("{key}={}\n", escape_ffmetadata(value)));
+        }
+    }
+
+    for chapter in chapters {
+        meta.push_str("[CHAPTER]\nTIMEBASE=1/1000\n");
+        meta.push_str(&format!("START={}\nEND={}\n", chapter.start_ms, chapter.end_ms));
+        meta.push_str(&format!("title={}\n", escape_ffmetadata(&chapter.title)));
+    }
+    meta
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_id3_tag_layout() {
+        let tags = AudioTags {
+            title: "Intro".into(),
+            artist: Some("Amy".into()),
+            ..Default::default()
+        };
+        let chapters = timed_chapters([("One", 1.5), ("Two", 2.0)]);
+        assert_eq!(chapters[1].start_ms, 1500);
+        assert_eq!(chapters[1].end_ms, 3500);
+
+        let tag = id3_tag(&tags, &chapters);
+        assert_eq!(&tag[..5], b"ID3\x03\x00");
+        assert_eq!(existing_id3_len(&tag), tag.len());
+        assert_eq!(&tag[10..14], b"TIT2");
+        let find = |id: &[u8]| tag.windows(4).filter(|w| *w == id).count();
+        assert_eq!(find(b"TPE1"), 1);
+        assert_eq!(find(b"CHAP"), 2);
+        assert_eq!(find(b"CTOC"), 1);
+    }
+
+    #[test]
+    fn test_ffmetadata_chapters() {
+        let tags = AudioTags { title: "Book".into(), ..Default::default() };
+        let meta = ffmetadata(&tags, &timed_chapters([("One", 1.5), ("Two; =b", 2.0)]));
+        assert!(meta.starts_with(";FFMETADATA1\ntitle=Book\n"));
+        assert!(meta.contains("START=0\nEND=1500\ntitle=One\n"));
+        assert!(meta.contains("START=1500\nEND=3500\ntitle=Two\\; \\=b\n"));
+    }
+}
@@ -25,6 +25,10 @@ pub enum TrayEvent {
     ShowWindow,
     HideWindow,
     ReadSelected,
+    DictateNote,
+    PronunciationTrainer,
+    ClipboardHistory,
+    CommandPalette,
     Quit,
 }
 
@@ -73,11 +77,19 @@ impl SystemTray {
             
             // Now create the tray icon in this GTK thread
             let read_selected_item = MenuItem::new(&read_selected_label, true, None);
+            let dictate_item = MenuItem::new("Dictate Note", true, None);
+            let trainer_item = MenuItem::new("Pronunciation Trainer", true, None);
+            let history_item = MenuItem::new("Clipboard History", true, None);
+            let palette_item = MenuItem::new("Command Palette", true, None);
             let show_item = MenuItem::new("Show Window", true, None);
             let hide_item = MenuItem::new("Hide Window", true, None);
             let quit_item = MenuItem::new("Quit", true, None);
             
             let read_selected_id = read_selected_item.id();
+            let dictate_id = dictate_item.id();
+            let trainer_id = trainer_item.id();
+            let history_id = history_item.id();
+            let palette_id = palette_item.id();
             let show_id = show_item.id();
             let hide_id = hide_item.id();
             let quit_id = quit_item.id();
@@ -89,6 +101,10 @@ impl SystemTray {
                 let _ = tray_ready_tx.send(None);
                 return;
             }
+            menu.append(&dictate_item).ok();
+            menu.append(&trainer_item).ok();
+            menu.append(&history_item).ok();
+            menu.append(&palette_item).ok();
             menu.append(&separator).ok();
             menu.append(&show_item).ok();
             menu.append(&hide_item).ok();
@@ -100,18 +116,28 @@ impl SystemTray {
             let show_id = show_id.clone();
             let hide_id = hide_id.clone();
             let read_selected_id = read_selected_id.clone();
+            let dictate_id = dictate_id.clone();
+            let trainer_id = trainer_id.clone();
+            let history_id = history_id.clone();
+            let palette_id = palette_id.clone();
             let quit_id = quit_id.clone();
             MenuEvent::set_event_handler(Some(move |event: MenuEvent| {
                 let event_to_send = match event.id {
                     id if id == show_id => Some(TrayEvent::ShowWindow),
                     id if id == hide_id => Some(TrayEvent::HideWindow),
                     id if id == read_selected_id => Some(TrayEvent::ReadSelected),
+                    id if id == dictate_id => Some(TrayEvent::DictateNote),
+                    id if id == trainer_id => Some(TrayEvent::PronunciationTrainer),
+                    id if id == history_id => Some(TrayEvent::ClipboardHistory),
+                    id if id == palette_id => Some(TrayEvent::CommandPalette),
                     id if id == quit_id => Some(TrayEvent::Quit),
                     _ => None,
                 };
                 
                 if let Some(evt) = event_to_send {
-                    let _ = sender_clone.send(evt);
+                    if sender_clone.send(evt).is_ok() {
+                        crate::system::wake(crate::system::WakeSource::Tray);
+                    }
                 }
             }));
             
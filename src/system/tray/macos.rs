@@ -23,6 +23,10 @@ pub enum TrayEvent {
     ShowWindow,
     HideWindow,
     ReadSelected,
+    DictateNote,
+    PronunciationTrainer,
+    ClipboardHistory,
+    CommandPalette,
     Quit,
 }
 
@@ -41,20 +45,32 @@ impl SystemTray {
         
         // Create menu items
         let read_selected_item = MenuItem::new(&read_selected_label, true, None);
+        let dictate_item = MenuItem::new("Dictate Note", true, None);
+        let trainer_item = MenuItem::new("Pronunciation Trainer", true, None);
+        let history_item = MenuItem::new("Clipboard History", true, None);
+        let palette_item = MenuItem::new("Command Palette", true, None);
         let show_item = MenuItem::new("Show Window", true, None);
         let hide_item = MenuItem::new("Hide Window", true, None);
         let quit_item = MenuItem::new("Quit", true, None);
         
         // Store menu item IDs
         let read_selected_item_id = read_selected_item.id();
+        let dictate_item_id = dictate_item.id();
+        let trainer_item_id = trainer_item.id();
+        let history_item_id = history_item.id();
+        let palette_item_id = palette_item.id();
         let show_item_id = show_item.id();
         let hide_item_id = hide_item.id();
         let quit_item_id = quit_item.id();
         
-        // Create menu - Read Selected first, then separator, then other items, then separator before Quit
+        // Create menu - Read Selected and Dictate Note first, then separator, then other items, then separator before Quit
         let separator = PredefinedMenuItem::separator();
         let menu = Menu::new();
         menu.append(&read_selected_item)?;
+        menu.append(&dictate_item)?;
+        menu.append(&trainer_item)?;
+        menu.append(&history_item)?;
+        menu.append(&palette_item)?;
         menu.append(&separator)?;
         menu.append(&show_item)?;
         menu.append(&hide_item)?;
@@ -72,18 +88,28 @@ impl SystemTray {
         let show_id = show_item_id.clone();
         let hide_id = hide_item_id.clone();
         let read_selected_id = read_selected_item_id.clone();
+        let dictate_id = dictate_item_id.clone();
+        let trainer_id = trainer_item_id.clone();
+        let history_id = history_item_id.clone();
+        let palette_id = palette_item_id.clone();
         let quit_id = quit_item_id.clone();
         MenuEvent::set_event_handler(Some(move |event: MenuEvent| {
             let event_to_send = match event.id {
                 id if id == show_id => Some(TrayEvent::ShowWindow),
                 id if id == hide_id => Some(TrayEvent::HideWindow),
                 id if id == read_selected_id => Some(TrayEvent::ReadSelected),
+                id if id == dictate_id => Some(TrayEvent::DictateNote),
+                id if id == trainer_id => Some(TrayEvent::PronunciationTrainer),
+                id if id == history_id => Some(TrayEvent::ClipboardHistory),
+                id if id == palette_id => Some(TrayEvent::CommandPalette),
                 id if id == quit_id => Some(TrayEvent::Quit),
                 _ => None,
             };
             
             if let Some(evt) = event_to_send {
-                let _ = sender_clone.send(evt);
+                if sender_clone.send(evt).is_ok() {
+                    crate::system::wake(crate::system::WakeSource::Tray);
+                }
             }
         }));
         
@@ -14,6 +14,10 @@ pub enum TrayEvent {
     ShowWindow,
     HideWindow,
     ReadSelected,
+    DictateNote,
+    PronunciationTrainer,
+    ClipboardHistory,
+    CommandPalette,
     Quit,
 }
 
@@ -0,0 +1,106 @@
+//! Detect when the user is typing, to pause readings that would talk over it.
+//!
+//! Only key presses count, not mouse movement, and only their time is kept:
+//! - macOS: the session's last key-down event, as tracked by the window server
+//! - Windows: a low-level keyboard hook on its own thread, installed the first
+//!   time typing is checked; keys injected by other programs (including the
+//!   copy shortcut sent when the hotkey captures a selection) are ignored
+//! - Linux: not available
+
+use std::time::Duration;
+
+/// Time since the user last pressed a key, `None` when this platform cannot tell.
+pub fn time_since_typing() -> Option<Duration> {
+    probe()
+}
+
+/// Whether typing can be detected on this platform.
+pub fn typing_detection_supported() -> bool {
+    cfg!(any(target_os = "macos", target_os = "windows"))
+}
+
+#[cfg(target_os = "macos")]
+fn probe() -> Option<Duration> {
+    /// kCGEventSourceStateCombinedSessionState
+    const COMBINED_SESSION_STATE: i32 = 0;
+    /// kCGEventKeyDown
+    const KEY_DOWN: u32 = 10;
+
+    #[link(name = "CoreGraphics", kind = "framework")]
+    extern "C" {
+        fn CGEventSourceSecondsSinceLastEventType(state: i32, event_type: u32) -> f64;
+    }
+
+    let seconds = unsafe { CGEventSourceSecondsSinceLastEventType(COMBINED_SESSION_STATE, KEY_DOWN) };
+    Duration::try_from_secs_f64(seconds).ok()
+}
+
+#[cfg(target_os = "windows")]
+fn probe() -> Option<Duration> {
+    windows_hook::time_since_key_press()
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn probe() -> Option<Duration> {
+    None
+}
+
+#[cfg(target_os = "windows")]
+mod windows_hook {
+    use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+    use std::sync::OnceLock;
+    use std::time::{Duration, Instant};
+
+    use tracing::{info, warn};
+    use windows::Win32::Foundation::{HINSTANCE, HWND, LPARAM, LRESULT, WPARAM};
+    use windows::Win32::UI::WindowsAndMessaging::{
+        CallNextHookEx, GetMessageW, SetWindowsHookExW, HHOOK, KBDLLHOOKSTRUCT, LLKHF_INJECTED, MSG,
+        WH_KEYBOARD_LL, WM_KEYDOWN, WM_SYSKEYDOWN,
+    };
+
+    /// When the hook was installed; key press times are kept relative to it.
+    static HOOK_START: OnceLock<Instant> = OnceLock::new();
+
+    /// Milliseconds from `HOOK_START` to the last key press.
+    static LAST_KEY_PRESS: AtomicU64 = AtomicU64::new(0);
+
+    /// Set once installing the hook failed, so it is not retried on every check.
+    static HOOK_FAILED: AtomicBool = AtomicBool::new(false);
+
+    pub fn time_since_key_press() -> Option<Duration> {
+        if HOOK_FAILED.load(Ordering::Relaxed) {
+            return None;
+        }
+        let start = *HOOK_START.get_or_init(|| {
+            std::thread::spawn(run_hook);
+            Instant::now()
+        });
+        let last = start + Duration::from_millis(LAST_KEY_PRESS.load(Ordering::Relaxed));
+        Some(last.elapsed())
+    }
+
+    /// Install the hook and pump this thread's messages, which the hook needs to be called.
+    fn run_hook() {
+        let hook = unsafe { SetWindowsHookExW(WH_KEYBOARD_LL, Some(keyboard_hook), HINSTANCE::default(), 0) };
+        if let Err(e) = hook {
+            warn!(error = %e, "Failed to install keyboard hook, typing is not detected");
+            HOOK_FAILED.store(true, Ordering::Relaxed);
+            return;
+        }
+        info!("Keyboard hook installed for typing detection");
+        let mut msg = MSG::default();
+        while unsafe { GetMessageW(&mut msg, HWND::default(), 0, 0) }.as_bool() {}
+    }
+
+    unsafe extern "system" fn keyboard_hook(code: i32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+        if code >= 0 && matches!(wparam.0 as u32, WM_KEYDOWN | WM_SYSKEYDOWN) {
+            let event = &*(lparam.0 as *const KBDLLHOOKSTRUCT);
+            if event.flags.0 & LLKHF_INJECTED.0 == 0 {
+                if let Some(start) = HOOK_START.get() {
+                    LAST_KEY_PRESS.store(start.elapsed().as_millis() as u64, Ordering::Relaxed);
+                }
+            }
+        }
+        CallNextHookEx(HHOOK::default(), code, wparam, lparam)
+    }
+}
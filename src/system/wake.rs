@@ -0,0 +1,40 @@
+//! Wake the UI when a background source has something for it.
+//!
+//! Tray menu and global hotkey events arrive on their own threads and are
+//! queued in channels. Instead of polling those channels on a timer, the
+//! sources call [`wake`] after queueing an event and the app drains the
+//! queue in response, so an idle app does no periodic work at all.
+
+use std::sync::Mutex;
+
+use tracing::debug;
+
+/// Which queue has a new event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WakeSource {
+    Tray,
+    // Linux has no global hotkey support yet
+    #[cfg_attr(not(any(target_os = "macos", target_os = "windows")), allow(dead_code))]
+    Hotkey,
+}
+
+/// Receives wake-ups; returns false once nobody is listening anymore.
+type WakeListener = Box<dyn Fn(WakeSource) -> bool + Send>;
+
+static WAKE_LISTENER: Mutex<Option<WakeListener>> = Mutex::new(None);
+
+/// Register the callback woken by background sources, replacing any previous one.
+pub fn set_wake_listener(listener: impl Fn(WakeSource) -> bool + Send + 'static) {
+    *WAKE_LISTENER.lock().unwrap() = Some(Box::new(listener));
+}
+
+/// Tell the app that `source` has queued an event.
+pub fn wake(source: WakeSource) {
+    let mut listener = WAKE_LISTENER.lock().unwrap();
+    if let Some(ref callback) = *listener {
+        if !callback(source) {
+            debug!("Wake listener gone, unregistering");
+            *listener = None;
+        }
+    }
+}
@@ -0,0 +1,121 @@
+//! Watch a folder for new files to read.
+//!
+//! Other tools "send to reader" by saving a file into the folder. A file is
+//! reported once it has stopped changing for a moment, so one that is still
+//! being written or copied is not read half-finished.
+
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use notify::event::{ModifyKind, RenameMode};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use tracing::{debug, info, warn};
+
+/// How long a new file must go without changes before it is reported.
+const SETTLE_TIME: Duration = Duration::from_secs(1);
+
+/// Watches a folder until dropped.
+pub struct FolderWatcher {
+    _watcher: RecommendedWatcher,
+}
+
+/// Watch `dir` (not its subfolders) and call `on_file` with each file created
+/// or moved into it; `on_file` returns false to stop.
+pub fn watch_folder(
+    dir: &Path,
+    on_file: impl Fn(PathBuf) -> bool + Send + 'static,
+) -> Result<FolderWatcher, String> {
+    let (sender, receiver) = mpsc::channel::<(PathBuf, bool)>();
+    let mut watcher = notify::recommended_watcher(move |result: notify::Result<Event>| match result {
+        Ok(event) => {
+            let is_new = is_new_file(&event.kind);
+            for path in event.paths {
+                let _ = sender.send((path, is_new));
+            }
+        }
+        Err(e) => warn!(error = %e, "Watch folder error"),
+    })
+    .map_err(|e| format!("Failed to create folder watcher: {e}"))?;
+    watcher
+        .watch(dir, RecursiveMode::NonRecursive)
+        .map_err(|e| format!("Failed to watch {}: {e}", dir.display()))?;
+    info!(dir = %dir.display(), "Watching folder for new files");
+
+    // Ends when the watcher is dropped and the channel closes
+    std::thread::spawn(move || {
+        let mut pending: Vec<(PathBuf, Instant)> = Vec::new();
+        loop {
+            match receiver.recv_timeout(SETTLE_TIME) {
+                Ok((path, is_new)) => {
+                    let now = Instant::now();
+                    match pending.iter_mut().find(|(pending_path, _)| *pending_path == path) {
+                        Some((_, changed)) => *changed = now,
+                        None if is_new && !is_hidden(&path) => pending.push((path, now)),
+                        None => {}
+                    }
+                }
+                Err(mpsc::RecvTimeoutError::Timeout) => {}
+                Err(mpsc::RecvTimeoutError::Disconnected) => break,
+            }
+            for path in settled(&mut pending, Instant::now()) {
+                if !path.is_file() {
+                    continue;
+                }
+                debug!(path = %path.display(), "New file in watch folder");
+                if !on_file(path) {
+                    return;
+                }
+            }
+        }
+        debug!("Folder watcher stopped");
+    });
+    Ok(FolderWatcher { _watcher: watcher })
+}
+
+/// Whether an event brings a new file into the folder.
+fn is_new_file(kind: &EventKind) -> bool {
+    matches!(
+        kind,
+        EventKind::Create(_) | EventKind::Modify(ModifyKind::Name(RenameMode::To | RenameMode::Any))
+    )
+}
+
+/// Hidden and temporary files that editors and downloads leave behind.
+fn is_hidden(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_none_or(|name| name.starts_with('.') || name.starts_with('~') || name.ends_with('~'))
+}
+
+/// Remove and return the pending files that have not changed for `SETTLE_TIME`.
+fn settled(pending: &mut Vec<(PathBuf, Instant)>, now: Instant) -> Vec<PathBuf> {
+    let mut ready = Vec::new();
+    pending.retain(|(path, changed)| {
+        let settled = now.duration_since(*changed) >= SETTLE_TIME;
+        if settled {
+            ready.push(path.clone());
+        }
+        !settled
+    });
+    ready
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settled() {
+        let start = Instant::now();
+        let mut pending = vec![
+            (PathBuf::from("done.txt"), start),
+            (PathBuf::from("copying.md"), start + SETTLE_TIME / 2),
+        ];
+        assert_eq!(settled(&mut pending, start + SETTLE_TIME), vec![PathBuf::from("done.txt")]);
+        assert_eq!(pending.len(), 1);
+        assert!(is_hidden(Path::new("/inbox/.notes.txt.swp")));
+        assert!(is_hidden(Path::new("/inbox/draft.txt~")));
+        assert!(!is_hidden(Path::new("/inbox/article.md")));
+    }
+}
@@ -0,0 +1,49 @@
+//! Fetching web pages to read them aloud (links found in screenshots)
+
+use tracing::{info, warn};
+
+use crate::text::html::{html_to_speech, SpeechOptions};
+
+/// Download a web page and return its text, verbalized like copied HTML.
+///
+/// Plain text pages are returned as they are; other content types are refused.
+pub async fn fetch_page_text(url: &str, options: SpeechOptions) -> Result<String, String> {
+    info!(url = %url, "Fetching web page");
+
+    let response = reqwest::get(url).await.map_err(|e| {
+        warn!(error = %e, url = %url, "Failed to fetch web page");
+        format!("Failed to fetch {url}: {e}")
+    })?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        warn!(?status, url = %url, "Web page request failed");
+        return Err(format!("Failed to fetch {url}: {status}"));
+    }
+
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("text/html")
+        .to_ascii_lowercase();
+    let body = response.text().await.map_err(|e| {
+        warn!(error = %e, url = %url, "Failed to read web page");
+        format!("Failed to read {url}: {e}")
+    })?;
+
+    let text = if content_type.contains("html") {
+        html_to_speech(&body, options)
+    } else if content_type.starts_with("text/") {
+        body
+    } else {
+        return Err(format!("{url} is not a web page ({content_type})"));
+    };
+
+    let text = text.trim().to_string();
+    if text.is_empty() {
+        return Err(format!("No text found at {url}"));
+    }
+    info!(bytes = text.len(), "Web page fetched");
+    Ok(text)
+}
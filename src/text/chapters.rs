@@ -0,0 +1,299 @@
+//! Split documents into chapters for batch synthesis (audiobook export).
+//!
+//! Supports EPUB (spine order, one chapter per content document) and plain
+//! text/Markdown (split on `#`/`##` headings or "Chapter N" lines).
+
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use tracing::{debug, warn};
+
+use super::html::{attr, html_to_text, parse_tag};
+
+/// Longest line still considered a "Chapter N" heading in plain text.
+const MAX_HEADING_CHARS: usize = 80;
+
+/// A chapter ready for synthesis.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Chapter {
+    pub title: String,
+    pub text: String,
+}
+
+/// A document split into chapters, with optional metadata.
+#[derive(Debug, Clone)]
+pub struct Document {
+    pub title: String,
+    pub author: Option<String>,
+    pub chapters: Vec<Chapter>,
+}
+
+/// Load a document from disk and split it into chapters.
+pub fn load_document(path: &Path) -> Result<Document, String> {
+    let file_title = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "Untitled".to_string());
+
+    let is_epub = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("epub"));
+    if is_epub {
+        return load_epub(path, file_title);
+    }
+
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+    let is_html = path.extension().is_some_and(|ext| {
+        ext.eq_ignore_ascii_case("html") || ext.eq_ignore_ascii_case("htm") || ext.eq_ignore_ascii_case("xhtml")
+    });
+    let (title, text) = if is_html {
+        let page_title = element_text(&content, "title").unwrap_or(file_title);
+        (page_title, html_to_text(&content))
+    } else {
+        (file_title, content)
+    };
+
+    Ok(Document {
+        title,
+        author: None,
+        chapters: split_plain_text(&text),
+    })
+}
+
+/// Whether a file can be read aloud with [`document_text`] (plain text, Markdown or HTML).
+pub fn is_readable_file(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| {
+        ["txt", "text", "md", "markdown", "html", "htm", "xhtml"]
+            .iter()
+            .any(|readable| ext.eq_ignore_ascii_case(readable))
+    })
+}
+
+/// Load a plain text, Markdown or HTML file as plain text for reading aloud.
+///
+/// HTML tags and Markdown markup are stripped; plain text is returned as is.
+pub fn document_text(path: &Path) -> Result<String, String> {
+    if !is_readable_file(path) {
+        return Err(format!("Unsupported file type: {}", path.display()));
+    }
+    let bytes = std::fs::read(path).map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+    let content = String::from_utf8_lossy(&bytes);
+    let ext = path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let text = match ext.as_str() {
+        "html" | "htm" | "xhtml" => html_to_text(&content),
+        "md" | "markdown" => super::markdown::markdown_to_text(&content),
+        _ => content.trim().to_string(),
+    };
+    debug!(path = %path.display(), chars = text.chars().count(), "Loaded document text");
+    Ok(text)
+}
+
+/// Split plain text or Markdown into chapters on headings.
+///
+/// Text before the first heading becomes an untitled opening chapter. If the
+/// text has no headings at all, it is returned as a single chapter titled by
+/// its first line.
+pub fn split_plain_text(text: &str) -> Vec<Chapter> {
+    fn flush(title: Option<String>, body: &mut String, chapters: &mut Vec<Chapter>) {
+        let text = body.trim().to_string();
+        body.clear();
+        if text.is_empty() {
+            return;
+        }
+        let number = chapters.len() + 1;
+        chapters.push(Chapter {
+            title: title.unwrap_or_else(|| format!("Chapter {number}")),
+            text,
+        });
+    }
+
+    let mut chapters = Vec::new();
+    let mut title: Option<String> = None;
+    let mut body = String::new();
+    let mut has_headings = false;
+
+    for line in text.lines() {
+        if let Some(heading) = heading_title(line) {
+            flush(title.take(), &mut body, &mut chapters);
+            title = Some(heading);
+            has_headings = true;
+        } else {
+            body.push_str(line);
+            body.push('\n');
+        }
+    }
+    if !has_headings {
+        title = super::title_from_text(&body);
+    }
+    flush(title, &mut body, &mut chapters);
+    chapters
+}
+
+/// Return the heading text if the line is a chapter heading.
+fn heading_title(line: &str) -> Option<String> {
+    let trimmed = line.trim();
+    if trimmed.is_empty() || trimmed.chars().count() > MAX_HEADING_CHARS {
+        return None;
+    }
+    if let Some(rest) = trimmed.strip_prefix("## ").or_else(|| trimmed.strip_prefix("# ")) {
+        return Some(rest.trim().to_string());
+    }
+    let lower = trimmed.to_lowercase();
+    let is_chapter_line = ["chapter ", "part ", "prologue", "epilogue"]
+        .iter()
+        .any(|prefix| lower.starts_with(prefix));
+    is_chapter_line.then(|| trimmed.to_string())
+}
+
+/// Read a file from the EPUB archive as a UTF-8 string.
+fn read_entry(archive: &mut zip::ZipArchive<File>, name: &str) -> Result<String, String> {
+    let mut entry = archive
+        .by_name(name)
+        .map_err(|e| format!("EPUB entry '{name}' not found: {e}"))?;
+    let mut content = String::new();
+    entry
+        .read_to_string(&mut content)
+        .map_err(|e| format!("Failed to read EPUB entry '{name}': {e}"))?;
+    Ok(content)
+}
+
+/// Text content of the first `<name>...</name>` element in an XML document.
+fn element_text(xml: &str, name: &str) -> Option<String> {
+    let start = xml.find(&format!("<{name}"))?;
+    let open_end = start + xml[start..].find('>')? + 1;
+    let close = open_end + xml[open_end..].find(&format!("</{name}"))?;
+    let text = html_to_text(&xml[open_end..close]);
+    (!text.is_empty()).then_some(text)
+}
+
+/// Iterate over all tags in an XML/HTML document.
+fn tags(xml: &str) -> impl Iterator<Item = &str> {
+    xml.split('<')
+        .skip(1)
+        .filter_map(|chunk| chunk.split_once('>').map(|(inner, _)| inner))
+}
+
+/// Resolve an href relative to the OPF file's directory, dropping fragments.
+fn resolve_href(base_dir: &str, href: &str) -> String {
+    let href = href.split('#').next().unwrap_or(href);
+    let href = super::percent_decode(href);
+    let mut parts: Vec<&str> = base_dir.split('/').filter(|p| !p.is_empty()).collect();
+    for segment in href.split('/') {
+        match segment {
+            ".." => {
+                parts.pop();
+            }
+            "." | "" => {}
+            s => parts.push(s),
+        }
+    }
+    parts.join("/")
+}
+
+/// Load an EPUB file: metadata from the OPF package, chapters in spine order.
+fn load_epub(path: &Path, fallback_title: String) -> Result<Document, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open {}: {e}", path.display()))?;
+    let mut archive =
+        zip::ZipArchive::new(file).map_err(|e| format!("Not a valid EPUB (zip) file: {e}"))?;
+
+    // META-INF/container.xml points to the OPF package document
+    let container = read_entry(&mut archive, "META-INF/container.xml")?;
+    let opf_path = tags(&container)
+        .map(parse_tag)
+        .find(|tag| tag.name == "rootfile")
+        .and_then(|tag| attr(tag.attrs, "full-path").map(str::to_string))
+        .ok_or_else(|| "EPUB container.xml has no rootfile".to_string())?;
+    let opf = read_entry(&mut archive, &opf_path)?;
+    let base_dir = opf_path.rsplit_once('/').map(|(dir, _)| dir).unwrap_or("");
+
+    let title = element_text(&opf, "dc:title").unwrap_or(fallback_title);
+    let author = element_text(&opf, "dc:creator");
+
+    // Manifest: id -> href, spine: ordered ids
+    let mut manifest = Vec::new();
+    let mut spine = Vec::new();
+    for tag in tags(&opf).map(parse_tag) {
+        match tag.name.as_str() {
+            "item" => {
+                if let (Some(id), Some(href)) = (attr(tag.attrs, "id"), attr(tag.attrs, "href")) {
+                    manifest.push((id.to_string(), href.to_string()));
+                }
+            }
+            "itemref" => {
+                let linear = attr(tag.attrs, "linear").unwrap_or("yes");
+                if let Some(idref) = attr(tag.attrs, "idref") {
+                    if linear != "no" {
+                        spine.push(idref.to_string());
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    debug!(items = manifest.len(), spine = spine.len(), "Parsed EPUB package");
+
+    let mut chapters = Vec::new();
+    for idref in spine {
+        let Some((_, href)) = manifest.iter().find(|(id, _)| *id == idref) else {
+            warn!(idref = %idref, "EPUB spine item missing from manifest");
+            continue;
+        };
+        let entry = resolve_href(base_dir, href);
+        let html = match read_entry(&mut archive, &entry) {
+            Ok(html) => html,
+            Err(e) => {
+                warn!(error = %e, "Skipping unreadable EPUB chapter");
+                continue;
+            }
+        };
+        let text = html_to_text(&html);
+        if text.split_whitespace().next().is_none() {
+            continue;
+        }
+        let number = chapters.len() + 1;
+        let chapter_title = ["h1", "h2", "h3"]
+            .iter()
+            .find_map(|h| element_text(&html, h))
+            .map(|t| t.lines().collect::<Vec<_>>().join(" "))
+            .unwrap_or_else(|| format!("Chapter {number}"));
+        chapters.push(Chapter {
+            title: chapter_title,
+            text,
+        });
+    }
+
+    if chapters.is_empty() {
+        return Err("EPUB contains no readable chapters".to_string());
+    }
+    Ok(Document {
+        title,
+        author,
+        chapters,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_plain_text_on_headings() {
+        let text = "Preface text.\n\n# The Start\nOnce upon a time.\n\nChapter 2\nThe end.\n";
+        let chapters = split_plain_text(text);
+        let titles: Vec<&str> = chapters.iter().map(|c| c.title.as_str()).collect();
+        assert_eq!(titles, vec!["Chapter 1", "The Start", "Chapter 2"]);
+        assert_eq!(chapters[1].text, "Once upon a time.");
+    }
+
+    #[test]
+    fn test_resolve_href() {
+        assert_eq!(resolve_href("OEBPS", "text/ch%201.xhtml#top"), "OEBPS/text/ch 1.xhtml");
+        assert_eq!(resolve_href("OEBPS/text", "../images/a.png"), "OEBPS/images/a.png");
+        assert_eq!(resolve_href("", "ch1.xhtml"), "ch1.xhtml");
+    }
+}
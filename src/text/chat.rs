@@ -0,0 +1,285 @@
+//! Reading copied chat conversations (Slack, Discord, WhatsApp exports).
+//!
+//! Copied chats interleave sender names, timestamps and reaction counts with
+//! the messages themselves. [`chat_to_speech`] keeps who said what, drops the
+//! timestamps and reactions, and separates messages with a pause:
+//!
+//! ```text
+//! Alice Smith  10:42 AM          Alice Smith says: Are we still on for today?
+//! Are we still on for today?  ->
+//! :thumbsup: 2                   Bob says: Yes!
+//! Bob — Today at 10:43 AM
+//! Yes!
+//! ```
+//!
+//! Consecutive messages from the same sender are announced once.
+
+/// Longest sender name, in words, accepted in a message header.
+const MAX_SENDER_WORDS: usize = 4;
+
+/// Fewest message headers for a text to be treated as a chat.
+const MIN_CHAT_HEADERS: usize = 2;
+
+/// At least one header per this many lines for a text to be treated as a chat.
+const MAX_LINES_PER_HEADER: usize = 8;
+
+/// Separators between the sender and the time in a header ("Bob — Today at 9:15 PM").
+const HEADER_SEPARATORS: &[&str] = &["—", "–", "-", "|", "·"];
+
+/// Markers appended to edited messages.
+const EDITED_MARKERS: &[&str] = &["(edited)", "(bearbeitet)", "(modifié)", "(editado)"];
+
+/// A message and its sender, `None` for text before the first header.
+#[derive(Debug, PartialEq)]
+struct ChatMessage {
+    sender: Option<String>,
+    lines: Vec<String>,
+}
+
+/// What a line of a copied chat is.
+#[derive(Debug, PartialEq)]
+enum ChatLine<'a> {
+    /// Sender and time on their own line ("Alice  10:42 AM", "Bob — Today at 9:15 PM")
+    Header(String),
+    /// Time, sender and message on one line ("[10:42] Alice: Hi", "14/03/2024, 10:42 - Bob: Hi")
+    Inline(&'a str, &'a str),
+    /// A time on its own, starting another message from the same sender
+    Time,
+    /// Reactions and reply counts
+    Noise,
+    /// Part of the current message
+    Text(&'a str),
+}
+
+/// Whether `text` looks like a copied chat conversation.
+pub fn looks_like_chat(text: &str) -> bool {
+    let lines: Vec<&str> = text.lines().filter(|line| !line.trim().is_empty()).collect();
+    let headers = lines
+        .iter()
+        .filter(|line| matches!(classify(line), ChatLine::Header(_) | ChatLine::Inline(..)))
+        .count();
+    headers >= MIN_CHAT_HEADERS && headers * MAX_LINES_PER_HEADER >= lines.len()
+}
+
+/// Rewrite a copied chat as "Sender says: message" paragraphs.
+pub fn chat_to_speech(text: &str) -> String {
+    let mut announced: Option<&str> = None;
+    let mut paragraphs = Vec::new();
+    let messages = parse_messages(text);
+    for message in &messages {
+        let body = message.lines.join("\n");
+        let body = match body.chars().last() {
+            Some('.' | '!' | '?' | '…' | ':') => body,
+            _ => format!("{body}."),
+        };
+        match message.sender.as_deref() {
+            Some(sender) if announced != Some(sender) => {
+                announced = Some(sender);
+                paragraphs.push(format!("{sender} says: {body}"));
+            }
+            _ => paragraphs.push(body),
+        }
+    }
+    paragraphs.join("\n\n")
+}
+
+/// Group the lines of a chat into messages, leaving out timestamps and reactions.
+fn parse_messages(text: &str) -> Vec<ChatMessage> {
+    let mut messages: Vec<ChatMessage> = Vec::new();
+    for line in text.lines() {
+        match classify(line) {
+            ChatLine::Header(sender) => messages.push(ChatMessage { sender: Some(sender), lines: Vec::new() }),
+            ChatLine::Inline(sender, message) => messages.push(ChatMessage {
+                sender: Some(sender.to_string()),
+                lines: vec![strip_edited(message).to_string()],
+            }),
+            ChatLine::Time => {
+                let sender = messages.last().and_then(|message| message.sender.clone());
+                messages.push(ChatMessage { sender, lines: Vec::new() });
+            }
+            ChatLine::Noise => {}
+            ChatLine::Text(line) => {
+                let line = strip_edited(line);
+                if line.is_empty() {
+                    continue;
+                }
+                match messages.last_mut() {
+                    Some(message) => message.lines.push(line.to_string()),
+                    None => messages.push(ChatMessage { sender: None, lines: vec![line.to_string()] }),
+                }
+            }
+        }
+    }
+    messages.retain(|message| !message.lines.is_empty());
+    messages
+}
+
+fn classify(line: &str) -> ChatLine<'_> {
+    let line = line.trim();
+    if is_timestamp(line) {
+        return ChatLine::Time;
+    }
+    if line.is_empty() || is_reaction(line) || is_reply_count(line) {
+        return ChatLine::Noise;
+    }
+    if let Some((sender, message)) = inline_message(line) {
+        return ChatLine::Inline(sender, message);
+    }
+    if let Some(sender) = header_sender(line) {
+        return ChatLine::Header(sender);
+    }
+    ChatLine::Text(line)
+}
+
+/// Sender of a "Name  10:42 AM" or "Name — Today at 10:42 AM" header line.
+///
+/// Without a dash before the time, the name must be one word or capitalized,
+/// so "Doors open at 10:42 AM" is not taken for a header.
+fn header_sender(line: &str) -> Option<String> {
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    // A timestamp is at most four words ("Today at 10:42 AM")
+    let words = (1..=4.min(tokens.len().saturating_sub(1)))
+        .rev()
+        .find(|&words| is_timestamp(&tokens[tokens.len() - words..].join(" ")))?;
+    let mut name = &tokens[..tokens.len() - words];
+    let dashed = name.last().is_some_and(|last| HEADER_SEPARATORS.contains(last));
+    if dashed {
+        name = &name[..name.len() - 1];
+    }
+    let capitalized = name.len() == 1 || name.iter().all(|word| !word.starts_with(char::is_lowercase));
+    let sender = name.join(" ");
+    (is_sender(&sender) && (dashed || capitalized)).then_some(sender)
+}
+
+/// Sender and text of a "[10:42] Alice: Hi" or "14/03/2024, 10:42 - Alice: Hi" line.
+fn inline_message(line: &str) -> Option<(&str, &str)> {
+    let rest = if let Some(bracketed) = line.strip_prefix('[') {
+        let (time, rest) = bracketed.split_once(']')?;
+        is_timestamp(time).then_some(rest)?
+    } else {
+        let (time, rest) = line.split_once(" - ")?;
+        is_timestamp(time).then_some(rest)?
+    };
+    let (sender, message) = rest.split_once(':')?;
+    let sender = sender.trim();
+    let message = message.trim();
+    (is_sender(sender) && !message.is_empty()).then_some((sender, message))
+}
+
+/// Whether `name` can be a sender: a few words without sentence punctuation.
+fn is_sender(name: &str) -> bool {
+    let words = name.split_whitespace().count();
+    (1..=MAX_SENDER_WORDS).contains(&words)
+        && name.chars().any(char::is_alphabetic)
+        && !name.contains(['.', ',', '!', '?', ':', ';'])
+}
+
+/// Whether `s` is a time ("9:05", "10:42 AM", "21:07:33"), optionally with a
+/// date before it ("Today at 10:42 AM", "03/14/2024 9:15 PM", "14/03/2024, 10:42").
+fn is_timestamp(s: &str) -> bool {
+    let s = s.trim();
+    if is_time(s) {
+        return true;
+    }
+    let lower = s.to_lowercase();
+    for day in ["today at ", "yesterday at "] {
+        if let Some(time) = lower.strip_prefix(day) {
+            return is_time(time);
+        }
+    }
+    match s.split_once(char::is_whitespace) {
+        Some((date, time)) => is_date(date.trim_end_matches(',')) && is_time(time),
+        None => false,
+    }
+}
+
+/// Whether `s` is a clock time like "9:05", "10:42 AM" or "21:07:33".
+fn is_time(s: &str) -> bool {
+    let s = s.trim();
+    let lower = s.to_ascii_lowercase();
+    let clock = lower
+        .strip_suffix("am")
+        .or_else(|| lower.strip_suffix("pm"))
+        .unwrap_or(&lower)
+        .trim_end();
+    let parts: Vec<&str> = clock.split(':').collect();
+    let digits = |part: &str| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit());
+    (2..=3).contains(&parts.len())
+        && parts[0].len() <= 2
+        && digits(parts[0])
+        && parts[1..].iter().all(|part| part.len() == 2 && digits(part))
+}
+
+/// Whether `s` is a numeric date like "03/14/2024", "14.03.24" or "2024-03-14".
+fn is_date(s: &str) -> bool {
+    let parts: Vec<&str> = s.split(['/', '.', '-']).collect();
+    parts.len() == 3
+        && parts
+            .iter()
+            .all(|part| (1..=4).contains(&part.len()) && part.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// Whether a line only holds reactions with their counts (":thumbsup: 2", "👍 3 ❤️ 1").
+///
+/// A message made of emoji alone has no count, so it is still read.
+fn is_reaction(line: &str) -> bool {
+    let is_count = |token: &str| token.chars().all(|c| c.is_ascii_digit());
+    let is_emoji = |token: &str| {
+        (token.len() > 2 && token.starts_with(':') && token.ends_with(':')) || !token.chars().any(char::is_alphanumeric)
+    };
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    tokens.iter().any(|token| is_count(token))
+        && tokens.iter().any(|token| !is_count(token))
+        && tokens.iter().all(|token| is_count(token) || is_emoji(token))
+}
+
+/// Whether a line is a thread summary ("3 replies", "1 reply Last reply today at 10:50 AM", "View thread").
+fn is_reply_count(line: &str) -> bool {
+    let lower = line.to_lowercase();
+    let mut words = lower.split_whitespace();
+    lower == "view thread"
+        || (words.next().is_some_and(|count| count.chars().all(|c| c.is_ascii_digit()))
+            && matches!(words.next(), Some("reply" | "replies")))
+}
+
+/// Remove an "(edited)" marker from the end of a message line.
+fn strip_edited(line: &str) -> &str {
+    let line = line.trim();
+    EDITED_MARKERS
+        .iter()
+        .find_map(|marker| line.strip_suffix(marker))
+        .map_or(line, str::trim_end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chat_to_speech() {
+        let slack = "Alice Smith  10:42 AM\nAre we still on for today?\n:thumbsup: 2\n10:43\nI booked the room (edited)\nBob — Today at 10:45 AM\nYes\n3 replies";
+        assert!(looks_like_chat(slack));
+        assert_eq!(
+            chat_to_speech(slack),
+            "Alice Smith says: Are we still on for today?\n\nI booked the room.\n\nBob says: Yes."
+        );
+
+        let whatsapp = "[14/03/2024, 10:42:01] Alice: Running late\n[14/03/2024, 10:43:15] Bob: No problem!\n14/03/2024, 10:44 - Alice: See you at 11";
+        assert!(looks_like_chat(whatsapp));
+        assert_eq!(
+            chat_to_speech(whatsapp),
+            "Alice says: Running late.\n\nBob says: No problem!\n\nAlice says: See you at 11."
+        );
+    }
+
+    #[test]
+    fn test_looks_like_chat() {
+        assert!(!looks_like_chat("Meeting notes\nThe launch moves to 10:30 AM.\nNote: bring the slides."));
+        assert!(!looks_like_chat("Schedule\nOpening  9:00 AM\nKeynote and welcome by the team."));
+        assert_eq!(header_sender("Bob — 03/14/2024 9:15 PM").as_deref(), Some("Bob"));
+        assert_eq!(header_sender("Doors open at 10:42 AM"), None);
+        assert!(is_reaction("👍 3 ❤️ 1"));
+        assert!(!is_reaction("🎉🎉"));
+        assert!(!is_reaction("Sounds good :)"));
+    }
+}
@@ -0,0 +1,294 @@
+//! Reading copied or saved emails.
+//!
+//! An email copied from a mail client starts with a header block and often
+//! ends with a signature and the whole quoted conversation. [`email_to_speech`]
+//! reads the sender and subject once, then the new message only, and names
+//! the attachments at the end:
+//!
+//! ```text
+//! From: Alice Smith <alice@example.com>     From Alice Smith. Subject: Q3 budget.
+//! Subject: Q3 budget
+//! Attachments: budget.xlsx               -> Hi Bob, the numbers are in.
+//!
+//! Hi Bob, the numbers are in.               Attachment: budget.xlsx.
+//! --
+//! Alice Smith | Finance
+//! On Mon, Mar 11, 2024 at 9:00 AM Bob wrote:
+//! > Any news?
+//! ```
+//!
+//! Forwarded messages are kept, with their own sender and subject announced.
+
+/// Header names (lowercase, several languages) holding the sender.
+const FROM_HEADERS: &[&str] = &["from", "von", "de"];
+
+/// Header names holding the subject.
+const SUBJECT_HEADERS: &[&str] = &["subject", "betreff", "objet", "asunto", "assunto", "oggetto"];
+
+/// Header names holding the attachment list.
+const ATTACHMENT_HEADERS: &[&str] = &["attachments", "attachment", "anhänge", "pièces jointes", "adjuntos"];
+
+/// Other header names, recognized and skipped.
+const OTHER_HEADERS: &[&str] = &[
+    "to", "an", "à", "para", "a", "cc", "bcc", "date", "datum", "sent", "gesendet", "envoyé", "enviado", "inviato",
+    "reply-to", "importance", "message-id", "mime-version", "content-type", "content-transfer-encoding", "received",
+    "return-path", "in-reply-to", "references",
+];
+
+/// Endings of the line introducing a quoted reply ("On Mon, ... Bob wrote:").
+const REPLY_MARKERS: &[&str] = &["wrote:", "schrieb:", "a écrit :", "a écrit:", "escribió:", "escreveu:", "ha scritto:"];
+
+/// Lines starting a forwarded message.
+const FORWARD_MARKERS: &[&str] = &["---------- forwarded message", "begin forwarded message", "-------- forwarded message"];
+
+/// Footers added by mail apps, read as the start of the signature.
+const APP_FOOTERS: &[&str] = &["sent from my ", "sent from mail for ", "get outlook for "];
+
+/// Extensions of file names announced as attachments.
+const ATTACHMENT_EXTENSIONS: &[&str] = &[
+    "pdf", "doc", "docx", "xls", "xlsx", "ppt", "pptx", "odt", "ods", "odp", "csv", "txt", "rtf", "zip", "png",
+    "jpg", "jpeg", "gif", "heic", "ics", "eml", "mp3", "mp4", "mov",
+];
+
+/// Header lines searched for the header block when detecting an email.
+const DETECTION_LINES: usize = 12;
+
+/// Where in the email a line is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Part {
+    /// Header block, at the start or after a forward marker
+    Headers { forwarded: bool },
+    Body,
+    Signature,
+}
+
+/// Header kinds that are read or used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Header {
+    From,
+    Subject,
+    Attachments,
+    Other,
+}
+
+/// Whether `text` looks like an email: a header block with a sender or
+/// subject, or a reply quoting an earlier message.
+pub fn looks_like_email(text: &str) -> bool {
+    let headers: Vec<Header> = text
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .take(DETECTION_LINES)
+        .filter_map(|line| header(line).map(|(header, _)| header))
+        .collect();
+    let has_header_block =
+        headers.len() >= 2 && headers.iter().any(|header| matches!(header, Header::From | Header::Subject));
+    let lines: Vec<&str> = text.lines().map(str::trim).collect();
+    let quotes_reply = lines
+        .iter()
+        .position(|line| is_reply_marker(line))
+        .is_some_and(|marker| lines[marker + 1..].iter().any(|line| line.starts_with('>')));
+    has_header_block || quotes_reply
+}
+
+/// Rewrite an email as its sender and subject, the new message and its attachments.
+pub fn email_to_speech(text: &str) -> String {
+    let mut paragraphs: Vec<String> = Vec::new();
+    let mut body: Vec<&str> = Vec::new();
+    let mut attachments: Vec<String> = Vec::new();
+    let (mut from, mut subject) = (None, None);
+    let mut part = Part::Headers { forwarded: false };
+    let mut headers_seen = false;
+
+    for raw in text.lines() {
+        let line = raw.trim();
+        if let Some(name) = attachment_name(line) {
+            if !attachments.contains(&name) {
+                attachments.push(name);
+            }
+            continue;
+        }
+        if is_file_size(line) {
+            continue;
+        }
+        if let Part::Headers { forwarded } = part {
+            match header(line) {
+                Some((Header::From, value)) => from = Some(sender_name(value)),
+                Some((Header::Subject, value)) => subject = Some(value.to_string()),
+                Some((Header::Attachments, value)) => {
+                    for name in value.split([',', ';']).map(str::trim).filter(|name| !name.is_empty()) {
+                        attachments.push(name.to_string());
+                    }
+                }
+                Some((Header::Other, _)) => {}
+                // Folded lines of a raw message continue the previous header
+                None if raw.starts_with([' ', '\t']) && headers_seen => {}
+                None if line.is_empty() && !headers_seen => {}
+                None => part = Part::Body,
+            }
+            headers_seen |= header(line).is_some();
+            if part != Part::Body {
+                continue;
+            }
+            paragraphs.extend(announcement(forwarded, from.take(), subject.take()));
+            headers_seen = false;
+        }
+        // Outlook quotes the earlier message under its own header block
+        let quoted_header = !body.is_empty() && header(line).is_some_and(|(header, _)| header == Header::From);
+        if is_reply_marker(line) || is_separator(line) || quoted_header {
+            break;
+        }
+        let lower = line.to_lowercase();
+        if FORWARD_MARKERS.iter().any(|marker| lower.starts_with(marker)) {
+            flush(&mut paragraphs, &mut body);
+            part = Part::Headers { forwarded: true };
+            continue;
+        }
+        if line == "--" || APP_FOOTERS.iter().any(|footer| lower.starts_with(footer)) {
+            part = Part::Signature;
+        }
+        if part == Part::Body && !line.starts_with('>') {
+            body.push(line);
+        }
+    }
+    if let Part::Headers { forwarded } = part {
+        paragraphs.extend(announcement(forwarded, from, subject));
+    }
+    flush(&mut paragraphs, &mut body);
+
+    match attachments.as_slice() {
+        [] => {}
+        [name] => paragraphs.push(format!("Attachment: {name}.")),
+        [names @ .., last] => paragraphs.push(format!("Attachments: {} and {last}.", names.join(", "))),
+    }
+    paragraphs.join("\n\n")
+}
+
+/// "From Alice. Subject: Budget." for the header block just read.
+fn announcement(forwarded: bool, from: Option<String>, subject: Option<String>) -> Option<String> {
+    let mut sentences = Vec::new();
+    if forwarded {
+        sentences.push("Forwarded message.".to_string());
+    }
+    if let Some(from) = from.filter(|from| !from.is_empty()) {
+        sentences.push(format!("From {from}."));
+    }
+    if let Some(subject) = subject.filter(|subject| !subject.is_empty()) {
+        sentences.push(format!("Subject: {}.", subject.trim_end_matches(['.', '!', '?'])));
+    }
+    (!sentences.is_empty()).then(|| sentences.join(" "))
+}
+
+/// Move the body lines read so far into `paragraphs`, one paragraph per blank-line block.
+fn flush(paragraphs: &mut Vec<String>, body: &mut Vec<&str>) {
+    for block in body.split(|line| line.is_empty()).filter(|block| !block.is_empty()) {
+        paragraphs.push(block.join("\n"));
+    }
+    body.clear();
+}
+
+/// Kind and value of a "Name: value" header line.
+fn header(line: &str) -> Option<(Header, &str)> {
+    let (name, value) = line.split_once(':')?;
+    let name = name.trim().to_lowercase();
+    let header = if FROM_HEADERS.contains(&name.as_str()) {
+        Header::From
+    } else if SUBJECT_HEADERS.contains(&name.as_str()) {
+        Header::Subject
+    } else if ATTACHMENT_HEADERS.contains(&name.as_str()) {
+        Header::Attachments
+    } else if OTHER_HEADERS.contains(&name.as_str()) || name.starts_with("x-") {
+        Header::Other
+    } else {
+        return None;
+    };
+    Some((header, value.trim()))
+}
+
+/// Display name of a sender ("Alice Smith <alice@example.com>" is "Alice Smith").
+fn sender_name(value: &str) -> String {
+    let name = match value.split_once('<') {
+        Some((name, address)) if name.trim().is_empty() => address.trim_end_matches('>'),
+        Some((name, _)) => name,
+        None => value,
+    };
+    name.trim().trim_matches(['"', '\'']).trim().to_string()
+}
+
+/// Whether `line` introduces quoted earlier messages.
+fn is_reply_marker(line: &str) -> bool {
+    let lower = line.to_lowercase();
+    REPLY_MARKERS.iter().any(|marker| lower.ends_with(marker)) || lower.contains("-----original message-----")
+}
+
+/// Whether `line` is Outlook's rule above a quoted message.
+fn is_separator(line: &str) -> bool {
+    line.len() >= 10 && line.chars().all(|c| c == '_')
+}
+
+/// File name of an attachment line ("report.pdf", "📎 Q3 notes.docx", "Slides.pptx (2.1 MB)", `filename="a.pdf"`).
+///
+/// A name with spaces needs the clip or the size, so a short sentence
+/// mentioning a file is still read.
+fn attachment_name(line: &str) -> Option<String> {
+    if let Some((_, rest)) = line.split_once("filename=") {
+        let name = rest.trim_matches(['"', ';', ' ']);
+        return (!name.is_empty()).then(|| name.to_string());
+    }
+    let clipped = line.starts_with('📎');
+    let words: Vec<&str> = line.trim_start_matches('📎').split_whitespace().collect();
+    let sized = words.len() > 2 && is_file_size(&words[words.len() - 2..].join(" "));
+    let name = if sized { words[..words.len() - 2].join(" ") } else { words.join(" ") };
+    let (stem, extension) = name.rsplit_once('.')?;
+    let is_file = ATTACHMENT_EXTENSIONS.contains(&extension.to_lowercase().as_str())
+        && !stem.is_empty()
+        && (clipped || sized || !stem.contains(' '))
+        && !stem.contains([',', ':', '!', '?', '/']);
+    is_file.then_some(name)
+}
+
+/// Whether `s` is a file size ("245 KB", "(1.2 MB)").
+fn is_file_size(s: &str) -> bool {
+    match s.trim_matches(['(', ')']).split_once(' ') {
+        Some((number, unit)) => {
+            number.replace(',', ".").parse::<f32>().is_ok()
+                && ["bytes", "kb", "mb", "gb"].contains(&unit.to_lowercase().as_str())
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_email_to_speech() {
+        let outlook = "From: Alice Smith <alice@example.com>\nSent: Tuesday, March 12, 2024 10:42 AM\nTo: Bob <bob@example.com>\nSubject: Q3 budget\nAttachments: budget.xlsx; notes.pdf\n\nHi Bob,\n\nThe numbers are in. Let me know what you think.\n\nThanks,\nAlice\n\n________________________________\nFrom: Bob <bob@example.com>\nSent: Monday, March 11, 2024 9:00 AM\nSubject: Q3 budget\n\nAny news?";
+        assert!(looks_like_email(outlook));
+        assert_eq!(
+            email_to_speech(outlook),
+            "From Alice Smith. Subject: Q3 budget.\n\nHi Bob,\n\nThe numbers are in. Let me know what you think.\n\nThanks,\nAlice\n\nAttachments: budget.xlsx and notes.pdf."
+        );
+
+        let gmail = "Sounds good, see you there!\n\nreport_final.pdf\n245 KB\n--\nBob Jones\nSales | +1 555 0100\n\nOn Mon, Mar 11, 2024 at 9:00 AM Alice <alice@example.com> wrote:\n> Meeting at 3?\n> Alice";
+        assert!(looks_like_email(gmail));
+        assert_eq!(email_to_speech(gmail), "Sounds good, see you there!\n\nAttachment: report_final.pdf.");
+
+        let forwarded = "Subject: Fwd: Tickets\nFrom: Carol <carol@example.com>\n\nFYI\n\n---------- Forwarded message ---------\nFrom: Airline <noreply@example.com>\nDate: Fri, Mar 8, 2024\nSubject: Your booking\n\nYour flight is confirmed.\n\nSent from my iPhone";
+        assert_eq!(
+            email_to_speech(forwarded),
+            "From Carol. Subject: Fwd: Tickets.\n\nFYI\n\nForwarded message. From Airline. Subject: Your booking.\n\nYour flight is confirmed."
+        );
+    }
+
+    #[test]
+    fn test_looks_like_email() {
+        assert!(!looks_like_email("Note: the meeting moved.\nSubject to change.\nSee the agenda."));
+        assert!(!looks_like_email("He wrote:\nnothing worth quoting"));
+        assert_eq!(sender_name("\"Smith, Alice\" <alice@example.com>"), "Smith, Alice");
+        assert_eq!(sender_name("<alice@example.com>"), "alice@example.com");
+        assert_eq!(attachment_name("📎 Slides.pptx (2.1 MB)"), Some("Slides.pptx".to_string()));
+        assert_eq!(attachment_name("See the attached file.pdf, thanks."), None);
+        assert_eq!(attachment_name("Please review budget.xlsx"), None);
+    }
+}
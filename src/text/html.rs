@@ -0,0 +1,256 @@
+//! Minimal HTML/XHTML to plain text conversion.
+//!
+//! Good enough for EPUB chapters and clipboard HTML: block elements become
+//! paragraph breaks, scripts/styles are dropped, and entities are decoded.
+//!
+//! [`html_to_speech`] additionally verbalizes structure for reading aloud:
+//! headings are announced, list items end in a pause, and image alt text can
+//! be read.
+
+/// Elements that start a new paragraph.
+const BLOCK_TAGS: &[&str] = &[
+    "p", "div", "section", "article", "blockquote", "h1", "h2", "h3", "h4", "h5", "h6", "li",
+    "tr", "table", "ul", "ol", "pre", "figure", "header", "footer", "aside", "dd", "dt",
+];
+
+/// Elements whose content is never read.
+const SKIPPED_TAGS: &[&str] = &["script", "style", "head", "title", "svg"];
+
+/// Heading elements, announced by [`html_to_speech`].
+const HEADING_TAGS: &[&str] = &["h1", "h2", "h3", "h4", "h5", "h6"];
+
+/// Prefix announcing a heading.
+const HEADING_PREFIX: &str = "Heading: ";
+
+/// How HTML structure is verbalized by [`html_to_speech`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SpeechOptions {
+    /// Read the alt text of images ("Image: ...")
+    pub read_alt_text: bool,
+}
+
+/// A parsed tag: lowercase name, whether it is a closing tag, and its raw attributes.
+pub(crate) struct Tag<'a> {
+    pub name: String,
+    pub closing: bool,
+    pub attrs: &'a str,
+}
+
+/// Parse the inside of `<...>` (without the angle brackets).
+pub(crate) fn parse_tag(inner: &str) -> Tag<'_> {
+    let inner = inner.trim();
+    let (closing, inner) = match inner.strip_prefix('/') {
+        Some(rest) => (true, rest),
+        None => (false, inner),
+    };
+    let name_end = inner
+        .find(|c: char| c.is_whitespace() || c == '/')
+        .unwrap_or(inner.len());
+    Tag {
+        name: inner[..name_end].to_ascii_lowercase(),
+        closing,
+        attrs: &inner[name_end..],
+    }
+}
+
+/// Get an attribute value from a tag's raw attribute string.
+pub(crate) fn attr<'a>(attrs: &'a str, name: &str) -> Option<&'a str> {
+    let mut rest = attrs;
+    while let Some(pos) = rest.find(name) {
+        let before_ok = pos == 0 || rest[..pos].ends_with(|c: char| c.is_whitespace());
+        let after = rest[pos + name.len()..].trim_start();
+        if before_ok {
+            if let Some(value) = after.strip_prefix('=') {
+                let value = value.trim_start();
+                let quote = value.chars().next()?;
+                if quote == '"' || quote == '\'' {
+                    let end = value[1..].find(quote)?;
+                    return Some(&value[1..1 + end]);
+                }
+                let end = value
+                    .find(|c: char| c.is_whitespace() || c == '>' || c == '/')
+                    .unwrap_or(value.len());
+                return Some(&value[..end]);
+            }
+        }
+        rest = &rest[pos + name.len()..];
+    }
+    None
+}
+
+/// Decode HTML entities (named basics plus numeric references).
+pub fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let semi = rest
+            .char_indices()
+            .take(12)
+            .find(|&(_, c)| c == ';')
+            .map(|(i, _)| i);
+        let Some(semi) = semi else {
+            out.push('&');
+            rest = &rest[1..];
+            continue;
+        };
+        let entity = &rest[1..semi];
+        let decoded = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some(' '),
+            "mdash" => Some('—'),
+            "ndash" => Some('–'),
+            "hellip" => Some('…'),
+            "rsquo" => Some('’'),
+            "lsquo" => Some('‘'),
+            "rdquo" => Some('”'),
+            "ldquo" => Some('“'),
+            _ => entity
+                .strip_prefix("#x")
+                .or_else(|| entity.strip_prefix("#X"))
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                .and_then(char::from_u32),
+        };
+        match decoded {
+            Some(c) => {
+                out.push(c);
+                rest = &rest[semi + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Convert HTML to plain text with paragraphs separated by blank lines.
+pub fn html_to_text(html: &str) -> String {
+    convert(html, None)
+}
+
+/// Convert HTML to text for reading aloud, verbalizing headings, lists and images.
+pub fn html_to_speech(html: &str, options: SpeechOptions) -> String {
+    convert(html, Some(options))
+}
+
+/// End the text so far with a full stop, so speech pauses there.
+fn end_sentence(raw: &mut String) {
+    raw.truncate(raw.trim_end().len());
+    if raw.ends_with(HEADING_PREFIX.trim_end()) {
+        // Empty heading: drop the announcement
+        raw.truncate(raw.len() - HEADING_PREFIX.trim_end().len());
+    } else if raw.ends_with(char::is_alphanumeric) {
+        raw.push('.');
+    }
+}
+
+/// Walk the HTML, adding speech structure when `speech` is set.
+fn convert(html: &str, speech: Option<SpeechOptions>) -> String {
+    let mut raw = String::with_capacity(html.len());
+    let mut skip_depth = 0usize;
+    let mut rest = html;
+
+    while let Some(lt) = rest.find('<') {
+        if skip_depth == 0 {
+            raw.push_str(&rest[..lt]);
+        }
+        rest = &rest[lt..];
+
+        // Comments and CDATA
+        if rest.starts_with("<!--") {
+            let end = rest.find("-->").map(|i| i + 3).unwrap_or(rest.len());
+            rest = &rest[end..];
+            continue;
+        }
+
+        let Some(gt) = rest.find('>') else {
+            break;
+        };
+        let tag = parse_tag(&rest[1..gt]);
+        let self_closing = rest[..gt].ends_with('/');
+        rest = &rest[gt + 1..];
+
+        if SKIPPED_TAGS.contains(&tag.name.as_str()) && !self_closing {
+            if tag.closing {
+                skip_depth = skip_depth.saturating_sub(1);
+            } else {
+                skip_depth += 1;
+            }
+            continue;
+        }
+        if skip_depth > 0 {
+            continue;
+        }
+        if let Some(options) = speech {
+            if HEADING_TAGS.contains(&tag.name.as_str()) {
+                if !tag.closing {
+                    raw.push_str("\n\n");
+                    raw.push_str(HEADING_PREFIX);
+                    continue;
+                }
+                end_sentence(&mut raw);
+            } else if tag.name == "li" && tag.closing {
+                end_sentence(&mut raw);
+            } else if tag.name == "img" && options.read_alt_text {
+                if let Some(alt) = attr(tag.attrs, "alt").map(str::trim).filter(|alt| !alt.is_empty()) {
+                    raw.push_str(&format!(" Image: {alt}. "));
+                }
+            }
+        }
+        if tag.name == "br" {
+            raw.push('\n');
+        } else if BLOCK_TAGS.contains(&tag.name.as_str()) {
+            raw.push_str("\n\n");
+        } else if tag.name == "td" || tag.name == "th" {
+            raw.push(' ');
+        }
+    }
+    if skip_depth == 0 {
+        raw.push_str(rest);
+    }
+
+    super::tidy_paragraphs(&decode_entities(&raw))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_html_to_text_paragraphs_and_entities() {
+        let html = "<html><head><title>x</title><style>p{}</style></head><body>\
+                    <h1>Title</h1><p>Fish &amp; chips,<br/>twice&#33;</p>\
+                    <script>alert(1)</script><p>  Second   para </p></body></html>";
+        assert_eq!(html_to_text(html), "Title\n\nFish & chips,\ntwice!\n\nSecond para");
+    }
+
+    #[test]
+    fn test_html_to_speech_structure() {
+        let html = "<h2>Shopping</h2><ul><li>Milk</li><li>Eggs!</li></ul>\
+                    <p>See <img src=\"a.png\" alt=\"A chart\"> below.</p><h3></h3>";
+        assert_eq!(
+            html_to_speech(html, SpeechOptions::default()),
+            "Heading: Shopping.\n\nMilk.\n\nEggs!\n\nSee below."
+        );
+        let options = SpeechOptions { read_alt_text: true };
+        assert!(html_to_speech(html, options).contains("See Image: A chart. below."));
+    }
+
+    #[test]
+    fn test_attr() {
+        let tag = parse_tag(r#"item id="c1" href='ch1.xhtml' media-type="application/xhtml+xml"/"#);
+        assert_eq!(tag.name, "item");
+        assert_eq!(attr(tag.attrs, "href"), Some("ch1.xhtml"));
+        assert_eq!(attr(tag.attrs, "id"), Some("c1"));
+        assert_eq!(attr(tag.attrs, "missing"), None);
+    }
+}
@@ -0,0 +1,166 @@
+//! Fast language identification of short texts (OCR output).
+//!
+//! Good enough to pick an OCR language pack: the writing system decides
+//! most languages on its own (kana means Japanese, Hangul Korean, ...), and
+//! Latin-script languages are told apart by their most common words.
+
+/// A language guess.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Detection {
+    /// ISO 639-1 code, with the script for Chinese ("zh-Hans", "zh-Hant")
+    pub language: &'static str,
+    /// How sure the guess is, from 0 to 1
+    pub confidence: f32,
+}
+
+/// Common short words of the Latin-script languages that are told apart.
+const STOPWORDS: &[(&str, &[&str])] = &[
+    ("en", &["the", "and", "of", "to", "is", "in", "that", "it", "for", "with", "you", "this", "are", "was"]),
+    ("fr", &["le", "la", "les", "et", "des", "est", "une", "pour", "dans", "que", "qui", "pas", "sur", "avec"]),
+    ("de", &["der", "die", "und", "das", "ist", "nicht", "mit", "ein", "eine", "den", "zu", "sie", "auf", "für"]),
+    ("es", &["el", "los", "las", "y", "que", "es", "una", "por", "con", "para", "del", "como", "pero", "está"]),
+    ("it", &["il", "di", "che", "è", "per", "una", "non", "con", "sono", "gli", "della", "anche", "come", "questo"]),
+    ("pt", &["o", "os", "que", "é", "um", "uma", "não", "com", "para", "do", "da", "em", "mais", "você"]),
+];
+
+/// Characters written differently in Simplified and Traditional Chinese.
+const SIMPLIFIED_CHARS: &str = "这们个为说时国会来对发经过还没与样问开关";
+const TRADITIONAL_CHARS: &str = "這們個為說時國會來對發經過還沒與樣問開關";
+
+/// Letters specific to Ukrainian among Cyrillic languages.
+const UKRAINIAN_CHARS: &str = "іїєґІЇЄҐ";
+
+/// Fewest letters needed to guess at all.
+const MIN_LETTERS: usize = 4;
+
+/// Fewest common words needed to tell Latin-script languages apart.
+const MIN_STOPWORDS: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Script {
+    Latin,
+    Cyrillic,
+    Greek,
+    Arabic,
+    Hebrew,
+    Devanagari,
+    Thai,
+    Hangul,
+    Kana,
+    Han,
+}
+
+fn script_of(c: char) -> Option<Script> {
+    match c as u32 {
+        0x0041..=0x005A | 0x0061..=0x007A | 0x00C0..=0x024F => Some(Script::Latin),
+        0x0370..=0x03FF => Some(Script::Greek),
+        0x0400..=0x04FF => Some(Script::Cyrillic),
+        0x0590..=0x05FF => Some(Script::Hebrew),
+        0x0600..=0x06FF => Some(Script::Arabic),
+        0x0900..=0x097F => Some(Script::Devanagari),
+        0x0E00..=0x0E7F => Some(Script::Thai),
+        0x1100..=0x11FF | 0xAC00..=0xD7AF => Some(Script::Hangul),
+        0x3040..=0x30FF => Some(Script::Kana),
+        0x4E00..=0x9FFF | 0x3400..=0x4DBF => Some(Script::Han),
+        _ => None,
+    }
+}
+
+/// Guess the language of `text`, `None` when there is too little to go on.
+pub fn detect_language(text: &str) -> Option<Detection> {
+    let mut counts: Vec<(Script, usize)> = Vec::new();
+    for script in text.chars().filter_map(script_of) {
+        match counts.iter_mut().find(|(s, _)| *s == script) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((script, 1)),
+        }
+    }
+    let letters: usize = counts.iter().map(|(_, count)| count).sum();
+    if letters < MIN_LETTERS {
+        return None;
+    }
+    let count = |script| counts.iter().find(|(s, _)| *s == script).map_or(0, |(_, count)| *count);
+    let share = |n: usize| n as f32 / letters as f32;
+
+    // Japanese mixes kana with Han characters
+    let kana = count(Script::Kana);
+    if kana > 0 {
+        return Some(Detection { language: "ja", confidence: share(kana + count(Script::Han)) });
+    }
+    let (script, script_count) = counts.iter().copied().max_by_key(|(_, count)| *count)?;
+    let confidence = share(script_count);
+    let language = match script {
+        Script::Latin => return detect_latin(text, confidence),
+        Script::Cyrillic if text.chars().any(|c| UKRAINIAN_CHARS.contains(c)) => "uk",
+        Script::Cyrillic => "ru",
+        Script::Greek => "el",
+        Script::Arabic => "ar",
+        Script::Hebrew => "he",
+        Script::Devanagari => "hi",
+        Script::Thai => "th",
+        Script::Hangul => "ko",
+        Script::Kana => "ja",
+        Script::Han => {
+            let simplified = text.chars().filter(|&c| SIMPLIFIED_CHARS.contains(c)).count();
+            let traditional = text.chars().filter(|&c| TRADITIONAL_CHARS.contains(c)).count();
+            if simplified > traditional {
+                "zh-Hans"
+            } else {
+                "zh-Hant"
+            }
+        }
+    };
+    Some(Detection { language, confidence })
+}
+
+/// Tell Latin-script languages apart by their common words.
+fn detect_latin(text: &str, script_share: f32) -> Option<Detection> {
+    let words: Vec<String> = text
+        .split(|c: char| !c.is_alphabetic())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    let mut hits: Vec<(&'static str, usize)> = STOPWORDS
+        .iter()
+        .map(|(language, stopwords)| {
+            let count = words.iter().filter(|word| stopwords.contains(&word.as_str())).count();
+            (*language, count)
+        })
+        .collect();
+    hits.sort_by(|a, b| b.1.cmp(&a.1));
+    let (language, best) = hits[0];
+    if best < MIN_STOPWORDS {
+        return None;
+    }
+    let total: usize = hits.iter().map(|(_, count)| count).sum();
+    Some(Detection {
+        language,
+        confidence: script_share * best as f32 / total as f32,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn language(text: &str) -> Option<&'static str> {
+        detect_language(text).map(|detection| detection.language)
+    }
+
+    #[test]
+    fn test_detect_language() {
+        assert_eq!(language("The quick brown fox jumps over the lazy dog and the cat."), Some("en"));
+        assert_eq!(language("Der Hund ist nicht mit der Katze auf dem Sofa."), Some("de"));
+        assert_eq!(language("Le chat est sur la table avec les enfants."), Some("fr"));
+        assert_eq!(language("Привет, как дела? Всё хорошо."), Some("ru"));
+        assert_eq!(language("Привіт, як справи? Все добре, дякую і тобі."), Some("uk"));
+        assert_eq!(language("今日はいい天気ですね。"), Some("ja"));
+        assert_eq!(language("안녕하세요 반갑습니다"), Some("ko"));
+        assert_eq!(language("我们这个国家发展得很快"), Some("zh-Hans"));
+        assert_eq!(language("我們這個國家發展得很快"), Some("zh-Hant"));
+
+        assert_eq!(language("OK 42"), None);
+        assert_eq!(language("Invoice 2024-118 Total EUR"), None);
+        assert!(detect_language("Привет, как дела? Всё хорошо.").unwrap().confidence > 0.9);
+    }
+}
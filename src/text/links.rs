@@ -0,0 +1,59 @@
+//! Finding web links in captured text.
+//!
+//! OCR of a screenshot often contains a URL the user wants to open or read
+//! rather than hear spelled out. Links are recognized by their scheme or a
+//! leading `www.`; punctuation around them (brackets, a full stop ending the
+//! sentence) is not part of the link.
+
+/// Characters trimmed from the end of a link, which usually belong to the sentence.
+const TRAILING_PUNCTUATION: &str = ".,;:!?'\")]}>";
+
+/// Whether `text` is an http(s) URL that can be opened or fetched.
+pub fn is_web_url(text: &str) -> bool {
+    let lower = text.to_ascii_lowercase();
+    let rest = lower
+        .strip_prefix("https://")
+        .or_else(|| lower.strip_prefix("http://"))
+        .unwrap_or_default();
+    rest.split('/').next().is_some_and(|host| host.contains('.') || host.starts_with("localhost"))
+        && !text.contains(char::is_whitespace)
+}
+
+/// Web links in `text`, in order of appearance without duplicates.
+///
+/// `www.` links get an `https://` scheme.
+pub fn find_urls(text: &str) -> Vec<String> {
+    let mut urls: Vec<String> = Vec::new();
+    for token in text.split_whitespace() {
+        let token = token.trim_start_matches(|c: char| "\"'([{<".contains(c));
+        let token = token.trim_end_matches(|c: char| TRAILING_PUNCTUATION.contains(c));
+        let url = if token.len() > 4 && token.get(..4).is_some_and(|prefix| prefix.eq_ignore_ascii_case("www.")) {
+            format!("https://{token}")
+        } else {
+            token.to_string()
+        };
+        if is_web_url(&url) && !urls.contains(&url) {
+            urls.push(url);
+        }
+    }
+    urls
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_urls() {
+        assert_eq!(
+            find_urls("Docs at https://example.com/docs/start. Also (www.example.org) and https://example.com/docs/start"),
+            vec!["https://example.com/docs/start", "https://www.example.org"]
+        );
+        assert_eq!(find_urls("Call http://localhost:8080/health?full=1, then retry"), vec!["http://localhost:8080/health?full=1"]);
+        assert!(find_urls("No links here, just www. and https:// alone").is_empty());
+
+        assert!(is_web_url("HTTPS://Example.com"));
+        assert!(!is_web_url("ftp://example.com"));
+        assert!(!is_web_url("WIFI:S:home;T:WPA;P:secret;;"));
+    }
+}
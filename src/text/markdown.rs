@@ -0,0 +1,101 @@
+//! Minimal Markdown to plain text conversion.
+//!
+//! Removes the markup that would otherwise be read aloud (heading markers,
+//! emphasis, link targets, list bullets, code fences) while keeping the text
+//! and its paragraph structure.
+
+/// Convert Markdown to plain text suitable for reading aloud.
+pub fn markdown_to_text(markdown: &str) -> String {
+    let mut lines = Vec::new();
+    let mut in_code_block = false;
+    for line in markdown.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            lines.push(line.to_string());
+            continue;
+        }
+        if is_horizontal_rule(trimmed) {
+            lines.push(String::new());
+            continue;
+        }
+        let mut content = trimmed;
+        while let Some(rest) = content.strip_prefix('>') {
+            content = rest.trim_start();
+        }
+        let hashes = content.len() - content.trim_start_matches('#').len();
+        if hashes > 0 && content[hashes..].starts_with(' ') {
+            content = content[hashes..].trim_start();
+        }
+        for bullet in ["- [ ] ", "- [x] ", "- ", "* ", "+ "] {
+            if let Some(rest) = content.strip_prefix(bullet) {
+                content = rest;
+                break;
+            }
+        }
+        lines.push(strip_inline(content));
+    }
+    lines.join("\n").trim().to_string()
+}
+
+/// Whether the line is a `---`, `***` or `___` rule.
+fn is_horizontal_rule(line: &str) -> bool {
+    let compact: String = line.chars().filter(|c| !c.is_whitespace()).collect();
+    compact.len() >= 3 && ['-', '*', '_'].iter().any(|&marker| compact.chars().all(|c| c == marker))
+}
+
+/// Remove inline markup: images and links keep their text, emphasis and code marks are dropped.
+fn strip_inline(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(c) = rest.chars().next() {
+        if c == '!' && rest[1..].starts_with('[') {
+            rest = &rest[1..];
+            continue;
+        }
+        if c == '[' {
+            if let Some((text, after)) = link_parts(rest) {
+                out.push_str(&strip_inline(text));
+                rest = after;
+                continue;
+            }
+        }
+        match c {
+            '*' | '`' => {}
+            // Underscores inside words (snake_case) are not emphasis
+            '_' if !out.ends_with(|p: char| p.is_alphanumeric()) || !rest[1..].starts_with(char::is_alphanumeric) => {}
+            _ => out.push(c),
+        }
+        rest = &rest[c.len_utf8()..];
+    }
+    out
+}
+
+/// Split `[text](target)rest` into the link text and the rest of the line.
+fn link_parts(s: &str) -> Option<(&str, &str)> {
+    let close = s.find("](")?;
+    let text = &s[1..close];
+    if text.contains(['[', ']']) {
+        return None;
+    }
+    let end = close + s[close..].find(')')?;
+    Some((text, &s[end + 1..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markdown_to_text() {
+        let markdown = "# Title\n\nSome **bold** and _italic_ text with a [link](https://example.com) and snake_case.\n\n- one\n- two\n\n> quoted `code`\n\n---\n\n```\nlet x = 1;\n```";
+        assert_eq!(
+            markdown_to_text(markdown),
+            "Title\n\nSome bold and italic text with a link and snake_case.\n\none\ntwo\n\nquoted code\n\n\n\nlet x = 1;"
+        );
+        assert_eq!(markdown_to_text("![A cat](cat.png)"), "A cat");
+    }
+}
@@ -0,0 +1,327 @@
+//! Reading math notation aloud.
+//!
+//! Technical text mixes LaTeX fragments (`$\frac{a}{b}$`), caret exponents
+//! (`x^2`) and Unicode symbols (`mc²`, `≤`, `½`) that TTS voices read as
+//! gibberish or skip. [`verbalize_math`] rewrites them as words: LaTeX inside
+//! `$...$`, `$$...$$`, `\(...\)` or `\[...\]` is converted fully, while plain
+//! text only has exponents and math symbols replaced.
+
+/// LaTeX commands read as a word or phrase.
+const LATEX_WORDS: &[(&str, &str)] = &[
+    ("alpha", "alpha"), ("beta", "beta"), ("gamma", "gamma"), ("delta", "delta"),
+    ("epsilon", "epsilon"), ("theta", "theta"), ("lambda", "lambda"), ("mu", "mu"),
+    ("pi", "pi"), ("rho", "rho"), ("sigma", "sigma"), ("tau", "tau"), ("phi", "phi"),
+    ("omega", "omega"), ("Delta", "delta"), ("Sigma", "sigma"), ("Omega", "omega"),
+    ("times", "times"), ("cdot", "times"), ("div", "divided by"), ("pm", "plus or minus"),
+    ("leq", "is less than or equal to"), ("le", "is less than or equal to"),
+    ("geq", "is greater than or equal to"), ("ge", "is greater than or equal to"),
+    ("neq", "is not equal to"), ("ne", "is not equal to"), ("approx", "is approximately"),
+    ("infty", "infinity"), ("to", "to"), ("rightarrow", "implies"), ("in", "in"),
+    ("partial", "partial"), ("nabla", "del"), ("ldots", "and so on"), ("cdots", "and so on"),
+    ("sin", "sine"), ("cos", "cosine"), ("tan", "tangent"), ("log", "log"), ("ln", "natural log"),
+    ("lim", "the limit"),
+];
+
+/// Big operators read as "the sum from ... to ... of".
+const LATEX_OPERATORS: &[(&str, &str)] = &[("sum", "the sum"), ("prod", "the product"), ("int", "the integral")];
+
+/// Unicode math symbols and the words they are read as.
+const SYMBOL_WORDS: &[(char, &str)] = &[
+    ('√', "the square root of"), ('≤', "is less than or equal to"), ('≥', "is greater than or equal to"),
+    ('≠', "is not equal to"), ('≈', "is approximately"), ('±', "plus or minus"), ('×', "times"),
+    ('÷', "divided by"), ('∞', "infinity"), ('π', "pi"), ('∑', "the sum of"), ('∫', "the integral of"),
+    ('½', "one half"), ('⅓', "one third"), ('⅔', "two thirds"), ('¼', "one quarter"), ('¾', "three quarters"),
+];
+
+/// Superscript characters and the digits or letters they stand for.
+const SUPERSCRIPTS: &[(char, char)] = &[
+    ('⁰', '0'), ('¹', '1'), ('²', '2'), ('³', '3'), ('⁴', '4'), ('⁵', '5'), ('⁶', '6'),
+    ('⁷', '7'), ('⁸', '8'), ('⁹', '9'), ('ⁿ', 'n'), ('⁻', '-'),
+];
+
+/// Plain digit or letter of a superscript character.
+fn superscript(c: char) -> Option<char> {
+    SUPERSCRIPTS.iter().find(|&&(sup, _)| sup == c).map(|&(_, plain)| plain)
+}
+
+/// Words for raising something to `exponent`.
+fn power_words(exponent: &str) -> String {
+    match exponent.trim() {
+        "2" => "squared".to_string(),
+        "3" => "cubed".to_string(),
+        exponent => match exponent.strip_prefix('-') {
+            Some(rest) => format!("to the power of minus {rest}"),
+            None => format!("to the power of {exponent}"),
+        },
+    }
+}
+
+/// Rewrite LaTeX fragments, exponents and math symbols in `text` as words.
+pub fn verbalize_math(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some((start, open, close)) = next_latex_delimiter(rest) {
+        let inner_start = start + open.len();
+        let Some(len) = rest[inner_start..].find(close) else {
+            break;
+        };
+        let inner = &rest[inner_start..inner_start + len];
+        let after = &rest[inner_start + len + close.len()..];
+        out.push_str(&verbalize_plain(&rest[..start]));
+        if open == "$" && !looks_like_inline_math(inner, after) {
+            // Currency or a lone dollar sign: keep the text as is
+            out.push_str(&verbalize_plain(open));
+            rest = &rest[inner_start..];
+            continue;
+        }
+        out.push(' ');
+        out.push_str(&latex_to_words(inner));
+        out.push(' ');
+        rest = after;
+    }
+    out.push_str(&verbalize_plain(rest));
+    collapse_spaces(&out)
+}
+
+/// Earliest LaTeX opening delimiter in `s`, with its matching closing delimiter.
+fn next_latex_delimiter(s: &str) -> Option<(usize, &'static str, &'static str)> {
+    [("$$", "$$"), ("\\(", "\\)"), ("\\[", "\\]"), ("$", "$")]
+        .into_iter()
+        .filter_map(|(open, close)| s.find(open).map(|start| (start, open, close)))
+        .min_by_key(|&(start, open, _)| (start, std::cmp::Reverse(open.len())))
+}
+
+/// Whether `$inner$` is math rather than prices like "$5 and $10" or
+/// variables like "$HOME/$USER": the opening `$` is followed by a non-space
+/// and the closing one by neither a letter nor a digit.
+fn looks_like_inline_math(inner: &str, after: &str) -> bool {
+    !inner.is_empty()
+        && !inner.starts_with(char::is_whitespace)
+        && !inner.ends_with(char::is_whitespace)
+        && !after.starts_with(char::is_alphanumeric)
+        && inner.contains(|c: char| c.is_alphabetic() || "\\^_=".contains(c))
+}
+
+/// Collapse runs of spaces (not line breaks), trim each line and drop the
+/// spaces left before punctuation ("r squared ," becomes "r squared,").
+fn collapse_spaces(text: &str) -> String {
+    text.split('\n')
+        .map(|line| {
+            let mut out = String::with_capacity(line.len());
+            for word in line.split(' ').filter(|word| !word.is_empty()) {
+                let punctuation = word.chars().all(|c| ",.;:!?".contains(c));
+                if !out.is_empty() && !punctuation {
+                    out.push(' ');
+                }
+                out.push_str(word);
+            }
+            out
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Replace exponents and math symbols in plain text.
+fn verbalize_plain(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if let Some(&(_, words)) = SYMBOL_WORDS.iter().find(|&&(symbol, _)| symbol == c) {
+            out.push_str(&format!(" {words} "));
+            continue;
+        }
+        let follows_operand = out.ends_with(|p: char| p.is_alphanumeric() || p == ')');
+        if let Some(first) = superscript(c).filter(|_| follows_operand) {
+            let mut exponent = first.to_string();
+            while let Some(plain) = chars.peek().copied().and_then(superscript) {
+                exponent.push(plain);
+                chars.next();
+            }
+            out.push_str(&format!(" {} ", power_words(&exponent)));
+            continue;
+        }
+        if c == '^' && follows_operand {
+            if let Some(exponent) = read_exponent(&mut chars) {
+                out.push_str(&format!(" {} ", power_words(&exponent)));
+                continue;
+            }
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Read a caret exponent: `{...}`, `(...)`, or a run of digits or letters.
+fn read_exponent(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> Option<String> {
+    match *chars.peek()? {
+        '{' | '(' => {
+            let close = if chars.next() == Some('{') { '}' } else { ')' };
+            let group: String = chars.by_ref().take_while(|&c| c != close).collect();
+            Some(latex_to_words(&group))
+        }
+        '-' => {
+            chars.next();
+            let digits: String = std::iter::from_fn(|| chars.next_if(char::is_ascii_digit)).collect();
+            Some(format!("-{digits}"))
+        }
+        c if c.is_ascii_digit() => Some(std::iter::from_fn(|| chars.next_if(char::is_ascii_digit)).collect()),
+        c if c.is_alphabetic() => Some(std::iter::from_fn(|| chars.next_if(|c| c.is_alphabetic())).collect()),
+        _ => None,
+    }
+}
+
+/// Read a LaTeX argument: a `{...}` group (nested braces allowed) or a single character.
+fn read_group(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> String {
+    while chars.next_if_eq(&' ').is_some() {}
+    match chars.next() {
+        Some('{') => {
+            let mut depth = 1;
+            let mut group = String::new();
+            for c in chars.by_ref() {
+                match c {
+                    '{' => depth += 1,
+                    '}' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            break;
+                        }
+                    }
+                    _ => {}
+                }
+                group.push(c);
+            }
+            group
+        }
+        Some('\\') => {
+            let name: String = std::iter::from_fn(|| chars.next_if(|c| c.is_ascii_alphabetic())).collect();
+            format!("\\{name}")
+        }
+        Some(c) => c.to_string(),
+        None => String::new(),
+    }
+}
+
+/// Convert a LaTeX math fragment to words.
+fn latex_to_words(latex: &str) -> String {
+    let mut out = String::with_capacity(latex.len() * 2);
+    let mut chars = latex.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                let name: String = std::iter::from_fn(|| chars.next_if(|c| c.is_ascii_alphabetic())).collect();
+                match name.as_str() {
+                    "frac" | "dfrac" | "tfrac" => {
+                        let numerator = latex_to_words(&read_group(&mut chars));
+                        let denominator = latex_to_words(&read_group(&mut chars));
+                        out.push_str(&format!(" {numerator} over {denominator} "));
+                    }
+                    "sqrt" => {
+                        let root = match chars.peek() {
+                            Some('[') => {
+                                chars.next();
+                                Some(chars.by_ref().take_while(|&c| c != ']').collect::<String>())
+                            }
+                            _ => None,
+                        };
+                        let radicand = latex_to_words(&read_group(&mut chars));
+                        match root.as_deref() {
+                            None | Some("2") => out.push_str(&format!(" the square root of {radicand} ")),
+                            Some("3") => out.push_str(&format!(" the cube root of {radicand} ")),
+                            Some(n) => out.push_str(&format!(" the {n}th root of {radicand} ")),
+                        }
+                    }
+                    "text" | "mathrm" | "mathbf" | "mathit" | "operatorname" => {
+                        out.push_str(&format!(" {} ", read_group(&mut chars)));
+                    }
+                    name => {
+                        if let Some(&(_, words)) = LATEX_OPERATORS.iter().find(|&&(op, _)| op == name) {
+                            let mut lower = None;
+                            let mut upper = None;
+                            while let Some(&limit) = chars.peek().filter(|&&c| c == '_' || c == '^') {
+                                chars.next();
+                                let bound = latex_to_words(&read_group(&mut chars));
+                                if limit == '_' { lower = Some(bound) } else { upper = Some(bound) }
+                            }
+                            out.push_str(&format!(" {words}"));
+                            if let Some(lower) = lower {
+                                out.push_str(&format!(" from {lower}"));
+                            }
+                            if let Some(upper) = upper {
+                                out.push_str(&format!(" to {upper}"));
+                            }
+                            out.push_str(" of ");
+                        } else if let Some(&(_, words)) = LATEX_WORDS.iter().find(|&&(command, _)| command == name) {
+                            out.push_str(&format!(" {words} "));
+                        } else if name.is_empty() {
+                            // Escaped character such as \{ or \,
+                            if let Some(escaped) = chars.next().filter(|c| "{}$%&#".contains(*c)) {
+                                out.push(escaped);
+                            }
+                        }
+                        // Other commands (\left, \right, spacing) are not read
+                    }
+                }
+            }
+            '^' => {
+                let exponent = latex_to_words(&read_group(&mut chars));
+                out.push_str(&format!(" {} ", power_words(&exponent)));
+            }
+            '_' => {
+                let subscript = latex_to_words(&read_group(&mut chars));
+                out.push_str(&format!(" sub {subscript} "));
+            }
+            '{' | '}' => out.push(' '),
+            '=' => out.push_str(" equals "),
+            '+' => out.push_str(" plus "),
+            '-' => out.push_str(" minus "),
+            '*' => out.push_str(" times "),
+            '/' => out.push_str(" over "),
+            '<' => out.push_str(" is less than "),
+            '>' => out.push_str(" is greater than "),
+            '~' => out.push(' '),
+            _ => out.push(c),
+        }
+    }
+    collapse_spaces(&out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Examples of technical text and how they should be read.
+    const CORPUS: &[(&str, &str)] = &[
+        ("x^2 + y^2 = z^2", "x squared + y squared = z squared"),
+        ("E = mc²", "E = mc squared"),
+        ("a volume of 2 m³", "a volume of 2 m cubed"),
+        ("2^10 = 1024", "2 to the power of 10 = 1024"),
+        ("x^n grows fast", "x to the power of n grows fast"),
+        ("10^-3 seconds", "10 to the power of minus 3 seconds"),
+        ("e^{i\\pi} + 1 = 0", "e to the power of i pi + 1 = 0"),
+        ("3 × 4 ≠ 11", "3 times 4 is not equal to 11"),
+        ("Add ½ cup", "Add one half cup"),
+        ("√2 is irrational", "the square root of 2 is irrational"),
+        ("where $\\frac{a}{b}$ is the ratio", "where a over b is the ratio"),
+        ("$\\sqrt{x^2+1}$", "the square root of x squared plus 1"),
+        ("$\\sqrt[3]{8} = 2$", "the cube root of 8 equals 2"),
+        ("$a_i + b_{ij}$", "a sub i plus b sub ij"),
+        ("\\(\\alpha \\leq \\beta\\)", "alpha is less than or equal to beta"),
+        (
+            "$$\\sum_{i=1}^{n} i = \\frac{n(n+1)}{2}$$",
+            "the sum from i equals 1 to n of i equals n(n plus 1) over 2",
+        ),
+        ("It costs $5 and $10 with tax", "It costs $5 and $10 with tax"),
+        ("Run cd $HOME/$USER first", "Run cd $HOME/$USER first"),
+        ("if $x > 0$, then", "if x is greater than 0, then"),
+        ("it grows with r^2, and m³.", "it grows with r squared, and m cubed."),
+        ("Plain text stays the same.\nSecond line", "Plain text stays the same.\nSecond line"),
+        ("version 2.0^ is not math", "version 2.0^ is not math"),
+    ];
+
+    #[test]
+    fn test_verbalize_math_corpus() {
+        for (input, expected) in CORPUS {
+            assert_eq!(verbalize_math(input), *expected, "input: {input}");
+        }
+    }
+}
@@ -0,0 +1,186 @@
+//! Text processing helpers applied to captured text before synthesis.
+
+pub mod chapters;
+pub mod chat;
+pub mod email;
+pub mod html;
+pub mod language;
+pub mod links;
+pub mod markdown;
+pub mod math;
+pub mod pipeline;
+pub mod prosody;
+pub mod rtf;
+pub mod sensitive;
+pub mod sentences;
+pub mod timing;
+
+/// Minimum length (in characters) before a capture is considered "long".
+///
+/// Short texts with many one-line paragraphs (lists, chat snippets) should
+/// not trigger the long text dialog, so both this and the paragraph count
+/// must be exceeded.
+const LONG_TEXT_MIN_CHARS: usize = 3000;
+
+/// Longest title derived from the first line of text.
+const MAX_TITLE_CHARS: usize = 80;
+
+/// Average speaking rate used for reading time estimates.
+const WORDS_PER_MINUTE: usize = 150;
+
+/// Split text into paragraphs separated by one or more blank lines.
+///
+/// Falls back to single line breaks when the text has no blank lines
+/// (common for OCR output and plain clipboard text).
+pub fn split_paragraphs(text: &str) -> Vec<&str> {
+    let by_blank_lines: Vec<&str> = text
+        .split("\n\n")
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .collect();
+
+    if by_blank_lines.len() > 1 {
+        return by_blank_lines;
+    }
+
+    text.lines()
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .collect()
+}
+
+/// Collapse runs of spaces within lines and drop empty lines and paragraphs.
+///
+/// Used by the markup converters, which emit `\n\n` between paragraphs.
+pub(crate) fn tidy_paragraphs(raw: &str) -> String {
+    raw.split("\n\n")
+        .map(|para| {
+            para.lines()
+                .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+                .filter(|line| !line.is_empty())
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .filter(|para| !para.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Return the first `count` paragraphs of the text, joined by blank lines.
+pub fn first_paragraphs(text: &str, count: usize) -> String {
+    split_paragraphs(text)
+        .into_iter()
+        .take(count)
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Check whether text is long enough to warrant the "read first N paragraphs" prompt.
+pub fn is_long_text(text: &str, preview_paragraphs: usize) -> bool {
+    text.chars().count() >= LONG_TEXT_MIN_CHARS && split_paragraphs(text).len() > preview_paragraphs
+}
+
+/// Estimate the reading time of the text in whole minutes (at least 1).
+pub fn estimated_minutes(text: &str) -> usize {
+    (text.split_whitespace().count() / WORDS_PER_MINUTE).max(1)
+}
+
+/// Hash text content for duplicate detection and audio caching.
+///
+/// Whitespace is normalized first so the same selection captured with
+/// different line wrapping or trailing newlines hashes identically.
+pub fn content_hash(text: &str) -> String {
+    let normalized = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    format!("{:x}", md5::compute(normalized.as_bytes()))
+}
+
+/// Derive a title from the first non-empty line of the text.
+///
+/// Long lines are cut at a word boundary. Callers should prefer a page title
+/// or source URL when one is known.
+pub fn title_from_text(text: &str) -> Option<String> {
+    let line = text.lines().map(str::trim).find(|line| !line.is_empty())?;
+    if line.chars().count() <= MAX_TITLE_CHARS {
+        return Some(line.to_string());
+    }
+    let cut: String = line.chars().take(MAX_TITLE_CHARS).collect();
+    let cut = match cut.rfind(' ') {
+        Some(space) if space > MAX_TITLE_CHARS / 2 => &cut[..space],
+        _ => cut.as_str(),
+    };
+    Some(format!("{}…", cut.trim_end_matches([',', ';', ':', ' '])))
+}
+
+/// Decode `%XX` escapes in URLs and EPUB hrefs.
+pub fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+            if let Some(byte) = hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+                out.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_paragraphs_blank_lines() {
+        let text = "First paragraph.\nStill first.\n\nSecond.\n\n\nThird.";
+        assert_eq!(
+            split_paragraphs(text),
+            vec!["First paragraph.\nStill first.", "Second.", "Third."]
+        );
+    }
+
+    #[test]
+    fn test_split_paragraphs_falls_back_to_lines() {
+        let text = "Line one\nLine two\n  \nLine three";
+        assert_eq!(split_paragraphs(text), vec!["Line one", "Line two", "Line three"]);
+    }
+
+    #[test]
+    fn test_first_paragraphs() {
+        let text = "A\n\nB\n\nC";
+        assert_eq!(first_paragraphs(text, 2), "A\n\nB");
+        assert_eq!(first_paragraphs(text, 10), "A\n\nB\n\nC");
+    }
+
+    #[test]
+    fn test_is_long_text_requires_length_and_paragraphs() {
+        let short = "para\n\n".repeat(20);
+        assert!(!is_long_text(&short, 5));
+
+        let long_single = "word ".repeat(1000);
+        assert!(!is_long_text(&long_single, 5));
+
+        let long = format!("{}\n\n", "word ".repeat(100)).repeat(10);
+        assert!(is_long_text(&long, 5));
+    }
+
+    #[test]
+    fn test_content_hash_ignores_whitespace_differences() {
+        assert_eq!(content_hash("Hello  world\n"), content_hash(" Hello\nworld"));
+        assert_ne!(content_hash("Hello world"), content_hash("Hello there"));
+    }
+
+    #[test]
+    fn test_title_from_text() {
+        assert_eq!(title_from_text("\n  Hello world.\nMore").as_deref(), Some("Hello world."));
+        let long = "word ".repeat(40);
+        let title = title_from_text(&long).unwrap();
+        assert!(title.ends_with('…') && title.chars().count() <= MAX_TITLE_CHARS + 1);
+        assert_eq!(title_from_text("  \n "), None);
+    }
+}
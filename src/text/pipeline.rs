@@ -0,0 +1,197 @@
+//! The passes captured text goes through before it is spoken.
+//!
+//! [`prepare`] cleans the text up for the kind of content it is and reads
+//! math aloud; Natural Reading, when on, rewrites the prepared text; [`pace`]
+//! then marks the pauses of lists. `insight-reader text preview` prints what
+//! the passes make of a text, and the golden files in `tests/golden` pin
+//! their output for each rule set.
+//!
+//! A [`Trace`] records each stage of a reading, down to what the provider is
+//! sent for each chunk, for `insight-reader speak --dry-run` and the
+//! Processing Chain window.
+
+use tracing::info;
+
+use super::prosody::{self, pace_lists, PAUSE};
+use super::sentences::sentences;
+use crate::config;
+use crate::model::{TTSBackend, TextProfile};
+
+/// Which passes run, as set under Spoken Text in the settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rules {
+    pub profile: TextProfile,
+    pub read_math: bool,
+    pub list_prosody: bool,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            profile: TextProfile::Auto,
+            read_math: true,
+            list_prosody: true,
+        }
+    }
+}
+
+impl Rules {
+    /// The rules from the settings.
+    pub fn from_config() -> Self {
+        Self {
+            profile: config::load_text_profile(),
+            read_math: config::load_read_math(),
+            list_prosody: config::load_list_prosody(),
+        }
+    }
+}
+
+/// Clean up text for the kind of content it is, detecting the kind with `TextProfile::Auto`.
+fn apply_profile(profile: TextProfile, text: String) -> String {
+    let profile = match profile {
+        TextProfile::Auto if super::email::looks_like_email(&text) => TextProfile::Email,
+        TextProfile::Auto if super::chat::looks_like_chat(&text) => TextProfile::Chat,
+        profile => profile,
+    };
+    match profile {
+        TextProfile::Chat => {
+            info!("Reading text as chat messages");
+            super::chat::chat_to_speech(&text)
+        }
+        TextProfile::Email => {
+            info!("Reading text as an email");
+            super::email::email_to_speech(&text)
+        }
+        TextProfile::Auto | TextProfile::Plain => text,
+    }
+}
+
+/// The passes that run before Natural Reading: the text profile and math.
+pub fn prepare(text: String, rules: &Rules) -> String {
+    let text = apply_profile(rules.profile, text);
+    if rules.read_math {
+        super::math::verbalize_math(&text)
+    } else {
+        text
+    }
+}
+
+/// Mark list pacing for the provider (see [`super::prosody`]) if it is on.
+/// Done last, so Natural Reading and the reading view never see the marks.
+pub fn pace(text: String, rules: &Rules) -> String {
+    if rules.list_prosody {
+        pace_lists(&text)
+    } else {
+        text
+    }
+}
+
+/// The text spoken for `text`, without Natural Reading or plugins.
+pub fn spoken_text(text: String, rules: &Rules) -> String {
+    pace(prepare(text, rules), rules)
+}
+
+/// `text` with its pauses written out as `[pause]`, for previews.
+pub fn show_pauses(text: &str) -> String {
+    text.replace(PAUSE, "[pause]")
+}
+
+/// One stage of the processing chain and what it made of the text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stage {
+    pub name: String,
+    /// The text after the stage; one entry per chunk once the text is segmented
+    pub output: Vec<String>,
+}
+
+/// The stages a reading went through, from the raw capture to the provider.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Trace {
+    pub stages: Vec<Stage>,
+}
+
+impl Trace {
+    /// Start a trace with the text as captured.
+    pub fn new(raw: &str) -> Self {
+        let mut trace = Self::default();
+        trace.record("Raw capture", raw);
+        trace
+    }
+
+    /// Record the text after the stage `name`.
+    pub fn record(&mut self, name: &str, text: &str) {
+        self.stages.push(Stage {
+            name: name.to_string(),
+            output: vec![show_pauses(text)],
+        });
+    }
+
+    /// Record how the text to speak is segmented and what `backend` is sent
+    /// for each chunk.
+    pub fn finish(&mut self, spoken: &str, backend: TTSBackend) {
+        let chunks = chunks(spoken, backend);
+        self.stages.push(Stage {
+            name: "Segmentation".to_string(),
+            output: chunks.iter().map(|chunk| show_pauses(chunk)).collect(),
+        });
+        self.stages.push(Stage {
+            name: format!("Provider payload ({})", config::backend_to_str(backend)),
+            output: chunks.iter().flat_map(|chunk| payload(chunk, backend)).collect(),
+        });
+    }
+}
+
+/// Trace of the chain for `raw`, without Natural Reading or plugins.
+pub fn trace(raw: &str, rules: &Rules, backend: TTSBackend) -> Trace {
+    let mut trace = Trace::new(raw);
+    let prepared = prepare(raw.to_string(), rules);
+    trace.record("Cleanup", &prepared);
+    let spoken = pace(prepared, rules);
+    trace.record("Pacing", &spoken);
+    trace.finish(&spoken, backend);
+    trace
+}
+
+/// Chunks `backend` synthesizes `text` in: sentence by sentence, except for
+/// single sentences and espeak-ng, which get the whole text.
+pub fn chunks(text: &str, backend: TTSBackend) -> Vec<&str> {
+    let parts = sentences(text);
+    if backend == TTSBackend::Espeak || parts.len() < 2 {
+        return vec![text.trim()];
+    }
+    parts
+}
+
+/// Requests `backend` is sent for `chunk`: Polly gets SSML for paced text,
+/// ElevenLabs break tags, and local voices one request per piece between
+/// pauses.
+pub fn payload(chunk: &str, backend: TTSBackend) -> Vec<String> {
+    match backend {
+        TTSBackend::AwsPolly if chunk.contains(PAUSE) => vec![prosody::to_ssml(chunk)],
+        TTSBackend::AwsPolly => vec![chunk.to_string()],
+        TTSBackend::ElevenLabs => vec![prosody::break_tags(chunk)],
+        TTSBackend::Piper | TTSBackend::MacOS | TTSBackend::Windows | TTSBackend::Espeak => {
+            prosody::pieces(chunk).map(str::to_string).collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trace() {
+        let trace = trace("Shopping:\n- Milk\n- Eggs & ham", &Rules::default(), TTSBackend::AwsPolly);
+        let names: Vec<&str> = trace.stages.iter().map(|stage| stage.name.as_str()).collect();
+        assert_eq!(names, ["Raw capture", "Cleanup", "Pacing", "Segmentation", "Provider payload (polly)"]);
+        assert_eq!(trace.stages[2].output, ["Shopping:\n[pause]Milk.\n[pause]Eggs & ham."]);
+        assert_eq!(trace.stages[3].output, ["Shopping:", "[pause]Milk.", "[pause]Eggs & ham."]);
+        assert_eq!(trace.stages[4].output[0], "Shopping:");
+        assert_eq!(trace.stages[4].output[2], "<speak><s>Eggs &amp; ham.</s></speak>");
+
+        let p = PAUSE;
+        assert_eq!(payload(&format!("One.{p}Two."), TTSBackend::Piper), ["One.", "Two."]);
+        assert_eq!(chunks("One. Two.", TTSBackend::Espeak), ["One. Two."]);
+    }
+}
@@ -0,0 +1,174 @@
+//! Pacing of lists and other line structure.
+//!
+//! Bulleted and numbered lists read as one breathless run of sentences.
+//! [`pace_lists`] marks where a short pause belongs with [`PAUSE`]: before
+//! each list item and after a line ending in a colon. Numbered items are read
+//! with a spoken ordinal ("First, ...", "Second, ..."). Each provider renders
+//! the pauses its own way: Polly as SSML breaks and sentences, ElevenLabs as
+//! break tags and Piper by synthesizing the pieces separately with silence in
+//! between, which also resets the pitch of each item.
+
+/// Marks a short pause in text to synthesize (INVISIBLE SEPARATOR).
+pub const PAUSE: char = '\u{2063}';
+
+/// Length of a [`PAUSE`].
+pub const PAUSE_MS: u32 = 350;
+
+/// Bullet characters that start a list item.
+const BULLETS: &[char] = &['-', '*', '•', '◦', '▪', '‣', '–', '+'];
+
+/// Ordinals spoken for numbered items.
+const ORDINALS: &[&str] = &[
+    "First", "Second", "Third", "Fourth", "Fifth", "Sixth", "Seventh", "Eighth", "Ninth", "Tenth",
+    "Eleventh", "Twelfth", "Thirteenth", "Fourteenth", "Fifteenth", "Sixteenth", "Seventeenth",
+    "Eighteenth", "Nineteenth", "Twentieth",
+];
+
+/// A list item line: its number if numbered, and its text.
+fn list_item(line: &str) -> Option<(Option<usize>, &str)> {
+    let line = line.trim_start();
+    if let Some(rest) = line.strip_prefix(BULLETS) {
+        let item = rest.strip_prefix(' ')?.trim();
+        return (!item.is_empty()).then_some((None, item));
+    }
+    let digits = line.len() - line.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    if digits == 0 || digits > 3 {
+        return None;
+    }
+    let number = line[..digits].parse().ok()?;
+    let item = line[digits..].strip_prefix(['.', ')'])?.strip_prefix(' ')?.trim();
+    (!item.is_empty()).then_some((Some(number), item))
+}
+
+/// How item `number` of a numbered list is introduced.
+fn ordinal(number: usize) -> String {
+    match number.checked_sub(1).and_then(|index| ORDINALS.get(index)) {
+        Some(ordinal) => format!("{ordinal}, "),
+        None => format!("Number {number}, "),
+    }
+}
+
+/// Mark pauses before list items and after lines ending in a colon, and read
+/// numbered items with a spoken ordinal. Items are ended with a full stop so
+/// each is read as a sentence.
+pub fn pace_lists(text: &str) -> String {
+    let lines: Vec<&str> = text.split('\n').collect();
+    let mut paced = String::with_capacity(text.len() + text.len() / 8);
+    for (index, line) in lines.iter().enumerate() {
+        if index > 0 {
+            paced.push('\n');
+        }
+        match list_item(line) {
+            Some((number, item)) => {
+                paced.push(PAUSE);
+                if let Some(number) = number {
+                    paced.push_str(&ordinal(number));
+                }
+                paced.push_str(item);
+                if !item.ends_with(['.', '!', '?', ':', ';']) {
+                    paced.push('.');
+                }
+            }
+            None => {
+                paced.push_str(line);
+                // A list item that follows pauses before itself already
+                let item_follows = lines.get(index + 1).is_some_and(|next| list_item(next).is_some());
+                if line.trim_end().ends_with(':') && !item_follows {
+                    paced.push(PAUSE);
+                }
+            }
+        }
+    }
+    paced
+}
+
+/// Synthesize the pieces of `text` between pauses one by one with
+/// `synthesize`, joined by [`PAUSE_MS`] of silence.
+pub fn synthesize_paced<E>(
+    text: &str,
+    sample_rate: u32,
+    mut synthesize: impl FnMut(&str) -> Result<Vec<f32>, E>,
+) -> Result<Vec<f32>, E> {
+    let silence = (sample_rate as u64 * PAUSE_MS as u64 / 1000) as usize;
+    let mut samples = Vec::new();
+    for (index, piece) in text.split(PAUSE).enumerate() {
+        if index > 0 {
+            samples.resize(samples.len() + silence, 0.0);
+        }
+        if !piece.trim().is_empty() {
+            samples.extend(synthesize(piece.trim())?);
+        }
+    }
+    Ok(samples)
+}
+
+/// The pieces of paced `text` between pauses, trimmed, empty ones left out.
+pub fn pieces(text: &str) -> impl Iterator<Item = &str> {
+    text.split(PAUSE).map(str::trim).filter(|piece| !piece.is_empty())
+}
+
+/// `text` with its pauses as ElevenLabs break tags.
+pub fn break_tags(text: &str) -> String {
+    text.replace(PAUSE, &format!(" <break time=\"{:.2}s\" /> ", PAUSE_MS as f32 / 1000.0))
+}
+
+/// Escape text for SSML.
+fn escape_ssml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Each paced piece of `text` as an SSML sentence, with a break where the
+/// pauses are.
+fn ssml_sentences(text: &str) -> String {
+    let pause = format!("<break time=\"{PAUSE_MS}ms\"/>");
+    let pieces: Vec<String> = pieces(text).map(|piece| format!("<s>{}</s>", escape_ssml(piece))).collect();
+    pieces.join(pause.as_str())
+}
+
+/// `text` as an SSML document: each paced piece a sentence, with a break
+/// where the pauses are.
+pub fn to_ssml(text: &str) -> String {
+    format!("<speak>{}</speak>", ssml_sentences(text))
+}
+
+/// `text` as an SSML document like [`to_ssml`], spoken `percent` higher
+/// (or lower, if negative) than the voice's own pitch.
+pub fn to_ssml_with_pitch(text: &str, percent: i32) -> String {
+    format!("<speak><prosody pitch=\"{percent:+}%\">{}</prosody></speak>", ssml_sentences(text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pace_lists() {
+        let text = "Steps:\n1. Open the lid\n2) Pour water!\n\nShopping\n- Milk\n• Eggs & ham\n-not a bullet\nNote: 2024 was good.\nAs follows:";
+        let paced = pace_lists(text);
+        let p = PAUSE;
+        assert_eq!(
+            paced,
+            format!(
+                "Steps:\n{p}First, Open the lid.\n{p}Second, Pour water!\n\nShopping\n{p}Milk.\n{p}Eggs & ham.\n-not a bullet\nNote: 2024 was good.\nAs follows:{p}"
+            )
+        );
+        assert_eq!(ordinal(42), "Number 42, ");
+
+        let samples = synthesize_paced::<()>(&format!("One{p}Two"), 1000, |piece| Ok(vec![1.0; piece.len()])).unwrap();
+        assert_eq!(samples.len(), 3 + PAUSE_MS as usize + 3);
+
+        assert_eq!(
+            to_ssml(&format!("Steps:{p}\n{p}Fish & chips.")),
+            "<speak><s>Steps:</s><break time=\"350ms\"/><s>Fish &amp; chips.</s></speak>"
+        );
+        assert_eq!(
+            to_ssml_with_pitch("Fish & chips.", -11),
+            "<speak><prosody pitch=\"-11%\"><s>Fish &amp; chips.</s></prosody></speak>"
+        );
+        assert_eq!(break_tags(&format!("One{p}Two")), "One <break time=\"0.35s\" /> Two");
+    }
+}
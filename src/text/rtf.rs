@@ -0,0 +1,160 @@
+//! Minimal RTF to plain text conversion.
+//!
+//! Keeps the text and its paragraph structure: `\par` ends a paragraph,
+//! `\line` breaks a line, list bullets (`\pntext`) are dropped, and the font,
+//! color and style tables, pictures and document info are skipped.
+
+/// Destinations (groups) whose content is never text.
+const SKIPPED_DESTINATIONS: &[&str] = &[
+    "fonttbl", "colortbl", "stylesheet", "info", "pict", "object", "header", "footer",
+    "listtable", "listoverridetable", "pntext", "generator", "themedata", "datastore",
+];
+
+/// Character used for `\'hh` escapes, assuming Windows-1252 (Latin-1 for the printable range).
+fn ansi_char(byte: u8) -> char {
+    match byte {
+        0x91 => '‘',
+        0x92 => '’',
+        0x93 => '“',
+        0x94 => '”',
+        0x96 => '–',
+        0x97 => '—',
+        0x85 => '…',
+        0x95 => '•',
+        _ => byte as char,
+    }
+}
+
+/// Convert RTF to plain text with paragraphs separated by blank lines.
+pub fn rtf_to_text(rtf: &str) -> String {
+    let mut out = String::with_capacity(rtf.len() / 2);
+    // Per group: whether its content is skipped, and the \ucN fallback length
+    let mut groups: Vec<(bool, usize)> = vec![(false, 1)];
+    // Fallback characters still to skip after a \uN escape
+    let mut skip_chars = 0usize;
+    let mut chars = rtf.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        let (skipped, uc) = *groups.last().unwrap_or(&(false, 1));
+        match c {
+            '{' => groups.push((skipped, uc)),
+            '}' => {
+                if groups.len() > 1 {
+                    groups.pop();
+                }
+            }
+            '\\' => {
+                let Some(&next) = chars.peek() else { break };
+                if !next.is_ascii_alphabetic() {
+                    chars.next();
+                    let text = match next {
+                        '\'' => {
+                            let hex: String = chars.by_ref().take(2).collect();
+                            u8::from_str_radix(&hex, 16).ok().map(ansi_char)
+                        }
+                        '*' => {
+                            // Unknown destination: skip the group
+                            if let Some(group) = groups.last_mut() {
+                                group.0 = true;
+                            }
+                            None
+                        }
+                        '~' => Some(' '),
+                        '\\' | '{' | '}' => Some(next),
+                        '\n' | '\r' => Some('\n'),
+                        _ => None,
+                    };
+                    if let Some(text) = text.filter(|_| !skipped) {
+                        if skip_chars > 0 {
+                            skip_chars -= 1;
+                        } else {
+                            out.push(text);
+                        }
+                    }
+                    continue;
+                }
+
+                let mut word = String::new();
+                while let Some(&c) = chars.peek().filter(|c| c.is_ascii_alphabetic()) {
+                    word.push(c);
+                    chars.next();
+                }
+                let mut param = String::new();
+                if chars.peek() == Some(&'-') {
+                    param.push('-');
+                    chars.next();
+                }
+                while let Some(&c) = chars.peek().filter(|c| c.is_ascii_digit()) {
+                    param.push(c);
+                    chars.next();
+                }
+                // A space after a control word belongs to it
+                if chars.peek() == Some(&' ') {
+                    chars.next();
+                }
+                let param: Option<i32> = param.parse().ok();
+
+                if SKIPPED_DESTINATIONS.contains(&word.as_str()) {
+                    if let Some(group) = groups.last_mut() {
+                        group.0 = true;
+                    }
+                    continue;
+                }
+                if skipped {
+                    continue;
+                }
+                match word.as_str() {
+                    "par" | "sect" | "page" => out.push_str("\n\n"),
+                    "line" | "row" => out.push('\n'),
+                    "tab" | "cell" => out.push(' '),
+                    "emdash" => out.push('—'),
+                    "endash" => out.push('–'),
+                    "bullet" => out.push('•'),
+                    "lquote" => out.push('‘'),
+                    "rquote" => out.push('’'),
+                    "ldblquote" => out.push('“'),
+                    "rdblquote" => out.push('”'),
+                    "uc" => {
+                        if let Some(group) = groups.last_mut() {
+                            group.1 = param.unwrap_or(1).max(0) as usize;
+                        }
+                    }
+                    "u" => {
+                        // Negative values are the signed 16-bit form
+                        let code = param.map(|p| if p < 0 { p + 65536 } else { p });
+                        if let Some(c) = code.and_then(|code| char::from_u32(code as u32)) {
+                            out.push(c);
+                        }
+                        skip_chars = uc;
+                    }
+                    _ => {}
+                }
+            }
+            '\r' | '\n' => {}
+            _ if skipped => {}
+            _ => {
+                if skip_chars > 0 {
+                    skip_chars -= 1;
+                } else {
+                    out.push(c);
+                }
+            }
+        }
+    }
+
+    super::tidy_paragraphs(&out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rtf_to_text() {
+        let rtf = r"{\rtf1\ansi\deff0{\fonttbl{\f0 Calibri;}}{\colortbl;\red0\green0\blue0;}
+{\*\generator Riched20;}\pard\b Title\b0\par
+Caf\'e9 \u8364? prices\line second line\par
+{\pntext\f1 \'b7\tab}Item one\par}";
+        assert_eq!(rtf_to_text(rtf), "Title\n\nCafé € prices\nsecond line\n\nItem one");
+    }
+}
@@ -0,0 +1,30 @@
+//! Splitting text into sentences for synthesis one sentence at a time.
+//!
+//! Sentences follow [`super::timing::sentence_spans`], within the paragraphs
+//! of [`super::split_paragraphs`], so the sentences synthesized separately are
+//! the same ones the reading view highlights and sentence skipping jumps to.
+
+use super::split_paragraphs;
+use super::timing::sentence_spans;
+
+/// Sentences of `text` in reading order, without surrounding whitespace.
+pub fn sentences(text: &str) -> Vec<&str> {
+    split_paragraphs(text)
+        .into_iter()
+        .flat_map(|paragraph| sentence_spans(paragraph).into_iter().map(move |span| paragraph[span].trim()))
+        .filter(|sentence| !sentence.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sentences() {
+        let text = "First one. Second sentence here. Third!\n\nNew paragraph";
+        assert_eq!(sentences(text), ["First one.", "Second sentence here.", "Third!", "New paragraph"]);
+        assert_eq!(sentences("One line\nanother line."), ["One line", "another line."]);
+        assert!(sentences(" \n\n ").is_empty());
+    }
+}
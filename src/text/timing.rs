@@ -0,0 +1,255 @@
+//! Locating the sentence being spoken during playback.
+//!
+//! Most providers do not report word timings, so the position is estimated
+//! from playback progress, assuming speech advances at a steady rate through
+//! the characters of the text. With Polly's speech marks, [`word_position`]
+//! places the word being spoken instead. Readings streamed sentence by
+//! sentence report where each synthesized sentence starts, which
+//! [`spoken_sentence`] uses for the transcript window.
+
+use std::ops::Range;
+use std::time::Duration;
+
+/// Where playback is within text split into paragraphs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SentencePosition {
+    /// Index of the current paragraph
+    pub paragraph: usize,
+    /// Byte range of the current sentence within that paragraph
+    pub sentence: Range<usize>,
+    /// Middle of the current sentence as a fraction of the whole text (0.0 to 1.0)
+    pub fraction: f32,
+    /// Byte range of the word being spoken within the paragraph, if known
+    pub word: Option<Range<usize>>,
+}
+
+/// Byte ranges of the sentences in a paragraph.
+///
+/// A sentence ends after `.`, `!`, `?` or `…` (and any closing quotes or
+/// brackets) followed by whitespace. Whitespace between sentences is left out.
+pub fn sentence_spans(paragraph: &str) -> Vec<Range<usize>> {
+    let mut spans = Vec::new();
+    let mut start = None;
+    let mut end_seen = false;
+    for (i, c) in paragraph.char_indices() {
+        if c.is_whitespace() {
+            if end_seen {
+                if let Some(s) = start.take() {
+                    spans.push(s..i);
+                }
+                end_seen = false;
+            }
+            continue;
+        }
+        start.get_or_insert(i);
+        match c {
+            '.' | '!' | '?' | '…' => end_seen = true,
+            '"' | '\'' | '”' | '’' | ')' | ']' => {}
+            _ => end_seen = false,
+        }
+    }
+    if let Some(s) = start {
+        spans.push(s..paragraph.trim_end().len());
+    }
+    spans
+}
+
+/// Paragraph index and byte offset `progress` (0.0 to 1.0) of the way
+/// through the characters of the paragraphs.
+fn offset_at(paragraphs: &[&str], progress: f32) -> Option<(usize, usize)> {
+    let total: usize = paragraphs.iter().map(|p| p.chars().count()).sum();
+    if total == 0 {
+        return None;
+    }
+    let target = ((progress.clamp(0.0, 1.0) * total as f32) as usize).min(total - 1);
+
+    let mut before = 0;
+    for (index, paragraph) in paragraphs.iter().enumerate() {
+        let len = paragraph.chars().count();
+        if target >= before + len {
+            before += len;
+            continue;
+        }
+        let offset = paragraph
+            .char_indices()
+            .nth(target - before)
+            .map_or(paragraph.len(), |(i, _)| i);
+        return Some((index, offset));
+    }
+    None
+}
+
+/// Position of the sentence around byte `offset` of paragraph `index`.
+fn position_at(paragraphs: &[&str], index: usize, offset: usize) -> SentencePosition {
+    let total: usize = paragraphs.iter().map(|p| p.chars().count()).sum();
+    let before: usize = paragraphs[..index].iter().map(|p| p.chars().count()).sum();
+    let paragraph = paragraphs[index];
+    let sentence = sentence_spans(paragraph)
+        .into_iter()
+        .find(|span| offset < span.end)
+        .unwrap_or(0..paragraph.len());
+    let middle = paragraph[..sentence.start].chars().count() + paragraph[sentence.clone()].chars().count() / 2;
+    SentencePosition {
+        paragraph: index,
+        sentence,
+        fraction: (before + middle) as f32 / total.max(1) as f32,
+        word: None,
+    }
+}
+
+/// Estimate the sentence being spoken at `progress` (0.0 to 1.0) through the paragraphs.
+pub fn sentence_at(paragraphs: &[&str], progress: f32) -> Option<SentencePosition> {
+    let (index, offset) = offset_at(paragraphs, progress)?;
+    Some(position_at(paragraphs, index, offset))
+}
+
+/// Place `word`, which starts `fraction` (0.0 to 1.0) of the way through
+/// the spoken text, in the paragraphs: the whole-word occurrence nearest that
+/// point in its paragraph, with the sentence around it.
+///
+/// The spoken text may differ a little from the paragraphs (paced lists, math
+/// read aloud), hence the search instead of using the offset as is. Without
+/// an occurrence, only the sentence is placed.
+pub fn word_position(paragraphs: &[&str], fraction: f32, word: &str) -> Option<SentencePosition> {
+    let (index, offset) = offset_at(paragraphs, fraction)?;
+    let paragraph = paragraphs[index];
+    let is_word_char = |c: Option<char>| c.is_some_and(char::is_alphanumeric);
+    let start = paragraph
+        .match_indices(word)
+        .map(|(start, _)| start)
+        .filter(|&start| {
+            !is_word_char(paragraph[..start].chars().next_back()) && !is_word_char(paragraph[start + word.len()..].chars().next())
+        })
+        .min_by_key(|&start| start.abs_diff(offset));
+    Some(match start {
+        Some(start) => SentencePosition {
+            word: Some(start..start + word.len()),
+            ..position_at(paragraphs, index, start)
+        },
+        None => position_at(paragraphs, index, offset),
+    })
+}
+
+/// Progress (0.0 to 1.0) at the start of the sentence `offset` sentences
+/// away from the one spoken at `progress`: 1 is the next one, -1 the previous.
+pub fn sentence_seek(paragraphs: &[&str], progress: f32, offset: i32) -> Option<f32> {
+    let total: usize = paragraphs.iter().map(|p| p.chars().count()).sum();
+    if total == 0 {
+        return None;
+    }
+    let mut starts = Vec::new();
+    let mut before = 0;
+    for paragraph in paragraphs {
+        for span in sentence_spans(paragraph) {
+            starts.push((before + paragraph[..span.start].chars().count()) as f32 / total as f32);
+        }
+        before += paragraph.chars().count();
+    }
+    let current = starts.iter().rposition(|&start| start <= progress).unwrap_or(0);
+    let target = (current as i64 + offset as i64).clamp(0, starts.len() as i64 - 1);
+    starts.get(target as usize).copied()
+}
+
+/// Index of the sentence spoken at `position` into a reading of `duration`.
+///
+/// `starts` are the start times of the sentences synthesized so far when the
+/// reading is streamed sentence by sentence; playback never runs past the
+/// last of them before the next is synthesized. Without them, the sentence is
+/// placed by its share of the characters.
+pub fn spoken_sentence(sentences: &[String], starts: &[Duration], position: Duration, duration: Duration) -> Option<usize> {
+    let last = sentences.len().checked_sub(1)?;
+    if let Some(index) = starts.iter().rposition(|&start| start <= position) {
+        return Some(index.min(last));
+    }
+    let total: usize = sentences.iter().map(|sentence| sentence.chars().count()).sum();
+    let progress = if duration.is_zero() {
+        0.0
+    } else {
+        (position.as_secs_f32() / duration.as_secs_f32()).clamp(0.0, 1.0)
+    };
+    let target = (progress * total as f32) as usize;
+    let mut before = 0;
+    Some(
+        sentences
+            .iter()
+            .position(|sentence| {
+                before += sentence.chars().count();
+                target < before
+            })
+            .unwrap_or(last),
+    )
+}
+
+/// Progress (0.0 to 1.0) at the start of sentence `index`, estimated from
+/// the characters of `sentences`.
+pub fn sentence_start_progress(sentences: &[String], index: usize) -> f32 {
+    let total: usize = sentences.iter().map(|sentence| sentence.chars().count()).sum();
+    let before: usize = sentences.iter().take(index).map(|sentence| sentence.chars().count()).sum();
+    before as f32 / total.max(1) as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sentence_spans() {
+        let paragraph = "Hello there. \"Is it?\" Yes… no";
+        let sentences: Vec<&str> = sentence_spans(paragraph)
+            .into_iter()
+            .map(|span| &paragraph[span])
+            .collect();
+        assert_eq!(sentences, vec!["Hello there.", "\"Is it?\"", "Yes…", "no"]);
+        assert_eq!(sentence_spans("v1.2 is out"), vec![0..11]);
+    }
+
+    #[test]
+    fn test_sentence_at_progress() {
+        let paragraphs = ["One. Two.", "Three."];
+        let start = sentence_at(&paragraphs, 0.0).unwrap();
+        assert_eq!((start.paragraph, start.sentence), (0, 0..4));
+        let middle = sentence_at(&paragraphs, 0.4).unwrap();
+        assert_eq!((middle.paragraph, middle.sentence), (0, 5..9));
+        let end = sentence_at(&paragraphs, 1.0).unwrap();
+        assert_eq!((end.paragraph, end.sentence), (1, 0..6));
+        assert!(start.fraction < middle.fraction && middle.fraction < end.fraction);
+        assert_eq!(sentence_at(&[], 0.5), None);
+    }
+
+    #[test]
+    fn test_spoken_sentence() {
+        let sentences = ["One.", "Two two.", "Three."].map(String::from);
+        let secs = Duration::from_secs;
+        // Estimated from the characters: "Two two." holds characters 4 to 11 of 18
+        assert_eq!(spoken_sentence(&sentences, &[], secs(5), secs(18)), Some(1));
+        assert_eq!(spoken_sentence(&sentences, &[], secs(18), secs(18)), Some(2));
+        // Start times reported by the stream win over the estimate
+        assert_eq!(spoken_sentence(&sentences, &[secs(0), secs(6)], secs(5), secs(18)), Some(0));
+        assert_eq!(spoken_sentence(&sentences, &[secs(0), secs(6)], secs(9), secs(18)), Some(1));
+        assert_eq!(spoken_sentence(&[], &[], secs(1), secs(2)), None);
+        assert_eq!(sentence_start_progress(&sentences, 2), 12.0 / 18.0);
+    }
+
+    #[test]
+    fn test_word_position() {
+        let paragraphs = ["The cat sat. The cat ran.", "Then it slept."];
+        // The occurrence nearest the point, whole words only ("The" is not in "Then")
+        let position = word_position(&paragraphs, 0.5, "cat").unwrap();
+        assert_eq!((position.paragraph, position.sentence, position.word), (0, 13..25, Some(17..20)));
+        let first = word_position(&paragraphs, 0.0, "The").unwrap();
+        assert_eq!(first.word, Some(0..3));
+        let missing = word_position(&paragraphs, 0.9, "dog").unwrap();
+        assert_eq!((missing.paragraph, missing.word), (1, None));
+    }
+
+    #[test]
+    fn test_sentence_seek() {
+        let paragraphs = ["One. Two.", "Three."];
+        assert_eq!(sentence_seek(&paragraphs, 0.1, 1), Some(5.0 / 15.0));
+        assert_eq!(sentence_seek(&paragraphs, 0.4, 1), Some(9.0 / 15.0));
+        assert_eq!(sentence_seek(&paragraphs, 0.7, -1), Some(5.0 / 15.0));
+        assert_eq!(sentence_seek(&paragraphs, 0.7, 5), Some(9.0 / 15.0));
+        assert_eq!(sentence_seek(&paragraphs, 0.0, -1), Some(0.0));
+        assert_eq!(sentence_seek(&[], 0.5, 1), None);
+    }
+}
@@ -0,0 +1,121 @@
+//! Keyboard operation, control labels and screen-reader friendly window titles.
+//!
+//! iced 0.14 has no AccessKit integration and does not expose an
+//! accessibility tree, so VoiceOver, NVDA and Orca cannot see widget names or
+//! roles, and giving the buttons accessible names is out of reach until it
+//! does. What the app covers instead:
+//! - every control of the main bar has a keyboard shortcut, and Escape closes
+//!   secondary windows;
+//! - icon-only buttons have a tooltip naming them and their keys (see
+//!   [`control_label`]), for sighted keyboard and magnifier users;
+//! - the main window title carries the playback state, which screen readers
+//!   announce with the window.
+
+use iced::keyboard::{key::Named, Key, Modifiers};
+
+use crate::activity::Activity;
+use crate::model::{App, Message};
+
+/// Main bar controls and their keys, as listed in the settings window.
+pub const MAIN_BAR_SHORTCUTS: &[(&str, &str)] = &[
+    ("Play or pause", "Space or K"),
+    ("Back 5 seconds", "Left or J"),
+    ("Forward 5 seconds", "Right or L"),
+    ("Previous sentence", "Shift+Left or ["),
+    ("Next sentence", "Shift+Right or ]"),
+    ("Stop", "Escape or S"),
+    ("Capture screen text", "C"),
+    ("Reading view", "R"),
+    ("Settings", "Ctrl+Comma"),
+];
+
+/// Tooltip of a main bar control: its name followed by its keys from
+/// [`MAIN_BAR_SHORTCUTS`] ("Stop (Escape or S)"), or just the name.
+pub fn control_label(control: &str) -> String {
+    match MAIN_BAR_SHORTCUTS.iter().find(|(name, _)| *name == control) {
+        Some((_, keys)) => format!("{control} ({keys})"),
+        None => control.to_string(),
+    }
+}
+
+/// Message for a key pressed in the main window, if it is a shortcut
+/// (see [`MAIN_BAR_SHORTCUTS`]).
+pub fn main_bar_shortcut(key: &Key, modifiers: Modifiers) -> Option<Message> {
+    let message = match key.as_ref() {
+        Key::Character(",") if modifiers.command() => Message::Settings,
+        Key::Named(Named::ArrowLeft) if modifiers == Modifiers::SHIFT => Message::SkipSentence(-1),
+        Key::Named(Named::ArrowRight) if modifiers == Modifiers::SHIFT => Message::SkipSentence(1),
+        _ if !modifiers.is_empty() => return None,
+        Key::Named(Named::Space) | Key::Character("k") => Message::PlayPause,
+        Key::Named(Named::ArrowLeft) | Key::Character("j") => Message::SkipBackward,
+        Key::Named(Named::ArrowRight) | Key::Character("l") => Message::SkipForward,
+        Key::Named(Named::Escape) | Key::Character("s") => Message::Stop,
+        Key::Character("[") => Message::SkipSentence(-1),
+        Key::Character("]") => Message::SkipSentence(1),
+        Key::Character("c") => Message::ScreenshotRequested,
+        Key::Character("r") => Message::OpenReadingView,
+        Key::Character("m") => Message::OpenMiniBar,
+        _ => return None,
+    };
+    Some(message)
+}
+
+/// Title of the main window, describing what the app is doing.
+///
+/// It only changes with the state, not with the progress, so screen readers
+/// announce it when something happens rather than at every percent played.
+pub fn main_window_title(app: &App) -> String {
+    let state = if let Some(ref error) = app.error_message {
+        format!("Error: {error}")
+    } else {
+        match &app.activity {
+            Activity::Loading { status } | Activity::Downloading { status } => status.clone(),
+            Activity::Playing => "Playing".to_string(),
+            Activity::Paused => "Paused".to_string(),
+            Activity::Failed { error } => format!("Error: {error}"),
+            Activity::Idle => return "Insight Reader".to_string(),
+        }
+    };
+    format!("Insight Reader - {state}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_main_bar_shortcuts() {
+        let shortcut = |key: Key, modifiers| main_bar_shortcut(&key, modifiers);
+        assert!(matches!(shortcut(Key::Named(Named::Space), Modifiers::empty()), Some(Message::PlayPause)));
+        assert!(matches!(shortcut(Key::Character("l".into()), Modifiers::empty()), Some(Message::SkipForward)));
+        assert!(matches!(shortcut(Key::Character(",".into()), Modifiers::COMMAND), Some(Message::Settings)));
+        assert!(matches!(shortcut(Key::Character("m".into()), Modifiers::empty()), Some(Message::OpenMiniBar)));
+        assert!(matches!(shortcut(Key::Character("]".into()), Modifiers::empty()), Some(Message::SkipSentence(1))));
+        assert!(matches!(
+            shortcut(Key::Named(Named::ArrowLeft), Modifiers::SHIFT),
+            Some(Message::SkipSentence(-1))
+        ));
+        assert!(shortcut(Key::Character("c".into()), Modifiers::CTRL).is_none());
+    }
+
+    #[test]
+    fn test_control_label_lists_keys() {
+        assert_eq!(control_label("Stop"), "Stop (Escape or S)");
+        assert_eq!(control_label("Settings"), "Settings (Ctrl+Comma)");
+        assert_eq!(control_label("Close"), "Close");
+    }
+
+    #[test]
+    fn test_main_window_title_follows_state_only() {
+        let mut app = App::default();
+        assert_eq!(main_window_title(&app), "Insight Reader");
+        app.activity = Activity::Playing;
+        app.progress = 0.4;
+        let playing = main_window_title(&app);
+        assert_eq!(playing, "Insight Reader - Playing");
+        app.progress = 0.41;
+        assert_eq!(main_window_title(&app), playing);
+        app.activity = Activity::Paused;
+        assert_eq!(main_window_title(&app), "Insight Reader - Paused");
+    }
+}
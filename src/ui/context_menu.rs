@@ -0,0 +1,56 @@
+//! Right-click menu of the main bar and mini players.
+//!
+//! The bar windows are too short for a drop-down, so the menu replaces the
+//! controls in place until an item is chosen or it is dismissed.
+
+use iced::widget::{button, container, row, Space};
+use iced::{Alignment, Element, Length};
+
+use crate::model::{App, Message};
+use crate::styles::{circle_button_style, transparent_button_style};
+use crate::ui::{white_text, with_tooltip};
+
+fn menu_item<'a>(label: &'a str, msg: Option<Message>) -> Element<'a, Message> {
+    button(white_text(label, 12))
+        .style(circle_button_style)
+        .padding([4.0, 10.0])
+        .on_press_maybe(msg)
+        .into()
+}
+
+/// Menu items in a row, with a close button on the right.
+pub fn bar_menu<'a>(app: &'a App) -> Element<'a, Message> {
+    let items = row![
+        menu_item("Read clipboard", Some(Message::ReadClipboard)),
+        menu_item("Replay", app.provider.is_some().then_some(Message::ReplayReading)),
+        menu_item("Export audio", app.provider.is_some().then_some(Message::ExportAudio)),
+        menu_item(
+            if app.reading_favorite { "Unfavorite" } else { "Favorite" },
+            app.history_entry.is_some().then_some(Message::ToggleFavorite),
+        ),
+        menu_item("Open history", Some(Message::OpenHistory)),
+        menu_item("Quit", Some(Message::Quit)),
+    ]
+    .spacing(6)
+    .align_y(Alignment::Center);
+
+    container(
+        row![
+            items,
+            Space::new().width(Length::Fill),
+            with_tooltip(
+                button(white_text("×", 16))
+                    .style(transparent_button_style)
+                    .padding([0.0, 0.0])
+                    .on_press(Message::CloseBarMenu),
+                "Close menu",
+            ),
+        ]
+        .align_y(Alignment::Center),
+    )
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .center_y(Length::Fill)
+    .padding([4.0, 16.0])
+    .into()
+}
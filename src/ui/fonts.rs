@@ -0,0 +1,49 @@
+//! Extra fonts loaded at startup from the app's fonts folder.
+//!
+//! Fonts such as OpenDyslexic can be dropped into `<app data>/fonts` (`.ttf`
+//! or `.otf`) when they are not installed system-wide; the reading font
+//! options pick them up by family name.
+
+use std::path::PathBuf;
+
+use iced::Task;
+use tracing::{debug, info, warn};
+
+/// Folder scanned for extra fonts.
+pub fn fonts_dir() -> PathBuf {
+    crate::sync::app_data_dir().join("fonts")
+}
+
+/// Read every font file in the fonts folder.
+async fn read_font_files() -> Vec<Vec<u8>> {
+    let dir = fonts_dir();
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        debug!(dir = %dir.display(), "No fonts folder");
+        return Vec::new();
+    };
+    entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("ttf") || ext.eq_ignore_ascii_case("otf"))
+        })
+        .filter_map(|path| match std::fs::read(&path) {
+            Ok(bytes) => {
+                info!(font = %path.display(), "Loading font");
+                Some(bytes)
+            }
+            Err(e) => {
+                warn!(error = %e, font = %path.display(), "Failed to read font");
+                None
+            }
+        })
+        .collect()
+}
+
+/// Load the fonts in the fonts folder in the background.
+pub fn load_user_fonts<T: Send + 'static>() -> Task<T> {
+    Task::future(read_font_files()).then(|fonts| {
+        Task::batch(fonts.into_iter().map(|bytes| iced::font::load(bytes).discard()))
+    })
+}
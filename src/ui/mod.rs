@@ -1,3 +1,42 @@
 //! UI modules organized by feature
 
+pub mod accessibility;
+pub mod context_menu;
+pub mod fonts;
+pub mod palette;
+pub mod reading;
+pub mod screenshot;
 pub mod settings;
+pub mod trainer;
+pub mod transcript;
+
+use iced::widget::{container, text, tooltip};
+use iced::{Color, Element};
+
+use crate::model::Message;
+use crate::styles::tooltip_style;
+
+/// Text in the slightly translucent white of labels across the windows.
+pub(crate) fn white_text<'a>(content: impl text::IntoFragment<'a>, size: u32) -> text::Text<'a> {
+    text(content)
+        .size(size)
+        .style(|_theme| iced::widget::text::Style {
+            color: Some(Color::from_rgba(1.0, 1.0, 1.0, 0.85)),
+        })
+}
+
+/// Wrap a control with a hover tooltip naming it, as icon-only buttons have
+/// no visible label (see [`accessibility::control_label`]).
+pub(crate) fn with_tooltip<'a>(content: impl Into<Element<'a, Message>>, label: impl Into<String>) -> Element<'a, Message> {
+    tooltip(
+        content,
+        container(text(label.into()).size(11).style(|_theme| iced::widget::text::Style {
+            color: Some(Color::WHITE),
+        }))
+        .padding([3.0, 8.0])
+        .style(tooltip_style),
+        tooltip::Position::Bottom,
+    )
+    .gap(4.0)
+    .into()
+}
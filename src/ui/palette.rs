@@ -0,0 +1,128 @@
+//! Command palette window: type to filter, Enter runs the first match.
+
+use iced::widget::{button, column, container, row, scrollable, text_input, Space};
+use iced::{Alignment, Background, Color, Element, Length};
+
+use crate::model::{App, Message, PaletteCommand};
+use crate::styles::{close_button_style, header_style, modal_content_style, transparent_button_style};
+use crate::ui::white_text;
+
+/// Widget ID of the filter input, focused when the palette opens.
+pub const PALETTE_INPUT_ID: &str = "command-palette-filter";
+
+/// Built-in commands followed by plugin actions, with their display labels.
+fn commands(app: &App) -> Vec<(String, PaletteCommand)> {
+    let mut commands = vec![
+        ("Read Selected Text".to_string(), PaletteCommand::ReadSelection),
+        ("Dictate Note".to_string(), PaletteCommand::DictateNote),
+        ("Pronunciation Trainer".to_string(), PaletteCommand::PronunciationTrainer),
+        ("Clipboard History".to_string(), PaletteCommand::ClipboardHistory),
+        ("Processing Chain".to_string(), PaletteCommand::ProcessingChain),
+        ("Reading View".to_string(), PaletteCommand::ReadingView),
+        ("Transcript".to_string(), PaletteCommand::Transcript),
+        ("Open Mini Player".to_string(), PaletteCommand::MiniPlayer),
+        ("Settings".to_string(), PaletteCommand::Settings),
+        ("Reload Plugins".to_string(), PaletteCommand::ReloadPlugins),
+    ];
+    if app.history_entry.is_some() {
+        let label = if app.reading_favorite { "Remove Reading from Favorites" } else { "Add Reading to Favorites" };
+        commands.push((label.to_string(), PaletteCommand::ToggleFavorite));
+    }
+    commands.extend(app.plugin_host.actions().into_iter().map(|action| {
+        (format!("{}: {}", action.plugin, action.name), PaletteCommand::Plugin(action))
+    }));
+    commands
+}
+
+/// Commands whose label contains every word of the filter (case-insensitive).
+fn matching_commands(app: &App) -> Vec<(String, PaletteCommand)> {
+    let filter = app.palette_filter.to_lowercase();
+    commands(app)
+        .into_iter()
+        .filter(|(label, _)| {
+            let label = label.to_lowercase();
+            filter.split_whitespace().all(|word| label.contains(word))
+        })
+        .collect()
+}
+
+/// Command palette window view.
+pub fn command_palette_view<'a>(app: &'a App) -> Element<'a, Message> {
+    let matches = matching_commands(app);
+
+    let mut filter_input = text_input("Type a command...", &app.palette_filter)
+        .id(PALETTE_INPUT_ID)
+        .on_input(Message::PaletteFilterChanged)
+        .padding(10)
+        .size(15);
+    if let Some((_, first)) = matches.first() {
+        filter_input = filter_input.on_submit(Message::RunPaletteCommand(first.clone()));
+    }
+
+    let list: Element<'a, Message> = if matches.is_empty() {
+        white_text("No matching commands", 13).into()
+    } else {
+        column(matches.into_iter().map(|(label, command)| {
+            button(white_text(label, 14))
+                .style(transparent_button_style)
+                .width(Length::Fill)
+                .padding([6.0, 8.0])
+                .on_press(Message::RunPaletteCommand(command))
+                .into()
+        }))
+        .spacing(2)
+        .into()
+    };
+
+    let selection_hint = match app.palette_text {
+        Some(ref text) => format!("Selection: {}", text.chars().take(60).collect::<String>()),
+        None => "No text selected".to_string(),
+    };
+
+    container(
+        column![
+            container(
+                row![
+                    white_text("Command Palette", 18),
+                    Space::new().width(Length::Fill),
+                    button(
+                        container(white_text("✕", 18))
+                            .width(Length::Fixed(28.0))
+                            .height(Length::Fixed(28.0))
+                            .center_x(Length::Fixed(28.0))
+                            .center_y(Length::Fixed(28.0)),
+                    )
+                    .style(close_button_style)
+                    .on_press(Message::CloseCommandPalette),
+                ]
+                .width(Length::Fill)
+                .align_y(Alignment::Center)
+            )
+            .width(Length::Fill)
+            .padding([14.0, 20.0])
+            .style(header_style),
+            container(
+                column![
+                    filter_input,
+                    white_text(selection_hint, 11),
+                    scrollable(list).height(Length::Fill),
+                ]
+                .spacing(10)
+            )
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .padding([16.0, 20.0])
+            .style(|_theme| container::Style {
+                background: Some(Background::Color(Color::from_rgb(0.12, 0.12, 0.14))),
+                ..Default::default()
+            }),
+        ]
+        .spacing(0)
+        .width(Length::Fill)
+        .height(Length::Fill),
+    )
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .style(modal_content_style)
+    .into()
+}
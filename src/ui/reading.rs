@@ -0,0 +1,160 @@
+//! Reading view window: the text being read, following the current sentence.
+//!
+//! In focus-follow mode the view scrolls to keep the current sentence near
+//! the middle and dims the other paragraphs (typewriter mode). The scrolling
+//! itself is driven from playback updates in `update.rs`. With providers that
+//! report word timings (Polly), the word being spoken is highlighted too.
+
+use iced::widget::{button, checkbox, column, container, rich_text, row, scrollable, span, Space};
+use iced::{Alignment, Background, Color, Element, Length};
+
+use crate::model::{App, Message};
+use crate::styles::{
+    close_button_style, color_scheme, header_style, modal_content_style, reading_font, reading_tint_colors,
+    white_checkbox_style,
+};
+use crate::text::timing::{sentence_at, word_position, SentencePosition};
+use crate::ui::white_text;
+
+/// Widget ID of the scrollable holding the text.
+pub const READING_SCROLL_ID: &str = "reading-view-text";
+
+/// Font size of the text in the reading view.
+const READING_TEXT_SIZE: f32 = 18.0;
+
+/// Position of the sentence being spoken, if the reading view has text and playback has started.
+pub fn current_position(app: &App) -> Option<SentencePosition> {
+    if !app.activity.has_speech() {
+        return None;
+    }
+    let text = app.reading_text.as_deref()?;
+    let paragraphs = crate::text::split_paragraphs(text);
+    match &app.current_word {
+        Some(word) => word_position(&paragraphs, word.fraction, &word.word),
+        None => sentence_at(&paragraphs, app.progress),
+    }
+}
+
+/// One paragraph, dimmed or with its current sentence highlighted.
+fn paragraph_view<'a>(
+    app: &App,
+    paragraph: &'a str,
+    index: usize,
+    position: Option<&SentencePosition>,
+    value: Color,
+) -> Element<'a, Message> {
+    let style = app.reading_style;
+    let current = position.filter(|position| position.paragraph == index);
+    let color = match (position, current) {
+        (Some(_), None) if style.focus_follow => Color { a: 1.0 - style.focus_dim, ..value },
+        _ => value,
+    };
+
+    let sentence_background = Color { a: 0.25, ..color_scheme().accent };
+    let spans = match current {
+        Some(position) => {
+            let sentence = position.sentence.clone();
+            // The word is highlighted within the sentence when it lies in it
+            let word = position
+                .word
+                .clone()
+                .filter(|word| sentence.start <= word.start && word.end <= sentence.end)
+                .unwrap_or(sentence.end..sentence.end);
+            vec![
+                span(&paragraph[..sentence.start]),
+                span(&paragraph[sentence.start..word.start]).background(sentence_background),
+                span(&paragraph[word.clone()]).background(Color { a: 0.6, ..color_scheme().accent }),
+                span(&paragraph[word.end..sentence.end]).background(sentence_background),
+                span(&paragraph[sentence.end..]),
+            ]
+        }
+        None => vec![span(paragraph)],
+    };
+
+    rich_text(spans)
+        .on_link_click(iced::never)
+        .font(reading_font(style.font))
+        .size(READING_TEXT_SIZE)
+        .line_height(iced::widget::text::LineHeight::Relative(style.line_spacing))
+        .color(color)
+        .into()
+}
+
+/// Reading view window view.
+pub fn reading_view<'a>(app: &'a App) -> Element<'a, Message> {
+    let (background, value) = reading_tint_colors(app.reading_style.tint);
+    let position = current_position(app);
+
+    let body: Element<'a, Message> = match app.reading_text.as_deref() {
+        Some(text) => scrollable(
+            column(
+                crate::text::split_paragraphs(text)
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, paragraph)| paragraph_view(app, paragraph, index, position.as_ref(), value)),
+            )
+            .spacing(READING_TEXT_SIZE)
+            .padding([24.0, 32.0]),
+        )
+        .id(READING_SCROLL_ID)
+        .height(Length::Fill)
+        .into(),
+        None => container(white_text("Nothing is being read", 14))
+            .center_x(Length::Fill)
+            .center_y(Length::Fill)
+            .into(),
+    };
+
+    container(
+        column![
+            container(
+                row![
+                    white_text("Reading View", 18),
+                    Space::new().width(Length::Fill),
+                    button(white_text("‹ Sentence", 13))
+                        .padding([4.0, 10.0])
+                        .style(close_button_style)
+                        .on_press(Message::SkipSentence(-1)),
+                    button(white_text("Sentence ›", 13))
+                        .padding([4.0, 10.0])
+                        .style(close_button_style)
+                        .on_press(Message::SkipSentence(1)),
+                    Space::new().width(Length::Fixed(16.0)),
+                    checkbox(app.reading_style.focus_follow)
+                        .label("Focus follow")
+                        .on_toggle(Message::FocusFollowToggled)
+                        .style(white_checkbox_style),
+                    Space::new().width(Length::Fixed(16.0)),
+                    button(
+                        container(white_text("✕", 18))
+                            .width(Length::Fixed(28.0))
+                            .height(Length::Fixed(28.0))
+                            .center_x(Length::Fixed(28.0))
+                            .center_y(Length::Fixed(28.0)),
+                    )
+                    .style(close_button_style)
+                    .on_press(Message::CloseReadingView),
+                ]
+                .width(Length::Fill)
+                .align_y(Alignment::Center)
+            )
+            .width(Length::Fill)
+            .padding([14.0, 20.0])
+            .style(header_style),
+            container(body)
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .style(move |_theme| container::Style {
+                    background: Some(Background::Color(background)),
+                    ..Default::default()
+                }),
+        ]
+        .spacing(0)
+        .width(Length::Fill)
+        .height(Length::Fill),
+    )
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .style(modal_content_style)
+    .into()
+}
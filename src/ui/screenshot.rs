@@ -0,0 +1,193 @@
+//! Zoom and pan state of the screenshot viewer.
+//!
+//! The viewer shows either the whole screenshot fitted to the window or a
+//! region of it at a fixed scale. The region is drawn with the image widget's
+//! crop, and the same region can be cropped out of the file to run OCR on
+//! just the visible part. Boxes of the lines OCR found are drawn over it.
+
+use iced::{Rectangle, Size, Vector};
+
+use crate::model::App;
+
+/// Zoom factor of one zoom button press or scroll step.
+pub const ZOOM_STEP: f32 = 1.25;
+
+/// Largest scale (screen pixels per image pixel).
+const MAX_SCALE: f32 = 8.0;
+
+/// Space taken by the header, the toolbar and the padding around the image.
+const CHROME_WIDTH: f32 = 40.0;
+const CHROME_HEIGHT: f32 = 150.0;
+
+/// Zoom and pan of the screenshot viewer.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ScreenshotViewport {
+    /// Screenshot size in pixels
+    pub image: Size,
+    /// Screen pixels per image pixel, `None` to fit the whole image
+    pub scale: Option<f32>,
+    /// Image point shown at the center of the viewer
+    pub center: Vector,
+}
+
+impl ScreenshotViewport {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            image: Size::new(width as f32, height as f32),
+            scale: None,
+            center: Vector::new(width as f32 / 2.0, height as f32 / 2.0),
+        }
+    }
+
+    /// Scale at which the whole image fits in `viewer`.
+    fn fit_scale(&self, viewer: Size) -> f32 {
+        if self.image.width <= 0.0 || self.image.height <= 0.0 {
+            return 1.0;
+        }
+        (viewer.width / self.image.width).min(viewer.height / self.image.height)
+    }
+
+    /// Scale the image is shown at.
+    pub fn current_scale(&self, viewer: Size) -> f32 {
+        self.scale.unwrap_or_else(|| self.fit_scale(viewer))
+    }
+
+    /// Multiply the scale by `factor`, going back to fit when zoomed out past it.
+    pub fn zoom(&mut self, factor: f32, viewer: Size) {
+        let scale = self.current_scale(viewer) * factor;
+        self.scale = (scale > self.fit_scale(viewer)).then_some(scale.min(MAX_SCALE));
+        self.clamp_center(viewer);
+    }
+
+    /// Show the image one image pixel per screen pixel.
+    pub fn actual_size(&mut self, viewer: Size) {
+        self.scale = Some(1.0);
+        self.clamp_center(viewer);
+    }
+
+    pub fn fit(&mut self) {
+        self.scale = None;
+        self.center = Vector::new(self.image.width / 2.0, self.image.height / 2.0);
+    }
+
+    /// Move the image by `delta` screen pixels (dragging right shows more of the left side).
+    pub fn pan(&mut self, delta: Vector, viewer: Size) {
+        let scale = self.current_scale(viewer);
+        self.center = self.center - delta * (1.0 / scale);
+        self.clamp_center(viewer);
+    }
+
+    /// Keep the visible region inside the image.
+    fn clamp_center(&mut self, viewer: Size) {
+        let scale = self.current_scale(viewer);
+        let half_width = (viewer.width / scale / 2.0).min(self.image.width / 2.0);
+        let half_height = (viewer.height / scale / 2.0).min(self.image.height / 2.0);
+        self.center.x = self.center.x.clamp(half_width, self.image.width - half_width);
+        self.center.y = self.center.y.clamp(half_height, self.image.height - half_height);
+    }
+
+    /// Visible part of the image in pixels, `None` when the whole image is shown.
+    pub fn region(&self, viewer: Size) -> Option<Rectangle<u32>> {
+        let scale = self.scale?;
+        let width = (viewer.width / scale).min(self.image.width);
+        let height = (viewer.height / scale).min(self.image.height);
+        if width >= self.image.width && height >= self.image.height {
+            return None;
+        }
+        let x = (self.center.x - width / 2.0).clamp(0.0, self.image.width - width);
+        let y = (self.center.y - height / 2.0).clamp(0.0, self.image.height - height);
+        Some(Rectangle {
+            x: x.round() as u32,
+            y: y.round() as u32,
+            width: (width.round() as u32).max(1),
+            height: (height.round() as u32).max(1),
+        })
+    }
+
+    /// Where a rectangle of the image is drawn in an `area` showing `region` of it
+    /// (`None` for the whole image), clipped to the visible part.
+    ///
+    /// The shown part is scaled to fit and centered, like the image widget's `ContentFit::Contain`.
+    pub fn to_viewer(&self, bounds: Rectangle, region: Option<Rectangle<u32>>, area: Size) -> Option<Rectangle> {
+        let shown = match region {
+            Some(region) => Rectangle {
+                x: region.x as f32,
+                y: region.y as f32,
+                width: region.width as f32,
+                height: region.height as f32,
+            },
+            None => Rectangle::with_size(self.image),
+        };
+        if shown.width <= 0.0 || shown.height <= 0.0 {
+            return None;
+        }
+        let scale = (area.width / shown.width).min(area.height / shown.height);
+        let left = (area.width - shown.width * scale) / 2.0;
+        let top = (area.height - shown.height * scale) / 2.0;
+        let visible = bounds.intersection(&shown)?;
+        Some(Rectangle {
+            x: left + (visible.x - shown.x) * scale,
+            y: top + (visible.y - shown.y) * scale,
+            width: visible.width * scale,
+            height: visible.height * scale,
+        })
+    }
+}
+
+/// Size of the image area of the screenshot viewer.
+pub fn viewer_size(app: &App) -> Size {
+    let window = app
+        .screenshot_window_id
+        .and_then(|id| app.window_geometry.get(&id))
+        .map(|(_, geometry)| Size::new(geometry.width, geometry.height))
+        .unwrap_or(Size::new(800.0, 600.0));
+    Size::new(
+        (window.width - CHROME_WIDTH).max(1.0),
+        (window.height - CHROME_HEIGHT).max(1.0),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zoom_and_pan_region() {
+        let viewer = Size::new(400.0, 300.0);
+        let mut viewport = ScreenshotViewport::new(1600, 1200);
+        assert_eq!(viewport.region(viewer), None);
+
+        viewport.actual_size(viewer);
+        assert_eq!(viewport.region(viewer), Some(Rectangle { x: 600, y: 450, width: 400, height: 300 }));
+
+        // Dragging right and down shows the top-left part, clamped to the image
+        viewport.pan(Vector::new(1000.0, 1000.0), viewer);
+        assert_eq!(viewport.region(viewer), Some(Rectangle { x: 0, y: 0, width: 400, height: 300 }));
+
+        viewport.zoom(1.0 / 8.0, viewer);
+        assert_eq!(viewport.scale, None);
+        assert_eq!(viewport.region(viewer), None);
+    }
+
+    #[test]
+    fn test_to_viewer() {
+        let viewport = ScreenshotViewport::new(800, 400);
+        let line = Rectangle { x: 100.0, y: 100.0, width: 200.0, height: 20.0 };
+
+        // Whole image fitted in a taller area: half size, centered vertically
+        let area = Size::new(400.0, 400.0);
+        assert_eq!(
+            viewport.to_viewer(line, None, area),
+            Some(Rectangle { x: 50.0, y: 150.0, width: 100.0, height: 10.0 })
+        );
+
+        // Zoomed in on a region: the line is clipped to it
+        let region = Rectangle { x: 200, y: 0, width: 400, height: 400 };
+        assert_eq!(
+            viewport.to_viewer(line, Some(region), area),
+            Some(Rectangle { x: 0.0, y: 100.0, width: 100.0, height: 20.0 })
+        );
+        let outside = Rectangle { x: 700.0, ..line };
+        assert_eq!(viewport.to_viewer(outside, Some(region), area), None);
+    }
+}
@@ -0,0 +1,122 @@
+//! Voice acceleration settings UI component (hardware Piper runs voices on, benchmark)
+
+use iced::widget::{button, column, container, radio, row, text, Space};
+use iced::{Alignment, Color, Element, Length};
+
+use crate::model::{App, Message};
+use crate::providers::recommended_provider;
+use crate::styles::{circle_button_style, section_style, white_radio_style};
+use crate::ui::white_text;
+
+/// Dimmed text for notes and providers that cannot be selected.
+fn note_text<'a>(content: impl text::IntoFragment<'a>) -> text::Text<'a> {
+    text(content).size(11).style(|_theme| iced::widget::text::Style {
+        color: Some(Color::from_rgba(1.0, 1.0, 1.0, 0.5)),
+    })
+}
+
+/// Create the voice acceleration settings section for the settings window
+pub fn acceleration_settings_section<'a>(app: &'a App) -> Element<'a, Message> {
+    let controls: Element<'a, Message> = match app.available_execution_providers {
+        None => white_text("Detecting hardware...", 12).into(),
+        Some(ref providers) => {
+            let choices = column(providers.iter().map(|&provider| -> Element<'a, Message> {
+                if provider.piper_args().is_some() {
+                    radio(provider.label(), provider, Some(app.execution_provider), Message::ExecutionProviderSelected)
+                        .style(white_radio_style)
+                        .into()
+                } else {
+                    note_text(format!("{} (found, but the Piper command cannot use it)", provider.label())).into()
+                }
+            }))
+            .spacing(6);
+
+            let usable = providers.iter().filter(|p| p.piper_args().is_some()).count();
+            let benchmark: Element<'a, Message> = if usable > 1 {
+                column![
+                    Space::new().height(Length::Fixed(8.0)),
+                    button(white_text(
+                        if app.execution_benchmark_running { "Benchmarking..." } else { "Benchmark" },
+                        12
+                    ))
+                    .style(circle_button_style)
+                    .padding([4.0, 10.0])
+                    .on_press_maybe((!app.execution_benchmark_running).then_some(Message::BenchmarkExecutionProviders)),
+                    benchmark_results(app),
+                ]
+                .into()
+            } else {
+                column![
+                    Space::new().height(Length::Fixed(6.0)),
+                    note_text("No GPU support found. Install onnxruntime-gpu next to Piper to use an NVIDIA GPU."),
+                ]
+                .into()
+            };
+
+            column![choices, benchmark].into()
+        }
+    };
+
+    let controls = column![
+        white_text("Run Piper voices on a GPU to speed up long readings", 12),
+        Space::new().height(Length::Fixed(8.0)),
+        controls,
+    ]
+    .spacing(0);
+
+    container(
+        row![
+            container(white_text("Voice Acceleration", 14))
+                .width(Length::Fixed(120.0))
+                .align_x(Alignment::Start),
+            Space::new().width(Length::Fixed(16.0)),
+            container(controls)
+                .width(Length::Fill)
+                .align_x(Alignment::Start),
+        ]
+        .align_y(Alignment::Center)
+        .width(Length::Fill)
+        .padding([12.0, 16.0])
+    )
+    .style(section_style)
+    .into()
+}
+
+/// Timings of the last benchmark and the provider it recommends.
+fn benchmark_results<'a>(app: &'a App) -> Element<'a, Message> {
+    let timings = match app.execution_benchmark {
+        None => return column![].into(),
+        Some(Err(ref e)) => {
+            return column![Space::new().height(Length::Fixed(6.0)), note_text(e.as_str())].into();
+        }
+        Some(Ok(ref timings)) => timings,
+    };
+    let summary = timings
+        .iter()
+        .map(|(provider, elapsed)| format!("{} {:.1} s", provider.label(), elapsed.as_secs_f32()))
+        .collect::<Vec<_>>()
+        .join(" · ");
+
+    let recommendation: Element<'a, Message> = match recommended_provider(timings) {
+        Some(best) if best != app.execution_provider => row![
+            white_text(format!("{} is fastest", best.label()), 12),
+            Space::new().width(Length::Fixed(8.0)),
+            button(white_text("Use it", 12))
+                .style(circle_button_style)
+                .padding([2.0, 10.0])
+                .on_press(Message::ExecutionProviderSelected(best)),
+        ]
+        .align_y(Alignment::Center)
+        .into(),
+        Some(best) => white_text(format!("{} is fastest and already selected", best.label()), 12).into(),
+        None => column![].into(),
+    };
+
+    column![
+        Space::new().height(Length::Fixed(6.0)),
+        note_text(summary),
+        Space::new().height(Length::Fixed(4.0)),
+        recommendation,
+    ]
+    .into()
+}
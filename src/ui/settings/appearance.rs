@@ -0,0 +1,118 @@
+//! Color scheme and waveform settings UI component
+
+use iced::widget::{column, container, radio, row, text_input, Space};
+use iced::{Alignment, Background, Border, Color, Element, Length};
+
+use crate::model::{App, ColorPreset, ColorRole, Message, VisualizerStyle};
+use crate::styles::{parse_hex_color, section_style, white_radio_style};
+use crate::ui::white_text;
+
+/// Hex input for one custom color, with a swatch showing the parsed color.
+fn color_input<'a>(app: &'a App, label: &'a str, role: ColorRole) -> Element<'a, Message> {
+    let value = app.custom_colors.get(role);
+    let swatch = match parse_hex_color(value) {
+        Some(color) => container(Space::new())
+            .width(Length::Fixed(18.0))
+            .height(Length::Fixed(18.0))
+            .style(move |_theme| container::Style {
+                background: Some(Background::Color(color)),
+                border: Border {
+                    color: Color::from_rgba(1.0, 1.0, 1.0, 0.6),
+                    width: 1.0,
+                    radius: 3.0.into(),
+                },
+                ..Default::default()
+            }),
+        None => container(white_text("?", 12))
+            .width(Length::Fixed(18.0))
+            .center_x(Length::Fixed(18.0)),
+    };
+    row![
+        container(white_text(label, 12)).width(Length::Fixed(80.0)),
+        text_input("#rrggbb", value)
+            .on_input(move |value| Message::CustomColorChanged(role, value))
+            .padding(4)
+            .size(12)
+            .width(Length::Fixed(100.0)),
+        Space::new().width(Length::Fixed(8.0)),
+        swatch,
+    ]
+    .align_y(Alignment::Center)
+    .into()
+}
+
+/// Create the appearance settings section for the settings window
+pub fn appearance_settings_section<'a>(app: &'a App) -> Element<'a, Message> {
+    let preset = |label, preset| {
+        radio(label, preset, Some(app.color_preset), Message::ColorPresetSelected).style(white_radio_style)
+    };
+
+    let mut controls = column![
+        preset("Default", ColorPreset::Default),
+        Space::new().height(Length::Fixed(6.0)),
+        preset("High contrast (thick yellow progress bar)", ColorPreset::HighContrast),
+        Space::new().height(Length::Fixed(6.0)),
+        preset("Warm", ColorPreset::Warm),
+        Space::new().height(Length::Fixed(6.0)),
+        preset("Custom", ColorPreset::Custom),
+    ]
+    .spacing(0);
+
+    if app.color_preset == ColorPreset::Custom {
+        controls = controls.push(
+            column![
+                color_input(app, "Accent", ColorRole::Accent),
+                color_input(app, "Progress", ColorRole::Progress),
+                color_input(app, "Waveform", ColorRole::Waveform),
+            ]
+            .spacing(6)
+            .padding([8.0, 0.0]),
+        );
+    }
+
+    let style = |label, style| {
+        radio(label, style, Some(app.visualizer_style), Message::VisualizerStyleSelected).style(white_radio_style)
+    };
+    let waveform_controls = column![
+        row![
+            style("Bars", VisualizerStyle::Bars),
+            style("Mirrored", VisualizerStyle::Mirrored),
+            style("Level meter", VisualizerStyle::Level),
+            style("Off", VisualizerStyle::Off),
+        ]
+        .spacing(16),
+        white_text("Off also skips analyzing the audio, saving some CPU.", 12),
+    ]
+    .spacing(6);
+
+    container(
+        column![
+            row![
+                container(white_text("Colors", 14))
+                    .width(Length::Fixed(120.0))
+                    .align_x(Alignment::Start),
+                Space::new().width(Length::Fixed(16.0)),
+                container(controls)
+                    .width(Length::Fill)
+                    .align_x(Alignment::Start),
+            ]
+            .align_y(Alignment::Center)
+            .width(Length::Fill),
+            row![
+                container(white_text("Waveform", 14))
+                    .width(Length::Fixed(120.0))
+                    .align_x(Alignment::Start),
+                Space::new().width(Length::Fixed(16.0)),
+                container(waveform_controls)
+                    .width(Length::Fill)
+                    .align_x(Alignment::Start),
+            ]
+            .align_y(Alignment::Center)
+            .width(Length::Fill),
+        ]
+        .spacing(16)
+        .padding([12.0, 16.0])
+    )
+    .style(section_style)
+    .into()
+}
@@ -0,0 +1,72 @@
+//! Audio cue settings UI component (on/off, volume, sound set, playback fades)
+
+use iced::widget::{checkbox, column, container, radio, row, slider, Space};
+use iced::{Alignment, Element, Length};
+
+use crate::config::MAX_FADE_MS;
+use crate::model::{App, Message};
+use crate::providers::EarconSet;
+use crate::styles::{section_style, white_checkbox_style, white_radio_style};
+use crate::ui::white_text;
+
+/// Create the audio cue settings section for the settings window
+pub fn audio_cues_settings_section<'a>(app: &'a App) -> Element<'a, Message> {
+    let earcons = app.earcons;
+    let set = |label, set| {
+        radio(label, set, Some(earcons.set), Message::EarconSetSelected).style(white_radio_style)
+    };
+
+    let controls = column![
+        checkbox(earcons.enabled)
+            .label("Play audio cues")
+            .on_toggle(Message::EarconsToggled)
+            .style(white_checkbox_style),
+        white_text(format!("Volume: {:.0}%", earcons.volume * 100.0), 12),
+        slider(0.0..=1.0, earcons.volume, Message::EarconVolumeChanged)
+            .step(0.05)
+            .width(Length::Fixed(220.0)),
+        Space::new().height(Length::Fixed(10.0)),
+        white_text("Sounds", 12),
+        row![
+            set("Chime", EarconSet::Chime),
+            set("Beep", EarconSet::Beep),
+            set("Click", EarconSet::Click),
+        ]
+        .spacing(12),
+        Space::new().height(Length::Fixed(8.0)),
+        white_text(
+            "Short sounds when reading starts, ends or fails, and in place of sentences that could not be read. Useful when the window is hidden.",
+            11,
+        ),
+        Space::new().height(Length::Fixed(10.0)),
+        white_text(
+            match app.fade_ms {
+                0 => "Fade in and out: off".to_string(),
+                ms => format!("Fade in and out: {ms} ms"),
+            },
+            12
+        ),
+        slider(0.0..=MAX_FADE_MS as f32, app.fade_ms as f32, |ms| Message::FadeDurationChanged(ms as u32))
+            .step(10.0)
+            .width(Length::Fixed(220.0)),
+        white_text("Softens the start and end of the voice on play, pause, stop and skips.", 11),
+    ]
+    .spacing(4);
+
+    container(
+        row![
+            container(white_text("Audio Cues", 14))
+                .width(Length::Fixed(120.0))
+                .align_x(Alignment::Start),
+            Space::new().width(Length::Fixed(16.0)),
+            container(controls)
+                .width(Length::Fill)
+                .align_x(Alignment::Start),
+        ]
+        .align_y(Alignment::Center)
+        .width(Length::Fill)
+        .padding([12.0, 16.0])
+    )
+    .style(section_style)
+    .into()
+}
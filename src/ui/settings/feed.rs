@@ -0,0 +1,85 @@
+//! Podcast feed settings UI component
+
+use iced::widget::{button, checkbox, column, container, row, text_input, Space};
+use iced::{Alignment, Color, Element, Length};
+
+use crate::model::{App, Message};
+use crate::styles::{circle_button_style, section_style, white_checkbox_style};
+use crate::ui::white_text;
+
+/// A labelled metadata text field.
+fn metadata_input<'a>(
+    label: &'a str,
+    value: &'a str,
+    on_input: fn(String) -> Message,
+) -> Element<'a, Message> {
+    row![
+        container(white_text(label, 12)).width(Length::Fixed(50.0)),
+        text_input(label, value).on_input(on_input).padding(6).size(12),
+    ]
+    .align_y(Alignment::Center)
+    .into()
+}
+
+/// Create the podcast feed settings section for the settings window
+pub fn podcast_feed_settings_section<'a>(app: &'a App) -> Element<'a, Message> {
+    let feed_checkbox = checkbox(app.feed_config.enabled)
+        .label("Share exported readings as a podcast feed on the local network")
+        .on_toggle(Message::PodcastFeedToggled)
+        .style(white_checkbox_style);
+
+    let details: Element<'a, Message> = if app.feed_config.enabled {
+        let status: Element<'a, Message> = match app.feed_error {
+            Some(ref error) => white_text(error, 11)
+                .style(|_theme| iced::widget::text::Style {
+                    color: Some(Color::from_rgb(1.0, 0.4, 0.4)),
+                })
+                .into(),
+            None => row![
+                white_text(&app.feed_url, 12),
+                Space::new().width(Length::Fixed(8.0)),
+                button(white_text("Copy URL", 12))
+                    .style(circle_button_style)
+                    .padding([4.0, 10.0])
+                    .on_press(Message::CopyPodcastFeedUrl),
+            ]
+            .align_y(Alignment::Center)
+            .into(),
+        };
+
+        column![
+            Space::new().height(Length::Fixed(8.0)),
+            status,
+            Space::new().height(Length::Fixed(8.0)),
+            metadata_input("Title", &app.feed_config.title, Message::PodcastFeedTitleChanged),
+            Space::new().height(Length::Fixed(6.0)),
+            metadata_input("Author", &app.feed_config.author, Message::PodcastFeedAuthorChanged),
+            Space::new().height(Length::Fixed(8.0)),
+            button(white_text("Open Folder", 12))
+                .style(circle_button_style)
+                .padding([4.0, 10.0])
+                .on_press(Message::OpenPodcastFeedFolder),
+        ]
+        .spacing(0)
+        .into()
+    } else {
+        column![].into()
+    };
+
+    container(
+        row![
+            container(white_text("Podcast Feed", 14))
+                .width(Length::Fixed(120.0))
+                .align_x(Alignment::Start),
+            Space::new().width(Length::Fixed(16.0)),
+            container(column![feed_checkbox, details].spacing(0))
+                .width(Length::Fill)
+                .align_x(Alignment::Start),
+        ]
+        .align_y(Alignment::Center)
+        .width(Length::Fill)
+        .padding([12.0, 16.0])
+    )
+    .style(section_style)
+    .into()
+}
@@ -1,19 +1,45 @@
 //! Hotkey configuration UI component
 
-use iced::widget::{button, checkbox, column, container, row, text, Space};
+use iced::widget::{button, checkbox, column, container, row, slider, text, Space};
 use iced::{Alignment, Color, Element, Length};
 
 use crate::model::Message;
 use crate::styles::{circle_button_style, section_style, white_checkbox_style};
-use crate::system::{format_hotkey_display};
+use crate::system::{format_hotkey_display, HotkeyConfig};
+use crate::ui::accessibility::MAIN_BAR_SHORTCUTS;
+use crate::ui::white_text;
 
-/// Helper to create white text with consistent styling (matching view.rs pattern).
-fn white_text(content: &str, size: u32) -> text::Text<'_> {
-    text(content)
-        .size(size)
-        .style(|_theme| iced::widget::text::Style {
-            color: Some(Color::from_rgba(1.0, 1.0, 1.0, 0.85)),
-        })
+/// Keyboard shortcuts of the main window as one line ("Space or K: Play or pause, ...").
+fn main_bar_keys() -> String {
+    MAIN_BAR_SHORTCUTS
+        .iter()
+        .map(|(name, keys)| format!("{keys}: {name}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Checkbox registering a variant of the hotkey, showing its key combination.
+/// Unavailable when the hotkey already holds the variant's modifier.
+fn variant_checkbox<'a>(
+    variant: Option<HotkeyConfig>,
+    modifier: &str,
+    enabled: bool,
+    is_disabled: bool,
+    action: &str,
+    on_toggle: fn(bool) -> Message,
+) -> Element<'a, Message> {
+    let label = match &variant {
+        Some(variant) => format!("{} {action}", format_hotkey_display(variant)),
+        None => format!("Hotkey + {modifier} {action} (unavailable: the hotkey uses {modifier})"),
+    };
+    let usable = variant.is_some() && !is_disabled;
+    let mut variant_checkbox = checkbox(usable && enabled)
+        .label(label)
+        .style(white_checkbox_style);
+    if usable {
+        variant_checkbox = variant_checkbox.on_toggle(on_toggle);
+    }
+    variant_checkbox.into()
 }
 
 /// Create the hotkey settings section for the settings window
@@ -74,6 +100,18 @@ pub fn hotkey_settings_section<'a>(app: &'a crate::model::App) -> Element<'a, Me
         None
     };
     
+    // The panic stop hotkey has its own registration, independent of the reading hotkey
+    let panic_stop_label = format!(
+        "Panic stop hotkey ({}) silences reading at once",
+        format_hotkey_display(&HotkeyConfig::panic_stop())
+    );
+    let mut panic_stop_checkbox = checkbox(!is_disabled && app.panic_stop_hotkey)
+        .label(panic_stop_label)
+        .style(white_checkbox_style);
+    if !is_disabled {
+        panic_stop_checkbox = panic_stop_checkbox.on_toggle(Message::PanicStopHotkeyToggled);
+    }
+
     let hotkey_control = column![
         row![
             hotkey_checkbox,
@@ -91,6 +129,56 @@ pub fn hotkey_settings_section<'a>(app: &'a crate::model::App) -> Element<'a, Me
         } else {
             column![].spacing(0)
         },
+        Space::new().height(Length::Fixed(8.0)),
+        checkbox(app.preview_before_reading)
+            .label("Preview the text before reading")
+            .on_toggle(Message::PreviewBeforeReadingToggled)
+            .style(white_checkbox_style),
+        Space::new().height(Length::Fixed(4.0)),
+        checkbox(app.edit_before_reading)
+            .label("Edit selections before reading")
+            .on_toggle(Message::EditBeforeReadingToggled)
+            .style(white_checkbox_style),
+        Space::new().height(Length::Fixed(4.0)),
+        variant_checkbox(
+            app.hotkey_config.edit_first_variant(),
+            "Shift",
+            app.hotkey_edit_variant,
+            is_disabled,
+            "edits the selection before reading it",
+            Message::HotkeyEditVariantToggled,
+        ),
+        Space::new().height(Length::Fixed(4.0)),
+        variant_checkbox(
+            app.hotkey_config.clipboard_image_variant(),
+            "Alt",
+            app.hotkey_image_variant,
+            is_disabled,
+            "reads the text of the image on the clipboard",
+            Message::HotkeyImageVariantToggled,
+        ),
+        Space::new().height(Length::Fixed(4.0)),
+        panic_stop_checkbox,
+        Space::new().height(Length::Fixed(8.0)),
+        text(format!("Main window keys: {}", main_bar_keys()))
+            .size(11)
+            .style(|_theme| iced::widget::text::Style {
+                color: Some(Color::from_rgba(1.0, 1.0, 1.0, 0.6)),
+            }),
+        Space::new().height(Length::Fixed(8.0)),
+        text(format!("Main window scroll wheel sensitivity: {:.2}×", app.scroll_sensitivity))
+            .size(12)
+            .style(|_theme| iced::widget::text::Style {
+                color: Some(Color::from_rgba(1.0, 1.0, 1.0, 0.85)),
+            }),
+        slider(0.25..=3.0, app.scroll_sensitivity, Message::ScrollSensitivityChanged)
+            .step(0.25)
+            .width(Length::Fixed(220.0)),
+        text("Scroll over the waveform for volume, over the progress bar to move by sentences, sideways for speed")
+            .size(11)
+            .style(|_theme| iced::widget::text::Style {
+                color: Some(Color::from_rgba(1.0, 1.0, 1.0, 0.6)),
+            }),
     ]
     .spacing(0);
 
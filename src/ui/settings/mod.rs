@@ -1,3 +1,16 @@
 //! Settings window UI components
 
+pub mod acceleration;
+pub mod appearance;
+pub mod audio_cues;
+pub mod feed;
 pub mod hotkeys;
+pub mod plugins;
+pub mod power;
+pub mod privacy;
+pub mod reading;
+pub mod spoken_text;
+pub mod storage;
+pub mod sync;
+pub mod tabs;
+pub mod watch;
@@ -0,0 +1,75 @@
+//! Plugin settings UI component
+
+use iced::widget::{button, checkbox, column, container, row, Space};
+use iced::{Alignment, Color, Element, Length};
+
+use crate::model::{App, Message};
+use crate::styles::{circle_button_style, section_style, white_checkbox_style};
+use crate::ui::white_text;
+
+/// Create the plugins settings section for the settings window
+pub fn plugins_settings_section<'a>(app: &'a App) -> Element<'a, Message> {
+    let plugins: Element<'a, Message> = if app.plugin_host.plugins.is_empty() {
+        white_text("No plugins installed. Add .rhai scripts to the plugins folder.", 12).into()
+    } else {
+        column(app.plugin_host.plugins.iter().map(|plugin| {
+            let name = plugin.name.clone();
+            let toggle = checkbox(plugin.enabled)
+                .label(plugin.name.as_str())
+                .on_toggle(move |enabled| Message::PluginToggled(name.clone(), enabled))
+                .style(white_checkbox_style);
+            match plugin.error {
+                Some(ref error) => column![
+                    toggle,
+                    white_text(error.as_str(), 11).style(|_theme| iced::widget::text::Style {
+                        color: Some(Color::from_rgb(1.0, 0.5, 0.5)),
+                    }),
+                ]
+                .spacing(2)
+                .into(),
+                None => toggle.into(),
+            }
+        }))
+        .spacing(6)
+        .into()
+    };
+
+    let buttons = row![
+        button(white_text("Open Folder", 12))
+            .style(circle_button_style)
+            .padding([4.0, 10.0])
+            .on_press(Message::OpenPluginsFolder),
+        Space::new().width(Length::Fixed(8.0)),
+        button(white_text("Reload", 12))
+            .style(circle_button_style)
+            .padding([4.0, 10.0])
+            .on_press(Message::ReloadPlugins),
+    ]
+    .align_y(Alignment::Center);
+
+    let controls = column![
+        white_text("Rhai scripts that rewrite text before reading or add command palette actions", 12),
+        Space::new().height(Length::Fixed(8.0)),
+        plugins,
+        Space::new().height(Length::Fixed(10.0)),
+        buttons,
+    ]
+    .spacing(0);
+
+    container(
+        row![
+            container(white_text("Plugins", 14))
+                .width(Length::Fixed(120.0))
+                .align_x(Alignment::Start),
+            Space::new().width(Length::Fixed(16.0)),
+            container(controls)
+                .width(Length::Fill)
+                .align_x(Alignment::Start),
+        ]
+        .align_y(Alignment::Center)
+        .width(Length::Fill)
+        .padding([12.0, 16.0])
+    )
+    .style(section_style)
+    .into()
+}
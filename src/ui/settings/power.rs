@@ -0,0 +1,122 @@
+//! Power & CPU settings UI component (how synthesis adapts to running on battery, Piper threads and priority)
+
+use iced::widget::{column, container, radio, row, slider, Space};
+use iced::{Alignment, Color, Element, Length};
+
+use crate::model::{App, Message};
+use crate::styles::{section_style, white_radio_style};
+use crate::system::{PowerPolicy, PowerSource, ProcessPriority};
+use crate::ui::white_text;
+
+/// Policies with their labels and what they do on battery.
+const POLICIES: [(PowerPolicy, &str, &str); 3] = [
+    (PowerPolicy::Performance, "Performance", "Always synthesize at full speed"),
+    (PowerPolicy::Balanced, "Balanced", "On battery, run voices on fewer cores"),
+    (
+        PowerPolicy::BatterySaver,
+        "Battery saver",
+        "On battery, use even fewer cores and the fastest downloaded quality of the voice",
+    ),
+];
+
+/// Priorities with their labels.
+const PRIORITIES: [(ProcessPriority, &str); 3] = [
+    (ProcessPriority::Normal, "Normal"),
+    (ProcessPriority::Low, "Low"),
+    (ProcessPriority::Lowest, "Lowest"),
+];
+
+/// Thread count slider and priority choice for the Piper processes.
+fn cpu_controls<'a>(app: &'a App) -> Element<'a, Message> {
+    let cores = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let threads = match app.synthesis_threads {
+        0 => format!("Threads: all {cores}"),
+        threads => format!("Threads: {} of {cores}", threads.min(cores)),
+    };
+    let priorities = row(PRIORITIES.into_iter().map(|(priority, label)| {
+        radio(label, priority, Some(app.synthesis_priority), Message::SynthesisPrioritySelected)
+            .style(white_radio_style)
+            .into()
+    }))
+    .spacing(16);
+
+    column![
+        white_text(threads, 12),
+        // The far right of the slider means no limit
+        slider(1.0..=(cores + 1) as f32, limit_position(app.synthesis_threads, cores), move |position| {
+            let threads = position as usize;
+            Message::SynthesisThreadsChanged(if threads > cores { 0 } else { threads })
+        })
+        .step(1.0)
+        .width(Length::Fixed(220.0)),
+        Space::new().height(Length::Fixed(8.0)),
+        row![white_text("Priority", 12), Space::new().width(Length::Fixed(12.0)), priorities].align_y(Alignment::Center),
+        white_text("Lower it so readings in the background leave the CPU to builds and games.", 11).style(|_theme| {
+            iced::widget::text::Style {
+                color: Some(Color::from_rgba(1.0, 1.0, 1.0, 0.6)),
+            }
+        }),
+    ]
+    .spacing(4)
+    .into()
+}
+
+/// Slider position of a thread limit: the limit itself, or past the last core for none.
+fn limit_position(threads: usize, cores: usize) -> f32 {
+    if threads == 0 || threads > cores {
+        (cores + 1) as f32
+    } else {
+        threads as f32
+    }
+}
+
+/// Create the power settings section for the settings window
+pub fn power_settings_section<'a>(app: &'a App) -> Element<'a, Message> {
+    let policies = column(POLICIES.into_iter().map(|(policy, label, description)| {
+        column![
+            radio(label, policy, Some(app.power_policy), Message::PowerPolicySelected).style(white_radio_style),
+            container(white_text(description, 11).style(|_theme| iced::widget::text::Style {
+                color: Some(Color::from_rgba(1.0, 1.0, 1.0, 0.6)),
+            }))
+            .padding([0.0, 26.0]),
+        ]
+        .spacing(2)
+        .into()
+    }))
+    .spacing(6);
+
+    let source = match app.power_source {
+        Some(PowerSource::Battery) => "Running on battery now",
+        Some(PowerSource::Ac) => "Plugged in now",
+        Some(PowerSource::Unknown) => "No battery found, the power policy has no effect",
+        None => "",
+    };
+
+    let controls = column![
+        policies,
+        Space::new().height(Length::Fixed(8.0)),
+        white_text(source, 11).style(|_theme| iced::widget::text::Style {
+            color: Some(Color::from_rgba(1.0, 1.0, 1.0, 0.7)),
+        }),
+        Space::new().height(Length::Fixed(12.0)),
+        cpu_controls(app),
+    ]
+    .spacing(0);
+
+    container(
+        row![
+            container(white_text("Power & CPU", 14))
+                .width(Length::Fixed(120.0))
+                .align_x(Alignment::Start),
+            Space::new().width(Length::Fixed(16.0)),
+            container(controls)
+                .width(Length::Fill)
+                .align_x(Alignment::Start),
+        ]
+        .align_y(Alignment::Center)
+        .width(Length::Fill)
+        .padding([12.0, 16.0])
+    )
+    .style(section_style)
+    .into()
+}
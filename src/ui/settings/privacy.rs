@@ -0,0 +1,134 @@
+//! Privacy settings UI component (headphone-only playback, holding readings during calls, pausing while typing, sensitive text guard, screenshots kept off the disk, clipboard history, apps kept offline, encrypted exports)
+
+use iced::widget::{checkbox, column, container, row, slider, text_input, Space};
+use iced::{Alignment, Element, Length};
+
+use crate::model::{App, Message};
+use crate::styles::{section_style, white_checkbox_style};
+use crate::system::typing_detection_supported;
+use crate::ui::white_text;
+
+/// Explain the offline list, naming the app of the last capture so it is easy to add.
+fn local_only_hint(app: &App) -> String {
+    let hint = "Text captured from these apps is read with Piper only, never sent to AWS Polly or Natural Reading.";
+    match app.capture_app {
+        Some(ref capture_app) if !capture_app.name.is_empty() => {
+            format!("{hint} Last capture came from \"{}\".", capture_app.name)
+        }
+        _ => hint.to_string(),
+    }
+}
+
+/// "Pause while typing" checkbox with its resume delay, or why it is unavailable.
+fn typing_pause_controls(app: &App) -> Element<'_, Message> {
+    if !typing_detection_supported() {
+        return column![
+            checkbox(false).label("Pause while I type").style(white_checkbox_style),
+            white_text("Typing cannot be detected on this platform.", 11),
+        ]
+        .spacing(4)
+        .into();
+    }
+    let mut controls = column![checkbox(app.pause_while_typing)
+        .label("Pause while I type")
+        .on_toggle(Message::PauseWhileTypingToggled)
+        .style(white_checkbox_style)]
+    .spacing(4);
+    if app.pause_while_typing {
+        controls = controls.push(white_text(format!("Resume after {} s without typing", app.typing_resume_secs), 12)).push(
+            slider(1.0..=10.0, app.typing_resume_secs as f32, |secs| Message::TypingResumeDelayChanged(secs as u32))
+                .step(1.0)
+                .width(Length::Fixed(220.0)),
+        );
+    }
+    controls
+        .push(white_text("Readings pause when you start typing in any app, so they do not talk over your writing.", 11))
+        .into()
+}
+
+/// Create the privacy settings section for the settings window
+pub fn privacy_settings_section<'a>(app: &'a App) -> Element<'a, Message> {
+    let controls = column![
+        checkbox(app.headphones_only)
+            .label("Only play automatically through headphones")
+            .on_toggle(Message::HeadphonesOnlyToggled)
+            .style(white_checkbox_style),
+        white_text(
+            "When the audio output is not headphones or a Bluetooth device, readings start paused until you press play.",
+            11,
+        ),
+        Space::new().height(Length::Fixed(10.0)),
+        checkbox(app.defer_while_mic_active)
+            .label("Hold hotkey readings while the microphone is in use")
+            .on_toggle(Message::DeferWhileMicActiveToggled)
+            .style(white_checkbox_style),
+        white_text(
+            "Text captured with the hotkey during a call or meeting is kept and read once no other app is recording.",
+            11,
+        ),
+        Space::new().height(Length::Fixed(10.0)),
+        typing_pause_controls(app),
+        Space::new().height(Length::Fixed(10.0)),
+        checkbox(app.sensitive_text_guard)
+            .label("Ask before reading text that looks like a password or token")
+            .on_toggle(Message::SensitiveTextGuardToggled)
+            .style(white_checkbox_style),
+        white_text(
+            "Secrets are not read aloud or sent to AWS Polly or Natural Reading until you confirm.",
+            11,
+        ),
+        Space::new().height(Length::Fixed(10.0)),
+        checkbox(app.private_screenshots)
+            .label("Keep screenshots off the disk")
+            .on_toggle(Message::PrivateScreenshotsToggled)
+            .style(white_checkbox_style),
+        white_text(
+            "Screenshots and clipboard images are read in memory. When a screenshot tool can only save a file, it is shredded right after reading.",
+            11,
+        ),
+        Space::new().height(Length::Fixed(10.0)),
+        checkbox(app.clipboard_history_enabled)
+            .label("Keep a clipboard history of the last 10 captures")
+            .on_toggle(Message::ClipboardHistoryToggled)
+            .style(white_checkbox_style),
+        white_text(
+            "Pick an earlier capture to read from Clipboard History in the tray menu or command palette. Kept in memory only, never saved; secrets and captures from offline apps are left out.",
+            11,
+        ),
+        Space::new().height(Length::Fixed(10.0)),
+        white_text("Apps that stay offline (comma-separated app names or window title words)", 12),
+        text_input("e.g. keepassxc, 1password, My Bank", &app.local_only_apps)
+            .on_input(Message::LocalOnlyAppsChanged)
+            .padding(6)
+            .size(12),
+        white_text(local_only_hint(app), 11),
+        Space::new().height(Length::Fixed(10.0)),
+        white_text("Encrypt exports to (comma-separated age or SSH public keys)", 12),
+        text_input("e.g. age1ql3z7hjy54pw3hyww5ayyfg7zqgvc7w3j2elw8zmrj2kg5sfn9aqmcac8p", &app.export_recipients)
+            .on_input(Message::ExportRecipientsChanged)
+            .padding(6)
+            .size(12),
+        white_text(
+            "Used by `insight-reader audiobook --encrypt` and `insight-reader history export --encrypt`. Needs the age tool on PATH; only public keys are stored.",
+            11,
+        ),
+    ]
+    .spacing(4);
+
+    container(
+        row![
+            container(white_text("Privacy", 14))
+                .width(Length::Fixed(120.0))
+                .align_x(Alignment::Start),
+            Space::new().width(Length::Fixed(16.0)),
+            container(controls)
+                .width(Length::Fill)
+                .align_x(Alignment::Start),
+        ]
+        .align_y(Alignment::Center)
+        .width(Length::Fill)
+        .padding([12.0, 16.0])
+    )
+    .style(section_style)
+    .into()
+}
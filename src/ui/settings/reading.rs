@@ -0,0 +1,77 @@
+//! Reading text settings UI component (font, line spacing, background tint)
+
+use iced::widget::{checkbox, column, container, radio, row, slider, Space};
+use iced::{Alignment, Element, Length};
+
+use crate::model::{App, Message, ReadingFont, ReadingTint};
+use crate::styles::{section_style, white_checkbox_style, white_radio_style};
+use crate::ui::white_text;
+
+/// Create the reading text settings section for the settings window
+pub fn reading_settings_section<'a>(app: &'a App) -> Element<'a, Message> {
+    let style = app.reading_style;
+    let font = |label, font| {
+        radio(label, font, Some(style.font), Message::ReadingFontSelected).style(white_radio_style)
+    };
+    let tint = |label, tint| {
+        radio(label, tint, Some(style.tint), Message::ReadingTintSelected).style(white_radio_style)
+    };
+
+    let controls = column![
+        white_text("Font", 12),
+        row![
+            font("Default", ReadingFont::Default),
+            font("OpenDyslexic", ReadingFont::OpenDyslexic),
+            font("Serif", ReadingFont::Serif),
+            font("Monospace", ReadingFont::Monospace),
+        ]
+        .spacing(12),
+        Space::new().height(Length::Fixed(10.0)),
+        white_text(format!("Line spacing: {:.1}", style.line_spacing), 12),
+        slider(1.0..=3.0, style.line_spacing, Message::ReadingLineSpacingChanged)
+            .step(0.1)
+            .width(Length::Fixed(220.0)),
+        Space::new().height(Length::Fixed(10.0)),
+        white_text("Background", 12),
+        row![
+            tint("Dark", ReadingTint::None),
+            tint("Cream", ReadingTint::Cream),
+            tint("Blue", ReadingTint::Blue),
+            tint("Green", ReadingTint::Green),
+            tint("Gray", ReadingTint::Gray),
+        ]
+        .spacing(12),
+        Space::new().height(Length::Fixed(10.0)),
+        checkbox(style.focus_follow)
+            .label("Reading view follows the current sentence")
+            .on_toggle(Message::FocusFollowToggled)
+            .style(white_checkbox_style),
+        white_text(format!("Dim other paragraphs: {:.0}%", style.focus_dim * 100.0), 12),
+        slider(0.0..=0.9, style.focus_dim, Message::FocusDimChanged)
+            .step(0.05)
+            .width(Length::Fixed(220.0)),
+        Space::new().height(Length::Fixed(8.0)),
+        white_text(
+            "Used by the extracted text window and the reading view. OpenDyslexic must be installed or placed in the app's fonts folder.",
+            11,
+        ),
+    ]
+    .spacing(4);
+
+    container(
+        row![
+            container(white_text("Reading Text", 14))
+                .width(Length::Fixed(120.0))
+                .align_x(Alignment::Start),
+            Space::new().width(Length::Fixed(16.0)),
+            container(controls)
+                .width(Length::Fill)
+                .align_x(Alignment::Start),
+        ]
+        .align_y(Alignment::Center)
+        .width(Length::Fill)
+        .padding([12.0, 16.0])
+    )
+    .style(section_style)
+    .into()
+}
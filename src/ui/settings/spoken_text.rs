@@ -0,0 +1,75 @@
+//! Spoken text settings UI component (how selections are verbalized)
+
+use iced::widget::{checkbox, column, container, radio, row, Space};
+use iced::{Alignment, Element, Length};
+
+use crate::model::{App, Message, TextProfile};
+use crate::styles::{section_style, white_checkbox_style, white_radio_style};
+use crate::ui::white_text;
+
+/// Create the spoken text settings section for the settings window
+pub fn spoken_text_settings_section<'a>(app: &'a App) -> Element<'a, Message> {
+    let controls = column![
+        checkbox(app.read_alt_text)
+            .label("Read image descriptions (alt text) in copied web content")
+            .on_toggle(Message::ReadAltTextToggled)
+            .style(white_checkbox_style),
+        Space::new().height(Length::Fixed(6.0)),
+        checkbox(app.read_math)
+            .label("Read math notation as words (x^2 as \"x squared\", LaTeX fractions and roots)")
+            .on_toggle(Message::ReadMathToggled)
+            .style(white_checkbox_style),
+        Space::new().height(Length::Fixed(6.0)),
+        checkbox(app.list_prosody)
+            .label("Pace lists: pause before each item and read numbered items as \"First, Second...\"")
+            .on_toggle(Message::ListProsodyToggled)
+            .style(white_checkbox_style),
+        Space::new().height(Length::Fixed(10.0)),
+        white_text("Text profile", 12),
+        Space::new().height(Length::Fixed(6.0)),
+        radio("Detect automatically", TextProfile::Auto, Some(app.text_profile), Message::TextProfileSelected)
+            .style(white_radio_style),
+        Space::new().height(Length::Fixed(6.0)),
+        radio("Plain text", TextProfile::Plain, Some(app.text_profile), Message::TextProfileSelected)
+            .style(white_radio_style),
+        Space::new().height(Length::Fixed(6.0)),
+        radio(
+            "Chat messages (Slack, Discord): announce senders, skip timestamps and reactions",
+            TextProfile::Chat,
+            Some(app.text_profile),
+            Message::TextProfileSelected
+        )
+        .style(white_radio_style),
+        Space::new().height(Length::Fixed(6.0)),
+        radio(
+            "Emails: read sender and subject once, skip quoted replies and signatures",
+            TextProfile::Email,
+            Some(app.text_profile),
+            Message::TextProfileSelected
+        )
+        .style(white_radio_style),
+        Space::new().height(Length::Fixed(6.0)),
+        white_text(
+            "When the selection comes from a web page or document, headings are announced and list items are read with a pause.",
+            11,
+        ),
+    ]
+    .spacing(0);
+
+    container(
+        row![
+            container(white_text("Spoken Text", 14))
+                .width(Length::Fixed(120.0))
+                .align_x(Alignment::Start),
+            Space::new().width(Length::Fixed(16.0)),
+            container(controls)
+                .width(Length::Fill)
+                .align_x(Alignment::Start),
+        ]
+        .align_y(Alignment::Center)
+        .width(Length::Fill)
+        .padding([12.0, 16.0])
+    )
+    .style(section_style)
+    .into()
+}
@@ -0,0 +1,121 @@
+//! Storage settings UI component (disk usage per category, purge buttons, cleanup schedule)
+
+use iced::widget::{button, column, container, radio, row, Space};
+use iced::{Alignment, Color, Element, Length};
+
+use crate::model::{App, Message};
+use crate::storage::{format_size, CleanupSchedule};
+use crate::styles::{circle_button_style, section_style, white_radio_style};
+use crate::ui::white_text;
+
+/// Days of history the cleanup can keep, with their labels (0 keeps everything).
+const HISTORY_RETENTION: [(u32, &str); 4] = [(30, "30 days"), (90, "90 days"), (365, "1 year"), (0, "Forever")];
+
+/// Size limits of the synthesis cache in MB, with their labels (0 turns it off).
+const SYNTHESIS_CACHE_SIZES: [(u32, &str); 4] = [(0, "Off"), (100, "100 MB"), (500, "500 MB"), (2000, "2 GB")];
+
+/// Create the storage settings section for the settings window
+pub fn storage_settings_section<'a>(app: &'a App) -> Element<'a, Message> {
+    let usage: Element<'a, Message> = if app.storage_usage.is_empty() {
+        white_text("Measuring disk usage...", 11).into()
+    } else {
+        column(app.storage_usage.iter().map(|&(category, bytes)| {
+            row![
+                white_text(category.label(), 12).width(Length::Fixed(100.0)),
+                white_text(format_size(bytes), 12).width(Length::Fixed(80.0)),
+                button(white_text("Purge", 11))
+                    .style(circle_button_style)
+                    .padding([2.0, 10.0])
+                    .on_press_maybe((bytes > 0).then_some(Message::PurgeStorage(category))),
+            ]
+            .align_y(Alignment::Center)
+            .into()
+        }))
+        .spacing(4)
+        .into()
+    };
+
+    let schedule = row![
+        radio("Off", CleanupSchedule::Off, Some(app.cleanup_schedule), Message::CleanupScheduleSelected)
+            .style(white_radio_style),
+        Space::new().width(Length::Fixed(12.0)),
+        radio("Daily", CleanupSchedule::Daily, Some(app.cleanup_schedule), Message::CleanupScheduleSelected)
+            .style(white_radio_style),
+        Space::new().width(Length::Fixed(12.0)),
+        radio("Weekly", CleanupSchedule::Weekly, Some(app.cleanup_schedule), Message::CleanupScheduleSelected)
+            .style(white_radio_style),
+    ]
+    .align_y(Alignment::Center);
+
+    let retention = row(HISTORY_RETENTION.into_iter().map(|(days, label)| {
+        radio(label, days, Some(app.history_max_days), Message::HistoryMaxDaysSelected)
+            .style(white_radio_style)
+            .into()
+    }))
+    .spacing(12)
+    .align_y(Alignment::Center);
+
+    let cache_size = row(SYNTHESIS_CACHE_SIZES.into_iter().map(|(mb, label)| {
+        radio(label, mb, Some(app.synthesis_cache_mb), Message::SynthesisCacheSizeSelected)
+            .style(white_radio_style)
+            .into()
+    }))
+    .spacing(12)
+    .align_y(Alignment::Center);
+
+    let cleanup_button = button(white_text(if app.cleanup_running { "Cleaning up..." } else { "Clean Up Now" }, 12))
+        .style(circle_button_style)
+        .padding([4.0, 10.0])
+        .on_press_maybe((!app.cleanup_running).then_some(Message::RunStorageCleanup));
+
+    let status: Element<'a, Message> = match app.storage_status {
+        Some(ref status) => white_text(status, 11)
+            .style(|_theme| iced::widget::text::Style {
+                color: Some(Color::from_rgba(1.0, 1.0, 1.0, 0.7)),
+            })
+            .into(),
+        None => column![].into(),
+    };
+
+    let controls = column![
+        usage,
+        Space::new().height(Length::Fixed(10.0)),
+        white_text("Clean up old files automatically", 12),
+        Space::new().height(Length::Fixed(4.0)),
+        schedule,
+        Space::new().height(Length::Fixed(8.0)),
+        white_text("Keep reading history for", 12),
+        Space::new().height(Length::Fixed(4.0)),
+        retention,
+        Space::new().height(Length::Fixed(8.0)),
+        white_text("Keep synthesized readings to replay instantly", 12),
+        Space::new().height(Length::Fixed(4.0)),
+        cache_size,
+        Space::new().height(Length::Fixed(4.0)),
+        white_text(
+            "Temporary audio and screenshots, logs older than two weeks and old history are removed. Favorites (marked from the main window's right-click menu) are always kept.",
+            11,
+        ),
+        Space::new().height(Length::Fixed(8.0)),
+        cleanup_button,
+        status,
+    ]
+    .spacing(0);
+
+    container(
+        row![
+            container(white_text("Storage", 14))
+                .width(Length::Fixed(120.0))
+                .align_x(Alignment::Start),
+            Space::new().width(Length::Fixed(16.0)),
+            container(controls)
+                .width(Length::Fill)
+                .align_x(Alignment::Start),
+        ]
+        .align_y(Alignment::Center)
+        .width(Length::Fill)
+        .padding([12.0, 16.0])
+    )
+    .style(section_style)
+    .into()
+}
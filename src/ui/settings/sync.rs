@@ -0,0 +1,117 @@
+//! Reading history and folder sync settings UI component
+
+use chrono::Weekday;
+use iced::widget::{button, checkbox, column, container, radio, row, slider, text, text_input, Space};
+use iced::{Alignment, Color, Element, Length};
+
+use crate::model::{App, Message};
+use crate::styles::{circle_button_style, section_style, white_checkbox_style, white_radio_style};
+use crate::ui::white_text;
+
+/// Days the weekly summary can be shown on, with their labels.
+const WEEKDAYS: [(Weekday, &str); 7] = [
+    (Weekday::Mon, "Mon"),
+    (Weekday::Tue, "Tue"),
+    (Weekday::Wed, "Wed"),
+    (Weekday::Thu, "Thu"),
+    (Weekday::Fri, "Fri"),
+    (Weekday::Sat, "Sat"),
+    (Weekday::Sun, "Sun"),
+];
+
+/// Create the history and sync settings section for the settings window
+pub fn history_sync_settings_section<'a>(app: &'a App) -> Element<'a, Message> {
+    let history_checkbox = checkbox(app.history_enabled)
+        .label("Keep a history of texts read aloud")
+        .on_toggle(Message::HistoryToggled)
+        .style(white_checkbox_style);
+
+    let summary_checkbox = checkbox(app.weekly_summary_enabled)
+        .label("Show a weekly listening summary")
+        .on_toggle(Message::WeeklySummaryToggled)
+        .style(white_checkbox_style);
+
+    let summary_time: Element<'a, Message> = if app.weekly_summary_enabled {
+        let days = row(WEEKDAYS.into_iter().map(|(day, label)| {
+            radio(label, day, Some(app.weekly_summary_day), Message::WeeklySummaryDaySelected)
+                .style(white_radio_style)
+                .into()
+        }))
+        .spacing(10)
+        .align_y(Alignment::Center);
+        let hour = row![
+            slider(0..=23, app.weekly_summary_hour, Message::WeeklySummaryHourChanged).width(Length::Fixed(200.0)),
+            Space::new().width(Length::Fixed(8.0)),
+            text(format!("at {:02}:00", app.weekly_summary_hour))
+                .size(12)
+                .style(|_theme| iced::widget::text::Style {
+                    color: Some(Color::from_rgba(1.0, 1.0, 1.0, 0.85)),
+                }),
+        ]
+        .align_y(Alignment::Center);
+        column![
+            Space::new().height(Length::Fixed(6.0)),
+            days,
+            Space::new().height(Length::Fixed(4.0)),
+            hour,
+        ]
+        .into()
+    } else {
+        column![].into()
+    };
+
+    let sync_row = row![
+        text_input("Sync folder (e.g. ~/Dropbox/InsightReader), empty for off", &app.sync_dir_input)
+            .on_input(Message::SyncDirInputChanged)
+            .on_submit(Message::SyncNow)
+            .padding(6)
+            .size(12),
+        Space::new().width(Length::Fixed(8.0)),
+        button(white_text("Sync Now", 12))
+            .style(circle_button_style)
+            .padding([4.0, 10.0])
+            .on_press(Message::SyncNow),
+    ]
+    .align_y(Alignment::Center);
+
+    let status: Element<'a, Message> = match app.sync_status {
+        Some(ref status) => column![
+            Space::new().height(Length::Fixed(6.0)),
+            white_text(status, 11).style(|_theme| iced::widget::text::Style {
+                color: Some(Color::from_rgba(1.0, 1.0, 1.0, 0.7)),
+            }),
+        ]
+        .into(),
+        None => column![].into(),
+    };
+
+    let controls = column![
+        history_checkbox,
+        Space::new().height(Length::Fixed(6.0)),
+        summary_checkbox,
+        summary_time,
+        Space::new().height(Length::Fixed(10.0)),
+        white_text("Sync settings and history through a shared folder (Dropbox, Syncthing, ...)", 12),
+        Space::new().height(Length::Fixed(6.0)),
+        sync_row,
+        status,
+    ]
+    .spacing(0);
+
+    container(
+        row![
+            container(white_text("History & Sync", 14))
+                .width(Length::Fixed(120.0))
+                .align_x(Alignment::Start),
+            Space::new().width(Length::Fixed(16.0)),
+            container(controls)
+                .width(Length::Fill)
+                .align_x(Alignment::Start),
+        ]
+        .align_y(Alignment::Center)
+        .width(Length::Fill)
+        .padding([12.0, 16.0])
+    )
+    .style(section_style)
+    .into()
+}
@@ -0,0 +1,107 @@
+//! Settings tabs and the search box that finds sections across them
+
+use iced::widget::{button, row, text_input, Space};
+use iced::{Alignment, Element, Length};
+
+use crate::model::{App, Message, SettingsSection, SettingsTab};
+use crate::styles::{selected_tab_button_style, tab_button_style};
+use crate::ui::white_text;
+
+/// Words the search box matches: the section title and what it controls.
+fn search_terms(section: SettingsSection) -> &'static str {
+    match section {
+        SettingsSection::Appearance => "colors appearance theme high contrast warm custom accent waveform progress bar visualizer visualization bars mirrored level meter off",
+        SettingsSection::Reading => "reading text font opendyslexic line spacing background tint focus follow sentence dim view",
+        SettingsSection::LongText => "long text paragraphs number read first read all ask interrupt short selection pause resume",
+        SettingsSection::TextSource => "text source priority order selection clipboard ocr screen region always ask hotkey",
+        SettingsSection::SameTextAgain => "same text again duplicate capture replay ignore repeat",
+        SettingsSection::Privacy => "privacy headphones speakers bluetooth microphone call meeting hold typing keyboard writing pause resume password secret token sensitive cloud offline apps allowlist denylist screenshot disk memory shred ocr clipboard history recent captures export encrypt encryption age key audiobook archive",
+        SettingsSection::Provider => "text-to-speech provider tts piper aws polly elevenlabs api key macos windows system voice avspeech sapi basic fallback espeak voice language download native names locale pitch semitones higher lower deeper",
+        SettingsSection::Acceleration => "voice acceleration gpu cuda nvidia coreml directml onnx execution provider hardware benchmark speed",
+        SettingsSection::Power => "power cpu battery laptop saver balanced performance energy threads cores priority nice background compile",
+        SettingsSection::SpokenText => "list bullet numbered pause pacing ordinal spoken text alt image descriptions math latex chat slack discord messages email profile",
+        SettingsSection::NaturalReading => "natural reading text cleanup cloud enhancement",
+        SettingsSection::Hotkeys => "global hotkey shortcut keys preview edit before reading mouse scroll wheel sensitivity panic emergency stop",
+        SettingsSection::Ocr => "ocr screenshot image text recognition",
+        SettingsSection::AudioCues => "audio cues earcons sounds chime beep click volume fade in out",
+        SettingsSection::PodcastFeed => "podcast feed export network share",
+        SettingsSection::HistorySync => "history sync folder dropbox weekly summary listening stats",
+        SettingsSection::WatchFolder => "watch folder inbox new files automatic send to reader",
+        SettingsSection::Storage => "storage disk usage cleanup purge cache synthesis replay screenshots logs history delete",
+        SettingsSection::Plugins => "plugins extensions",
+        SettingsSection::LogLevel => "log level logging debug trace",
+    }
+}
+
+/// Whether every word of `query` appears in the section's search terms.
+pub fn section_matches(section: SettingsSection, query: &str) -> bool {
+    let query = query.to_lowercase();
+    let terms = search_terms(section);
+    query.split_whitespace().all(|word| terms.contains(word))
+}
+
+/// Sections to show: those of the selected tab, or the matches of every tab while searching.
+pub fn visible_sections(app: &App) -> Vec<SettingsSection> {
+    let query = app.settings_search.trim();
+    SettingsSection::ALL
+        .into_iter()
+        .filter(|section| {
+            if query.is_empty() {
+                section.tab() == app.settings_tab
+            } else {
+                section_matches(*section, query)
+            }
+        })
+        .collect()
+}
+
+/// Tab bar with the search box at the end.
+pub fn settings_tab_bar<'a>(app: &'a App) -> Element<'a, Message> {
+    let searching = !app.settings_search.trim().is_empty();
+    let tabs = SettingsTab::ALL.into_iter().map(|tab| {
+        let style = if tab == app.settings_tab && !searching {
+            selected_tab_button_style
+        } else {
+            tab_button_style
+        };
+        button(white_text(tab.label(), 13))
+            .style(style)
+            .padding([5.0, 10.0])
+            .on_press(Message::SettingsTabSelected(tab))
+            .into()
+    });
+
+    row(tabs)
+        .push(Space::new().width(Length::Fill))
+        .push(
+            text_input("Search settings", &app.settings_search)
+                .on_input(Message::SettingsSearchChanged)
+                .on_submit(Message::SettingsSearchSubmitted)
+                .padding(6)
+                .size(12)
+                .width(Length::Fixed(160.0)),
+        )
+        .spacing(4)
+        .align_y(Alignment::Center)
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_matches_sections() {
+        let matching = |query: &str| {
+            SettingsSection::ALL
+                .into_iter()
+                .filter(|&section| section_matches(section, query))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(matching("Polly"), vec![SettingsSection::Provider]);
+        assert_eq!(matching("hotkey preview"), vec![SettingsSection::Hotkeys]);
+        assert_eq!(matching("gpu"), vec![SettingsSection::Acceleration]);
+        assert!(matching("volume").contains(&SettingsSection::AudioCues));
+        assert!(matching("nothing like this").is_empty());
+    }
+}
@@ -0,0 +1,61 @@
+//! Watch folder settings UI component (folder whose new files are read automatically)
+
+use iced::widget::{button, column, container, row, text_input, Space};
+use iced::{Alignment, Color, Element, Length};
+
+use crate::model::{App, Message};
+use crate::styles::{circle_button_style, section_style};
+use crate::ui::white_text;
+
+/// Create the watch folder settings section for the settings window
+pub fn watch_folder_settings_section<'a>(app: &'a App) -> Element<'a, Message> {
+    let folder_row = row![
+        text_input("Folder (e.g. ~/ReadLater), empty for off", &app.watch_dir_input)
+            .on_input(Message::WatchDirInputChanged)
+            .on_submit(Message::WatchDirSaved)
+            .padding(6)
+            .size(12),
+        Space::new().width(Length::Fixed(8.0)),
+        button(white_text("Watch", 12))
+            .style(circle_button_style)
+            .padding([4.0, 10.0])
+            .on_press(Message::WatchDirSaved),
+    ]
+    .align_y(Alignment::Center);
+
+    let status: Element<'a, Message> = match app.watch_status {
+        Some(ref status) => column![
+            Space::new().height(Length::Fixed(6.0)),
+            white_text(status, 11).style(|_theme| iced::widget::text::Style {
+                color: Some(Color::from_rgba(1.0, 1.0, 1.0, 0.7)),
+            }),
+        ]
+        .into(),
+        None => column![].into(),
+    };
+
+    let controls = column![
+        white_text("Read .txt, .md and .html files as soon as they are saved into this folder", 12),
+        Space::new().height(Length::Fixed(6.0)),
+        folder_row,
+        status,
+    ]
+    .spacing(0);
+
+    container(
+        row![
+            container(white_text("Watch Folder", 14))
+                .width(Length::Fixed(120.0))
+                .align_x(Alignment::Start),
+            Space::new().width(Length::Fixed(16.0)),
+            container(controls)
+                .width(Length::Fill)
+                .align_x(Alignment::Start),
+        ]
+        .align_y(Alignment::Center)
+        .width(Length::Fill)
+        .padding([12.0, 16.0])
+    )
+    .style(section_style)
+    .into()
+}
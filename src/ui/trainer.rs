@@ -0,0 +1,175 @@
+//! Pronunciation trainer window: listen to a sentence, record an attempt, compare.
+
+use iced::widget::{button, column, container, row, text_input, Space};
+use iced::{Alignment, Background, Color, Element, Length};
+
+use crate::activity::Activity;
+use crate::model::{App, Message};
+use crate::styles::{
+    circle_button_style, close_button_style, header_style, modal_content_style, section_style,
+    wave_bar_style,
+};
+use crate::system::waveform_envelope;
+use crate::ui::white_text;
+
+/// Number of bars drawn per waveform.
+const ENVELOPE_BARS: usize = 60;
+
+/// Sample rate of the reference and attempt buffers stored in [`App`].
+pub const TRAINER_SAMPLE_RATE: u32 = 16000;
+
+/// Length difference (as a fraction of the reference) considered "about the same".
+const SIMILAR_LENGTH_TOLERANCE: f32 = 0.1;
+
+/// Duration in seconds of a trainer buffer.
+fn duration_secs(samples: &[f32]) -> f32 {
+    samples.len() as f32 / TRAINER_SAMPLE_RATE as f32
+}
+
+/// A labelled waveform row, or a placeholder when there is no audio yet.
+fn waveform_row<'a>(label: &'a str, samples: Option<&[f32]>, placeholder: &'a str) -> Element<'a, Message> {
+    let body: Element<'a, Message> = match samples {
+        Some(samples) => row(waveform_envelope(samples, ENVELOPE_BARS)
+            .into_iter()
+            .map(|amplitude| {
+                container(
+                    Space::new()
+                        .width(Length::Fixed(3.0))
+                        .height(Length::Fixed(2.0 + amplitude * 38.0)),
+                )
+                .style(wave_bar_style)
+                .into()
+            })
+            .collect::<Vec<Element<Message>>>())
+        .spacing(3)
+        .align_y(Alignment::Center)
+        .into(),
+        None => white_text(placeholder, 12).into(),
+    };
+
+    let length = samples
+        .map(|s| format!("{:.1} s", duration_secs(s)))
+        .unwrap_or_default();
+
+    container(
+        row![
+            container(white_text(label, 14)).width(Length::Fixed(60.0)),
+            container(body)
+                .width(Length::Fill)
+                .height(Length::Fixed(44.0))
+                .center_y(Length::Fixed(44.0)),
+            container(white_text(length, 13)).width(Length::Fixed(50.0)),
+        ]
+        .align_y(Alignment::Center)
+        .padding([8.0, 16.0]),
+    )
+    .style(section_style)
+    .into()
+}
+
+/// Describe how the attempt's length compares with the reference.
+fn comparison_summary(reference: &[f32], attempt: &[f32]) -> String {
+    let reference_secs = duration_secs(reference);
+    let attempt_secs = duration_secs(attempt);
+    if reference_secs <= 0.0 {
+        return String::new();
+    }
+    let diff = (attempt_secs - reference_secs) / reference_secs;
+    if diff.abs() <= SIMILAR_LENGTH_TOLERANCE {
+        format!("Good pacing: {:.1} s vs {:.1} s", attempt_secs, reference_secs)
+    } else if diff > 0.0 {
+        format!(
+            "You took {:.1} s longer ({:+.0}%) - try speaking a bit faster",
+            attempt_secs - reference_secs,
+            diff * 100.0
+        )
+    } else {
+        format!(
+            "You were {:.1} s shorter ({:+.0}%) - try slowing down",
+            reference_secs - attempt_secs,
+            diff * 100.0
+        )
+    }
+}
+
+/// Pronunciation trainer window view.
+pub fn pronunciation_trainer_view<'a>(app: &'a App) -> Element<'a, Message> {
+    let sentence_input = text_input("Type or paste a sentence to practice", &app.trainer_sentence)
+        .on_input(Message::TrainerSentenceChanged)
+        .on_submit(Message::TrainerListen)
+        .padding(10)
+        .size(15);
+
+    let is_recording = app.trainer_recorder.is_some();
+    let loading = matches!(app.activity, Activity::Loading { .. });
+    let can_listen = !app.trainer_sentence.trim().is_empty() && !loading && !is_recording;
+
+    let mut listen_button = button(white_text(if loading { "Synthesizing..." } else { "Listen" }, 13))
+        .style(circle_button_style)
+        .padding([8.0, 16.0]);
+    if can_listen {
+        listen_button = listen_button.on_press(Message::TrainerListen);
+    }
+
+    let record_button = button(white_text(if is_recording { "Stop Recording" } else { "Record" }, 13))
+        .style(circle_button_style)
+        .padding([8.0, 16.0])
+        .on_press(Message::TrainerToggleRecording);
+
+    let summary = match (&app.trainer_reference, &app.trainer_attempt) {
+        (Some(reference), Some(attempt)) => comparison_summary(reference, attempt),
+        (Some(_), None) if is_recording => "Recording... press Stop Recording when done".to_string(),
+        (Some(_), None) => "Now record yourself saying the sentence".to_string(),
+        (None, _) => "Listen to the sentence first, then record your attempt".to_string(),
+    };
+
+    container(
+        column![
+            container(
+                row![
+                    white_text("Pronunciation Trainer", 20),
+                    Space::new().width(Length::Fill),
+                    button(
+                        container(white_text("✕", 18))
+                            .width(Length::Fixed(28.0))
+                            .height(Length::Fixed(28.0))
+                            .center_x(Length::Fixed(28.0))
+                            .center_y(Length::Fixed(28.0)),
+                    )
+                    .style(close_button_style)
+                    .on_press(Message::ClosePronunciationTrainer),
+                ]
+                .width(Length::Fill)
+                .align_y(Alignment::Center)
+            )
+            .width(Length::Fill)
+            .padding([20.0, 24.0])
+            .style(header_style),
+            container(
+                column![
+                    sentence_input,
+                    row![listen_button, Space::new().width(Length::Fixed(8.0)), record_button]
+                        .align_y(Alignment::Center),
+                    waveform_row("Voice", app.trainer_reference.as_deref(), "Not played yet"),
+                    waveform_row("You", app.trainer_attempt.as_deref(), "Not recorded yet"),
+                    white_text(summary, 13),
+                ]
+                .spacing(12)
+            )
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .padding([20.0, 24.0])
+            .style(|_theme| container::Style {
+                background: Some(Background::Color(Color::from_rgb(0.12, 0.12, 0.14))),
+                ..Default::default()
+            }),
+        ]
+        .spacing(0)
+        .width(Length::Fill)
+        .height(Length::Fill),
+    )
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .style(modal_content_style)
+    .into()
+}
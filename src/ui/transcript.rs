@@ -0,0 +1,97 @@
+//! Transcript window: the sentences of the text being spoken, the current
+//! one highlighted. Clicking a sentence seeks playback there.
+//!
+//! The sentences are those the provider synthesizes, after cleanup and
+//! pacing. Streamed readings report where each sentence starts in the audio,
+//! so highlighting and seeking follow the speech exactly once a sentence is
+//! synthesized; otherwise both are estimated from the characters (see
+//! [`crate::text::timing::spoken_sentence`]).
+
+use iced::widget::{button, column, container, row, scrollable, Space};
+use iced::{Alignment, Background, Color, Element, Length, Theme};
+
+use crate::model::{App, Message};
+use crate::styles::{close_button_style, color_scheme, header_style, modal_content_style};
+use crate::ui::white_text;
+
+/// Widget ID of the scrollable holding the sentences.
+pub const TRANSCRIPT_SCROLL_ID: &str = "transcript-sentences";
+
+/// Sentence button, tinted with the accent color when it is being spoken.
+fn sentence_style(current: bool) -> impl Fn(&Theme, button::Status) -> button::Style {
+    move |_theme, status| {
+        let alpha = match (current, status) {
+            (true, _) => 0.25,
+            (false, button::Status::Hovered | button::Status::Pressed) => 0.1,
+            (false, _) => 0.0,
+        };
+        button::Style {
+            background: Some(Background::Color(Color { a: alpha, ..color_scheme().accent })),
+            text_color: Color::WHITE,
+            border: iced::Border {
+                radius: 4.0.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+}
+
+/// Transcript window view.
+pub fn transcript_view(app: &App) -> Element<'_, Message> {
+    let body: Element<'_, Message> = if app.transcript.is_empty() {
+        container(white_text("Nothing is being read", 14))
+            .center_x(Length::Fill)
+            .center_y(Length::Fill)
+            .into()
+    } else {
+        scrollable(
+            column(app.transcript.iter().enumerate().map(|(index, sentence)| {
+                button(white_text(sentence.as_str(), 15))
+                    .style(sentence_style(app.transcript_sentence == Some(index)))
+                    .width(Length::Fill)
+                    .padding([4.0, 8.0])
+                    .on_press(Message::TranscriptSentenceClicked(index))
+                    .into()
+            }))
+            .spacing(2)
+            .padding([16.0, 20.0]),
+        )
+        .id(TRANSCRIPT_SCROLL_ID)
+        .height(Length::Fill)
+        .into()
+    };
+
+    container(
+        column![
+            container(
+                row![
+                    white_text("Transcript", 18),
+                    Space::new().width(Length::Fill),
+                    button(
+                        container(white_text("✕", 18))
+                            .width(Length::Fixed(28.0))
+                            .height(Length::Fixed(28.0))
+                            .center_x(Length::Fixed(28.0))
+                            .center_y(Length::Fixed(28.0)),
+                    )
+                    .style(close_button_style)
+                    .on_press(Message::CloseTranscript),
+                ]
+                .width(Length::Fill)
+                .align_y(Alignment::Center)
+            )
+            .width(Length::Fill)
+            .padding([14.0, 20.0])
+            .style(header_style),
+            body,
+        ]
+        .spacing(0)
+        .width(Length::Fill)
+        .height(Length::Fill),
+    )
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .style(modal_content_style)
+    .into()
+}
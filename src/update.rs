@@ -3,13 +3,24 @@
 use iced::window;
 use iced::{Size, Task};
 use std::sync::mpsc;
+use std::time::Instant;
 use tracing::{debug, error, info, trace, warn};
 
+use crate::activity::{Activity, Event};
+use crate::capture::{self, CaptureSource, Captured, SourcePriority};
 use crate::config;
-use crate::logging;
-use crate::model::{App, Message, OCRBackend, PlaybackState, TTSBackend};
-use crate::providers::{PiperTTSProvider, PollyTTSProvider, TTSProvider};
+use crate::logging::{self, trace_throttled};
+use crate::model::{
+    App, ColorRole, DuplicateCaptureAction, InterruptedReading, LongTextAction, Message, OCRBackend, PaletteCommand, SettingsSection,
+    SettingsTab, TTSBackend,
+};
+use crate::providers::{
+    default_output_kind, play_earcon, set_earcon_settings, Earcon, ElevenLabsTTSProvider, EspeakTTSProvider, macos_provider, windows_provider, OutputKind, PiperTTSProvider, PlaybackUpdate, PollyTTSProvider, samples_to_wav, TTSProvider, NUM_BANDS,
+};
 use crate::system;
+use crate::text;
+use crate::ui::palette::PALETTE_INPUT_ID;
+use crate::ui::trainer::TRAINER_SAMPLE_RATE;
 
 // Wrapper to make TTSProvider Send (required for cross-thread usage)
 // SAFETY: This is safe because we only move the provider between threads during initialization,
@@ -20,8 +31,25 @@ unsafe impl Send for SendTTSProvider {}
 // Static storage for provider during async initialization
 static PENDING_PROVIDER: std::sync::Mutex<Option<SendTTSProvider>> = std::sync::Mutex::new(None);
 
+// Voice and text of the reading handed over with the pending provider, for the synthesis cache
+static PENDING_CACHE_KEY: std::sync::Mutex<Option<(String, String)>> = std::sync::Mutex::new(None);
+
+// Provider built ahead of time for the next reading, keyed by backend and voice
+static WARM_PROVIDER: std::sync::Mutex<Option<(String, SendTTSProvider)>> = std::sync::Mutex::new(None);
+
+// When the current reading was requested (hotkey, tray, palette), for the latency metric
+static READ_REQUESTED_AT: std::sync::Mutex<Option<Instant>> = std::sync::Mutex::new(None);
+
+// Whether the next reading starts paused, as when the voice changes during a paused reading
+static START_PAUSED: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+// Application that had the focus when the selection was last captured
+static CAPTURE_APP: std::sync::Mutex<Option<system::ForegroundApp>> = std::sync::Mutex::new(None);
+
 const SKIP_SECONDS: f32 = 5.0;
-const NUM_BANDS: usize = 10;
+
+/// How often typing is checked while reading with "pause while typing" on.
+pub const TYPING_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_millis(400);
 
 /// Check if an error string indicates an AWS credential/authentication issue.
 fn is_aws_credential_error(error_str: &str) -> bool {
@@ -59,18 +87,71 @@ where
     Task::none()
 }
 
-/// Set loading state on the app with a status message.
-fn set_loading_state(app: &mut App, status: &str) {
-    app.is_loading = true;
-    app.loading_animation_time = 0.0;
-    app.status_text = Some(status.to_string());
+/// Move the activity on with `event` (see [`crate::activity`]).
+///
+/// A new reading stops the one playing, and any change of activity clears
+/// the note in the status line.
+fn transition(app: &mut App, event: Event) {
+    let Some(next) = app.activity.on(event.clone()) else {
+        debug!(activity = ?app.activity, ?event, "Event does not apply, activity unchanged");
+        return;
+    };
+    if next == app.activity {
+        return;
+    }
+    if matches!(event, Event::Load(_)) && app.activity.has_speech() {
+        if let Some(ref mut provider) = app.provider {
+            if let Err(e) = provider.stop() {
+                error!(error = %e, "Failed to stop playback for the new reading");
+            }
+        }
+    }
+    if next.is_busy() && !app.activity.is_busy() {
+        app.loading_animation_time = 0.0;
+    }
+    if matches!(event, Event::Load(_)) && !app.activity.is_busy() {
+        // A new reading: its sentences come with the text handed to the provider
+        app.transcript.clear();
+        app.spoken_sentences.clear();
+        app.transcript_sentence = None;
+    }
+    debug!(from = ?app.activity, to = ?next, "Activity changed");
+    app.activity = next;
+    app.status_text = None;
 }
 
-/// Clear loading state on the app.
-fn clear_loading_state(app: &mut App) {
-    app.is_loading = false;
-    app.loading_animation_time = 0.0;
-    app.status_text = None;
+/// Leave the loading state when what was prepared is not read aloud
+/// (a dictated note, a file without text).
+fn finish_loading(app: &mut App) {
+    if matches!(app.activity, Activity::Loading { .. }) {
+        transition(app, Event::Stop);
+    }
+}
+
+/// Start the podcast feed server if the feed is enabled and not already running.
+pub(crate) fn start_feed_server(app: &mut App) {
+    if !app.feed_config.enabled || app.feed_server.is_some() {
+        return;
+    }
+    app.feed_url = app.feed_config.feed_url();
+    match system::FeedServer::start(&app.feed_config) {
+        Ok(server) => {
+            app.feed_server = Some(server);
+            app.feed_error = None;
+        }
+        Err(e) => {
+            warn!(error = %e, "Failed to start podcast feed server");
+            app.feed_error = Some(e);
+        }
+    }
+}
+
+/// Persist feed metadata and regenerate the feed document.
+fn save_feed_metadata(app: &App) {
+    config::save_feed_config(&app.feed_config);
+    if let Err(e) = system::refresh_feed(&app.feed_config) {
+        warn!(error = %e, "Failed to refresh podcast feed");
+    }
 }
 
 /// Open a URL in the default browser (cross-platform).
@@ -95,6 +176,104 @@ fn open_settings_window() -> (window::Id, Task<Message>) {
     (window_id, task.map(Message::WindowOpened))
 }
 
+/// Load the Piper and AWS Polly voice lists unless they are loaded or already being fetched.
+///
+/// Called when the settings window opens rather than at startup, so launching
+/// the app does no network requests or credential checks. Cached lists show
+/// right away; those older than [`crate::voices::VOICE_LIST_TTL`] are fetched
+/// again in the background. The ElevenLabs and system voice lists are
+/// fetched only while that provider is selected.
+fn fetch_voice_lists(app: &mut App) -> Task<Message> {
+    let elevenlabs = if app.selected_backend == TTSBackend::ElevenLabs && app.elevenlabs_voices.is_none() {
+        load_elevenlabs_voices(app)
+    } else {
+        Task::none()
+    };
+    let system = if app.selected_backend.is_system() && app.system_voices.is_none() {
+        load_system_voices(app.selected_backend)
+    } else {
+        Task::none()
+    };
+    Task::batch([load_piper_voices(app, false), load_polly_voices(app, false), elevenlabs, system])
+}
+
+/// Show the cached Piper voice list, fetching it if it is missing or old, or
+/// right away with `refresh`.
+fn load_piper_voices(app: &mut App, refresh: bool) -> Task<Message> {
+    if app.voices_loading {
+        return Task::none();
+    }
+    if app.voices.is_none() {
+        app.voices = crate::voices::load_cached_voices_json();
+    }
+    if app.voices.is_some() && !refresh && crate::voices::voices_json_fresh() {
+        return Task::none();
+    }
+    app.voices_loading = true;
+    Task::perform(
+        async {
+            debug!("Fetching voices.json from Hugging Face");
+            crate::voices::fetch_voices_json().await
+        },
+        Message::VoicesJsonLoaded,
+    )
+}
+
+/// Show the cached AWS Polly voice list, fetching it if it is missing or old,
+/// or right away with `refresh`.
+fn load_polly_voices(app: &mut App, refresh: bool) -> Task<Message> {
+    if app.polly_voices_loading {
+        return Task::none();
+    }
+    if app.polly_voices.is_none() {
+        app.polly_voices = crate::voices::aws::load_cached_polly_voices();
+    }
+    if app.polly_voices.is_some() && !refresh && crate::voices::aws::polly_voices_fresh() {
+        return Task::none();
+    }
+    app.polly_voices_loading = true;
+    Task::perform(
+        async {
+            // Check credentials first before attempting to fetch
+            if PollyTTSProvider::check_credentials().is_ok() {
+                debug!("Fetching AWS Polly voices");
+                crate::voices::aws::fetch_polly_voices().await
+            } else {
+                debug!("AWS credentials not available, skipping voice fetch");
+                Err("AWS credentials not configured".to_string())
+            }
+        },
+        Message::PollyVoicesLoaded,
+    )
+}
+
+/// Fetch the voices of the ElevenLabs account, unless they are already being fetched.
+fn load_elevenlabs_voices(app: &mut App) -> Task<Message> {
+    if app.elevenlabs_voices_loading {
+        return Task::none();
+    }
+    app.elevenlabs_voices_loading = true;
+    let api_key = config::load_elevenlabs_api_key();
+    Task::perform(
+        crate::voices::elevenlabs::fetch_elevenlabs_voices(api_key),
+        Message::ElevenLabsVoicesLoaded,
+    )
+}
+
+/// List the voices installed with the system of a system voice `backend`.
+fn load_system_voices(backend: TTSBackend) -> Task<Message> {
+    Task::perform(
+        async move {
+            if backend == TTSBackend::Windows {
+                crate::voices::system::list_windows_voices()
+            } else {
+                crate::voices::system::list_macos_voices()
+            }
+        },
+        Message::SystemVoicesLoaded,
+    )
+}
+
 /// Helper to open a simple info window (centered, non-resizable).
 /// Returns the window ID and task mapped to Message::WindowOpened.
 fn open_info_window(size: Size) -> (window::Id, Task<Message>) {
@@ -119,7 +298,7 @@ fn close_window_if_some(window_id: Option<window::Id>) -> Task<Message> {
 /// Returns the window ID and task mapped to Message::WindowOpened.
 fn open_main_window() -> (window::Id, Task<Message>) {
     let (window_id, task) = window::open(window::Settings {
-        size: Size::new(410.0, 70.0),
+        size: Size::new(450.0, 70.0),
         resizable: false,
         decorations: false,
         transparent: true,
@@ -137,14 +316,192 @@ fn open_main_window() -> (window::Id, Task<Message>) {
     (window_id, task.map(Message::WindowOpened))
 }
 
+/// Kinds of resizable windows whose size and position are remembered.
+const EXTRACTED_TEXT_WINDOW: &str = "extracted_text";
+const SCREENSHOT_VIEWER_WINDOW: &str = "screenshot_viewer";
+
+/// Open a resizable, decorated window at its last size and position, or
+/// centered at `default_size` the first time.
+/// Returns the window ID and task mapped to Message::WindowOpened.
+fn open_remembered_window(app: &mut App, kind: &'static str, default_size: Size) -> (window::Id, Task<Message>) {
+    let geometry = config::load_window_geometry(kind).unwrap_or(config::WindowGeometry {
+        width: default_size.width,
+        height: default_size.height,
+        position: None,
+    });
+    let position = match geometry.position {
+        Some((x, y)) => window::Position::Specific(iced::Point::new(x.max(0.0), y.max(0.0))),
+        None => window::Position::Centered,
+    };
+    let (window_id, task) = window::open(window::Settings {
+        size: Size::new(geometry.width, geometry.height),
+        resizable: true,
+        decorations: true,
+        transparent: false,
+        visible: true,
+        position,
+        ..Default::default()
+    });
+    app.window_geometry.insert(window_id, (kind, geometry));
+    (window_id, task.map(Message::WindowOpened))
+}
+
+/// Open the extracted text dialog window (resizable, decorated).
+/// Returns the window ID and task mapped to Message::WindowOpened.
+fn open_extracted_text_window(app: &mut App) -> (window::Id, Task<Message>) {
+    open_remembered_window(app, EXTRACTED_TEXT_WINDOW, Size::new(600.0, 400.0))
+}
+
+/// How the HTML flavor of a selection is verbalized, from the app settings.
+pub(crate) fn speech_options(app: &App) -> text::html::SpeechOptions {
+    text::html::SpeechOptions {
+        read_alt_text: app.read_alt_text,
+    }
+}
+
+/// Read the selected text, keeping its structure when the selection is
+/// available as HTML (headings, lists and images are verbalized) or RTF.
+/// Blocks on the clipboard.
+pub(crate) fn capture_selected_text(options: text::html::SpeechOptions) -> Option<String> {
+    // Probe before copying: some clipboard helpers briefly take the focus
+    if let Ok(mut capture_app) = CAPTURE_APP.lock() {
+        *capture_app = system::foreground_app();
+    }
+    let converted = match crate::system::get_selected_content()? {
+        crate::system::SelectedContent::Text(text) => return Some(text),
+        crate::system::SelectedContent::Html(html) => text::html::html_to_speech(&html, options),
+        crate::system::SelectedContent::Rtf(rtf) => text::rtf::rtf_to_text(&rtf),
+    };
+    if converted.trim().is_empty() {
+        debug!("Rich selection has no readable text, using plain text");
+        return crate::system::get_selected_text();
+    }
+    debug!(bytes = converted.len(), "Using the rich flavor of the selection");
+    Some(converted)
+}
+
 /// Fetch selected text asynchronously.
 /// Returns a Task that will complete with SelectedTextFetched message.
-fn fetch_selected_text_task(context: &'static str) -> Task<Message> {
+fn fetch_selected_text_task(app: &App, context: &'static str) -> Task<Message> {
+    fetch_selected_text_then(app, context, Message::SelectedTextFetched)
+}
+
+/// Read the text of a capture source for the hotkey. Blocks on the clipboard.
+fn read_capture_source(source: CaptureSource, options: text::html::SpeechOptions) -> Option<String> {
+    match source {
+        CaptureSource::Selection => capture_selected_text(options),
+        CaptureSource::Clipboard => {
+            if let Ok(mut capture_app) = CAPTURE_APP.lock() {
+                *capture_app = system::foreground_app();
+            }
+            system::get_clipboard_text()
+        }
+        // Never read without asking first
+        CaptureSource::Ocr => None,
+    }
+}
+
+/// Start a hotkey reading with the text of the first of `sources` that has some.
+fn hotkey_read(app: &mut App, edit_first: bool, sources: &'static [CaptureSource]) -> Task<Message> {
+    if edit_first {
+        return fetch_hotkey_sources_then(app, sources, Message::SelectedTextForEditing);
+    }
+    // Use the same logic as ReadSelected
+    if app.defer_while_mic_active || app.preview_before_reading {
+        // Held during calls or previewed first, decided once the text is in
+        return fetch_hotkey_text_task(app, sources);
+    }
+    let fetch_task = fetch_hotkey_sources_then(app, sources, Message::SelectedTextFetched);
+    Task::batch([show_main_window(app), fetch_task])
+}
+
+/// Fetch the text of the first of `sources` that has some, completing with
+/// the given message, or offering OCR if the sources run out at it.
+fn fetch_hotkey_sources_then(
+    app: &App,
+    sources: &'static [CaptureSource],
+    on_fetched: fn(Option<String>) -> Message,
+) -> Task<Message> {
+    let options = speech_options(app);
+    if let Ok(mut requested_at) = READ_REQUESTED_AT.lock() {
+        *requested_at = Some(Instant::now());
+    }
+    Task::perform(
+        async move {
+            debug!(?sources, "Fetching hotkey text");
+            let result = tokio::task::spawn_blocking(move || {
+                capture::capture_text(sources, |source| read_capture_source(source, options))
+            })
+            .await;
+            result.unwrap_or_else(|e| {
+                tracing::warn!(error = %e, "Failed to join blocking task for hotkey text fetch");
+                Captured::Nothing
+            })
+        },
+        move |captured| match captured {
+            Captured::Text(text) => on_fetched(Some(text)),
+            Captured::OfferOcr => Message::OfferOcrCapture,
+            Captured::Nothing => on_fetched(None),
+        },
+    )
+}
+
+/// Fetch the text for a hotkey reading from `sources`, checking at the same time
+/// whether another app is using the microphone if readings are held during calls.
+fn fetch_hotkey_text_task(app: &App, sources: &'static [CaptureSource]) -> Task<Message> {
+    let options = speech_options(app);
+    let probe_microphone = app.defer_while_mic_active;
+    if let Ok(mut requested_at) = READ_REQUESTED_AT.lock() {
+        *requested_at = Some(Instant::now());
+    }
+    Task::perform(
+        async move {
+            let result = tokio::task::spawn_blocking(move || {
+                let captured = capture::capture_text(sources, |source| read_capture_source(source, options));
+                (captured, probe_microphone && system::microphone_in_use())
+            })
+            .await;
+            result.unwrap_or_else(|e| {
+                tracing::warn!(error = %e, "Failed to join blocking task for hotkey text fetch");
+                (Captured::Nothing, false)
+            })
+        },
+        |(captured, mic_in_use)| match captured {
+            Captured::Text(text) => Message::HotkeyTextCaptured(Some(text), mic_in_use),
+            Captured::OfferOcr => Message::OfferOcrCapture,
+            Captured::Nothing => Message::HotkeyTextCaptured(None, mic_in_use),
+        },
+    )
+}
+
+/// Open the capture source prompt, asking which source to read or, when
+/// `asking` is false, offering to capture a screen region for OCR.
+fn open_capture_source_prompt(app: &mut App, asking: bool, edit_first: bool) -> Task<Message> {
+    app.capture_source_asking = asking;
+    app.capture_source_edit_first = edit_first;
+    if app.capture_source_window_id.is_some() {
+        return Task::none();
+    }
+    let (window_id, task) = open_info_window(Size::new(380.0, 220.0));
+    app.capture_source_window_id = Some(window_id);
+    task
+}
+
+/// Fetch selected text asynchronously, completing with the given message.
+fn fetch_selected_text_then(
+    app: &App,
+    context: &'static str,
+    on_fetched: fn(Option<String>) -> Message,
+) -> Task<Message> {
+    let options = speech_options(app);
+    if let Ok(mut requested_at) = READ_REQUESTED_AT.lock() {
+        *requested_at = Some(Instant::now());
+    }
     Task::perform(
         async move {
             debug!("Fetching selected text: {}", context);
-            let result = tokio::task::spawn_blocking(|| {
-                crate::system::get_selected_text()
+            let result = tokio::task::spawn_blocking(move || {
+                capture_selected_text(options)
             })
             .await;
             result.unwrap_or_else(|e| {
@@ -152,7 +509,7 @@ fn fetch_selected_text_task(context: &'static str) -> Task<Message> {
                 None
             })
         },
-        Message::SelectedTextFetched,
+        on_fetched,
     )
 }
 
@@ -163,15 +520,68 @@ fn open_settings_if_needed(app: &mut App, error_msg: String) -> Task<Message> {
         let (window_id, task) = open_settings_window();
         app.settings_window_id = Some(window_id);
         app.show_settings_modal = true;
+        config::begin_staging();
+        restore_language_browsing(app);
         task
     } else {
         Task::none()
     };
 
+    // Errors are shown in the provider section
+    app.settings_tab = SettingsTab::Voices;
+    app.settings_search.clear();
     app.error_message = Some(error_msg);
     task
 }
 
+/// Measure disk usage of each storage category in the background.
+fn measure_storage_task() -> Task<Message> {
+    Task::perform(
+        async {
+            tokio::task::spawn_blocking(crate::storage::usage)
+                .await
+                .unwrap_or_default()
+        },
+        Message::StorageMeasured,
+    )
+}
+
+/// Detect the hardware Piper can run voices on, once per run.
+fn detect_execution_providers_task(app: &App) -> Task<Message> {
+    if app.available_execution_providers.is_some() {
+        return Task::none();
+    }
+    Task::perform(
+        async {
+            tokio::task::spawn_blocking(PiperTTSProvider::available_execution_providers)
+                .await
+                .unwrap_or_default()
+        },
+        Message::ExecutionProvidersDetected,
+    )
+}
+
+/// Take the application the last selection was captured from.
+fn take_capture_app() -> Option<system::ForegroundApp> {
+    CAPTURE_APP.lock().ok().and_then(|mut app| app.take())
+}
+
+/// Whether the text being read was captured from an app that must stay offline.
+fn capture_is_local_only(app: &App) -> bool {
+    app.capture_app
+        .as_ref()
+        .is_some_and(|capture_app| capture_app.matches_any(app.local_only_apps.split(',')))
+}
+
+/// Backend to read the current capture with: Piper for apps that must stay offline.
+fn reading_backend(app: &App) -> TTSBackend {
+    if !app.selected_backend.is_local() && capture_is_local_only(app) {
+        info!(app = ?app.capture_app, "Capture from an offline-only app, reading with Piper");
+        return TTSBackend::Piper;
+    }
+    app.selected_backend
+}
+
 /// Process text: send to cleanup API if enabled, otherwise return task to initialize TTS directly.
 /// Sets loading state before returning.
 fn process_text_for_tts(
@@ -179,727 +589,3684 @@ fn process_text_for_tts(
     text: String,
     context: &'static str,
 ) -> Task<Message> {
-    if app.text_cleanup_enabled {
-        set_loading_state(app, "Processing content...");
+    interrupt_reading(app, &text);
+    if app
+        .current_document
+        .as_ref()
+        .is_some_and(|document| document.text_hash != text::content_hash(&text))
+    {
+        leave_document(app);
+    }
+    record_listening(app);
+    record_reading(app, &text, context);
+    app.processing_trace = text::pipeline::Trace::new(&text);
+    let captured = text.clone();
+    let text = app.plugin_host.transform(text);
+    if text != captured {
+        app.processing_trace.record("Plugins", &text);
+    }
+    app.reading_text = Some(text.clone());
+    app.current_word = None;
+    let text = text::pipeline::prepare(text, &spoken_rules(app));
+    app.processing_trace.record("Cleanup", &text);
+    let suggest_task = suggest_voice_download(app, &text);
+    if app.text_cleanup_enabled && capture_is_local_only(app) {
+        info!(context, "Capture from an offline-only app, skipping Natural Reading");
+    } else if app.text_cleanup_enabled {
+        transition(app, Event::Load("Processing content...".to_string()));
         info!(context, "Natural Reading enabled, sending to service");
-        Task::perform(
+        let cleanup_task = Task::perform(
             async move { system::cleanup_text(&text).await },
             Message::TextCleanupResponse,
-        )
-    } else {
-        set_loading_state(app, "Synthesizing voice...");
-        info!(context, "Initializing TTS directly");
-        initialize_tts_async(app.selected_backend, text, context, app.selected_polly_voice.clone())
+        );
+        return Task::batch([suggest_task, cleanup_task]);
     }
+    transition(app, Event::Load("Synthesizing voice...".to_string()));
+    info!(context, "Initializing TTS directly");
+    let backend = reading_backend(app);
+    let text = paced(app, text, backend);
+    let tts_task = initialize_tts_async(backend, text, context, app.selected_polly_voice.clone());
+    Task::batch([suggest_task, tts_task])
 }
 
-/// Initialize TTS provider and start speaking with the given text asynchronously.
-/// Returns a Task that will complete when synthesis is done.
-/// This prevents blocking the UI thread during TTS synthesis.
-fn initialize_tts_async(
-    backend: TTSBackend,
-    text: String,
-    context: &'static str,
-    polly_voice_id: Option<String>,
-) -> Task<Message> {
-    info!(
-        context,
-        backend = ?backend,
-        bytes = text.len(),
-        "Starting async TTS initialization"
-    );
+/// Which text passes run, from the Spoken Text settings.
+fn spoken_rules(app: &App) -> text::pipeline::Rules {
+    text::pipeline::Rules {
+        profile: app.text_profile,
+        read_math: app.read_math,
+        list_prosody: app.list_prosody,
+    }
+}
 
-    // Check AWS credentials before attempting to initialize (synchronous, fast)
-    if backend == TTSBackend::AwsPolly {
-        if let Err(e) = PollyTTSProvider::check_credentials() {
-            warn!("AWS credentials not found during initialization");
-            return Task::perform(
-                async move { Err(e) },
-                Message::TTSInitialized,
-            );
-        }
+/// Mark list pacing for the provider (see [`text::pipeline::pace`]), and
+/// finish the trace of the reading for the Processing Chain window and its
+/// sentences for the transcript.
+fn paced(app: &mut App, text: String, backend: TTSBackend) -> String {
+    let text = text::pipeline::pace(text, &spoken_rules(app));
+    app.processing_trace.record("Pacing", &text);
+    app.processing_trace.finish(&text, backend);
+    set_transcript(app, &text);
+    text
+}
+
+/// Split the text handed to the provider into its sentences, kept paced for
+/// a voice change and without the pacing for the transcript.
+fn set_transcript(app: &mut App, text: &str) {
+    // Same split as the streamed parts, so sentence starts line up
+    app.spoken_sentences = text::sentences::sentences(text).into_iter().map(str::to_string).collect();
+    app.transcript = app
+        .spoken_sentences
+        .iter()
+        .map(|sentence| sentence.replace(text::prosody::PAUSE, "").trim().to_string())
+        .collect();
+    app.transcript_sentence = None;
+}
+
+/// Read the rest of the reading again with the voice just selected, from the
+/// sentence being spoken, instead of finishing it with the old voice. A
+/// paused reading is ready to resume with the new voice.
+fn swap_voice(app: &mut App) -> Task<Message> {
+    let backend = reading_backend(app);
+    if backend != app.selected_backend {
+        debug!("Reading uses another provider, the voice applies to the next reading");
+        return Task::none();
     }
+    let from = app.transcript_sentence.unwrap_or(0);
+    // From the paced sentences, so list pauses carry over
+    let rest = app.spoken_sentences.get(from..).map(|sentences| sentences.join(" ")).unwrap_or_default();
+    if rest.trim().is_empty() {
+        return Task::none();
+    }
+    info!(sentence = from, sentences = app.transcript.len(), "Voice changed during playback, synthesizing the rest with it");
+    // A paused reading stays paused with the new voice
+    START_PAUSED.store(app.activity == Activity::Paused, std::sync::atomic::Ordering::Relaxed);
+    // The rest is read as a reading of its own, so the document position is saved now
+    leave_document(app);
+    record_listening(app);
+    transition(app, Event::Load("Switching voice...".to_string()));
+    app.reading_text = Some(rest.clone());
+    app.current_word = None;
+    app.last_capture_hash = None;
+    set_transcript(app, &rest);
+    initialize_tts_async(backend, rest, "VoiceChanged", app.selected_polly_voice.clone())
+}
 
-    // Create provider (this is fast and happens on main thread)
-    let provider_result = match backend {
-        TTSBackend::Piper => PiperTTSProvider::new().map(|p| Box::new(p) as Box<dyn TTSProvider>),
-        TTSBackend::AwsPolly => {
-            // Use provided voice ID or fall back to config/default
-            let voice_id = polly_voice_id.or_else(|| config::load_selected_polly_voice());
-            PollyTTSProvider::new(voice_id).map(|p| Box::new(p) as Box<dyn TTSProvider>)
-        }
+/// Keep the audio of the reading that just played to its end in the
+/// synthesis cache (see [`crate::synthesis_cache`]), in the background.
+fn cache_synthesized_reading(app: &mut App) {
+    let (Some((voice, text)), Some(provider)) = (app.synthesis_cache_key.take(), app.provider.as_ref()) else {
+        return;
+    };
+    if app.synthesis_cache_mb == 0 {
+        return;
     }
-    .map_err(|e| format!("{}", e));
+    let (samples, sample_rate) = provider.synthesized_audio();
+    let limit_mb = app.synthesis_cache_mb;
+    std::thread::spawn(move || crate::synthesis_cache::store(&voice, &text, &samples, sample_rate, limit_mb));
+}
 
-    match provider_result {
-        Ok(provider) => {
-            // Wrap provider to make it Send-safe for cross-thread usage
-            let send_provider = SendTTSProvider(provider);
-            
-            // Spawn thread to do blocking synthesis
-            let (tx, rx) = mpsc::channel();
-            
-            std::thread::spawn(move || {
-                let mut send_provider = send_provider;
-                let provider = &mut send_provider.0;
-                info!(text = %text, "Synthesizing text");
-                let result = provider.speak(&text);
-                
-                match result {
-                    Ok(()) => {
-                        info!(context, "TTS synthesis completed successfully");
-                        if let Ok(mut guard) = PENDING_PROVIDER.lock() {
-                            *guard = Some(send_provider);
-                        }
-                        let _ = tx.send(Ok(()));
-                    }
-                    Err(e) => {
-                        error!(error = %e, "TTS speak failed");
-                        let error_msg = format_tts_error(&format!("{}", e), backend);
-                        let _ = tx.send(Err(error_msg));
-                    }
-                }
-            });
+/// Least confidence in the detected language for a voice to be suggested.
+const MIN_VOICE_SUGGESTION_CONFIDENCE: f32 = 0.6;
 
-            // Return a task that waits for synthesis (non-blocking for UI)
-            Task::perform(
-                async move {
-                    tokio::task::spawn_blocking(move || {
-                        rx.recv().unwrap_or_else(|e| Err(format!("Channel error: {}", e)))
-                    })
-                    .await
-                    .unwrap_or_else(|e| Err(format!("Task join error: {}", e)))
-                },
-                Message::TTSInitialized,
-            )
-        }
-        Err(e) => {
-            let error_msg = format_tts_error(&e, backend);
-            Task::perform(
-                async move { Err(error_msg) },
-                Message::TTSInitialized,
-            )
-        }
+/// How long the voice suggestion popup stays up unanswered.
+const VOICE_SUGGESTION_SECONDS: u64 = 20;
+
+/// Look for a Piper voice to offer when `text` is in a language no installed
+/// voice speaks. The text is read with the current voice either way.
+fn suggest_voice_download(app: &App, text: &str) -> Task<Message> {
+    if reading_backend(app) != TTSBackend::Piper || app.voice_suggestion.is_some() || app.downloading_voice.is_some() {
+        return Task::none();
+    }
+    let Some(detection) = text::language::detect_language(text) else {
+        return Task::none();
+    };
+    // "zh-Hans" and "zh-Hant" are both spoken by the "zh" voices
+    let family = detection.language.split('-').next().unwrap_or(detection.language).to_string();
+    if detection.confidence < MIN_VOICE_SUGGESTION_CONFIDENCE || app.declined_voice_languages.contains(&family) {
+        return Task::none();
     }
+    let selected_voice = PiperTTSProvider::selected_voice_key();
+    let installed = crate::voices::download::installed_voice_keys();
+    if std::iter::once(&selected_voice)
+        .chain(&installed)
+        .any(|voice_key| crate::voices::voice_family(voice_key) == family)
+    {
+        return Task::none();
+    }
+    debug!(language = %family, confidence = detection.confidence, "No installed voice for the text's language");
+    let known_voices = app.voices.clone();
+    Task::perform(
+        async move {
+            // The voice list is only fetched when settings open, it may not be loaded yet
+            let voices = match known_voices.or_else(crate::voices::load_cached_voices_json) {
+                Some(voices) => voices,
+                None => crate::voices::fetch_voices_json().await.ok()?,
+            };
+            crate::voices::suggest_voice(&voices, &family).cloned()
+        },
+        Message::VoiceSuggested,
+    )
 }
 
-pub fn update(app: &mut App, message: Message) -> Task<Message> {
-    match message {
-        Message::SkipBackward => {
-            handle_skip(app, |p| p.skip_backward(SKIP_SECONDS), "backward")
-        }
-        Message::SkipForward => {
-            handle_skip(app, |p| p.skip_forward(SKIP_SECONDS), "forward")
-        }
-        Message::PlayPause => {
-            let Some(ref mut provider) = app.provider else {
-                warn!("PlayPause received with no active provider");
-                return Task::none();
-            };
-            
-            match app.playback_state {
-                PlaybackState::Playing => {
+/// OCR the image on the clipboard, for the clipboard image hotkey.
+///
+/// With `private` set, the image is handed to OCR in memory instead of as a temp file.
+fn read_clipboard_image_task(private: bool) -> Task<Message> {
+    Task::perform(
+        async move {
+            tokio::task::spawn_blocking(move || {
+                let lines = if private {
+                    system::extract_text_lines_from_png(&system::get_clipboard_image_png()?)?
+                } else {
+                    system::extract_text_lines(&system::get_clipboard_image()?)?
+                };
+                Ok(system::lines_text(&lines))
+            })
+            .await
+            .unwrap_or_else(|e| Err(format!("Task join error: {}", e)))
+        },
+        Message::ClipboardImageTextExtracted,
+    )
+}
+
+/// Scroll the reading view so the current sentence stays near the middle.
+///
+/// Runs on every playback update, so the text glides along as progress advances.
+fn follow_current_sentence(app: &App) -> Task<Message> {
+    if app.reading_view_window_id.is_none() || !app.reading_style.focus_follow {
+        return Task::none();
+    }
+    match crate::ui::reading::current_position(app) {
+        Some(position) => iced::widget::operation::snap_to(
+            crate::ui::reading::READING_SCROLL_ID,
+            iced::widget::scrollable::RelativeOffset { x: 0.0, y: position.fraction },
+        ),
+        None => Task::none(),
+    }
+}
+
+/// Scroll the transcript so the sentence being spoken is in view.
+///
+/// Runs when the spoken sentence changes.
+fn follow_transcript(app: &App) -> Task<Message> {
+    match (app.transcript_window_id, app.transcript_sentence) {
+        (Some(_), Some(index)) if app.transcript.len() > 1 => iced::widget::operation::snap_to(
+            crate::ui::transcript::TRANSCRIPT_SCROLL_ID,
+            iced::widget::scrollable::RelativeOffset { x: 0.0, y: index as f32 / (app.transcript.len() - 1) as f32 },
+        ),
+        _ => Task::none(),
+    }
+}
+
+/// Path typed in settings, with a leading `~/` standing for the home folder.
+fn expand_home(dir: &str) -> std::path::PathBuf {
+    match (dir.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => std::path::PathBuf::from(dir),
+    }
+}
+
+/// Close the main bar, leaving mini players open.
+fn close_main_window(app: &App) -> Task<Message> {
+    match app.main_window_id {
+        Some(id) => window::close(id),
+        None => window::latest().and_then(window::close),
+    }
+}
+
+/// Show the main window if it is hidden or closed.
+fn show_main_window(app: &mut App) -> Task<Message> {
+    if !app.window_hidden && app.main_window_id.is_some() {
+        return Task::none();
+    }
+    let (window_id, open_task) = open_main_window();
+    app.main_window_id = Some(window_id);
+    app.window_hidden = false;
+    open_task
+}
+
+/// Read text as if it had just been selected, showing the main window first if needed.
+fn read_in_main_window(app: &mut App, text: String) -> Task<Message> {
+    let read_task = Task::perform(async move { Message::SelectedTextFetched(Some(text)) }, |msg| msg);
+    Task::batch([show_main_window(app), read_task])
+}
+
+/// Open text in the extracted text editor, to be read from there.
+fn open_text_in_editor(app: &mut App, text: String) -> Task<Message> {
+    app.extracted_text_editor = Some(iced::widget::text_editor::Content::with_text(&text));
+    app.extracted_text = Some(text);
+    if app.extracted_text_dialog_window_id.is_some() {
+        return Task::none();
+    }
+    let (window_id, open_task) = open_extracted_text_window(app);
+    app.extracted_text_dialog_window_id = Some(window_id);
+    open_task
+}
+
+/// Handle each message in turn, as if the user had made the changes.
+fn dispatch_all(messages: Vec<Message>) -> Task<Message> {
+    Task::batch(
+        messages
+            .into_iter()
+            .map(|msg| Task::perform(async move { msg }, |msg| msg)),
+    )
+}
+
+/// Volume change per scroll wheel line over the waveform.
+const VOLUME_STEP: f32 = 0.05;
+
+/// Speed change per horizontal scroll wheel line.
+const SPEED_STEP: f32 = 0.1;
+
+/// Playback speed range reachable by scrolling.
+const MIN_SPEED: f32 = 0.5;
+const MAX_SPEED: f32 = 3.0;
+
+/// Speeds the speed button of the main window steps through.
+const SPEED_PRESETS: [f32; 9] = [0.5, 0.75, 1.0, 1.25, 1.5, 1.75, 2.0, 2.5, 3.0];
+
+/// Change the playback speed of the current and next readings and save it.
+fn apply_playback_speed(app: &mut App, speed: f32) {
+    app.playback_speed = speed;
+    if let Some(ref mut provider) = app.provider {
+        provider.set_speed(speed);
+    }
+    config::save_playback_speed(speed);
+}
+
+/// Move playback `offset` sentences away from the current one.
+///
+/// Streamed readings jump to where the sentence starts in the audio; otherwise
+/// the position is estimated from the text.
+fn seek_sentences(app: &mut App, offset: i32) {
+    let (Some(provider), Some(text)) = (app.provider.as_mut(), app.reading_text.as_ref()) else {
+        return;
+    };
+    if provider.skip_sentences(offset) {
+        debug!(offset, "Skipped sentences");
+    } else {
+        let paragraphs = crate::text::split_paragraphs(text);
+        let Some(target) = text::timing::sentence_seek(&paragraphs, app.progress, offset) else {
+            return;
+        };
+        debug!(offset, target, "Seeking by sentences");
+        provider.seek(target);
+    }
+    app.progress = provider.get_progress();
+}
+
+/// Change the speech volume of the current and next readings.
+fn apply_playback_volume(app: &mut App, volume: f32) {
+    app.playback_volume = volume;
+    match app.provider {
+        Some(ref mut provider) => provider.set_volume(volume),
+        None => crate::providers::set_playback_volume(volume),
+    }
+}
+
+/// Languages kept in the recent row of the voice language grid.
+const RECENT_LANGUAGES: usize = 4;
+
+/// Voices kept at the top of the settings voice section.
+const RECENT_VOICES: usize = 5;
+
+/// Move a language to the front of the recently browsed ones and persist them.
+fn remember_recent_language(app: &mut App, lang_code: &str) {
+    app.recent_languages.retain(|code| code != lang_code);
+    app.recent_languages.insert(0, lang_code.to_string());
+    app.recent_languages.truncate(RECENT_LANGUAGES);
+    config::save_recent_languages(&app.recent_languages);
+}
+
+/// Move a voice to the front of the ones recently chosen with the selected provider and persist them.
+fn remember_recent_voice(app: &mut App, voice_key: &str) {
+    app.recent_voices.retain(|key| key != voice_key);
+    app.recent_voices.insert(0, voice_key.to_string());
+    app.recent_voices.truncate(RECENT_VOICES);
+    config::save_recent_voices(app.selected_backend, &app.recent_voices);
+}
+
+/// Pick voice browsing up where it was left with the selected provider: its
+/// last language is selected, with only that language's region unfolded.
+fn restore_language_browsing(app: &mut App) {
+    use crate::voices::labels::{world_region, WorldRegion};
+
+    app.selected_language = config::load_last_language(app.selected_backend);
+    app.recent_voices = config::load_recent_voices(app.selected_backend);
+    if let Some(ref code) = app.selected_language {
+        let region = world_region(code);
+        app.collapsed_language_regions = WorldRegion::ALL.into_iter().filter(|r| *r != region).collect();
+    }
+}
+
+/// Messages that change the settings of `section` from `app`'s values to `target`'s.
+///
+/// Going through the usual messages keeps their side effects (hotkey
+/// registration, feed server, color scheme...) in one place.
+fn settings_changes(
+    section: SettingsSection,
+    app: &App,
+    target: &App,
+    disabled_plugins: &[String],
+) -> Vec<Message> {
+    let mut changes = Vec::new();
+    match section {
+        SettingsSection::Appearance => {
+            for role in [ColorRole::Accent, ColorRole::Progress, ColorRole::Waveform] {
+                if app.custom_colors.get(role) != target.custom_colors.get(role) {
+                    changes.push(Message::CustomColorChanged(role, target.custom_colors.get(role).to_string()));
+                }
+            }
+            if app.color_preset != target.color_preset {
+                changes.push(Message::ColorPresetSelected(target.color_preset));
+            }
+            if app.visualizer_style != target.visualizer_style {
+                changes.push(Message::VisualizerStyleSelected(target.visualizer_style));
+            }
+        }
+        SettingsSection::Reading => {
+            let (current, wanted) = (&app.reading_style, &target.reading_style);
+            if current.font != wanted.font {
+                changes.push(Message::ReadingFontSelected(wanted.font));
+            }
+            if current.line_spacing != wanted.line_spacing {
+                changes.push(Message::ReadingLineSpacingChanged(wanted.line_spacing));
+            }
+            if current.tint != wanted.tint {
+                changes.push(Message::ReadingTintSelected(wanted.tint));
+            }
+            if current.focus_follow != wanted.focus_follow {
+                changes.push(Message::FocusFollowToggled(wanted.focus_follow));
+            }
+            if current.focus_dim != wanted.focus_dim {
+                changes.push(Message::FocusDimChanged(wanted.focus_dim));
+            }
+        }
+        SettingsSection::LongText => {
+            if app.long_text_action != target.long_text_action {
+                changes.push(Message::LongTextActionSelected(target.long_text_action));
+            }
+            if app.long_text_paragraphs != target.long_text_paragraphs {
+                changes.push(Message::LongTextParagraphsChanged(target.long_text_paragraphs));
+            }
+            if app.interrupt_readings != target.interrupt_readings {
+                changes.push(Message::InterruptReadingsToggled(target.interrupt_readings));
+            }
+        }
+        SettingsSection::TextSource => {
+            if app.source_priority != target.source_priority {
+                changes.push(Message::SourcePrioritySelected(target.source_priority));
+            }
+        }
+        SettingsSection::SameTextAgain => {
+            if app.duplicate_capture_action != target.duplicate_capture_action {
+                changes.push(Message::DuplicateCaptureActionSelected(target.duplicate_capture_action));
+            }
+        }
+        SettingsSection::Privacy => {
+            if app.headphones_only != target.headphones_only {
+                changes.push(Message::HeadphonesOnlyToggled(target.headphones_only));
+            }
+            if app.defer_while_mic_active != target.defer_while_mic_active {
+                changes.push(Message::DeferWhileMicActiveToggled(target.defer_while_mic_active));
+            }
+            if app.pause_while_typing != target.pause_while_typing {
+                changes.push(Message::PauseWhileTypingToggled(target.pause_while_typing));
+            }
+            if app.typing_resume_secs != target.typing_resume_secs {
+                changes.push(Message::TypingResumeDelayChanged(target.typing_resume_secs));
+            }
+            if app.sensitive_text_guard != target.sensitive_text_guard {
+                changes.push(Message::SensitiveTextGuardToggled(target.sensitive_text_guard));
+            }
+            if app.local_only_apps != target.local_only_apps {
+                changes.push(Message::LocalOnlyAppsChanged(target.local_only_apps.clone()));
+            }
+            if app.private_screenshots != target.private_screenshots {
+                changes.push(Message::PrivateScreenshotsToggled(target.private_screenshots));
+            }
+            if app.export_recipients != target.export_recipients {
+                changes.push(Message::ExportRecipientsChanged(target.export_recipients.clone()));
+            }
+            if app.clipboard_history_enabled != target.clipboard_history_enabled {
+                changes.push(Message::ClipboardHistoryToggled(target.clipboard_history_enabled));
+            }
+        }
+        SettingsSection::Provider => {
+            if app.elevenlabs_api_key != target.elevenlabs_api_key {
+                changes.push(Message::ElevenLabsApiKeyChanged(target.elevenlabs_api_key.clone()));
+            }
+            if app.selected_backend != target.selected_backend {
+                changes.push(Message::ProviderSelected(target.selected_backend));
+            }
+            if app.native_language_names != target.native_language_names {
+                changes.push(Message::NativeLanguageNamesToggled(target.native_language_names));
+            }
+            if app.pitch_semitones != target.pitch_semitones {
+                changes.push(Message::PitchChanged(target.pitch_semitones));
+            }
+        }
+        SettingsSection::Acceleration => {
+            if app.execution_provider != target.execution_provider {
+                changes.push(Message::ExecutionProviderSelected(target.execution_provider));
+            }
+        }
+        SettingsSection::Power => {
+            if app.power_policy != target.power_policy {
+                changes.push(Message::PowerPolicySelected(target.power_policy));
+            }
+            if app.synthesis_threads != target.synthesis_threads {
+                changes.push(Message::SynthesisThreadsChanged(target.synthesis_threads));
+            }
+            if app.synthesis_priority != target.synthesis_priority {
+                changes.push(Message::SynthesisPrioritySelected(target.synthesis_priority));
+            }
+        }
+        SettingsSection::SpokenText => {
+            if app.read_alt_text != target.read_alt_text {
+                changes.push(Message::ReadAltTextToggled(target.read_alt_text));
+            }
+            if app.read_math != target.read_math {
+                changes.push(Message::ReadMathToggled(target.read_math));
+            }
+            if app.list_prosody != target.list_prosody {
+                changes.push(Message::ListProsodyToggled(target.list_prosody));
+            }
+            if app.text_profile != target.text_profile {
+                changes.push(Message::TextProfileSelected(target.text_profile));
+            }
+        }
+        SettingsSection::NaturalReading => {
+            if app.text_cleanup_enabled != target.text_cleanup_enabled {
+                changes.push(Message::TextCleanupToggled(target.text_cleanup_enabled));
+            }
+        }
+        SettingsSection::Hotkeys => {
+            if app.hotkey_config != target.hotkey_config {
+                changes.push(Message::HotkeyConfigChanged(target.hotkey_config.clone()));
+            }
+            if app.hotkey_enabled != target.hotkey_enabled {
+                changes.push(Message::HotkeyToggled(target.hotkey_enabled));
+            }
+            if app.preview_before_reading != target.preview_before_reading {
+                changes.push(Message::PreviewBeforeReadingToggled(target.preview_before_reading));
+            }
+            if app.edit_before_reading != target.edit_before_reading {
+                changes.push(Message::EditBeforeReadingToggled(target.edit_before_reading));
+            }
+            if app.panic_stop_hotkey != target.panic_stop_hotkey {
+                changes.push(Message::PanicStopHotkeyToggled(target.panic_stop_hotkey));
+            }
+            if app.hotkey_edit_variant != target.hotkey_edit_variant {
+                changes.push(Message::HotkeyEditVariantToggled(target.hotkey_edit_variant));
+            }
+            if app.hotkey_image_variant != target.hotkey_image_variant {
+                changes.push(Message::HotkeyImageVariantToggled(target.hotkey_image_variant));
+            }
+            if app.scroll_sensitivity != target.scroll_sensitivity {
+                changes.push(Message::ScrollSensitivityChanged(target.scroll_sensitivity));
+            }
+        }
+        SettingsSection::Ocr => {
+            if app.selected_ocr_backend != target.selected_ocr_backend {
+                changes.push(Message::OCRBackendSelected(target.selected_ocr_backend));
+            }
+        }
+        SettingsSection::AudioCues => {
+            let (current, wanted) = (&app.earcons, &target.earcons);
+            if current.enabled != wanted.enabled {
+                changes.push(Message::EarconsToggled(wanted.enabled));
+            }
+            if current.volume != wanted.volume {
+                changes.push(Message::EarconVolumeChanged(wanted.volume));
+            }
+            if current.set != wanted.set {
+                changes.push(Message::EarconSetSelected(wanted.set));
+            }
+            if app.fade_ms != target.fade_ms {
+                changes.push(Message::FadeDurationChanged(target.fade_ms));
+            }
+        }
+        SettingsSection::PodcastFeed => {
+            let (current, wanted) = (&app.feed_config, &target.feed_config);
+            if current.title != wanted.title {
+                changes.push(Message::PodcastFeedTitleChanged(wanted.title.clone()));
+            }
+            if current.author != wanted.author {
+                changes.push(Message::PodcastFeedAuthorChanged(wanted.author.clone()));
+            }
+            if current.enabled != wanted.enabled {
+                changes.push(Message::PodcastFeedToggled(wanted.enabled));
+            }
+        }
+        SettingsSection::Storage => {
+            if app.cleanup_schedule != target.cleanup_schedule {
+                changes.push(Message::CleanupScheduleSelected(target.cleanup_schedule));
+            }
+            if app.history_max_days != target.history_max_days {
+                changes.push(Message::HistoryMaxDaysSelected(target.history_max_days));
+            }
+            if app.synthesis_cache_mb != target.synthesis_cache_mb {
+                changes.push(Message::SynthesisCacheSizeSelected(target.synthesis_cache_mb));
+            }
+        }
+        SettingsSection::HistorySync => {
+            if app.history_enabled != target.history_enabled {
+                changes.push(Message::HistoryToggled(target.history_enabled));
+            }
+            if app.weekly_summary_enabled != target.weekly_summary_enabled {
+                changes.push(Message::WeeklySummaryToggled(target.weekly_summary_enabled));
+            }
+            if app.weekly_summary_day != target.weekly_summary_day {
+                changes.push(Message::WeeklySummaryDaySelected(target.weekly_summary_day));
+            }
+            if app.weekly_summary_hour != target.weekly_summary_hour {
+                changes.push(Message::WeeklySummaryHourChanged(target.weekly_summary_hour));
+            }
+            if app.sync_dir_input.trim() != target.sync_dir_input.trim() {
+                changes.push(Message::SyncDirInputChanged(target.sync_dir_input.clone()));
+                changes.push(Message::SyncNow);
+            }
+        }
+        SettingsSection::WatchFolder => {
+            if app.watch_dir_input.trim() != target.watch_dir_input.trim() {
+                changes.push(Message::WatchDirInputChanged(target.watch_dir_input.clone()));
+                changes.push(Message::WatchDirSaved);
+            }
+        }
+        SettingsSection::Plugins => {
+            for plugin in &app.plugin_host.plugins {
+                let enabled = !disabled_plugins.contains(&plugin.name);
+                if plugin.enabled != enabled {
+                    changes.push(Message::PluginToggled(plugin.name.clone(), enabled));
+                }
+            }
+        }
+        SettingsSection::LogLevel => {
+            if app.log_level != target.log_level {
+                changes.push(Message::LogLevelSelected(target.log_level));
+            }
+        }
+    }
+    changes
+}
+
+/// Drop settings changes that were not applied and put the app back to the saved settings.
+fn revert_settings(app: &mut App) -> Task<Message> {
+    if !config::has_staged_changes() {
+        config::discard_staged_changes();
+        return Task::none();
+    }
+    info!("Reverting settings changes that were not applied");
+    config::discard_staged_changes();
+    let saved = App::saved_settings();
+    // Nothing to redo for these, the saved values are still in use on disk
+    app.selected_voice = saved.selected_voice.clone();
+    app.selected_polly_voice = saved.selected_polly_voice.clone();
+    app.selected_elevenlabs_voice = saved.selected_elevenlabs_voice.clone();
+    app.selected_macos_voice = saved.selected_macos_voice.clone();
+    app.selected_windows_voice = saved.selected_windows_voice.clone();
+    app.sync_dir_input = saved.sync_dir_input.clone();
+    app.watch_dir_input = saved.watch_dir_input.clone();
+    let disabled_plugins = config::load_disabled_plugins();
+    let changes = SettingsSection::ALL
+        .into_iter()
+        .flat_map(|section| settings_changes(section, app, &saved, &disabled_plugins))
+        .collect();
+    dispatch_all(changes)
+}
+
+/// How long the preview popup stays up before the captured text is read.
+const PREVIEW_SECONDS: u64 = 3;
+
+/// Show the preview popup for a hotkey capture; the text is read when it times out.
+fn open_preview(app: &mut App, text: String) -> Task<Message> {
+    debug!(bytes = text.len(), "Previewing hotkey capture");
+    app.preview_text = Some(text);
+    let close_task = close_window_if_some(app.preview_window_id.take());
+    let (window_id, open_task) = window::open(window::Settings {
+        size: Size::new(420.0, 110.0),
+        resizable: false,
+        decorations: false,
+        transparent: true,
+        visible: true,
+        level: window::Level::AlwaysOnTop,
+        position: window::Position::SpecificWith(|window_size, monitor_size| {
+            let margin = 70.0;
+            iced::Point::new(margin, monitor_size.height - window_size.height - margin)
+        }),
+        ..Default::default()
+    });
+    app.preview_window_id = Some(window_id);
+    let timeout = Task::perform(
+        async { tokio::time::sleep(std::time::Duration::from_secs(PREVIEW_SECONDS)).await },
+        move |_| Message::PreviewTimedOut(window_id),
+    );
+    Task::batch([close_task, open_task.map(Message::WindowOpened), timeout])
+}
+
+/// Route captured text to TTS, asking first if the text is very long.
+///
+/// Depending on the configured long text action, long captures either open the
+/// long text dialog, get trimmed to the first N paragraphs, or are read in full.
+fn process_captured_text(
+    app: &mut App,
+    text: String,
+    context: &'static str,
+) -> Task<Message> {
+    let hash = text::content_hash(&text);
+    if app.last_capture_hash.as_deref() == Some(hash.as_str()) {
+        if let Some(task) = handle_duplicate_capture(app, context) {
+            return task;
+        }
+    }
+    app.last_capture_hash = Some(hash);
+    app.capture_app = take_capture_app();
+    if app.clipboard_history_enabled {
+        remember_capture(app, &text);
+    }
+
+    if let Some(task) = ask_if_sensitive(app, &text, context) {
+        return task;
+    }
+    process_confirmed_text(app, text, context)
+}
+
+/// Ask before reading `text` if it looks like a secret and the guard is on.
+/// Returns the task opening the confirmation dialog, or `None` to read on.
+fn ask_if_sensitive(app: &mut App, text: &str, context: &'static str) -> Option<Task<Message>> {
+    if !app.sensitive_text_guard {
+        return None;
+    }
+    let kind = text::sensitive::detect_sensitive(text)?;
+    info!(context, kind, "Capture looks like a secret, asking before reading");
+    app.sensitive_pending = Some((text.to_string(), kind, context));
+    if app.sensitive_dialog_window_id.is_some() {
+        return Some(Task::none());
+    }
+    let (window_id, task) = open_info_window(Size::new(460.0, 220.0));
+    app.sensitive_dialog_window_id = Some(window_id);
+    Some(task)
+}
+
+/// Read the text of the extracted text editor as it is, without Natural
+/// Reading or the other text passes.
+fn read_extracted_text(app: &mut App, text: String) -> Task<Message> {
+    info!(bytes = text.len(), "Sending extracted text to TTS (bypassing text cleanup)");
+    // OCR text: skip all preprocessing (cleanup API, markdown parsing, etc.)
+    // Send directly to TTS to preserve original formatting and line breaks
+    record_reading(app, &text, "ReadExtractedText");
+    transition(app, Event::Load("Synthesizing voice...".to_string()));
+    initialize_tts_async(reading_backend(app), text, "ReadExtractedText", app.selected_polly_voice.clone())
+}
+
+/// Add the text about to be read to the reading history, if it is kept,
+/// remembering its entry so the reading can be marked as a favorite.
+fn record_reading(app: &mut App, text: &str, context: &str) {
+    app.history_entry = None;
+    app.reading_favorite = false;
+    if !app.history_enabled {
+        return;
+    }
+    crate::history::record_reading(text, context);
+    let id = text::content_hash(text);
+    app.reading_favorite = crate::history::is_favorite(&id);
+    app.history_entry = Some(id);
+}
+
+/// Captures listed in the clipboard history window.
+const CLIPBOARD_HISTORY_LEN: usize = 10;
+
+/// Put a capture at the top of the clipboard history, unless it looks like a
+/// secret or comes from an app kept offline.
+fn remember_capture(app: &mut App, text: &str) {
+    if text.trim().is_empty() || text::sensitive::detect_sensitive(text).is_some() || capture_is_local_only(app) {
+        return;
+    }
+    app.clipboard_history.retain(|item| item != text);
+    app.clipboard_history.push_front(text.to_string());
+    app.clipboard_history.truncate(CLIPBOARD_HISTORY_LEN);
+}
+
+/// Continue with a capture that passed the sensitive text check: ask about
+/// long texts, then read.
+fn process_confirmed_text(app: &mut App, text: String, context: &'static str) -> Task<Message> {
+    if !text::is_long_text(&text, app.long_text_paragraphs) {
+        return process_text_for_tts(app, text, context);
+    }
+
+    info!(context, action = ?app.long_text_action, "Long text captured");
+    match app.long_text_action {
+        LongTextAction::ReadAll => process_text_for_tts(app, text, context),
+        LongTextAction::ReadFirst => {
+            let trimmed = text::first_paragraphs(&text, app.long_text_paragraphs);
+            process_text_for_tts(app, trimmed, context)
+        }
+        LongTextAction::Ask => {
+            app.long_text_pending = Some(text);
+            if app.long_text_dialog_window_id.is_some() {
+                return Task::none();
+            }
+            let (window_id, task) = open_info_window(Size::new(460.0, 260.0));
+            app.long_text_dialog_window_id = Some(window_id);
+            task
+        }
+    }
+}
+
+/// Read a file or web page, first offering to continue where it was left
+/// last time. `key` comes from [`crate::history::file_document_key`] or
+/// [`crate::history::url_document_key`].
+fn read_document(app: &mut App, key: String, text: String, context: &'static str) -> Task<Message> {
+    leave_document(app);
+    let saved = if app.history_enabled { crate::history::resume_position(&key) } else { None };
+    app.current_document = Some(crate::history::DocumentReading::new(key, &text));
+    let Some(progress) = saved else {
+        return process_captured_text(app, text, context);
+    };
+
+    info!(context, progress, "Document read before, asking whether to continue");
+    app.resume_pending = Some((text, progress, context));
+    if app.resume_dialog_window_id.is_some() {
+        return Task::none();
+    }
+    let (window_id, task) = open_info_window(Size::new(420.0, 200.0));
+    app.resume_dialog_window_id = Some(window_id);
+    task
+}
+
+/// Save how far the current document got, and stop tracking it.
+fn leave_document(app: &mut App) {
+    let Some(document) = app.current_document.take() else {
+        return;
+    };
+    if app.history_enabled && document.progress > 0.0 {
+        crate::history::record_position(&document.key, document.progress);
+    }
+}
+
+/// Longest capture read as an interruption of a reading (see [`interrupt_reading`]).
+const INTERRUPT_MAX_CHARS: usize = 280;
+
+/// Put the reading playing aside when `text` is a short capture and the
+/// reading a long one, so it resumes where it was once `text` was read.
+///
+/// Readings stack two deep: a short capture during an interruption replaces
+/// the interruption, a long one also replaces the reading put aside.
+fn interrupt_reading(app: &mut App, text: &str) {
+    // Opening a file or page is never an interruption, however short
+    let document = app
+        .current_document
+        .as_ref()
+        .is_some_and(|document| document.text_hash == text::content_hash(text));
+    let short = !document && text.chars().count() <= INTERRUPT_MAX_CHARS;
+    if app.interrupted_reading.is_some() {
+        if !short {
+            info!("Long capture during an interruption, dropping the interrupted reading");
+            drop_interrupted_reading(app);
+        }
+        return;
+    }
+    let long_reading = app
+        .reading_text
+        .as_ref()
+        .is_some_and(|reading| reading.chars().count() > INTERRUPT_MAX_CHARS);
+    if !app.interrupt_readings || !short || !long_reading || app.activity != Activity::Playing {
+        return;
+    }
+    let Some(mut provider) = app.provider.take() else {
+        return;
+    };
+    if let Err(e) = provider.pause() {
+        warn!(error = %e, "Failed to pause the reading for an interruption, replacing it");
+        app.provider = Some(provider);
+        return;
+    }
+    info!(progress = provider.get_progress(), "Short capture interrupts the reading, resuming it afterwards");
+    record_listening(app);
+    app.interrupted_reading = Some(InterruptedReading {
+        provider,
+        text: app.reading_text.take(),
+        transcript: std::mem::take(&mut app.transcript),
+        spoken_sentences: std::mem::take(&mut app.spoken_sentences),
+        document: app.current_document.take(),
+        synthesis_cache_key: app.synthesis_cache_key.take(),
+    });
+}
+
+/// Carry on with the reading put aside for an interruption, if any.
+/// Returns whether it resumed.
+fn resume_interrupted_reading(app: &mut App) -> bool {
+    let Some(mut reading) = app.interrupted_reading.take() else {
+        return false;
+    };
+    if let Err(e) = reading.provider.resume() {
+        error!(error = %e, "Failed to resume the interrupted reading");
+        app.interrupted_reading = Some(reading);
+        drop_interrupted_reading(app);
+        return false;
+    }
+    app.progress = reading.provider.get_progress();
+    app.provider = Some(reading.provider);
+    app.reading_text = reading.text;
+    app.transcript = reading.transcript;
+    app.spoken_sentences = reading.spoken_sentences;
+    app.transcript_sentence = None;
+    app.current_document = reading.document;
+    app.synthesis_cache_key = reading.synthesis_cache_key;
+    // Capturing the interruption again reads it again rather than replaying this
+    app.last_capture_hash = None;
+    transition(app, Event::Play);
+    info!(progress = app.progress, "Interruption finished, resuming the reading");
+    true
+}
+
+/// Record that the reading being prepared failed, then carry on with the
+/// reading it interrupted, if any, keeping `error` in the status line.
+/// Returns whether an interrupted reading resumed.
+fn fail_reading(app: &mut App, error: String) -> bool {
+    transition(app, Event::Fail(error.clone()));
+    if !resume_interrupted_reading(app) {
+        return false;
+    }
+    app.status_text = Some(error);
+    true
+}
+
+/// Give up the reading put aside for an interruption, remembering how far
+/// its document got.
+fn drop_interrupted_reading(app: &mut App) {
+    let Some(document) = app.interrupted_reading.take().and_then(|reading| reading.document) else {
+        return;
+    };
+    if app.history_enabled && document.progress > 0.0 {
+        crate::history::record_position(&document.key, document.progress);
+    }
+}
+
+/// Add the time the current reading was played to the listening statistics.
+fn record_listening(app: &mut App) {
+    let seconds = std::mem::take(&mut app.listened_secs);
+    if seconds > 0.0 {
+        crate::stats::record_listening(seconds);
+    }
+}
+
+/// Handle a capture identical to the previous one without re-synthesizing it.
+///
+/// Returns `None` if there is no synthesized audio to fall back on, in which
+/// case the text should be processed normally.
+fn handle_duplicate_capture(app: &mut App, context: &'static str) -> Option<Task<Message>> {
+    let provider = app.provider.as_mut()?;
+    info!(context, action = ?app.duplicate_capture_action, "Duplicate capture detected, skipping synthesis");
+
+    match app.duplicate_capture_action {
+        DuplicateCaptureAction::Replay => {
+            if let Err(e) = provider.replay() {
+                warn!(error = %e, "Failed to replay audio, synthesizing again");
+                return None;
+            }
+            transition(app, Event::Play);
+            Some(Task::none())
+        }
+        DuplicateCaptureAction::Ignore => {
+            // Nothing is playing, so the main window was only opened for this capture
+            if app.activity.is_idle() {
+                if let Some(window_id) = app.main_window_id {
+                    return Some(window::close(window_id));
+                }
+            }
+            Some(Task::none())
+        }
+    }
+}
+
+/// Key identifying the provider a reading needs: backend plus voice.
+fn provider_key(backend: TTSBackend, polly_voice_id: Option<&str>) -> String {
+    match backend {
+        TTSBackend::Piper => format!(
+            "piper:{}:{:?}:{:?}",
+            config::load_selected_voice().unwrap_or_default(),
+            config::load_execution_provider(),
+            system::current_synthesis_limits()
+        ),
+        TTSBackend::AwsPolly => format!("polly:{}", polly_voice_id.unwrap_or_default()),
+        TTSBackend::ElevenLabs => format!("elevenlabs:{}", config::load_selected_elevenlabs_voice().unwrap_or_default()),
+        TTSBackend::MacOS => format!("macos:{}", config::load_selected_macos_voice().unwrap_or_default()),
+        TTSBackend::Windows => format!("windows:{}", config::load_selected_windows_voice().unwrap_or_default()),
+        TTSBackend::Espeak => "espeak".to_string(),
+    }
+}
+
+/// Voice a phrase is cached for: the audio does not depend on where or how
+/// fast the provider synthesizes, unlike [`provider_key`], but Piper and
+/// Polly speak at the pitch set.
+fn phrase_voice(backend: TTSBackend, polly_voice_id: Option<&str>) -> String {
+    let pitch = match config::load_pitch_semitones() {
+        0 => String::new(),
+        semitones => format!("@{semitones:+}"),
+    };
+    match backend {
+        TTSBackend::Piper => format!("piper:{}{pitch}", config::load_selected_voice().unwrap_or_default()),
+        TTSBackend::AwsPolly => format!("polly:{}{pitch}", polly_voice_id.unwrap_or_default()),
+        TTSBackend::ElevenLabs => format!("elevenlabs:{}", config::load_selected_elevenlabs_voice().unwrap_or_default()),
+        TTSBackend::MacOS => format!("macos:{}", config::load_selected_macos_voice().unwrap_or_default()),
+        TTSBackend::Windows => format!("windows:{}", config::load_selected_windows_voice().unwrap_or_default()),
+        TTSBackend::Espeak => "espeak".to_string(),
+    }
+}
+
+/// Create a provider for the backend (Polly uses the given voice or its default,
+/// the others the voice selected in the config).
+///
+/// Until a Piper voice is downloaded, Piper falls back to the basic voice.
+fn create_provider(backend: TTSBackend, polly_voice_id: Option<String>) -> Result<Box<dyn TTSProvider>, String> {
+    match backend {
+        TTSBackend::Piper
+            if crate::voices::download::installed_voice_keys().is_empty() && EspeakTTSProvider::check_binary().is_ok() =>
+        {
+            info!("No Piper voice downloaded yet, speaking with the basic voice");
+            EspeakTTSProvider::new().map(|p| Box::new(p) as Box<dyn TTSProvider>)
+        }
+        TTSBackend::Piper => PiperTTSProvider::new().map(|p| Box::new(p) as Box<dyn TTSProvider>),
+        TTSBackend::AwsPolly => PollyTTSProvider::new(polly_voice_id).map(|p| Box::new(p) as Box<dyn TTSProvider>),
+        TTSBackend::ElevenLabs => ElevenLabsTTSProvider::new(config::load_selected_elevenlabs_voice())
+            .map(|p| Box::new(p) as Box<dyn TTSProvider>),
+        TTSBackend::MacOS => macos_provider(config::load_selected_macos_voice()),
+        TTSBackend::Windows => windows_provider(config::load_selected_windows_voice()),
+        TTSBackend::Espeak => EspeakTTSProvider::new().map(|p| Box::new(p) as Box<dyn TTSProvider>),
+    }
+    .map_err(|e| format!("{}", e))
+}
+
+/// Take the pre-warmed provider if it was built for `key`.
+fn take_warm_provider(key: &str) -> Option<Box<dyn TTSProvider>> {
+    let mut warm = WARM_PROVIDER.lock().ok()?;
+    match warm.take() {
+        Some((warm_key, provider)) if warm_key == key => {
+            debug!(key, "Using pre-warmed provider");
+            Some(provider.0)
+        }
+        other => {
+            *warm = other;
+            None
+        }
+    }
+}
+
+/// Build a provider for the backend and voice in the background and keep it
+/// for the next reading: the audio output is opened, the Polly client is
+/// constructed and the Piper model is loaded in a long-running process.
+///
+/// Replaces a provider warmed for another voice; does nothing if one for
+/// this voice is already waiting.
+pub(crate) fn prewarm_provider(backend: TTSBackend, polly_voice_id: Option<String>) {
+    std::thread::spawn(move || {
+        let polly_voice_id = polly_voice_id.or_else(config::load_selected_polly_voice);
+        let key = provider_key(backend, polly_voice_id.as_deref());
+        if WARM_PROVIDER
+            .lock()
+            .is_ok_and(|warm| warm.as_ref().is_some_and(|(warm_key, _)| *warm_key == key))
+        {
+            return;
+        }
+        if backend == TTSBackend::AwsPolly && PollyTTSProvider::check_credentials().is_err() {
+            debug!("AWS credentials not available, not pre-warming Polly");
+            return;
+        }
+        if backend == TTSBackend::ElevenLabs && ElevenLabsTTSProvider::check_api_key().is_err() {
+            debug!("No ElevenLabs API key, not pre-warming ElevenLabs");
+            return;
+        }
+
+        let started = Instant::now();
+        let mut provider = match create_provider(backend, polly_voice_id) {
+            Ok(provider) => provider,
+            Err(e) => {
+                debug!(error = %e, ?backend, "Failed to pre-warm provider");
+                return;
+            }
+        };
+        if let Err(e) = provider.prewarm() {
+            warn!(error = %e, ?backend, "Provider warm-up failed, it will start cold");
+        }
+        debug!(key, elapsed_ms = started.elapsed().as_millis() as u64, "Provider pre-warmed");
+        if let Ok(mut warm) = WARM_PROVIDER.lock() {
+            *warm = Some((key, SendTTSProvider(provider)));
+        }
+    });
+}
+
+/// Initialize TTS provider and start speaking with the given text asynchronously.
+/// Returns a Task that will complete when synthesis is done.
+/// This prevents blocking the UI thread during TTS synthesis.
+/// Phrases synthesized per idle warm-up, so one run never keeps the CPU busy for long.
+const PHRASES_PER_WARMUP: usize = 5;
+
+/// Synthesize frequently read phrases that are not cached for the current
+/// voice yet (see [`crate::phrase_cache`]), with the pre-warmed provider if it
+/// matches, and keep the provider for the next reading.
+///
+/// Blocks; returns how many phrases were synthesized.
+fn warm_phrase_cache(backend: TTSBackend, polly_voice_id: Option<String>) -> usize {
+    let polly_voice_id = polly_voice_id.or_else(config::load_selected_polly_voice);
+    let voice = phrase_voice(backend, polly_voice_id.as_deref());
+    let pending = crate::phrase_cache::pending(&voice);
+    if pending.is_empty() {
+        return 0;
+    }
+    if backend == TTSBackend::AwsPolly && PollyTTSProvider::check_credentials().is_err() {
+        debug!("AWS credentials not available, not warming the phrase cache");
+        return 0;
+    }
+    if backend == TTSBackend::ElevenLabs && ElevenLabsTTSProvider::check_api_key().is_err() {
+        debug!("No ElevenLabs API key, not warming the phrase cache");
+        return 0;
+    }
+
+    let key = provider_key(backend, polly_voice_id.as_deref());
+    let provider = match take_warm_provider(&key) {
+        Some(provider) => Ok(provider),
+        None => create_provider(backend, polly_voice_id),
+    };
+    let mut provider = match provider {
+        Ok(provider) => provider,
+        Err(e) => {
+            debug!(error = %e, ?backend, "Failed to create provider for the phrase cache");
+            return 0;
+        }
+    };
+    let mut warmed = 0;
+    for phrase in pending.iter().take(PHRASES_PER_WARMUP) {
+        match provider.synthesize(phrase) {
+            Ok(samples) => {
+                crate::phrase_cache::store(&voice, phrase, &samples, provider.sample_rate());
+                warmed += 1;
+            }
+            Err(e) => {
+                warn!(error = %e, "Failed to synthesize phrase for the cache");
+                break;
+            }
+        }
+    }
+    // Unless a reading warmed another provider meanwhile
+    if let Ok(mut warm) = WARM_PROVIDER.lock() {
+        if warm.is_none() {
+            *warm = Some((key, SendTTSProvider(provider)));
+        }
+    }
+    warmed
+}
+
+fn initialize_tts_async(
+    backend: TTSBackend,
+    text: String,
+    context: &'static str,
+    polly_voice_id: Option<String>,
+) -> Task<Message> {
+    info!(
+        context,
+        backend = ?backend,
+        bytes = text.len(),
+        "Starting async TTS initialization"
+    );
+    // Taken before anything can fail, so it never carries over to a later reading
+    let start_paused = START_PAUSED.swap(false, std::sync::atomic::Ordering::Relaxed);
+
+    // Check AWS credentials before attempting to initialize (synchronous, fast)
+    if backend == TTSBackend::AwsPolly {
+        if let Err(e) = PollyTTSProvider::check_credentials() {
+            warn!("AWS credentials not found during initialization");
+            return Task::perform(
+                async move { Err(e) },
+                Message::TTSInitialized,
+            );
+        }
+    }
+    if backend == TTSBackend::ElevenLabs {
+        if let Err(e) = ElevenLabsTTSProvider::check_api_key() {
+            warn!("ElevenLabs API key not set during initialization");
+            return Task::perform(async move { Err(e) }, Message::TTSInitialized);
+        }
+    }
+
+    let started = Instant::now();
+    let requested_at = READ_REQUESTED_AT.lock().ok().and_then(|mut at| at.take());
+
+    // Use the pre-warmed provider if it matches, otherwise create one (fast, on main thread)
+    let polly_voice_id = polly_voice_id.or_else(config::load_selected_polly_voice);
+    let key = provider_key(backend, polly_voice_id.as_deref());
+    let phrase_voice = phrase_voice(backend, polly_voice_id.as_deref());
+    let warm_provider = take_warm_provider(&key);
+    let warm = warm_provider.is_some();
+    let provider_result = match warm_provider {
+        Some(provider) => Ok(provider),
+        None => create_provider(backend, polly_voice_id.clone()),
+    };
+
+    match provider_result {
+        Ok(provider) => {
+            // Wrap provider to make it Send-safe for cross-thread usage
+            let send_provider = SendTTSProvider(provider);
+            
+            // Spawn thread to do blocking synthesis
+            let (tx, rx) = mpsc::channel();
+            
+            std::thread::spawn(move || {
+                let mut send_provider = send_provider;
+                let provider = &mut send_provider.0;
+                if start_paused {
+                    provider.hold_next_playback();
+                } else if config::load_headphones_only() {
+                    let output = default_output_kind();
+                    if output != OutputKind::Headphones {
+                        info!(?output, "Headphones not in use, starting paused");
+                        provider.hold_next_playback();
+                    }
+                }
+                let sample_rate = provider.sample_rate();
+                let cache_on = config::load_synthesis_cache_mb() > 0;
+                let phrase = crate::phrase_cache::cached(&phrase_voice, &text, sample_rate);
+                let reading = if phrase.is_none() && cache_on {
+                    crate::synthesis_cache::cached(&phrase_voice, &text, sample_rate)
+                } else {
+                    None
+                };
+                let synthesized = phrase.is_none() && reading.is_none();
+                let result = if let Some(samples) = phrase {
+                    info!(text = %text, "Playing phrase from the cache");
+                    provider.play_samples(samples)
+                } else if let Some(samples) = reading {
+                    info!(chars = text.len(), "Playing reading from the synthesis cache");
+                    provider.play_samples(samples)
+                } else {
+                    info!(text = %text, "Synthesizing text");
+                    provider.speak(&text)
+                };
+                if config::load_history_enabled() {
+                    crate::phrase_cache::record(&text);
+                }
+                
+                match result {
+                    Ok(()) => {
+                        info!(context, "TTS playback started");
+                        info!(
+                            context,
+                            ?backend,
+                            warm,
+                            chars = text.len(),
+                            synthesis_ms = started.elapsed().as_millis() as u64,
+                            latency_ms = requested_at.unwrap_or(started).elapsed().as_millis() as u64,
+                            "Reading latency"
+                        );
+                        if let Ok(mut key) = PENDING_CACHE_KEY.lock() {
+                            *key = (synthesized && cache_on).then_some((phrase_voice, text));
+                        }
+                        if let Ok(mut guard) = PENDING_PROVIDER.lock() {
+                            *guard = Some(send_provider);
+                        }
+                        let _ = tx.send(Ok(()));
+                    }
+                    Err(e) => {
+                        error!(error = %e, "TTS speak failed");
+                        let error_msg = format_tts_error(&format!("{}", e), backend);
+                        let _ = tx.send(Err(error_msg));
+                    }
+                }
+
+                // Get the next reading's provider ready while this one plays
+                prewarm_provider(backend, polly_voice_id);
+            });
+
+            // Return a task that waits for synthesis (non-blocking for UI)
+            Task::perform(
+                async move {
+                    tokio::task::spawn_blocking(move || {
+                        rx.recv().unwrap_or_else(|e| Err(format!("Channel error: {}", e)))
+                    })
+                    .await
+                    .unwrap_or_else(|e| Err(format!("Task join error: {}", e)))
+                },
+                Message::TTSInitialized,
+            )
+        }
+        Err(e) => {
+            let error_msg = format_tts_error(&e, backend);
+            Task::perform(
+                async move { Err(error_msg) },
+                Message::TTSInitialized,
+            )
+        }
+    }
+}
+
+pub fn update(app: &mut App, message: Message) -> Task<Message> {
+    match message {
+        Message::SkipBackward => {
+            handle_skip(app, |p| p.skip_backward(SKIP_SECONDS), "backward")
+        }
+        Message::SkipForward => {
+            handle_skip(app, |p| p.skip_forward(SKIP_SECONDS), "forward")
+        }
+        Message::PlayPause => {
+            let Some(ref mut provider) = app.provider else {
+                warn!("PlayPause received with no active provider");
+                return Task::none();
+            };
+            
+            match app.activity {
+                Activity::Playing => {
                     if let Err(e) = provider.pause() {
                         error!(error = %e, "Failed to pause playback");
                     } else {
-                        app.playback_state = PlaybackState::Paused;
-                        info!("Playback paused");
+                        transition(app, Event::Pause);
+                        info!("Playback paused");
+                    }
+                }
+                Activity::Paused => {
+                    // A reading paused for typing and resumed by hand is the user's again
+                    app.paused_for_typing = false;
+                    if let Err(e) = provider.resume() {
+                        error!(error = %e, "Failed to resume playback");
+                    } else {
+                        // Also drops the headphone-only hint once the user chose to play
+                        transition(app, Event::Play);
+                        info!("Playback resumed");
+                    }
+                }
+                _ => {}
+            }
+            Task::none()
+        }
+        Message::Stop => {
+            if app.dictation_recorder.take().is_some() {
+                info!("Dictation cancelled");
+            }
+            if !app.held_readings.is_empty() {
+                info!(held = app.held_readings.len(), "Held hotkey readings cancelled");
+                app.held_readings.clear();
+            }
+            drop_interrupted_reading(app);
+            if let Some(ref mut provider) = app.provider {
+                if let Err(e) = provider.stop() {
+                    error!(error = %e, "Failed to stop playback");
+                }
+            }
+            leave_document(app);
+            record_listening(app);
+            transition(app, Event::Stop);
+            app.progress = 0.0;
+            app.playback_time = Default::default();
+            app.current_word = None;
+            app.transcript_sentence = None;
+            app.frequency_bands = vec![0.0; NUM_BANDS];
+            info!("Playback stopped, closing main window");
+            close_main_window(app)
+        }
+        Message::Tick => {
+            if app.activity.has_speech() {
+                if let Some(ref provider) = app.provider {
+                    app.playback_time = (provider.position(), provider.duration());
+                }
+            }
+            // Handle loading animation (for TTS or voice downloads)
+            if app.activity.is_busy() || app.downloading_voice.is_some() {
+                app.loading_animation_time += 0.15; // Increment animation time (faster animation)
+                if app.loading_animation_time > std::f32::consts::PI * 2.0 {
+                    app.loading_animation_time -= std::f32::consts::PI * 2.0;
+                }
+                
+                // Generate animated bar values using sine waves (not for downloads in the background of a reading)
+                if app.activity.is_busy() {
+                    // Creates a smooth wave that travels across the bars
+                    app.frequency_bands = (0..NUM_BANDS)
+                        .map(|i| {
+                            // Create a traveling wave effect
+                            let position = i as f32 / NUM_BANDS as f32;
+                            let wave = (app.loading_animation_time * 2.0 + position * std::f32::consts::PI * 2.0).sin();
+                            // Add some variation with a secondary wave
+                            let secondary = (app.loading_animation_time * 1.5 + position * std::f32::consts::PI * 3.0).sin() * 0.3;
+                            // Normalize to 0.0-1.0 range with some minimum height
+                            ((wave + secondary) * 0.4 + 0.5).clamp(0.2, 1.0)
+                        })
+                        .collect();
+                }
+            } else {
+                trace_throttled!("Tick received while idle");
+            }
+            Task::none()
+        }
+        Message::PlaybackUpdated(update) => {
+            // Ignore stragglers from a player that was stopped or replaced
+            if app.provider.is_none() || !app.activity.has_speech() {
+                trace_throttled!("Playback update received while stopped, ignoring");
+                return Task::none();
+            }
+            app.progress = update.progress;
+            app.current_word = app.provider.as_ref().and_then(|provider| provider.get_current_word());
+            let spoken = app.provider.as_ref().and_then(|provider| {
+                let duration = provider.duration();
+                text::timing::spoken_sentence(&app.transcript, &provider.sentence_starts(), duration.mul_f32(update.progress.clamp(0.0, 1.0)), duration)
+            });
+            let transcript_task = if spoken != app.transcript_sentence {
+                app.transcript_sentence = spoken;
+                follow_transcript(app)
+            } else {
+                Task::none()
+            };
+            if let Some(document) = app.current_document.as_mut() {
+                document.progress = document.progress.max(update.progress);
+            }
+
+            if update.finished {
+                leave_document(app);
+                record_listening(app);
+                cache_synthesized_reading(app);
+                app.current_word = None;
+                app.transcript_sentence = None;
+                transition(app, Event::Stop);
+                play_earcon(Earcon::End);
+                if resume_interrupted_reading(app) {
+                    return Task::none();
+                }
+                if let Some(text) = app.queued_readings.pop_front() {
+                    info!(left = app.queued_readings.len(), "Playback finished, reading the next queued text");
+                    return read_in_main_window(app, text);
+                }
+                if app.trainer_window_id.is_some() || app.reading_view_window_id.is_some() || app.transcript_window_id.is_some() {
+                    debug!("Playback finished, keeping windows open for pronunciation trainer, reading view or transcript");
+                    return Task::none();
+                }
+                info!("Playback finished, stopping and closing window");
+                return close_main_window(app);
+            }
+            app.listened_secs += crate::providers::CHUNK_DURATION_MS as f32 / 1000.0;
+            Task::batch([follow_current_sentence(app), transcript_task])
+        }
+        Message::Settings => {
+            if app.settings_window_id.is_some() {
+                debug!("Settings window already open, ignoring request");
+                return Task::none();
+            }
+            
+            debug!("Settings clicked");
+            let (window_id, task) = open_settings_window();
+            debug!(?window_id, "Opening settings window");
+            app.settings_window_id = Some(window_id);
+            app.show_settings_modal = true;
+            config::begin_staging();
+            restore_language_browsing(app);
+            Task::batch([
+                task,
+                fetch_voice_lists(app),
+                measure_storage_task(),
+                detect_execution_providers_task(app),
+                Task::perform(
+                    async { tokio::task::spawn_blocking(system::power_source).await.unwrap_or(system::PowerSource::Unknown) },
+                    Message::PowerSourceChecked,
+                ),
+            ])
+        }
+        Message::CloseSettings => {
+            app.show_settings_modal = false;
+            app.settings_search.clear();
+            app.language_search.clear();
+            let revert_task = revert_settings(app);
+            Task::batch([close_window_if_some(app.settings_window_id.take()), revert_task])
+        }
+        Message::ApplySettings => {
+            info!("Applying settings changes");
+            config::apply_staged_changes();
+            config::begin_staging();
+            Task::none()
+        }
+        Message::ResetSettingsToDefaults => {
+            info!("Resetting all settings to defaults");
+            let defaults = App::default_settings();
+            let changes = SettingsSection::ALL
+                .into_iter()
+                .flat_map(|section| settings_changes(section, app, &defaults, &[]))
+                .collect();
+            dispatch_all(changes)
+        }
+        Message::ResetSectionToDefaults(section) => {
+            info!(?section, "Resetting settings section to defaults");
+            dispatch_all(settings_changes(section, app, &App::default_settings(), &[]))
+        }
+        Message::SettingsTabSelected(tab) => {
+            debug!(?tab, "Settings tab selected");
+            app.settings_tab = tab;
+            app.settings_search.clear();
+            if tab == SettingsTab::Advanced {
+                return measure_storage_task();
+            }
+            Task::none()
+        }
+        Message::SettingsSearchChanged(query) => {
+            app.settings_search = query;
+            Task::none()
+        }
+        Message::SettingsSearchSubmitted => {
+            if let Some(section) = crate::ui::settings::tabs::visible_sections(app).first() {
+                debug!(?section, "Jumping to settings section from search");
+                app.settings_tab = section.tab();
+                app.settings_search.clear();
+            }
+            Task::none()
+        }
+        Message::ProviderSelected(backend) => {
+            info!(?backend, "TTS provider selected");
+            app.selected_backend = backend;
+            restore_language_browsing(app);
+            let mut voices_task = Task::none();
+            
+            // Check AWS credentials if AWS Polly is selected
+            if backend == TTSBackend::AwsPolly {
+                match PollyTTSProvider::check_credentials() {
+                    Ok(()) => {
+                        app.error_message = None;
+                        app.polly_error_message = None; // Clear Polly error when credentials are valid
+                        info!("AWS credentials found");
+                        // Fetch AWS voices if not already loaded
+                        if app.polly_voices.is_none() && !app.polly_voices_loading {
+                            app.polly_voices_loading = true;
+                            return Task::perform(
+                                async {
+                                    crate::voices::aws::fetch_polly_voices().await
+                                },
+                                Message::PollyVoicesLoaded,
+                            );
+                        }
+                    }
+                    Err(e) => {
+                        app.error_message = Some(e);
+                        warn!("AWS credentials not found when selecting AWS Polly");
+                        // Clear voices if credentials are not available
+                        app.polly_voices = None;
+                        app.polly_error_message = None; // Don't show service error if credentials are missing
+                    }
+                }
+            } else if backend == TTSBackend::ElevenLabs {
+                app.polly_error_message = None;
+                match ElevenLabsTTSProvider::check_api_key() {
+                    Ok(_) => {
+                        app.error_message = None;
+                        if app.elevenlabs_voices.is_none() {
+                            voices_task = load_elevenlabs_voices(app);
+                        }
+                    }
+                    Err(e) => app.error_message = Some(e),
+                }
+            } else if backend.is_system() {
+                app.error_message = None;
+                app.polly_error_message = None;
+                if app.system_voices.is_none() {
+                    voices_task = load_system_voices(backend);
+                }
+            } else if backend == TTSBackend::Espeak {
+                app.polly_error_message = None;
+                app.error_message = EspeakTTSProvider::check_binary().err();
+            } else {
+                // Clear error message when switching to Piper
+                app.error_message = None;
+                app.polly_error_message = None;
+            }
+            
+            // Persist the selected backend so future runs remember the choice.
+            config::save_voice_provider(backend);
+            prewarm_provider(backend, app.selected_polly_voice.clone());
+            voices_task
+        }
+        Message::LogLevelSelected(level) => {
+            info!(?level, "Log level selected");
+            app.log_level = level;
+            // Persist the selected log level so future runs remember the choice.
+            config::save_log_level(level);
+            // Update runtime log level
+            logging::set_verbosity(level);
+            Task::none()
+        }
+        Message::NativeLanguageNamesToggled(native) => {
+            debug!(?native, "Native language names toggled");
+            app.native_language_names = native;
+            config::save_native_language_names(native);
+            Task::none()
+        }
+        Message::LanguageSearchChanged(query) => {
+            app.language_search = query;
+            Task::none()
+        }
+        Message::LanguageRegionToggled(region) => {
+            if let Some(index) = app.collapsed_language_regions.iter().position(|r| *r == region) {
+                app.collapsed_language_regions.remove(index);
+            } else {
+                app.collapsed_language_regions.push(region);
+            }
+            Task::none()
+        }
+        Message::VolumeScrolled(lines) => {
+            let volume = (app.playback_volume + lines * VOLUME_STEP * app.scroll_sensitivity).clamp(0.0, 1.0);
+            trace!(volume, "Volume scrolled");
+            apply_playback_volume(app, volume);
+            config::save_playback_volume(volume);
+            Task::none()
+        }
+        Message::VolumePopoverToggled(id) => {
+            app.volume_popover_window = match app.volume_popover_window {
+                Some(open) if open == id => None,
+                _ => Some(id),
+            };
+            Task::none()
+        }
+        Message::VolumeChanged(volume) => {
+            trace!(volume, "Volume slider moved");
+            apply_playback_volume(app, volume);
+            Task::none()
+        }
+        Message::VolumeReleased => {
+            config::save_playback_volume(app.playback_volume);
+            Task::none()
+        }
+        Message::SpeedScrolled(lines) => {
+            let speed = (app.playback_speed + lines * SPEED_STEP * app.scroll_sensitivity).clamp(MIN_SPEED, MAX_SPEED);
+            // Keep to round steps so the speed can be brought back to exactly 1×
+            let speed = (speed * 20.0).round() / 20.0;
+            trace!(speed, "Speed scrolled");
+            apply_playback_speed(app, speed);
+            Task::none()
+        }
+        Message::SpeedCycled => {
+            // The first preset above the current speed, back to the slowest after the fastest
+            let speed = SPEED_PRESETS
+                .into_iter()
+                .find(|preset| *preset > app.playback_speed + 0.01)
+                .unwrap_or(SPEED_PRESETS[0]);
+            debug!(speed, "Speed button pressed");
+            apply_playback_speed(app, speed);
+            Task::none()
+        }
+        Message::SentenceScrolled(lines) => {
+            app.scroll_seek_lines -= lines * app.scroll_sensitivity;
+            let sentences = app.scroll_seek_lines.trunc();
+            if sentences == 0.0 {
+                return Task::none();
+            }
+            app.scroll_seek_lines -= sentences;
+            seek_sentences(app, sentences as i32);
+            Task::none()
+        }
+        Message::SkipSentence(offset) => {
+            seek_sentences(app, offset);
+            Task::none()
+        }
+        Message::ScrollSensitivityChanged(sensitivity) => {
+            app.scroll_sensitivity = sensitivity;
+            config::save_scroll_sensitivity(sensitivity);
+            Task::none()
+        }
+        Message::TextCleanupToggled(enabled) => {
+            info!(?enabled, "Natural Reading toggled");
+            app.text_cleanup_enabled = enabled;
+            // Persist the setting
+            config::save_text_cleanup_enabled(enabled);
+            Task::none()
+        }
+        Message::WindowOpened(id) => {
+            info!(?id, "Window opened event received");
+            if app.main_window_id.is_none() {
+                app.main_window_id = Some(id);
+                info!("Main window ID set - UI is now visible");
+                crate::startup::finish();
+                
+                // If we already have pending text (from async fetch), initialize TTS now
+                if let Some(text) = app.pending_text.take() {
+                    return process_captured_text(app, text, "WindowOpened");
+                }
+            } else {
+                debug!(?id, "Window opened but main window ID already set");
+            }
+            app.current_window_id = Some(id);
+            Task::none()
+        }
+        Message::WindowKeyPressed(id, key, modifiers) => {
+            if app.main_window_id == Some(id) || app.mini_bar_window_ids.contains(&id) {
+                return match crate::ui::accessibility::main_bar_shortcut(&key, modifiers) {
+                    Some(message) => update(app, message),
+                    None => Task::none(),
+                };
+            }
+            // Escape closes secondary windows
+            if key == iced::keyboard::Key::Named(iced::keyboard::key::Named::Escape) && modifiers.is_empty() {
+                debug!(?id, "Escape pressed, closing window");
+                return window::close(id);
+            }
+            Task::none()
+        }
+        Message::WindowResized(id, size) => {
+            if let Some((_, geometry)) = app.window_geometry.get_mut(&id) {
+                geometry.width = size.width;
+                geometry.height = size.height;
+            }
+            Task::none()
+        }
+        Message::WindowMoved(id, position) => {
+            if let Some((_, geometry)) = app.window_geometry.get_mut(&id) {
+                geometry.position = Some((position.x, position.y));
+            }
+            Task::none()
+        }
+        Message::WindowClosed(id) => {
+            debug!(?id, "Window closed");
+            if let Some((kind, geometry)) = app.window_geometry.remove(&id) {
+                config::save_window_geometry(kind, geometry);
+            }
+            if app.bar_menu_window == Some(id) {
+                app.bar_menu_window = None;
+            }
+            if app.volume_popover_window == Some(id) {
+                app.volume_popover_window = None;
+            }
+            let mut revert_task = Task::none();
+            if app.settings_window_id == Some(id) {
+                app.settings_window_id = None;
+                app.show_settings_modal = false;
+                // Closing the window drops changes that were not applied
+                revert_task = revert_settings(app);
+            }
+            if app.voice_selection_window_id == Some(id) {
+                app.voice_selection_window_id = None;
+            }
+            if app.polly_info_window_id == Some(id) {
+                app.polly_info_window_id = None;
+            }
+            if app.elevenlabs_info_window_id == Some(id) {
+                app.elevenlabs_info_window_id = None;
+            }
+            if app.capture_source_window_id == Some(id) {
+                app.capture_source_window_id = None;
+            }
+            if app.screenshot_window_id == Some(id) {
+                app.screenshot_window_id = None;
+                app.screenshot_dragging = false;
+                app.screenshot_cursor = None;
+            }
+            if app.ocr_info_window_id == Some(id) {
+                app.ocr_info_window_id = None;
+            }
+            if app.text_cleanup_info_window_id == Some(id) {
+                app.text_cleanup_info_window_id = None;
+            }
+            if app.extracted_text_dialog_window_id == Some(id) {
+                app.extracted_text_dialog_window_id = None;
+                app.extracted_text = None;
+                app.extracted_text_editor = None;
+                app.qr_codes.clear();
+            }
+            if app.long_text_dialog_window_id == Some(id) {
+                app.long_text_dialog_window_id = None;
+                app.long_text_pending = None;
+            }
+            if app.sensitive_dialog_window_id == Some(id) {
+                app.sensitive_dialog_window_id = None;
+                app.sensitive_pending = None;
+            }
+            if app.weekly_summary_window_id == Some(id) {
+                app.weekly_summary_window_id = None;
+                app.weekly_summary_text = None;
+            }
+            if app.clipboard_history_window_id == Some(id) {
+                app.clipboard_history_window_id = None;
+            }
+            if app.processing_chain_window_id == Some(id) {
+                app.processing_chain_window_id = None;
+            }
+            if app.resume_dialog_window_id == Some(id) {
+                app.resume_dialog_window_id = None;
+                if app.resume_pending.take().is_some() {
+                    app.current_document = None;
+                }
+            }
+            if app.preview_window_id == Some(id) {
+                app.preview_window_id = None;
+                app.preview_text = None;
+            }
+            if app.voice_suggestion_window_id == Some(id) {
+                app.voice_suggestion_window_id = None;
+                app.voice_suggestion = None;
+            }
+            if app.trainer_window_id == Some(id) {
+                app.trainer_window_id = None;
+                app.trainer_recorder = None;
+            }
+            if app.palette_window_id == Some(id) {
+                app.palette_window_id = None;
+                app.palette_text = None;
+            }
+            if app.reading_view_window_id == Some(id) {
+                app.reading_view_window_id = None;
+                crate::providers::set_word_marks_wanted(false);
+            }
+            if app.transcript_window_id == Some(id) {
+                app.transcript_window_id = None;
+            }
+            app.mini_bar_window_ids.retain(|&mini_bar| mini_bar != id);
+            if app.current_window_id == Some(id) {
+                app.current_window_id = None;
+            }
+            // Hide window instead of exiting if system tray is available
+            if app.main_window_id == Some(id) {
+                if app.system_tray.is_some() {
+                    info!("Main window closed, hiding to system tray");
+                    app.window_hidden = true;
+                    // Don't clear main_window_id so we know to reopen it later
+                    // The window is already closed by the user, so we just mark it as hidden
+                } else {
+                    info!("Main window closed, exiting (no system tray)");
+                    return iced::exit();
+                }
+            }
+            revert_task
+        }
+        Message::SelectedTextFetched(text) => {
+            info!("Selected text fetched asynchronously");
+            if let Some(ref t) = text {
+                info!(bytes = t.len(), preview = %t.chars().take(50).collect::<String>(), "Text selected");
+            } else {
+                info!("No text selected - app will wait for text or close");
+            }
+            
+            // Initialize TTS if window is already open, otherwise store for later
+            if let Some(window_id) = app.main_window_id {
+                if let Some(text) = text {
+                    return process_captured_text(app, text, "SelectedTextFetched");
+                }
+                warn!("No text selected - closing window");
+                return window::close(window_id);
+            }
+            
+            // Window not ready yet, store text for WindowOpened handler
+            app.pending_text = text;
+            trace!("Window not ready yet, text stored for later initialization");
+            Task::none()
+        }
+        Message::TextCleanupResponse(result) => {
+            match result {
+                Ok(cleaned_text) => {
+                    info!(bytes = cleaned_text.len(), "Natural Reading successful, initializing TTS");
+                    // Update status to show we're now synthesizing
+                    transition(app, Event::Load("Synthesizing voice...".to_string()));
+                    app.reading_text = Some(cleaned_text.clone());
+                    app.processing_trace.record("Natural Reading", &cleaned_text);
+                    let backend = app.selected_backend;
+                    let cleaned_text = paced(app, cleaned_text, backend);
+                    return initialize_tts_async(app.selected_backend, cleaned_text, "TextCleanupResponse", app.selected_polly_voice.clone());
+                }
+                Err(e) => {
+                    error!(error = %e, "Natural Reading service failed");
+                    if fail_reading(app, "Natural Reading failed".to_string()) {
+                        return Task::none();
+                    }
+                    return open_settings_if_needed(app, e);
+                }
+            }
+        }
+        Message::TTSInitialized(result) => {
+            match result {
+                Ok(()) => {
+                    // Retrieve provider from static storage
+                    let Ok(mut guard) = PENDING_PROVIDER.lock() else {
+                        error!("Failed to lock PENDING_PROVIDER mutex");
+                        app.error_message = Some("Internal error: mutex lock failed".to_string());
+                        transition(app, Event::Fail("Internal error".to_string()));
+                        return Task::none();
+                    };
+                    
+                    let Some(send_provider) = guard.take() else {
+                        error!("TTS initialization succeeded but no provider found in storage");
+                        app.error_message = Some("Internal error: provider not found".to_string());
+                        transition(app, Event::Fail("Internal error".to_string()));
+                        return Task::none();
+                    };
+                    
+                    app.provider = Some(send_provider.0);
+                    app.playback_time = app.provider.as_ref().map(|p| (p.position(), p.duration())).unwrap_or_default();
+                    app.synthesis_cache_key = PENDING_CACHE_KEY.lock().ok().and_then(|mut key| key.take());
+                    transition(app, Event::Play);
+                    // Continuing a document from where it was left
+                    if let (Some(progress), Some(provider)) = (app.pending_seek.take(), app.provider.as_mut()) {
+                        provider.seek(progress);
+                        app.progress = progress;
+                    }
+                    if app.trainer_awaiting_reference {
+                        app.trainer_awaiting_reference = false;
+                        if let Some(ref provider) = app.provider {
+                            let (samples, sample_rate) = provider.synthesized_audio();
+                            let samples = system::resample(&samples, sample_rate, TRAINER_SAMPLE_RATE);
+                            app.trainer_reference = Some(system::trim_silence(&samples).to_vec());
+                            app.trainer_attempt = None;
+                        }
+                    }
+                    app.error_message = None;
+                    app.damaged_voice = None;
+                    // Held by the headphone-only option
+                    if app.provider.as_ref().is_some_and(|p| p.is_paused()) {
+                        info!("TTS provider initialized, playback held until resumed");
+                        transition(app, Event::Pause);
+                        app.status_text = Some("No headphones: press play to read aloud".to_string());
+                        return Task::none();
+                    }
+                    play_earcon(Earcon::Start);
+                    info!("TTS provider initialized and playback started");
+                    // Very short audio can end before the provider is handed over,
+                    // in which case its final playback update was ignored
+                    if app.provider.as_ref().is_some_and(|p| !p.is_playing() && !p.is_paused()) {
+                        let finished = PlaybackUpdate {
+                            progress: 1.0,
+                            finished: true,
+                        };
+                        return Task::perform(async move { Message::PlaybackUpdated(finished) }, |msg| msg);
+                    }
+                }
+                Err(e) => {
+                    error!(error = %e, "TTS initialization failed");
+                    play_earcon(Earcon::Error);
+                    app.pending_seek = None;
+                    app.trainer_awaiting_reference = false;
+                    // The current provider (if any) holds audio for older text
+                    app.last_capture_hash = None;
+                    
+                    // For "No audio data generated" errors, show in status text instead of opening settings
+                    if e.contains("No audio data generated by piper") {
+                        const DEFAULT_MSG: &str = "Voice gen. failed: Text too short or invalid";
+                        
+                        // Extract stderr info if available, otherwise use default message
+                        let user_message = if let Some(start) = e.find("stderr:") {
+                            let stderr_content = e[start + 7..].trim();
+                            if !stderr_content.is_empty() {
+                                format!("Voice gen. failed: {}", stderr_content)
+                            } else {
+                                DEFAULT_MSG.to_string()
+                            }
+                        } else {
+                            DEFAULT_MSG.to_string()
+                        };
+                        
+                        fail_reading(app, user_message);
+                        info!("TTS error shown in status text instead of settings window");
+                        return Task::none();
+                    }
+                    
+                    // Damaged model files: offer to download the voice again
+                    if e.starts_with("Voice model is damaged") {
+                        app.damaged_voice = Some(PiperTTSProvider::selected_voice_key());
+                    }
+
+                    // For other errors, use the existing behavior (open settings window),
+                    // unless an interrupted reading carries on
+                    if fail_reading(app, "Voice generation failed".to_string()) {
+                        return Task::none();
+                    }
+                    return open_settings_if_needed(app, e);
+                }
+            }
+            Task::none()
+        }
+        Message::StartDrag(id) => window::drag(id),
+        Message::OpenMiniBar => {
+            debug!(open = app.mini_bar_window_ids.len(), "Opening mini player window");
+            let (window_id, task) = window::open(window::Settings {
+                size: Size::new(450.0, 70.0),
+                resizable: false,
+                decorations: false,
+                transparent: true,
+                visible: true,
+                level: window::Level::AlwaysOnTop,
+                position: window::Position::SpecificWith(|window_size, monitor_size| {
+                    // Top-right corner, away from the main bar; drag it to another monitor or workspace
+                    let margin = 70.0;
+                    iced::Point::new(monitor_size.width - window_size.width - margin, margin)
+                }),
+                ..Default::default()
+            });
+            app.mini_bar_window_ids.push(window_id);
+            task.map(Message::WindowOpened)
+        }
+        Message::CloseMiniBar(id) => {
+            debug!(?id, "Closing mini player window");
+            window::close(id)
+        }
+        Message::ShowBarMenu(id) => {
+            app.bar_menu_window = Some(id);
+            Task::none()
+        }
+        Message::CloseBarMenu => {
+            app.bar_menu_window = None;
+            Task::none()
+        }
+        Message::ReadClipboard => {
+            app.bar_menu_window = None;
+            Task::perform(
+                async { tokio::task::spawn_blocking(system::get_clipboard_text).await.unwrap_or_default() },
+                Message::ClipboardTextFetched,
+            )
+        }
+        Message::ClipboardTextFetched(text) => match text {
+            Some(text) => read_in_main_window(app, text),
+            None => {
+                info!("No text on the clipboard to read");
+                Task::none()
+            }
+        },
+        Message::ReplayReading => {
+            app.bar_menu_window = None;
+            let Some(ref mut provider) = app.provider else {
+                warn!("Replay requested with no active provider");
+                return Task::none();
+            };
+            if let Err(e) = provider.replay() {
+                warn!(error = %e, "Failed to replay audio");
+            } else {
+                app.paused_for_typing = false;
+                transition(app, Event::Play);
+                info!("Replaying the last reading");
+            }
+            Task::none()
+        }
+        Message::ExportAudio => {
+            app.bar_menu_window = None;
+            let Some(ref provider) = app.provider else {
+                warn!("Audio export requested with no active provider");
+                return Task::none();
+            };
+            let (samples, sample_rate) = provider.synthesized_audio();
+            let title = app
+                .reading_text
+                .as_deref()
+                .and_then(text::title_from_text)
+                .unwrap_or_else(|| "Reading".to_string());
+            info!(samples = samples.len(), sample_rate, "Exporting the audio of the last reading");
+            Task::perform(
+                async move {
+                    tokio::task::spawn_blocking(move || {
+                        // MP3 when ffmpeg can encode it
+                        let format = if system::find_ffmpeg().is_some() { system::AudioFormat::Mp3 } else { system::AudioFormat::Wav };
+                        let stamp = chrono::Local::now().format("%Y-%m-%d %H.%M.%S");
+                        let name = format!("{stamp} {}.{}", system::sanitize_file_name(&title), format.extension());
+                        let out = system::default_export_dir().join(name);
+                        let tags = system::AudioTags { title, ..Default::default() };
+                        system::export_audio(&samples_to_wav(&samples, sample_rate), &out, &tags).map(|()| out)
+                    })
+                    .await
+                    .unwrap_or_else(|e| Err(format!("Audio export failed: {e}")))
+                },
+                Message::AudioExported,
+            )
+        }
+        Message::AudioExported(result) => {
+            match result {
+                Ok(path) => {
+                    info!(path = %path.display(), "Audio exported");
+                    app.status_text = Some(format!("Audio saved to {}", path.display()));
+                }
+                Err(e) => {
+                    error!(error = %e, "Audio export failed");
+                    app.error_message = Some(e);
+                }
+            }
+            Task::none()
+        }
+        Message::OpenHistory => {
+            app.bar_menu_window = None;
+            let path = crate::history::history_path();
+            if !path.exists() {
+                info!("No reading history yet");
+                return Task::none();
+            }
+            if let Err(e) = open::that(&path) {
+                error!("Failed to open history '{}': {}", path.display(), e);
+            }
+            Task::none()
+        }
+        Message::ToggleFavorite => {
+            app.bar_menu_window = None;
+            let Some(id) = app.history_entry.as_deref() else {
+                debug!("Reading not in the history, nothing to mark as favorite");
+                return Task::none();
+            };
+            let favorite = !app.reading_favorite;
+            if crate::history::set_favorite(id, favorite) {
+                info!(favorite, "Reading favorite toggled");
+                app.reading_favorite = favorite;
+            } else {
+                warn!("Reading no longer in the history, cannot mark it as favorite");
+                app.history_entry = None;
+            }
+            Task::none()
+        }
+        Message::VoicesJsonLoaded(result) => {
+            app.voices_loading = false;
+            match result {
+                Ok(voices) => {
+                    info!(count = voices.len(), "Voices.json loaded successfully");
+                    app.voices = Some(voices);
+                }
+                Err(e) if app.voices.is_some() => {
+                    warn!(error = %e, "Failed to refresh voices.json, keeping the cached list");
+                }
+                Err(e) => {
+                    error!(error = %e, "Failed to load voices.json");
+                    // Show error to user in settings window if it's open
+                    if app.settings_window_id.is_some() {
+                        app.error_message = Some(format!("Failed to load voices: {}. Check your internet connection.", e));
+                    }
+                }
+            }
+            Task::none()
+        }
+        Message::PollyVoicesLoaded(result) => {
+            app.polly_voices_loading = false;
+            match result {
+                Ok(voices) => {
+                    info!(count = voices.len(), "AWS Polly voices loaded successfully");
+                    app.polly_voices = Some(voices);
+                    app.polly_error_message = None; // Clear error on success
+                }
+                Err(e) => {
+                    debug!(error = %e, "Failed to load AWS Polly voices (credentials may not be configured)");
+                    // Show error for service errors (e.g., clock skew, network issues) but not credential errors
+                    let error_lower = e.to_lowercase();
+                    let is_credential_error = error_lower.contains("credentials")
+                        || error_lower.contains("authentication")
+                        || error_lower.contains("unauthorized");
+                    let is_service_error = error_lower.contains("service error")
+                        || error_lower.contains("network")
+                        || error_lower.contains("timeout")
+                        || error_lower.contains("clock");
+                    // Cached voices stay listed unless the credentials are gone
+                    if is_credential_error {
+                        app.polly_voices = None;
+                    }
+                    
+                    app.polly_error_message = if app.polly_voices.is_some() {
+                        warn!(error = %e, "Failed to refresh AWS Polly voices, keeping the cached list");
+                        None
+                    } else if is_service_error || !is_credential_error {
+                        Some(e)
+                    } else {
+                        None
+                    };
+                }
+            }
+            Task::none()
+        }
+        Message::RefreshVoices(backend) => {
+            info!(?backend, "Refreshing voice list");
+            match backend {
+                TTSBackend::Piper => load_piper_voices(app, true),
+                TTSBackend::AwsPolly => load_polly_voices(app, true),
+                TTSBackend::ElevenLabs => load_elevenlabs_voices(app),
+                TTSBackend::MacOS | TTSBackend::Windows => load_system_voices(backend),
+                TTSBackend::Espeak => Task::none(),
+            }
+        }
+        Message::OpenVoiceSelection(lang_code) => {
+            if app.voice_selection_window_id.is_some() {
+                debug!("Voice selection window already open, ignoring request");
+                return Task::none();
+            }
+            
+            debug!(language = %lang_code, "Opening voice selection window");
+            remember_recent_language(app, &lang_code);
+            config::save_last_language(app.selected_backend, &lang_code);
+            app.selected_language = Some(lang_code);
+            
+            let (window_id, task) = window::open(window::Settings {
+                size: Size::new(400.0, 500.0), // 33% narrower: 600 * 0.67 ≈ 400
+                resizable: false,
+                decorations: false,
+                transparent: false,
+                visible: true,
+                position: window::Position::Centered,
+                ..Default::default()
+            });
+            app.voice_selection_window_id = Some(window_id);
+            task.map(Message::WindowOpened)
+        }
+        Message::CloseVoiceSelection => {
+            close_window_if_some(app.voice_selection_window_id.take())
+        }
+        Message::OpenPollyInfo => {
+            if app.polly_info_window_id.is_some() {
+                debug!("Polly info window already open, ignoring request");
+                return Task::none();
+            }
+            
+            debug!("Opening AWS Polly pricing info window");
+            let (window_id, task) = open_info_window(Size::new(500.0, 400.0));
+            app.polly_info_window_id = Some(window_id);
+            task
+        }
+        Message::ClosePollyInfo => {
+            close_window_if_some(app.polly_info_window_id.take())
+        }
+        Message::OpenPollyPricingUrl => {
+            let url = "https://aws.amazon.com/polly/pricing/";
+            open_url(url);
+            info!("Opening AWS Polly pricing URL in browser");
+            Task::none()
+        }
+        Message::ElevenLabsApiKeyChanged(api_key) => {
+            debug!(set = !api_key.trim().is_empty(), "ElevenLabs API key changed");
+            config::save_elevenlabs_api_key(&api_key);
+            app.elevenlabs_api_key = api_key;
+            // The voices belong to the account of the old key
+            app.elevenlabs_voices = None;
+            app.elevenlabs_error_message = None;
+            Task::none()
+        }
+        Message::ElevenLabsVoicesLoaded(result) => {
+            app.elevenlabs_voices_loading = false;
+            match result {
+                Ok(voices) => {
+                    info!(count = voices.len(), "ElevenLabs voices loaded");
+                    app.elevenlabs_voices = Some(voices);
+                    app.elevenlabs_error_message = None;
+                }
+                Err(e) => {
+                    warn!(error = %e, "Failed to load ElevenLabs voices");
+                    app.elevenlabs_error_message = Some(e);
+                }
+            }
+            Task::none()
+        }
+        Message::SystemVoicesLoaded(result) => {
+            match result {
+                Ok(voices) => {
+                    info!(count = voices.len(), "System voices listed");
+                    app.system_voices = Some(voices);
+                    app.system_voices_error = None;
+                }
+                Err(e) => {
+                    warn!(error = %e, "Failed to list system voices");
+                    app.system_voices_error = Some(e);
+                }
+            }
+            Task::none()
+        }
+        Message::OpenElevenLabsInfo => {
+            if app.elevenlabs_info_window_id.is_some() {
+                debug!("ElevenLabs info window already open, ignoring request");
+                return Task::none();
+            }
+
+            debug!("Opening ElevenLabs pricing info window");
+            let (window_id, task) = open_info_window(Size::new(500.0, 400.0));
+            app.elevenlabs_info_window_id = Some(window_id);
+            task
+        }
+        Message::CloseElevenLabsInfo => {
+            close_window_if_some(app.elevenlabs_info_window_id.take())
+        }
+        Message::OpenElevenLabsPricingUrl => {
+            open_url("https://elevenlabs.io/pricing");
+            info!("Opening ElevenLabs pricing URL in browser");
+            Task::none()
+        }
+        Message::OCRBackendSelected(backend) => {
+            // Prevent selection of BetterOCR since it's not available yet
+            if backend == OCRBackend::BetterOCR {
+                debug!("Better OCR is not available yet, ignoring selection");
+                return Task::none();
+            }
+            info!(?backend, "OCR backend selected");
+            app.selected_ocr_backend = backend;
+            config::save_ocr_backend(backend);
+            Task::none()
+        }
+        Message::OpenOCRInfo => {
+            if app.ocr_info_window_id.is_some() {
+                debug!("OCR info window already open, ignoring request");
+                return Task::none();
+            }
+            
+            debug!("Opening Better OCR info window");
+            let (window_id, task) = open_info_window(Size::new(500.0, 300.0));
+            app.ocr_info_window_id = Some(window_id);
+            task
+        }
+        Message::CloseOCRInfo => {
+            close_window_if_some(app.ocr_info_window_id.take())
+        }
+        Message::OpenTextCleanupInfo => {
+            if app.text_cleanup_info_window_id.is_some() {
+                debug!("Natural Reading info window already open, ignoring request");
+                return Task::none();
+            }
+            
+            debug!("Opening Natural Reading info window");
+            let (window_id, task) = open_info_window(Size::new(500.0, 300.0));
+            app.text_cleanup_info_window_id = Some(window_id);
+            task
+        }
+        Message::CloseTextCleanupInfo => {
+            close_window_if_some(app.text_cleanup_info_window_id.take())
+        }
+        Message::VoiceSelected(voice_key) => {
+            info!(voice = %voice_key, "Voice selected");
+            let previous = provider_key(app.selected_backend, app.selected_polly_voice.as_deref());
+            remember_recent_voice(app, &voice_key);
+            match app.selected_backend {
+                TTSBackend::Piper => {
+                    app.selected_voice = Some(voice_key.clone());
+                    config::save_selected_voice(voice_key);
+                }
+                TTSBackend::AwsPolly => {
+                    app.selected_polly_voice = Some(voice_key.clone());
+                    config::save_selected_polly_voice(voice_key);
+                }
+                TTSBackend::ElevenLabs => {
+                    app.selected_elevenlabs_voice = Some(voice_key.clone());
+                    config::save_selected_elevenlabs_voice(voice_key);
+                }
+                TTSBackend::MacOS => {
+                    app.selected_macos_voice = Some(voice_key.clone());
+                    config::save_selected_macos_voice(voice_key);
+                }
+                TTSBackend::Windows => {
+                    app.selected_windows_voice = Some(voice_key.clone());
+                    config::save_selected_windows_voice(voice_key);
+                }
+                // The basic voice has no voices to choose from
+                TTSBackend::Espeak => {}
+            }
+            let close_task = close_window_if_some(app.voice_selection_window_id.take());
+            let changed = provider_key(app.selected_backend, app.selected_polly_voice.as_deref()) != previous;
+            if changed && app.activity.has_speech() {
+                return Task::batch([close_task, swap_voice(app)]);
+            }
+            prewarm_provider(app.selected_backend, app.selected_polly_voice.clone());
+            close_task
+        }
+        Message::VoiceDownloadRequested(voice_key) => {
+            info!(voice = %voice_key, "Voice download requested");
+            
+            let voice_info = app.voices.as_ref()
+                .and_then(|voices| voices.get(&voice_key).cloned());
+            
+            if let Some(voice_info) = voice_info {
+                // Set downloading state
+                app.downloading_voice = Some(voice_key.clone());
+                transition(app, Event::Download(format!("Downloading voice: {}...", voice_info.name)));
+                
+                // Start async download
+                Task::perform(
+                    async move {
+                        use crate::voices::download;
+                        download::download_voice(&voice_key, &voice_info)
+                            .await
+                            .map(|_| voice_key)
+                    },
+                    Message::VoiceDownloaded,
+                )
+            } else {
+                error!(voice = %voice_key, "Voice not found in voices.json");
+                app.error_message = Some(format!("Voice {} not found", voice_key));
+                Task::none()
+            }
+        }
+        Message::RepairVoice(voice_key) => {
+            info!(voice = %voice_key, "Re-downloading damaged voice");
+            app.damaged_voice = None;
+            app.error_message = None;
+            app.downloading_voice = Some(voice_key.clone());
+            transition(app, Event::Download("Downloading voice again...".to_string()));
+            let known_info = app.voices.as_ref().and_then(|voices| voices.get(&voice_key).cloned());
+            Task::perform(
+                async move {
+                    // The voice list is only fetched when settings open, it may not be loaded yet
+                    let voice_info = match known_info {
+                        Some(info) => info,
+                        None => crate::voices::fetch_voices_json()
+                            .await?
+                            .remove(&voice_key)
+                            .ok_or_else(|| format!("Voice {voice_key} not found"))?,
+                    };
+                    crate::voices::download::download_voice(&voice_key, &voice_info)
+                        .await
+                        .map(|_| voice_key)
+                },
+                Message::VoiceDownloaded,
+            )
+        }
+        Message::VoiceSuggested(None) => Task::none(),
+        Message::VoiceSuggested(Some(voice)) => {
+            if app.voice_suggestion.is_some() {
+                return Task::none();
+            }
+            info!(voice = %voice.key, "Suggesting a voice for the language being read");
+            app.voice_suggestion = Some(voice);
+            let (window_id, open_task) = window::open(window::Settings {
+                size: Size::new(420.0, 110.0),
+                resizable: false,
+                decorations: false,
+                transparent: true,
+                visible: true,
+                level: window::Level::AlwaysOnTop,
+                // Just above the main window
+                position: window::Position::SpecificWith(|window_size, monitor_size| {
+                    let margin = 150.0;
+                    iced::Point::new(70.0, monitor_size.height - window_size.height - margin)
+                }),
+                ..Default::default()
+            });
+            app.voice_suggestion_window_id = Some(window_id);
+            let timeout = Task::perform(
+                async { tokio::time::sleep(std::time::Duration::from_secs(VOICE_SUGGESTION_SECONDS)).await },
+                move |_| Message::VoiceSuggestionTimedOut(window_id),
+            );
+            Task::batch([open_task.map(Message::WindowOpened), timeout])
+        }
+        Message::DownloadSuggestedVoice => {
+            let close_task = close_window_if_some(app.voice_suggestion_window_id.take());
+            let Some(voice_info) = app.voice_suggestion.take() else {
+                return close_task;
+            };
+            info!(voice = %voice_info.key, "Downloading suggested voice");
+            app.downloading_voice = Some(voice_info.key.clone());
+            transition(app, Event::Download(format!("Downloading voice: {}...", voice_info.name)));
+            let download_task = Task::perform(
+                async move {
+                    crate::voices::download::download_voice(&voice_info.key, &voice_info)
+                        .await
+                        .map(|_| voice_info.key)
+                },
+                Message::VoiceDownloaded,
+            );
+            Task::batch([close_task, download_task])
+        }
+        Message::DeclineVoiceSuggestion(for_good) => {
+            if let Some(voice) = app.voice_suggestion.take() {
+                let family = voice.language.family;
+                info!(language = %family, for_good, "Voice suggestion declined");
+                if for_good {
+                    let mut ignored = config::load_ignored_voice_languages();
+                    ignored.push(family.clone());
+                    config::save_ignored_voice_languages(&ignored);
+                }
+                app.declined_voice_languages.push(family);
+            }
+            close_window_if_some(app.voice_suggestion_window_id.take())
+        }
+        Message::VoiceSuggestionTimedOut(window_id) => {
+            if app.voice_suggestion_window_id != Some(window_id) {
+                return Task::none();
+            }
+            debug!("Voice suggestion unanswered, asking again next time");
+            app.voice_suggestion = None;
+            close_window_if_some(app.voice_suggestion_window_id.take())
+        }
+        Message::ExecutionProvidersDetected(providers) => {
+            app.available_execution_providers = Some(providers);
+            Task::none()
+        }
+        Message::ExecutionProviderSelected(provider) => {
+            info!(?provider, "Execution provider selected");
+            app.execution_provider = provider;
+            config::save_execution_provider(provider);
+            prewarm_provider(app.selected_backend, app.selected_polly_voice.clone());
+            Task::none()
+        }
+        Message::BenchmarkExecutionProviders => {
+            let Some(providers) = app.available_execution_providers.clone() else {
+                return Task::none();
+            };
+            if app.execution_benchmark_running {
+                return Task::none();
+            }
+            info!(?providers, "Benchmarking execution providers");
+            app.execution_benchmark_running = true;
+            app.execution_benchmark = None;
+            Task::perform(
+                async move {
+                    tokio::task::spawn_blocking(move || PiperTTSProvider::benchmark_execution_providers(&providers))
+                        .await
+                        .unwrap_or_else(|e| Err(format!("Benchmark failed: {e}")))
+                },
+                Message::ExecutionProvidersBenchmarked,
+            )
+        }
+        Message::PowerPolicySelected(policy) => {
+            info!(?policy, "Power policy selected");
+            app.power_policy = policy;
+            config::save_power_policy(policy);
+            prewarm_provider(app.selected_backend, app.selected_polly_voice.clone());
+            Task::none()
+        }
+        Message::SynthesisThreadsChanged(threads) => {
+            debug!(threads, "Synthesis threads changed");
+            app.synthesis_threads = threads;
+            config::save_synthesis_threads(threads);
+            Task::none()
+        }
+        Message::SynthesisPrioritySelected(priority) => {
+            info!(?priority, "Synthesis priority selected");
+            app.synthesis_priority = priority;
+            config::save_synthesis_priority(priority);
+            prewarm_provider(app.selected_backend, app.selected_polly_voice.clone());
+            Task::none()
+        }
+        Message::PowerSourceChecked(source) => {
+            app.power_source = Some(source);
+            Task::none()
+        }
+        Message::ExecutionProvidersBenchmarked(result) => {
+            app.execution_benchmark_running = false;
+            match &result {
+                Ok(timings) => info!(?timings, "Execution provider benchmark finished"),
+                Err(e) => warn!(error = %e, "Execution provider benchmark failed"),
+            }
+            app.execution_benchmark = Some(result);
+            Task::none()
+        }
+        Message::VoiceDownloaded(result) => {
+            app.downloading_voice = None;
+            match result {
+                Ok(voice_key) => {
+                    info!(voice = %voice_key, "Voice downloaded successfully");
+                    transition(app, Event::Downloaded);
+                    app.status_text = Some("Voice downloaded successfully".to_string());
+                    // Auto-select the downloaded voice
+                    app.selected_voice = Some(voice_key.clone());
+                    config::save_selected_voice(voice_key);
+                    prewarm_provider(app.selected_backend, app.selected_polly_voice.clone());
+                }
+                Err(e) => {
+                    error!(error = %e, "Voice download failed");
+                    transition(app, Event::Fail("Voice download failed".to_string()));
+                    app.error_message = Some(format!("Download failed: {}", e));
+                }
+            }
+            Task::none()
+        }
+        Message::ScreenshotRequested => {
+            info!(private = app.private_screenshots, "Screenshot button clicked, starting region selection");
+            let private = app.private_screenshots;
+            // Spawn async task to capture screenshot region
+            Task::perform(
+                async move {
+                    debug!("Starting async screenshot capture task");
+                    // Use spawn_blocking for the blocking shell command
+                    let result = tokio::task::spawn_blocking(move || {
+                        debug!("Executing capture_region in blocking thread");
+                        if private {
+                            crate::system::capture_region_in_memory()
+                                .map(|png| crate::system::Screenshot::Png(std::sync::Arc::new(png)))
+                        } else {
+                            crate::system::capture_region().map(crate::system::Screenshot::File)
+                        }
+                    })
+                    .await;
+                    debug!("Screenshot capture task completed");
+                    result.unwrap_or_else(|e| {
+                        tracing::warn!(error = %e, "Failed to join blocking task for screenshot capture");
+                        Err(format!("Task join error: {}", e))
+                    })
+                },
+                Message::ScreenshotCaptured,
+            )
+        }
+        Message::ScreenshotCaptured(result) => {
+            match result {
+                Ok(screenshot) => {
+                    info!(?screenshot, "Screenshot captured successfully");
+                    app.screenshot_handle = Some(match screenshot {
+                        crate::system::Screenshot::File(ref path) => iced::widget::image::Handle::from_path(path),
+                        crate::system::Screenshot::Png(ref png) => iced::widget::image::Handle::from_bytes(png.to_vec()),
+                    });
+                    app.screenshot = Some(screenshot.clone());
+                    app.ocr_lines.clear();
+                    app.qr_codes.clear();
+                    app.screenshot_viewport = match screenshot.dimensions() {
+                        Ok((width, height)) => crate::ui::screenshot::ScreenshotViewport::new(width, height),
+                        Err(e) => {
+                            warn!(error = %e, "Failed to read screenshot size, zoom disabled");
+                            Default::default()
+                        }
+                    };
+                    app.status_text = Some("Extracting text from image...".to_string());
+                    
+                    // Automatically extract text from the screenshot
+                    let ocr_screenshot = screenshot.clone();
+                    let ocr_task = Task::perform(
+                        async move {
+                            debug!("Starting async text extraction from screenshot");
+                            // Use spawn_blocking for the blocking shell command
+                            let result = tokio::task::spawn_blocking(move || {
+                                debug!("Executing extract_text_lines in blocking thread");
+                                ocr_screenshot.text_lines()
+                            })
+                            .await;
+                            debug!("Text extraction task completed");
+                            result.unwrap_or_else(|e| {
+                                tracing::warn!(error = %e, "Failed to join blocking task for text extraction");
+                                Err(format!("Task join error: {}", e))
+                            })
+                        },
+                        Message::ScreenshotTextExtracted,
+                    );
+                    
+                    // Look for QR codes (links) alongside, they often come without any text
+                    let qr_task = Task::perform(
+                        async move {
+                            tokio::task::spawn_blocking(move || screenshot.qr_codes())
+                                .await
+                                .unwrap_or_default()
+                        },
+                        Message::ScreenshotQrDecoded,
+                    );
+                    Task::batch([ocr_task, qr_task])
+                }
+                Err(e) => {
+                    // Don't show error for user cancellation
+                    if e.contains("cancelled") {
+                        debug!("User cancelled screenshot selection");
+                    } else {
+                        error!(error = %e, "Screenshot capture failed");
+                        app.error_message = Some(format!("Screenshot failed: {}", e));
                     }
+                    Task::none()
                 }
-                PlaybackState::Paused => {
-                    if let Err(e) = provider.resume() {
-                        error!(error = %e, "Failed to resume playback");
+            }
+        }
+        Message::ScreenshotTextExtracted(result) => {
+            match result {
+                Ok(lines) => {
+                    let extracted_text = crate::system::lines_text(&lines);
+                    info!(bytes = extracted_text.len(), lines = lines.len(), "Text extracted from screenshot successfully");
+                    info!(
+                        text = %extracted_text,
+                        "Extracted text from screenshot"
+                    );
+                    app.status_text = Some("Text extracted from image".to_string());
+                    
+                    // Store extracted text and initialize editor content
+                    app.extracted_text = Some(extracted_text.clone());
+                    app.capture_app = None;
+                    app.extracted_text_editor = Some(iced::widget::text_editor::Content::with_text(&extracted_text));
+                    app.ocr_lines = lines;
+                    
+                    // Open the extracted text dialog window
+                    if app.extracted_text_dialog_window_id.is_none() {
+                        let (window_id, task) = open_extracted_text_window(app);
+                        app.extracted_text_dialog_window_id = Some(window_id);
+                        return task;
+                    }
+                }
+                Err(e) => {
+                    warn!(error = %e, "Failed to extract text from screenshot");
+                    app.ocr_lines.clear();
+                    // Don't show error if no text was found (image might not contain text)
+                    if e.contains("No text found") {
+                        app.status_text = Some("No text found in image".to_string());
                     } else {
-                        app.playback_state = PlaybackState::Playing;
-                        info!("Playback resumed");
+                        app.error_message = Some(format!("Text extraction failed: {}", e));
+                        app.status_text = Some("Text extraction failed".to_string());
                     }
                 }
-                PlaybackState::Stopped => {}
             }
             Task::none()
         }
-        Message::Stop => {
+        Message::OpenScreenshotViewer => {
+            if app.screenshot_window_id.is_some() {
+                debug!("Screenshot window already open, ignoring request");
+                return Task::none();
+            }
+            
+            if app.screenshot.is_none() {
+                debug!("No screenshot available to display");
+                return Task::none();
+            }
+            
+            debug!("Opening screenshot viewer window");
+            let (window_id, task) = open_remembered_window(app, SCREENSHOT_VIEWER_WINDOW, Size::new(800.0, 600.0));
+            app.screenshot_window_id = Some(window_id);
+            task
+        }
+        Message::CloseScreenshotViewer => {
+            close_window_if_some(app.screenshot_window_id.take())
+        }
+        Message::ScreenshotZoom(factor) => {
+            let viewer = crate::ui::screenshot::viewer_size(app);
+            app.screenshot_viewport.zoom(factor, viewer);
+            Task::none()
+        }
+        Message::ScreenshotScrolled(delta) => {
+            let lines = match delta {
+                iced::mouse::ScrollDelta::Lines { y, .. } => y,
+                iced::mouse::ScrollDelta::Pixels { y, .. } => y / 40.0,
+            };
+            if lines == 0.0 {
+                return Task::none();
+            }
+            let viewer = crate::ui::screenshot::viewer_size(app);
+            app.screenshot_viewport
+                .zoom(crate::ui::screenshot::ZOOM_STEP.powf(lines.clamp(-3.0, 3.0)), viewer);
+            Task::none()
+        }
+        Message::ScreenshotFit => {
+            app.screenshot_viewport.fit();
+            Task::none()
+        }
+        Message::ScreenshotActualSize => {
+            let viewer = crate::ui::screenshot::viewer_size(app);
+            app.screenshot_viewport.actual_size(viewer);
+            Task::none()
+        }
+        Message::ScreenshotDragStarted => {
+            app.screenshot_dragging = true;
+            Task::none()
+        }
+        Message::ScreenshotDragEnded => {
+            app.screenshot_dragging = false;
+            Task::none()
+        }
+        Message::ScreenshotCursorMoved(position) => {
+            if let Some(last) = app.screenshot_cursor.filter(|_| app.screenshot_dragging) {
+                let viewer = crate::ui::screenshot::viewer_size(app);
+                app.screenshot_viewport.pan(position - last, viewer);
+            }
+            app.screenshot_cursor = Some(position);
+            Task::none()
+        }
+        Message::OcrVisibleRegion => {
+            let Some(screenshot) = app.screenshot.clone() else {
+                return Task::none();
+            };
+            let region = app.screenshot_viewport.region(crate::ui::screenshot::viewer_size(app));
+            info!(?region, "Running OCR on the visible part of the screenshot");
+            app.status_text = Some("Extracting text from image...".to_string());
+            Task::perform(
+                async move {
+                    let result = tokio::task::spawn_blocking(move || {
+                        let Some(region) = region else {
+                            return screenshot.text_lines();
+                        };
+                        // Boxes found in the crop are moved back to screenshot coordinates
+                        let lines = screenshot.crop(region.x, region.y, region.width, region.height)?.text_lines()?;
+                        Ok(lines
+                            .into_iter()
+                            .map(|line| line.offset(region.x as f32, region.y as f32))
+                            .collect())
+                    })
+                    .await;
+                    result.unwrap_or_else(|e| {
+                        tracing::warn!(error = %e, "Failed to join blocking task for text extraction");
+                        Err(format!("Task join error: {}", e))
+                    })
+                },
+                Message::ScreenshotTextExtracted,
+            )
+        }
+        Message::ScreenshotQrDecoded(codes) => {
+            if codes.is_empty() {
+                return Task::none();
+            }
+            app.qr_codes = codes;
+            // Show the links even when the screenshot has no text to extract
+            if app.extracted_text_dialog_window_id.is_some() {
+                return Task::none();
+            }
+            let (window_id, task) = open_extracted_text_window(app);
+            app.extracted_text_dialog_window_id = Some(window_id);
+            task
+        }
+        Message::OpenLink(url) => {
+            info!(url = %url, "Opening link from screenshot");
+            open_url(&url);
+            Task::none()
+        }
+        Message::ReadLinkedPage(url) => {
+            app.status_text = Some("Fetching page...".to_string());
+            let options = speech_options(app);
+            Task::perform(
+                async move {
+                    let result = system::fetch_page_text(&url, options).await;
+                    (url, result)
+                },
+                |(url, result)| Message::LinkedPageFetched(url, result),
+            )
+        }
+        Message::LinkedPageFetched(url, result) => match result {
+            Ok(text) => {
+                app.status_text = None;
+                let read_task = read_document(app, crate::history::url_document_key(&url), text, "LinkedPage");
+                Task::batch([show_main_window(app), read_task])
+            }
+            Err(e) => {
+                app.status_text = None;
+                app.error_message = Some(e);
+                Task::none()
+            }
+        },
+        Message::CopyLink(link) => {
+            match system::copy_to_clipboard(&link) {
+                Ok(()) => app.status_text = Some("Link copied to clipboard".to_string()),
+                Err(e) => {
+                    error!(error = %e, "Failed to copy link to clipboard");
+                    app.error_message = Some(format!("Failed to copy to clipboard: {}", e));
+                }
+            }
+            Task::none()
+        }
+        Message::ToggleOcrBoxes => {
+            app.show_ocr_boxes = !app.show_ocr_boxes;
+            Task::none()
+        }
+        Message::OcrLineClicked(index) => {
+            let Some(line) = app.ocr_lines.get(index) else {
+                return Task::none();
+            };
+            debug!(index, text = %line.text, "OCR box clicked");
+            // The dialog may have been closed since the OCR ran
+            let editor = app.extracted_text_editor.get_or_insert_with(|| {
+                iced::widget::text_editor::Content::with_text(&crate::system::lines_text(&app.ocr_lines))
+            });
+            app.extracted_text = Some(editor.text());
+
+            // Select the line, unless it has been edited away
+            if let Some(line_length) = editor.line(index).map(|line| line.text.len()) {
+                use iced::widget::text_editor::{Cursor, Position};
+                editor.move_to(Cursor {
+                    position: Position { line: index, column: line_length },
+                    selection: Some(Position { line: index, column: 0 }),
+                });
+            }
+
+            match app.extracted_text_dialog_window_id {
+                Some(id) => window::gain_focus(id),
+                None => {
+                    let (window_id, task) = open_extracted_text_window(app);
+                    app.extracted_text_dialog_window_id = Some(window_id);
+                    task
+                }
+            }
+        }
+        Message::OpenExtractedTextDialog => {
+            if app.extracted_text_dialog_window_id.is_some() {
+                debug!("Extracted text dialog already open, ignoring request");
+                return Task::none();
+            }
+            
+            if app.extracted_text.is_none() {
+                debug!("No extracted text available to display");
+                return Task::none();
+            }
+            
+            debug!("Opening extracted text dialog window");
+            let (window_id, task) = open_extracted_text_window(app);
+            app.extracted_text_dialog_window_id = Some(window_id);
+            task
+        }
+        Message::CloseExtractedTextDialog => {
+            app.extracted_text = None;
+            app.extracted_text_editor = None;
+            app.qr_codes.clear();
+            close_window_if_some(app.extracted_text_dialog_window_id.take())
+        }
+        Message::CopyExtractedTextToClipboard => {
+            let text_to_copy = app.extracted_text_editor.as_ref()
+                .map(|e| e.text())
+                .or_else(|| app.extracted_text.clone());
+            
+            let Some(text_to_copy) = text_to_copy else {
+                warn!("No extracted text available to copy");
+                return Task::none();
+            };
+            
+            match system::copy_to_clipboard(&text_to_copy) {
+                Ok(()) => {
+                    info!(bytes = text_to_copy.len(), "Text copied to clipboard successfully");
+                    app.status_text = Some("Text copied to clipboard".to_string());
+                }
+                Err(e) => {
+                    error!(error = %e, "Failed to copy text to clipboard");
+                    app.error_message = Some(format!("Failed to copy to clipboard: {}", e));
+                }
+            }
+            Task::none()
+        }
+        Message::ExtractedTextEditorAction(action) => {
+            // Apply the action to the editor content
+            if let Some(ref mut editor_content) = app.extracted_text_editor {
+                editor_content.perform(action);
+                // Update the extracted_text string for consistency
+                app.extracted_text = Some(editor_content.text());
+            }
+            Task::none()
+        }
+        Message::ReadExtractedText => {
+            let text_to_read = app.extracted_text_editor.as_ref()
+                .map(|e| e.text())
+                .or_else(|| app.extracted_text.clone());
+            
+            let Some(text_to_read) = text_to_read else {
+                warn!("No extracted text available to read");
+                return Task::none();
+            };
+            
+            if text_to_read.trim().is_empty() {
+                warn!("Extracted text is empty, cannot read");
+                return Task::none();
+            }
+            
+            // Edited selections and OCR text can hold secrets as much as captures
+            if let Some(task) = ask_if_sensitive(app, &text_to_read, "ReadExtractedText") {
+                return task;
+            }
+            read_extracted_text(app, text_to_read)
+        }
+        Message::TrayEventReceived => {
+            // Poll for tray events and convert them to messages
+            if let Some(ref tray) = app.system_tray {
+                if let Some(event) = tray.try_recv() {
+                    let message = match event {
+                        crate::system::TrayEvent::ShowWindow => Message::ShowWindow,
+                        crate::system::TrayEvent::HideWindow => Message::HideWindow,
+                        crate::system::TrayEvent::ReadSelected => Message::ReadSelected,
+                        crate::system::TrayEvent::DictateNote => Message::ToggleDictation,
+                        crate::system::TrayEvent::PronunciationTrainer => Message::OpenPronunciationTrainer,
+                        crate::system::TrayEvent::ClipboardHistory => Message::OpenClipboardHistory,
+                        crate::system::TrayEvent::CommandPalette => Message::OpenCommandPalette,
+                        crate::system::TrayEvent::Quit => Message::Quit,
+                    };
+                    return Task::perform(async { message }, |msg| msg);
+                }
+            }
+            Task::none()
+        }
+        Message::ShowWindow => {
+            // Reopen the window if it was hidden/closed
+            if app.window_hidden || app.main_window_id.is_none() {
+                info!("Reopening main window from tray");
+                let (window_id, open_task) = open_main_window();
+                app.main_window_id = Some(window_id);
+                app.window_hidden = false;
+                return open_task;
+            }
+            Task::none()
+        }
+        Message::HideWindow => {
+            // Close the window (user can reopen from tray)
+            if let Some(window_id) = app.main_window_id {
+                info!("Hiding main window to tray");
+                app.window_hidden = true;
+                return window::close(window_id);
+            }
+            Task::none()
+        }
+        Message::ReadSelected => {
+            info!("Read Selected triggered from tray menu");
+            if app.edit_before_reading {
+                return fetch_selected_text_then(app, "tray menu", Message::SelectedTextForEditing);
+            }
+            // Ensure window is visible when reading
+            let fetch_task = fetch_selected_text_task(app, "tray menu");
+            if app.window_hidden || app.main_window_id.is_none() {
+                // Show window first, then fetch text
+                let (window_id, open_task) = open_main_window();
+                app.main_window_id = Some(window_id);
+                app.window_hidden = false;
+                return Task::batch([open_task, fetch_task]);
+            }
+            fetch_task
+        }
+        Message::ToggleDictation => {
+            if let Some(recorder) = app.dictation_recorder.take() {
+                let samples = match recorder.stop(system::WHISPER_SAMPLE_RATE) {
+                    Ok(samples) => samples,
+                    Err(e) => {
+                        warn!(error = %e, "Dictation recording unusable");
+                        app.status_text = Some(format!("Dictation failed: {}", e));
+                        return Task::none();
+                    }
+                };
+                transition(app, Event::Load("Transcribing note...".to_string()));
+                return Task::perform(
+                    async move {
+                        tokio::task::spawn_blocking(move || system::transcribe(&samples))
+                            .await
+                            .unwrap_or_else(|e| Err(format!("Task join error: {}", e)))
+                    },
+                    Message::DictationTranscribed,
+                );
+            }
+
+            // Stop any reading so it doesn't end up in the recording
             if let Some(ref mut provider) = app.provider {
                 if let Err(e) = provider.stop() {
-                    error!(error = %e, "Failed to stop playback");
+                    error!(error = %e, "Failed to stop playback before dictation");
                 }
+                transition(app, Event::Stop);
             }
-            app.playback_state = PlaybackState::Stopped;
-            app.progress = 0.0;
-            app.frequency_bands = vec![0.0; NUM_BANDS];
-            clear_loading_state(app);
-            info!("Playback stopped, closing main window");
-            window::latest().and_then(window::close)
-        }
-        Message::Tick => {
-            // Handle loading animation (for TTS or voice downloads)
-            if app.is_loading || app.downloading_voice.is_some() {
-                app.loading_animation_time += 0.15; // Increment animation time (faster animation)
-                if app.loading_animation_time > std::f32::consts::PI * 2.0 {
-                    app.loading_animation_time -= std::f32::consts::PI * 2.0;
+
+            match system::Recorder::start() {
+                Ok(recorder) => {
+                    app.dictation_recorder = Some(recorder);
+                    app.status_text = Some("Recording... (Dictate Note again to stop)".to_string());
                 }
-                
-                // Generate animated bar values using sine waves (only for TTS loading, not voice downloads)
-                if app.is_loading {
-                    // Creates a smooth wave that travels across the bars
-                    app.frequency_bands = (0..NUM_BANDS)
-                        .map(|i| {
-                            // Create a traveling wave effect
-                            let position = i as f32 / NUM_BANDS as f32;
-                            let wave = (app.loading_animation_time * 2.0 + position * std::f32::consts::PI * 2.0).sin();
-                            // Add some variation with a secondary wave
-                            let secondary = (app.loading_animation_time * 1.5 + position * std::f32::consts::PI * 3.0).sin() * 0.3;
-                            // Normalize to 0.0-1.0 range with some minimum height
-                            ((wave + secondary) * 0.4 + 0.5).clamp(0.2, 1.0)
-                        })
-                        .collect();
+                Err(e) => {
+                    error!(error = %e, "Failed to start dictation");
+                    app.status_text = Some(format!("Dictation failed: {}", e));
+                }
+            }
+
+            if app.window_hidden || app.main_window_id.is_none() {
+                let (window_id, open_task) = open_main_window();
+                app.main_window_id = Some(window_id);
+                app.window_hidden = false;
+                return open_task;
+            }
+            Task::none()
+        }
+        Message::DictationTranscribed(result) => {
+            finish_loading(app);
+            let transcript = match result {
+                Ok(raw) => system::clean_transcript(&raw),
+                Err(e) => {
+                    error!(error = %e, "Dictation transcription failed");
+                    app.status_text = Some("Transcription failed".to_string());
+                    app.error_message = Some(e);
+                    return Task::none();
                 }
-            } else if let Some(ref provider) = app.provider {
-                app.progress = provider.get_progress();
-                app.frequency_bands = provider.get_frequency_bands(NUM_BANDS);
+            };
+            if transcript.is_empty() {
+                info!("Dictation transcript is empty");
+                app.status_text = Some("No speech detected".to_string());
+                return Task::none();
+            }
 
-                if !provider.is_playing() && !provider.is_paused() {
-                    info!("Playback finished, stopping and closing window");
-                    app.playback_state = PlaybackState::Stopped;
-                    return window::latest().and_then(window::close);
+            info!(bytes = transcript.len(), "Dictation transcribed");
+            // Show the note in the extracted text dialog, which offers Read and Copy
+            app.extracted_text_editor = Some(iced::widget::text_editor::Content::with_text(&transcript));
+            app.extracted_text = Some(transcript);
+            app.capture_app = None;
+            if app.extracted_text_dialog_window_id.is_none() {
+                let (window_id, task) = open_extracted_text_window(app);
+                app.extracted_text_dialog_window_id = Some(window_id);
+                return task;
+            }
+            Task::none()
+        }
+        Message::OpenPronunciationTrainer => {
+            if app.trainer_window_id.is_some() {
+                debug!("Pronunciation trainer already open, ignoring request");
+                return Task::none();
+            }
+            if app.trainer_sentence.is_empty() {
+                if let Some(ref text) = app.extracted_text {
+                    app.trainer_sentence = text.lines().next().unwrap_or_default().trim().to_string();
                 }
-            } else {
-                trace!("Tick received with no active provider");
             }
+            debug!("Opening pronunciation trainer window");
+            let (window_id, task) = window::open(window::Settings {
+                size: Size::new(640.0, 420.0),
+                resizable: true,
+                decorations: true,
+                transparent: false,
+                visible: true,
+                position: window::Position::Centered,
+                ..Default::default()
+            });
+            app.trainer_window_id = Some(window_id);
+            task.map(Message::WindowOpened)
+        }
+        Message::ClosePronunciationTrainer => {
+            app.trainer_recorder = None;
+            close_window_if_some(app.trainer_window_id.take())
+        }
+        Message::TrainerSentenceChanged(sentence) => {
+            if sentence != app.trainer_sentence {
+                app.trainer_reference = None;
+                app.trainer_attempt = None;
+            }
+            app.trainer_sentence = sentence;
             Task::none()
         }
-        Message::Settings => {
-            if app.settings_window_id.is_some() {
-                debug!("Settings window already open, ignoring request");
+        Message::TrainerListen => {
+            let sentence = app.trainer_sentence.trim().to_string();
+            if sentence.is_empty() || matches!(app.activity, Activity::Loading { .. }) || app.trainer_recorder.is_some() {
                 return Task::none();
             }
-            
-            debug!("Settings clicked");
-            let (window_id, task) = open_settings_window();
-            debug!(?window_id, "Opening settings window");
-            app.settings_window_id = Some(window_id);
-            app.show_settings_modal = true;
-            task
+            info!(bytes = sentence.len(), "Pronunciation trainer: playing sentence");
+            app.trainer_awaiting_reference = true;
+            // Practice sentences are read verbatim, without Natural Reading
+            transition(app, Event::Load("Synthesizing voice...".to_string()));
+            initialize_tts_async(app.selected_backend, sentence, "PronunciationTrainer", app.selected_polly_voice.clone())
         }
-        Message::CloseSettings => {
-            app.show_settings_modal = false;
-            close_window_if_some(app.settings_window_id.take())
-        }
-        Message::ProviderSelected(backend) => {
-            info!(?backend, "TTS provider selected");
-            app.selected_backend = backend;
-            
-            // Check AWS credentials if AWS Polly is selected
-            if backend == TTSBackend::AwsPolly {
-                match PollyTTSProvider::check_credentials() {
-                    Ok(()) => {
-                        app.error_message = None;
-                        app.polly_error_message = None; // Clear Polly error when credentials are valid
-                        info!("AWS credentials found");
-                        // Fetch AWS voices if not already loaded
-                        if app.polly_voices.is_none() {
-                            return Task::perform(
-                                async {
-                                    crate::voices::aws::fetch_polly_voices().await
-                                },
-                                Message::PollyVoicesLoaded,
-                            );
-                        }
+        Message::TrainerToggleRecording => {
+            if let Some(recorder) = app.trainer_recorder.take() {
+                match recorder.stop(TRAINER_SAMPLE_RATE) {
+                    Ok(samples) => {
+                        let trimmed = system::trim_silence(&samples);
+                        info!(samples = trimmed.len(), "Pronunciation attempt recorded");
+                        app.trainer_attempt = Some(trimmed.to_vec());
                     }
                     Err(e) => {
-                        app.error_message = Some(e);
-                        warn!("AWS credentials not found when selecting AWS Polly");
-                        // Clear voices if credentials are not available
-                        app.polly_voices = None;
-                        app.polly_error_message = None; // Don't show service error if credentials are missing
+                        warn!(error = %e, "Pronunciation attempt unusable");
+                        app.trainer_attempt = None;
                     }
                 }
-            } else {
-                // Clear error message when switching to Piper
-                app.error_message = None;
-                app.polly_error_message = None;
+                return Task::none();
+            }
+
+            if let Some(ref mut provider) = app.provider {
+                if let Err(e) = provider.stop() {
+                    error!(error = %e, "Failed to stop playback before recording");
+                }
+                transition(app, Event::Stop);
+            }
+            match system::Recorder::start() {
+                Ok(recorder) => {
+                    app.trainer_recorder = Some(recorder);
+                    app.trainer_attempt = None;
+                }
+                Err(e) => {
+                    error!(error = %e, "Failed to start recording pronunciation attempt");
+                    app.error_message = Some(format!("Recording failed: {}", e));
+                }
             }
-            
-            // Persist the selected backend so future runs remember the choice.
-            config::save_voice_provider(backend);
-            Task::none()
-        }
-        Message::LogLevelSelected(level) => {
-            info!(?level, "Log level selected");
-            app.log_level = level;
-            // Persist the selected log level so future runs remember the choice.
-            config::save_log_level(level);
-            // Update runtime log level
-            logging::set_verbosity(level);
             Task::none()
         }
-        Message::TextCleanupToggled(enabled) => {
-            info!(?enabled, "Natural Reading toggled");
-            app.text_cleanup_enabled = enabled;
-            // Persist the setting
-            config::save_text_cleanup_enabled(enabled);
-            Task::none()
+        Message::Quit => {
+            info!("Quitting application from tray menu");
+            leave_document(app);
+            record_listening(app);
+            if let Err(e) = crate::sync::sync_now() {
+                warn!(error = %e, "Sync on quit failed");
+            }
+            iced::exit()
         }
-        Message::WindowOpened(id) => {
-            info!(?id, "Window opened event received");
-            if app.main_window_id.is_none() {
-                app.main_window_id = Some(id);
-                info!("Main window ID set - UI is now visible");
-                
-                // If we already have pending text (from async fetch), initialize TTS now
-                if let Some(text) = app.pending_text.take() {
-                    return process_text_for_tts(app, text, "WindowOpened");
+        Message::HotkeyPressed => {
+            // Check if hotkey event actually occurred
+            if let Some(ref mut hotkey_manager) = app.hotkey_manager {
+                if let Some(action) = hotkey_manager.try_recv() {
+                    if action == system::HotkeyAction::PanicStop {
+                        // The audio is already silent; stop the reading like the Stop button
+                        info!("Panic stop hotkey pressed");
+                        return Task::perform(async { Message::Stop }, |msg| msg);
+                    }
+                    info!(?action, "Hotkey pressed - triggering read");
+                    if action == system::HotkeyAction::ReadClipboardImage {
+                        return read_clipboard_image_task(app.private_screenshots);
+                    }
+                    let edit_first = action == system::HotkeyAction::EditFirst || app.edit_before_reading;
+                    if app.source_priority == SourcePriority::AlwaysAsk {
+                        debug!("Asking which source to read");
+                        return open_capture_source_prompt(app, true, edit_first);
+                    }
+                    return hotkey_read(app, edit_first, app.source_priority.sources());
                 }
-            } else {
-                debug!(?id, "Window opened but main window ID already set");
             }
-            app.current_window_id = Some(id);
             Task::none()
         }
-        Message::WindowClosed(id) => {
-            debug!(?id, "Window closed");
-            if app.settings_window_id == Some(id) {
-                app.settings_window_id = None;
-                app.show_settings_modal = false;
-            }
-            if app.voice_selection_window_id == Some(id) {
-                app.voice_selection_window_id = None;
-            }
-            if app.polly_info_window_id == Some(id) {
-                app.polly_info_window_id = None;
-            }
-            if app.screenshot_window_id == Some(id) {
-                app.screenshot_window_id = None;
+        Message::CaptureSourceChosen(source) => {
+            info!(?source, "Capture source chosen");
+            let close_task = close_window_if_some(app.capture_source_window_id.take());
+            let read_task = match source {
+                CaptureSource::Ocr => Task::perform(async { Message::ScreenshotRequested }, |msg| msg),
+                _ => hotkey_read(app, app.capture_source_edit_first, source.only()),
+            };
+            Task::batch([close_task, read_task])
+        }
+        Message::CloseCaptureSourcePrompt => {
+            close_window_if_some(app.capture_source_window_id.take())
+        }
+        Message::OfferOcrCapture => {
+            info!("No text in the selection or clipboard, offering to capture a screen region");
+            // Like an empty capture, don't leave an empty main window behind
+            let close_task = match app.main_window_id {
+                Some(window_id) if app.provider.is_none() => window::close(window_id),
+                _ => Task::none(),
+            };
+            Task::batch([close_task, open_capture_source_prompt(app, false, false)])
+        }
+        Message::ClipboardImageTextExtracted(result) => match result {
+            Ok(text) => {
+                info!(bytes = text.len(), "Reading text of the clipboard image");
+                read_in_main_window(app, text)
             }
-            if app.ocr_info_window_id == Some(id) {
-                app.ocr_info_window_id = None;
+            Err(e) => {
+                warn!(error = %e, "Failed to read the clipboard image");
+                app.status_text = Some(if e.contains("No text found") {
+                    "No text found in the clipboard image".to_string()
+                } else {
+                    e
+                });
+                show_main_window(app)
             }
-            if app.text_cleanup_info_window_id == Some(id) {
-                app.text_cleanup_info_window_id = None;
+        },
+        Message::HotkeyTextCaptured(text, mic_in_use) => match text {
+            Some(text) if mic_in_use => {
+                app.held_readings.push(text);
+                info!(held = app.held_readings.len(), "Microphone in use, holding hotkey reading");
+                app.status_text = Some(format!(
+                    "Microphone in use: {} reading(s) held until it is free",
+                    app.held_readings.len()
+                ));
+                show_main_window(app)
             }
-            if app.extracted_text_dialog_window_id == Some(id) {
-                app.extracted_text_dialog_window_id = None;
-                app.extracted_text = None;
-                app.extracted_text_editor = None;
+            Some(text) if !app.held_readings.is_empty() => {
+                // Read what was held back first
+                app.held_readings.push(text);
+                let text = std::mem::take(&mut app.held_readings).join("\n\n");
+                read_in_main_window(app, text)
             }
-            if app.current_window_id == Some(id) {
-                app.current_window_id = None;
+            Some(text) if app.preview_before_reading => open_preview(app, text),
+            text => {
+                let read_task = Task::perform(async move { Message::SelectedTextFetched(text) }, |msg| msg);
+                Task::batch([show_main_window(app), read_task])
             }
-            // Hide window instead of exiting if system tray is available
-            if app.main_window_id == Some(id) {
-                if app.system_tray.is_some() {
-                    info!("Main window closed, hiding to system tray");
-                    app.window_hidden = true;
-                    // Don't clear main_window_id so we know to reopen it later
-                    // The window is already closed by the user, so we just mark it as hidden
+        },
+        Message::PreviewBeforeReadingToggled(enabled) => {
+            info!(enabled, "Preview before reading toggled");
+            app.preview_before_reading = enabled;
+            config::save_preview_before_reading(enabled);
+            Task::none()
+        }
+        Message::PreviewTimedOut(window_id) if app.preview_window_id != Some(window_id) => {
+            trace!("Preview timeout for a dismissed popup, ignoring");
+            Task::none()
+        }
+        Message::PreviewRead | Message::PreviewTimedOut(_) => {
+            let close_task = close_window_if_some(app.preview_window_id.take());
+            let Some(text) = app.preview_text.take() else {
+                return close_task;
+            };
+            info!(bytes = text.len(), "Reading previewed capture");
+            Task::batch([close_task, read_in_main_window(app, text)])
+        }
+        Message::PreviewEdit => {
+            let close_task = close_window_if_some(app.preview_window_id.take());
+            let Some(text) = app.preview_text.take() else {
+                return close_task;
+            };
+            debug!("Opening previewed capture in extracted text editor");
+            Task::batch([close_task, open_text_in_editor(app, text)])
+        }
+        Message::PreviewCancel => {
+            debug!("Preview dismissed, discarding capture");
+            app.preview_text = None;
+            close_window_if_some(app.preview_window_id.take())
+        }
+        Message::EditBeforeReadingToggled(enabled) => {
+            info!(enabled, "Edit before reading toggled");
+            app.edit_before_reading = enabled;
+            config::save_edit_before_reading(enabled);
+            Task::none()
+        }
+        Message::PanicStopHotkeyToggled(enabled) => {
+            info!(enabled, "Panic stop hotkey toggled");
+            app.panic_stop_hotkey = enabled;
+            if let Some(ref mut hotkey_manager) = app.hotkey_manager {
+                let result = if enabled {
+                    hotkey_manager.register_panic_stop()
                 } else {
-                    info!("Main window closed, exiting (no system tray)");
-                    return iced::exit();
+                    hotkey_manager.unregister_panic_stop()
+                };
+                if let Err(e) = result {
+                    error!(error = %e, "Failed to update the panic stop hotkey");
+                    app.error_message = Some(e.to_string());
                 }
             }
+            config::save_panic_stop_hotkey(enabled);
             Task::none()
         }
-        Message::SelectedTextFetched(text) => {
-            info!("Selected text fetched asynchronously");
-            if let Some(ref t) = text {
-                info!(bytes = t.len(), preview = %t.chars().take(50).collect::<String>(), "Text selected");
-            } else {
-                info!("No text selected - app will wait for text or close");
+        Message::HotkeyEditVariantToggled(enabled) => {
+            info!(enabled, "Edit-first hotkey toggled");
+            app.hotkey_edit_variant = enabled;
+            if let Some(ref mut hotkey_manager) = app.hotkey_manager {
+                hotkey_manager.set_variants(app.hotkey_edit_variant, app.hotkey_image_variant);
             }
-            
-            // Initialize TTS if window is already open, otherwise store for later
-            if let Some(window_id) = app.main_window_id {
-                if let Some(text) = text {
-                    return process_text_for_tts(app, text, "SelectedTextFetched");
-                }
-                warn!("No text selected - closing window");
-                return window::close(window_id);
+            config::save_hotkey_edit_variant(enabled);
+            Task::none()
+        }
+        Message::HotkeyImageVariantToggled(enabled) => {
+            info!(enabled, "Clipboard image hotkey toggled");
+            app.hotkey_image_variant = enabled;
+            if let Some(ref mut hotkey_manager) = app.hotkey_manager {
+                hotkey_manager.set_variants(app.hotkey_edit_variant, app.hotkey_image_variant);
             }
-            
-            // Window not ready yet, store text for WindowOpened handler
-            app.pending_text = text;
-            trace!("Window not ready yet, text stored for later initialization");
+            config::save_hotkey_image_variant(enabled);
             Task::none()
         }
-        Message::TextCleanupResponse(result) => {
-            match result {
-                Ok(cleaned_text) => {
-                    info!(bytes = cleaned_text.len(), "Natural Reading successful, initializing TTS");
-                    // Update status to show we're now synthesizing
-                    app.status_text = Some("Synthesizing voice...".to_string());
-                    return initialize_tts_async(app.selected_backend, cleaned_text, "TextCleanupResponse", app.selected_polly_voice.clone());
-                }
-                Err(e) => {
-                    error!(error = %e, "Natural Reading service failed");
-                    clear_loading_state(app);
-                    return open_settings_if_needed(app, e);
-                }
+        Message::SelectedTextForEditing(text) => match text {
+            Some(text) => {
+                debug!(bytes = text.len(), "Opening selected text in extracted text editor");
+                app.capture_app = take_capture_app();
+                open_text_in_editor(app, text)
+            }
+            None => {
+                info!("No text selected to edit");
+                Task::none()
+            }
+        },
+        Message::CheckMicrophone => Task::perform(
+            async {
+                tokio::task::spawn_blocking(system::microphone_in_use)
+                    .await
+                    .unwrap_or(false)
+            },
+            Message::MicrophoneChecked,
+        ),
+        Message::MicrophoneChecked(in_use) => {
+            if in_use || app.held_readings.is_empty() {
+                return Task::none();
             }
+            info!(held = app.held_readings.len(), "Microphone free, reading held text");
+            app.status_text = None;
+            let text = std::mem::take(&mut app.held_readings).join("\n\n");
+            process_captured_text(app, text, "MicrophoneChecked")
         }
-        Message::TTSInitialized(result) => {
-            // Clear loading state regardless of result
-            clear_loading_state(app);
+        Message::HotkeyConfigChanged(config) => {
+            // Ignore if hotkeys are disabled due to Wayland/Hyprland
+            if app.hotkeys_disabled_wayland {
+                return Task::none();
+            }
             
-            match result {
-                Ok(()) => {
-                    // Retrieve provider from static storage
-                    let Ok(mut guard) = PENDING_PROVIDER.lock() else {
-                        error!("Failed to lock PENDING_PROVIDER mutex");
-                        app.error_message = Some("Internal error: mutex lock failed".to_string());
-                        return Task::none();
-                    };
-                    
-                    let Some(send_provider) = guard.take() else {
-                        error!("TTS initialization succeeded but no provider found in storage");
-                        app.error_message = Some("Internal error: provider not found".to_string());
-                        return Task::none();
-                    };
-                    
-                    app.provider = Some(send_provider.0);
-                    app.playback_state = PlaybackState::Playing;
-                    app.error_message = None;
-                    info!("TTS provider initialized and playback started");
-                }
-                Err(e) => {
-                    error!(error = %e, "TTS initialization failed");
-                    
-                    // For "No audio data generated" errors, show in status text instead of opening settings
-                    if e.contains("No audio data generated by piper") {
-                        const DEFAULT_MSG: &str = "Voice gen. failed: Text too short or invalid";
-                        
-                        // Extract stderr info if available, otherwise use default message
-                        let user_message = if let Some(start) = e.find("stderr:") {
-                            let stderr_content = e[start + 7..].trim();
-                            if !stderr_content.is_empty() {
-                                format!("Voice gen. failed: {}", stderr_content)
-                            } else {
-                                DEFAULT_MSG.to_string()
-                            }
-                        } else {
-                            DEFAULT_MSG.to_string()
-                        };
-                        
-                        app.status_text = Some(user_message);
-                        info!("TTS error shown in status text instead of settings window");
-                        return Task::none();
-                    }
-                    
-                    // For other errors, use the existing behavior (open settings window)
-                    return open_settings_if_needed(app, e);
+            info!("Hotkey configuration changed");
+            app.hotkey_config = config.clone();
+            
+            // Update hotkey registration if enabled
+            if app.hotkey_enabled {
+                if let Some(ref mut hotkey_manager) = app.hotkey_manager {
+                    if let Err(e) = hotkey_manager.register(config) {
+                        error!(error = %e, "Failed to register new hotkey");
+                        app.error_message = Some(format!("Failed to register hotkey: {e}"));
+                    } else {
+                        info!("Hotkey re-registered successfully");
+                        app.error_message = None;
+                    }
                 }
             }
+            
+            crate::config::save_hotkey_config(&app.hotkey_config, app.hotkey_enabled);
             Task::none()
         }
-        Message::StartDrag => {
-            if let Some(id) = app.main_window_id {
-                window::drag(id)
-            } else {
-                Task::none()
+        Message::HotkeyToggled(enabled) => {
+            // Ignore if hotkeys are disabled due to Wayland/Hyprland
+            if app.hotkeys_disabled_wayland {
+                return Task::none();
             }
-        }
-        Message::VoicesJsonLoaded(result) => {
-            match result {
-                Ok(voices) => {
-                    info!(count = voices.len(), "Voices.json loaded successfully");
-                    app.voices = Some(voices);
-                }
-                Err(e) => {
-                    error!(error = %e, "Failed to load voices.json");
-                    // Show error to user in settings window if it's open
-                    if app.settings_window_id.is_some() {
-                        app.error_message = Some(format!("Failed to load voices: {}. Check your internet connection.", e));
+            
+            info!(enabled, "Hotkey toggled");
+            app.hotkey_enabled = enabled;
+            
+            if let Some(ref mut hotkey_manager) = app.hotkey_manager {
+                if enabled {
+                    if let Err(e) = hotkey_manager.register(app.hotkey_config.clone()) {
+                        error!(error = %e, "Failed to register hotkey");
+                        app.error_message = Some(format!("Failed to register hotkey: {e}"));
+                        app.hotkey_enabled = false; // Revert if registration failed
+                    } else {
+                        info!("Hotkey registered successfully");
+                        app.error_message = None;
                     }
+                } else if let Err(e) = hotkey_manager.unregister() {
+                    warn!(error = %e, "Failed to unregister hotkey");
+                } else {
+                    info!("Hotkey unregistered successfully");
                 }
             }
+            
+            crate::config::save_hotkey_config(&app.hotkey_config, app.hotkey_enabled);
             Task::none()
         }
-        Message::PollyVoicesLoaded(result) => {
-            match result {
-                Ok(voices) => {
-                    info!(count = voices.len(), "AWS Polly voices loaded successfully");
-                    app.polly_voices = Some(voices);
-                    app.polly_error_message = None; // Clear error on success
-                }
-                Err(e) => {
-                    debug!(error = %e, "Failed to load AWS Polly voices (credentials may not be configured)");
-                    app.polly_voices = None;
-                    // Show error for service errors (e.g., clock skew, network issues) but not credential errors
-                    let error_lower = e.to_lowercase();
-                    let is_credential_error = error_lower.contains("credentials")
-                        || error_lower.contains("authentication")
-                        || error_lower.contains("unauthorized");
-                    let is_service_error = error_lower.contains("service error")
-                        || error_lower.contains("network")
-                        || error_lower.contains("timeout")
-                        || error_lower.contains("clock");
-                    
-                    app.polly_error_message = if is_service_error || !is_credential_error {
-                        Some(e)
-                    } else {
-                        None
-                    };
-                }
+        Message::StartListeningForHotkey => {
+            // Ignore if hotkeys are disabled due to Wayland/Hyprland
+            if app.hotkeys_disabled_wayland {
+                return Task::none();
             }
+            
+            info!("Starting to listen for hotkey input");
+            app.listening_for_hotkey = true;
+            app.error_message = None; // Clear any previous errors
             Task::none()
         }
-        Message::OpenVoiceSelection(lang_code) => {
-            if app.voice_selection_window_id.is_some() {
-                debug!("Voice selection window already open, ignoring request");
+        Message::StopListeningForHotkey => {
+            info!("Stopped listening for hotkey input");
+            app.listening_for_hotkey = false;
+            app.error_message = None;
+            Task::none()
+        }
+        Message::HotkeyCaptured(key, modifiers) => {
+            // Ignore if hotkeys are disabled due to Wayland/Hyprland
+            if app.hotkeys_disabled_wayland {
+                app.listening_for_hotkey = false;
                 return Task::none();
             }
+            info!(?key, ?modifiers, "Hotkey combination captured");
             
-            debug!(language = %lang_code, "Opening voice selection window");
-            app.selected_language = Some(lang_code);
+            // Convert Iced key/modifiers to global_hotkey format
+            use crate::ui::settings::hotkeys::{iced_key_to_global_hotkey_code, iced_modifiers_to_global_hotkey_modifiers};
             
-            let (window_id, task) = window::open(window::Settings {
-                size: Size::new(400.0, 500.0), // 33% narrower: 600 * 0.67 ≈ 400
-                resizable: false,
-                decorations: false,
-                transparent: false,
-                visible: true,
-                position: window::Position::Centered,
-                ..Default::default()
-            });
-            app.voice_selection_window_id = Some(window_id);
-            task.map(Message::WindowOpened)
-        }
-        Message::CloseVoiceSelection => {
-            close_window_if_some(app.voice_selection_window_id.take())
-        }
-        Message::OpenPollyInfo => {
-            if app.polly_info_window_id.is_some() {
-                debug!("Polly info window already open, ignoring request");
+            let Some(code) = iced_key_to_global_hotkey_code(&key) else {
+                error!("Invalid key captured: {:?}", key);
+                app.error_message = Some("Invalid key. Please try again.".to_string());
+                app.listening_for_hotkey = false;
+                return Task::none();
+            };
+            
+            let gh_modifiers = iced_modifiers_to_global_hotkey_modifiers(modifiers);
+            
+            // Validate that we have at least one modifier
+            if gh_modifiers.is_empty() {
+                error!("No modifiers in captured hotkey");
+                app.error_message = Some("Hotkey must include at least one modifier (Ctrl/Cmd, Shift, or Alt).".to_string());
+                app.listening_for_hotkey = false;
                 return Task::none();
             }
             
-            debug!("Opening AWS Polly pricing info window");
-            let (window_id, task) = open_info_window(Size::new(500.0, 400.0));
-            app.polly_info_window_id = Some(window_id);
-            task
+            // Create new hotkey config
+            let new_config = crate::system::HotkeyConfig {
+                modifiers: gh_modifiers,
+                key: code,
+            };
+            
+            // Exit listening mode
+            app.listening_for_hotkey = false;
+            
+            // Update the hotkey configuration
+            // This will trigger HotkeyConfigChanged internally
+            app.hotkey_config = new_config.clone();
+            
+            // Update hotkey registration if enabled
+            if app.hotkey_enabled {
+                if let Some(ref mut hotkey_manager) = app.hotkey_manager {
+                    if let Err(e) = hotkey_manager.register(new_config.clone()) {
+                        error!(error = %e, "Failed to register new hotkey");
+                        app.error_message = Some(format!("Failed to register hotkey: {e}"));
+                    } else {
+                        info!("Hotkey registered successfully");
+                        app.error_message = None;
+                    }
+                }
+            }
+            
+            // Save to config
+            crate::config::save_hotkey_config(&app.hotkey_config, app.hotkey_enabled);
+            Task::none()
         }
-        Message::ClosePollyInfo => {
-            close_window_if_some(app.polly_info_window_id.take())
+        Message::LongTextActionSelected(action) => {
+            info!(?action, "Long text action selected");
+            app.long_text_action = action;
+            config::save_long_text_action(action);
+            Task::none()
         }
-        Message::OpenPollyPricingUrl => {
-            let url = "https://aws.amazon.com/polly/pricing/";
-            open_url(url);
-            info!("Opening AWS Polly pricing URL in browser");
+        Message::LongTextParagraphsChanged(paragraphs) => {
+            debug!(paragraphs, "Long text paragraphs changed");
+            app.long_text_paragraphs = paragraphs;
+            config::save_long_text_paragraphs(paragraphs);
             Task::none()
         }
-        Message::OCRBackendSelected(backend) => {
-            // Prevent selection of BetterOCR since it's not available yet
-            if backend == OCRBackend::BetterOCR {
-                debug!("Better OCR is not available yet, ignoring selection");
-                return Task::none();
+        Message::InterruptReadingsToggled(enabled) => {
+            info!(enabled, "Interrupting readings toggled");
+            app.interrupt_readings = enabled;
+            if !enabled {
+                drop_interrupted_reading(app);
             }
-            info!(?backend, "OCR backend selected");
-            app.selected_ocr_backend = backend;
-            config::save_ocr_backend(backend);
+            config::save_interrupt_readings(enabled);
             Task::none()
         }
-        Message::OpenOCRInfo => {
-            if app.ocr_info_window_id.is_some() {
-                debug!("OCR info window already open, ignoring request");
+        Message::ReadFirstParagraphs => {
+            let Some(text) = app.long_text_pending.take() else {
+                warn!("ReadFirstParagraphs received with no pending text");
                 return Task::none();
-            }
-            
-            debug!("Opening Better OCR info window");
-            let (window_id, task) = open_info_window(Size::new(500.0, 300.0));
-            app.ocr_info_window_id = Some(window_id);
-            task
+            };
+            let trimmed = text::first_paragraphs(&text, app.long_text_paragraphs);
+            info!(
+                paragraphs = app.long_text_paragraphs,
+                bytes = trimmed.len(),
+                "Reading first paragraphs of long text"
+            );
+            let close_task = close_window_if_some(app.long_text_dialog_window_id.take());
+            Task::batch([close_task, process_text_for_tts(app, trimmed, "ReadFirstParagraphs")])
         }
-        Message::CloseOCRInfo => {
-            close_window_if_some(app.ocr_info_window_id.take())
+        Message::ReadAllText => {
+            let Some(text) = app.long_text_pending.take() else {
+                warn!("ReadAllText received with no pending text");
+                return Task::none();
+            };
+            info!(bytes = text.len(), "Reading all of long text");
+            let close_task = close_window_if_some(app.long_text_dialog_window_id.take());
+            Task::batch([close_task, process_text_for_tts(app, text, "ReadAllText")])
         }
-        Message::OpenTextCleanupInfo => {
-            if app.text_cleanup_info_window_id.is_some() {
-                debug!("Natural Reading info window already open, ignoring request");
+        Message::OpenLongTextInEditor => {
+            let Some(text) = app.long_text_pending.take() else {
+                warn!("OpenLongTextInEditor received with no pending text");
                 return Task::none();
+            };
+            debug!("Opening long text in extracted text editor");
+            app.extracted_text_editor = Some(iced::widget::text_editor::Content::with_text(&text));
+            app.extracted_text = Some(text);
+            let close_task = close_window_if_some(app.long_text_dialog_window_id.take());
+            if app.extracted_text_dialog_window_id.is_some() {
+                return close_task;
             }
-            
-            debug!("Opening Natural Reading info window");
-            let (window_id, task) = open_info_window(Size::new(500.0, 300.0));
-            app.text_cleanup_info_window_id = Some(window_id);
-            task
+            let (window_id, open_task) = open_extracted_text_window(app);
+            app.extracted_text_dialog_window_id = Some(window_id);
+            Task::batch([close_task, open_task])
         }
-        Message::CloseTextCleanupInfo => {
-            close_window_if_some(app.text_cleanup_info_window_id.take())
+        Message::ColorPresetSelected(preset) => {
+            info!(?preset, "Color scheme selected");
+            app.color_preset = preset;
+            crate::styles::set_color_scheme(crate::styles::ColorScheme::resolve(preset, &app.custom_colors));
+            config::save_color_scheme(preset, &app.custom_colors);
+            Task::none()
         }
-        Message::VoiceSelected(voice_key) => {
-            info!(voice = %voice_key, "Voice selected");
-            match app.selected_backend {
-                TTSBackend::Piper => {
-                    app.selected_voice = Some(voice_key.clone());
-                    config::save_selected_voice(voice_key);
-                }
-                TTSBackend::AwsPolly => {
-                    app.selected_polly_voice = Some(voice_key.clone());
-                    config::save_selected_polly_voice(voice_key);
-                }
-            }
-            close_window_if_some(app.voice_selection_window_id.take())
+        Message::VisualizerStyleSelected(style) => {
+            info!(?style, "Visualizer style selected");
+            app.visualizer_style = style;
+            crate::providers::set_visualizer_style(style);
+            config::save_visualizer_style(style);
+            Task::none()
         }
-        Message::VoiceDownloadRequested(voice_key) => {
-            info!(voice = %voice_key, "Voice download requested");
-            
-            let voice_info = app.voices.as_ref()
-                .and_then(|voices| voices.get(&voice_key).cloned());
-            
-            if let Some(voice_info) = voice_info {
-                // Set downloading state
-                app.downloading_voice = Some(voice_key.clone());
-                set_loading_state(app, &format!("Downloading voice: {}...", voice_info.name));
-                
-                // Start async download
-                Task::perform(
-                    async move {
-                        use crate::voices::download;
-                        download::download_voice(&voice_key, &voice_info)
-                            .await
-                            .map(|_| voice_key)
-                    },
-                    Message::VoiceDownloaded,
-                )
-            } else {
-                error!(voice = %voice_key, "Voice not found in voices.json");
-                app.error_message = Some(format!("Voice {} not found", voice_key));
-                Task::none()
-            }
+        Message::CustomColorChanged(role, value) => {
+            app.custom_colors.set(role, value);
+            crate::styles::set_color_scheme(crate::styles::ColorScheme::resolve(app.color_preset, &app.custom_colors));
+            config::save_color_scheme(app.color_preset, &app.custom_colors);
+            Task::none()
         }
-        Message::VoiceDownloaded(result) => {
-            clear_loading_state(app);
-            app.downloading_voice = None;
-            match result {
-                Ok(voice_key) => {
-                    info!(voice = %voice_key, "Voice downloaded successfully");
-                    app.status_text = Some("Voice downloaded successfully".to_string());
-                    // Auto-select the downloaded voice
-                    app.selected_voice = Some(voice_key.clone());
-                    config::save_selected_voice(voice_key);
-                }
-                Err(e) => {
-                    error!(error = %e, "Voice download failed");
-                    app.error_message = Some(format!("Download failed: {}", e));
-                }
-            }
+        Message::ReadingFontSelected(font) => {
+            info!(?font, "Reading font selected");
+            app.reading_style.font = font;
+            config::save_reading_style(&app.reading_style);
+            Task::none()
+        }
+        Message::ReadingLineSpacingChanged(spacing) => {
+            app.reading_style.line_spacing = spacing;
+            config::save_reading_style(&app.reading_style);
             Task::none()
         }
-        Message::ScreenshotRequested => {
-            info!("Screenshot button clicked, starting region selection");
-            // Spawn async task to capture screenshot region
+        Message::ReadingTintSelected(tint) => {
+            info!(?tint, "Reading background tint selected");
+            app.reading_style.tint = tint;
+            config::save_reading_style(&app.reading_style);
+            Task::none()
+        }
+        Message::FileDropped(id, path) => {
+            if app.main_window_id != Some(id) {
+                debug!(?id, path = %path.display(), "File dropped outside the main window, ignoring");
+                return Task::none();
+            }
+            if !text::chapters::is_readable_file(&path) {
+                warn!(path = %path.display(), "Dropped file is not a text, Markdown or HTML file");
+                app.error_message = Some("Drop a .txt, .md or .html file to read it".to_string());
+                return Task::none();
+            }
+            info!(path = %path.display(), "File dropped, loading text");
+            transition(app, Event::Load("Loading file...".to_string()));
             Task::perform(
-                async {
-                    debug!("Starting async screenshot capture task");
-                    // Use spawn_blocking for the blocking shell command
-                    let result = tokio::task::spawn_blocking(|| {
-                        debug!("Executing capture_region in blocking thread");
-                        crate::system::capture_region()
-                    })
-                    .await;
-                    debug!("Screenshot capture task completed");
-                    result.unwrap_or_else(|e| {
-                        tracing::warn!(error = %e, "Failed to join blocking task for screenshot capture");
-                        Err(format!("Task join error: {}", e))
-                    })
+                async move {
+                    tokio::task::spawn_blocking(move || text::chapters::document_text(&path))
+                        .await
+                        .unwrap_or_else(|e| Err(format!("Failed to load file: {e}")))
                 },
-                Message::ScreenshotCaptured,
+                Message::DroppedFileLoaded,
             )
         }
-        Message::ScreenshotCaptured(result) => {
+        Message::DroppedFileLoaded(result) => {
+            finish_loading(app);
             match result {
-                Ok(file_path) => {
-                    info!(path = %file_path, "Screenshot captured successfully");
-                    app.screenshot_path = Some(file_path.clone());
-                    app.status_text = Some("Extracting text from image...".to_string());
-                    
-                    // Automatically extract text from the screenshot
-                    let file_path_clone = file_path.clone();
-                    Task::perform(
-                        async move {
-                            debug!("Starting async text extraction from screenshot");
-                            // Use spawn_blocking for the blocking shell command
-                            let result = tokio::task::spawn_blocking(move || {
-                                debug!("Executing extract_text_from_image in blocking thread");
-                                crate::system::extract_text_from_image(&file_path_clone)
-                            })
-                            .await;
-                            debug!("Text extraction task completed");
-                            result.unwrap_or_else(|e| {
-                                tracing::warn!(error = %e, "Failed to join blocking task for text extraction");
-                                Err(format!("Task join error: {}", e))
-                            })
-                        },
-                        Message::ScreenshotTextExtracted,
-                    )
+                Ok(text) if text.trim().is_empty() => {
+                    warn!("Dropped file has no text to read");
+                    app.error_message = Some("The dropped file has no text to read".to_string());
+                    Task::none()
+                }
+                Ok(text) => {
+                    app.error_message = None;
+                    read_document(app, crate::history::file_document_key(&text), text, "FileDropped")
                 }
                 Err(e) => {
-                    // Don't show error for user cancellation
-                    if e.contains("cancelled") {
-                        debug!("User cancelled screenshot selection");
-                    } else {
-                        error!(error = %e, "Screenshot capture failed");
-                        app.error_message = Some(format!("Screenshot failed: {}", e));
-                    }
+                    error!(error = %e, "Failed to load dropped file");
+                    app.error_message = Some(e);
                     Task::none()
                 }
             }
         }
-        Message::ScreenshotTextExtracted(result) => {
-            match result {
-                Ok(extracted_text) => {
-                    info!(bytes = extracted_text.len(), "Text extracted from screenshot successfully");
-                    info!(
-                        text = %extracted_text,
-                        "Extracted text from screenshot"
-                    );
-                    app.status_text = Some("Text extracted from image".to_string());
-                    
-                    // Store extracted text and initialize editor content
-                    app.extracted_text = Some(extracted_text.clone());
-                    app.extracted_text_editor = Some(iced::widget::text_editor::Content::with_text(&extracted_text));
-                    
-                    // Open the extracted text dialog window
-                    if app.extracted_text_dialog_window_id.is_none() {
-                        let (window_id, task) = window::open(window::Settings {
-                            size: Size::new(600.0, 400.0),
-                            resizable: true,
-                            decorations: true,
-                            transparent: false,
-                            visible: true,
-                            position: window::Position::Centered,
-                            ..Default::default()
-                        });
-                        app.extracted_text_dialog_window_id = Some(window_id);
-                        return task.map(Message::WindowOpened);
+        Message::ReadAltTextToggled(enabled) => {
+            info!(enabled, "Read image alt text toggled");
+            app.read_alt_text = enabled;
+            config::save_read_alt_text(enabled);
+            Task::none()
+        }
+        Message::ReadMathToggled(enabled) => {
+            info!(enabled, "Read math notation toggled");
+            app.read_math = enabled;
+            config::save_read_math(enabled);
+            Task::none()
+        }
+        Message::ListProsodyToggled(enabled) => {
+            info!(enabled, "List prosody toggled");
+            app.list_prosody = enabled;
+            config::save_list_prosody(enabled);
+            Task::none()
+        }
+        Message::TextProfileSelected(profile) => {
+            info!(?profile, "Text profile selected");
+            app.text_profile = profile;
+            config::save_text_profile(profile);
+            Task::none()
+        }
+        Message::DeferWhileMicActiveToggled(enabled) => {
+            info!(enabled, "Holding hotkey readings during calls toggled");
+            app.defer_while_mic_active = enabled;
+            config::save_defer_while_mic_active(enabled);
+            if !enabled {
+                app.held_readings.clear();
+            }
+            Task::none()
+        }
+        Message::PauseWhileTypingToggled(enabled) => {
+            info!(enabled, "Pausing while typing toggled");
+            app.pause_while_typing = enabled;
+            config::save_pause_while_typing(enabled);
+            Task::none()
+        }
+        Message::TypingResumeDelayChanged(secs) => {
+            debug!(secs, "Typing resume delay changed");
+            app.typing_resume_secs = secs;
+            config::save_typing_resume_secs(secs);
+            Task::none()
+        }
+        Message::CheckTyping => {
+            let Some(idle) = system::time_since_typing() else {
+                return Task::none();
+            };
+            // A check from long ago belongs to an earlier reading; keys pressed
+            // before the first check (like the hotkey that started this one) do not count
+            let last_check = app
+                .typing_checked_at
+                .replace(Instant::now())
+                .filter(|checked_at| checked_at.elapsed() < TYPING_CHECK_INTERVAL * 3);
+            if app.activity != Activity::Paused {
+                app.paused_for_typing = false;
+            }
+            let Some(ref mut provider) = app.provider else {
+                return Task::none();
+            };
+            match app.activity {
+                Activity::Playing if last_check.is_some_and(|checked_at| idle < checked_at.elapsed()) => {
+                    if let Err(e) = provider.pause() {
+                        error!(error = %e, "Failed to pause playback for typing");
+                    } else {
+                        transition(app, Event::Pause);
+                        app.paused_for_typing = true;
+                        info!("Typing started, playback paused");
                     }
                 }
-                Err(e) => {
-                    warn!(error = %e, "Failed to extract text from screenshot");
-                    // Don't show error if no text was found (image might not contain text)
-                    if e.contains("No text found") {
-                        app.status_text = Some("No text found in image".to_string());
+                Activity::Paused
+                    if app.paused_for_typing && idle >= std::time::Duration::from_secs(app.typing_resume_secs.into()) =>
+                {
+                    if let Err(e) = provider.resume() {
+                        error!(error = %e, "Failed to resume playback after typing");
                     } else {
-                        app.error_message = Some(format!("Text extraction failed: {}", e));
-                        app.status_text = Some("Text extraction failed".to_string());
+                        transition(app, Event::Play);
+                        info!(idle_secs = idle.as_secs(), "Typing stopped, playback resumed");
                     }
+                    app.paused_for_typing = false;
                 }
+                _ => {}
             }
             Task::none()
         }
-        Message::OpenScreenshotViewer => {
-            if app.screenshot_window_id.is_some() {
-                debug!("Screenshot window already open, ignoring request");
-                return Task::none();
-            }
-            
-            if app.screenshot_path.is_none() {
-                debug!("No screenshot available to display");
+        Message::HeadphonesOnlyToggled(enabled) => {
+            info!(enabled, "Headphone-only playback toggled");
+            app.headphones_only = enabled;
+            config::save_headphones_only(enabled);
+            Task::none()
+        }
+        Message::EarconsToggled(enabled) => {
+            info!(enabled, "Audio cues toggled");
+            app.earcons.enabled = enabled;
+            set_earcon_settings(app.earcons);
+            config::save_earcon_settings(&app.earcons);
+            Task::none()
+        }
+        Message::EarconVolumeChanged(volume) => {
+            app.earcons.volume = volume;
+            set_earcon_settings(app.earcons);
+            config::save_earcon_settings(&app.earcons);
+            Task::none()
+        }
+        Message::EarconSetSelected(set) => {
+            info!(?set, "Audio cue sound set selected");
+            app.earcons.set = set;
+            set_earcon_settings(app.earcons);
+            config::save_earcon_settings(&app.earcons);
+            // Let the user hear the new sounds
+            play_earcon(Earcon::Start);
+            Task::none()
+        }
+        Message::FadeDurationChanged(ms) => {
+            app.fade_ms = ms;
+            crate::providers::set_fade_duration(ms);
+            config::save_fade_ms(ms);
+            Task::none()
+        }
+        Message::PitchChanged(semitones) => {
+            app.pitch_semitones = semitones;
+            crate::providers::set_pitch(semitones);
+            config::save_pitch_semitones(semitones);
+            Task::none()
+        }
+        Message::OpenReadingView => {
+            if app.reading_view_window_id.is_some() {
+                debug!("Reading view already open, ignoring request");
                 return Task::none();
             }
-            
-            debug!("Opening screenshot viewer window");
+            debug!("Opening reading view window");
             let (window_id, task) = window::open(window::Settings {
-                size: Size::new(800.0, 600.0),
+                size: Size::new(720.0, 560.0),
                 resizable: true,
                 decorations: true,
                 transparent: false,
@@ -907,26 +4274,23 @@ pub fn update(app: &mut App, message: Message) -> Task<Message> {
                 position: window::Position::Centered,
                 ..Default::default()
             });
-            app.screenshot_window_id = Some(window_id);
+            app.reading_view_window_id = Some(window_id);
+            // Polly word marks cost a request each, so only ask for them while they are shown
+            crate::providers::set_word_marks_wanted(true);
             task.map(Message::WindowOpened)
         }
-        Message::CloseScreenshotViewer => {
-            close_window_if_some(app.screenshot_window_id.take())
+        Message::CloseReadingView => {
+            crate::providers::set_word_marks_wanted(false);
+            close_window_if_some(app.reading_view_window_id.take())
         }
-        Message::OpenExtractedTextDialog => {
-            if app.extracted_text_dialog_window_id.is_some() {
-                debug!("Extracted text dialog already open, ignoring request");
+        Message::OpenTranscript => {
+            if app.transcript_window_id.is_some() {
+                debug!("Transcript already open, ignoring request");
                 return Task::none();
             }
-            
-            if app.extracted_text.is_none() {
-                debug!("No extracted text available to display");
-                return Task::none();
-            }
-            
-            debug!("Opening extracted text dialog window");
+            debug!("Opening transcript window");
             let (window_id, task) = window::open(window::Settings {
-                size: Size::new(600.0, 400.0),
+                size: Size::new(560.0, 520.0),
                 resizable: true,
                 decorations: true,
                 transparent: false,
@@ -934,266 +4298,719 @@ pub fn update(app: &mut App, message: Message) -> Task<Message> {
                 position: window::Position::Centered,
                 ..Default::default()
             });
-            app.extracted_text_dialog_window_id = Some(window_id);
-            task.map(Message::WindowOpened)
-        }
-        Message::CloseExtractedTextDialog => {
-            app.extracted_text = None;
-            app.extracted_text_editor = None;
-            close_window_if_some(app.extracted_text_dialog_window_id.take())
+            app.transcript_window_id = Some(window_id);
+            Task::batch([task.map(Message::WindowOpened), follow_transcript(app)])
         }
-        Message::CopyExtractedTextToClipboard => {
-            let text_to_copy = app.extracted_text_editor.as_ref()
-                .map(|e| e.text())
-                .or_else(|| app.extracted_text.clone());
-            
-            let Some(text_to_copy) = text_to_copy else {
-                warn!("No extracted text available to copy");
+        Message::CloseTranscript => close_window_if_some(app.transcript_window_id.take()),
+        Message::TranscriptSentenceClicked(index) => {
+            let Some(provider) = app.provider.as_mut() else {
+                debug!("Transcript sentence clicked with nothing playing, ignoring");
                 return Task::none();
             };
-            
-            match system::copy_to_clipboard(&text_to_copy) {
-                Ok(()) => {
-                    info!(bytes = text_to_copy.len(), "Text copied to clipboard successfully");
-                    app.status_text = Some("Text copied to clipboard".to_string());
-                }
-                Err(e) => {
-                    error!(error = %e, "Failed to copy text to clipboard");
-                    app.error_message = Some(format!("Failed to copy to clipboard: {}", e));
-                }
+            let current = app.transcript_sentence.unwrap_or(0);
+            // Skip through the streamed parts when they are known, else seek by characters
+            if !provider.skip_sentences(index as i32 - current as i32) {
+                provider.seek(text::timing::sentence_start_progress(&app.transcript, index));
             }
+            app.progress = provider.get_progress();
+            app.transcript_sentence = Some(index);
+            debug!(index, "Seeked to transcript sentence");
             Task::none()
         }
-        Message::ExtractedTextEditorAction(action) => {
-            // Apply the action to the editor content
-            if let Some(ref mut editor_content) = app.extracted_text_editor {
-                editor_content.perform(action);
-                // Update the extracted_text string for consistency
-                app.extracted_text = Some(editor_content.text());
+        Message::FocusFollowToggled(enabled) => {
+            info!(enabled, "Reading view focus follow toggled");
+            app.reading_style.focus_follow = enabled;
+            config::save_reading_style(&app.reading_style);
+            follow_current_sentence(app)
+        }
+        Message::FocusDimChanged(dim) => {
+            app.reading_style.focus_dim = dim;
+            config::save_reading_style(&app.reading_style);
+            Task::none()
+        }
+        Message::SourcePrioritySelected(priority) => {
+            info!(?priority, "Capture source order selected");
+            app.source_priority = priority;
+            config::save_source_priority(priority);
+            Task::none()
+        }
+        Message::DuplicateCaptureActionSelected(action) => {
+            info!(?action, "Duplicate capture action selected");
+            app.duplicate_capture_action = action;
+            config::save_duplicate_capture_action(action);
+            Task::none()
+        }
+        Message::SensitiveTextGuardToggled(enabled) => {
+            info!(enabled, "Sensitive text guard toggled");
+            app.sensitive_text_guard = enabled;
+            config::save_sensitive_text_guard(enabled);
+            Task::none()
+        }
+        Message::ClipboardHistoryToggled(enabled) => {
+            info!(enabled, "Clipboard history toggled");
+            app.clipboard_history_enabled = enabled;
+            config::save_clipboard_history(enabled);
+            if !enabled {
+                app.clipboard_history.clear();
             }
             Task::none()
         }
-        Message::ReadExtractedText => {
-            let text_to_read = app.extracted_text_editor.as_ref()
-                .map(|e| e.text())
-                .or_else(|| app.extracted_text.clone());
-            
-            let Some(text_to_read) = text_to_read else {
-                warn!("No extracted text available to read");
+        Message::OpenClipboardHistory => {
+            if app.clipboard_history_window_id.is_some() {
+                debug!("Clipboard history already open, ignoring request");
+                return Task::none();
+            }
+            let (window_id, task) = open_info_window(Size::new(480.0, 420.0));
+            app.clipboard_history_window_id = Some(window_id);
+            task
+        }
+        Message::CloseClipboardHistory => close_window_if_some(app.clipboard_history_window_id.take()),
+        Message::OpenProcessingChain => {
+            if app.processing_chain_window_id.is_some() {
+                debug!("Processing chain already open, ignoring request");
+                return Task::none();
+            }
+            let (window_id, task) = open_info_window(Size::new(560.0, 520.0));
+            app.processing_chain_window_id = Some(window_id);
+            task
+        }
+        Message::CloseProcessingChain => close_window_if_some(app.processing_chain_window_id.take()),
+        Message::ReadClipboardHistoryItem(index) => {
+            let Some(text) = app.clipboard_history.get(index).cloned() else {
                 return Task::none();
             };
-            
-            if text_to_read.trim().is_empty() {
-                warn!("Extracted text is empty, cannot read");
+            info!(index, bytes = text.len(), "Reading an earlier capture from the clipboard history");
+            // Picking the latest capture again is a choice, not a duplicate hotkey press
+            app.last_capture_hash = None;
+            let close_task = close_window_if_some(app.clipboard_history_window_id.take());
+            Task::batch([close_task, read_in_main_window(app, text)])
+        }
+        Message::ClearClipboardHistory => {
+            info!("Clipboard history cleared");
+            app.clipboard_history.clear();
+            Task::none()
+        }
+        Message::PrivateScreenshotsToggled(enabled) => {
+            info!(enabled, "Private screenshots toggled");
+            app.private_screenshots = enabled;
+            config::save_private_screenshots(enabled);
+            Task::none()
+        }
+        Message::ExportRecipientsChanged(recipients) => {
+            debug!(recipients = %recipients, "Export recipients changed");
+            config::save_export_recipients(&recipients);
+            app.export_recipients = recipients;
+            Task::none()
+        }
+        Message::LocalOnlyAppsChanged(apps) => {
+            debug!(apps = %apps, "Offline-only apps changed");
+            config::save_local_only_apps(&apps);
+            app.local_only_apps = apps;
+            Task::none()
+        }
+        Message::CleanupScheduleSelected(schedule) => {
+            info!(?schedule, "Cleanup schedule selected");
+            app.cleanup_schedule = schedule;
+            config::save_cleanup_schedule(schedule);
+            Task::none()
+        }
+        Message::HistoryMaxDaysSelected(days) => {
+            info!(days, "History retention selected");
+            app.history_max_days = days;
+            config::save_history_max_days(days);
+            Task::none()
+        }
+        Message::SynthesisCacheSizeSelected(mb) => {
+            info!(mb, "Synthesis cache size selected");
+            app.synthesis_cache_mb = mb;
+            config::save_synthesis_cache_mb(mb);
+            Task::none()
+        }
+        Message::CleanupTick => {
+            let idle = app.activity.is_idle();
+            let due = crate::storage::cleanup_due(
+                app.cleanup_schedule,
+                crate::storage::last_cleanup(),
+                chrono::Utc::now().timestamp(),
+            );
+            if idle && due {
+                return Task::perform(async { Message::RunStorageCleanup }, |msg| msg);
+            }
+            Task::none()
+        }
+        Message::WeeklySummaryToggled(enabled) => {
+            info!(enabled, "Weekly summary toggled");
+            app.weekly_summary_enabled = enabled;
+            config::save_weekly_summary(enabled);
+            Task::none()
+        }
+        Message::WeeklySummaryDaySelected(day) => {
+            info!(%day, "Weekly summary day selected");
+            app.weekly_summary_day = day;
+            config::save_weekly_summary_time(day, app.weekly_summary_hour);
+            Task::none()
+        }
+        Message::WeeklySummaryHourChanged(hour) => {
+            debug!(hour, "Weekly summary hour changed");
+            app.weekly_summary_hour = hour;
+            config::save_weekly_summary_time(app.weekly_summary_day, hour);
+            Task::none()
+        }
+        Message::WeeklySummaryTick => {
+            if !app.weekly_summary_enabled || app.weekly_summary_window_id.is_some() {
                 return Task::none();
             }
-            
-            info!(bytes = text_to_read.len(), "Sending extracted text to TTS (bypassing text cleanup)");
-            // OCR text: skip all preprocessing (cleanup API, markdown parsing, etc.)
-            // Send directly to TTS to preserve original formatting and line breaks
-            set_loading_state(app, "Synthesizing voice...");
-            initialize_tts_async(app.selected_backend, text_to_read, "ReadExtractedText", app.selected_polly_voice.clone())
+            let now = chrono::Local::now().naive_local();
+            let last_shown = crate::stats::last_summary_shown();
+            if !crate::stats::summary_due(now, app.weekly_summary_day, app.weekly_summary_hour, last_shown) {
+                return Task::none();
+            }
+            crate::stats::mark_summary_shown(now);
+            let summary = crate::stats::last_week();
+            if summary.items == 0 {
+                debug!("Nothing listened to this week, skipping the weekly summary");
+                return Task::none();
+            }
+            info!(seconds = summary.seconds, items = summary.items, "Showing the weekly summary");
+            app.weekly_summary_text = Some(summary.describe());
+            let (window_id, task) = open_info_window(Size::new(420.0, 200.0));
+            app.weekly_summary_window_id = Some(window_id);
+            task
+        }
+        Message::CloseWeeklySummary => {
+            app.weekly_summary_text = None;
+            close_window_if_some(app.weekly_summary_window_id.take())
+        }
+        Message::WeeklySummaryOptOut => {
+            info!("Weekly summary turned off from its window");
+            app.weekly_summary_enabled = false;
+            config::save_weekly_summary(false);
+            app.weekly_summary_text = None;
+            close_window_if_some(app.weekly_summary_window_id.take())
+        }
+        Message::PhraseWarmupTick => {
+            let idle = app.activity.is_idle();
+            if !idle || !app.history_enabled || app.phrase_warmup_running {
+                return Task::none();
+            }
+            app.phrase_warmup_running = true;
+            let backend = app.selected_backend;
+            let polly_voice_id = app.selected_polly_voice.clone();
+            Task::perform(
+                async move {
+                    tokio::task::spawn_blocking(move || warm_phrase_cache(backend, polly_voice_id))
+                        .await
+                        .unwrap_or(0)
+                },
+                Message::PhrasesWarmed,
+            )
+        }
+        Message::PhrasesWarmed(count) => {
+            app.phrase_warmup_running = false;
+            if count > 0 {
+                info!(count, "Frequent phrases synthesized into the cache");
+            }
+            Task::none()
+        }
+        Message::RunStorageCleanup => {
+            if app.cleanup_running {
+                return Task::none();
+            }
+            app.cleanup_running = true;
+            let history_max_days = app.history_max_days;
+            Task::perform(
+                async move {
+                    tokio::task::spawn_blocking(move || crate::storage::run_cleanup(history_max_days))
+                        .await
+                        .unwrap_or(0)
+                },
+                Message::StorageCleanedUp,
+            )
+        }
+        Message::StorageCleanedUp(freed) => {
+            app.cleanup_running = false;
+            app.storage_status = Some(format!("Cleanup freed {}", crate::storage::format_size(freed)));
+            if app.settings_window_id.is_some() {
+                return measure_storage_task();
+            }
+            Task::none()
+        }
+        Message::MeasureStorage => measure_storage_task(),
+        Message::StorageMeasured(usage) => {
+            app.storage_usage = usage;
+            Task::none()
+        }
+        Message::PurgeStorage(category) => {
+            info!(category = category.label(), "Purging storage");
+            Task::perform(
+                async move {
+                    let freed = tokio::task::spawn_blocking(move || crate::storage::purge(category))
+                        .await
+                        .unwrap_or(0);
+                    (category, freed)
+                },
+                |(category, freed)| Message::StoragePurged(category, freed),
+            )
+        }
+        Message::StoragePurged(category, freed) => {
+            app.storage_status = Some(format!(
+                "{} purged, freed {}",
+                category.label(),
+                crate::storage::format_size(freed)
+            ));
+            measure_storage_task()
+        }
+        Message::ReadSensitiveText => {
+            let Some((text, kind, context)) = app.sensitive_pending.take() else {
+                warn!("ReadSensitiveText received with no pending text");
+                return Task::none();
+            };
+            info!(kind, context, "Reading text confirmed despite looking like a secret");
+            let close_task = close_window_if_some(app.sensitive_dialog_window_id.take());
+            let read_task = if context == "ReadExtractedText" {
+                read_extracted_text(app, text)
+            } else {
+                process_confirmed_text(app, text, "ReadSensitiveText")
+            };
+            Task::batch([close_task, read_task])
+        }
+        Message::CloseSensitiveDialog => {
+            debug!("Sensitive text dialog dismissed, discarding text");
+            app.sensitive_pending = None;
+            // Nothing was read, so capturing the same text again is not a duplicate
+            app.last_capture_hash = None;
+            close_window_if_some(app.sensitive_dialog_window_id.take())
+        }
+        Message::ContinueDocument => {
+            let Some((text, progress, context)) = app.resume_pending.take() else {
+                warn!("ContinueDocument received with no pending document");
+                return Task::none();
+            };
+            info!(progress, "Continuing document from its saved position");
+            let close_task = close_window_if_some(app.resume_dialog_window_id.take());
+            // The whole text, so the saved position still lines up
+            app.pending_seek = Some(progress);
+            Task::batch([close_task, process_text_for_tts(app, text, context)])
+        }
+        Message::RestartDocument => {
+            let Some((text, _, context)) = app.resume_pending.take() else {
+                warn!("RestartDocument received with no pending document");
+                return Task::none();
+            };
+            info!("Reading document from the start");
+            let close_task = close_window_if_some(app.resume_dialog_window_id.take());
+            Task::batch([close_task, process_captured_text(app, text, context)])
         }
-        Message::TrayEventReceived => {
-            // Poll for tray events and convert them to messages
-            if let Some(ref tray) = app.system_tray {
-                if let Some(event) = tray.try_recv() {
-                    let message = match event {
-                        crate::system::TrayEvent::ShowWindow => Message::ShowWindow,
-                        crate::system::TrayEvent::HideWindow => Message::HideWindow,
-                        crate::system::TrayEvent::ReadSelected => Message::ReadSelected,
-                        crate::system::TrayEvent::Quit => Message::Quit,
-                    };
-                    return Task::perform(async { message }, |msg| msg);
-                }
+        Message::CloseResumeDialog => {
+            debug!("Continue reading dialog dismissed, discarding document");
+            app.resume_pending = None;
+            app.current_document = None;
+            close_window_if_some(app.resume_dialog_window_id.take())
+        }
+        Message::CloseLongTextDialog => {
+            debug!("Long text dialog dismissed, discarding text");
+            app.long_text_pending = None;
+            app.last_capture_hash = None;
+            close_window_if_some(app.long_text_dialog_window_id.take())
+        }
+        Message::PodcastFeedToggled(enabled) => {
+            info!(enabled, "Podcast feed toggled");
+            app.feed_config.enabled = enabled;
+            config::save_feed_config(&app.feed_config);
+            if enabled {
+                start_feed_server(app);
+            } else {
+                app.feed_server = None;
+                app.feed_error = None;
             }
             Task::none()
         }
-        Message::ShowWindow => {
-            // Reopen the window if it was hidden/closed
-            if app.window_hidden || app.main_window_id.is_none() {
-                info!("Reopening main window from tray");
-                let (window_id, open_task) = open_main_window();
-                app.main_window_id = Some(window_id);
-                app.window_hidden = false;
-                return open_task;
-            }
+        Message::PodcastFeedTitleChanged(title) => {
+            app.feed_config.title = title;
+            save_feed_metadata(app);
             Task::none()
         }
-        Message::HideWindow => {
-            // Close the window (user can reopen from tray)
-            if let Some(window_id) = app.main_window_id {
-                info!("Hiding main window to tray");
-                app.window_hidden = true;
-                return window::close(window_id);
+        Message::PodcastFeedAuthorChanged(author) => {
+            app.feed_config.author = author;
+            save_feed_metadata(app);
+            Task::none()
+        }
+        Message::CopyPodcastFeedUrl => {
+            if let Err(e) = system::copy_to_clipboard(&app.feed_url) {
+                warn!(error = %e, "Failed to copy podcast feed URL");
             }
             Task::none()
         }
-        Message::ReadSelected => {
-            info!("Read Selected triggered from tray menu");
-            // Ensure window is visible when reading
-            let fetch_task = fetch_selected_text_task("tray menu");
-            if app.window_hidden || app.main_window_id.is_none() {
-                // Show window first, then fetch text
-                let (window_id, open_task) = open_main_window();
-                app.main_window_id = Some(window_id);
-                app.window_hidden = false;
-                return Task::batch([open_task, fetch_task]);
+        Message::OpenPodcastFeedFolder => {
+            if let Err(e) = std::fs::create_dir_all(&app.feed_config.dir) {
+                warn!(error = %e, "Failed to create podcast feed folder");
             }
-            fetch_task
+            if let Err(e) = open::that(&app.feed_config.dir) {
+                error!("Failed to open folder '{}': {}", app.feed_config.dir.display(), e);
+            }
+            Task::none()
         }
-        Message::Quit => {
-            info!("Quitting application from tray menu");
-            iced::exit()
+        Message::HistoryToggled(enabled) => {
+            info!(enabled, "Reading history toggled");
+            app.history_enabled = enabled;
+            config::save_history_enabled(enabled);
+            Task::none()
         }
-        Message::HotkeyPressed => {
-            // Check if hotkey event actually occurred
-            if let Some(ref mut hotkey_manager) = app.hotkey_manager {
-                if hotkey_manager.try_recv().is_some() {
-                    info!("Hotkey pressed - triggering read");
-                    // Use the same logic as ReadSelected
-                    let fetch_task = fetch_selected_text_task("hotkey");
-                    if app.window_hidden || app.main_window_id.is_none() {
-                        // Show window first, then fetch text
-                        let (window_id, open_task) = open_main_window();
-                        app.main_window_id = Some(window_id);
-                        app.window_hidden = false;
-                        return Task::batch([open_task, fetch_task]);
-                    }
-                    return fetch_task;
-                }
+        Message::OpenCommandPalette => {
+            if app.palette_window_id.is_some() {
+                debug!("Command palette already open, ignoring request");
+                return Task::none();
             }
-            Task::none()
+            // Capture the selection before the palette window takes focus
+            fetch_selected_text_then(app, "command palette", Message::CommandPaletteTextFetched)
         }
-        Message::HotkeyConfigChanged(config) => {
-            // Ignore if hotkeys are disabled due to Wayland/Hyprland
-            if app.hotkeys_disabled_wayland {
+        Message::CommandPaletteTextFetched(text) => {
+            if app.palette_window_id.is_some() {
                 return Task::none();
             }
-            
-            info!("Hotkey configuration changed");
-            app.hotkey_config = config.clone();
-            
-            // Update hotkey registration if enabled
-            if app.hotkey_enabled {
-                if let Some(ref mut hotkey_manager) = app.hotkey_manager {
-                    if let Err(e) = hotkey_manager.register(config) {
-                        error!(error = %e, "Failed to register new hotkey");
-                        app.error_message = Some(format!("Failed to register hotkey: {e}"));
-                    } else {
-                        info!("Hotkey re-registered successfully");
-                        app.error_message = None;
+            debug!(has_text = text.is_some(), "Opening command palette window");
+            app.palette_text = text;
+            app.palette_filter.clear();
+            let (window_id, task) = window::open(window::Settings {
+                size: Size::new(480.0, 420.0),
+                resizable: true,
+                decorations: true,
+                transparent: false,
+                visible: true,
+                position: window::Position::Centered,
+                ..Default::default()
+            });
+            app.palette_window_id = Some(window_id);
+            Task::batch([task.map(Message::WindowOpened), iced::widget::operation::focus(PALETTE_INPUT_ID)])
+        }
+        Message::CloseCommandPalette => {
+            app.palette_text = None;
+            close_window_if_some(app.palette_window_id.take())
+        }
+        Message::PaletteFilterChanged(filter) => {
+            app.palette_filter = filter;
+            Task::none()
+        }
+        Message::RunPaletteCommand(command) => {
+            info!(?command, "Running command palette command");
+            let selection = app.palette_text.take();
+            let close_task = close_window_if_some(app.palette_window_id.take());
+            let task = match command {
+                PaletteCommand::ReadSelection => match selection {
+                    Some(text) => read_in_main_window(app, text),
+                    None => {
+                        warn!("No text was selected when the command palette opened");
+                        Task::none()
+                    }
+                },
+                PaletteCommand::DictateNote => Task::perform(async { Message::ToggleDictation }, |msg| msg),
+                PaletteCommand::PronunciationTrainer => {
+                    Task::perform(async { Message::OpenPronunciationTrainer }, |msg| msg)
+                }
+                PaletteCommand::ClipboardHistory => Task::perform(async { Message::OpenClipboardHistory }, |msg| msg),
+                PaletteCommand::ToggleFavorite => Task::perform(async { Message::ToggleFavorite }, |msg| msg),
+                PaletteCommand::ProcessingChain => Task::perform(async { Message::OpenProcessingChain }, |msg| msg),
+                PaletteCommand::ReadingView => Task::perform(async { Message::OpenReadingView }, |msg| msg),
+                PaletteCommand::Transcript => Task::perform(async { Message::OpenTranscript }, |msg| msg),
+                PaletteCommand::MiniPlayer => Task::perform(async { Message::OpenMiniBar }, |msg| msg),
+                PaletteCommand::Settings => Task::perform(async { Message::Settings }, |msg| msg),
+                PaletteCommand::ReloadPlugins => Task::perform(async { Message::ReloadPlugins }, |msg| msg),
+                PaletteCommand::Plugin(action) => {
+                    let input = selection.unwrap_or_default();
+                    match app.plugin_host.run_action(&action, &input) {
+                        Ok(text) if text.trim().is_empty() => {
+                            debug!(action = %action.name, "Plugin action returned nothing to read");
+                            Task::none()
+                        }
+                        Ok(text) => read_in_main_window(app, text),
+                        Err(e) => {
+                            warn!(error = %e, "Plugin action failed");
+                            app.error_message = Some(format!("Plugin action failed: {e}"));
+                            Task::none()
+                        }
                     }
                 }
+            };
+            Task::batch([close_task, task])
+        }
+        Message::PluginToggled(name, enabled) => {
+            info!(plugin = %name, enabled, "Plugin toggled");
+            if let Some(plugin) = app.plugin_host.plugins.iter_mut().find(|p| p.name == name) {
+                plugin.enabled = enabled;
             }
-            
-            crate::config::save_hotkey_config(&app.hotkey_config, app.hotkey_enabled);
+            config::save_disabled_plugins(&app.plugin_host.disabled_names());
             Task::none()
         }
-        Message::HotkeyToggled(enabled) => {
-            // Ignore if hotkeys are disabled due to Wayland/Hyprland
-            if app.hotkeys_disabled_wayland {
-                return Task::none();
+        Message::ReloadPlugins => {
+            app.plugin_host.reload(&config::load_disabled_plugins());
+            Task::none()
+        }
+        Message::OpenPluginsFolder => {
+            let dir = crate::plugins::plugins_dir();
+            if let Err(e) = std::fs::create_dir_all(&dir) {
+                warn!(error = %e, "Failed to create plugins folder");
             }
-            
-            info!(enabled, "Hotkey toggled");
-            app.hotkey_enabled = enabled;
-            
-            if let Some(ref mut hotkey_manager) = app.hotkey_manager {
-                if enabled {
-                    if let Err(e) = hotkey_manager.register(app.hotkey_config.clone()) {
-                        error!(error = %e, "Failed to register hotkey");
-                        app.error_message = Some(format!("Failed to register hotkey: {e}"));
-                        app.hotkey_enabled = false; // Revert if registration failed
-                    } else {
-                        info!("Hotkey registered successfully");
-                        app.error_message = None;
-                    }
-                } else if let Err(e) = hotkey_manager.unregister() {
-                    warn!(error = %e, "Failed to unregister hotkey");
-                } else {
-                    info!("Hotkey unregistered successfully");
-                }
+            if let Err(e) = open::that(&dir) {
+                error!("Failed to open folder '{}': {}", dir.display(), e);
             }
-            
-            crate::config::save_hotkey_config(&app.hotkey_config, app.hotkey_enabled);
             Task::none()
         }
-        Message::StartListeningForHotkey => {
-            // Ignore if hotkeys are disabled due to Wayland/Hyprland
-            if app.hotkeys_disabled_wayland {
-                return Task::none();
-            }
-            
-            info!("Starting to listen for hotkey input");
-            app.listening_for_hotkey = true;
-            app.error_message = None; // Clear any previous errors
+        Message::SyncDirInputChanged(dir) => {
+            app.sync_dir_input = dir;
             Task::none()
         }
-        Message::StopListeningForHotkey => {
-            info!("Stopped listening for hotkey input");
-            app.listening_for_hotkey = false;
-            app.error_message = None;
+        Message::SyncNow => {
+            let dir = expand_home(app.sync_dir_input.trim());
+            config::save_sync_dir(Some(dir.as_path()).filter(|d| !d.as_os_str().is_empty()));
+            app.sync_status = if dir.as_os_str().is_empty() {
+                Some("Sync is off".to_string())
+            } else {
+                match crate::sync::sync_now() {
+                    Ok(()) => Some(format!(
+                        "Synced at {} - settings from other machines apply after restart",
+                        chrono::Local::now().format("%H:%M")
+                    )),
+                    Err(e) => {
+                        warn!(error = %e, "Sync failed");
+                        Some(format!("Sync failed: {e}"))
+                    }
+                }
+            };
             Task::none()
         }
-        Message::HotkeyCaptured(key, modifiers) => {
-            // Ignore if hotkeys are disabled due to Wayland/Hyprland
-            if app.hotkeys_disabled_wayland {
-                app.listening_for_hotkey = false;
+        Message::WatchDirInputChanged(dir) => {
+            app.watch_dir_input = dir;
+            Task::none()
+        }
+        Message::WatchDirSaved => {
+            let dir = Some(expand_home(app.watch_dir_input.trim())).filter(|d| !d.as_os_str().is_empty());
+            if let Some(dir) = dir.as_ref().filter(|d| !d.is_dir()) {
+                app.watch_status = Some(format!("{} is not a folder", dir.display()));
                 return Task::none();
             }
-            info!(?key, ?modifiers, "Hotkey combination captured");
-            
-            // Convert Iced key/modifiers to global_hotkey format
-            use crate::ui::settings::hotkeys::{iced_key_to_global_hotkey_code, iced_modifiers_to_global_hotkey_modifiers};
-            
-            let Some(code) = iced_key_to_global_hotkey_code(&key) else {
-                error!("Invalid key captured: {:?}", key);
-                app.error_message = Some("Invalid key. Please try again.".to_string());
-                app.listening_for_hotkey = false;
-                return Task::none();
-            };
-            
-            let gh_modifiers = iced_modifiers_to_global_hotkey_modifiers(modifiers);
-            
-            // Validate that we have at least one modifier
-            if gh_modifiers.is_empty() {
-                error!("No modifiers in captured hotkey");
-                app.error_message = Some("Hotkey must include at least one modifier (Ctrl/Cmd, Shift, or Alt).".to_string());
-                app.listening_for_hotkey = false;
+            config::save_watch_dir(dir.as_deref());
+            app.watch_status = Some(match &dir {
+                Some(dir) => format!("Watching {}", dir.display()),
+                None => "Watch folder is off".to_string(),
+            });
+            info!(?dir, "Watch folder changed");
+            app.watch_dir = dir;
+            Task::none()
+        }
+        Message::WatchedFileAdded(path) => {
+            if !text::chapters::is_readable_file(&path) {
+                debug!(path = %path.display(), "New file in the watch folder is not text, ignoring");
                 return Task::none();
             }
-            
-            // Create new hotkey config
-            let new_config = crate::system::HotkeyConfig {
-                modifiers: gh_modifiers,
-                key: code,
-            };
-            
-            // Exit listening mode
-            app.listening_for_hotkey = false;
-            
-            // Update the hotkey configuration
-            // This will trigger HotkeyConfigChanged internally
-            app.hotkey_config = new_config.clone();
-            
-            // Update hotkey registration if enabled
-            if app.hotkey_enabled {
-                if let Some(ref mut hotkey_manager) = app.hotkey_manager {
-                    if let Err(e) = hotkey_manager.register(new_config.clone()) {
-                        error!(error = %e, "Failed to register new hotkey");
-                        app.error_message = Some(format!("Failed to register hotkey: {e}"));
-                    } else {
-                        info!("Hotkey registered successfully");
-                        app.error_message = None;
-                    }
+            info!(path = %path.display(), "New file in the watch folder, loading text");
+            Task::perform(
+                async move {
+                    tokio::task::spawn_blocking(move || text::chapters::document_text(&path))
+                        .await
+                        .unwrap_or_else(|e| Err(format!("Failed to load file: {e}")))
+                },
+                Message::WatchedFileLoaded,
+            )
+        }
+        Message::WatchedFileLoaded(result) => match result {
+            Ok(text) if text.trim().is_empty() => {
+                warn!("File from the watch folder has no text to read");
+                Task::none()
+            }
+            Ok(text) => {
+                app.queued_readings.push_back(text);
+                if !app.activity.is_idle() {
+                    info!(queued = app.queued_readings.len(), "Reading in progress, queueing file from the watch folder");
+                    return Task::none();
                 }
+                let text = app.queued_readings.pop_front().unwrap_or_default();
+                read_in_main_window(app, text)
             }
-            
-            // Save to config
-            crate::config::save_hotkey_config(&app.hotkey_config, app.hotkey_enabled);
+            Err(e) => {
+                warn!(error = %e, "Failed to load file from the watch folder");
+                app.error_message = Some(e);
+                Task::none()
+            }
+        },
+        Message::ReadingForwarded(system::ForwardedReading::Text(text)) => {
+            info!(bytes = text.len(), "Reading text forwarded from another app");
+            app.error_message = None;
+            read_in_main_window(app, text)
+        }
+        Message::ReadingForwarded(system::ForwardedReading::Files(paths)) => {
+            info!(files = paths.len(), "Loading files forwarded from another app");
+            transition(app, Event::Load("Loading file...".to_string()));
+            let load_task = Task::perform(
+                async move {
+                    tokio::task::spawn_blocking(move || {
+                        let texts = paths
+                            .iter()
+                            .map(|path| text::chapters::document_text(path))
+                            .collect::<Result<Vec<String>, String>>()?;
+                        Ok(texts.join("\n\n"))
+                    })
+                    .await
+                    .unwrap_or_else(|e| Err(format!("Failed to load file: {e}")))
+                },
+                Message::ForwardedFilesLoaded,
+            );
+            Task::batch([show_main_window(app), load_task])
+        }
+        Message::ForwardedFilesLoaded(result) => {
+            finish_loading(app);
+            match result {
+                Ok(text) if text.trim().is_empty() => {
+                    warn!("Forwarded files have no text to read");
+                    app.error_message = Some("The chosen files have no text to read".to_string());
+                    Task::none()
+                }
+                Ok(text) => {
+                    app.error_message = None;
+                    read_document(app, crate::history::file_document_key(&text), text, "Forwarded")
+                }
+                Err(e) => {
+                    error!(error = %e, "Failed to load forwarded files");
+                    app.error_message = Some(e);
+                    Task::none()
+                }
+            }
+        }
+        Message::WatchFolderFailed(e) => {
+            warn!(error = %e, "Watch folder unavailable");
+            app.watch_status = Some(e);
             Task::none()
         }
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::TTSError;
+    use std::time::Duration;
+
+    /// Provider of a reading put aside, paused until resumed.
+    #[derive(Default)]
+    struct PausedProvider {
+        playing: bool,
+    }
+
+    impl TTSProvider for PausedProvider {
+        fn speak(&mut self, _text: &str) -> Result<(), TTSError> {
+            Ok(())
+        }
+        fn synthesize(&mut self, _text: &str) -> Result<Vec<f32>, TTSError> {
+            Ok(Vec::new())
+        }
+        fn sample_rate(&self) -> u32 {
+            22050
+        }
+        fn play_samples(&mut self, _samples: Vec<f32>) -> Result<(), TTSError> {
+            Ok(())
+        }
+        fn hold_next_playback(&mut self) {}
+        fn pause(&mut self) -> Result<(), TTSError> {
+            self.playing = false;
+            Ok(())
+        }
+        fn resume(&mut self) -> Result<(), TTSError> {
+            self.playing = true;
+            Ok(())
+        }
+        fn stop(&mut self) -> Result<(), TTSError> {
+            self.playing = false;
+            Ok(())
+        }
+        fn replay(&mut self) -> Result<(), TTSError> {
+            Ok(())
+        }
+        fn is_playing(&self) -> bool {
+            self.playing
+        }
+        fn is_paused(&self) -> bool {
+            !self.playing
+        }
+        fn skip_forward(&mut self, _seconds: f32) {}
+        fn skip_backward(&mut self, _seconds: f32) {}
+        fn get_progress(&self) -> f32 {
+            0.5
+        }
+        fn duration(&self) -> Duration {
+            Duration::from_secs(60)
+        }
+        fn position(&self) -> Duration {
+            Duration::from_secs(30)
+        }
+        fn set_volume(&mut self, _volume: f32) {}
+        fn set_speed(&mut self, _speed: f32) {}
+        fn seek(&mut self, _progress: f32) {}
+        fn skip_sentences(&mut self, _offset: i32) -> bool {
+            false
+        }
+        fn sentence_starts(&self) -> Vec<Duration> {
+            Vec::new()
+        }
+        fn synthesized_audio(&self) -> (Vec<f32>, u32) {
+            (Vec::new(), 22050)
+        }
+    }
+
+    #[test]
+    fn test_failed_interruption_resumes_the_reading() {
+        let mut app = App::default();
+        app.interrupted_reading = Some(InterruptedReading {
+            provider: Box::new(PausedProvider::default()),
+            text: Some("The long reading.".to_string()),
+            transcript: vec!["The long reading.".to_string()],
+            spoken_sentences: vec!["The long reading.".to_string()],
+            document: None,
+            synthesis_cache_key: None,
+        });
+        transition(&mut app, Event::Load("Synthesizing voice...".to_string()));
+
+        // The failure is kept in the status line while the reading carries on
+        assert!(fail_reading(&mut app, "Voice generation failed".to_string()));
+        assert_eq!(app.activity, Activity::Playing);
+        assert_eq!(app.status_text.as_deref(), Some("Voice generation failed"));
+        assert!(app.provider.as_ref().is_some_and(|provider| provider.is_playing()));
+        assert_eq!(app.reading_text.as_deref(), Some("The long reading."));
+
+        // Without a reading to carry on with, the failure shows as the activity
+        transition(&mut app, Event::Load("Synthesizing voice...".to_string()));
+        assert!(!fail_reading(&mut app, "Voice generation failed".to_string()));
+        assert_eq!(app.activity.status(), Some("Voice generation failed"));
+    }
+
+    #[test]
+    fn test_secret_read_from_editor_asks_first() {
+        let mut app = App::default();
+        app.history_enabled = false;
+        app.extracted_text = Some("Password: Tr0ub4dor&3".to_string());
+
+        let _ = update(&mut app, Message::ReadExtractedText);
+        assert!(matches!(app.sensitive_pending, Some((_, "a password", "ReadExtractedText"))));
+        assert!(app.sensitive_dialog_window_id.is_some());
+        assert_eq!(app.activity, Activity::Idle);
+
+        let _ = update(&mut app, Message::CloseSensitiveDialog);
+        assert!(app.sensitive_pending.is_none());
+        assert_eq!(app.activity, Activity::Idle);
+    }
+
+    #[test]
+    fn test_capture_after_cancelled_dialog_is_not_a_duplicate() {
+        let mut app = App::default();
+        // Audio of an earlier reading that a duplicate capture would replay
+        app.provider = Some(Box::new(PausedProvider::default()));
+        let secret = "Password: Tr0ub4dor&3";
+
+        let _ = process_captured_text(&mut app, secret.to_string(), "Test");
+        assert!(app.sensitive_pending.is_some());
+        let _ = update(&mut app, Message::CloseSensitiveDialog);
+
+        // Capturing it again asks again instead of replaying the old audio
+        let _ = process_captured_text(&mut app, secret.to_string(), "Test");
+        assert!(matches!(app.sensitive_pending, Some((_, "a password", "Test"))));
+        assert_eq!(app.activity, Activity::Idle);
+    }
+}
@@ -1,16 +1,28 @@
 //! UI rendering logic
 
-use iced::widget::{button, checkbox, column, container, mouse_area, progress_bar, radio, row, scrollable, svg, text, text_editor, Space};
+use iced::widget::{
+    button, checkbox, column, container, mouse_area, pin, progress_bar, radio, responsive, row, scrollable, slider, stack, svg, text,
+    text_editor, text_input, Space, Stack,
+};
 use iced::{Alignment, Background, Color, ContentFit, Element, Length};
 
+use crate::activity::Activity;
+use crate::config;
 use crate::flags;
-use crate::model::{App, LanguageInfo, LogLevel, Message, OCRBackend, PlaybackState, TTSBackend};
+use crate::logging::trace_throttled;
+use crate::model::{
+    App, CaptureSource, DuplicateCaptureAction, LanguageInfo, LogLevel, LongTextAction, Message, OCRBackend, SettingsSection,
+    SourcePriority, TTSBackend, VisualizerStyle,
+};
 use crate::styles::{
     circle_button_style, close_button_style, error_container_style, header_style,
-    modal_content_style, section_style, transparent_button_style, wave_bar_style,
+    modal_content_style, ocr_box_style, progress_bar_style, section_style, transparent_button_style, wave_bar_style,
     white_checkbox_style, white_radio_style, window_style,
 };
-use crate::ui::settings::hotkeys;
+use crate::ui::accessibility::control_label;
+use crate::ui::settings::tabs;
+use crate::ui::with_tooltip;
+use crate::ui::settings::{acceleration, appearance, audio_cues, feed, hotkeys, plugins, power, privacy, reading, spoken_text, storage, sync, watch};
 
 const MIN_HEIGHT: f32 = 4.0;
 const MAX_HEIGHT: f32 = 24.0;
@@ -105,48 +117,50 @@ fn white_text(content: &str, size: u32) -> text::Text<'_> {
         })
 }
 
-/// Create a language selection grid from a list of language codes and info.
-///
-/// Returns a scrollable grid with 4 columns showing flag icons and language names.
-/// Each language button opens the voice selection window for that language.
-fn create_language_grid(
-    languages: Vec<(String, crate::model::LanguageInfo)>,
-    selected_language: Option<&str>,
+/// Create a button for one language of the language grid, opening the voice
+/// selection window for that language.
+fn language_button(
+    lang_code: &str,
+    lang_info: &LanguageInfo,
+    native: bool,
+    is_selected: bool,
 ) -> Element<'static, Message> {
+    let flag_icon = flags::get_flag_icon(lang_code);
+    let label_text = crate::voices::labels::language_label(lang_info, native);
+
+    button(
+        container(
+            row![
+                flag_icon,
+                Space::new().width(Length::Fixed(6.0)),
+                text(label_text).size(13)
+                    .style(move |_theme| iced::widget::text::Style {
+                        color: Some(if is_selected {
+                            Color::WHITE
+                        } else {
+                            Color::from_rgba(1.0, 1.0, 1.0, 0.7)
+                        }),
+                    })
+            ]
+            .align_y(Alignment::Center)
+        )
+        .padding([5.0, 8.0])
+        .width(Length::Fill)
+    )
+    .style(transparent_button_style)
+    .width(Length::Fill)
+    .on_press(Message::OpenVoiceSelection(lang_code.to_string()))
+    .into()
+}
+
+/// Lay language buttons out in rows of 4 columns.
+fn language_rows(buttons: Vec<Element<'static, Message>>) -> iced::widget::Column<'static, Message> {
     const COLS: usize = 4;
     let mut grid_rows = column![].spacing(6);
     let mut current_row = row![].spacing(8);
     let mut col_count = 0;
     
-    for (lang_code, lang_info) in languages.iter() {
-        let flag_icon = flags::get_flag_icon(lang_code);
-        let label_text = format!("{} ({})", lang_info.name_english, lang_code);
-        let lang_code_clone = lang_code.clone();
-        let is_selected = selected_language == Some(lang_code.as_str());
-        
-        let lang_button = button(
-            container(
-                row![
-                    flag_icon,
-                    Space::new().width(Length::Fixed(6.0)),
-                    text(label_text).size(13)
-                        .style(move |_theme| iced::widget::text::Style {
-                            color: Some(if is_selected {
-                                Color::WHITE
-                            } else {
-                                Color::from_rgba(1.0, 1.0, 1.0, 0.7)
-                            }),
-                        })
-                ]
-                .align_y(Alignment::Center)
-            )
-            .padding([5.0, 8.0])
-            .width(Length::Fill)
-        )
-        .style(transparent_button_style)
-        .width(Length::Fill)
-        .on_press(Message::OpenVoiceSelection(lang_code_clone));
-        
+    for lang_button in buttons {
         current_row = current_row.push(
             container(lang_button)
                 .width(Length::Fill)
@@ -171,10 +185,132 @@ fn create_language_grid(
         }
         grid_rows = grid_rows.push(current_row);
     }
-    
-    scrollable(grid_rows)
-        .height(Length::Fixed(300.0))
+    grid_rows
+}
+
+/// "Refresh voices" button of a provider's voice section, disabled while the list is fetched.
+fn refresh_voices_button(backend: TTSBackend, loading: bool) -> Element<'static, Message> {
+    let label = if loading { "Refreshing..." } else { "Refresh voices" };
+    button(white_text(label, 12))
+        .style(transparent_button_style)
+        .padding([4.0, 8.0])
+        .on_press_maybe((!loading).then_some(Message::RefreshVoices(backend)))
+        .into()
+}
+
+/// Create a row of the voices chosen most recently, each a `(key, flag code, label)`,
+/// switching back to a voice with a click.
+fn recent_voices_row(voices: Vec<(String, String, String)>) -> Element<'static, Message> {
+    if voices.is_empty() {
+        return column![].into();
+    }
+    let buttons = voices.into_iter().map(|(key, lang_code, label)| {
+        button(
+            row![
+                flags::get_flag_icon(&lang_code),
+                Space::new().width(Length::Fixed(6.0)),
+                text(label).size(12).style(|_theme| iced::widget::text::Style {
+                    color: Some(Color::from_rgba(1.0, 1.0, 1.0, 0.85)),
+                }),
+            ]
+            .align_y(Alignment::Center),
+        )
+        .style(transparent_button_style)
+        .padding([4.0, 8.0])
+        .on_press(Message::VoiceSelected(key))
         .into()
+    });
+    column![
+        white_text("Recently used voices", 12),
+        row(buttons).spacing(4).wrap(),
+    ]
+    .spacing(4)
+    .padding([0.0, 16.0])
+    .into()
+}
+
+/// Create a language selection grid from a list of language codes and info.
+///
+/// Returns a search box, a row of the recently browsed languages and a
+/// scrollable list of collapsible regions (Europe, Americas...), each a grid
+/// with 4 columns showing flag icons and language names. Names are in their
+/// own language when the user prefers it, with the user's locale first.
+/// Each language button opens the voice selection window for that language.
+fn create_language_grid(
+    mut languages: Vec<(String, LanguageInfo)>,
+    app: &App,
+) -> Element<'static, Message> {
+    use crate::voices::labels::{self, WorldRegion};
+
+    let native = app.native_language_names;
+    let selected_language = app.selected_language.as_deref();
+    let searching = !app.language_search.trim().is_empty();
+    labels::sort_by_locale_proximity(&mut languages, crate::system::ui_locale(), native);
+    languages.retain(|(_, info)| labels::matches_search(info, &app.language_search));
+
+    let mut groups = column![].spacing(8);
+    for region in WorldRegion::ALL {
+        let buttons: Vec<Element<'static, Message>> = languages
+            .iter()
+            .filter(|(code, _)| labels::world_region(code) == region)
+            .map(|(code, info)| language_button(code, info, native, selected_language == Some(code.as_str())))
+            .collect();
+        if buttons.is_empty() {
+            continue;
+        }
+        // Search results are shown even in folded regions
+        let expanded = searching || !app.collapsed_language_regions.contains(&region);
+        let header = button(
+            text(format!("{} {} ({})", if expanded { "▾" } else { "▸" }, region.label(), buttons.len()))
+                .size(13)
+                .style(|_theme| iced::widget::text::Style {
+                    color: Some(Color::WHITE),
+                }),
+        )
+        .style(transparent_button_style)
+        .padding([2.0, 4.0])
+        .on_press(Message::LanguageRegionToggled(region));
+        groups = groups.push(header);
+        if expanded {
+            groups = groups.push(language_rows(buttons));
+        }
+    }
+    if languages.is_empty() {
+        groups = groups.push(white_text("No language matches the search", 12));
+    }
+
+    let recent: Vec<Element<'static, Message>> = app
+        .recent_languages
+        .iter()
+        .filter_map(|code| languages.iter().find(|(lang_code, _)| lang_code == code))
+        .map(|(code, info)| language_button(code, info, native, selected_language == Some(code.as_str())))
+        .collect();
+    let recent_row: Element<'static, Message> = if recent.is_empty() || searching {
+        column![].into()
+    } else {
+        column![white_text("Recent", 12), language_rows(recent)].spacing(4).into()
+    };
+
+    column![
+        row![
+            text_input("Search languages", &app.language_search)
+                .on_input(Message::LanguageSearchChanged)
+                .padding(6)
+                .size(12)
+                .width(Length::Fixed(200.0)),
+            Space::new().width(Length::Fixed(12.0)),
+            checkbox(native)
+                .label("Show languages in their own language")
+                .on_toggle(Message::NativeLanguageNamesToggled)
+                .style(white_checkbox_style),
+        ]
+        .align_y(Alignment::Center),
+        Space::new().height(Length::Fixed(6.0)),
+        recent_row,
+        Space::new().height(Length::Fixed(6.0)),
+        scrollable(groups).height(Length::Fixed(300.0)),
+    ]
+    .into()
 }
 
 /// Helper to create red error text with consistent styling.
@@ -188,7 +324,7 @@ fn error_text(content: &str, size: u32) -> text::Text<'_> {
 
 /// Helper to create a close button for modal windows.
 fn close_button<'a>(msg: Message) -> Element<'a, Message> {
-    button(
+    let close = button(
         container(white_text("✕", 18))
             .width(Length::Fixed(28.0))
             .height(Length::Fixed(28.0))
@@ -196,8 +332,8 @@ fn close_button<'a>(msg: Message) -> Element<'a, Message> {
             .center_y(Length::Fixed(28.0)),
     )
     .style(close_button_style)
-    .on_press(msg)
-    .into()
+    .on_press(msg);
+    with_tooltip(close, "Close (Escape)")
 }
 
 /// Helper to create a modal header bar with title and close button.
@@ -225,11 +361,17 @@ pub fn settings_window_view<'a>(app: &'a App) -> Element<'a, Message> {
 
     // Error message display (if present)
     let error_display: Element<'a, Message> = if let Some(error_msg) = &app.error_message {
+        let mut error_content = column![error_text(error_msg, 13).width(Length::Fill)].spacing(8);
+        if let Some(voice_key) = &app.damaged_voice {
+            error_content = error_content.push(
+                button(white_text("Download voice again", 12))
+                    .style(transparent_button_style)
+                    .padding([4.0, 8.0])
+                    .on_press(Message::RepairVoice(voice_key.clone())),
+            );
+        }
         container(
-            container(
-                error_text(error_msg, 13)
-                    .width(Length::Fill)
-            )
+            container(error_content)
             .width(Length::Fill)
             .padding(12)
             .style(error_container_style)
@@ -241,6 +383,23 @@ pub fn settings_window_view<'a>(app: &'a App) -> Element<'a, Message> {
         column![].spacing(0).into()
     };
 
+    // The system voices are only offered where they exist
+    let system_voice = if cfg!(target_os = "macos") {
+        Some(("System Voice (macOS, no setup)", TTSBackend::MacOS))
+    } else if cfg!(target_os = "windows") {
+        Some(("System Voice (Windows, offline)", TTSBackend::Windows))
+    } else {
+        None
+    };
+    let system_radio: Element<'a, Message> = match system_voice {
+        Some((label, backend)) => column![
+            Space::new().height(Length::Fixed(6.0)),
+            radio(label, backend, Some(app.selected_backend), Message::ProviderSelected).style(white_radio_style),
+        ]
+        .into(),
+        None => column![].spacing(0).into(),
+    };
+
     // TTS Provider section
     let provider_controls = column![
         radio(
@@ -280,6 +439,44 @@ pub fn settings_window_view<'a>(app: &'a App) -> Element<'a, Message> {
         ]
         .align_y(Alignment::Center)
         .spacing(0),
+        Space::new().height(Length::Fixed(6.0)),
+        row![
+            radio(
+                "ElevenLabs (Cloud, API key)",
+                TTSBackend::ElevenLabs,
+                Some(app.selected_backend),
+                Message::ProviderSelected
+            )
+            .style(white_radio_style),
+            Space::new().width(Length::Fixed(8.0)),
+            button(
+                container(
+                    white_text("ⓘ", 16)
+                        .style(|_theme| iced::widget::text::Style {
+                            color: Some(Color::from_rgb(0.3, 0.6, 1.0)),
+                        })
+                )
+                .width(Length::Fixed(24.0))
+                .height(Length::Fixed(24.0))
+                .center_x(Length::Fixed(24.0))
+                .center_y(Length::Fixed(24.0))
+            )
+            .style(transparent_button_style)
+            .width(Length::Fixed(24.0))
+            .height(Length::Fixed(24.0))
+            .on_press(Message::OpenElevenLabsInfo),
+        ]
+        .align_y(Alignment::Center)
+        .spacing(0),
+        system_radio,
+        Space::new().height(Length::Fixed(6.0)),
+        radio(
+            "Basic voice (fallback)",
+            TTSBackend::Espeak,
+            Some(app.selected_backend),
+            Message::ProviderSelected
+        )
+        .style(white_radio_style),
     ]
     .spacing(0);
 
@@ -324,10 +521,23 @@ pub fn settings_window_view<'a>(app: &'a App) -> Element<'a, Message> {
                 })
         };
         
+        // Voices chosen before, still downloaded, other than the current one
+        let recent_voices = recent_voices_row(
+            app.recent_voices
+                .iter()
+                .filter(|key| app.selected_voice.as_ref() != Some(*key))
+                .filter(|key| crate::voices::download::is_voice_downloaded(key))
+                .map(|key| match app.voices.as_ref().and_then(|voices| voices.get(key)) {
+                    Some(voice) => (key.clone(), voice.language.code.clone(), format!("{} ({})", voice.name, voice.quality)),
+                    None => (key.clone(), key.split('-').next().unwrap_or(key).to_string(), key.clone()),
+                })
+                .collect(),
+        );
+
         // Get available languages from voices
         let language_controls: Element<'a, Message> = if let Some(ref voices) = app.voices {
             let languages = voices::get_available_languages(voices);
-            create_language_grid(languages, app.selected_language.as_deref()).into()
+            create_language_grid(languages, app).into()
         } else {
             // Voices not loaded yet
             column![
@@ -344,14 +554,22 @@ pub fn settings_window_view<'a>(app: &'a App) -> Element<'a, Message> {
             container(
                 column![
                     // Current voice display
-                    container(current_voice_display)
-                        .width(Length::Fill)
-                        .align_x(Alignment::Start)
-                        .padding([12.0, 16.0]),
+                    container(
+                        row![
+                            current_voice_display,
+                            Space::new().width(Length::Fill),
+                            refresh_voices_button(TTSBackend::Piper, app.voices_loading),
+                        ]
+                        .align_y(Alignment::Center)
+                    )
+                    .width(Length::Fill)
+                    .align_x(Alignment::Start)
+                    .padding([12.0, 16.0]),
+                    recent_voices,
                     // Language grid below
                     container(language_controls)
                         .width(Length::Fill)
-                        .padding([0.0, 16.0]),
+                        .padding([8.0, 16.0]),
                 ]
                 .spacing(0)
             )
@@ -398,22 +616,43 @@ pub fn settings_window_view<'a>(app: &'a App) -> Element<'a, Message> {
                     })
             };
             
+            // Voices chosen before, other than the current one
+            let recent_voices = recent_voices_row(
+                app.recent_voices
+                    .iter()
+                    .filter(|key| app.selected_polly_voice.as_ref() != Some(*key))
+                    .filter_map(|key| voices.get(key).map(|voice| (key, voice)))
+                    .map(|(key, voice)| {
+                        let engine = key.split_once(':').map_or(voice.engine.as_str(), |(_, engine)| engine);
+                        (key.clone(), voice.language.code.clone(), format!("{} ({})", voice.name, engine_display_name(engine)))
+                    })
+                    .collect(),
+            );
+
             // Get available languages from AWS voices
             let languages = aws::get_available_languages(voices);
-            let language_controls: Element<'a, Message> = create_language_grid(languages, app.selected_language.as_deref()).into();
+            let language_controls: Element<'a, Message> = create_language_grid(languages, app).into();
             
             container(
                 container(
                     column![
                         // Current voice display
-                        container(current_voice_display)
-                            .width(Length::Fill)
-                            .align_x(Alignment::Start)
-                            .padding([12.0, 16.0]),
+                        container(
+                            row![
+                                current_voice_display,
+                                Space::new().width(Length::Fill),
+                                refresh_voices_button(TTSBackend::AwsPolly, app.polly_voices_loading),
+                            ]
+                            .align_y(Alignment::Center)
+                        )
+                        .width(Length::Fill)
+                        .align_x(Alignment::Start)
+                        .padding([12.0, 16.0]),
+                        recent_voices,
                         // Language grid below
                         container(language_controls)
                             .width(Length::Fill)
-                            .padding([0.0, 16.0]),
+                            .padding([8.0, 16.0]),
                     ]
                     .spacing(0)
                 )
@@ -423,13 +662,218 @@ pub fn settings_window_view<'a>(app: &'a App) -> Element<'a, Message> {
             .width(Length::Fill)
             .into()
         } else {
-            // No voices loaded - don't show anything
-            column![].spacing(0).into()
+            // No voices loaded (e.g. credentials not set up yet): offer to check again
+            container(row![Space::new().width(Length::Fill), refresh_voices_button(TTSBackend::AwsPolly, app.polly_voices_loading)])
+                .padding([0.0, 16.0])
+                .width(Length::Fill)
+                .into()
         }
     } else {
         column![].spacing(0).into()
     };
 
+    // ElevenLabs section (only shown when ElevenLabs is selected): API key and voice list
+    let elevenlabs_section: Element<'a, Message> = if app.selected_backend == TTSBackend::ElevenLabs {
+        let dim = |_theme: &iced::Theme| iced::widget::text::Style {
+            color: Some(Color::from_rgba(1.0, 1.0, 1.0, 0.7)),
+        };
+        let api_key_row = row![
+            white_text("API key", 13),
+            Space::new().width(Length::Fixed(12.0)),
+            text_input("From elevenlabs.io, Profile → API Keys", &app.elevenlabs_api_key)
+                .on_input(Message::ElevenLabsApiKeyChanged)
+                .secure(true)
+                .padding(6)
+                .size(12)
+                .width(Length::Fixed(320.0)),
+        ]
+        .align_y(Alignment::Center);
+
+        let voices = app.elevenlabs_voices.as_deref().unwrap_or_default();
+        let current_voice_display = match app.selected_elevenlabs_voice {
+            Some(ref id) => {
+                let name = voices.iter().find(|voice| voice.id == *id).map_or(id.as_str(), |voice| voice.name.as_str());
+                text(format!("ElevenLabs voice selected: {name}")).size(14).style(dim)
+            }
+            None => text("No voice selected (Rachel is used)").size(14).style(dim),
+        };
+
+        let voice_list: Element<'a, Message> = if let Some(ref error_msg) = app.elevenlabs_error_message {
+            container(error_text(error_msg, 13).width(Length::Fill))
+                .width(Length::Fill)
+                .padding(12)
+                .style(error_container_style)
+                .into()
+        } else if voices.is_empty() {
+            let hint = if app.elevenlabs_voices_loading {
+                "Loading voices..."
+            } else {
+                "Enter your API key and apply, then refresh the voices."
+            };
+            white_text(hint, 12).style(dim).into()
+        } else {
+            let rows = voices.iter().map(|voice| {
+                let selected = app.selected_elevenlabs_voice.as_deref() == Some(voice.id.as_str());
+                let description = voice.description();
+                let label = if description.is_empty() {
+                    voice.name.clone()
+                } else {
+                    format!("{} · {description}", voice.name)
+                };
+                button(text(label).size(13).style(move |_theme| iced::widget::text::Style {
+                    color: Some(if selected {
+                        Color::from_rgb(0.3, 0.6, 1.0)
+                    } else {
+                        Color::from_rgba(1.0, 1.0, 1.0, 0.85)
+                    }),
+                }))
+                .style(transparent_button_style)
+                .width(Length::Fill)
+                .padding([4.0, 8.0])
+                .on_press(Message::VoiceSelected(voice.id.clone()))
+                .into()
+            });
+            scrollable(column(rows).spacing(2)).height(Length::Fixed(220.0)).into()
+        };
+
+        container(
+            container(
+                column![
+                    api_key_row,
+                    row![
+                        current_voice_display,
+                        Space::new().width(Length::Fill),
+                        refresh_voices_button(TTSBackend::ElevenLabs, app.elevenlabs_voices_loading),
+                    ]
+                    .align_y(Alignment::Center),
+                    voice_list,
+                ]
+                .spacing(12)
+                .padding([12.0, 16.0])
+            )
+            .style(section_style)
+        )
+        .padding([16, 16])
+        .width(Length::Fill)
+        .into()
+    } else {
+        column![].spacing(0).into()
+    };
+
+    // System voice section (only shown when the system voices are selected): voice list
+    let system_voice_section: Element<'a, Message> = if app.selected_backend.is_system() {
+        let dim = |_theme: &iced::Theme| iced::widget::text::Style {
+            color: Some(Color::from_rgba(1.0, 1.0, 1.0, 0.7)),
+        };
+        let (selected_voice, default_voice, voice_settings) = match app.selected_backend {
+            TTSBackend::Windows => (
+                &app.selected_windows_voice,
+                "No voice selected (the Windows default voice is used)",
+                "More voices can be added in Settings → Time & language → Speech.",
+            ),
+            _ => (
+                &app.selected_macos_voice,
+                "No voice selected (the macOS system voice is used)",
+                "More voices can be added in System Settings → Accessibility → Spoken Content.",
+            ),
+        };
+        let voices = app.system_voices.as_deref().unwrap_or_default();
+        let current_voice_display = match selected_voice {
+            Some(ref id) => {
+                let name = voices.iter().find(|voice| voice.id == *id).map_or(id.as_str(), |voice| voice.name.as_str());
+                text(format!("System voice selected: {name}")).size(14).style(dim)
+            }
+            None => text(default_voice).size(14).style(dim),
+        };
+
+        let voice_list: Element<'a, Message> = if let Some(ref error_msg) = app.system_voices_error {
+            container(error_text(error_msg, 13).width(Length::Fill))
+                .width(Length::Fill)
+                .padding(12)
+                .style(error_container_style)
+                .into()
+        } else if voices.is_empty() {
+            white_text("Loading voices...", 12).style(dim).into()
+        } else {
+            let rows = voices.iter().map(|voice| {
+                let selected = selected_voice.as_deref() == Some(voice.id.as_str());
+                button(text(format!("{} · {}", voice.name, voice.language)).size(13).style(move |_theme| {
+                    iced::widget::text::Style {
+                        color: Some(if selected {
+                            Color::from_rgb(0.3, 0.6, 1.0)
+                        } else {
+                            Color::from_rgba(1.0, 1.0, 1.0, 0.85)
+                        }),
+                    }
+                }))
+                .style(transparent_button_style)
+                .width(Length::Fill)
+                .padding([4.0, 8.0])
+                .on_press(Message::VoiceSelected(voice.id.clone()))
+                .into()
+            });
+            scrollable(column(rows).spacing(2)).height(Length::Fixed(220.0)).into()
+        };
+
+        container(
+            container(
+                column![
+                    row![
+                        current_voice_display,
+                        Space::new().width(Length::Fill),
+                        refresh_voices_button(app.selected_backend, false),
+                    ]
+                    .align_y(Alignment::Center),
+                    white_text(voice_settings, 12).style(dim),
+                    voice_list,
+                ]
+                .spacing(12)
+                .padding([12.0, 16.0])
+            )
+            .style(section_style)
+        )
+        .padding([16, 16])
+        .width(Length::Fill)
+        .into()
+    } else {
+        column![].spacing(0).into()
+    };
+
+    // Pitch (Piper and Polly only): Piper's audio is shifted, Polly is asked in SSML
+    let pitch_section: Element<'a, Message> = if matches!(app.selected_backend, TTSBackend::Piper | TTSBackend::AwsPolly) {
+        let max = config::MAX_PITCH_SEMITONES;
+        let label = match app.pitch_semitones {
+            0 => "Pitch: voice's own".to_string(),
+            semitones => format!("Pitch: {semitones:+} semitones"),
+        };
+        let hint = if app.selected_backend == TTSBackend::AwsPolly {
+            "Only standard Polly voices can change pitch; the others keep their own."
+        } else {
+            "Raises or lowers the voice without changing its speed."
+        };
+        container(
+            container(
+                column![
+                    white_text(label, 12),
+                    slider(-max as f32..=max as f32, app.pitch_semitones as f32, |semitones| {
+                        Message::PitchChanged(semitones.round() as i32)
+                    })
+                    .step(1.0)
+                    .width(Length::Fixed(220.0)),
+                    white_text(hint, 11),
+                ]
+                .spacing(4)
+                .padding([12.0, 16.0])
+            )
+            .style(section_style)
+        )
+        .padding([16, 16])
+        .width(Length::Fill)
+        .into()
+    } else {
+        column![].spacing(0).into()
+    };
+
     let provider_section = container(
         column![
             row![
@@ -453,6 +897,9 @@ pub fn settings_window_view<'a>(app: &'a App) -> Element<'a, Message> {
             polly_error_display,
             piper_voice_section,
             polly_voice_section,
+            elevenlabs_section,
+            system_voice_section,
+            pitch_section,
         ]
         .spacing(8)
     )
@@ -558,28 +1005,157 @@ pub fn settings_window_view<'a>(app: &'a App) -> Element<'a, Message> {
     )
     .style(section_style);
 
-    // OCR section
-    // Platform-specific OCR backend label
-    let default_ocr_label = {
-        #[cfg(target_os = "macos")]
-        {
-            "Standard OCR (Apple Vision Framework, local)"
-        }
-        #[cfg(target_os = "linux")]
-        {
-            "Standard OCR (EasyOCR, local)"
-        }
-        #[cfg(target_os = "windows")]
-        {
-            "Standard OCR (Windows Ocr2, local)"
-        }
-        #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
-        {
-            "Standard OCR (local)"
-        }
+    // Text Source section
+    let source_radio = |label: &'static str, priority: SourcePriority| {
+        radio(label, priority, Some(app.source_priority), Message::SourcePrioritySelected).style(white_radio_style)
     };
-    
-    let ocr_controls = column![
+    let text_source_controls = column![
+        source_radio("Selection, then clipboard, then offer OCR", SourcePriority::SelectionFirst),
+        Space::new().height(Length::Fixed(6.0)),
+        source_radio("Clipboard, then selection, then offer OCR", SourcePriority::ClipboardFirst),
+        Space::new().height(Length::Fixed(6.0)),
+        source_radio("Always ask", SourcePriority::AlwaysAsk),
+    ]
+    .spacing(0);
+
+    let text_source_section = container(
+        row![
+            container(
+                white_text("Text Source", 14)
+                    .style(|_theme| iced::widget::text::Style {
+                        color: Some(Color::from_rgba(1.0, 1.0, 1.0, 0.85)),
+                    })
+            )
+            .width(Length::Fixed(120.0))
+            .align_x(Alignment::Start),
+            Space::new().width(Length::Fixed(16.0)),
+            container(text_source_controls)
+                .width(Length::Fill)
+                .align_x(Alignment::Start),
+        ]
+        .align_y(Alignment::Center)
+        .width(Length::Fill)
+        .padding([12.0, 16.0])
+    )
+    .style(section_style);
+
+    // Long Text section
+    let long_text_controls = column![
+        radio("Ask each time", LongTextAction::Ask, Some(app.long_text_action), Message::LongTextActionSelected)
+            .style(white_radio_style),
+        Space::new().height(Length::Fixed(6.0)),
+        radio(
+            format!("Read first {} paragraphs", app.long_text_paragraphs),
+            LongTextAction::ReadFirst,
+            Some(app.long_text_action),
+            Message::LongTextActionSelected
+        )
+        .style(white_radio_style),
+        Space::new().height(Length::Fixed(4.0)),
+        slider(
+            1.0..=config::MAX_LONG_TEXT_PARAGRAPHS as f32,
+            app.long_text_paragraphs as f32,
+            |paragraphs| Message::LongTextParagraphsChanged(paragraphs as usize)
+        )
+        .step(1.0)
+        .width(Length::Fixed(220.0)),
+        Space::new().height(Length::Fixed(6.0)),
+        radio("Read all", LongTextAction::ReadAll, Some(app.long_text_action), Message::LongTextActionSelected)
+            .style(white_radio_style),
+        Space::new().height(Length::Fixed(10.0)),
+        checkbox(app.interrupt_readings)
+            .label("Let short selections interrupt a long reading")
+            .on_toggle(Message::InterruptReadingsToggled)
+            .style(white_checkbox_style),
+        Space::new().height(Length::Fixed(4.0)),
+        white_text("The long reading pauses while the short text is read, then carries on where it was.", 11),
+    ]
+    .spacing(0);
+
+    let long_text_section = container(
+        row![
+            container(
+                white_text("Long Text", 14)
+                    .style(|_theme| iced::widget::text::Style {
+                        color: Some(Color::from_rgba(1.0, 1.0, 1.0, 0.85)),
+                    })
+            )
+            .width(Length::Fixed(120.0))
+            .align_x(Alignment::Start),
+            Space::new().width(Length::Fixed(16.0)),
+            container(long_text_controls)
+                .width(Length::Fill)
+                .align_x(Alignment::Start),
+        ]
+        .align_y(Alignment::Center)
+        .width(Length::Fill)
+        .padding([12.0, 16.0])
+    )
+    .style(section_style);
+
+    // Repeated capture section
+    let duplicate_capture_controls = column![
+        radio(
+            "Replay the last reading",
+            DuplicateCaptureAction::Replay,
+            Some(app.duplicate_capture_action),
+            Message::DuplicateCaptureActionSelected
+        )
+        .style(white_radio_style),
+        Space::new().height(Length::Fixed(6.0)),
+        radio(
+            "Ignore",
+            DuplicateCaptureAction::Ignore,
+            Some(app.duplicate_capture_action),
+            Message::DuplicateCaptureActionSelected
+        )
+        .style(white_radio_style),
+    ]
+    .spacing(0);
+
+    let duplicate_capture_section = container(
+        row![
+            container(
+                white_text("Same Text Again", 14)
+                    .style(|_theme| iced::widget::text::Style {
+                        color: Some(Color::from_rgba(1.0, 1.0, 1.0, 0.85)),
+                    })
+            )
+            .width(Length::Fixed(120.0))
+            .align_x(Alignment::Start),
+            Space::new().width(Length::Fixed(16.0)),
+            container(duplicate_capture_controls)
+                .width(Length::Fill)
+                .align_x(Alignment::Start),
+        ]
+        .align_y(Alignment::Center)
+        .width(Length::Fill)
+        .padding([12.0, 16.0])
+    )
+    .style(section_style);
+
+    // OCR section
+    // Platform-specific OCR backend label
+    let default_ocr_label = {
+        #[cfg(target_os = "macos")]
+        {
+            "Standard OCR (Apple Vision Framework, local)"
+        }
+        #[cfg(target_os = "linux")]
+        {
+            "Standard OCR (EasyOCR, local)"
+        }
+        #[cfg(target_os = "windows")]
+        {
+            "Standard OCR (Windows Ocr2, local)"
+        }
+        #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+        {
+            "Standard OCR (local)"
+        }
+    };
+    
+    let ocr_controls = column![
         radio(
             default_ocr_label,
             OCRBackend::Default,
@@ -648,23 +1224,90 @@ pub fn settings_window_view<'a>(app: &'a App) -> Element<'a, Message> {
     )
     .style(section_style);
 
+    let mut sections: Vec<(SettingsSection, Element<'a, Message>)> = vec![
+        (SettingsSection::Appearance, appearance::appearance_settings_section(app)),
+        (SettingsSection::Reading, reading::reading_settings_section(app)),
+        (SettingsSection::TextSource, text_source_section.into()),
+        (SettingsSection::LongText, long_text_section.into()),
+        (SettingsSection::SameTextAgain, duplicate_capture_section.into()),
+        (SettingsSection::Privacy, privacy::privacy_settings_section(app)),
+        (SettingsSection::Provider, provider_section.into()),
+        (SettingsSection::Acceleration, acceleration::acceleration_settings_section(app)),
+        (SettingsSection::Power, power::power_settings_section(app)),
+        (SettingsSection::SpokenText, spoken_text::spoken_text_settings_section(app)),
+        (SettingsSection::NaturalReading, text_cleanup_section.into()),
+        (SettingsSection::Hotkeys, hotkeys::hotkey_settings_section(app)),
+        (SettingsSection::Ocr, ocr_section.into()),
+        (SettingsSection::AudioCues, audio_cues::audio_cues_settings_section(app)),
+        (SettingsSection::PodcastFeed, feed::podcast_feed_settings_section(app)),
+        (SettingsSection::HistorySync, sync::history_sync_settings_section(app)),
+        (SettingsSection::WatchFolder, watch::watch_folder_settings_section(app)),
+        (SettingsSection::Storage, storage::storage_settings_section(app)),
+        (SettingsSection::Plugins, plugins::plugins_settings_section(app)),
+        (SettingsSection::LogLevel, log_level_section.into()),
+    ];
+    let visible = tabs::visible_sections(app);
+    sections.retain(|(section, _)| visible.contains(section));
+
+    let section_list: Element<'a, Message> = if sections.is_empty() {
+        white_text("No matching settings", 13).into()
+    } else {
+        column(sections.into_iter().map(|(section, element)| {
+            column![
+                element,
+                row![
+                    Space::new().width(Length::Fill),
+                    button(white_text("Restore defaults", 11))
+                        .style(transparent_button_style)
+                        .padding([2.0, 4.0])
+                        .on_press(Message::ResetSectionToDefaults(section)),
+                ],
+            ]
+            .spacing(2)
+            .into()
+        }))
+        .spacing(8)
+        .into()
+    };
+
+    // Changes apply right away as a preview, and are saved with Apply
+    let has_changes = config::has_staged_changes();
+    let footer = container(
+        row![
+            button(white_text("Reset to defaults", 12))
+                .style(circle_button_style)
+                .padding([5.0, 12.0])
+                .on_press(Message::ResetSettingsToDefaults),
+            Space::new().width(Length::Fill),
+            white_text(if has_changes { "Unsaved changes" } else { "" }, 12),
+            Space::new().width(Length::Fixed(12.0)),
+            button(white_text("Cancel", 12))
+                .style(circle_button_style)
+                .padding([5.0, 12.0])
+                .on_press(Message::CloseSettings),
+            Space::new().width(Length::Fixed(8.0)),
+            button(white_text("Apply", 12))
+                .style(circle_button_style)
+                .padding([5.0, 12.0])
+                .on_press_maybe(has_changes.then_some(Message::ApplySettings)),
+        ]
+        .align_y(Alignment::Center),
+    )
+    .width(Length::Fill)
+    .padding([10.0, 24.0])
+    .style(header_style);
+
     container(
         column![
             modal_header("Settings", Message::CloseSettings),
+            container(tabs::settings_tab_bar(app))
+                .width(Length::Fill)
+                .padding([10.0, 24.0])
+                .style(header_style),
             // Scrollable content area
             scrollable(
                 container(
-                    column![
-                        hotkeys::hotkey_settings_section(app),
-                        Space::new().height(Length::Fixed(12.0)),
-                        ocr_section,
-                        Space::new().height(Length::Fixed(12.0)),
-                        text_cleanup_section,
-                        Space::new().height(Length::Fixed(12.0)),
-                        provider_section,
-                        Space::new().height(Length::Fixed(12.0)),
-                        log_level_section,
-                    ]
+                    column![section_list]
                     .padding([20.0, 24.0])
                     .spacing(0)
                     .align_x(Alignment::Start),
@@ -677,6 +1320,7 @@ pub fn settings_window_view<'a>(app: &'a App) -> Element<'a, Message> {
             )
             .width(Length::Fill)
             .height(Length::Fill),
+            footer,
         ]
         .spacing(0)
         .width(Length::Fill)
@@ -697,27 +1341,135 @@ pub fn settings_window_view<'a>(app: &'a App) -> Element<'a, Message> {
 /// │  [vol] ||||||||  [-5s] [+5s] [▶] [■]          [⚙]   │
 /// │  ════════════════════════════════════════════════    │
 /// └──────────────────────────────────────────────────────┘
-pub fn main_view(app: &App) -> Element<'_, Message> {
-    // 1. Waveform: 10 vertical bars
-    let waveform: Element<Message> = row((0..NUM_BARS)
-        .map(|i| {
-            let amplitude = app.frequency_bands.get(i).copied().unwrap_or(0.0);
-            let height = bar_height(amplitude);
+/// Main bar, also used for mini player windows (which get a close button).
+/// Pixels of touchpad scrolling counted as one scroll wheel line.
+const PIXELS_PER_LINE: f32 = 40.0;
+
+/// Message for scrolling over a main bar control: sideways changes the speed,
+/// up and down goes to `vertical` with the lines scrolled.
+fn bar_scroll(delta: iced::mouse::ScrollDelta, vertical: fn(f32) -> Message) -> Message {
+    let (x, y) = match delta {
+        iced::mouse::ScrollDelta::Lines { x, y } => (x, y),
+        iced::mouse::ScrollDelta::Pixels { x, y } => (x / PIXELS_PER_LINE, y / PIXELS_PER_LINE),
+    };
+    if x.abs() > y.abs() {
+        Message::SpeedScrolled(x)
+    } else {
+        vertical(y)
+    }
+}
+
+/// Provider and voice speaking, for the main bar tooltip ("Piper · Lessac (medium)").
+fn current_voice_label(app: &App) -> String {
+    match app.selected_backend {
+        TTSBackend::Piper => {
+            let voice = match app.selected_voice {
+                Some(ref key) => match app.voices.as_ref().and_then(|voices| voices.get(key)) {
+                    Some(voice) => format!("{} ({})", voice.name, voice.quality),
+                    None => key.clone(),
+                },
+                None => "default voice".to_string(),
+            };
+            format!("Piper · {voice}")
+        }
+        TTSBackend::AwsPolly => {
+            let voice = match app.selected_polly_voice.as_deref().map(|key| key.split_once(':').unwrap_or((key, ""))) {
+                Some((id, "")) => id.to_string(),
+                Some((id, engine)) => format!("{id} ({})", engine_display_name(engine)),
+                None => "default voice".to_string(),
+            };
+            format!("AWS Polly · {voice}")
+        }
+        TTSBackend::ElevenLabs => {
+            let voice = match app.selected_elevenlabs_voice {
+                Some(ref id) => match app.elevenlabs_voices.as_ref().and_then(|voices| voices.iter().find(|voice| voice.id == *id)) {
+                    Some(voice) => voice.name.clone(),
+                    None => id.clone(),
+                },
+                None => "default voice".to_string(),
+            };
+            format!("ElevenLabs · {voice}")
+        }
+        TTSBackend::MacOS | TTSBackend::Windows => {
+            let (selected_voice, system) = match app.selected_backend {
+                TTSBackend::Windows => (&app.selected_windows_voice, "Windows"),
+                _ => (&app.selected_macos_voice, "macOS"),
+            };
+            let voice = match selected_voice {
+                Some(ref id) => match app.system_voices.as_ref().and_then(|voices| voices.iter().find(|voice| voice.id == *id)) {
+                    Some(voice) => voice.name.clone(),
+                    // Identifiers end with the voice name ("com.apple.voice.compact.en-US.Samantha")
+                    None => id.rsplit(['.', '\\']).next().unwrap_or(id).to_string(),
+                },
+                None => "system voice".to_string(),
+            };
+            format!("{system} · {voice}")
+        }
+        TTSBackend::Espeak => "Basic voice (espeak-ng)".to_string(),
+    }
+}
+
+/// Playback position and length as "m:ss / m:ss", as of the last Tick.
+fn progress_time_label(app: &App) -> String {
+    let (position, duration) = app.playback_time;
+    let minutes_seconds = |time: std::time::Duration| {
+        let secs = time.as_secs_f32().round() as u64;
+        format!("{}:{:02}", secs / 60, secs % 60)
+    };
+    format!("{} / {}", minutes_seconds(position.min(duration)), minutes_seconds(duration))
+}
+
+pub fn main_view(app: &App, window: iced::window::Id) -> Element<'_, Message> {
+    trace_throttled!(?window, activity = ?app.activity, "Rendering main bar");
+    // 1. Waveform in the chosen style (live bands from the visualizer while
+    //    playing, otherwise the loading animation or silence)
+    let live_bands = app.activity.has_speech().then(crate::providers::current_bands);
+    let band = |i: usize| match live_bands {
+        Some(ref bands) => bands.get(i).copied().unwrap_or(0.0),
+        None => app.frequency_bands.get(i).copied().unwrap_or(0.0),
+    };
+    let wave_bar = |amplitude: f32| -> Element<'static, Message> {
+        container(
+            Space::new()
+                .width(Length::Fixed(3.0))
+                .height(Length::Fixed(bar_height(amplitude))),
+        )
+        .style(wave_bar_style)
+        .into()
+    };
+    let waveform_width = NUM_BARS as f32 * 3.0 + (NUM_BARS - 1) as f32 * 4.0;
+    let waveform: Element<Message> = match app.visualizer_style {
+        VisualizerStyle::Bars => row((0..NUM_BARS).map(|i| wave_bar(band(i))))
+            .spacing(4)
+            .align_y(Alignment::Center)
+            .into(),
+        // The lower half of the bands, lowest in the middle
+        VisualizerStyle::Mirrored => row((0..NUM_BARS).map(|i| {
+            let half = NUM_BARS / 2;
+            wave_bar(band(if i < half { half - 1 - i } else { i - half }))
+        }))
+        .spacing(4)
+        .align_y(Alignment::Center)
+        .into(),
+        VisualizerStyle::Level => {
+            // Live playback publishes the level as the first band
+            let level = match live_bands {
+                Some(ref bands) => bands[0],
+                None => app.frequency_bands.iter().sum::<f32>() / NUM_BARS as f32,
+            };
             container(
-                Space::new()
-                    .width(Length::Fixed(3.0))
-                    .height(Length::Fixed(height)),
+                container(Space::new().width(Length::Fixed(waveform_width * level.clamp(0.0, 1.0))).height(Length::Fixed(4.0)))
+                    .style(wave_bar_style),
             )
-            .style(wave_bar_style)
+            .width(Length::Fixed(waveform_width))
+            .align_y(Alignment::Center)
             .into()
-        })
-        .collect::<Vec<Element<Message>>>())
-    .spacing(4)
-    .align_y(Alignment::Center)
-    .into();
+        }
+        VisualizerStyle::Off => Space::new().width(Length::Fixed(waveform_width)).into(),
+    };
 
     // 2. Play/pause icon
-    let play_pause_icon: Element<Message> = if app.playback_state == PlaybackState::Playing {
+    let play_pause_icon: Element<Message> = if app.activity == Activity::Playing {
         pause_icon(16.0).into()
     } else {
         play_icon(16.0).into()
@@ -725,30 +1477,75 @@ pub fn main_view(app: &App) -> Element<'_, Message> {
 
     // 3. Control buttons row
     let controls = row![
-        circle_button(white_text("-5s", 12), Message::SkipBackward),
-        circle_button(white_text("+5s", 12), Message::SkipForward),
-        circle_button(play_pause_icon, Message::PlayPause),
-        circle_button(stop_icon(16.0), Message::Stop),
-        circle_button(camera_icon(16.0), Message::ScreenshotRequested),
+        with_tooltip(circle_button(white_text("-5s", 12), Message::SkipBackward), control_label("Back 5 seconds")),
+        with_tooltip(circle_button(white_text("+5s", 12), Message::SkipForward), control_label("Forward 5 seconds")),
+        with_tooltip(circle_button(play_pause_icon, Message::PlayPause), control_label("Play or pause")),
+        with_tooltip(circle_button(stop_icon(16.0), Message::Stop), control_label("Stop")),
+        with_tooltip(
+            circle_button(
+                text(format!("{}×", app.playback_speed)).size(11).style(|_theme| iced::widget::text::Style {
+                    color: Some(Color::from_rgba(1.0, 1.0, 1.0, 0.85)),
+                }),
+                Message::SpeedCycled
+            ),
+            "Speed (click for the next, scroll sideways to fine-tune)"
+        ),
+        with_tooltip(circle_button(camera_icon(16.0), Message::ScreenshotRequested), control_label("Capture screen text")),
     ]
     .spacing(6)
     .align_y(Alignment::Center);
 
     // 4. Base content row (without gear): [volume] [waveform] [controls]
-    let content_row = row![
-        volume_icon(28.0),
-        Space::new().width(Length::Fixed(12.0)),
-        waveform,
-        Space::new().width(Length::Fixed(12.0)),
-        controls,
-    ]
+    //    Scrolling over the volume icon and waveform changes the volume, and
+    //    clicking the icon swaps the waveform and controls for a volume slider
+    let volume_percent = format!("Volume {:.0}%", app.playback_volume * 100.0);
+    let volume_button = with_tooltip(
+        button(volume_icon(28.0))
+            .style(transparent_button_style)
+            .padding([0.0, 0.0])
+            .on_press(Message::VolumePopoverToggled(window)),
+        volume_percent.clone(),
+    );
+    let content_row = if app.volume_popover_window == Some(window) {
+        let volume_slider = slider(0.0..=1.0, app.playback_volume, Message::VolumeChanged)
+            .on_release(Message::VolumeReleased)
+            .step(0.01)
+            .width(Length::Fixed(200.0));
+        row![
+            mouse_area(volume_button).on_scroll(|delta| bar_scroll(delta, Message::VolumeScrolled)),
+            Space::new().width(Length::Fixed(12.0)),
+            volume_slider,
+            Space::new().width(Length::Fixed(12.0)),
+            text(volume_percent).size(12).style(|_theme| iced::widget::text::Style {
+                color: Some(Color::from_rgba(1.0, 1.0, 1.0, 0.85)),
+            }),
+        ]
+    } else {
+        let voice_label = match app.playback_speed {
+            speed if speed != 1.0 => format!("{} · {speed}×", current_voice_label(app)),
+            _ => current_voice_label(app),
+        };
+        let sound = mouse_area(row![
+            volume_button,
+            Space::new().width(Length::Fixed(12.0)),
+            with_tooltip(waveform, voice_label),
+        ]
+        .align_y(Alignment::Center))
+        .on_scroll(|delta| bar_scroll(delta, Message::VolumeScrolled));
+        row![
+            sound,
+            Space::new().width(Length::Fixed(12.0)),
+            controls,
+        ]
+    }
     .align_y(Alignment::Center)
     .padding([8.0, 16.0]);
 
     // 5. Progress bar OR status text directly under the content row (not under gear)
     // Progress bar extends from left edge of content_row to right edge of screenshot button
-    let (progress_or_status, gap_height): (Element<Message>, f32) = if let Some(status) = &app.status_text {
-        // Show status text during loading (pushed up above where progress bar would be)
+    let (progress_or_status, gap_height): (Element<Message>, f32) = if let Some(status) = app.activity.status().or(app.status_text.as_deref()) {
+        // Show the status while preparing, downloading or after a failure, or a
+        // note (pushed up above where progress bar would be)
         let elem = container(
             text(status)
                 .size(11)
@@ -764,12 +1561,35 @@ pub fn main_view(app: &App) -> Element<'_, Message> {
     } else {
         // Show progress bar during playback (stays in same position)
         // Extends from left padding (16.0) to end of screenshot button
-        let elem = container(progress_bar(0.0..=1.0, app.progress))
-            .width(Length::Fill)
-            .height(Length::Fixed(1.0))
-            .padding([0.0, 16.0])
-            .into();
-        (elem, 3.0)
+        let progress_height = crate::styles::color_scheme().progress_height;
+        // Scrolling over the progress bar moves between sentences
+        let time_label = if app.activity.has_speech() { progress_time_label(app) } else { String::new() };
+        let elem = container(
+            row![
+                mouse_area(
+                    progress_bar(0.0..=1.0, app.progress)
+                        .girth(Length::Fixed(progress_height))
+                        .style(progress_bar_style),
+                )
+                .on_scroll(|delta| bar_scroll(delta, Message::SentenceScrolled)),
+                // Fixed width so the bar keeps its length as the time changes
+                container(
+                    text(time_label)
+                        .size(10)
+                        .style(|_theme| iced::widget::text::Style {
+                            color: Some(Color::from_rgba(1.0, 1.0, 1.0, 0.6)),
+                        }),
+                )
+                .align_right(Length::Fixed(72.0)),
+            ]
+            .spacing(8)
+            .align_y(Alignment::Center),
+        )
+        .width(Length::Fill)
+        .height(Length::Fixed(12.0))
+        .padding([0.0, 16.0])
+        .into();
+        (elem, -1.0)
     };
 
     let content_column = column![
@@ -780,28 +1600,50 @@ pub fn main_view(app: &App) -> Element<'_, Message> {
     .width(Length::Shrink);
 
     // 6. Settings gear (transparent button) on the right
-    let settings_btn = button(settings_icon(18.0))
-        .style(transparent_button_style)
-        .padding([0.0, 0.0])
-        .on_press(Message::Settings);
+    let settings_btn = with_tooltip(
+        button(settings_icon(18.0))
+            .style(transparent_button_style)
+            .padding([0.0, 0.0])
+            .on_press(Message::Settings),
+        control_label("Settings"),
+    );
 
-    // 7. Final row: [content_column | spacer | gear], centered with padding
-    let content = row![
+    // 7. Final row: [content_column | spacer | gear (| close on mini players)], centered with padding
+    let mut content = row![
         content_column,
         Space::new().width(Length::Fill),
         settings_btn,
     ]
     .align_y(Alignment::Center)
     .padding([4.0, 10.0]); // [top/bottom, left/right]
+    if app.mini_bar_window_ids.contains(&window) {
+        content = content.push(Space::new().width(Length::Fixed(6.0))).push(with_tooltip(
+            button(white_text("×", 16))
+                .style(transparent_button_style)
+                .padding([0.0, 0.0])
+                .on_press(Message::CloseMiniBar(window)),
+            "Close mini player",
+        ));
+    }
+
+    // 8. The right-click menu takes the place of the controls while open
+    let content: Element<Message> = if app.bar_menu_window == Some(window) {
+        crate::ui::context_menu::bar_menu(app)
+    } else {
+        content.into()
+    };
 
-    // 8. Outer container with window styling, wrapped in mouse_area for dragging
+    // 9. Outer container with window styling, wrapped in mouse_area for dragging,
+    //    the right-click menu and middle-click play/pause
     mouse_area(
         container(content)
             .width(Length::Fill)
             .height(Length::Fill)
             .style(window_style),
     )
-    .on_press(Message::StartDrag)
+    .on_press(Message::StartDrag(window))
+    .on_right_press(Message::ShowBarMenu(window))
+    .on_middle_press(Message::PlayPause)
     .into()
 }
 
@@ -1028,10 +1870,12 @@ pub fn voice_selection_window_view<'a>(app: &'a App) -> Element<'a, Message> {
                     .find(|voice| voice.language.code == *lang_code)
                     .map(|voice| voice.language.clone())
             }),
+            // ElevenLabs and system voices are not browsed by language
+            TTSBackend::ElevenLabs | TTSBackend::MacOS | TTSBackend::Windows | TTSBackend::Espeak => None,
         };
         
         let name = if let Some(lang_info) = lang_info {
-            format!("{} ({})", lang_info.name_english, lang_code)
+            crate::voices::labels::language_label(&lang_info, app.native_language_names)
         } else {
             lang_code.to_string()
         };
@@ -1124,6 +1968,9 @@ pub fn polly_info_window_view<'a>(_app: &'a App) -> Element<'a, Message> {
                                 • Neural voices: 1 million characters per month (first 12 months)\n\
                                 • Long-Form voices: 500 thousand characters per month (first 12 months)\n\
                                 • Generative voices: 100 thousand characters per month (first 12 months)\n\n\
+                                While the reading view is open, Polly is also asked when each word is spoken, to highlight it. \
+                                These requests are billed for the same characters as the audio, so text read with the reading view \
+                                open costs about twice as much.\n\n\
                                 Please review AWS pricing before using this service to understand potential charges.",
                                 13
                             )
@@ -1173,6 +2020,81 @@ pub fn polly_info_window_view<'a>(_app: &'a App) -> Element<'a, Message> {
     .into()
 }
 
+/// ElevenLabs pricing information modal window
+pub fn elevenlabs_info_window_view<'a>(_app: &'a App) -> Element<'a, Message> {
+    container(
+        column![
+            modal_header("ElevenLabs Pricing Information", Message::CloseElevenLabsInfo),
+            // Content area
+            scrollable(
+                container(
+                    column![
+                        container(
+                            white_text("Important: Please check ElevenLabs pricing", 16)
+                                .style(|_theme| iced::widget::text::Style {
+                                    color: Some(Color::WHITE),
+                                })
+                        )
+                        .width(Length::Fill)
+                        .padding([20.0, 24.0]),
+                        container(
+                            white_text(
+                                "ElevenLabs counts every character read against the credits of your plan: \
+                                with the Multilingual v2 model used here, one character costs one credit. \
+                                Credits renew monthly and do not carry over.\n\n\
+                                • The free plan includes 10,000 credits per month (roughly 10 minutes of speech)\n\
+                                • Paid plans include more credits, and some bill the usage beyond them\n\
+                                • Reading a long article can use a few thousand credits at once\n\n\
+                                Your text and API key are sent to ElevenLabs. Please review ElevenLabs pricing \
+                                before using this service to understand potential charges.",
+                                13
+                            )
+                            .style(|_theme| iced::widget::text::Style {
+                                color: Some(Color::from_rgba(1.0, 1.0, 1.0, 0.85)),
+                            })
+                        )
+                        .width(Length::Fill)
+                        .padding([0.0, 24.0]),
+                        Space::new().height(Length::Fixed(16.0)),
+                        container(
+                            button(
+                                white_text("View ElevenLabs Pricing Details →", 13)
+                                    .style(|_theme| iced::widget::text::Style {
+                                        color: Some(Color::from_rgb(0.3, 0.6, 1.0)),
+                                    })
+                            )
+                            .style(transparent_button_style)
+                            .padding([8.0, 12.0])
+                            .on_press(Message::OpenElevenLabsPricingUrl)
+                        )
+                        .width(Length::Fill)
+                        .padding([0.0, 24.0])
+                        .align_x(Alignment::Start),
+                        Space::new().height(Length::Fixed(20.0)),
+                    ]
+                    .spacing(12)
+                )
+                .width(Length::Fill)
+                .style(|_theme| container::Style {
+                    background: Some(Background::Color(Color::from_rgb(0.12, 0.12, 0.14))),
+                    ..Default::default()
+                }),
+            )
+            .width(Length::Fill)
+            .height(Length::Fill),
+        ]
+        .spacing(0)
+        .width(Length::Fill)
+        .height(Length::Fill),
+    )
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .center_x(Length::Fill)
+    .center_y(Length::Fill)
+    .style(modal_content_style)
+    .into()
+}
+
 /// Natural Reading information modal window
 pub fn text_cleanup_info_window_view<'a>(_app: &'a App) -> Element<'a, Message> {
     container(
@@ -1296,6 +2218,10 @@ pub fn extracted_text_dialog_view<'a>(app: &'a App) -> Element<'a, Message> {
         container(
             text_editor(editor_content)
                 .on_action(Message::ExtractedTextEditorAction)
+                .font(crate::styles::reading_font(app.reading_style.font))
+                .line_height(iced::widget::text::LineHeight::Relative(app.reading_style.line_spacing))
+                .style(crate::styles::reading_editor_style(app.reading_style.tint))
+                .height(Length::Fill)
         )
         .width(Length::Fill)
         .height(Length::Fill)
@@ -1385,6 +2311,7 @@ pub fn extracted_text_dialog_view<'a>(app: &'a App) -> Element<'a, Message> {
             .width(Length::Fill)
             .padding([20.0, 24.0])
             .style(header_style),
+            links_bar(app),
             // Text content area (editable text input)
             text_content,
         ]
@@ -1400,20 +2327,733 @@ pub fn extracted_text_dialog_view<'a>(app: &'a App) -> Element<'a, Message> {
     .into()
 }
 
-/// Screenshot viewer window - displays the captured screenshot
-pub fn screenshot_viewer_view<'a>(app: &'a App) -> Element<'a, Message> {
+/// Links found in the extracted text and QR codes of the screenshot, with their actions.
+fn links_bar(app: &App) -> Element<'_, Message> {
+    let mut links = app.qr_codes.clone();
+    for url in app.extracted_text.as_deref().map(crate::text::links::find_urls).unwrap_or_default() {
+        if !links.contains(&url) {
+            links.push(url);
+        }
+    }
+    if links.is_empty() {
+        return Space::new().into();
+    }
 
-    // Display the screenshot image if available
-    let image_content: Element<'a, Message> = if let Some(ref screenshot_path) = app.screenshot_path {
-        // Load image from file path using Iced's image widget
-        use iced::widget::image::{Image, Handle};
-        let image_handle = Handle::from_path(screenshot_path);
-        let img = Image::new(image_handle)
+    let link_button = |label: &'static str, message: Message| {
+        button(white_text(label, 11))
+            .style(circle_button_style)
+            .padding([3.0, 10.0])
+            .on_press(message)
+    };
+    let rows = links.into_iter().map(|link| {
+        let shown = if link.chars().count() > 60 {
+            format!("{}…", link.chars().take(60).collect::<String>())
+        } else {
+            link.clone()
+        };
+        let mut actions = row![].spacing(4).align_y(Alignment::Center);
+        if crate::text::links::is_web_url(&link) {
+            actions = actions
+                .push(link_button("Open", Message::OpenLink(link.clone())))
+                .push(link_button("Read Page", Message::ReadLinkedPage(link.clone())));
+        }
+        actions = actions.push(link_button("Copy", Message::CopyLink(link)));
+        row![white_text(shown, 12).width(Length::Fill), actions]
+            .spacing(8)
+            .align_y(Alignment::Center)
+            .into()
+    });
+
+    container(column(rows).spacing(4))
+        .width(Length::Fill)
+        .padding([8.0, 24.0])
+        .into()
+}
+
+/// Preview popup for a hotkey capture: the first line of the text, read after a short delay.
+pub fn preview_popup_view<'a>(app: &'a App) -> Element<'a, Message> {
+    let first_line = app
+        .preview_text
+        .as_deref()
+        .and_then(|t| t.lines().map(str::trim).find(|line| !line.is_empty()))
+        .unwrap_or_default();
+    let preview = if first_line.chars().count() > 60 {
+        format!("{}…", first_line.chars().take(60).collect::<String>().trim_end())
+    } else {
+        first_line.to_string()
+    };
+
+    let action_button = |label: &'a str, msg: Message| {
+        button(white_text(label, 12))
+            .style(transparent_button_style)
+            .padding([4.0, 10.0])
+            .on_press(msg)
+    };
+
+    container(
+        column![
+            text(preview)
+                .size(13)
+                .style(|_theme| iced::widget::text::Style {
+                    color: Some(Color::from_rgba(1.0, 1.0, 1.0, 0.85)),
+                }),
+            row![
+                text("Reading in a moment...")
+                    .size(11)
+                    .style(|_theme| iced::widget::text::Style {
+                        color: Some(Color::from_rgba(1.0, 1.0, 1.0, 0.6)),
+                    }),
+                Space::new().width(Length::Fill),
+                action_button("Read", Message::PreviewRead),
+                action_button("Edit", Message::PreviewEdit),
+                action_button("Cancel", Message::PreviewCancel),
+            ]
+            .spacing(4)
+            .align_y(Alignment::Center),
+        ]
+        .spacing(12),
+    )
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .padding([16.0, 18.0])
+    .style(window_style)
+    .into()
+}
+
+/// Voice suggestion popup: offers a voice for the language of the text being read.
+pub fn voice_suggestion_popup_view<'a>(app: &'a App) -> Element<'a, Message> {
+    let question = app.voice_suggestion.as_ref().map_or_else(String::new, |voice| {
+        let megabytes = crate::voices::voice_size_bytes(voice).div_ceil(1_000_000);
+        format!("Download a {} voice ({megabytes} MB)?", voice.language.name_english)
+    });
+
+    let action_button = |label: &'a str, msg: Message| {
+        button(white_text(label, 12))
+            .style(transparent_button_style)
+            .padding([4.0, 10.0])
+            .on_press(msg)
+    };
+
+    container(
+        column![
+            text(question)
+                .size(13)
+                .style(|_theme| iced::widget::text::Style {
+                    color: Some(Color::from_rgba(1.0, 1.0, 1.0, 0.85)),
+                }),
+            row![
+                text("No installed voice speaks this language")
+                    .size(11)
+                    .style(|_theme| iced::widget::text::Style {
+                        color: Some(Color::from_rgba(1.0, 1.0, 1.0, 0.6)),
+                    }),
+                Space::new().width(Length::Fill),
+                action_button("Download", Message::DownloadSuggestedVoice),
+                action_button("Not now", Message::DeclineVoiceSuggestion(false)),
+                action_button("Never", Message::DeclineVoiceSuggestion(true)),
+            ]
+            .spacing(4)
+            .align_y(Alignment::Center),
+        ]
+        .spacing(12),
+    )
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .padding([16.0, 18.0])
+    .style(window_style)
+    .into()
+}
+
+/// Long text dialog - asks how much of a long capture to read
+pub fn long_text_dialog_view<'a>(app: &'a App) -> Element<'a, Message> {
+    let (paragraphs, minutes) = app
+        .long_text_pending
+        .as_deref()
+        .map(|t| {
+            (
+                crate::text::split_paragraphs(t).len(),
+                crate::text::estimated_minutes(t),
+            )
+        })
+        .unwrap_or((0, 0));
+
+    let summary = text(format!(
+        "This text has {} paragraphs and takes about {} min to read.",
+        paragraphs, minutes
+    ))
+    .size(13)
+    .style(|_theme| iced::widget::text::Style {
+        color: Some(Color::from_rgba(1.0, 1.0, 1.0, 0.85)),
+    });
+
+    let action_button = |label: String, msg: Message| {
+        button(
+            container(
+                text(label)
+                    .size(13)
+                    .style(|_theme| iced::widget::text::Style {
+                        color: Some(Color::WHITE),
+                    })
+            )
+            .padding([8.0, 16.0])
+        )
+        .style(transparent_button_style)
+        .on_press(msg)
+    };
+
+    container(
+        column![
+            modal_header("Long Text", Message::CloseLongTextDialog),
+            container(
+                column![
+                    summary,
+                    Space::new().height(Length::Fixed(16.0)),
+                    row![
+                        action_button(
+                            format!("Read first {} paragraphs", app.long_text_paragraphs),
+                            Message::ReadFirstParagraphs
+                        ),
+                        Space::new().width(Length::Fixed(4.0)),
+                        action_button("Read all".to_string(), Message::ReadAllText),
+                        Space::new().width(Length::Fixed(4.0)),
+                        action_button("Open in editor".to_string(), Message::OpenLongTextInEditor),
+                    ]
+                    .align_y(Alignment::Center),
+                ]
+                .spacing(0)
+            )
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .padding([20.0, 24.0])
+            .style(|_theme| container::Style {
+                background: Some(Background::Color(Color::from_rgb(0.12, 0.12, 0.14))),
+                ..Default::default()
+            }),
+        ]
+        .spacing(0)
+        .width(Length::Fill)
+        .height(Length::Fill),
+    )
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .center_x(Length::Fill)
+    .center_y(Length::Fill)
+    .style(modal_content_style)
+    .into()
+}
+
+/// Sensitive text dialog - asks before reading a capture that looks like a secret
+pub fn sensitive_text_dialog_view<'a>(app: &'a App) -> Element<'a, Message> {
+    let kind = app.sensitive_pending.as_ref().map(|(_, kind, _)| *kind).unwrap_or("a secret");
+    let destination = if app.text_cleanup_enabled {
+        " and send it to the Natural Reading service"
+    } else if app.selected_backend == TTSBackend::AwsPolly {
+        " and send it to AWS Polly"
+    } else if app.selected_backend == TTSBackend::ElevenLabs {
+        " and send it to ElevenLabs"
+    } else {
+        ""
+    };
+    let summary = text(format!(
+        "This text seems to contain {kind}. Read it aloud{destination} anyway?"
+    ))
+    .size(13)
+    .style(|_theme| iced::widget::text::Style {
+        color: Some(Color::from_rgba(1.0, 1.0, 1.0, 0.85)),
+    });
+
+    let action_button = |label: &'a str, msg: Message| {
+        button(
+            container(
+                text(label)
+                    .size(13)
+                    .style(|_theme| iced::widget::text::Style {
+                        color: Some(Color::WHITE),
+                    })
+            )
+            .padding([8.0, 16.0])
+        )
+        .style(transparent_button_style)
+        .on_press(msg)
+    };
+
+    container(
+        column![
+            modal_header("Sensitive Text", Message::CloseSensitiveDialog),
+            container(
+                column![
+                    summary,
+                    Space::new().height(Length::Fixed(16.0)),
+                    row![
+                        action_button("Read anyway", Message::ReadSensitiveText),
+                        Space::new().width(Length::Fixed(4.0)),
+                        action_button("Don't read", Message::CloseSensitiveDialog),
+                    ]
+                    .align_y(Alignment::Center),
+                ]
+                .spacing(0)
+            )
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .padding([20.0, 24.0])
+            .style(|_theme| container::Style {
+                background: Some(Background::Color(Color::from_rgb(0.12, 0.12, 0.14))),
+                ..Default::default()
+            }),
+        ]
+        .spacing(0)
+        .width(Length::Fill)
+        .height(Length::Fill),
+    )
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .center_x(Length::Fill)
+    .center_y(Length::Fill)
+    .style(modal_content_style)
+    .into()
+}
+
+/// Capture source prompt - asks where to read from on a hotkey press, or
+/// offers OCR when the selection and clipboard have no text
+pub fn capture_source_prompt_view<'a>(app: &'a App) -> Element<'a, Message> {
+    let summary = if app.capture_source_asking {
+        "What should be read?"
+    } else {
+        "No text is selected or copied. Capture a screen region and read its text?"
+    };
+    let summary = text(summary)
+        .size(13)
+        .style(|_theme| iced::widget::text::Style {
+            color: Some(Color::from_rgba(1.0, 1.0, 1.0, 0.85)),
+        });
+
+    let action_button = |label: &'a str, msg: Message| {
+        button(
+            container(
+                text(label)
+                    .size(13)
+                    .style(|_theme| iced::widget::text::Style {
+                        color: Some(Color::WHITE),
+                    })
+            )
+            .padding([8.0, 16.0])
+        )
+        .style(transparent_button_style)
+        .on_press(msg)
+    };
+
+    let actions = if app.capture_source_asking {
+        column![
+            action_button("Selected text", Message::CaptureSourceChosen(CaptureSource::Selection)),
+            action_button("Clipboard", Message::CaptureSourceChosen(CaptureSource::Clipboard)),
+            action_button("Screen region (OCR)", Message::CaptureSourceChosen(CaptureSource::Ocr)),
+        ]
+    } else {
+        column![row![
+            action_button("Capture region", Message::CaptureSourceChosen(CaptureSource::Ocr)),
+            Space::new().width(Length::Fixed(4.0)),
+            action_button("Cancel", Message::CloseCaptureSourcePrompt),
+        ]
+        .align_y(Alignment::Center)]
+    };
+
+    container(
+        column![
+            modal_header("Read From", Message::CloseCaptureSourcePrompt),
+            container(
+                column![
+                    summary,
+                    Space::new().height(Length::Fixed(12.0)),
+                    actions.spacing(2),
+                ]
+                .spacing(0)
+            )
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .padding([20.0, 24.0])
+            .style(|_theme| container::Style {
+                background: Some(Background::Color(Color::from_rgb(0.12, 0.12, 0.14))),
+                ..Default::default()
+            }),
+        ]
+        .spacing(0)
+        .width(Length::Fill)
+        .height(Length::Fill),
+    )
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .center_x(Length::Fill)
+    .center_y(Length::Fill)
+    .style(modal_content_style)
+    .into()
+}
+
+/// Continue reading dialog - offers to continue a file or web page from where it was left
+pub fn resume_document_dialog_view<'a>(app: &'a App) -> Element<'a, Message> {
+    let percent = app.resume_pending.as_ref().map_or(0.0, |(_, progress, _)| progress * 100.0);
+    let summary = text(format!("You read {percent:.0}% of this document last time."))
+        .size(13)
+        .style(|_theme| iced::widget::text::Style {
+            color: Some(Color::from_rgba(1.0, 1.0, 1.0, 0.85)),
+        });
+
+    let action_button = |label: String, msg: Message| {
+        button(
+            container(
+                text(label)
+                    .size(13)
+                    .style(|_theme| iced::widget::text::Style {
+                        color: Some(Color::WHITE),
+                    })
+            )
+            .padding([8.0, 16.0])
+        )
+        .style(transparent_button_style)
+        .on_press(msg)
+    };
+
+    container(
+        column![
+            modal_header("Continue Reading", Message::CloseResumeDialog),
+            container(
+                column![
+                    summary,
+                    Space::new().height(Length::Fixed(16.0)),
+                    row![
+                        action_button(format!("Continue from {percent:.0}%"), Message::ContinueDocument),
+                        Space::new().width(Length::Fixed(4.0)),
+                        action_button("Start over".to_string(), Message::RestartDocument),
+                    ]
+                    .align_y(Alignment::Center),
+                ]
+                .spacing(0)
+            )
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .padding([20.0, 24.0])
+            .style(|_theme| container::Style {
+                background: Some(Background::Color(Color::from_rgb(0.12, 0.12, 0.14))),
+                ..Default::default()
+            }),
+        ]
+        .spacing(0)
+        .width(Length::Fill)
+        .height(Length::Fill),
+    )
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .center_x(Length::Fill)
+    .center_y(Length::Fill)
+    .style(modal_content_style)
+    .into()
+}
+
+/// Weekly summary window - the last seven days of listening
+pub fn weekly_summary_dialog_view<'a>(app: &'a App) -> Element<'a, Message> {
+    let summary = text(app.weekly_summary_text.as_deref().unwrap_or_default())
+        .size(13)
+        .style(|_theme| iced::widget::text::Style {
+            color: Some(Color::from_rgba(1.0, 1.0, 1.0, 0.85)),
+        });
+    let hint = text(format!(
+        "Shown every {} at {:02}:00. Change it under History & Sync in Settings.",
+        app.weekly_summary_day, app.weekly_summary_hour
+    ))
+    .size(11)
+    .style(|_theme| iced::widget::text::Style {
+        color: Some(Color::from_rgba(1.0, 1.0, 1.0, 0.6)),
+    });
+
+    let action_button = |label: &'a str, msg: Message| {
+        button(
+            container(
+                text(label)
+                    .size(13)
+                    .style(|_theme| iced::widget::text::Style {
+                        color: Some(Color::WHITE),
+                    })
+            )
+            .padding([8.0, 16.0])
+        )
+        .style(transparent_button_style)
+        .on_press(msg)
+    };
+
+    container(
+        column![
+            modal_header("This Week", Message::CloseWeeklySummary),
+            container(
+                column![
+                    summary,
+                    Space::new().height(Length::Fixed(6.0)),
+                    hint,
+                    Space::new().height(Length::Fixed(16.0)),
+                    row![
+                        action_button("Close", Message::CloseWeeklySummary),
+                        Space::new().width(Length::Fixed(4.0)),
+                        action_button("Don't show again", Message::WeeklySummaryOptOut),
+                    ]
+                    .align_y(Alignment::Center),
+                ]
+                .spacing(0)
+            )
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .padding([20.0, 24.0])
+            .style(|_theme| container::Style {
+                background: Some(Background::Color(Color::from_rgb(0.12, 0.12, 0.14))),
+                ..Default::default()
+            }),
+        ]
+        .spacing(0)
+        .width(Length::Fill)
+        .height(Length::Fill),
+    )
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .center_x(Length::Fill)
+    .center_y(Length::Fill)
+    .style(modal_content_style)
+    .into()
+}
+
+/// Clipboard history window: the last captured texts, newest first; clicking
+/// one reads it.
+pub fn clipboard_history_view<'a>(app: &'a App) -> Element<'a, Message> {
+    let hint = |content: &'a str| {
+        text(content).size(11).style(|_theme| iced::widget::text::Style {
+            color: Some(Color::from_rgba(1.0, 1.0, 1.0, 0.6)),
+        })
+    };
+
+    let list: Element<'a, Message> = if !app.clipboard_history_enabled {
+        hint("The clipboard history is off. Turn it on under Privacy in Settings to pick from your last captures here.")
+            .into()
+    } else if app.clipboard_history.is_empty() {
+        hint("Nothing captured yet. Texts you read with the hotkey or the tray show up here.").into()
+    } else {
+        scrollable(
+            column(app.clipboard_history.iter().enumerate().map(|(index, item)| {
+                let preview: String = item.split_whitespace().collect::<Vec<_>>().join(" ");
+                let preview = match preview.char_indices().nth(160) {
+                    Some((end, _)) => format!("{}…", &preview[..end]),
+                    None => preview,
+                };
+                let label = text(preview).size(13).style(|_theme| iced::widget::text::Style {
+                    color: Some(Color::from_rgba(1.0, 1.0, 1.0, 0.85)),
+                });
+                button(label)
+                    .style(transparent_button_style)
+                    .width(Length::Fill)
+                    .padding([6.0, 8.0])
+                    .on_press(Message::ReadClipboardHistoryItem(index))
+                    .into()
+            }))
+            .spacing(2),
+        )
+        .height(Length::Fill)
+        .into()
+    };
+
+    let clear_button = button(white_text("Clear", 13))
+        .style(transparent_button_style)
+        .padding([6.0, 12.0])
+        .on_press_maybe((!app.clipboard_history.is_empty()).then_some(Message::ClearClipboardHistory));
+
+    container(
+        column![
+            modal_header("Clipboard History", Message::CloseClipboardHistory),
+            container(
+                column![
+                    list,
+                    row![hint("Kept in memory only, forgotten when the app quits."), Space::new().width(Length::Fill), clear_button]
+                        .align_y(Alignment::Center),
+                ]
+                .spacing(8)
+            )
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .padding([12.0, 16.0]),
+        ]
+        .spacing(0)
+        .width(Length::Fill)
+        .height(Length::Fill),
+    )
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .style(modal_content_style)
+    .into()
+}
+
+/// Processing chain window: each stage of the last reading, from the raw
+/// capture to what the provider was sent for each chunk.
+pub fn processing_chain_view<'a>(app: &'a App) -> Element<'a, Message> {
+    let dim = |content: String, size: u32| {
+        text(content).size(size).style(|_theme| iced::widget::text::Style {
+            color: Some(Color::from_rgba(1.0, 1.0, 1.0, 0.6)),
+        })
+    };
+
+    let stages: Element<'a, Message> = if app.processing_trace.stages.is_empty() {
+        dim("Nothing read yet. Read a text, then open this window to see how it was processed.".to_string(), 11).into()
+    } else {
+        scrollable(
+            column(app.processing_trace.stages.iter().map(|stage| {
+                let chunks = stage.output.iter().enumerate().map(|(index, chunk)| {
+                    let number = if stage.output.len() > 1 { format!("{}", index + 1) } else { String::new() };
+                    row![
+                        container(dim(number, 11)).width(Length::Fixed(24.0)),
+                        white_text(chunk.as_str(), 12).width(Length::Fill),
+                    ]
+                    .into()
+                });
+                column![
+                    white_text(stage.name.as_str(), 13),
+                    column(chunks).spacing(4),
+                ]
+                .spacing(6)
+                .into()
+            }))
+            .spacing(16),
+        )
+        .height(Length::Fill)
+        .into()
+    };
+
+    container(
+        column![
+            modal_header("Processing Chain", Message::CloseProcessingChain),
+            container(
+                column![stages, dim("Pauses are shown as [pause].".to_string(), 11)].spacing(8)
+            )
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .padding([12.0, 16.0]),
+        ]
+        .spacing(0)
+        .width(Length::Fill)
+        .height(Length::Fill),
+    )
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .style(modal_content_style)
+    .into()
+}
+
+/// Screenshot viewer window - displays the captured screenshot
+/// Zoom buttons and OCR of the visible region, above the screenshot.
+fn screenshot_toolbar(app: &App, viewer: iced::Size) -> Element<'_, Message> {
+    let has_image = app.screenshot.is_some() && app.screenshot_viewport.image.width > 0.0;
+    let tool_button = |label: &'static str, message: Message| {
+        button(white_text(label, 12))
+            .style(circle_button_style)
+            .padding([4.0, 10.0])
+            .on_press_maybe(has_image.then_some(message))
+    };
+    let zoom_label = format!("{:.0}%", app.screenshot_viewport.current_scale(viewer) * 100.0);
+    let is_zoomed = app.screenshot_viewport.scale.is_some();
+
+    container(
+        row![
+            tool_button("−", Message::ScreenshotZoom(1.0 / crate::ui::screenshot::ZOOM_STEP)),
+            tool_button("+", Message::ScreenshotZoom(crate::ui::screenshot::ZOOM_STEP)),
+            container(text(zoom_label).size(12).style(|_theme| iced::widget::text::Style {
+                color: Some(Color::from_rgba(1.0, 1.0, 1.0, 0.7)),
+            }))
+            .width(Length::Fixed(56.0))
+            .center_x(Length::Fixed(56.0)),
+            if is_zoomed {
+                tool_button("Fit", Message::ScreenshotFit)
+            } else {
+                tool_button("1:1", Message::ScreenshotActualSize)
+            },
+            Space::new().width(Length::Fill),
+            if app.ocr_lines.is_empty() {
+                Element::from(Space::new())
+            } else {
+                tool_button(
+                    if app.show_ocr_boxes { "Hide Boxes" } else { "Show Boxes" },
+                    Message::ToggleOcrBoxes,
+                )
+                .into()
+            },
+            tool_button(
+                if is_zoomed { "Read Visible Region" } else { "Read Again" },
+                Message::OcrVisibleRegion,
+            ),
+        ]
+        .spacing(6)
+        .align_y(Alignment::Center),
+    )
+    .padding([6.0, 20.0])
+    .width(Length::Fill)
+    .into()
+}
+
+/// Clickable boxes over the lines OCR found, placed like the screenshot drawn under them
+/// in an image area of size `area`.
+fn ocr_boxes_overlay<'a>(app: &'a App, region: Option<iced::Rectangle<u32>>, area: iced::Size) -> Element<'a, Message> {
+    let boxes = app.ocr_lines.iter().enumerate().filter_map(|(index, line)| {
+        let line_bounds = iced::Rectangle {
+            x: line.x,
+            y: line.y,
+            width: line.width,
+            height: line.height,
+        };
+        let bounds = app.screenshot_viewport.to_viewer(line_bounds, region, area)?;
+        let ocr_box = button(Space::new())
+            .width(Length::Fixed(bounds.width))
+            .height(Length::Fixed(bounds.height))
+            .padding(0)
+            .style(ocr_box_style)
+            .on_press(Message::OcrLineClicked(index));
+        Some(pin(ocr_box).x(bounds.x).y(bounds.y).into())
+    });
+    Stack::with_children(boxes)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .into()
+}
+
+pub fn screenshot_viewer_view<'a>(app: &'a App) -> Element<'a, Message> {
+
+    let viewer = crate::ui::screenshot::viewer_size(app);
+    let viewport = &app.screenshot_viewport;
+
+    // Display the screenshot image if available, zoomed to the visible region
+    let image_content: Element<'a, Message> = if let Some(ref image_handle) = app.screenshot_handle {
+        // Show the image using Iced's image widget; the handle is made once per capture
+        use iced::widget::image::Image;
+        let mut img = Image::new(image_handle.clone())
             .width(Length::Fill)
             .height(Length::Fill)
             .content_fit(ContentFit::Contain);
-        
-        container(img)
+        let region = viewport.region(viewer);
+        if let Some(region) = region {
+            img = img.crop(region);
+        }
+
+        // Drag to pan, scroll to zoom
+        let interaction = match (viewport.scale.is_some(), app.screenshot_dragging) {
+            (false, _) => iced::mouse::Interaction::default(),
+            (true, false) => iced::mouse::Interaction::Grab,
+            (true, true) => iced::mouse::Interaction::Grabbing,
+        };
+        let image_area = mouse_area(img)
+            .on_press(Message::ScreenshotDragStarted)
+            .on_release(Message::ScreenshotDragEnded)
+            .on_exit(Message::ScreenshotDragEnded)
+            .on_move(Message::ScreenshotCursorMoved)
+            .on_scroll(Message::ScreenshotScrolled)
+            .interaction(interaction);
+
+        // Boxes of the OCR lines on top; clicking one selects its text in the extracted text dialog
+        let mut layers = stack![image_area].width(Length::Fill).height(Length::Fill);
+        if app.show_ocr_boxes && !app.ocr_lines.is_empty() {
+            layers = layers.push(responsive(move |area| ocr_boxes_overlay(app, region, area)));
+        }
+
+        container(layers)
             .width(Length::Fill)
             .height(Length::Fill)
             .padding(20)
@@ -1435,6 +3075,7 @@ pub fn screenshot_viewer_view<'a>(app: &'a App) -> Element<'a, Message> {
     container(
         column![
             modal_header("Screenshot", Message::CloseScreenshotViewer),
+            screenshot_toolbar(app, viewer),
             // Image content area
             container(image_content)
                 .width(Length::Fill)
@@ -1,14 +1,18 @@
 //! AWS Polly voice metadata management
 //!
-//! Handles fetching and organizing voices from AWS Polly using the AWS SDK.
+//! Handles fetching and organizing voices from AWS Polly using the AWS SDK,
+//! and the pronunciation lexicons stored in Polly.
 
 use std::collections::HashMap;
-use tracing::{debug, trace};
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, trace, warn};
 
 use crate::model::LanguageInfo;
 
 /// Voice metadata from AWS Polly
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PollyVoiceInfo {
     pub id: String,              // AWS VoiceId (e.g., "Matthew", "Joanna")
     pub name: String,            // Voice name
@@ -97,9 +101,128 @@ pub async fn fetch_polly_voices() -> Result<HashMap<String, PollyVoiceInfo>, Str
         count = voices.len(),
         "AWS Polly: converted AWS voices to internal format"
     );
+    cache_polly_voices(&region, &voices);
     Ok(voices)
 }
 
+/// Voice list from the last successful fetch, and the region it came from.
+#[derive(Serialize, Deserialize)]
+struct PollyVoiceCache {
+    region: String,
+    voices: HashMap<String, PollyVoiceInfo>,
+}
+
+fn polly_voices_cache_path() -> Option<PathBuf> {
+    Some(dirs::data_dir()?.join("insight-reader").join("polly-voices.json"))
+}
+
+fn cache_polly_voices(region: &str, voices: &HashMap<String, PollyVoiceInfo>) {
+    let Some(path) = polly_voices_cache_path() else {
+        return;
+    };
+    let cache = PollyVoiceCache {
+        region: region.to_string(),
+        voices: voices.clone(),
+    };
+    let result = std::fs::create_dir_all(path.parent().unwrap_or(&path))
+        .map_err(|e| e.to_string())
+        .and_then(|_| serde_json::to_string(&cache).map_err(|e| e.to_string()))
+        .and_then(|json| std::fs::write(&path, json).map_err(|e| e.to_string()));
+    if let Err(e) = result {
+        warn!(error = %e, path = %path.display(), "Failed to cache AWS Polly voices");
+    }
+}
+
+/// Load the AWS Polly voices from the last successful fetch, if it was for
+/// the current region.
+pub fn load_cached_polly_voices() -> Option<HashMap<String, PollyVoiceInfo>> {
+    let json_text = std::fs::read_to_string(polly_voices_cache_path()?).ok()?;
+    let cache: PollyVoiceCache = serde_json::from_str(&json_text).ok()?;
+    (cache.region == detect_aws_region()).then_some(cache.voices)
+}
+
+/// Whether the cached AWS Polly voices were fetched within [`super::VOICE_LIST_TTL`].
+pub fn polly_voices_fresh() -> bool {
+    polly_voices_cache_path().is_some_and(|path| super::is_fresh(&path))
+}
+
+/// A pronunciation lexicon stored in AWS Polly.
+#[derive(Debug, Clone)]
+pub struct PollyLexicon {
+    pub name: String,
+    /// Language code ("en-US")
+    pub language: String,
+    /// Number of words the lexicon defines
+    pub lexemes: i32,
+}
+
+async fn polly_client() -> aws_sdk_polly::Client {
+    let config = aws_config::defaults(aws_config::BehaviorVersion::latest())
+        .region(aws_config::Region::new(detect_aws_region()))
+        .load()
+        .await;
+    aws_sdk_polly::Client::new(&config)
+}
+
+/// List the pronunciation lexicons stored in AWS Polly in the current region.
+pub async fn list_polly_lexicons() -> Result<Vec<PollyLexicon>, String> {
+    let client = polly_client().await;
+    let mut lexicons = Vec::new();
+    let mut next_token = None;
+    loop {
+        let response = client
+            .list_lexicons()
+            .set_next_token(next_token)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to list AWS Polly lexicons: {e}"))?;
+        for lexicon in response.lexicons() {
+            let attributes = lexicon.attributes();
+            lexicons.push(PollyLexicon {
+                name: lexicon.name().unwrap_or_default().to_string(),
+                language: attributes
+                    .and_then(|a| a.language_code())
+                    .map(|code| code.as_str().to_string())
+                    .unwrap_or_default(),
+                lexemes: attributes.map(|a| a.lexemes_count()).unwrap_or(0),
+            });
+        }
+        next_token = response.next_token().map(str::to_string);
+        if next_token.is_none() {
+            break;
+        }
+    }
+    debug!(count = lexicons.len(), "AWS Polly: listed lexicons");
+    Ok(lexicons)
+}
+
+/// Store a PLS document in AWS Polly as `name`, replacing any lexicon of that name.
+pub async fn upload_polly_lexicon(name: &str, pls: &str) -> Result<(), String> {
+    polly_client()
+        .await
+        .put_lexicon()
+        .name(name)
+        .content(pls)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to upload lexicon '{name}' to AWS Polly: {e}"))?;
+    debug!(name, "AWS Polly: lexicon uploaded");
+    Ok(())
+}
+
+/// Delete a lexicon from AWS Polly.
+pub async fn delete_polly_lexicon(name: &str) -> Result<(), String> {
+    polly_client()
+        .await
+        .delete_lexicon()
+        .name(name)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to delete AWS Polly lexicon '{name}': {e}"))?;
+    debug!(name, "AWS Polly: lexicon deleted");
+    Ok(())
+}
+
 /// Detect AWS region from environment or config file.
 ///
 /// Priority:
@@ -197,7 +320,10 @@ fn create_language_info(lang_code: &str) -> LanguageInfo {
 
     // Map common language codes to language names
     let (name_english, country_english) = get_language_names(&lang_family, &region);
-    let name_native = name_english.clone(); // AWS doesn't provide native names
+    // AWS doesn't provide native names
+    let name_native = super::labels::native_language_name(&lang_family)
+        .map(str::to_string)
+        .unwrap_or_else(|| name_english.clone());
 
     LanguageInfo {
         code: normalized_code,
@@ -122,6 +122,18 @@ pub fn is_voice_downloaded(voice_key: &str) -> bool {
     onnx_path.exists() && json_path.exists()
 }
 
+/// Keys of the voices downloaded into the models directory.
+pub fn installed_voice_keys() -> Vec<String> {
+    let Ok(entries) = get_model_directory("").and_then(|dir| fs::read_dir(dir).map_err(|e| e.to_string())) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.file_name().to_str()?.strip_suffix(".onnx").map(str::to_string))
+        .filter(|voice_key| is_voice_downloaded(voice_key))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,89 @@
+//! ElevenLabs voice list
+//!
+//! Fetches the voices available to the user's ElevenLabs account: the
+//! premade voices plus any the user cloned or added from the voice library.
+
+use serde::Deserialize;
+use tracing::debug;
+
+/// ElevenLabs API base URL.
+pub const API_URL: &str = "https://api.elevenlabs.io/v1";
+
+/// Voice metadata from ElevenLabs
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ElevenLabsVoice {
+    #[serde(rename = "voice_id")]
+    pub id: String,
+    pub name: String,
+    /// "premade", "cloned", "generated" or "professional"
+    #[serde(default)]
+    pub category: String,
+    /// Accent, gender, age and use case, as far as ElevenLabs knows them
+    #[serde(default)]
+    pub labels: std::collections::BTreeMap<String, String>,
+}
+
+impl ElevenLabsVoice {
+    /// "American, female, young", from the labels ElevenLabs gives.
+    pub fn description(&self) -> String {
+        ["accent", "gender", "age"]
+            .iter()
+            .filter_map(|key| self.labels.get(*key))
+            .filter(|label| !label.is_empty())
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+#[derive(Deserialize)]
+struct VoicesResponse {
+    voices: Vec<ElevenLabsVoice>,
+}
+
+/// Fetch the voices of the account the API key belongs to, sorted by name.
+pub async fn fetch_elevenlabs_voices(api_key: String) -> Result<Vec<ElevenLabsVoice>, String> {
+    if api_key.trim().is_empty() {
+        return Err("No ElevenLabs API key: enter one in Settings".to_string());
+    }
+    debug!("ElevenLabs: fetching voices");
+    let response = reqwest::Client::new()
+        .get(format!("{API_URL}/voices"))
+        .header("xi-api-key", api_key.trim())
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch voices from ElevenLabs: {e}"))?;
+    if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+        return Err("ElevenLabs rejected the API key. Check it in Settings.".to_string());
+    }
+    let response = response
+        .error_for_status()
+        .map_err(|e| format!("Failed to fetch voices from ElevenLabs: {e}"))?;
+    let mut voices = response
+        .json::<VoicesResponse>()
+        .await
+        .map_err(|e| format!("Failed to parse ElevenLabs voices: {e}"))?
+        .voices;
+    voices.sort_by(|a, b| a.name.cmp(&b.name));
+    debug!(count = voices.len(), "ElevenLabs: received voices");
+    Ok(voices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_voices() {
+        let json = r#"{"voices": [
+            {"voice_id": "21m00Tcm4TlvDq8ikWAM", "name": "Rachel", "category": "premade",
+             "labels": {"accent": "american", "gender": "female", "age": "young", "use_case": "narration"}},
+            {"voice_id": "abc", "name": "My Voice"}
+        ]}"#;
+        let voices = serde_json::from_str::<VoicesResponse>(json).unwrap().voices;
+        assert_eq!(voices[0].id, "21m00Tcm4TlvDq8ikWAM");
+        assert_eq!(voices[0].description(), "american, female, young");
+        assert_eq!(voices[1].category, "");
+        assert_eq!(voices[1].description(), "");
+    }
+}
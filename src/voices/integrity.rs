@@ -0,0 +1,150 @@
+//! Integrity check for installed Piper voice models.
+//!
+//! A voice is an `.onnx` model and its `.onnx.json` config. Interrupted
+//! downloads and disk problems leave files that make Piper fail with
+//! unhelpful process errors, so the pair is checked before use: both files
+//! must be non-empty, the config must parse and the model must look like an
+//! ONNX file. If voices.json was fetched before, sizes and MD5 hashes are
+//! compared with it too.
+
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use tracing::{debug, info};
+
+/// Size and modification time of a file, to notice when it changes.
+type FileStamp = (u64, Option<SystemTime>);
+
+/// Models verified in this session, with the model and config stamps they had.
+static VERIFIED: Mutex<Vec<(PathBuf, FileStamp, FileStamp)>> = Mutex::new(Vec::new());
+
+fn file_stamp(path: &Path) -> Result<FileStamp, String> {
+    let meta = std::fs::metadata(path).map_err(|_| format!("{} is missing", file_name(path)))?;
+    Ok((meta.len(), meta.modified().ok()))
+}
+
+/// Check the model at `model_path` (without extension) and its config.
+///
+/// Returns a short description of the problem if the voice is damaged. A
+/// model that passed once is not hashed again until it changes on disk.
+pub fn check_model(model_path: &Path) -> Result<(), String> {
+    let onnx_path = model_path.with_extension("onnx");
+    let json_path = model_path.with_extension("onnx.json");
+    let onnx_stamp = file_stamp(&onnx_path)?;
+    let stamp = (onnx_path.clone(), onnx_stamp, file_stamp(&json_path)?);
+    if VERIFIED.lock().unwrap().contains(&stamp) {
+        return Ok(());
+    }
+
+    if onnx_stamp.0 == 0 {
+        return Err("the model file is empty".to_string());
+    }
+    check_onnx_header(&onnx_path)?;
+    check_config(&json_path)?;
+
+    let voice_key = model_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    match super::load_cached_voices_json().and_then(|voices| voices.get(&voice_key).cloned()) {
+        Some(voice) => {
+            for (remote_path, expected) in &voice.files {
+                let local = if remote_path.ends_with(".onnx.json") {
+                    &json_path
+                } else if remote_path.ends_with(".onnx") {
+                    &onnx_path
+                } else {
+                    continue;
+                };
+                let (size, _) = file_stamp(local)?;
+                if size != expected.size_bytes {
+                    return Err(format!(
+                        "{} is {size} bytes, expected {}",
+                        file_name(local),
+                        expected.size_bytes
+                    ));
+                }
+                if file_md5(local)? != expected.md5_digest {
+                    return Err(format!("{} does not match its checksum", file_name(local)));
+                }
+            }
+            info!(voice = %voice_key, "Voice model checksums verified");
+        }
+        None => debug!(voice = %voice_key, "No voices.json entry for model, checked structure only"),
+    }
+
+    VERIFIED.lock().unwrap().push(stamp);
+    Ok(())
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+/// ONNX models are protobuf messages starting with the `ir_version` field (tag 0x08).
+fn check_onnx_header(path: &Path) -> Result<(), String> {
+    let mut first = [0u8; 1];
+    File::open(path)
+        .and_then(|mut file| file.read_exact(&mut first))
+        .map_err(|e| format!("cannot read {}: {e}", file_name(path)))?;
+    if first[0] != 0x08 {
+        return Err(format!("{} is not an ONNX model", file_name(path)));
+    }
+    Ok(())
+}
+
+/// The config must be JSON with the sample rate Piper reads.
+fn check_config(path: &Path) -> Result<(), String> {
+    let content = std::fs::read_to_string(path).map_err(|e| format!("cannot read {}: {e}", file_name(path)))?;
+    let config: serde_json::Value =
+        serde_json::from_str(&content).map_err(|e| format!("the voice config is not valid JSON: {e}"))?;
+    if config["audio"]["sample_rate"].as_u64().is_none() {
+        return Err("the voice config has no sample rate".to_string());
+    }
+    Ok(())
+}
+
+fn file_md5(path: &Path) -> Result<String, String> {
+    let mut file = File::open(path).map_err(|e| format!("cannot read {}: {e}", file_name(path)))?;
+    let mut context = md5::Context::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+    loop {
+        let read = file
+            .read(&mut buffer)
+            .map_err(|e| format!("cannot read {}: {e}", file_name(path)))?;
+        if read == 0 {
+            break;
+        }
+        context.consume(&buffer[..read]);
+    }
+    Ok(format!("{:x}", context.compute()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_model_structure() {
+        let dir = std::env::temp_dir().join(format!("insight-reader-integrity-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let model = dir.join("xx_XX-test-low");
+        std::fs::write(model.with_extension("onnx"), [0x08, 0x07, 0x12]).unwrap();
+        std::fs::write(model.with_extension("onnx.json"), r#"{"audio": {"sample_rate": 16000}}"#).unwrap();
+        assert_eq!(check_model(&model), Ok(()));
+
+        std::fs::write(model.with_extension("onnx.json"), r#"{"audio": {"#).unwrap();
+        assert!(check_model(&model).is_err());
+
+        std::fs::write(model.with_extension("onnx.json"), r#"{"audio": {"sample_rate": 16000}}"#).unwrap();
+        std::fs::write(model.with_extension("onnx"), "<html>Not found</html>").unwrap();
+        assert!(check_model(&model).is_err());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,273 @@
+//! Display names of voice languages, in English or in the language itself.
+//!
+//! voices.json carries the native name of each language but only the English
+//! name of the country, and Polly carries neither, so the native names of
+//! languages and countries missing from the metadata come from the tables here.
+
+use crate::model::LanguageInfo;
+
+/// Label of a language for the voice pickers: "Português (Brasil)" with
+/// `native`, "Portuguese (Brazil)" without.
+pub fn language_label(info: &LanguageInfo, native: bool) -> String {
+    let (name, country) = if native {
+        let name = if info.name_native.is_empty() || info.name_native == info.name_english {
+            native_language_name(&info.family).unwrap_or(&info.name_english)
+        } else {
+            &info.name_native
+        };
+        (capitalize(name), native_country_name(&info.code).unwrap_or(&info.country_english))
+    } else {
+        (info.name_english.clone(), info.country_english.as_str())
+    };
+    if country.is_empty() || country == "Unknown" {
+        name
+    } else {
+        format!("{name} ({country})")
+    }
+}
+
+/// Sort languages for display: the user's own locale first, then the other
+/// regions of their language, then everything else by label.
+pub fn sort_by_locale_proximity(languages: &mut [(String, LanguageInfo)], locale: Option<&str>, native: bool) {
+    let family = locale.and_then(|locale| locale.split('_').next());
+    let rank = |info: &LanguageInfo| {
+        if locale == Some(info.code.as_str()) {
+            0
+        } else if family == Some(info.family.as_str()) {
+            1
+        } else {
+            2
+        }
+    };
+    languages.sort_by_cached_key(|(_, info)| (rank(info), language_label(info, native).to_lowercase()));
+}
+
+/// Part of the world a voice's country is in, to group the language grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorldRegion {
+    Europe,
+    Americas,
+    Asia,
+    MiddleEast,
+    Africa,
+    Oceania,
+    Other,
+}
+
+impl WorldRegion {
+    pub const ALL: [WorldRegion; 7] = [
+        WorldRegion::Europe,
+        WorldRegion::Americas,
+        WorldRegion::Asia,
+        WorldRegion::MiddleEast,
+        WorldRegion::Africa,
+        WorldRegion::Oceania,
+        WorldRegion::Other,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            WorldRegion::Europe => "Europe",
+            WorldRegion::Americas => "Americas",
+            WorldRegion::Asia => "Asia",
+            WorldRegion::MiddleEast => "Middle East",
+            WorldRegion::Africa => "Africa",
+            WorldRegion::Oceania => "Oceania",
+            WorldRegion::Other => "Other",
+        }
+    }
+}
+
+/// Region of the country of a language code ("pt_BR"), from its ISO 3166 part.
+pub fn world_region(code: &str) -> WorldRegion {
+    let Some((_, country)) = code.split_once('_') else {
+        // Polly codes without a country ("arb")
+        return match code {
+            "ar" | "arb" => WorldRegion::MiddleEast,
+            _ => WorldRegion::Other,
+        };
+    };
+    match country {
+        "AT" | "BE" | "BG" | "CH" | "CZ" | "DE" | "DK" | "EE" | "ES" | "FI" | "FR" | "GB" | "GR" | "HR" | "HU" | "IE"
+        | "IS" | "IT" | "LT" | "LU" | "LV" | "MT" | "NL" | "NO" | "PL" | "PT" | "RO" | "RS" | "RU" | "SE" | "SI" | "SK"
+        | "TR" | "UA" => WorldRegion::Europe,
+        "AR" | "BO" | "BR" | "CA" | "CL" | "CO" | "CR" | "CU" | "DO" | "EC" | "MX" | "PA" | "PE" | "PY" | "US" | "UY"
+        | "VE" => WorldRegion::Americas,
+        "BD" | "CN" | "GE" | "HK" | "ID" | "IN" | "JP" | "KR" | "KZ" | "MY" | "NP" | "PH" | "PK" | "TH" | "TW" | "VN" => {
+            WorldRegion::Asia
+        }
+        "AE" | "IL" | "IQ" | "IR" | "JO" | "SA" => WorldRegion::MiddleEast,
+        "CD" | "EG" | "KE" | "NG" | "ZA" => WorldRegion::Africa,
+        "AU" | "NZ" => WorldRegion::Oceania,
+        _ => WorldRegion::Other,
+    }
+}
+
+/// Whether a language matches a search of the grid, by its English or
+/// native label or its code.
+pub fn matches_search(info: &LanguageInfo, query: &str) -> bool {
+    let query = query.trim().to_lowercase();
+    query.is_empty()
+        || [language_label(info, false), language_label(info, true), info.code.clone()]
+            .iter()
+            .any(|label| label.to_lowercase().contains(&query))
+}
+
+/// voices.json writes some native names in lower case ("català").
+fn capitalize(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Name of a language in itself, for languages whose metadata has none.
+pub fn native_language_name(family: &str) -> Option<&'static str> {
+    Some(match family {
+        "ar" | "arb" => "العربية",
+        "ca" => "Català",
+        "cmn" | "zh" => "中文",
+        "cs" => "Čeština",
+        "cy" => "Cymraeg",
+        "da" => "Dansk",
+        "de" => "Deutsch",
+        "en" => "English",
+        "es" => "Español",
+        "fi" => "Suomi",
+        "fr" => "Français",
+        "ga" => "Gaeilge",
+        "he" => "עברית",
+        "hi" => "हिन्दी",
+        "hu" => "Magyar",
+        "id" => "Bahasa Indonesia",
+        "is" => "Íslenska",
+        "it" => "Italiano",
+        "ja" => "日本語",
+        "ko" => "한국어",
+        "ms" => "Bahasa Melayu",
+        "mt" => "Malti",
+        "nb" => "Norsk bokmål",
+        "nl" => "Nederlands",
+        "no" => "Norsk",
+        "pl" => "Polski",
+        "pt" => "Português",
+        "ro" => "Română",
+        "ru" => "Русский",
+        "sv" => "Svenska",
+        "th" => "ไทย",
+        "tr" => "Türkçe",
+        "vi" => "Tiếng Việt",
+        "yue" => "粵語",
+        _ => return None,
+    })
+}
+
+/// Name of a country in the language of a voice code ("pt_BR" → "Brasil"),
+/// for countries whose name differs from the English one.
+fn native_country_name(code: &str) -> Option<&'static str> {
+    Some(match code {
+        "ar_AE" => "الإمارات",
+        "ar_JO" => "الأردن",
+        "ar_SA" => "السعودية",
+        "ca_ES" => "Espanya",
+        "cmn_CN" | "yue_CN" | "zh_CN" => "中国",
+        "cs_CZ" => "Česko",
+        "cy_GB" => "Y Deyrnas Unedig",
+        "da_DK" => "Danmark",
+        "de_AT" => "Österreich",
+        "de_CH" => "Schweiz",
+        "de_DE" => "Deutschland",
+        "el_GR" => "Ελλάδα",
+        "es_AR" => "Argentina",
+        "es_ES" => "España",
+        "es_MX" => "México",
+        "es_US" => "Estados Unidos",
+        "fa_IR" => "ایران",
+        "fi_FI" => "Suomi",
+        "fr_BE" => "Belgique",
+        "fr_CA" => "Canada",
+        "fr_CH" => "Suisse",
+        "fr_FR" => "France",
+        "hi_IN" => "भारत",
+        "hu_HU" => "Magyarország",
+        "is_IS" => "Ísland",
+        "it_IT" => "Italia",
+        "ja_JP" => "日本",
+        "ka_GE" => "საქართველო",
+        "kk_KZ" => "Қазақстан",
+        "ko_KR" => "대한민국",
+        "lb_LU" => "Lëtzebuerg",
+        "lv_LV" => "Latvija",
+        "ml_IN" => "ഇന്ത്യ",
+        "nb_NO" | "no_NO" => "Norge",
+        "ne_NP" => "नेपाल",
+        "nl_BE" => "België",
+        "nl_NL" => "Nederland",
+        "pl_PL" => "Polska",
+        "pt_BR" => "Brasil",
+        "pt_PT" => "Portugal",
+        "ro_RO" => "România",
+        "ru_RU" => "Россия",
+        "sk_SK" => "Slovensko",
+        "sl_SI" => "Slovenija",
+        "sr_RS" => "Србија",
+        "sv_SE" => "Sverige",
+        "te_IN" => "భారతదేశం",
+        "tr_TR" => "Türkiye",
+        "uk_UA" => "Україна",
+        "vi_VN" => "Việt Nam",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn language(code: &str, name_native: &str, name_english: &str, country_english: &str) -> (String, LanguageInfo) {
+        let (family, region) = code.split_once('_').unwrap_or((code, ""));
+        let info = LanguageInfo {
+            code: code.to_string(),
+            family: family.to_string(),
+            region: region.to_string(),
+            name_native: name_native.to_string(),
+            name_english: name_english.to_string(),
+            country_english: country_english.to_string(),
+        };
+        (code.to_string(), info)
+    }
+
+    #[test]
+    fn test_labels_and_order() {
+        let (_, brazil) = language("pt_BR", "Português", "Portuguese", "Brazil");
+        assert_eq!(language_label(&brazil, true), "Português (Brasil)");
+        assert_eq!(language_label(&brazil, false), "Portuguese (Brazil)");
+        // Polly metadata repeats the English name as the native one
+        let (_, polly) = language("de_AT", "German", "German", "Austria");
+        assert_eq!(language_label(&polly, true), "Deutsch (Österreich)");
+        let (_, catalan) = language("ca_ES", "català", "Catalan", "Spain");
+        assert_eq!(language_label(&catalan, true), "Català (Espanya)");
+
+        let mut languages = vec![
+            language("de_DE", "Deutsch", "German", "Germany"),
+            language("pt_PT", "Português", "Portuguese", "Portugal"),
+            language("en_US", "English", "English", "United States"),
+            language("pt_BR", "Português", "Portuguese", "Brazil"),
+        ];
+        sort_by_locale_proximity(&mut languages, Some("pt_BR"), false);
+        let codes: Vec<&str> = languages.iter().map(|(code, _)| code.as_str()).collect();
+        assert_eq!(codes, ["pt_BR", "pt_PT", "en_US", "de_DE"]);
+        sort_by_locale_proximity(&mut languages, None, true);
+        let codes: Vec<&str> = languages.iter().map(|(code, _)| code.as_str()).collect();
+        assert_eq!(codes, ["de_DE", "en_US", "pt_BR", "pt_PT"]);
+
+        assert_eq!(world_region("pt_BR"), WorldRegion::Americas);
+        assert_eq!(world_region("de_AT"), WorldRegion::Europe);
+        assert_eq!(world_region("arb"), WorldRegion::MiddleEast);
+        assert!(matches_search(&brazil, "brasil"));
+        assert!(matches_search(&brazil, " Portuguese"));
+        assert!(matches_search(&brazil, "pt_br"));
+        assert!(!matches_search(&brazil, "deutsch"));
+    }
+}
@@ -1,17 +1,38 @@
 //! Voice metadata management for Piper TTS
 //!
 //! Handles fetching and parsing voices.json from Hugging Face's piper-voices repository.
+//!
+//! The Piper and AWS Polly voice lists are cached on disk. Lists fetched within
+//! [`VOICE_LIST_TTL`] are used as they are; older ones are shown right away and
+//! fetched again in the background, so new voices appear without a restart.
 
 pub mod aws;
 pub mod download;
+pub mod elevenlabs;
+pub mod integrity;
+pub mod labels;
+pub mod system;
 
 use std::collections::HashMap;
-use tracing::debug;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use dirs::data_dir;
+use tracing::{debug, warn};
 
 use crate::model::{LanguageInfo, VoiceInfo};
 
 const VOICES_JSON_URL: &str = "https://huggingface.co/rhasspy/piper-voices/resolve/main/voices.json";
 
+/// Cached voice lists older than this are fetched again.
+pub const VOICE_LIST_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Voice qualities in the order they are suggested: medium sounds natural at a moderate download size.
+const SUGGESTED_QUALITIES: &[&str] = &["medium", "low", "high", "x_low"];
+
+/// Voice qualities from fastest to slowest to synthesize.
+const QUALITIES_BY_SPEED: &[&str] = &["x_low", "low", "medium", "high"];
+
 /// Fetch voices.json from Hugging Face
 pub async fn fetch_voices_json() -> Result<HashMap<String, VoiceInfo>, String> {
     debug!("Fetching voices.json from Hugging Face");
@@ -34,7 +55,39 @@ pub async fn fetch_voices_json() -> Result<HashMap<String, VoiceInfo>, String> {
     
     debug!(bytes = json_text.len(), "Received voices.json");
     
-    parse_voices_json(&json_text)
+    let voices = parse_voices_json(&json_text)?;
+    // Keep a copy so installed models can be verified offline
+    if let Some(path) = cached_voices_json_path() {
+        if let Err(e) = std::fs::create_dir_all(path.parent().unwrap_or(&path)).and_then(|_| std::fs::write(&path, &json_text)) {
+            warn!(error = %e, path = %path.display(), "Failed to cache voices.json");
+        }
+    }
+    Ok(voices)
+}
+
+/// Location of the copy of voices.json kept by [`fetch_voices_json`].
+fn cached_voices_json_path() -> Option<PathBuf> {
+    Some(data_dir()?.join("insight-reader").join("voices.json"))
+}
+
+/// Load the voices.json copy from the last successful fetch, if any.
+pub fn load_cached_voices_json() -> Option<HashMap<String, VoiceInfo>> {
+    let json_text = std::fs::read_to_string(cached_voices_json_path()?).ok()?;
+    parse_voices_json(&json_text).ok()
+}
+
+/// Whether the cached voices.json was fetched within [`VOICE_LIST_TTL`].
+pub fn voices_json_fresh() -> bool {
+    cached_voices_json_path().is_some_and(|path| is_fresh(&path))
+}
+
+/// Whether the file at `path` was written within [`VOICE_LIST_TTL`].
+fn is_fresh(path: &Path) -> bool {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .is_some_and(|age| age < VOICE_LIST_TTL)
 }
 
 /// Parse voices.json into a HashMap of VoiceInfo
@@ -79,3 +132,96 @@ pub fn get_voices_for_language<'a>(
         .filter(|voice| voice.language.code == language_code)
         .collect()
 }
+
+/// Language family of a voice key ("pt" for "pt_BR-faber-medium").
+pub fn voice_family(voice_key: &str) -> &str {
+    voice_key.split(['_', '-']).next().unwrap_or(voice_key)
+}
+
+/// The fastest installed variant of a voice ("en_US-lessac-low" for
+/// "en_US-lessac-medium"), if one is faster than the voice itself.
+pub fn faster_variant(voice_key: &str, installed: &[String]) -> Option<String> {
+    let speed_rank = |quality: &str| QUALITIES_BY_SPEED.iter().position(|&q| q == quality);
+    let (name, quality) = voice_key.rsplit_once('-')?;
+    let current = speed_rank(quality)?;
+    installed
+        .iter()
+        .filter_map(|key| {
+            let (other_name, other_quality) = key.rsplit_once('-')?;
+            let rank = speed_rank(other_quality)?;
+            (other_name == name && rank < current).then_some((rank, key))
+        })
+        .min()
+        .map(|(_, key)| key.clone())
+}
+
+/// The voice to suggest for a language family ("pt"): the best-sounding
+/// quality of moderate size, from the region with the most voices.
+pub fn suggest_voice<'a>(voices: &'a HashMap<String, VoiceInfo>, family: &str) -> Option<&'a VoiceInfo> {
+    let candidates: Vec<&VoiceInfo> = voices.values().filter(|voice| voice.language.family == family).collect();
+    let region_size = |code: &str| candidates.iter().filter(|voice| voice.language.code == code).count();
+    let quality_rank = |quality: &str| {
+        SUGGESTED_QUALITIES
+            .iter()
+            .position(|&q| q == quality)
+            .unwrap_or(SUGGESTED_QUALITIES.len())
+    };
+    candidates.iter().copied().min_by(|a, b| {
+        quality_rank(&a.quality)
+            .cmp(&quality_rank(&b.quality))
+            .then(region_size(&b.language.code).cmp(&region_size(&a.language.code)))
+            .then(a.key.cmp(&b.key))
+    })
+}
+
+/// Download size of a voice's model files, in bytes.
+pub fn voice_size_bytes(voice: &VoiceInfo) -> u64 {
+    voice.files.values().map(|file| file.size_bytes).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn voice(key: &str, quality: &str, size_bytes: u64) -> String {
+        let (code, _) = key.split_once('-').unwrap();
+        format!(
+            r#""{key}": {{"key": "{key}", "name": "{key}", "quality": "{quality}", "num_speakers": 1,
+                "language": {{"code": "{code}", "family": "{}", "region": "", "name_native": "", "name_english": "", "country_english": ""}},
+                "files": {{"{key}.onnx": {{"size_bytes": {size_bytes}, "md5_digest": ""}}, "{key}.onnx.json": {{"size_bytes": 5000, "md5_digest": ""}}}}}}"#,
+            voice_family(key)
+        )
+    }
+
+    #[test]
+    fn test_suggest_voice() {
+        let entries = [
+            voice("pt_PT-tugao-medium", "medium", 63_000_000),
+            voice("pt_BR-faber-medium", "medium", 63_000_000),
+            voice("pt_BR-cadu-high", "high", 114_000_000),
+            voice("pt_BR-edresson-low", "low", 63_000_000),
+            voice("en_US-lessac-medium", "medium", 63_000_000),
+        ];
+        let voices = parse_voices_json(&format!("{{{}}}", entries.join(","))).unwrap();
+        let suggested = suggest_voice(&voices, "pt").unwrap();
+        assert_eq!(suggested.key, "pt_BR-faber-medium");
+        assert_eq!(voice_size_bytes(suggested), 63_005_000);
+        assert!(suggest_voice(&voices, "ko").is_none());
+        assert_eq!(voice_family("zh_CN-huayan-medium"), "zh");
+        let installed = ["en_US-lessac-high", "en_US-lessac-low", "en_US-amy-x_low"].map(String::from);
+        assert_eq!(faster_variant("en_US-lessac-medium", &installed).as_deref(), Some("en_US-lessac-low"));
+        assert_eq!(faster_variant("en_US-lessac-low", &installed), None);
+    }
+
+    #[test]
+    fn test_is_fresh() {
+        let path = std::env::temp_dir().join(format!("insight-reader-voice-list-{}", std::process::id()));
+        assert!(!is_fresh(&path));
+        std::fs::write(&path, "{}").unwrap();
+        assert!(is_fresh(&path));
+        let stale = std::time::SystemTime::now() - VOICE_LIST_TTL - Duration::from_secs(60);
+        std::fs::File::options().write(true).open(&path).unwrap().set_modified(stale).unwrap();
+        assert!(!is_fresh(&path));
+        let _ = std::fs::remove_file(&path);
+    }
+}
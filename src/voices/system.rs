@@ -0,0 +1,131 @@
+//! System voice lists
+//!
+//! Lists the voices installed with the operating system: on macOS those
+//! AVSpeechSynthesizer offers (Settings → Accessibility → Spoken Content →
+//! System Voice), on Windows those of Windows.Media.SpeechSynthesis
+//! (Settings → Time & language → Speech).
+
+/// A voice installed with the operating system
+#[derive(Debug, Clone, PartialEq)]
+pub struct SystemVoice {
+    /// Identifier, e.g. "com.apple.voice.compact.en-US.Samantha"
+    pub id: String,
+    pub name: String,
+    /// BCP 47 language code, e.g. "en-US"
+    pub language: String,
+}
+
+/// Sort voices speaking `user_language` first, then by language and name.
+#[cfg_attr(not(any(target_os = "macos", target_os = "windows")), allow(dead_code))]
+fn sort_voices(voices: &mut [SystemVoice], user_language: Option<&str>) {
+    let speaks_user_language = |voice: &SystemVoice| {
+        user_language.is_some_and(|language| voice.language.split('-').next() == Some(language))
+    };
+    voices.sort_by(|a, b| {
+        speaks_user_language(b)
+            .cmp(&speaks_user_language(a))
+            .then_with(|| a.language.cmp(&b.language))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+}
+
+/// The listed voices sorted for display, or an error telling where to add some.
+#[cfg_attr(not(any(target_os = "macos", target_os = "windows")), allow(dead_code))]
+fn sorted(mut voices: Vec<SystemVoice>, settings: &str) -> Result<Vec<SystemVoice>, String> {
+    if voices.is_empty() {
+        return Err(format!("No system voices installed. Add one in {settings}."));
+    }
+    sort_voices(&mut voices, crate::system::ui_language().as_deref());
+    tracing::debug!(count = voices.len(), "Listed system voices");
+    Ok(voices)
+}
+
+/// List the installed macOS voices, the user's language first.
+#[cfg(target_os = "macos")]
+pub fn list_macos_voices() -> Result<Vec<SystemVoice>, String> {
+    use objc2_avf_audio::AVSpeechSynthesisVoice;
+
+    let voices = unsafe { AVSpeechSynthesisVoice::speechVoices() }
+        .iter()
+        .map(|voice| unsafe {
+            SystemVoice {
+                id: voice.identifier().to_string(),
+                name: voice.name().to_string(),
+                language: voice.language().to_string(),
+            }
+        })
+        .collect();
+    sorted(voices, "System Settings → Accessibility → Spoken Content")
+}
+
+/// List the installed macOS voices (macOS only).
+#[cfg(not(target_os = "macos"))]
+pub fn list_macos_voices() -> Result<Vec<SystemVoice>, String> {
+    Err("macOS voices are only available on macOS".to_string())
+}
+
+/// Run `f` with COM initialized on this thread, as WinRT needs.
+#[cfg(target_os = "windows")]
+pub(crate) fn with_com<T>(f: impl FnOnce() -> T) -> T {
+    use windows::Win32::System::Com::{CoInitializeEx, CoUninitialize, COINIT_MULTITHREADED};
+
+    let hr = unsafe { CoInitializeEx(None, COINIT_MULTITHREADED) };
+    let result = f();
+    // Only a successful initialization (S_OK or S_FALSE) is balanced
+    if hr.is_ok() {
+        unsafe { CoUninitialize() };
+    }
+    result
+}
+
+/// List the installed Windows voices, the user's language first.
+#[cfg(target_os = "windows")]
+pub fn list_windows_voices() -> Result<Vec<SystemVoice>, String> {
+    use windows::Media::SpeechSynthesis::SpeechSynthesizer;
+
+    let voices = with_com(|| {
+        SpeechSynthesizer::AllVoices().map(|voices| {
+            voices
+                .into_iter()
+                .filter_map(|voice| {
+                    Some(SystemVoice {
+                        id: voice.Id().ok()?.to_string(),
+                        name: voice.DisplayName().ok()?.to_string(),
+                        language: voice.Language().ok()?.to_string(),
+                    })
+                })
+                .collect()
+        })
+    })
+    .map_err(|e| format!("Failed to list Windows voices: {e}"))?;
+    sorted(voices, "Settings → Time & language → Speech")
+}
+
+/// List the installed Windows voices (Windows only).
+#[cfg(not(target_os = "windows"))]
+pub fn list_windows_voices() -> Result<Vec<SystemVoice>, String> {
+    Err("Windows voices are only available on Windows".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sort_voices() {
+        let voice = |name: &str, language: &str| SystemVoice {
+            id: format!("com.apple.voice.compact.{language}.{name}"),
+            name: name.to_string(),
+            language: language.to_string(),
+        };
+        let mut voices = vec![
+            voice("Anna", "de-DE"),
+            voice("Samantha", "en-US"),
+            voice("Thomas", "fr-FR"),
+            voice("Amélie", "fr-CA"),
+        ];
+        sort_voices(&mut voices, Some("fr"));
+        let names: Vec<&str> = voices.iter().map(|voice| voice.name.as_str()).collect();
+        assert_eq!(names, ["Amélie", "Thomas", "Anna", "Samantha"]);
+    }
+}
@@ -0,0 +1,58 @@
+//! End-to-end tests of `insight-reader rpc`, driving the binary over stdio the
+//! way an embedding app does. Only requests that need no audio device are sent.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use serde_json::{json, Value};
+
+/// Send `requests` to a fresh `rpc` process and return what it writes back.
+fn exchange(requests: &[&str]) -> Vec<Value> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_insight-reader"))
+        .args(["rpc", "--provider", "piper"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("failed to start insight-reader rpc");
+    {
+        let mut stdin = child.stdin.take().unwrap();
+        for request in requests {
+            writeln!(stdin, "{request}").unwrap();
+        }
+        // Dropping stdin ends the session
+    }
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("every output line is JSON"))
+        .collect()
+}
+
+#[test]
+fn test_rpc_status() {
+    let responses = exchange(&[r#"{"jsonrpc": "2.0", "id": 1, "method": "status"}"#]);
+    assert_eq!(responses.len(), 1);
+    let status = &responses[0];
+    assert_eq!(status["jsonrpc"], "2.0");
+    assert_eq!(status["id"], 1);
+    assert_eq!(status["result"]["protocol"], 1);
+    assert_eq!(status["result"]["state"], "idle");
+    assert_eq!(status["result"]["provider"], "piper");
+}
+
+#[test]
+fn test_rpc_errors() {
+    let responses = exchange(&[
+        "not json",
+        r#"{"jsonrpc": "2.0", "id": 2, "method": "sing"}"#,
+        r#"{"jsonrpc": "2.0", "id": 3, "method": "speak", "params": {"text": " "}}"#,
+        r#"{"jsonrpc": "2.0", "method": "stop"}"#,
+        r#"{"jsonrpc": "2.0", "id": 4, "method": "stop"}"#,
+    ]);
+    let codes: Vec<&Value> = responses.iter().map(|response| &response["error"]["code"]).collect();
+    assert_eq!(codes, [&json!(-32700), &json!(-32601), &json!(-32602), &Value::Null]);
+    assert_eq!(responses[3], json!({"jsonrpc": "2.0", "id": 4, "result": null}));
+}